@@ -405,6 +405,77 @@ fn bench_msgpack_serialize(c: &mut Criterion) {
     });
 }
 
+/// Benchmark the structured search-response wire formats against each other.
+///
+/// Robot consumers can negotiate `--robot-format msgpack` instead of compact
+/// JSON for large result sets; this tracks serialization cost and payload
+/// size for both encodings over the same hit payload so a regression in
+/// either path (or the size advantage eroding) is visible in CI history.
+fn bench_wire_format_search_payload(c: &mut Criterion) {
+    fn synthetic_search_payload(hit_count: usize) -> serde_json::Value {
+        let hits: Vec<serde_json::Value> = (0..hit_count)
+            .map(|i| {
+                serde_json::json!({
+                    "score": 12.5 - (i as f64) * 0.001,
+                    "agent": if i % 2 == 0 { "claude-code" } else { "codex" },
+                    "workspace": format!("/home/user/projects/repo-{}", i % 7),
+                    "source_path": format!("/home/user/.sessions/conv-{i}.jsonl"),
+                    "title": format!("Session {i}: investigating retry logic"),
+                    "snippet": "Lorem ipsum dolor sit amet, consectetur adipiscing elit. \
+                                Sed do eiusmod tempor incididunt ut labore et dolore magna.",
+                    "created_at": 1_700_000_000_000_i64 + i as i64 * 60_000,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "query": "retry logic",
+            "count": hits.len(),
+            "total_matches": hits.len(),
+            "hits": hits,
+        })
+    }
+
+    let mut group = c.benchmark_group("wire_format_search_payload");
+
+    for &hit_count in &[100usize, 1_000, 5_000] {
+        let payload = synthetic_search_payload(hit_count);
+        let json_size = serde_json::to_vec(&payload).expect("encode json").len();
+        let msgpack_size = rmp_serde::to_vec(&payload).expect("encode msgpack").len();
+        println!(
+            "wire_format_search_payload/{hit_count}_hits: json={} msgpack={} ({}% of json)",
+            format_size(json_size),
+            format_size(msgpack_size),
+            msgpack_size * 100 / json_size.max(1)
+        );
+
+        group.throughput(Throughput::Bytes(json_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("json", format!("{}_hits", hit_count)),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    let encoded = serde_json::to_vec(payload).expect("encode json");
+                    black_box(encoded)
+                })
+            },
+        );
+
+        group.throughput(Throughput::Bytes(msgpack_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("msgpack", format!("{}_hits", hit_count)),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    let encoded = rmp_serde::to_vec(payload).expect("encode msgpack");
+                    black_box(encoded)
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
@@ -443,7 +514,8 @@ criterion_group!(roundtrip_benches, bench_roundtrip);
 criterion_group!(
     serialize_benches,
     bench_json_serialize,
-    bench_msgpack_serialize
+    bench_msgpack_serialize,
+    bench_wire_format_search_payload
 );
 
 criterion_main!(
@@ -0,0 +1,375 @@
+//! Stable embedding API for downstream tools.
+//!
+//! Everything else in this crate is internal plumbing for the `cass` binary
+//! and changes freely between releases. This module is the supported surface
+//! for using cass as a library — embedding search into your own TUI, building
+//! exporters, or scripting against the index:
+//!
+//! ```no_run
+//! use cass::api::Client;
+//!
+//! let client = Client::open_default()?;
+//! for hit in client.search("retry logic", 10)? {
+//!     println!("{:>6.2}  {}  {}", hit.score, hit.agent, hit.title);
+//! }
+//! # Ok::<(), cass::api::Error>(())
+//! ```
+//!
+//! Stability contract:
+//!
+//! * Types here are plain data with public fields, marked `#[non_exhaustive]`
+//!   so fields can be **added** in minor releases; existing fields keep their
+//!   names and meanings.
+//! * No `anyhow` in signatures — fallible calls return [`Error`], a proper
+//!   enum downstream code can match on.
+//! * Internal types (`SearchHit`, `FrankenStorage`, connector traits) are
+//!   deliberately not re-exported; they do not carry this guarantee.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error as ThisError;
+
+use crate::search::query::{FieldMask, SearchClient, SearchFilters};
+use crate::storage::sqlite::FrankenStorage;
+
+/// Errors surfaced by the embedding API.
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum Error {
+    /// No index exists under the data dir; run `cass index` (or
+    /// `cass index --full`) first.
+    #[error("no search index at {}; run 'cass index --full' first", path.display())]
+    IndexNotFound {
+        /// The data dir that was probed.
+        path: PathBuf,
+    },
+    /// The canonical database could not be opened or queried.
+    #[error("storage error: {0}")]
+    Storage(String),
+    /// The search engine rejected or failed the query.
+    #[error("search error: {0}")]
+    Search(String),
+    /// No indexed conversation matches the given source path.
+    #[error("no indexed conversation at {0}")]
+    ConversationNotFound(String),
+    /// A scoped access token was rejected (bad signature, expired, or
+    /// malformed).
+    #[error("access token rejected: {0}")]
+    TokenRejected(String),
+}
+
+/// Result alias for the embedding API.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// One search result.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Hit {
+    /// Conversation title (heuristically derived when the source had none).
+    pub title: String,
+    /// Match snippet with the query terms in context.
+    pub snippet: String,
+    /// Relevance score; higher is better. Only comparable within one query.
+    pub score: f32,
+    /// Agent slug (`claude_code`, `codex`, ...).
+    pub agent: String,
+    /// Workspace path, or empty when unknown.
+    pub workspace: String,
+    /// Session source file; the stable handle for [`Client::conversation`].
+    pub source_path: String,
+    /// Message timestamp (unix millis), when recorded.
+    pub created_at: Option<i64>,
+    /// 1-indexed line in the source file where the matched message starts.
+    pub line_number: Option<usize>,
+    /// Source identifier (`local`, or a remote source name).
+    pub source_id: String,
+}
+
+/// An indexed conversation's envelope.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Session {
+    /// Agent slug.
+    pub agent: String,
+    /// Conversation title, if any.
+    pub title: Option<String>,
+    /// Workspace path, if known.
+    pub workspace: Option<String>,
+    /// Session source file.
+    pub source_path: String,
+    /// Start timestamp (unix millis), when recorded.
+    pub started_at: Option<i64>,
+    /// End timestamp (unix millis), when recorded.
+    pub ended_at: Option<i64>,
+    /// Number of indexed messages.
+    pub message_count: u64,
+}
+
+/// One message within a conversation.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Message {
+    /// Stored role string (`user`, `agent`, `tool`, `system`, ...).
+    pub role: String,
+    /// Message timestamp (unix millis), when recorded.
+    pub created_at: Option<i64>,
+    /// Message content.
+    pub content: String,
+}
+
+/// Handle over an existing cass index (canonical DB plus search index).
+///
+/// Read-only from the caller's perspective: nothing here mutates indexed
+/// content. The handle is cheap to keep around and reuse across queries.
+pub struct Client {
+    storage: FrankenStorage,
+    search: Option<SearchClient>,
+    data_dir: PathBuf,
+    /// When set, every read path filters to these workspace prefixes.
+    /// `None` means unrestricted (the local single-user default).
+    scope: Option<crate::workspace_keys::TokenScope>,
+}
+
+impl Client {
+    /// Open the default cass data dir (respects `CASS_DATA_DIR` /
+    /// `XDG_DATA_HOME`, matching the binary's resolution order).
+    pub fn open_default() -> Result<Self> {
+        Self::open(&crate::default_data_dir())
+    }
+
+    /// Open an explicit data dir (the directory containing `agent_search.db`
+    /// and the search index, as produced by `cass index`).
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let db_path = data_dir.join("agent_search.db");
+        if !db_path.is_file() {
+            return Err(Error::IndexNotFound {
+                path: data_dir.to_path_buf(),
+            });
+        }
+        let storage = FrankenStorage::open(&db_path).map_err(|e| Error::Storage(e.to_string()))?;
+        let index_path = crate::search::tantivy::expected_index_dir(data_dir);
+        let search = SearchClient::open(&index_path, Some(&db_path))
+            .map_err(|e| Error::Search(e.to_string()))?;
+        Ok(Self {
+            storage,
+            search,
+            data_dir: data_dir.to_path_buf(),
+            scope: None,
+        })
+    }
+
+    /// Restrict every read path to the given workspace path prefixes.
+    ///
+    /// Matching is on whole path components (see
+    /// [`crate::workspace_keys::TokenScope::allows`]). A server embedding
+    /// this client applies the restriction per request, so one client's
+    /// scope never leaks into another's.
+    #[must_use]
+    pub fn restrict_to_workspaces(mut self, workspaces: Vec<String>) -> Self {
+        self.scope = Some(crate::workspace_keys::TokenScope {
+            workspaces,
+            expires_at_ms: i64::MAX,
+        });
+        self
+    }
+
+    /// Restrict every read path to the scope granted by a signed token
+    /// (see [`crate::workspace_keys::mint_scoped_token`]). The token is
+    /// verified against `master` and the current wall clock; a rejected
+    /// token yields [`Error::TokenRejected`] rather than an unrestricted
+    /// client.
+    pub fn restrict_to_token(mut self, master: &[u8], token: &str) -> Result<Self> {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let scope = crate::workspace_keys::verify_scoped_token(master, token, now_ms)
+            .map_err(|e| Error::TokenRejected(e.to_string()))?;
+        self.scope = Some(scope);
+        Ok(self)
+    }
+
+    /// Whether the current scope (if any) covers a workspace path. An
+    /// unknown/empty workspace is only visible to unrestricted clients.
+    fn in_scope(&self, workspace: &str) -> bool {
+        match &self.scope {
+            None => true,
+            Some(scope) => !workspace.is_empty() && scope.allows(workspace),
+        }
+    }
+
+    /// The data dir this client was opened over.
+    #[must_use]
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Run a search and return up to `limit` hits (0 means the engine's
+    /// no-limit cap). Uses the same hybrid lexical/semantic pipeline as the
+    /// `cass search` command.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<Hit>> {
+        let Some(client) = &self.search else {
+            return Err(Error::IndexNotFound {
+                path: self.data_dir.clone(),
+            });
+        };
+        let hits = client
+            .search(query, SearchFilters::default(), limit, 0, FieldMask::FULL)
+            .map_err(|e| Error::Search(e.to_string()))?;
+        Ok(hits
+            .into_iter()
+            .filter(|hit| self.in_scope(&hit.workspace))
+            .map(|hit| Hit {
+                title: hit.title,
+                snippet: hit.snippet,
+                score: hit.score,
+                agent: hit.agent,
+                workspace: hit.workspace,
+                source_path: hit.source_path,
+                created_at: hit.created_at,
+                line_number: hit.line_number,
+                source_id: hit.source_id,
+            })
+            .collect())
+    }
+
+    /// List indexed sessions, most recently active first.
+    pub fn sessions(&self, limit: usize) -> Result<Vec<Session>> {
+        use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+        let limit = i64::try_from(limit.max(1)).unwrap_or(i64::MAX);
+        self.storage
+            .raw()
+            .query_map_collect(
+                "SELECT COALESCE(a.slug, 'unknown'), c.title, w.path, c.source_path,
+                        c.started_at, c.ended_at,
+                        (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
+                 FROM conversations c
+                 LEFT JOIN agents a ON c.agent_id = a.id
+                 LEFT JOIN workspaces w ON c.workspace_id = w.id
+                 ORDER BY COALESCE(c.ended_at, c.started_at) DESC
+                 LIMIT ?",
+                &[ParamValue::from(limit)],
+                |r: &frankensqlite::Row| {
+                    Ok(Session {
+                        agent: r.get_typed(0)?,
+                        title: r.get_typed(1)?,
+                        workspace: r.get_typed(2)?,
+                        source_path: r.get_typed(3)?,
+                        started_at: r.get_typed(4)?,
+                        ended_at: r.get_typed(5)?,
+                        message_count: r.get_typed::<i64>(6)?.max(0) as u64,
+                    })
+                },
+            )
+            .map_err(|e| Error::Storage(e.to_string()))
+            .map(|rows: Vec<Session>| {
+                rows.into_iter()
+                    .filter(|s| self.in_scope(s.workspace.as_deref().unwrap_or("")))
+                    .collect()
+            })
+    }
+
+    /// Load one conversation's envelope and messages by source path.
+    pub fn conversation(&self, source_path: &str) -> Result<(Session, Vec<Message>)> {
+        use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
+
+        let conn = self.storage.raw();
+        let row: Option<(i64, Session)> = conn
+            .query_row_map(
+                "SELECT c.id, COALESCE(a.slug, 'unknown'), c.title, w.path, c.source_path,
+                        c.started_at, c.ended_at
+                 FROM conversations c
+                 LEFT JOIN agents a ON c.agent_id = a.id
+                 LEFT JOIN workspaces w ON c.workspace_id = w.id
+                 WHERE c.source_path = ?
+                 ORDER BY c.started_at DESC LIMIT 1",
+                &[ParamValue::from(source_path)],
+                |r: &frankensqlite::Row| {
+                    Ok((
+                        r.get_typed(0)?,
+                        Session {
+                            agent: r.get_typed(1)?,
+                            title: r.get_typed(2)?,
+                            workspace: r.get_typed(3)?,
+                            source_path: r.get_typed(4)?,
+                            started_at: r.get_typed(5)?,
+                            ended_at: r.get_typed(6)?,
+                            message_count: 0,
+                        },
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        let Some((conv_id, mut session)) = row else {
+            return Err(Error::ConversationNotFound(source_path.to_string()));
+        };
+        // Out-of-scope reads report "not found" rather than "forbidden" so a
+        // scoped token cannot be used to probe which paths exist.
+        if !self.in_scope(session.workspace.as_deref().unwrap_or("")) {
+            return Err(Error::ConversationNotFound(source_path.to_string()));
+        }
+
+        let messages: Vec<Message> = conn
+            .query_map_collect(
+                "SELECT role, created_at, content FROM messages
+                 WHERE conversation_id = ? ORDER BY idx",
+                &[ParamValue::from(conv_id)],
+                |r: &frankensqlite::Row| {
+                    Ok(Message {
+                        role: r.get_typed(0)?,
+                        created_at: r.get_typed(1)?,
+                        content: r.get_typed(2)?,
+                    })
+                },
+            )
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        session.message_count = messages.len() as u64;
+        Ok((session, messages))
+    }
+
+    /// Distinct agent slugs present in the index.
+    pub fn agents(&self) -> Result<Vec<String>> {
+        use frankensqlite::compat::{ConnectionExt, RowExt};
+
+        self.storage
+            .raw()
+            .query_map_collect(
+                "SELECT DISTINCT COALESCE(a.slug, 'unknown')
+                 FROM conversations c
+                 LEFT JOIN agents a ON c.agent_id = a.id
+                 ORDER BY 1",
+                &[],
+                |r: &frankensqlite::Row| r.get_typed(0),
+            )
+            .map_err(|e| Error::Storage(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_data_dir_reports_index_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        match Client::open(dir.path()) {
+            Err(Error::IndexNotFound { path }) => assert_eq!(path, dir.path()),
+            other => panic!("expected IndexNotFound, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn error_messages_are_actionable() {
+        let err = Error::IndexNotFound {
+            path: PathBuf::from("/data/cass"),
+        };
+        assert!(err.to_string().contains("cass index --full"));
+        assert!(
+            Error::ConversationNotFound("/tmp/x.jsonl".to_string())
+                .to_string()
+                .contains("/tmp/x.jsonl")
+        );
+    }
+}
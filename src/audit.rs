@@ -0,0 +1,287 @@
+//! Audit trail for machine-driven queries.
+//!
+//! When another tool queries cass through the robot interface (structured
+//! output requested via `--json`/`--robot`/`CASS_ROBOT_FORMAT`), the query is
+//! recorded in an `audit_log` table so an operator can later answer "what has
+//! been searching my session history, and for what". Uses a separate `SQLite`
+//! database file (`audit.db`, like `bookmarks.db`) so the hot search path
+//! never takes a write lock on the main index.
+//!
+//! Recording is best-effort and never fails the query being audited.
+//! `CASS_AUDIT_LOG=0` disables it entirely; `CASS_AUDIT_LOG=1` also records
+//! interactive (human-mode) queries. Retention defaults to 30 days and is
+//! applied opportunistically on each write; `CASS_AUDIT_RETENTION_DAYS=0`
+//! keeps entries forever.
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::{ConnectionExt, RowExt};
+use frankensqlite::params;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Default retention window applied on write, in days.
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// One recorded query.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    /// Row id.
+    pub id: i64,
+    /// When the query ran (unix millis).
+    pub ts: i64,
+    /// Caller identity: `CASS_CALLER` when the calling tool sets it,
+    /// otherwise the invoking OS user.
+    pub actor: String,
+    /// Subcommand that served the query (`search`, `get-context`, ...).
+    pub command: String,
+    /// The query text, when the command has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Number of results returned, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_count: Option<i64>,
+}
+
+/// Storage backend for the audit trail.
+pub struct AuditLog {
+    conn: Connection,
+}
+
+impl AuditLog {
+    /// Open or create the audit log at the given path.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating audit directory {}", parent.display()))?;
+        }
+
+        let conn = Connection::open(path.to_string_lossy().as_ref())
+            .with_context(|| format!("opening audit db at {}", path.display()))?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Record one query.
+    pub fn record(
+        &self,
+        actor: &str,
+        command: &str,
+        query: Option<&str>,
+        result_count: Option<i64>,
+    ) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO audit_log (ts, actor, command, query, result_count)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                chrono::Utc::now().timestamp_millis(),
+                actor,
+                command,
+                query,
+                result_count
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent entries, newest first, optionally filtered by command.
+    pub fn recent(&self, limit: usize, command: Option<&str>) -> Result<Vec<AuditEntry>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let map_row = |row: &frankensqlite::Row| {
+            Ok(AuditEntry {
+                id: row.get_typed(0)?,
+                ts: row.get_typed(1)?,
+                actor: row.get_typed(2)?,
+                command: row.get_typed(3)?,
+                query: row.get_typed(4)?,
+                result_count: row.get_typed(5)?,
+            })
+        };
+        let entries = if let Some(command) = command {
+            self.conn.query_map_collect(
+                "SELECT id, ts, actor, command, query, result_count
+                 FROM audit_log WHERE command = ?1
+                 ORDER BY ts DESC, id DESC LIMIT ?2",
+                params![command, limit],
+                map_row,
+            )?
+        } else {
+            self.conn.query_map_collect(
+                "SELECT id, ts, actor, command, query, result_count
+                 FROM audit_log
+                 ORDER BY ts DESC, id DESC LIMIT ?1",
+                params![limit],
+                map_row,
+            )?
+        };
+        Ok(entries)
+    }
+
+    /// Delete entries older than `retention_days`. `0` means keep forever.
+    /// Returns the number of rows removed.
+    pub fn prune(&self, retention_days: i64) -> Result<usize> {
+        if retention_days <= 0 {
+            return Ok(0);
+        }
+        let cutoff = chrono::Utc::now().timestamp_millis() - retention_days * 86_400_000;
+        let removed = self
+            .conn
+            .execute_compat("DELETE FROM audit_log WHERE ts < ?1", params![cutoff])?;
+        Ok(removed)
+    }
+}
+
+/// Default audit database path (`data_dir/audit.db`).
+#[must_use]
+pub fn audit_db_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("audit.db")
+}
+
+/// Caller identity for audit entries: the calling tool's self-declared
+/// `CASS_CALLER`, falling back to the invoking OS user.
+#[must_use]
+pub fn caller_identity() -> String {
+    dotenvy::var("CASS_CALLER")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| dotenvy::var("USER").ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Configured retention window in days (`CASS_AUDIT_RETENTION_DAYS`, default
+/// [`DEFAULT_RETENTION_DAYS`]; `0` keeps entries forever).
+#[must_use]
+pub fn configured_retention_days() -> i64 {
+    dotenvy::var("CASS_AUDIT_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS)
+}
+
+/// Whether this invocation should be recorded. Robot-mode queries are audited
+/// by default; `CASS_AUDIT_LOG=0` opts out, `CASS_AUDIT_LOG=1` also captures
+/// interactive queries.
+#[must_use]
+pub fn should_record(robot_mode: bool) -> bool {
+    match dotenvy::var("CASS_AUDIT_LOG").ok().as_deref() {
+        Some("0") | Some("false") => false,
+        Some("1") | Some("true") => true,
+        _ => robot_mode,
+    }
+}
+
+/// Best-effort hook for query paths: append an entry and apply retention.
+/// Failures are logged at debug level and never surface to the caller.
+pub fn record_query(
+    data_dir: &Path,
+    command: &str,
+    query: Option<&str>,
+    result_count: Option<i64>,
+    robot_mode: bool,
+) {
+    if !should_record(robot_mode) {
+        return;
+    }
+    let result = AuditLog::open(&audit_db_path(data_dir)).and_then(|log| {
+        log.record(&caller_identity(), command, query, result_count)?;
+        log.prune(configured_retention_days())?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        tracing::debug!("failed to record audit entry: {e}");
+    }
+}
+
+/// SQL schema for the audit database.
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY,
+    ts INTEGER NOT NULL,
+    actor TEXT NOT NULL,
+    command TEXT NOT NULL,
+    query TEXT,
+    result_count INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_ts ON audit_log(ts DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_command ON audit_log(command);
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn open_temp() -> (tempfile::TempDir, AuditLog) {
+        let dir = tempdir().unwrap();
+        let log = AuditLog::open(&audit_db_path(dir.path())).unwrap();
+        (dir, log)
+    }
+
+    #[test]
+    fn record_and_list_round_trip() {
+        let (_dir, log) = open_temp();
+        log.record("agent-a", "search", Some("rust panic"), Some(7))
+            .unwrap();
+        log.record("agent-b", "get-context", None, Some(5)).unwrap();
+
+        let entries = log.recent(10, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        // Newest first.
+        assert_eq!(entries[0].command, "get-context");
+        assert_eq!(entries[1].actor, "agent-a");
+        assert_eq!(entries[1].query.as_deref(), Some("rust panic"));
+        assert_eq!(entries[1].result_count, Some(7));
+    }
+
+    #[test]
+    fn recent_filters_by_command() {
+        let (_dir, log) = open_temp();
+        log.record("a", "search", Some("one"), Some(1)).unwrap();
+        log.record("a", "get-context", None, Some(3)).unwrap();
+        log.record("a", "search", Some("two"), Some(2)).unwrap();
+
+        let searches = log.recent(10, Some("search")).unwrap();
+        assert_eq!(searches.len(), 2);
+        assert!(searches.iter().all(|e| e.command == "search"));
+    }
+
+    #[test]
+    fn prune_removes_only_expired_entries() {
+        let (_dir, log) = open_temp();
+        let old_ts = chrono::Utc::now().timestamp_millis() - 90 * 86_400_000;
+        log.conn
+            .execute_compat(
+                "INSERT INTO audit_log (ts, actor, command, query, result_count)
+                 VALUES (?1, 'a', 'search', 'stale', 0)",
+                params![old_ts],
+            )
+            .unwrap();
+        log.record("a", "search", Some("fresh"), Some(1)).unwrap();
+
+        assert_eq!(log.prune(30).unwrap(), 1);
+        let entries = log.recent(10, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].query.as_deref(), Some("fresh"));
+
+        // Retention 0 keeps everything.
+        assert_eq!(log.prune(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn should_record_defaults_to_robot_mode_only() {
+        // Env-dependent branches are covered implicitly; the default path
+        // must gate on robot mode when CASS_AUDIT_LOG is unset.
+        if dotenvy::var("CASS_AUDIT_LOG").is_err() {
+            assert!(should_record(true));
+            assert!(!should_record(false));
+        }
+    }
+}
@@ -36,6 +36,12 @@ pub struct Bookmark {
     pub updated_at: i64,
     /// Original search snippet (for context)
     pub snippet: String,
+    /// Device that last wrote this annotation (see
+    /// `user_meta::local_device_id`). Empty on rows written before device
+    /// tracking existed; used as the deterministic tie-break when two
+    /// machines edited the same bookmark at the same millisecond.
+    #[serde(default)]
+    pub device_id: String,
 }
 
 impl Bookmark {
@@ -60,6 +66,7 @@ impl Bookmark {
             created_at: now,
             updated_at: now,
             snippet: String::new(),
+            device_id: String::new(),
         }
     }
 
@@ -129,6 +136,16 @@ impl BookmarkStore {
         // Create schema if needed
         conn.execute_batch(SCHEMA)?;
 
+        // Pre-device-tracking databases lack the device_id column; add it in
+        // place (single additive column, no data rewrite).
+        if conn
+            .query("SELECT device_id FROM bookmarks LIMIT 1")
+            .is_err()
+        {
+            conn.execute_batch("ALTER TABLE bookmarks ADD COLUMN device_id TEXT DEFAULT '';")
+                .context("adding bookmarks.device_id column")?;
+        }
+
         Ok(Self { conn })
     }
 
@@ -143,8 +160,8 @@ impl BookmarkStore {
         let line_number = line_number_to_db(bookmark.line_number)?;
 
         self.conn.execute_compat(
-            "INSERT INTO bookmarks (title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO bookmarks (title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet, device_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 bookmark.title.as_str(),
                 bookmark.source_path.as_str(),
@@ -156,6 +173,7 @@ impl BookmarkStore {
                 bookmark.created_at,
                 bookmark.updated_at,
                 bookmark.snippet.as_str(),
+                bookmark.device_id.as_str(),
             ],
         )?;
 
@@ -193,7 +211,7 @@ impl BookmarkStore {
     pub fn get(&self, id: i64) -> Result<Option<Bookmark>> {
         self.conn
             .query_row_map(
-                "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet
+                "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet, device_id
                  FROM bookmarks WHERE id = ?1",
                 params![id],
                 row_to_bookmark,
@@ -204,7 +222,7 @@ impl BookmarkStore {
 
     /// List all bookmarks, optionally filtered by tag
     pub fn list(&self, tag_filter: Option<&str>) -> Result<Vec<Bookmark>> {
-        let sql = "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet
+        let sql = "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet, device_id
                    FROM bookmarks ORDER BY created_at DESC";
 
         let all_bookmarks: Vec<Bookmark> =
@@ -231,7 +249,7 @@ impl BookmarkStore {
         let pattern = format!("%{escaped}%");
 
         let results = self.conn.query_map_collect(
-            "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet
+            "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet, device_id
              FROM bookmarks
              WHERE LOWER(title) LIKE ?1 ESCAPE '\\' OR LOWER(note) LIKE ?1 ESCAPE '\\' OR LOWER(snippet) LIKE ?1 ESCAPE '\\'
              ORDER BY created_at DESC",
@@ -331,6 +349,133 @@ impl BookmarkStore {
 
         Ok(imported)
     }
+
+    /// The bookmark at `source_path` + `line_number`, if any — the stable
+    /// identity annotations merge on (row ids differ between machines).
+    fn get_by_location(
+        &self,
+        source_path: &str,
+        line_number: Option<i64>,
+    ) -> Result<Option<Bookmark>> {
+        self.conn
+            .query_row_map(
+                "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet, device_id
+                 FROM bookmarks WHERE source_path = ?1 AND line_number IS ?2",
+                params![source_path, line_number],
+                row_to_bookmark,
+            )
+            .optional()
+            .context("querying bookmark by location")
+    }
+
+    /// Merge annotations from another machine, conflict-free where possible
+    /// and deterministic everywhere (see `cass meta import`).
+    ///
+    /// Bookmarks are matched by `source_path` + `line_number`. Unmatched
+    /// entries are inserted with their original clock and device id. For a
+    /// matched pair, tags are a set union (commutative, so both directions
+    /// of a two-machine sync converge), while the note — a single register —
+    /// goes to the last writer, ordered by `(updated_at, device_id)` so two
+    /// writes in the same millisecond still resolve the same way on both
+    /// machines. A genuine note conflict (both sides non-empty and
+    /// different) is resolved, not dropped: the losing side is reported in
+    /// [`BookmarkMergeOutcome::conflicts`].
+    pub fn merge_annotations(&self, incoming: &[Bookmark]) -> Result<BookmarkMergeOutcome> {
+        let mut outcome = BookmarkMergeOutcome::default();
+        for inc in incoming {
+            let line_number = line_number_to_db(inc.line_number)?;
+            let Some(local) = self.get_by_location(&inc.source_path, line_number)? else {
+                let mut fresh = inc.clone();
+                fresh.id = 0;
+                self.add(&fresh)?;
+                outcome.imported += 1;
+                continue;
+            };
+
+            let merged_tags = union_tags(&local, inc);
+            let incoming_wins = (inc.updated_at, inc.device_id.as_str())
+                > (local.updated_at, local.device_id.as_str());
+            let local_note = local.note.trim();
+            let incoming_note = inc.note.trim();
+            let merged_note = if incoming_note.is_empty() || local_note == incoming_note {
+                local.note.clone()
+            } else if local_note.is_empty() {
+                inc.note.clone()
+            } else {
+                outcome.conflicts.push(BookmarkConflict {
+                    source_path: local.source_path.clone(),
+                    line_number: local.line_number,
+                    kept: if incoming_wins { "imported" } else { "local" }.to_string(),
+                    local_device: local.device_id.clone(),
+                    imported_device: inc.device_id.clone(),
+                });
+                if incoming_wins {
+                    inc.note.clone()
+                } else {
+                    local.note.clone()
+                }
+            };
+
+            if merged_note == local.note && merged_tags == local.tags {
+                continue;
+            }
+            let (updated_at, device_id) = if incoming_wins {
+                (inc.updated_at.max(local.updated_at), inc.device_id.as_str())
+            } else {
+                (local.updated_at, local.device_id.as_str())
+            };
+            self.conn.execute_compat(
+                "UPDATE bookmarks SET note = ?1, tags = ?2, updated_at = ?3, device_id = ?4
+                 WHERE id = ?5",
+                params![
+                    merged_note.as_str(),
+                    merged_tags.as_str(),
+                    updated_at,
+                    device_id,
+                    local.id
+                ],
+            )?;
+            outcome.merged += 1;
+        }
+        Ok(outcome)
+    }
+}
+
+/// Deterministic tag union of two bookmarks: case-insensitive dedupe,
+/// case-insensitive sort, so merging A into B and B into A produce the same
+/// string.
+fn union_tags(a: &Bookmark, b: &Bookmark) -> String {
+    let mut tags: Vec<&str> = a.tag_list();
+    for tag in b.tag_list() {
+        if !tags.iter().any(|have| have.eq_ignore_ascii_case(tag)) {
+            tags.push(tag);
+        }
+    }
+    tags.sort_by_key(|tag| tag.to_ascii_lowercase());
+    tags.join(",")
+}
+
+/// One bookmark where both machines wrote a different note. Reported by
+/// [`BookmarkStore::merge_annotations`] instead of silently dropping a side.
+#[derive(Debug, Clone, Serialize)]
+pub struct BookmarkConflict {
+    pub source_path: String,
+    pub line_number: Option<usize>,
+    /// Which side's note won: `"local"` or `"imported"`.
+    pub kept: String,
+    pub local_device: String,
+    pub imported_device: String,
+}
+
+/// Counts and conflicts from one [`BookmarkStore::merge_annotations`] call.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BookmarkMergeOutcome {
+    /// Bookmarks that did not exist locally and were inserted.
+    pub imported: usize,
+    /// Existing bookmarks that gained tags or a newer note.
+    pub merged: usize,
+    /// Note conflicts that were resolved by last-writer-wins.
+    pub conflicts: Vec<BookmarkConflict>,
 }
 
 /// Convert a database row to a Bookmark
@@ -347,6 +492,7 @@ fn row_to_bookmark(row: &frankensqlite::Row) -> Result<Bookmark, frankensqlite::
         created_at: row.get_typed(8)?,
         updated_at: row.get_typed(9)?,
         snippet: row.get_typed(10)?,
+        device_id: row.get_typed::<Option<String>>(11)?.unwrap_or_default(),
     })
 }
 
@@ -368,7 +514,8 @@ CREATE TABLE IF NOT EXISTS bookmarks (
     tags TEXT DEFAULT '',
     created_at INTEGER NOT NULL,
     updated_at INTEGER NOT NULL,
-    snippet TEXT DEFAULT ''
+    snippet TEXT DEFAULT '',
+    device_id TEXT DEFAULT ''
 );
 
 CREATE INDEX IF NOT EXISTS idx_bookmarks_source ON bookmarks(source_path, line_number);
@@ -647,4 +794,63 @@ mod tests {
         assert!(store.is_bookmarked("/same.rs", None).unwrap());
         assert!(store.is_bookmarked("/same.rs", Some(10)).unwrap());
     }
+
+    #[test]
+    fn test_merge_annotations_unions_tags_and_resolves_note_conflicts() {
+        let (store, _dir) = test_store();
+        let mut local = Bookmark::new("A", "/a.rs", "agent", "/w")
+            .with_note("local note")
+            .with_tags("rust, Search");
+        local.updated_at = 1_000;
+        local.device_id = "aaaa".into();
+        store.add(&local).unwrap();
+
+        let mut same_row = local.clone();
+        same_row.note = "remote note".into();
+        same_row.tags = "search,sync".into();
+        same_row.updated_at = 2_000;
+        same_row.device_id = "bbbb".into();
+        let mut fresh = Bookmark::new("B", "/b.rs", "agent", "/w").with_tags("new");
+        fresh.device_id = "bbbb".into();
+
+        let outcome = store.merge_annotations(&[same_row.clone(), fresh]).unwrap();
+        assert_eq!(outcome.imported, 1);
+        assert_eq!(outcome.merged, 1);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].kept, "imported");
+        assert_eq!(outcome.conflicts[0].local_device, "aaaa");
+
+        let merged = store.get_by_location("/a.rs", None).unwrap().unwrap();
+        assert_eq!(merged.note, "remote note", "newer writer's note wins");
+        assert_eq!(merged.tags, "rust,Search,sync", "tags are a set union");
+        assert_eq!(merged.device_id, "bbbb");
+        assert_eq!(store.count().unwrap(), 2);
+
+        // Re-merging the same snapshot is a no-op: the merge converged.
+        same_row.tags = "sync,SEARCH".into();
+        let outcome = store.merge_annotations(&[same_row]).unwrap();
+        assert_eq!(outcome.imported, 0);
+        assert_eq!(outcome.merged, 0);
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_annotations_ties_break_on_device_id() {
+        let (store, _dir) = test_store();
+        let mut local = Bookmark::new("A", "/a.rs", "agent", "/w").with_note("from aaaa");
+        local.updated_at = 5_000;
+        local.device_id = "aaaa".into();
+        store.add(&local).unwrap();
+
+        // Same millisecond on both machines: the higher device id wins, so
+        // both sides of a two-machine sync settle on the same note.
+        let mut incoming = local.clone();
+        incoming.note = "from bbbb".into();
+        incoming.device_id = "bbbb".into();
+        let outcome = store.merge_annotations(&[incoming]).unwrap();
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].kept, "imported");
+        let merged = store.get_by_location("/a.rs", None).unwrap().unwrap();
+        assert_eq!(merged.note, "from bbbb");
+    }
 }
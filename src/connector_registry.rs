@@ -0,0 +1,335 @@
+//! Lazy connector registry with compiled-in feature gates and a TTL'd
+//! detection cache.
+//!
+//! Cold start used to pay for every connector even when only one or two
+//! agents exist on the machine. Three pieces keep that cost down:
+//!
+//! 1. **Feature gates** — each connector slug has a matching cargo feature,
+//!    with the default `all-connectors` umbrella enabling everything. A
+//!    minimal build (`--no-default-features --features qr,codex,claude`)
+//!    registers only the named connectors. The gates act at registration
+//!    time: `franken_agent_detection` still compiles, but unregistered
+//!    connectors are never constructed, detected, or scanned.
+//! 2. **Lazy construction** — [`ConnectorRegistry`] builds each connector on
+//!    first use and keeps the instance for the rest of the process.
+//! 3. **Detection caching** — [`cached_connector_detection`] memoizes
+//!    `Connector::detect()` results on disk with a TTL so repeated runs skip
+//!    the filesystem probing entirely. One file per connector under
+//!    `<data_dir>/detection_cache/` keeps parallel producer threads from
+//!    clobbering each other's entries.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::connectors::{Connector, DetectionResult};
+
+/// Factory signature shared with the indexer's connector plumbing.
+pub type ConnectorFactory = fn() -> Box<dyn Connector + Send>;
+
+/// Default lifetime of a cached detection result. Detection probes well-known
+/// home-directory locations, so results only change when an agent is
+/// installed or removed; fifteen minutes keeps repeated `cass` invocations
+/// cheap without hiding a fresh install for long.
+pub const DEFAULT_DETECTION_TTL_SECS: i64 = 900;
+
+/// Directory under the data dir holding per-connector detection cache files.
+pub const DETECTION_CACHE_DIR: &str = "detection_cache";
+
+/// Whether `slug`'s connector is compiled into this binary.
+///
+/// Unknown slugs stay enabled so connectors added in
+/// `franken_agent_detection` keep working before a matching feature exists
+/// here.
+#[must_use]
+pub fn connector_compiled_in(slug: &str) -> bool {
+    if cfg!(feature = "all-connectors") {
+        return true;
+    }
+    match slug {
+        "aider" => cfg!(feature = "aider"),
+        "amp" => cfg!(feature = "amp"),
+        "antigravity" => cfg!(feature = "antigravity"),
+        "chatgpt" => cfg!(feature = "chatgpt"),
+        "claude" => cfg!(feature = "claude"),
+        "clawdbot" => cfg!(feature = "clawdbot"),
+        "cline" => cfg!(feature = "cline"),
+        "codex" => cfg!(feature = "codex"),
+        "copilot" => cfg!(feature = "copilot"),
+        "copilot_cli" => cfg!(feature = "copilot_cli"),
+        "crush" => cfg!(feature = "crush"),
+        "cursor" => cfg!(feature = "cursor"),
+        "factory" => cfg!(feature = "factory"),
+        "gemini" => cfg!(feature = "gemini"),
+        "hermes" => cfg!(feature = "hermes"),
+        "kimi" => cfg!(feature = "kimi"),
+        "openclaw" => cfg!(feature = "openclaw"),
+        "opencode" => cfg!(feature = "opencode"),
+        "pi_agent" => cfg!(feature = "pi_agent"),
+        "qwen" => cfg!(feature = "qwen"),
+        "vibe" => cfg!(feature = "vibe"),
+        _ => true,
+    }
+}
+
+/// All connector factories compiled into this binary, in registration order.
+#[must_use]
+pub fn compiled_connector_factories() -> Vec<(&'static str, ConnectorFactory)> {
+    crate::connectors::get_connector_factories()
+        .into_iter()
+        .filter(|(slug, _)| connector_compiled_in(slug))
+        .collect()
+}
+
+/// Registry that constructs connectors on first use and reuses them after.
+pub struct ConnectorRegistry {
+    factories: Vec<(&'static str, ConnectorFactory)>,
+    built: BTreeMap<&'static str, Box<dyn Connector + Send>>,
+}
+
+impl ConnectorRegistry {
+    /// Registry over every connector compiled into this binary.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_factories(compiled_connector_factories())
+    }
+
+    /// Registry over an explicit factory list (tests, filtered subsets).
+    #[must_use]
+    pub fn with_factories(factories: Vec<(&'static str, ConnectorFactory)>) -> Self {
+        Self {
+            factories,
+            built: BTreeMap::new(),
+        }
+    }
+
+    /// Registered slugs, in registration order. Does not construct anything.
+    #[must_use]
+    pub fn slugs(&self) -> Vec<&'static str> {
+        self.factories.iter().map(|(slug, _)| *slug).collect()
+    }
+
+    /// Number of connectors constructed so far.
+    #[must_use]
+    pub fn built_count(&self) -> usize {
+        self.built.len()
+    }
+
+    /// Get the connector for `slug`, constructing it on first use.
+    pub fn get(&mut self, slug: &str) -> Option<&dyn Connector> {
+        let (name, factory) = self
+            .factories
+            .iter()
+            .find(|(name, _)| *name == slug)
+            .copied()?;
+        Some(self.built.entry(name).or_insert_with(factory).as_ref())
+    }
+}
+
+impl Default for ConnectorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One persisted detection result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDetection {
+    detected: bool,
+    evidence: Vec<String>,
+    root_paths: Vec<PathBuf>,
+    /// When the probe actually ran (unix millis).
+    checked_at_ms: i64,
+}
+
+/// Effective detection cache TTL in seconds. `CASS_DETECTION_CACHE_TTL_SECS`
+/// overrides the default; `0` (or negative) disables caching.
+#[must_use]
+pub fn detection_cache_ttl_secs() -> i64 {
+    dotenvy::var("CASS_DETECTION_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_DETECTION_TTL_SECS)
+}
+
+fn detection_cache_file(data_dir: &Path, slug: &str) -> PathBuf {
+    data_dir
+        .join(DETECTION_CACHE_DIR)
+        .join(format!("{slug}.json"))
+}
+
+fn load_cached_detection(path: &Path, now_ms: i64, ttl_secs: i64) -> Option<DetectionResult> {
+    let raw = fs::read_to_string(path).ok()?;
+    let cached: CachedDetection = serde_json::from_str(&raw).ok()?;
+    let age_ms = now_ms.saturating_sub(cached.checked_at_ms);
+    if age_ms < 0 || age_ms > ttl_secs.saturating_mul(1000) {
+        return None;
+    }
+    // Root paths can disappear between runs (agent uninstalled, dir pruned);
+    // a stale positive would make the scan spin on missing directories.
+    if cached.detected
+        && !cached.root_paths.iter().any(|p| p.exists())
+        && !cached.root_paths.is_empty()
+    {
+        return None;
+    }
+    Some(DetectionResult {
+        detected: cached.detected,
+        evidence: cached.evidence,
+        root_paths: cached.root_paths,
+    })
+}
+
+fn store_cached_detection(path: &Path, result: &DetectionResult, now_ms: i64) {
+    let cached = CachedDetection {
+        detected: result.detected,
+        evidence: result.evidence.clone(),
+        root_paths: result.root_paths.clone(),
+        checked_at_ms: now_ms,
+    };
+    let Ok(json) = serde_json::to_string_pretty(&cached) else {
+        return;
+    };
+    if let Some(parent) = path.parent()
+        && fs::create_dir_all(parent).is_err()
+    {
+        return;
+    }
+    // Best-effort: a failed write just means detection runs again next time.
+    let _ = fs::write(path, json);
+}
+
+/// Run `connector.detect()` through the on-disk cache.
+///
+/// Cache hits within the TTL skip the probe entirely. Misses (and disabled
+/// caching via `CASS_DETECTION_CACHE_TTL_SECS=0`) fall through to the real
+/// probe, and fresh results are persisted best-effort.
+pub fn cached_connector_detection(
+    data_dir: &Path,
+    slug: &str,
+    connector: &dyn Connector,
+) -> DetectionResult {
+    let ttl_secs = detection_cache_ttl_secs();
+    if ttl_secs <= 0 {
+        return connector.detect();
+    }
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let path = detection_cache_file(data_dir, slug);
+    if let Some(hit) = load_cached_detection(&path, now_ms, ttl_secs) {
+        tracing::debug!(slug, "detection cache hit");
+        return hit;
+    }
+    let result = connector.detect();
+    store_cached_detection(&path, &result, now_ms);
+    result
+}
+
+/// Run `connector.detect()` unconditionally and overwrite the cached entry.
+///
+/// Used when the caller explicitly asked for fresh evidence (`cass agents
+/// --refresh`) and a TTL'd hit would be misleading.
+pub fn refresh_connector_detection(
+    data_dir: &Path,
+    slug: &str,
+    connector: &dyn Connector,
+) -> DetectionResult {
+    let result = connector.detect();
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    store_cached_detection(&detection_cache_file(data_dir, slug), &result, now_ms);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PROBE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingConnector;
+
+    impl Connector for CountingConnector {
+        fn detect(&self) -> DetectionResult {
+            PROBE_COUNT.fetch_add(1, Ordering::SeqCst);
+            DetectionResult {
+                detected: true,
+                evidence: vec!["fixture".to_string()],
+                root_paths: Vec::new(),
+            }
+        }
+
+        fn scan(
+            &self,
+            _ctx: &crate::connectors::ScanContext,
+        ) -> anyhow::Result<Vec<crate::connectors::NormalizedConversation>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn registry_builds_lazily_and_reuses_instances() {
+        let mut registry = ConnectorRegistry::new();
+        let slugs = registry.slugs();
+        assert!(!slugs.is_empty());
+        assert_eq!(registry.built_count(), 0);
+
+        let first = slugs[0];
+        assert!(registry.get(first).is_some());
+        assert_eq!(registry.built_count(), 1);
+        assert!(registry.get(first).is_some());
+        assert_eq!(registry.built_count(), 1);
+        assert!(registry.get("no-such-connector").is_none());
+    }
+
+    #[test]
+    fn unknown_slugs_stay_compiled_in() {
+        assert!(connector_compiled_in("some-future-connector"));
+    }
+
+    #[test]
+    fn detection_cache_round_trips_and_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = detection_cache_file(dir.path(), "codex");
+        let result = DetectionResult {
+            detected: true,
+            evidence: vec!["~/.codex".to_string()],
+            root_paths: vec![dir.path().to_path_buf()],
+        };
+
+        store_cached_detection(&path, &result, 1_000_000);
+        let hit = load_cached_detection(&path, 1_000_500, 900).expect("fresh entry");
+        assert!(hit.detected);
+        assert_eq!(hit.evidence, vec!["~/.codex".to_string()]);
+
+        // Past the TTL the entry is ignored.
+        assert!(load_cached_detection(&path, 1_000_000 + 901_000, 900).is_none());
+        // Clock rollback also invalidates.
+        assert!(load_cached_detection(&path, 999_000, 900).is_none());
+    }
+
+    #[test]
+    fn stale_positive_with_missing_roots_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = detection_cache_file(dir.path(), "claude");
+        let result = DetectionResult {
+            detected: true,
+            evidence: Vec::new(),
+            root_paths: vec![dir.path().join("gone")],
+        };
+        store_cached_detection(&path, &result, 1_000_000);
+        assert!(load_cached_detection(&path, 1_000_500, 900).is_none());
+    }
+
+    #[test]
+    fn cached_detection_skips_repeat_probes() {
+        let dir = tempfile::tempdir().unwrap();
+        let connector = CountingConnector;
+        let before = PROBE_COUNT.load(Ordering::SeqCst);
+        let first = cached_connector_detection(dir.path(), "counting", &connector);
+        assert!(first.detected);
+        let second = cached_connector_detection(dir.path(), "counting", &connector);
+        assert!(second.detected);
+        assert_eq!(PROBE_COUNT.load(Ordering::SeqCst), before + 1);
+    }
+}
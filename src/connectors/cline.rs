@@ -1,3 +1,429 @@
-//! Implementation lives in `franken_agent_detection::connectors::cline`.
+//! Connector for Cline task histories.
+//!
+//! The base parser lives in `franken_agent_detection::connectors::cline`;
+//! this wrapper merges the rest of a task directory on top of it:
+//!
+//! - `ui_messages.json` command runs, browser actions, and checkpoint marks
+//!   are folded in alongside the `api_conversation_history.json` messages,
+//!   ordered by timestamp;
+//! - tool uses keep their structured payloads as invocations instead of
+//!   flattening to plain text;
+//! - the workspace is recovered from `task_metadata.json` when the base
+//!   parser could not infer one.
 
-pub use franken_agent_detection::ClineConnector;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{
+    Connector, DetectionResult, DiscoveredSourceFile, NormalizedConversation, NormalizedMessage,
+    ScanContext, reindex_messages,
+};
+
+pub struct ClineConnector {
+    inner: franken_agent_detection::ClineConnector,
+}
+
+impl Default for ClineConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClineConnector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: franken_agent_detection::ClineConnector::new(),
+        }
+    }
+}
+
+impl Connector for ClineConnector {
+    fn detect(&self) -> DetectionResult {
+        self.inner.detect()
+    }
+
+    fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
+        let mut conversations = self.inner.scan(ctx)?;
+        for conversation in &mut conversations {
+            augment_cline_task(conversation);
+        }
+        Ok(conversations)
+    }
+
+    fn supports_streaming_scan(&self) -> bool {
+        self.inner.supports_streaming_scan()
+    }
+
+    fn discover_source_files(&self, ctx: &ScanContext) -> Result<Vec<DiscoveredSourceFile>> {
+        self.inner.discover_source_files(ctx)
+    }
+
+    fn scan_with_callback(
+        &self,
+        ctx: &ScanContext,
+        on_conversation: &mut dyn FnMut(NormalizedConversation) -> Result<()>,
+    ) -> Result<()> {
+        self.inner.scan_with_callback(ctx, &mut |mut conversation| {
+            augment_cline_task(&mut conversation);
+            on_conversation(conversation)
+        })
+    }
+}
+
+/// Merge the rest of a Cline task directory into the base conversation:
+/// `ui_messages.json` tool activity and checkpoints, plus the workspace from
+/// the task metadata.
+fn augment_cline_task(conversation: &mut NormalizedConversation) {
+    let Some(task_dir) = find_task_dir(&conversation.source_path) else {
+        return;
+    };
+
+    if conversation.workspace.is_none()
+        && let Some(workspace) = workspace_from_task_metadata(&task_dir)
+    {
+        conversation.workspace = Some(workspace);
+    }
+
+    let Some(ui_messages) = load_json_array(&task_dir.join("ui_messages.json")) else {
+        return;
+    };
+
+    let mut seen_contents: HashSet<[u8; 32]> = conversation
+        .messages
+        .iter()
+        .map(|message| *blake3::hash(message.content.as_bytes()).as_bytes())
+        .collect();
+
+    let mut added = false;
+    for raw in &ui_messages {
+        let Some(message) = ui_message(raw) else {
+            continue;
+        };
+        if !seen_contents.insert(*blake3::hash(message.content.as_bytes()).as_bytes()) {
+            continue;
+        }
+        conversation.messages.push(message);
+        added = true;
+    }
+
+    if added {
+        conversation.messages.sort_by(|left, right| {
+            left.created_at
+                .cmp(&right.created_at)
+                .then_with(|| left.idx.cmp(&right.idx))
+        });
+        reindex_messages(&mut conversation.messages);
+    }
+}
+
+/// Walk up from the conversation's source path to the task directory (the
+/// one holding `api_conversation_history.json` / `ui_messages.json`).
+fn find_task_dir(source_path: &Path) -> Option<PathBuf> {
+    source_path
+        .ancestors()
+        .find(|dir| {
+            dir.join("api_conversation_history.json").is_file()
+                || dir.join("ui_messages.json").is_file()
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Recover the workspace from `task_metadata.json`. Cline has stored it under
+/// a few names across versions; fall back to the common ancestor of the
+/// files-in-context records when no explicit key is present.
+fn workspace_from_task_metadata(task_dir: &Path) -> Option<PathBuf> {
+    let raw_text = std::fs::read_to_string(task_dir.join("task_metadata.json")).ok()?;
+    let raw: Value = serde_json::from_str(&raw_text).ok()?;
+
+    for key in [
+        "cwd",
+        "cwdOnTaskInitialization",
+        "workspace",
+        "workspaceFolder",
+    ] {
+        if let Some(path) = raw.get(key).and_then(Value::as_str)
+            && !path.trim().is_empty()
+        {
+            return Some(PathBuf::from(path.trim()));
+        }
+    }
+
+    let files = raw.get("files_in_context")?.as_array()?;
+    let mut common: Option<PathBuf> = None;
+    for file in files {
+        let path = file.get("path").and_then(Value::as_str)?;
+        let parent = Path::new(path).parent()?.to_path_buf();
+        common = Some(match common {
+            None => parent,
+            Some(existing) => common_ancestor(&existing, &parent)?,
+        });
+    }
+    common.filter(|path| path.is_absolute())
+}
+
+fn common_ancestor(left: &Path, right: &Path) -> Option<PathBuf> {
+    let mut ancestor = PathBuf::new();
+    for (a, b) in left.components().zip(right.components()) {
+        if a != b {
+            break;
+        }
+        ancestor.push(a);
+    }
+    (ancestor.components().next().is_some()).then_some(ancestor)
+}
+
+fn load_json_array(path: &Path) -> Option<Vec<Value>> {
+    let raw_text = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<Value>(&raw_text).ok()? {
+        Value::Array(entries) => Some(entries),
+        _ => None,
+    }
+}
+
+/// Build a normalized message from one `ui_messages.json` entry. Only the
+/// entries the API history does not carry become messages: command runs,
+/// browser actions, and checkpoint marks. Plain `say: text` entries duplicate
+/// the API history, so they are left to the base parser.
+fn ui_message(raw: &Value) -> Option<NormalizedMessage> {
+    let created_at = raw.get("ts").and_then(Value::as_i64);
+    let kind = raw.get("say").or_else(|| raw.get("ask"))?.as_str()?;
+    let text = raw.get("text").and_then(Value::as_str).unwrap_or("");
+
+    match kind {
+        "command" => {
+            let command = text.trim();
+            (!command.is_empty()).then(|| NormalizedMessage {
+                idx: 0,
+                role: "assistant".to_string(),
+                author: None,
+                created_at,
+                content: format!("[Command]\n$ {command}"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: vec![franken_agent_detection::NormalizedInvocation {
+                    kind: "command".to_string(),
+                    name: command
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or("command")
+                        .to_string(),
+                    raw_name: None,
+                    call_id: None,
+                    arguments: Some(Value::String(command.to_string())),
+                }],
+            })
+        }
+        "command_output" => {
+            let output = text.trim();
+            (!output.is_empty()).then(|| NormalizedMessage {
+                idx: 0,
+                role: "tool".to_string(),
+                author: None,
+                created_at,
+                content: format!("[Command output]\n{output}"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: Vec::new(),
+            })
+        }
+        "browser_action" => {
+            // The text payload is itself JSON: {"action": "click", ...}.
+            let payload: Option<Value> = serde_json::from_str(text).ok();
+            let action = payload
+                .as_ref()
+                .and_then(|p| p.get("action"))
+                .and_then(Value::as_str)
+                .unwrap_or("action")
+                .to_string();
+            Some(NormalizedMessage {
+                idx: 0,
+                role: "assistant".to_string(),
+                author: None,
+                created_at,
+                content: format!("[Browser: {action}]"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: vec![franken_agent_detection::NormalizedInvocation {
+                    kind: "browser".to_string(),
+                    name: action,
+                    raw_name: None,
+                    call_id: None,
+                    arguments: payload,
+                }],
+            })
+        }
+        "browser_action_result" => {
+            let summary: Option<Value> = serde_json::from_str(text).ok();
+            let logs = summary
+                .as_ref()
+                .and_then(|p| p.get("logs"))
+                .and_then(Value::as_str)
+                .unwrap_or(text)
+                .trim()
+                .to_string();
+            (!logs.is_empty()).then(|| NormalizedMessage {
+                idx: 0,
+                role: "tool".to_string(),
+                author: None,
+                created_at,
+                content: format!("[Browser result]\n{logs}"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: Vec::new(),
+            })
+        }
+        "checkpoint_created" => Some(NormalizedMessage {
+            idx: 0,
+            role: "assistant".to_string(),
+            author: None,
+            created_at,
+            content: match raw.get("lastCheckpointHash").and_then(Value::as_str) {
+                Some(hash) => format!("[Checkpoint {hash}]"),
+                None => "[Checkpoint]".to_string(),
+            },
+            extra: raw.clone(),
+            snippets: Vec::new(),
+            invocations: Vec::new(),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn task_dir_with(root: &Path, ui_messages: &Value, task_metadata: Option<&Value>) -> PathBuf {
+        let task_dir = root.join("tasks").join("1699999999999");
+        std::fs::create_dir_all(&task_dir).unwrap();
+        std::fs::write(task_dir.join("api_conversation_history.json"), "[]").unwrap();
+        std::fs::write(
+            task_dir.join("ui_messages.json"),
+            serde_json::to_string(ui_messages).unwrap(),
+        )
+        .unwrap();
+        if let Some(metadata) = task_metadata {
+            std::fs::write(
+                task_dir.join("task_metadata.json"),
+                serde_json::to_string(metadata).unwrap(),
+            )
+            .unwrap();
+        }
+        task_dir
+    }
+
+    fn conversation(task_dir: &Path) -> NormalizedConversation {
+        NormalizedConversation {
+            agent_slug: "cline".into(),
+            external_id: Some("1699999999999".into()),
+            title: Some("Cline task".into()),
+            workspace: None,
+            source_path: task_dir.join("api_conversation_history.json"),
+            started_at: Some(1_699_999_999_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1_699_999_999_000),
+                content: "add a retry loop".into(),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+                invocations: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn merges_commands_and_browser_actions_in_timestamp_order() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = task_dir_with(
+            dir.path(),
+            &serde_json::json!([
+                {"ts": 1_700_000_000_200_i64, "type": "say", "say": "command_output",
+                 "text": "2 passed"},
+                {"ts": 1_700_000_000_100_i64, "type": "say", "say": "command",
+                 "text": "cargo test"},
+                {"ts": 1_700_000_000_300_i64, "type": "say", "say": "browser_action",
+                 "text": "{\"action\":\"click\",\"coordinate\":\"100,200\"}"},
+            ]),
+            None,
+        );
+
+        let mut conv = conversation(&task_dir);
+        augment_cline_task(&mut conv);
+        assert_eq!(conv.messages.len(), 4);
+        assert!(conv.messages[1].content.contains("$ cargo test"));
+        assert_eq!(conv.messages[1].invocations[0].kind, "command");
+        assert_eq!(conv.messages[1].invocations[0].name, "cargo");
+        assert!(conv.messages[2].content.contains("2 passed"));
+        assert_eq!(conv.messages[2].role, "tool");
+        assert_eq!(conv.messages[3].content, "[Browser: click]");
+        assert_eq!(conv.messages[3].invocations[0].kind, "browser");
+        let idx: Vec<_> = conv.messages.iter().map(|m| m.idx).collect();
+        assert_eq!(idx, vec![0, 1, 2, 3], "messages reindexed after merge");
+    }
+
+    #[test]
+    fn recovers_workspace_from_task_metadata() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = task_dir_with(
+            dir.path(),
+            &serde_json::json!([]),
+            Some(&serde_json::json!({"cwd": "/home/u/dev/foo"})),
+        );
+
+        let mut conv = conversation(&task_dir);
+        augment_cline_task(&mut conv);
+        assert_eq!(
+            conv.workspace.as_deref(),
+            Some(Path::new("/home/u/dev/foo"))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_files_in_context_common_ancestor() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = task_dir_with(
+            dir.path(),
+            &serde_json::json!([]),
+            Some(&serde_json::json!({"files_in_context": [
+                {"path": "/home/u/dev/foo/src/main.rs"},
+                {"path": "/home/u/dev/foo/tests/it.rs"},
+            ]})),
+        );
+
+        let mut conv = conversation(&task_dir);
+        augment_cline_task(&mut conv);
+        assert_eq!(
+            conv.workspace.as_deref(),
+            Some(Path::new("/home/u/dev/foo"))
+        );
+    }
+
+    #[test]
+    fn checkpoint_marks_become_messages_and_dedup_is_content_based() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = task_dir_with(
+            dir.path(),
+            &serde_json::json!([
+                {"ts": 1_700_000_000_400_i64, "type": "say", "say": "checkpoint_created",
+                 "lastCheckpointHash": "deadbeef"},
+                {"ts": 1_700_000_000_500_i64, "type": "say", "say": "checkpoint_created",
+                 "lastCheckpointHash": "deadbeef"},
+            ]),
+            None,
+        );
+
+        let mut conv = conversation(&task_dir);
+        augment_cline_task(&mut conv);
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[1].content, "[Checkpoint deadbeef]");
+    }
+}
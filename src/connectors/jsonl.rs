@@ -0,0 +1,259 @@
+//! Resilient JSONL reading for live agent session files.
+//!
+//! Agents append to their JSONL transcripts while cass reads them, so the
+//! final line is frequently a partial write — and a single corrupt line
+//! mid-file must not swallow every valid line after it. This reader gives
+//! all JSONL consumers one hardened policy instead of per-connector loops
+//! with subtly different failure behavior:
+//!
+//! * **Interior bad lines** are skipped and logged (with source, line
+//!   number, and byte offset) — parsing continues on the next line.
+//! * **A truncated tail** — a final segment with no trailing newline that
+//!   does not parse — is recognized as an in-flight write, not corruption:
+//!   it is neither indexed nor warned about, and its byte offset is
+//!   reported so a later pass can resume exactly there via
+//!   [`read_resilient_from`].
+//! * A final segment with no trailing newline that *does* parse is
+//!   accepted; the writer simply hadn't flushed the newline yet.
+//!
+//! Local connectors route through this module; upstream
+//! `franken_agent_detection` parsers adopt it as they migrate in-tree.
+
+use std::io;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use serde_json::Value;
+use tracing::{debug, warn};
+
+/// One successfully parsed JSONL line.
+#[derive(Debug)]
+pub struct JsonlLine {
+    /// 1-based line number, counted from the start of this read (not the
+    /// file, when resuming from a non-zero offset).
+    pub line_no: usize,
+    /// Byte offset of the line's first byte within the file.
+    pub byte_offset: u64,
+    pub value: Value,
+}
+
+/// An unparseable final segment with no trailing newline — almost always a
+/// write still in flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncatedTail {
+    /// Byte offset the partial line starts at; resume reading here once the
+    /// writer has finished.
+    pub byte_offset: u64,
+    /// Bytes seen of the partial line so far.
+    pub len: usize,
+}
+
+/// Outcome of a resilient JSONL read.
+#[derive(Debug, Default)]
+pub struct JsonlRead {
+    pub lines: Vec<JsonlLine>,
+    /// Interior lines dropped because they did not parse.
+    pub skipped: usize,
+    /// Partial final line, if the file ended mid-write.
+    pub truncated_tail: Option<TruncatedTail>,
+    /// Byte offset up to which content was fully consumed; pass back to
+    /// [`read_resilient_from`] to pick up where this read stopped.
+    pub resume_offset: u64,
+}
+
+/// Read a whole JSONL file resiliently.
+pub fn read_resilient(path: &Path) -> io::Result<JsonlRead> {
+    read_resilient_from(path, 0)
+}
+
+/// Read a JSONL file from `start_offset` (a prior read's `resume_offset`).
+pub fn read_resilient_from(path: &Path, start_offset: u64) -> io::Result<JsonlRead> {
+    let mut file = std::fs::File::open(path)?;
+    if start_offset > 0 {
+        file.seek(io::SeekFrom::Start(start_offset))?;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(parse_resilient(
+        &bytes,
+        start_offset,
+        &path.display().to_string(),
+    ))
+}
+
+/// Pure parsing core over an in-memory chunk starting at `base_offset`.
+/// `source` labels log lines (the file path for real reads).
+#[must_use]
+pub fn parse_resilient(bytes: &[u8], base_offset: u64, source: &str) -> JsonlRead {
+    let mut read = JsonlRead {
+        resume_offset: base_offset,
+        ..JsonlRead::default()
+    };
+    let mut line_no = 0usize;
+    let mut segment_start = 0usize;
+
+    loop {
+        let rest = &bytes[segment_start..];
+        let Some(newline_at) = rest.iter().position(|b| *b == b'\n') else {
+            break;
+        };
+        let segment = &rest[..newline_at];
+        line_no += 1;
+        consume_complete_line(
+            &mut read,
+            segment,
+            base_offset,
+            segment_start,
+            line_no,
+            source,
+        );
+        segment_start += newline_at + 1;
+        read.resume_offset = base_offset + segment_start as u64;
+    }
+
+    // Final segment without a trailing newline: accept it when it already
+    // parses, otherwise treat it as an in-flight write and leave the resume
+    // offset pointing at its first byte.
+    let tail = &bytes[segment_start..];
+    let trimmed = tail.trim_ascii();
+    if !trimmed.is_empty() {
+        line_no += 1;
+        match serde_json::from_slice::<Value>(trimmed) {
+            Ok(value) => {
+                read.lines.push(JsonlLine {
+                    line_no,
+                    byte_offset: base_offset + segment_start as u64,
+                    value,
+                });
+                read.resume_offset = base_offset + bytes.len() as u64;
+            }
+            Err(_) => {
+                let tail = TruncatedTail {
+                    byte_offset: base_offset + segment_start as u64,
+                    len: tail.len(),
+                };
+                debug!(
+                    source = source,
+                    byte_offset = tail.byte_offset,
+                    partial_bytes = tail.len,
+                    "JSONL tail looks mid-write; will resume at this offset",
+                );
+                read.truncated_tail = Some(tail);
+            }
+        }
+    } else if !tail.is_empty() {
+        // Whitespace-only tail is fully consumed.
+        read.resume_offset = base_offset + bytes.len() as u64;
+    }
+
+    read
+}
+
+/// Handle one newline-terminated line: parse, or skip-and-log.
+fn consume_complete_line(
+    read: &mut JsonlRead,
+    segment: &[u8],
+    base_offset: u64,
+    segment_start: usize,
+    line_no: usize,
+    source: &str,
+) {
+    let trimmed = segment.trim_ascii();
+    if trimmed.is_empty() {
+        return;
+    }
+    match serde_json::from_slice::<Value>(trimmed) {
+        Ok(value) => read.lines.push(JsonlLine {
+            line_no,
+            byte_offset: base_offset + segment_start as u64,
+            value,
+        }),
+        Err(parse_err) => {
+            // A complete (newline-terminated) line that fails to parse is
+            // corruption, not an in-flight write: log it for operator
+            // correlation and keep going so valid lines after it survive.
+            read.skipped += 1;
+            warn!(
+                source = source,
+                line_no = line_no,
+                byte_offset = base_offset + segment_start as u64,
+                error = %parse_err,
+                "JSONL line failed to parse; skipping",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_lines_survive_a_corrupt_interior_line() {
+        let data = b"{\"a\":1}\nnot json at all\n{\"b\":2}\n";
+        let read = parse_resilient(data, 0, "test");
+        assert_eq!(read.lines.len(), 2);
+        assert_eq!(read.lines[0].value["a"], 1);
+        assert_eq!(read.lines[1].value["b"], 2);
+        assert_eq!(read.lines[1].line_no, 3);
+        assert_eq!(read.skipped, 1);
+        assert_eq!(read.truncated_tail, None);
+        assert_eq!(read.resume_offset, data.len() as u64);
+    }
+
+    #[test]
+    fn truncated_tail_is_remembered_not_skipped() {
+        let data = b"{\"a\":1}\n{\"b\":2,\"partial";
+        let read = parse_resilient(data, 0, "test");
+        assert_eq!(read.lines.len(), 1);
+        assert_eq!(read.skipped, 0, "a mid-write tail is not corruption");
+        let tail = read.truncated_tail.expect("tail detected");
+        assert_eq!(tail.byte_offset, 8);
+        // Resuming from the reported offset after the writer finishes picks
+        // up the completed line.
+        assert_eq!(read.resume_offset, 8);
+        let finished = b"{\"b\":2,\"partial\":true}\n";
+        let resumed = parse_resilient(finished, read.resume_offset, "test");
+        assert_eq!(resumed.lines.len(), 1);
+        assert_eq!(resumed.lines[0].byte_offset, 8);
+        assert_eq!(resumed.lines[0].value["partial"], true);
+    }
+
+    #[test]
+    fn unterminated_but_complete_final_line_is_accepted() {
+        let read = parse_resilient(b"{\"a\":1}\n{\"b\":2}", 0, "test");
+        assert_eq!(read.lines.len(), 2);
+        assert_eq!(read.truncated_tail, None);
+        assert_eq!(read.resume_offset, 15);
+    }
+
+    #[test]
+    fn blank_lines_and_empty_input_are_fine() {
+        let read = parse_resilient(b"\n\n{\"a\":1}\n\n", 0, "test");
+        assert_eq!(read.lines.len(), 1);
+        assert_eq!(read.skipped, 0);
+        assert_eq!(read.resume_offset, 11);
+        let empty = parse_resilient(b"", 5, "test");
+        assert!(empty.lines.is_empty());
+        assert_eq!(empty.resume_offset, 5);
+    }
+
+    #[test]
+    fn read_resilient_from_resumes_across_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, b"{\"n\":1}\n{\"n\":2,").unwrap();
+        let first = read_resilient(&path).unwrap();
+        assert_eq!(first.lines.len(), 1);
+        assert!(first.truncated_tail.is_some());
+
+        let mut all = std::fs::read(&path).unwrap();
+        all.extend_from_slice(b"\"done\":true}\n{\"n\":3}\n");
+        std::fs::write(&path, &all).unwrap();
+        let second = read_resilient_from(&path, first.resume_offset).unwrap();
+        assert_eq!(second.lines.len(), 2);
+        assert_eq!(second.lines[0].value["n"], 2);
+        assert_eq!(second.lines[1].value["n"], 3);
+        assert_eq!(second.truncated_tail, None);
+    }
+}
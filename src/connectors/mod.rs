@@ -197,6 +197,213 @@ fn is_codex_rollout_file(path: &Path) -> bool {
             })
 }
 
+/// Directory names pruned from project scan-root preflights regardless of
+/// gitignore contents: dependency and build trees that never contain agent
+/// session history but dominate walk time on real projects.
+const PROJECT_SCAN_PRUNED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".tox",
+    ".mypy_cache",
+    ".ruff_cache",
+    "dist",
+    "build",
+    ".next",
+    ".cache",
+];
+
+/// Session-history filenames for connectors that keep their history inside
+/// the project tree. Only these connectors get the ignore-aware preflight;
+/// other connectors keep their configured roots untouched because their file
+/// layout is owned by `franken_agent_detection`.
+fn project_history_file_names(provider: &str) -> &'static [&'static str] {
+    match provider {
+        "aider" => &[".aider.chat.history.md"],
+        _ => &[],
+    }
+}
+
+/// Overrides for the ignore-aware project scan-root preflight.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScanIgnoreOverrides {
+    /// Extra directory names to prune (`CASS_SCAN_IGNORE_DIRS`).
+    pub extra_pruned: Vec<String>,
+    /// Default-pruned directory names to scan anyway (`CASS_SCAN_KEEP_DIRS`).
+    pub keep: Vec<String>,
+    /// Disable the preflight entirely (`CASS_SCAN_NO_IGNORE=1`).
+    pub disabled: bool,
+}
+
+impl ProjectScanIgnoreOverrides {
+    /// Read overrides from the environment (dotenv-aware, like the other
+    /// `CASS_*` indexing knobs).
+    #[must_use]
+    pub fn from_env() -> Self {
+        fn names(var: &str) -> Vec<String> {
+            dotenvy::var(var)
+                .ok()
+                .map(|raw| {
+                    raw.split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        Self {
+            extra_pruned: names("CASS_SCAN_IGNORE_DIRS"),
+            keep: names("CASS_SCAN_KEEP_DIRS"),
+            disabled: dotenvy::var("CASS_SCAN_NO_IGNORE")
+                .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true")),
+        }
+    }
+
+    fn prunes(&self, name: &str) -> bool {
+        if self.keep.iter().any(|keep| keep == name) {
+            return false;
+        }
+        PROJECT_SCAN_PRUNED_DIRS.contains(&name)
+            || self.extra_pruned.iter().any(|extra| extra == name)
+    }
+}
+
+/// Result of the project scan-root preflight. Mirrors [`CodexScanPreflight`]:
+/// directory roots are replaced with explicit history-file roots while
+/// preserving each root's provenance and workspace rewrite metadata.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct ProjectScanPreflight {
+    pub scan_roots: Vec<ScanRoot>,
+    pub original_roots: usize,
+    pub explicit_file_roots: usize,
+    pub fallback_roots: usize,
+}
+
+/// Expand configured project-directory scan roots into explicit session
+/// history files for connectors that embed history inside the project tree
+/// (Aider-style), honoring `.gitignore`/`.ignore` rules plus a built-in prune
+/// list so the connector never descends into `node_modules`, `target/`, or
+/// virtualenvs. Roots for other connectors, explicit file roots, and
+/// unreadable directories pass through unchanged.
+#[doc(hidden)]
+#[must_use]
+pub fn preflight_project_scan_roots(
+    provider: &str,
+    roots: &[ScanRoot],
+    since_ts: Option<i64>,
+    overrides: &ProjectScanIgnoreOverrides,
+) -> ProjectScanPreflight {
+    let patterns = project_history_file_names(provider);
+    if overrides.disabled || patterns.is_empty() {
+        return ProjectScanPreflight {
+            scan_roots: roots.to_vec(),
+            original_roots: roots.len(),
+            explicit_file_roots: 0,
+            fallback_roots: 0,
+        };
+    }
+
+    let mut scan_roots = Vec::new();
+    let mut explicit_file_roots = 0usize;
+    let mut fallback_roots = 0usize;
+
+    for root in roots {
+        if root.path.is_file() {
+            if path_matches_history_file(&root.path, patterns) {
+                explicit_file_roots = explicit_file_roots.saturating_add(1);
+            }
+            scan_roots.push(root.clone());
+            continue;
+        }
+
+        // An unreadable root keeps the directory scan so the connector's own
+        // error reporting stays the source of truth.
+        if fs::read_dir(&root.path).is_err() {
+            fallback_roots = fallback_roots.saturating_add(1);
+            scan_roots.push(root.clone());
+            continue;
+        }
+
+        let expanded = project_history_file_roots_for_root(root, patterns, since_ts, overrides);
+        explicit_file_roots = explicit_file_roots.saturating_add(expanded.len());
+        scan_roots.extend(expanded);
+    }
+
+    ProjectScanPreflight {
+        scan_roots,
+        original_roots: roots.len(),
+        explicit_file_roots,
+        fallback_roots,
+    }
+}
+
+fn path_matches_history_file(path: &Path, patterns: &[&str]) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| patterns.contains(&name))
+}
+
+fn project_history_file_roots_for_root(
+    root: &ScanRoot,
+    patterns: &[&str],
+    since_ts: Option<i64>,
+    overrides: &ProjectScanIgnoreOverrides,
+) -> Vec<ScanRoot> {
+    let filter_overrides = overrides.clone();
+    let mut builder = ignore::WalkBuilder::new(&root.path);
+    builder
+        .follow_links(false)
+        // `.aider.*` history files are dot-files; ignore-awareness comes from
+        // the ignore files and the prune list, not hidden-entry filtering.
+        .hidden(false)
+        .parents(false)
+        .git_global(false)
+        .git_exclude(true)
+        .git_ignore(true)
+        .ignore(true)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return true;
+            }
+            entry
+                .file_name()
+                .to_str()
+                .is_none_or(|name| !filter_overrides.prunes(name))
+        });
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for entry in builder.build() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let path = entry.into_path();
+        if path_matches_history_file(&path, patterns) && file_modified_since(&path, since_ts) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    files
+        .into_iter()
+        .map(|path| {
+            let mut file_root = root.clone();
+            file_root.path = path;
+            file_root
+        })
+        .collect()
+}
+
+// Shared resilient JSONL reading for connectors that parse in-tree.
+pub mod jsonl;
+
 // Connector re-export stubs — each module file re-exports from FAD.
 pub mod aider;
 pub mod amp;
@@ -219,3 +426,98 @@ pub mod opencode;
 pub mod pi_agent;
 pub mod qwen;
 pub mod vibe;
+
+#[cfg(test)]
+mod project_scan_preflight_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_history(dir: &Path) {
+        fs::create_dir_all(dir).expect("create dir");
+        fs::write(dir.join(".aider.chat.history.md"), "# aider chat").expect("write history");
+    }
+
+    #[test]
+    fn prunes_default_dirs_and_gitignored_dirs() {
+        let tmp = TempDir::new().expect("tempdir");
+        let project = tmp.path().join("project");
+        write_history(&project);
+        write_history(&project.join("node_modules").join("dep"));
+        write_history(&project.join("scratch"));
+        fs::write(project.join(".gitignore"), "scratch/\n").expect("write gitignore");
+
+        let preflight = preflight_project_scan_roots(
+            "aider",
+            &[ScanRoot::local(project.clone())],
+            None,
+            &ProjectScanIgnoreOverrides::default(),
+        );
+
+        assert_eq!(preflight.original_roots, 1);
+        assert_eq!(preflight.fallback_roots, 0);
+        assert_eq!(preflight.explicit_file_roots, 1);
+        assert_eq!(
+            preflight
+                .scan_roots
+                .iter()
+                .map(|root| root.path.clone())
+                .collect::<Vec<_>>(),
+            vec![project.join(".aider.chat.history.md")]
+        );
+    }
+
+    #[test]
+    fn overrides_extend_and_relax_the_prune_list() {
+        let tmp = TempDir::new().expect("tempdir");
+        let project = tmp.path().join("project");
+        write_history(&project.join("vendor"));
+        write_history(&project.join("node_modules"));
+
+        let overrides = ProjectScanIgnoreOverrides {
+            extra_pruned: vec!["vendor".to_string()],
+            keep: vec!["node_modules".to_string()],
+            disabled: false,
+        };
+        let preflight = preflight_project_scan_roots(
+            "aider",
+            &[ScanRoot::local(project.clone())],
+            None,
+            &overrides,
+        );
+
+        assert_eq!(
+            preflight
+                .scan_roots
+                .iter()
+                .map(|root| root.path.clone())
+                .collect::<Vec<_>>(),
+            vec![project.join("node_modules").join(".aider.chat.history.md")]
+        );
+    }
+
+    #[test]
+    fn non_project_providers_and_disabled_overrides_pass_through() {
+        let tmp = TempDir::new().expect("tempdir");
+        let project = tmp.path().join("project");
+        write_history(&project.join("node_modules"));
+        let roots = vec![ScanRoot::local(project.clone())];
+
+        let codex = preflight_project_scan_roots(
+            "codex",
+            &roots,
+            None,
+            &ProjectScanIgnoreOverrides::default(),
+        );
+        assert_eq!(codex.scan_roots.len(), 1);
+        assert_eq!(codex.scan_roots[0].path, project);
+        assert_eq!(codex.explicit_file_roots, 0);
+
+        let disabled = ProjectScanIgnoreOverrides {
+            disabled: true,
+            ..ProjectScanIgnoreOverrides::default()
+        };
+        let aider = preflight_project_scan_roots("aider", &roots, None, &disabled);
+        assert_eq!(aider.scan_roots.len(), 1);
+        assert_eq!(aider.scan_roots[0].path, project);
+    }
+}
@@ -1,5 +1,496 @@
 //! Connector for OpenCode session logs.
 //!
-//! Implementation lives in `franken_agent_detection::connectors::opencode`.
+//! The base parser lives in `franken_agent_detection::connectors::opencode`;
+//! this wrapper layers support for the newer storage schema on top of it:
+//!
+//! - parts moved from `part/<message>/` to `part/<session>/<message>/` in
+//!   recent OpenCode versions — both layouts are read;
+//! - `reasoning` parts (model thinking) are indexed when
+//!   `CASS_INCLUDE_THINKING` is truthy and skipped otherwise;
+//! - `patch` parts are recorded as snippets on a synthetic assistant message;
+//! - `agent` parts (sub-agent handoffs) become regular assistant messages.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{
+    Connector, DetectionResult, DiscoveredSourceFile, NormalizedConversation, NormalizedMessage,
+    NormalizedSnippet, ScanContext, reindex_messages,
+};
+
+pub struct OpenCodeConnector {
+    inner: franken_agent_detection::OpenCodeConnector,
+}
+
+impl Default for OpenCodeConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenCodeConnector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: franken_agent_detection::OpenCodeConnector::new(),
+        }
+    }
+}
+
+impl Connector for OpenCodeConnector {
+    fn detect(&self) -> DetectionResult {
+        self.inner.detect()
+    }
+
+    fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
+        let include_thinking = include_thinking_enabled();
+        let mut conversations = self.inner.scan(ctx)?;
+        for conversation in &mut conversations {
+            augment_opencode_parts(conversation, include_thinking);
+        }
+        Ok(conversations)
+    }
+
+    fn supports_streaming_scan(&self) -> bool {
+        self.inner.supports_streaming_scan()
+    }
+
+    fn discover_source_files(&self, ctx: &ScanContext) -> Result<Vec<DiscoveredSourceFile>> {
+        self.inner.discover_source_files(ctx)
+    }
+
+    fn scan_with_callback(
+        &self,
+        ctx: &ScanContext,
+        on_conversation: &mut dyn FnMut(NormalizedConversation) -> Result<()>,
+    ) -> Result<()> {
+        let include_thinking = include_thinking_enabled();
+        self.inner.scan_with_callback(ctx, &mut |mut conversation| {
+            augment_opencode_parts(&mut conversation, include_thinking);
+            on_conversation(conversation)
+        })
+    }
+}
+
+fn include_thinking_enabled() -> bool {
+    dotenvy::var("CASS_INCLUDE_THINKING")
+        .ok()
+        .map(|value| {
+            matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Fold `reasoning`, `patch`, and `agent` parts — which the base parser
+/// skips — into the conversation, reading both the legacy `part/<message>/`
+/// and the newer `part/<session>/<message>/` storage layouts.
+fn augment_opencode_parts(conversation: &mut NormalizedConversation, include_thinking: bool) {
+    let Some(session_id) = conversation.external_id.clone() else {
+        return;
+    };
+    let Some(storage) = find_storage_root(&conversation.source_path) else {
+        return;
+    };
+
+    // Message metadata supplies role and timestamp for parts, which carry
+    // neither in either layout.
+    let message_meta = load_message_metadata(&storage, &session_id);
+    if message_meta.is_empty() {
+        return;
+    }
+
+    let mut seen_part_ids: HashSet<String> = HashSet::new();
+    let mut seen_contents: HashSet<[u8; 32]> = conversation
+        .messages
+        .iter()
+        .map(|message| *blake3::hash(message.content.as_bytes()).as_bytes())
+        .collect();
+
+    let mut added = false;
+    for part_path in collect_part_files(&storage, &session_id, &message_meta) {
+        let Ok(raw_text) = std::fs::read_to_string(&part_path) else {
+            continue;
+        };
+        let Ok(raw) = serde_json::from_str::<Value>(&raw_text) else {
+            continue;
+        };
+        if let Some(part_id) = raw.get("id").and_then(Value::as_str)
+            && !seen_part_ids.insert(part_id.to_string())
+        {
+            // Same part visible through both layouts (post-migration stores).
+            continue;
+        }
+        let message_id = raw.get("messageID").and_then(Value::as_str);
+        let meta = message_id.and_then(|id| message_meta.get(id));
+        let Some(message) = part_message(&raw, meta, include_thinking) else {
+            continue;
+        };
+        if !seen_contents.insert(*blake3::hash(message.content.as_bytes()).as_bytes()) {
+            continue;
+        }
+        conversation.messages.push(message);
+        added = true;
+    }
+
+    if added {
+        conversation.messages.sort_by(|left, right| {
+            left.created_at
+                .cmp(&right.created_at)
+                .then_with(|| left.idx.cmp(&right.idx))
+        });
+        reindex_messages(&mut conversation.messages);
+    }
+}
+
+/// Walk up from the conversation's source path to the OpenCode storage root
+/// (the directory holding both `message/` and `part/`).
+fn find_storage_root(source_path: &Path) -> Option<PathBuf> {
+    source_path
+        .ancestors()
+        .find(|dir| dir.join("message").is_dir() && dir.join("part").is_dir())
+        .map(Path::to_path_buf)
+}
+
+#[derive(Debug, Clone)]
+struct OpenCodeMessageMeta {
+    role: String,
+    created_at: Option<i64>,
+}
+
+/// Load role and creation time for every message in the session from
+/// `message/<session>/<message>.json`.
+fn load_message_metadata(storage: &Path, session_id: &str) -> HashMap<String, OpenCodeMessageMeta> {
+    let mut meta = HashMap::new();
+    let message_dir = storage.join("message").join(session_id);
+    let Ok(entries) = std::fs::read_dir(&message_dir) else {
+        return meta;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(message_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let Ok(raw_text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(raw) = serde_json::from_str::<Value>(&raw_text) else {
+            continue;
+        };
+        let role = raw
+            .get("role")
+            .and_then(Value::as_str)
+            .unwrap_or("assistant")
+            .to_string();
+        let created_at = raw
+            .get("time")
+            .and_then(|time| time.get("created"))
+            .and_then(Value::as_i64);
+        meta.insert(
+            message_id.to_string(),
+            OpenCodeMessageMeta { role, created_at },
+        );
+    }
+    meta
+}
+
+/// Enumerate part files across both storage layouts. Order does not matter:
+/// duplicates are filtered by part id and the merged messages are re-sorted
+/// by timestamp afterwards.
+fn collect_part_files(
+    storage: &Path,
+    session_id: &str,
+    message_meta: &HashMap<String, OpenCodeMessageMeta>,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let part_root = storage.join("part");
+
+    // Newer layout: part/<session>/<message>/<part>.json
+    let session_dir = part_root.join(session_id);
+    if let Ok(message_dirs) = std::fs::read_dir(&session_dir) {
+        for message_dir in message_dirs.flatten() {
+            push_json_files(&message_dir.path(), &mut files);
+        }
+    }
+
+    // Legacy layout: part/<message>/<part>.json
+    for message_id in message_meta.keys() {
+        push_json_files(&part_root.join(message_id), &mut files);
+    }
+
+    files
+}
+
+fn push_json_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            files.push(path);
+        }
+    }
+}
+
+/// Build a normalized message for the part kinds the base parser skips.
+/// Returns `None` for everything it already handles (`text`, `tool`, ...).
+fn part_message(
+    raw: &Value,
+    meta: Option<&OpenCodeMessageMeta>,
+    include_thinking: bool,
+) -> Option<NormalizedMessage> {
+    let created_at = meta.and_then(|meta| meta.created_at);
+    match raw.get("type").and_then(Value::as_str)? {
+        "reasoning" => {
+            if !include_thinking {
+                return None;
+            }
+            let text = raw.get("text").and_then(Value::as_str)?.trim();
+            (!text.is_empty()).then(|| NormalizedMessage {
+                idx: 0,
+                role: meta.map_or_else(|| "assistant".to_string(), |meta| meta.role.clone()),
+                author: None,
+                created_at,
+                content: format!("[Thinking]\n{text}"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: Vec::new(),
+            })
+        }
+        "patch" => {
+            let (snippets, file_list) = patch_snippets(raw);
+            if snippets.is_empty() {
+                return None;
+            }
+            let hash = raw.get("hash").and_then(Value::as_str).unwrap_or("");
+            let content = if hash.is_empty() {
+                format!("[Patch]\n{file_list}")
+            } else {
+                format!("[Patch {hash}]\n{file_list}")
+            };
+            Some(NormalizedMessage {
+                idx: 0,
+                role: "assistant".to_string(),
+                author: None,
+                created_at,
+                content,
+                extra: raw.clone(),
+                snippets,
+                invocations: Vec::new(),
+            })
+        }
+        "agent" => {
+            let name = raw
+                .get("name")
+                .or_else(|| raw.get("agent"))
+                .and_then(Value::as_str)?
+                .trim();
+            (!name.is_empty()).then(|| NormalizedMessage {
+                idx: 0,
+                role: "assistant".to_string(),
+                author: Some(name.to_string()),
+                created_at,
+                content: format!("[Agent: {name}]"),
+                extra: raw.clone(),
+                snippets: Vec::new(),
+                invocations: Vec::new(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Turn a patch part's file set into snippets. `files` is an array of paths
+/// in older versions and a path→diff map in newer ones; both are accepted.
+fn patch_snippets(raw: &Value) -> (Vec<NormalizedSnippet>, String) {
+    let mut snippets = Vec::new();
+    let mut names = Vec::new();
+    match raw.get("files") {
+        Some(Value::Array(paths)) => {
+            for path in paths.iter().filter_map(Value::as_str) {
+                names.push(path.to_string());
+                snippets.push(patch_snippet(path, None));
+            }
+        }
+        Some(Value::Object(map)) => {
+            for (path, diff) in map {
+                names.push(path.clone());
+                snippets.push(patch_snippet(path, diff.as_str()));
+            }
+        }
+        _ => {}
+    }
+    let file_list = names.join("\n");
+    (snippets, file_list)
+}
+
+fn patch_snippet(path: &str, diff: Option<&str>) -> NormalizedSnippet {
+    NormalizedSnippet {
+        file_path: Some(path.into()),
+        start_line: None,
+        end_line: None,
+        language: None,
+        snippet_text: diff
+            .map(str::trim)
+            .filter(|diff| !diff.is_empty())
+            .map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_json(path: &Path, value: &Value) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, serde_json::to_string(value).unwrap()).unwrap();
+    }
+
+    fn storage_with_session(root: &Path, session_id: &str, message_id: &str) -> PathBuf {
+        let storage = root.join("storage");
+        write_json(
+            &storage
+                .join("message")
+                .join(session_id)
+                .join(format!("{message_id}.json")),
+            &serde_json::json!({
+                "id": message_id,
+                "sessionID": session_id,
+                "role": "assistant",
+                "time": {"created": 1_700_000_300_000_i64},
+            }),
+        );
+        // `find_storage_root` requires both stores to exist.
+        std::fs::create_dir_all(storage.join("part")).unwrap();
+        storage
+    }
+
+    fn conversation(storage: &Path, session_id: &str) -> NormalizedConversation {
+        NormalizedConversation {
+            agent_slug: "opencode".into(),
+            external_id: Some(session_id.into()),
+            title: Some("OpenCode session".into()),
+            workspace: None,
+            source_path: storage
+                .join("session")
+                .join("info")
+                .join(format!("{session_id}.json")),
+            started_at: Some(1_700_000_300_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1_700_000_299_000),
+                content: "please fix the bug".into(),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+                invocations: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn reads_reasoning_parts_from_new_layout_behind_thinking_flag() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage_with_session(dir.path(), "ses_1", "msg_1");
+        write_json(
+            &storage
+                .join("part")
+                .join("ses_1")
+                .join("msg_1")
+                .join("prt_1.json"),
+            &serde_json::json!({
+                "id": "prt_1",
+                "sessionID": "ses_1",
+                "messageID": "msg_1",
+                "type": "reasoning",
+                "text": "the bug is in the loop bound",
+            }),
+        );
+
+        let mut without = conversation(&storage, "ses_1");
+        augment_opencode_parts(&mut without, false);
+        assert_eq!(without.messages.len(), 1, "reasoning skipped by default");
+
+        let mut with = conversation(&storage, "ses_1");
+        augment_opencode_parts(&mut with, true);
+        assert_eq!(with.messages.len(), 2);
+        let reasoning = &with.messages[1];
+        assert_eq!(reasoning.role, "assistant");
+        assert_eq!(reasoning.created_at, Some(1_700_000_300_000));
+        assert!(reasoning.content.starts_with("[Thinking]"));
+        assert!(reasoning.content.contains("the bug is in the loop bound"));
+    }
+
+    #[test]
+    fn reads_patch_parts_from_legacy_layout_as_snippets() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage_with_session(dir.path(), "ses_2", "msg_2");
+        write_json(
+            &storage.join("part").join("msg_2").join("prt_2.json"),
+            &serde_json::json!({
+                "id": "prt_2",
+                "sessionID": "ses_2",
+                "messageID": "msg_2",
+                "type": "patch",
+                "hash": "abc123",
+                "files": {"src/main.rs": "-old line\n+new line"},
+            }),
+        );
+
+        let mut conv = conversation(&storage, "ses_2");
+        augment_opencode_parts(&mut conv, false);
+        assert_eq!(conv.messages.len(), 2);
+        let patch = &conv.messages[1];
+        assert!(patch.content.contains("[Patch abc123]"));
+        assert!(patch.content.contains("src/main.rs"));
+        assert_eq!(patch.snippets.len(), 1);
+        assert_eq!(
+            patch.snippets[0].snippet_text.as_deref(),
+            Some("-old line\n+new line")
+        );
+    }
+
+    #[test]
+    fn duplicate_parts_across_layouts_are_indexed_once() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage_with_session(dir.path(), "ses_3", "msg_3");
+        let part = serde_json::json!({
+            "id": "prt_3",
+            "sessionID": "ses_3",
+            "messageID": "msg_3",
+            "type": "agent",
+            "name": "reviewer",
+        });
+        write_json(
+            &storage
+                .join("part")
+                .join("ses_3")
+                .join("msg_3")
+                .join("prt_3.json"),
+            &part,
+        );
+        write_json(
+            &storage.join("part").join("msg_3").join("prt_3.json"),
+            &part,
+        );
 
-pub use franken_agent_detection::OpenCodeConnector;
+        let mut conv = conversation(&storage, "ses_3");
+        augment_opencode_parts(&mut conv, false);
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[1].content, "[Agent: reviewer]");
+        assert_eq!(conv.messages[1].author.as_deref(), Some("reviewer"));
+    }
+}
@@ -0,0 +1,382 @@
+//! Roll-up digest of the corrections you issue to your agents.
+//!
+//! `cass corrections --since 90d` answers "what do I keep having to fix?":
+//! it scans user messages that immediately follow an agent turn for
+//! correction language ("no, I meant…", "that's wrong", "revert that"),
+//! buckets each hit into a small closed category set, and aggregates the
+//! counts per agent. The per-category tallies — plus a few short excerpts —
+//! are direct input for standing-instructions files: the category you
+//! correct most often is the paragraph your AGENTS.md is missing.
+//!
+//! Detection is textual and deliberately conservative: only the prose
+//! portion of a message is scanned (fenced code blocks are skipped), most
+//! triggers are multi-word phrases, and a message lands in exactly one
+//! category — the first match in priority order — so a single rant never
+//! inflates several buckets.
+//!
+//! ## Pure and deterministic
+//!
+//! Extraction ([`extract_corrections`]) does no I/O: the caller supplies
+//! per-conversation `(role, content)` sequences with the owning agent name
+//! and the same input always yields the same report. The CLI handler in
+//! `lib.rs` owns the database read and the time-window resolution.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Excerpts kept per category per agent.
+const MAX_EXAMPLES: usize = 3;
+
+/// Excerpt length in characters; enough to recognize the complaint without
+/// reproducing the whole message.
+const EXAMPLE_CHARS: usize = 100;
+
+/// Closed set of correction categories, in detection priority order: a
+/// message is attributed to the first category whose trigger fires. More
+/// specific complaints ("revert that") outrank the generic "that's wrong".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CorrectionCategory {
+    /// The agent changed something it should not have and was told to back
+    /// the change out ("revert", "undo that", "put it back").
+    UnwantedChange,
+    /// The agent did more than asked — extra files, drive-by refactors
+    /// ("I didn't ask", "don't touch", "stick to").
+    ScopeCreep,
+    /// The agent ignored or forgot a standing instruction it was already
+    /// given ("I told you", "as I said", "again:").
+    IgnoredInstructions,
+    /// The agent solved the wrong problem — the request was misread
+    /// ("no, I meant", "that's not what I asked").
+    MisreadIntent,
+    /// The agent's output is simply incorrect or broken ("that's wrong",
+    /// "still failing", "doesn't work").
+    WrongResult,
+}
+
+impl CorrectionCategory {
+    /// Every category, in detection priority order.
+    pub const ALL: [CorrectionCategory; 5] = [
+        CorrectionCategory::UnwantedChange,
+        CorrectionCategory::ScopeCreep,
+        CorrectionCategory::IgnoredInstructions,
+        CorrectionCategory::MisreadIntent,
+        CorrectionCategory::WrongResult,
+    ];
+
+    /// Stable kebab-case label used in both JSON and plain output.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CorrectionCategory::UnwantedChange => "unwanted-change",
+            CorrectionCategory::ScopeCreep => "scope-creep",
+            CorrectionCategory::IgnoredInstructions => "ignored-instructions",
+            CorrectionCategory::MisreadIntent => "misread-intent",
+            CorrectionCategory::WrongResult => "wrong-result",
+        }
+    }
+
+    /// Phrases that attribute a correction to this category. Matched against
+    /// lowercased prose, anywhere in the message.
+    const fn triggers(self) -> &'static [&'static str] {
+        match self {
+            CorrectionCategory::UnwantedChange => &[
+                "revert",
+                "undo that",
+                "undo the",
+                "put it back",
+                "put that back",
+                "why did you change",
+                "why did you delete",
+                "you shouldn't have changed",
+                "restore the original",
+            ],
+            CorrectionCategory::ScopeCreep => &[
+                "i didn't ask",
+                "i did not ask",
+                "don't touch",
+                "do not touch",
+                "stick to",
+                "only change",
+                "stop changing",
+                "stay focused on",
+                "that's out of scope",
+            ],
+            CorrectionCategory::IgnoredInstructions => &[
+                "i told you",
+                "i already told you",
+                "i already said",
+                "as i said",
+                "like i said",
+                "i asked you to",
+                "you were supposed to",
+                "follow the instructions",
+                "read the instructions",
+            ],
+            CorrectionCategory::MisreadIntent => &[
+                "no, i meant",
+                "no i meant",
+                "i meant",
+                "i actually meant",
+                "that's not what i asked",
+                "that's not what i meant",
+                "not what i wanted",
+                "not what i asked for",
+                "you misunderstood",
+            ],
+            CorrectionCategory::WrongResult => &[
+                "that's wrong",
+                "that is wrong",
+                "this is wrong",
+                "still broken",
+                "still fails",
+                "still failing",
+                "still doesn't work",
+                "doesn't work",
+                "does not work",
+                "didn't work",
+                "you broke",
+                "that broke",
+                "introduced a bug",
+            ],
+        }
+    }
+}
+
+/// One conversation flattened for correction mining: the owning agent and
+/// its `(role, content)` messages in turn order.
+#[derive(Debug, Clone)]
+pub struct CorrectionConversation {
+    /// Agent name as stored in the index (`claude-code`, `codex`, ...).
+    pub agent: String,
+    /// Messages in `idx` order.
+    pub messages: Vec<(String, String)>,
+}
+
+/// One category's tally for one agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryCount {
+    /// Kebab-case category label.
+    pub category: String,
+    /// Correction messages attributed to this category.
+    pub count: u64,
+    /// Up to [`MAX_EXAMPLES`] short excerpts of the triggering messages.
+    pub examples: Vec<String>,
+}
+
+/// Correction digest for one agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentCorrectionSummary {
+    /// Agent name.
+    pub agent: String,
+    /// User messages that directly followed an agent turn (the denominator).
+    pub user_followups: usize,
+    /// Follow-ups classified as corrections.
+    pub corrections: usize,
+    /// Per-category tallies, highest count first.
+    pub categories: Vec<CategoryCount>,
+}
+
+/// The full roll-up, most-corrected agent first.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrectionReport {
+    /// Per-agent digests, corrections descending.
+    pub agents: Vec<AgentCorrectionSummary>,
+    /// Conversations scanned.
+    pub conversations: usize,
+}
+
+/// Mine correction turns from per-conversation message sequences and
+/// aggregate them per agent. Only user messages that immediately follow an
+/// agent message are considered — an opening prompt can't be a correction —
+/// and each hit lands in exactly one category. Output ordering is
+/// deterministic: agents by correction count descending then name,
+/// categories by count descending then label.
+#[must_use]
+pub fn extract_corrections(conversations: &[CorrectionConversation]) -> CorrectionReport {
+    let mut followups: HashMap<String, usize> = HashMap::new();
+    let mut tallies: HashMap<(String, CorrectionCategory), (u64, Vec<String>)> = HashMap::new();
+
+    for conversation in conversations {
+        let mut previous_was_agent = false;
+        for (role, content) in &conversation.messages {
+            match role.as_str() {
+                "agent" => previous_was_agent = true,
+                "user" => {
+                    if previous_was_agent {
+                        *followups.entry(conversation.agent.clone()).or_insert(0) += 1;
+                        let prose = prose_lowercase(content);
+                        if let Some(category) = classify(&prose) {
+                            let entry = tallies
+                                .entry((conversation.agent.clone(), category))
+                                .or_insert_with(|| (0, Vec::new()));
+                            entry.0 += 1;
+                            if entry.1.len() < MAX_EXAMPLES {
+                                entry.1.push(excerpt(content));
+                            }
+                        }
+                    }
+                    previous_was_agent = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut per_agent: HashMap<String, Vec<CategoryCount>> = HashMap::new();
+    for ((agent, category), (count, examples)) in tallies {
+        per_agent.entry(agent).or_default().push(CategoryCount {
+            category: category.as_str().to_string(),
+            count,
+            examples,
+        });
+    }
+
+    let mut agents: Vec<AgentCorrectionSummary> = followups
+        .into_iter()
+        .map(|(agent, user_followups)| {
+            let mut categories = per_agent.remove(&agent).unwrap_or_default();
+            categories.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.category.cmp(&b.category))
+            });
+            let corrections = categories.iter().map(|c| c.count as usize).sum();
+            AgentCorrectionSummary {
+                agent,
+                user_followups,
+                corrections,
+                categories,
+            }
+        })
+        .collect();
+    agents.sort_by(|a, b| {
+        b.corrections
+            .cmp(&a.corrections)
+            .then_with(|| a.agent.cmp(&b.agent))
+    });
+
+    CorrectionReport {
+        agents,
+        conversations: conversations.len(),
+    }
+}
+
+/// First matching category in priority order, or `None` when the message
+/// reads as an ordinary follow-up.
+fn classify(prose: &str) -> Option<CorrectionCategory> {
+    CorrectionCategory::ALL
+        .into_iter()
+        .find(|category| category.triggers().iter().any(|t| prose.contains(t)))
+}
+
+/// Lowercased prose portion of a message. Fenced code blocks are skipped so
+/// a pasted diff containing the word "revert" never counts as a correction.
+fn prose_lowercase(text: &str) -> String {
+    let mut prose = String::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        prose.push_str(&line.to_lowercase());
+        prose.push(' ');
+    }
+    prose
+}
+
+/// Short whitespace-collapsed excerpt of the triggering message.
+fn excerpt(content: &str) -> String {
+    let collapsed: String = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= EXAMPLE_CHARS {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(EXAMPLE_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conv(agent: &str, messages: &[(&str, &str)]) -> CorrectionConversation {
+        CorrectionConversation {
+            agent: agent.to_string(),
+            messages: messages
+                .iter()
+                .map(|(role, content)| (role.to_string(), content.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn corrections_only_count_after_an_agent_turn() {
+        let report = extract_corrections(&[conv(
+            "claude-code",
+            &[
+                // Opening prompt contains a trigger but follows nothing.
+                ("user", "I meant to clean this module up for a while"),
+                ("agent", "Done, refactored the module."),
+                ("user", "No, I meant only the parser, revert the rest"),
+            ],
+        )]);
+        assert_eq!(report.agents.len(), 1);
+        let agent = &report.agents[0];
+        assert_eq!(agent.user_followups, 1);
+        assert_eq!(agent.corrections, 1);
+    }
+
+    #[test]
+    fn priority_order_attributes_one_category_per_message() {
+        // "revert" (unwanted-change) and "i meant" (misread-intent) both
+        // fire; priority order keeps the hit in unwanted-change only.
+        let report = extract_corrections(&[conv(
+            "codex",
+            &[
+                ("agent", "Renamed the helpers too."),
+                ("user", "Revert that — I meant just the one function"),
+            ],
+        )]);
+        let categories = &report.agents[0].categories;
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].category, "unwanted-change");
+        assert_eq!(categories[0].count, 1);
+    }
+
+    #[test]
+    fn fenced_code_never_triggers_a_correction() {
+        let report = extract_corrections(&[conv(
+            "codex",
+            &[
+                ("agent", "Here is the patch."),
+                (
+                    "user",
+                    "Looks good:\n```\ngit revert abc123\n# still failing\n```\nship it",
+                ),
+            ],
+        )]);
+        assert_eq!(report.agents[0].corrections, 0);
+        assert_eq!(report.agents[0].user_followups, 1);
+    }
+
+    #[test]
+    fn agents_sort_by_correction_count_and_examples_are_capped() {
+        let noisy: Vec<(&str, &str)> = (0..5)
+            .flat_map(|_| [("agent", "done"), ("user", "that's wrong, still failing")])
+            .collect();
+        let report = extract_corrections(&[
+            conv("codex", &noisy),
+            conv(
+                "claude-code",
+                &[("agent", "done"), ("user", "thanks, looks great")],
+            ),
+        ]);
+        assert_eq!(report.conversations, 2);
+        assert_eq!(report.agents[0].agent, "codex");
+        assert_eq!(report.agents[0].corrections, 5);
+        assert_eq!(report.agents[0].categories[0].examples.len(), MAX_EXAMPLES);
+        assert_eq!(report.agents[1].corrections, 0);
+    }
+}
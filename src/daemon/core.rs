@@ -171,6 +171,11 @@ pub struct DaemonConfig {
     pub nice_value: i32,
     /// IO priority class (0-3).
     pub ionice_class: u32,
+    /// Per-connection request rate limit in requests/minute (0 = unlimited).
+    pub rate_limit_per_min: u32,
+    /// Maximum texts/documents accepted in one embed/rerank request
+    /// (0 = unlimited).
+    pub max_batch_size: usize,
 }
 
 impl Default for DaemonConfig {
@@ -183,6 +188,8 @@ impl Default for DaemonConfig {
             memory_limit: 0,                      // Unlimited
             nice_value: 10,                       // Low priority
             ionice_class: 2,                      // Best-effort
+            rate_limit_per_min: 600,              // 10 req/s sustained per client
+            max_batch_size: 512,
         }
     }
 }
@@ -232,6 +239,18 @@ impl DaemonConfig {
             cfg.ionice_class = n;
         }
 
+        if let Ok(val) = dotenvy::var("CASS_DAEMON_RATE_LIMIT_PER_MIN")
+            && let Ok(n) = val.parse()
+        {
+            cfg.rate_limit_per_min = n;
+        }
+
+        if let Ok(val) = dotenvy::var("CASS_DAEMON_MAX_BATCH")
+            && let Ok(n) = val.parse()
+        {
+            cfg.max_batch_size = n;
+        }
+
         cfg
     }
 }
@@ -406,6 +425,13 @@ impl ModelDaemon {
         // Start background embedding worker
         self.init_worker();
 
+        // Hot config reload, same contract as the watch loop: pick up
+        // cass.toml edits without a restart, keep the old settings (with a
+        // warning) when the new version doesn't parse.
+        let mut config_reloader = crate::search_defaults::ConfigReloader::new();
+        let mut last_config_poll = Instant::now();
+        let config_poll_interval = Duration::from_secs(5);
+
         std::thread::scope(|s| {
             loop {
                 // Check for shutdown
@@ -434,6 +460,22 @@ impl ModelDaemon {
                     break;
                 }
 
+                if last_config_poll.elapsed() >= config_poll_interval {
+                    last_config_poll = Instant::now();
+                    match config_reloader.poll() {
+                        crate::search_defaults::ConfigReload::Applied(changes) => {
+                            info!(changes = %changes.join("; "), "config reloaded");
+                        }
+                        crate::search_defaults::ConfigReload::Rejected(error) => {
+                            warn!(
+                                %error,
+                                "invalid config update ignored; keeping previous settings"
+                            );
+                        }
+                        crate::search_defaults::ConfigReload::Unchanged => {}
+                    }
+                }
+
                 // Accept new connections
                 match listener.accept() {
                     Ok((stream, _addr)) => {
@@ -552,6 +594,10 @@ impl ModelDaemon {
         let idle_poll = IDLE_SHUTDOWN_POLL.min(request_timeout);
         stream.set_write_timeout(Some(request_timeout))?;
 
+        // Each connection is one client: give it its own request budget.
+        let mut rate_limiter =
+            super::rate_limit::TokenBucket::per_minute(self.config.rate_limit_per_min);
+
         loop {
             // Idle read (length prefix): short-poll so shutdown cancels
             // promptly. Track `filled` manually because `read_exact`
@@ -594,7 +640,13 @@ impl ModelDaemon {
                 Ok(msg) => {
                     self.total_requests.fetch_add(1, Ordering::Relaxed);
                     self.touch_activity();
-                    let response = self.handle_request(msg.request_id.clone(), msg.payload);
+                    let response = if let Some(limit_error) =
+                        self.enforce_request_limits(&msg.payload, rate_limiter.as_mut())
+                    {
+                        Response::Error(limit_error)
+                    } else {
+                        self.handle_request(msg.request_id.clone(), msg.payload)
+                    };
                     FramedMessage::new(msg.request_id, response)
                 }
                 Err(e) => {
@@ -623,6 +675,60 @@ impl ModelDaemon {
         }
     }
 
+    /// Reject a request up front when it exceeds the per-connection rate
+    /// limit or the configured batch cap, before any model or database work
+    /// happens. Health/status/shutdown traffic is never throttled so
+    /// monitoring and graceful shutdown keep working while a client backs
+    /// off.
+    fn enforce_request_limits(
+        &self,
+        request: &Request,
+        rate_limiter: Option<&mut super::rate_limit::TokenBucket>,
+    ) -> Option<ErrorResponse> {
+        let throttled = !matches!(
+            request,
+            Request::Health | Request::Status | Request::Shutdown
+        );
+        if throttled
+            && let Some(bucket) = rate_limiter
+            && let Err(wait) = bucket.try_acquire()
+        {
+            return Some(ErrorResponse {
+                code: ErrorCode::Overloaded,
+                message: format!(
+                    "rate limit exceeded ({} requests/min per client)",
+                    self.config.rate_limit_per_min
+                ),
+                retryable: true,
+                retry_after_ms: Some((wait.as_millis() as u64).max(1)),
+            });
+        }
+
+        let cap = self.config.max_batch_size;
+        if cap > 0 {
+            let batch_len = match request {
+                Request::Embed { texts, .. } => Some(texts.len()),
+                Request::Rerank { documents, .. } => Some(documents.len()),
+                _ => None,
+            };
+            if let Some(len) = batch_len
+                && len > cap
+            {
+                return Some(ErrorResponse {
+                    code: ErrorCode::InvalidInput,
+                    message: format!(
+                        "batch of {len} items exceeds the cap of {cap}; \
+                         split the request or raise CASS_DAEMON_MAX_BATCH"
+                    ),
+                    retryable: false,
+                    retry_after_ms: None,
+                });
+            }
+        }
+
+        None
+    }
+
     /// Handle a single request.
     fn handle_request(&self, request_id: String, request: Request) -> Response {
         let start = Instant::now();
@@ -852,6 +958,74 @@ mod tests {
         assert_eq!(config.max_connections, 16);
         assert_eq!(config.nice_value, 10);
         assert_eq!(config.ionice_class, 2);
+        assert_eq!(config.rate_limit_per_min, 600);
+        assert_eq!(config.max_batch_size, 512);
+    }
+
+    #[test]
+    fn test_batch_cap_rejects_oversized_embed() {
+        let config = DaemonConfig {
+            max_batch_size: 2,
+            ..DaemonConfig::default()
+        };
+        let models = ModelManager::new(&test_data_dir());
+        let daemon = ModelDaemon::new(config, models);
+
+        let request = Request::Embed {
+            texts: vec!["a".into(), "b".into(), "c".into()],
+            model: "fast".into(),
+            dims: None,
+        };
+        let err = daemon
+            .enforce_request_limits(&request, None)
+            .expect("3 texts over a cap of 2 must be rejected");
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert!(!err.retryable);
+
+        let within = Request::Embed {
+            texts: vec!["a".into(), "b".into()],
+            model: "fast".into(),
+            dims: None,
+        };
+        assert!(daemon.enforce_request_limits(&within, None).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_throttles_work_but_not_health() {
+        let config = DaemonConfig {
+            rate_limit_per_min: 1,
+            ..DaemonConfig::default()
+        };
+        let models = ModelManager::new(&test_data_dir());
+        let daemon = ModelDaemon::new(config.clone(), models);
+
+        let mut bucket =
+            super::super::rate_limit::TokenBucket::per_minute(config.rate_limit_per_min)
+                .expect("limit 1 yields a bucket");
+        let embed = Request::Embed {
+            texts: vec!["a".into()],
+            model: "fast".into(),
+            dims: None,
+        };
+
+        assert!(
+            daemon
+                .enforce_request_limits(&embed, Some(&mut bucket))
+                .is_none()
+        );
+        let err = daemon
+            .enforce_request_limits(&embed, Some(&mut bucket))
+            .expect("second request in the same minute must throttle");
+        assert_eq!(err.code, ErrorCode::Overloaded);
+        assert!(err.retryable);
+        assert!(err.retry_after_ms.is_some_and(|ms| ms > 0));
+
+        // Health is never throttled, even with an exhausted bucket.
+        assert!(
+            daemon
+                .enforce_request_limits(&Request::Health, Some(&mut bucket))
+                .is_none()
+        );
     }
 
     #[test]
@@ -45,6 +45,7 @@ pub mod client;
 pub mod core;
 pub mod models;
 pub mod protocol;
+pub mod rate_limit;
 pub mod resource;
 pub mod worker;
 
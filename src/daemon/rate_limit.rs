@@ -0,0 +1,98 @@
+//! Per-client request rate limiting for the daemon.
+//!
+//! Each client connection gets its own token bucket so one misbehaving agent
+//! loop cannot starve other callers or hammer the database. The bucket holds
+//! a full minute's budget (allowing short bursts) and refills continuously;
+//! when empty, the caller gets a structured `Overloaded` error carrying the
+//! wait until the next token, which maps onto the protocol's existing
+//! `retryable`/`retry_after_ms` contract (the daemon's 429).
+
+use std::time::{Duration, Instant};
+
+/// Continuous-refill token bucket sized in requests per minute.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Bucket allowing `limit_per_min` requests per minute, with burst
+    /// capacity of one full minute's budget. `None` when the limit is 0
+    /// (unlimited).
+    #[must_use]
+    pub fn per_minute(limit_per_min: u32) -> Option<Self> {
+        if limit_per_min == 0 {
+            return None;
+        }
+        let capacity = f64::from(limit_per_min);
+        Some(Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: Instant::now(),
+        })
+    }
+
+    /// Take one token, or report how long until one is available.
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.refill_per_sec)
+            .min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let deficit = 1.0 - self.tokens;
+            Err(Duration::from_secs_f64(deficit / self.refill_per_sec))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_limit_means_unlimited() {
+        assert!(TokenBucket::per_minute(0).is_none());
+    }
+
+    #[test]
+    fn burst_up_to_capacity_then_throttles() {
+        let mut bucket = TokenBucket::per_minute(5).unwrap();
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(bucket.try_acquire_at(now).is_ok());
+        }
+        let wait = bucket
+            .try_acquire_at(now)
+            .expect_err("sixth request in the same instant should throttle");
+        // 5/min refills one token every 12s.
+        assert!(wait > Duration::from_secs(11) && wait <= Duration::from_secs(12));
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let mut bucket = TokenBucket::per_minute(60).unwrap();
+        let start = Instant::now();
+        for _ in 0..60 {
+            assert!(bucket.try_acquire_at(start).is_ok());
+        }
+        assert!(bucket.try_acquire_at(start).is_err());
+        // One second refills one token at 60/min.
+        assert!(
+            bucket
+                .try_acquire_at(start + Duration::from_millis(1100))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Approximate "what changed since yesterday" summary for a workspace.
+//!
+//! `cass delta --workspace ~/dev/foo --since 1d` is the pre-standup report:
+//! which sessions ran, which files the agents touched, which commands they
+//! executed, and which errors they hit, all mined from the indexed message
+//! history inside the time window. The extraction is heuristic on purpose —
+//! sessions do not carry structured tool-call provenance across every agent,
+//! so this module pattern-matches the textual traces that survive
+//! normalization (path-shaped tokens, `$`-prefixed command lines, error
+//! banners) and accepts some noise in exchange for working uniformly over
+//! every connector.
+//!
+//! ## Pure and deterministic
+//!
+//! Extraction ([`extract_activity`]) and rendering
+//! ([`WorkspaceDelta::render_markdown`]) do no I/O: the caller supplies
+//! already-fetched sessions and `(role, content)` pairs, and the same input
+//! always yields the same report. The CLI handler in `lib.rs` owns the
+//! database read and the time-window resolution.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Cap on distinct files listed; a refactor session can touch hundreds.
+const MAX_FILES: usize = 40;
+
+/// Cap on distinct commands listed.
+const MAX_COMMANDS: usize = 30;
+
+/// Cap on distinct errors listed; one flaky test can repeat forever.
+const MAX_ERRORS: usize = 20;
+
+/// Longest line kept verbatim in any section.
+const MAX_LINE_CHARS: usize = 200;
+
+/// One session that was active inside the window.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeltaSession {
+    /// Agent slug (`claude_code`, `codex`, ...).
+    pub agent: String,
+    /// Conversation title, if any.
+    pub title: Option<String>,
+    /// Session source file.
+    pub source_path: String,
+    /// Start timestamp (unix millis), when recorded.
+    pub started_at: Option<i64>,
+    /// Messages inside the window.
+    pub messages: usize,
+}
+
+/// Aggregated activity for one workspace and time window.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceDelta {
+    /// Window start (unix millis).
+    pub since_ms: i64,
+    /// Window end (unix millis).
+    pub until_ms: i64,
+    /// Sessions active inside the window, most recent first.
+    pub sessions: Vec<DeltaSession>,
+    /// Path-shaped tokens seen in agent/tool output, first-seen order.
+    pub files: Vec<String>,
+    /// Command lines seen in the transcripts, first-seen order.
+    pub commands: Vec<String>,
+    /// Error lines seen in the transcripts, first-seen order.
+    pub errors: Vec<String>,
+}
+
+/// First tokens that mark a line as a command the session ran.
+const COMMAND_TOOLS: &[&str] = &[
+    "cargo", "npm", "pnpm", "yarn", "bun", "make", "just", "pip", "uv", "go", "git", "docker",
+    "pytest", "bash", "sh", "rm", "cp", "mv", "mkdir", "curl", "grep", "rg", "sed",
+];
+
+/// Lowercase substrings that mark a line as an error report.
+const ERROR_CUES: &[&str] = &[
+    "error:",
+    "error[",
+    "panicked at",
+    "traceback (most recent call last)",
+    "fatal:",
+    "exception:",
+    "failed with",
+    "test failed",
+    "assertion failed",
+];
+
+/// Extensions that make a path-shaped token worth reporting. Bare
+/// slash-containing strings match far too much prose (URLs, "and/or",
+/// ratios); requiring a known source extension keeps the list honest.
+const FILE_EXTENSIONS: &[&str] = &[
+    ".rs", ".toml", ".md", ".py", ".ts", ".tsx", ".js", ".jsx", ".json", ".yaml", ".yml", ".sh",
+    ".go", ".java", ".c", ".h", ".cpp", ".hpp", ".css", ".html", ".sql", ".txt", ".lock",
+];
+
+/// Mine files, commands, and errors out of windowed messages, in message
+/// order. Each list is deduplicated case-sensitively in first-seen order and
+/// capped; `sessions` is passed through untouched so the caller controls its
+/// ordering.
+#[must_use]
+pub fn extract_activity(
+    since_ms: i64,
+    until_ms: i64,
+    sessions: Vec<DeltaSession>,
+    messages: &[(String, String)],
+) -> WorkspaceDelta {
+    let mut files = Vec::new();
+    let mut commands = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen_files = BTreeSet::new();
+    let mut seen_commands = BTreeSet::new();
+    let mut seen_errors = BTreeSet::new();
+
+    for (role, content) in messages {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.chars().count() > MAX_LINE_CHARS {
+                continue;
+            }
+            if let Some(command) = command_from_line(line)
+                && commands.len() < MAX_COMMANDS
+                && seen_commands.insert(command.clone())
+            {
+                commands.push(command);
+            }
+            let lowered = line.to_ascii_lowercase();
+            if ERROR_CUES.iter().any(|cue| lowered.contains(cue))
+                && errors.len() < MAX_ERRORS
+                && seen_errors.insert(line.to_string())
+            {
+                errors.push(line.to_string());
+            }
+            // File mentions in user prose are usually requests, not changes;
+            // only agent/tool output counts as "touched".
+            if role != "user" {
+                for token in
+                    line.split(|ch: char| ch.is_whitespace() || "\"'`()[]{},;".contains(ch))
+                {
+                    let Some(path) = file_path_from_token(token) else {
+                        continue;
+                    };
+                    if files.len() < MAX_FILES && seen_files.insert(path.clone()) {
+                        files.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    WorkspaceDelta {
+        since_ms,
+        until_ms,
+        sessions,
+        files,
+        commands,
+        errors,
+    }
+}
+
+/// Extract a command line, stripping shell-prompt and backtick wrapping.
+/// Accepts `$ cargo test`-style lines anywhere and bare tool invocations
+/// (`cargo test --workspace`) when the first token is a known tool.
+fn command_from_line(line: &str) -> Option<String> {
+    let stripped = line
+        .strip_prefix("$ ")
+        .or_else(|| line.strip_prefix("> "))
+        .unwrap_or(line)
+        .trim_matches('`')
+        .trim();
+    let first = stripped.split_whitespace().next()?;
+    if !COMMAND_TOOLS.contains(&first) {
+        return None;
+    }
+    // A bare tool name ("cargo") is a mention, not a command.
+    if stripped.split_whitespace().nth(1).is_none() {
+        return None;
+    }
+    Some(stripped.to_string())
+}
+
+/// Keep a token when it is shaped like a source path: contains a directory
+/// separator, ends in a known extension, and carries no URL scheme.
+fn file_path_from_token(token: &str) -> Option<String> {
+    let token = token
+        .trim_end_matches(|ch: char| ch == ':' || ch == '.' || ch == '*')
+        .trim_start_matches("./");
+    if token.len() < 4 || token.len() > 160 {
+        return None;
+    }
+    if !token.contains('/') || token.contains("://") {
+        return None;
+    }
+    if !FILE_EXTENSIONS.iter().any(|ext| token.ends_with(ext)) {
+        return None;
+    }
+    Some(token.to_string())
+}
+
+impl WorkspaceDelta {
+    /// Render the report as standup-ready Markdown. Empty sections are
+    /// skipped; an entirely quiet window renders a one-line note instead of
+    /// empty headings.
+    #[must_use]
+    pub fn render_markdown(&self, workspace_label: &str) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("# Delta for {workspace_label}\n\n"));
+        if self.sessions.is_empty() {
+            out.push_str("No session activity in this window.\n");
+            return out;
+        }
+        out.push_str(&format!("## Sessions ({})\n\n", self.sessions.len()));
+        for session in &self.sessions {
+            out.push_str(&format!(
+                "- [{}] {} — {} message(s)\n",
+                session.agent,
+                session.title.as_deref().unwrap_or("(untitled)"),
+                session.messages,
+            ));
+        }
+        for (heading, entries, fenced) in [
+            ("Files touched", &self.files, false),
+            ("Commands run", &self.commands, true),
+            ("Errors encountered", &self.errors, false),
+        ] {
+            if entries.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {heading} ({})\n\n", entries.len()));
+            for entry in entries {
+                if fenced {
+                    out.push_str(&format!("- `{entry}`\n"));
+                } else {
+                    out.push_str(&format!("- {entry}\n"));
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    fn one_session() -> Vec<DeltaSession> {
+        vec![DeltaSession {
+            agent: "codex".to_string(),
+            title: Some("Fix retries".to_string()),
+            source_path: "/log/a.jsonl".to_string(),
+            started_at: Some(1_000),
+            messages: 3,
+        }]
+    }
+
+    #[test]
+    fn extracts_commands_files_and_errors() {
+        let delta = extract_activity(
+            0,
+            10_000,
+            one_session(),
+            &[
+                msg(
+                    "agent",
+                    "Edited src/search/query.rs and ran:\n$ cargo test --workspace",
+                ),
+                msg(
+                    "tool",
+                    "error[E0308]: mismatched types\n  --> src/search/query.rs:42",
+                ),
+                msg("user", "please also look at docs/guide.md"),
+            ],
+        );
+        assert_eq!(delta.commands, vec!["cargo test --workspace".to_string()]);
+        assert_eq!(delta.files, vec!["src/search/query.rs".to_string()]);
+        assert_eq!(delta.errors.len(), 1);
+        assert!(delta.errors[0].starts_with("error[E0308]"));
+    }
+
+    #[test]
+    fn user_prose_paths_and_bare_tool_mentions_are_ignored() {
+        let delta = extract_activity(
+            0,
+            1,
+            one_session(),
+            &[msg(
+                "user",
+                "cargo\nthe bug is in src/lib.rs I think\nmaybe try either/or",
+            )],
+        );
+        assert!(delta.files.is_empty());
+        assert!(delta.commands.is_empty());
+    }
+
+    #[test]
+    fn repeats_dedupe_in_first_seen_order() {
+        let delta = extract_activity(
+            0,
+            1,
+            one_session(),
+            &[
+                msg("agent", "$ cargo build\n$ cargo test"),
+                msg("agent", "$ cargo build"),
+            ],
+        );
+        assert_eq!(
+            delta.commands,
+            vec!["cargo build".to_string(), "cargo test".to_string()]
+        );
+    }
+
+    #[test]
+    fn markdown_skips_empty_sections_and_handles_quiet_windows() {
+        let quiet = extract_activity(0, 1, Vec::new(), &[]);
+        assert!(
+            quiet
+                .render_markdown("~/dev/foo")
+                .contains("No session activity")
+        );
+
+        let busy = extract_activity(0, 1, one_session(), &[msg("agent", "$ cargo build")]);
+        let md = busy.render_markdown("~/dev/foo");
+        assert!(md.contains("## Sessions (1)"));
+        assert!(md.contains("- `cargo build`"));
+        assert!(!md.contains("## Files touched"));
+        assert!(!md.contains("## Errors encountered"));
+    }
+}
@@ -0,0 +1,436 @@
+//! Distill durable facts from a workspace's indexed sessions into the memory
+//! files coding agents actually read (`AGENTS.md` / `CLAUDE.md`).
+//!
+//! Sessions are full of throwaway chatter, but a few lines in them stay true
+//! long after the session ends: the setup command that finally worked, the
+//! convention the user and agent agreed on, the gotcha that cost an hour.
+//! `cass distill` mines those lines out of a workspace's message history and
+//! writes them under a managed marker block in the workspace's memory file, so
+//! the next agent session starts with them already in context.
+//!
+//! ## Idempotent by construction
+//!
+//! The rendered section lives between [`DISTILL_MARKER_BEGIN`] and
+//! [`DISTILL_MARKER_END`]. [`upsert_block`] replaces an existing block in place
+//! and appends one only when no markers are present, so re-running the command
+//! converges instead of duplicating content, and hand-written text outside the
+//! markers is never touched.
+//!
+//! ## Pure and deterministic
+//!
+//! Extraction ([`extract_facts`]) and rendering ([`render_block`]) do no I/O:
+//! the caller supplies already-fetched `(role, content)` pairs in message
+//! order, and the same input always yields the same block. The CLI handler in
+//! `lib.rs` owns the database read and the file write.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Start of the managed block; everything between this and
+/// [`DISTILL_MARKER_END`] is overwritten on each run.
+pub const DISTILL_MARKER_BEGIN: &str = "<!-- cass:distill:begin -->";
+
+/// End of the managed block.
+pub const DISTILL_MARKER_END: &str = "<!-- cass:distill:end -->";
+
+/// Cap per [`FactKind`] so a long history cannot bloat the memory file past
+/// what an agent will actually read.
+const MAX_FACTS_PER_KIND: usize = 12;
+
+/// Memory-file dialect to write. Both are plain Markdown; they differ only in
+/// which filename the target agent looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryFormat {
+    /// `AGENTS.md` — the cross-agent convention.
+    AgentsMd,
+    /// `CLAUDE.md` — read by agents that follow the Claude memory convention.
+    ClaudeMd,
+}
+
+impl MemoryFormat {
+    /// Parse a `--format` value (`agents-md` / `claude-md`, separators
+    /// forgiven).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "agents-md" | "agents.md" | "agents" => Some(Self::AgentsMd),
+            "claude-md" | "claude.md" | "claude" => Some(Self::ClaudeMd),
+            _ => None,
+        }
+    }
+
+    /// File name the block is written to inside the workspace directory.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::AgentsMd => "AGENTS.md",
+            Self::ClaudeMd => "CLAUDE.md",
+        }
+    }
+
+    /// Stable label for structured output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::AgentsMd => "agents-md",
+            Self::ClaudeMd => "claude-md",
+        }
+    }
+}
+
+/// Category of a distilled fact; drives which section it renders under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FactKind {
+    /// A command line that was reported to work (build, test, install, ...).
+    SetupCommand,
+    /// A convention or rule the session agreed on.
+    Convention,
+    /// A pitfall or surprise worth warning the next session about.
+    Gotcha,
+}
+
+impl FactKind {
+    fn heading(&self) -> &'static str {
+        match self {
+            Self::SetupCommand => "Setup commands that worked",
+            Self::Convention => "Conventions",
+            Self::Gotcha => "Gotchas",
+        }
+    }
+}
+
+/// One durable fact mined from session history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DistilledFact {
+    /// Which section the fact belongs to.
+    pub kind: FactKind,
+    /// The fact itself, verbatim from the session (single line, trimmed).
+    pub text: String,
+}
+
+/// First tokens that mark a line as a tooling command worth remembering.
+const SETUP_TOOLS: &[&str] = &[
+    "cargo", "npm", "pnpm", "yarn", "bun", "make", "just", "pip", "uv", "go", "git", "docker",
+];
+
+/// Lowercase substrings that count as evidence a command in the same message
+/// actually worked.
+const SUCCESS_CUES: &[&str] = &[
+    "worked",
+    "works",
+    "passed",
+    "passing",
+    "succeeded",
+    "success",
+    "fixed",
+    "green",
+    "resolved",
+    "builds",
+    "compiles",
+];
+
+/// Lowercase substrings that mark a sentence as a convention/agreement.
+const CONVENTION_CUES: &[&str] = &[
+    "always ",
+    "never ",
+    "prefer ",
+    "convention",
+    "we agreed",
+    "agreed to",
+    "going forward",
+    "from now on",
+    "the rule is",
+];
+
+/// Lowercase substrings that mark a sentence as a pitfall warning.
+const GOTCHA_CUES: &[&str] = &[
+    "gotcha",
+    "watch out",
+    "be careful",
+    "pitfall",
+    "caveat",
+    "turns out",
+    "the catch",
+    "known issue",
+    "beware",
+    "heads up",
+];
+
+/// Mine durable facts from `(role, content)` pairs in message order.
+///
+/// Heuristics, not NLP: a setup command is a line whose first token is a known
+/// tool, in a message that also contains a success cue (so proposed-but-untried
+/// commands stay out); conventions and gotchas are single lines from user or
+/// agent messages containing the respective cue phrases. Facts are deduped
+/// case-insensitively and capped at [`MAX_FACTS_PER_KIND`] per kind, keeping
+/// first occurrence order so the output is deterministic.
+pub fn extract_facts(messages: &[(String, String)]) -> Vec<DistilledFact> {
+    let mut facts: Vec<DistilledFact> = Vec::new();
+    let mut seen: BTreeSet<(u8, String)> = BTreeSet::new();
+    let mut counts = [0usize; 3];
+
+    let mut push = |kind: FactKind, text: String, facts: &mut Vec<DistilledFact>| {
+        let slot = kind as u8;
+        if counts[slot as usize] >= MAX_FACTS_PER_KIND {
+            return;
+        }
+        let key = (
+            slot,
+            text.split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase(),
+        );
+        if key.1.is_empty() || !seen.insert(key) {
+            return;
+        }
+        counts[slot as usize] += 1;
+        facts.push(DistilledFact { kind, text });
+    };
+
+    for (role, content) in messages {
+        let lower = content.to_lowercase();
+        let conversational = matches!(role.as_str(), "user" | "agent" | "assistant");
+        let has_success_cue = SUCCESS_CUES.iter().any(|cue| lower.contains(cue));
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.len() > 240 {
+                continue;
+            }
+
+            if has_success_cue && let Some(command) = setup_command_from_line(line) {
+                push(FactKind::SetupCommand, command, &mut facts);
+                continue;
+            }
+
+            if !conversational {
+                continue;
+            }
+            let line_lower = line.to_lowercase();
+            let sentence = line.trim_start_matches(['-', '*', ' ']).trim();
+            if sentence.len() < 16 {
+                continue;
+            }
+            if CONVENTION_CUES.iter().any(|cue| line_lower.contains(cue)) {
+                push(FactKind::Convention, sentence.to_string(), &mut facts);
+            } else if GOTCHA_CUES.iter().any(|cue| line_lower.contains(cue)) {
+                push(FactKind::Gotcha, sentence.to_string(), &mut facts);
+            }
+        }
+    }
+
+    facts.sort_by_key(|fact| fact.kind as u8);
+    facts
+}
+
+/// Recognize a line as a runnable tooling command, stripping shell-prompt
+/// (`$ `, `> `) and inline-code backtick dressing. Lines with `<placeholder>`
+/// args are rejected — they were never run as written.
+fn setup_command_from_line(line: &str) -> Option<String> {
+    let stripped = line
+        .trim_start_matches("$ ")
+        .trim_start_matches("> ")
+        .trim_matches('`')
+        .trim();
+    if stripped.len() > 120 || stripped.contains('<') {
+        return None;
+    }
+    let mut tokens = stripped.split_whitespace();
+    let first = tokens.next()?;
+    // Bare tool names ("cargo") are not commands; require an argument.
+    tokens.next()?;
+    SETUP_TOOLS.contains(&first).then(|| stripped.to_string())
+}
+
+/// Render the managed Markdown block (markers included) for a workspace.
+/// Empty kinds are skipped; commands render as inline code.
+pub fn render_block(workspace_label: &str, facts: &[DistilledFact]) -> String {
+    let mut out = String::new();
+    out.push_str(DISTILL_MARKER_BEGIN);
+    out.push_str("\n## Session memory (cass distill)\n\n");
+    out.push_str(&format!(
+        "Distilled from indexed sessions for `{workspace_label}`. Re-run \
+         `cass distill` to refresh; edits inside this block are overwritten.\n"
+    ));
+    for kind in [
+        FactKind::SetupCommand,
+        FactKind::Convention,
+        FactKind::Gotcha,
+    ] {
+        let section: Vec<&DistilledFact> = facts.iter().filter(|f| f.kind == kind).collect();
+        if section.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n### {}\n\n", kind.heading()));
+        for fact in section {
+            match kind {
+                FactKind::SetupCommand => out.push_str(&format!("- `{}`\n", fact.text)),
+                _ => out.push_str(&format!("- {}\n", fact.text)),
+            }
+        }
+    }
+    out.push('\n');
+    out.push_str(DISTILL_MARKER_END);
+    out
+}
+
+/// Replace the existing managed block in `existing`, or append one if no
+/// markers are present. Applying the same block twice is a no-op.
+pub fn upsert_block(existing: &str, block: &str) -> String {
+    let block = block.trim_end();
+    if let Some(begin) = existing.find(DISTILL_MARKER_BEGIN)
+        && let Some(end_start) = existing[begin..].find(DISTILL_MARKER_END)
+    {
+        let end = begin + end_start + DISTILL_MARKER_END.len();
+        let mut out = String::with_capacity(existing.len() + block.len());
+        out.push_str(&existing[..begin]);
+        out.push_str(block);
+        out.push_str(&existing[end..]);
+        return out;
+    }
+    if existing.trim().is_empty() {
+        return format!("{block}\n");
+    }
+    format!("{}\n\n{block}\n", existing.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msgs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(role, content)| (role.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn format_parse_accepts_common_spellings() {
+        assert_eq!(
+            MemoryFormat::parse("agents-md"),
+            Some(MemoryFormat::AgentsMd)
+        );
+        assert_eq!(
+            MemoryFormat::parse("AGENTS.md"),
+            Some(MemoryFormat::AgentsMd)
+        );
+        assert_eq!(
+            MemoryFormat::parse("claude_md"),
+            Some(MemoryFormat::ClaudeMd)
+        );
+        assert_eq!(MemoryFormat::parse("org-mode"), None);
+        assert_eq!(MemoryFormat::AgentsMd.file_name(), "AGENTS.md");
+        assert_eq!(MemoryFormat::ClaudeMd.file_name(), "CLAUDE.md");
+    }
+
+    #[test]
+    fn setup_commands_require_a_success_cue() {
+        let facts = extract_facts(&msgs(&[
+            ("agent", "Try this:\n$ cargo build --workspace"),
+            (
+                "agent",
+                "Ran it again:\ncargo test --workspace\nand everything passed.",
+            ),
+        ]));
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].kind, FactKind::SetupCommand);
+        assert_eq!(facts[0].text, "cargo test --workspace");
+    }
+
+    #[test]
+    fn placeholder_and_bare_tool_lines_are_not_commands() {
+        let facts = extract_facts(&msgs(&[(
+            "agent",
+            "That worked. Next time run:\ncargo\ngit push origin <branch>",
+        )]));
+        assert!(facts.is_empty());
+    }
+
+    #[test]
+    fn conventions_and_gotchas_come_from_conversational_roles_only() {
+        let facts = extract_facts(&msgs(&[
+            ("user", "We agreed to always run clippy before committing."),
+            (
+                "agent",
+                "Watch out: the indexer locks the db during full rebuilds.",
+            ),
+            (
+                "tool",
+                "we agreed this tool line should be ignored entirely",
+            ),
+        ]));
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0].kind, FactKind::Convention);
+        assert_eq!(facts[1].kind, FactKind::Gotcha);
+    }
+
+    #[test]
+    fn facts_dedupe_case_insensitively_and_cap_per_kind() {
+        let mut pairs = vec![
+            (
+                "user".to_string(),
+                "Always use spaces, not tabs.".to_string(),
+            ),
+            (
+                "user".to_string(),
+                "ALWAYS use spaces, not tabs.".to_string(),
+            ),
+        ];
+        for i in 0..20 {
+            pairs.push((
+                "user".to_string(),
+                format!("Always write doc comments on rule number {i}."),
+            ));
+        }
+        let facts = extract_facts(&pairs);
+        assert_eq!(facts.len(), MAX_FACTS_PER_KIND);
+        assert!(facts.iter().all(|f| f.kind == FactKind::Convention));
+    }
+
+    #[test]
+    fn render_skips_empty_sections_and_quotes_commands() {
+        let block = render_block(
+            "/home/u/proj",
+            &[DistilledFact {
+                kind: FactKind::SetupCommand,
+                text: "cargo build".to_string(),
+            }],
+        );
+        assert!(block.starts_with(DISTILL_MARKER_BEGIN));
+        assert!(block.ends_with(DISTILL_MARKER_END));
+        assert!(block.contains("- `cargo build`"));
+        assert!(block.contains("### Setup commands that worked"));
+        assert!(!block.contains("### Conventions"));
+        assert!(!block.contains("### Gotchas"));
+    }
+
+    #[test]
+    fn upsert_appends_once_then_replaces_in_place() {
+        let block_v1 = render_block("/w", &[]);
+        let first = upsert_block("# My project\n\nHand-written notes.\n", &block_v1);
+        assert!(first.starts_with("# My project"));
+        assert_eq!(first.matches(DISTILL_MARKER_BEGIN).count(), 1);
+
+        // Same block again: converges.
+        assert_eq!(upsert_block(&first, &block_v1), first);
+
+        // New block: replaced between markers, prose untouched.
+        let block_v2 = render_block(
+            "/w",
+            &[DistilledFact {
+                kind: FactKind::Gotcha,
+                text: "turns out the cache is per-branch".to_string(),
+            }],
+        );
+        let second = upsert_block(&first, &block_v2);
+        assert!(second.starts_with("# My project"));
+        assert!(second.contains("per-branch"));
+        assert_eq!(second.matches(DISTILL_MARKER_BEGIN).count(), 1);
+    }
+
+    #[test]
+    fn upsert_into_empty_file_is_just_the_block() {
+        let block = render_block("/w", &[]);
+        assert_eq!(upsert_block("", &block), format!("{block}\n"));
+    }
+}
@@ -11,6 +11,13 @@ use std::borrow::Cow;
 
 use crate::search::query::SearchHit;
 
+/// Provenance marker embedded near the top of every exported file so the
+/// indexer can recognize cass's own output and refuse to re-ingest it (the
+/// feedback-loop guard in `indexer::should_skip_self_output_source`). Kept
+/// format-agnostic: an HTML comment in Markdown, a `generator` field in JSON,
+/// a bracketed line in plain text — the sniffer only looks for the substring.
+pub const EXPORT_MARKER: &str = "cass:exported";
+
 /// Supported export formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ExportFormat {
@@ -151,7 +158,8 @@ fn markdown_code_span(content: &str) -> String {
 fn export_markdown(hits: &[SearchHit], options: &ExportOptions) -> String {
     let mut output = String::new();
 
-    // Header
+    // Header (the marker comment is invisible when rendered)
+    output.push_str(&format!("<!-- {EXPORT_MARKER} -->\n\n"));
     output.push_str("# Search Results\n\n");
 
     if let Some(query) = &options.query {
@@ -257,6 +265,7 @@ fn export_json_value(
     exported_at: &str,
 ) -> serde_json::Value {
     serde_json::json!({
+        "generator": EXPORT_MARKER,
         "query": options.query,
         "count": hits.len(),
         "exported_at": exported_at,
@@ -324,6 +333,7 @@ fn export_plain_text(hits: &[SearchHit], options: &ExportOptions) -> String {
 
     // Header
     output.push_str("SEARCH RESULTS\n");
+    output.push_str(&format!("[{EXPORT_MARKER}]\n"));
     output.push_str(&"=".repeat(60));
     output.push('\n');
 
@@ -501,6 +511,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn every_format_carries_the_export_marker() {
+        let hits = vec![sample_hit()];
+        let options = ExportOptions::default();
+        for format in ExportFormat::all() {
+            let output = export_results(&hits, *format, &options);
+            assert!(
+                output.contains(EXPORT_MARKER),
+                "{} export must embed the self-output marker",
+                format.name()
+            );
+        }
+    }
+
     #[test]
     fn test_export_json() {
         let hits = vec![sample_hit()];
@@ -524,6 +548,7 @@ mod tests {
         assert_eq!(
             projected,
             serde_json::json!({
+                "generator": EXPORT_MARKER,
                 "query": "authentication error",
                 "count": 1,
                 "exported_at": "2026-04-26T17:26:00Z",
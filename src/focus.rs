@@ -0,0 +1,194 @@
+//! Soft real-time indexing priority for one "focused" workspace.
+//!
+//! `cass focus ~/dev/foo` writes a small JSON control file under the data
+//! dir, exactly like `watch_control`. The watch loop polls it on the same
+//! cadence as pause/resume: pending session files that belong to the focused
+//! workspace skip the scan cooldown once the debounce settles, so an
+//! in-progress session becomes searchable within seconds even while a bulk
+//! backfill of old history owns the regular cycle. Everything else stays on
+//! the normal schedule — focus never starves the backfill, it only jumps the
+//! queue.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Focus control-file name under the data dir.
+const FOCUS_FILE: &str = "focus_control.json";
+
+/// Operator-requested focus, written by `cass focus`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FocusControl {
+    /// Absolute workspace path whose sessions get priority indexing.
+    /// `None` means no focus: all workspaces share the normal schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Whether the focus came from `--auto` (most recently active workspace)
+    /// rather than an explicit path, echoed back by `cass focus` with no args.
+    #[serde(default)]
+    pub auto_detected: bool,
+    /// When the request was written (unix millis).
+    #[serde(default)]
+    pub set_at_ms: i64,
+}
+
+/// Path of the focus control file under `data_dir`.
+#[must_use]
+pub fn focus_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(FOCUS_FILE)
+}
+
+/// Read the current focus. A missing or unparseable file means "no focus" —
+/// like the watch control file, this is advisory, never load-bearing.
+#[must_use]
+pub fn load_focus(data_dir: &Path) -> FocusControl {
+    std::fs::read_to_string(focus_path(data_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Write a focus request for the watch loop. `None` clears the focus.
+pub fn write_focus(data_dir: &Path, workspace: Option<&Path>, auto_detected: bool) -> Result<()> {
+    let control = FocusControl {
+        workspace: workspace.map(|p| p.to_string_lossy().into_owned()),
+        auto_detected,
+        set_at_ms: chrono::Utc::now().timestamp_millis(),
+    };
+    let path = focus_path(data_dir);
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("creating data dir {}", data_dir.display()))?;
+    std::fs::write(&path, serde_json::to_string_pretty(&control)?)
+        .with_context(|| format!("writing focus control file {}", path.display()))?;
+    Ok(())
+}
+
+/// True when a watched session file plausibly belongs to `workspace`.
+///
+/// Agent session stores rarely live inside the workspace itself, so this
+/// matches three layouts:
+/// - session files stored under the workspace tree;
+/// - stores that encode the absolute workspace path in a directory name by
+///   dashing separators (Claude Code's `~/.claude/projects/-home-u-dev-foo`);
+/// - stores that use the workspace's final component as a directory name.
+///
+/// False positives only promote a file ahead of the cooldown — the file was
+/// going to be indexed anyway — so the heuristic errs on the generous side.
+#[must_use]
+pub fn session_path_matches_workspace(session_path: &Path, workspace: &Path) -> bool {
+    if session_path.starts_with(workspace) {
+        return true;
+    }
+    let encoded = dash_encode(workspace);
+    let workspace_name = workspace.file_name().and_then(|name| name.to_str());
+    session_path.components().any(|component| {
+        let Some(component) = component.as_os_str().to_str() else {
+            return false;
+        };
+        if !encoded.is_empty() && component.eq_ignore_ascii_case(&encoded) {
+            return true;
+        }
+        workspace_name.is_some_and(|name| component.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Stable-partition `pending` so focused paths land first in a mixed flush.
+/// Relative order within each group is preserved.
+pub fn order_focused_first(pending: &mut Vec<PathBuf>, workspace: &Path) {
+    let (mut focused, rest): (Vec<PathBuf>, Vec<PathBuf>) = std::mem::take(pending)
+        .into_iter()
+        .partition(|path| session_path_matches_workspace(path, workspace));
+    focused.extend(rest);
+    *pending = focused;
+}
+
+/// Dash-encode an absolute path the way Claude Code names its per-project
+/// session directories: every separator-ish character becomes `-`.
+fn dash_encode(path: &Path) -> String {
+    path.to_string_lossy()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | '.' | '_' | ':' | ' ' => '-',
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn focus_round_trips_and_defaults_to_unfocused() {
+        let dir = tempdir().unwrap();
+        assert!(load_focus(dir.path()).workspace.is_none());
+
+        write_focus(dir.path(), Some(Path::new("/home/u/dev/foo")), false).unwrap();
+        let control = load_focus(dir.path());
+        assert_eq!(control.workspace.as_deref(), Some("/home/u/dev/foo"));
+        assert!(!control.auto_detected);
+
+        write_focus(dir.path(), None, false).unwrap();
+        assert!(load_focus(dir.path()).workspace.is_none());
+    }
+
+    #[test]
+    fn corrupt_focus_file_means_no_focus() {
+        let dir = tempdir().unwrap();
+        std::fs::write(focus_path(dir.path()), "{not json").unwrap();
+        assert!(load_focus(dir.path()).workspace.is_none());
+    }
+
+    #[test]
+    fn matches_sessions_stored_inside_the_workspace() {
+        assert!(session_path_matches_workspace(
+            Path::new("/home/u/dev/foo/.aider.chat.history.md"),
+            Path::new("/home/u/dev/foo"),
+        ));
+        assert!(!session_path_matches_workspace(
+            Path::new("/home/u/dev/bar/.aider.chat.history.md"),
+            Path::new("/home/u/dev/foo"),
+        ));
+    }
+
+    #[test]
+    fn matches_claude_style_dash_encoded_project_dirs() {
+        assert!(session_path_matches_workspace(
+            Path::new("/home/u/.claude/projects/-home-u-dev-foo/abc123.jsonl"),
+            Path::new("/home/u/dev/foo"),
+        ));
+        assert!(!session_path_matches_workspace(
+            Path::new("/home/u/.claude/projects/-home-u-dev-foobar/abc123.jsonl"),
+            Path::new("/home/u/dev/foo"),
+        ));
+    }
+
+    #[test]
+    fn matches_workspace_name_as_store_directory() {
+        assert!(session_path_matches_workspace(
+            Path::new("/home/u/.some-agent/sessions/foo/2024-01-01.json"),
+            Path::new("/home/u/dev/foo"),
+        ));
+    }
+
+    #[test]
+    fn order_focused_first_is_a_stable_partition() {
+        let mut pending = vec![
+            PathBuf::from("/home/u/.claude/projects/-home-u-dev-bar/a.jsonl"),
+            PathBuf::from("/home/u/dev/foo/session-1.md"),
+            PathBuf::from("/home/u/.claude/projects/-home-u-dev-bar/b.jsonl"),
+            PathBuf::from("/home/u/dev/foo/session-2.md"),
+        ];
+        order_focused_first(&mut pending, Path::new("/home/u/dev/foo"));
+        assert_eq!(
+            pending,
+            vec![
+                PathBuf::from("/home/u/dev/foo/session-1.md"),
+                PathBuf::from("/home/u/dev/foo/session-2.md"),
+                PathBuf::from("/home/u/.claude/projects/-home-u-dev-bar/a.jsonl"),
+                PathBuf::from("/home/u/.claude/projects/-home-u-dev-bar/b.jsonl"),
+            ]
+        );
+    }
+}
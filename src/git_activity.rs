@@ -0,0 +1,223 @@
+//! Workspace recency boost from local git activity.
+//!
+//! "Projects I committed to this week" is a strong prior on what a search is
+//! about, so this module lifts hits whose workspace is a git repo with a
+//! recent local commit. Activity is detected by reading the repository's
+//! HEAD reflog (`.git/logs/HEAD`) directly — no `git` subprocess, no lock
+//! taken, nothing written — and only for workspaces that actually appear in
+//! the result set, so a search never stats repos it did not surface anyway.
+//!
+//! The boost is **opt-in**: reading reflogs reveals commit times, which a
+//! privacy-sensitive setup may not want a search tool to touch. Enable it
+//! with `git_recency_boost = true` under `[search]` in
+//! `~/.config/cass/cass.toml`, or `CASS_GIT_RECENCY_BOOST=1` (env wins, `0`
+//! there disables a config-file `true`). When enabled, hits from workspaces
+//! with a commit in the last [`ACTIVE_WINDOW_DAYS`] days get a fixed
+//! multiplicative lift and the page is re-sorted; the user ranking script
+//! and explicit `--sort`/`--group-by` still run afterwards, so both can
+//! override it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::search::query::SearchHit;
+
+/// How recent a commit must be for its workspace to count as active.
+pub const ACTIVE_WINDOW_DAYS: u64 = 7;
+
+/// Multiplicative score lift for hits from active workspaces. Deliberately
+/// mild — a strong prior, not a filter — so relevant hits from dormant
+/// projects still surface above weak hits from busy ones.
+const BOOST_FACTOR: f32 = 1.15;
+
+/// Whether the recency boost is enabled: `CASS_GIT_RECENCY_BOOST`, then
+/// `[search] git_recency_boost`, default off.
+#[must_use]
+pub fn boost_enabled() -> bool {
+    boost_enabled_from_parts(
+        dotenvy::var("CASS_GIT_RECENCY_BOOST").ok(),
+        crate::search_defaults::load_search_defaults()
+            .ok()
+            .and_then(|defaults| defaults.git_recency_boost),
+    )
+}
+
+/// Pure resolution against already-read env/config values, mirroring the
+/// testability contract of `search_defaults::resolve_*`.
+#[must_use]
+pub fn boost_enabled_from_parts(env: Option<String>, config: Option<bool>) -> bool {
+    if let Some(raw) = env {
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            return trimmed == "1" || trimmed.eq_ignore_ascii_case("true");
+        }
+    }
+    config.unwrap_or(false)
+}
+
+/// Unix-millis timestamp of the most recent HEAD reflog entry of the git
+/// repo at `workspace`, or `None` when the workspace is not a repo, has no
+/// reflog, or the reflog tail does not parse. Handles both a `.git`
+/// directory and the `gitdir:` pointer file worktrees and submodules use.
+#[must_use]
+pub fn last_reflog_commit_ms(workspace: &Path) -> Option<i64> {
+    let git_dir = resolve_git_dir(workspace)?;
+    let reflog = std::fs::read_to_string(git_dir.join("logs").join("HEAD")).ok()?;
+    let last = reflog.lines().rev().find(|line| !line.trim().is_empty())?;
+    // Reflog line shape: `<old> <new> <ident> <email> <epoch> <tz>\t<msg>`.
+    let header = last.split('\t').next()?;
+    let mut fields = header.split_whitespace().rev();
+    let _tz = fields.next()?;
+    let epoch: i64 = fields.next()?.parse().ok()?;
+    Some(epoch.saturating_mul(1000))
+}
+
+/// Resolve `workspace`'s git directory: a `.git` directory as-is, or the
+/// target of a `gitdir: <path>` pointer file (relative paths resolved
+/// against the workspace).
+fn resolve_git_dir(workspace: &Path) -> Option<PathBuf> {
+    let dot_git = workspace.join(".git");
+    let meta = std::fs::symlink_metadata(&dot_git).ok()?;
+    if meta.is_dir() {
+        return Some(dot_git);
+    }
+    let pointer = std::fs::read_to_string(&dot_git).ok()?;
+    let target = pointer.strip_prefix("gitdir:")?.trim();
+    if target.is_empty() {
+        return None;
+    }
+    let target = PathBuf::from(target);
+    Some(if target.is_absolute() {
+        target
+    } else {
+        workspace.join(target)
+    })
+}
+
+/// Lift hits from workspaces with a reflog entry in the last `window_days`
+/// days and re-sort the page by score. Each distinct workspace is inspected
+/// once per call; workspaces that are not git repos cost one failed stat.
+pub fn apply_recency_boost(hits: &mut [SearchHit], window_days: u64, now_ms: i64) {
+    let cutoff_ms = now_ms.saturating_sub((window_days as i64).saturating_mul(24 * 3600 * 1000));
+    let mut active_by_workspace: HashMap<String, bool> = HashMap::new();
+    let mut boosted = 0usize;
+    for hit in hits.iter_mut() {
+        if hit.workspace.is_empty() {
+            continue;
+        }
+        let active = *active_by_workspace
+            .entry(hit.workspace.clone())
+            .or_insert_with(|| {
+                last_reflog_commit_ms(Path::new(&hit.workspace))
+                    .is_some_and(|last_ms| last_ms >= cutoff_ms)
+            });
+        if active {
+            hit.score *= BOOST_FACTOR;
+            boosted += 1;
+        }
+    }
+    if boosted == 0 {
+        return;
+    }
+    tracing::debug!(
+        boosted,
+        workspaces = active_by_workspace.values().filter(|a| **a).count(),
+        "applied git recency boost"
+    );
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(workspace: &str, score: f32) -> SearchHit {
+        SearchHit {
+            title: "t".into(),
+            snippet: String::new(),
+            content: String::new(),
+            content_hash: 0,
+            conversation_id: None,
+            score,
+            source_path: "/tmp/s.jsonl".into(),
+            agent: "claude_code".into(),
+            workspace: workspace.into(),
+            workspace_original: None,
+            created_at: None,
+            line_number: None,
+            match_type: Default::default(),
+            source_id: "local".into(),
+            origin_kind: "local".into(),
+            origin_host: None,
+        }
+    }
+
+    fn seed_repo_with_reflog(root: &Path, epoch_secs: i64) {
+        let logs = root.join(".git").join("logs");
+        std::fs::create_dir_all(&logs).unwrap();
+        std::fs::write(
+            logs.join("HEAD"),
+            format!(
+                "{0} {1} Dev Eloper <dev@example.com> {epoch_secs} +0000\tcommit: tweak\n",
+                "0".repeat(40),
+                "1".repeat(40),
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn flag_resolution_env_beats_config_and_defaults_off() {
+        assert!(!boost_enabled_from_parts(None, None));
+        assert!(boost_enabled_from_parts(None, Some(true)));
+        assert!(boost_enabled_from_parts(Some("1".into()), None));
+        assert!(!boost_enabled_from_parts(Some("0".into()), Some(true)));
+        assert!(boost_enabled_from_parts(Some("  ".into()), Some(true)));
+    }
+
+    #[test]
+    fn reflog_tail_timestamp_is_read_without_git() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        seed_repo_with_reflog(tmp.path(), 1_700_000_123);
+        assert_eq!(last_reflog_commit_ms(tmp.path()), Some(1_700_000_123_000));
+
+        // A worktree-style `.git` pointer file resolves to the real gitdir.
+        let real = tmp.path().join("real-gitdir");
+        std::fs::create_dir_all(real.join("logs")).unwrap();
+        std::fs::rename(
+            tmp.path().join(".git").join("logs").join("HEAD"),
+            real.join("logs").join("HEAD"),
+        )
+        .unwrap();
+        let worktree = tmp.path().join("wt");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: ../real-gitdir\n").unwrap();
+        assert_eq!(last_reflog_commit_ms(&worktree), Some(1_700_000_123_000));
+
+        assert_eq!(last_reflog_commit_ms(&tmp.path().join("not-a-repo")), None);
+    }
+
+    #[test]
+    fn boost_lifts_active_workspaces_and_resorts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let active = tmp.path().join("active");
+        let dormant = tmp.path().join("dormant");
+        let now_ms = 1_700_000_000_000;
+        seed_repo_with_reflog(&active, now_ms / 1000 - 3600);
+        seed_repo_with_reflog(&dormant, now_ms / 1000 - 90 * 24 * 3600);
+
+        let mut hits = vec![
+            hit(dormant.to_str().unwrap(), 1.0),
+            hit(active.to_str().unwrap(), 0.95),
+            hit("", 0.9),
+        ];
+        apply_recency_boost(&mut hits, ACTIVE_WINDOW_DAYS, now_ms);
+        assert_eq!(hits[0].workspace, active.to_str().unwrap());
+        assert!((hits[0].score - 0.95 * 1.15).abs() < 1e-6);
+        assert_eq!(hits[1].score, 1.0, "dormant workspace is untouched");
+    }
+}
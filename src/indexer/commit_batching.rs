@@ -0,0 +1,337 @@
+//! Histogram-driven adaptive commit batching for the SQLite writer.
+//!
+//! The serial batched ingest path groups conversations into chunks and
+//! commits one chunk per transaction. A fixed chunk size is a bad fit for
+//! real corpora: message counts per conversation are wildly skewed (a few
+//! thousand-message agent marathons among thousands of ten-message chats),
+//! so a conversation-count chunk either thrashes WAL with tiny commits or
+//! stalls the pipeline behind one enormous transaction.
+//!
+//! [`AdaptiveCommitBatcher`] plans chunks by *message* budget instead and
+//! adapts that budget from observed commit latency, AIMD-style:
+//!
+//! * commits landing under the fast watermark several times in a row grow
+//!   the budget (multiplicative, capped) — the writer has headroom;
+//! * a commit over the slow watermark halves the budget immediately
+//!   (bounded below) — the writer is the bottleneck and smaller commits
+//!   keep progress visible and WAL checkpoints cheap.
+//!
+//! Every commit is also recorded in a log₂ duration histogram, exposed via
+//! [`AdaptiveCommitBatcher::metrics`] and logged as a one-line summary per
+//! persist batch, so a slow backfill shows *where* commit time went rather
+//! than just how long it took.
+
+use std::time::Duration;
+
+/// Default target commit latency in milliseconds. Commits faster than a
+/// quarter of this grow the budget; commits slower than double it shrink.
+const DEFAULT_TARGET_COMMIT_MS: u64 = 300;
+
+/// Initial message budget per commit. Matches the streaming batch limit so
+/// the first commit of a run behaves like the previous fixed sizing.
+const DEFAULT_INITIAL_MESSAGE_BUDGET: usize = 2_000;
+
+/// Floor for the adaptive message budget. Below this, per-transaction
+/// overhead dominates and shrinking further cannot help.
+const MIN_MESSAGE_BUDGET: usize = 128;
+
+/// Default ceiling for the adaptive message budget.
+const DEFAULT_MAX_MESSAGE_BUDGET: usize = 16_000;
+
+/// Consecutive fast commits required before the budget grows. Mirrors the
+/// responsiveness governor's hysteresis: shrink fast, grow cautiously.
+const GROWTH_CONSECUTIVE_FAST_COMMITS: u32 = 2;
+
+/// Number of log₂ histogram buckets: `<1ms, <2ms, <4ms, ... <4.1s, rest`.
+const HISTOGRAM_BUCKETS: usize = 14;
+
+/// Log₂-bucketed commit-duration histogram.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CommitDurationHistogram {
+    counts: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl CommitDurationHistogram {
+    /// Bucket index for a duration: bucket `i` holds commits in
+    /// `[2^(i-1), 2^i)` milliseconds, with the last bucket open-ended.
+    fn bucket_index(duration: Duration) -> usize {
+        let ms = duration.as_millis().max(1) as u64;
+        let index = (64 - ms.leading_zeros()) as usize;
+        index.min(HISTOGRAM_BUCKETS - 1)
+    }
+
+    fn record(&mut self, duration: Duration) {
+        self.counts[Self::bucket_index(duration)] += 1;
+    }
+
+    /// Render as `"<1ms:3 <8ms:12 >8.1s:1"`, skipping empty buckets. Compact
+    /// enough for a single tracing field, precise enough to spot bimodality.
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        for (index, &count) in self.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let upper_ms = 1u64 << index;
+            if index == HISTOGRAM_BUCKETS - 1 {
+                parts.push(format!(">={}ms:{count}", upper_ms >> 1));
+            } else {
+                parts.push(format!("<{upper_ms}ms:{count}"));
+            }
+        }
+        if parts.is_empty() {
+            "empty".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    pub(crate) fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// Commit-batch metrics accumulated over one persist run.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommitBatchMetrics {
+    /// Transactions committed.
+    pub commits: u64,
+    /// Conversations persisted across all commits.
+    pub conversations: u64,
+    /// Messages persisted across all commits.
+    pub messages: u64,
+    /// Wall time spent inside commits.
+    pub total_commit: Duration,
+    /// Slowest single commit.
+    pub max_commit: Duration,
+    /// Commit-duration histogram (log₂ millisecond buckets).
+    pub histogram: CommitDurationHistogram,
+    /// Message budget after the final adaptation step.
+    pub final_message_budget: usize,
+}
+
+/// Adaptive chunk planner for the serial batched SQLite writer.
+#[derive(Debug)]
+pub(crate) struct AdaptiveCommitBatcher {
+    message_budget: usize,
+    max_message_budget: usize,
+    target_commit: Duration,
+    consecutive_fast: u32,
+    metrics: CommitBatchMetrics,
+}
+
+impl AdaptiveCommitBatcher {
+    /// Build a batcher from env-tunable thresholds.
+    ///
+    /// `CASS_COMMIT_BATCH_TARGET_MS` moves the latency watermarks;
+    /// `CASS_COMMIT_BATCH_MAX_MESSAGES` caps how far the budget can grow.
+    pub(crate) fn from_env() -> Self {
+        let target_ms = dotenvy::var("CASS_COMMIT_BATCH_TARGET_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TARGET_COMMIT_MS);
+        let max_budget = dotenvy::var("CASS_COMMIT_BATCH_MAX_MESSAGES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|v| *v >= MIN_MESSAGE_BUDGET)
+            .unwrap_or(DEFAULT_MAX_MESSAGE_BUDGET);
+        Self::new(Duration::from_millis(target_ms), max_budget)
+    }
+
+    pub(crate) fn new(target_commit: Duration, max_message_budget: usize) -> Self {
+        let max_message_budget = max_message_budget.max(MIN_MESSAGE_BUDGET);
+        Self {
+            message_budget: DEFAULT_INITIAL_MESSAGE_BUDGET.min(max_message_budget),
+            max_message_budget,
+            target_commit,
+            consecutive_fast: 0,
+            metrics: CommitBatchMetrics::default(),
+        }
+    }
+
+    /// Current per-commit message budget.
+    pub(crate) fn message_budget(&self) -> usize {
+        self.message_budget
+    }
+
+    /// Plan the exclusive end of the next chunk starting at `start`.
+    ///
+    /// Greedily extends the chunk while it stays within the message budget
+    /// and `max_conversations`. Always admits at least one conversation, so
+    /// a single conversation larger than the whole budget still commits
+    /// (alone) rather than wedging the pipeline.
+    pub(crate) fn plan_chunk_end(
+        &self,
+        message_counts: &[usize],
+        start: usize,
+        max_conversations: usize,
+    ) -> usize {
+        debug_assert!(start < message_counts.len());
+        let max_conversations = max_conversations.max(1);
+        let mut end = start + 1;
+        let mut messages = message_counts[start];
+        while end < message_counts.len()
+            && end - start < max_conversations
+            && messages.saturating_add(message_counts[end]) <= self.message_budget
+        {
+            messages += message_counts[end];
+            end += 1;
+        }
+        end
+    }
+
+    /// Record a committed chunk and adapt the budget from its latency.
+    pub(crate) fn record_commit(
+        &mut self,
+        conversations: usize,
+        messages: usize,
+        duration: Duration,
+    ) {
+        self.metrics.commits += 1;
+        self.metrics.conversations += conversations as u64;
+        self.metrics.messages += messages as u64;
+        self.metrics.total_commit += duration;
+        self.metrics.max_commit = self.metrics.max_commit.max(duration);
+        self.metrics.histogram.record(duration);
+
+        let fast_watermark = self.target_commit / 4;
+        let slow_watermark = self.target_commit * 2;
+        if duration > slow_watermark {
+            self.consecutive_fast = 0;
+            self.message_budget = (self.message_budget / 2).max(MIN_MESSAGE_BUDGET);
+        } else if duration < fast_watermark {
+            // Only grow when the budget was actually the binding constraint:
+            // a fast commit of a half-empty final chunk says nothing about
+            // writer headroom at the current budget.
+            if messages * 2 >= self.message_budget {
+                self.consecutive_fast += 1;
+                if self.consecutive_fast >= GROWTH_CONSECUTIVE_FAST_COMMITS {
+                    self.consecutive_fast = 0;
+                    self.message_budget =
+                        (self.message_budget.saturating_mul(2)).min(self.max_message_budget);
+                }
+            }
+        } else {
+            self.consecutive_fast = 0;
+        }
+        self.metrics.final_message_budget = self.message_budget;
+    }
+
+    /// Metrics accumulated so far.
+    pub(crate) fn metrics(&self) -> &CommitBatchMetrics {
+        &self.metrics
+    }
+
+    /// Emit the one-line commit-batch summary for this persist run.
+    pub(crate) fn log_summary(&self, context: &str) {
+        let metrics = self.metrics();
+        if metrics.commits == 0 {
+            return;
+        }
+        tracing::info!(
+            target: "cass::indexer::commit_batch",
+            context,
+            commits = metrics.commits,
+            conversations = metrics.conversations,
+            messages = metrics.messages,
+            total_commit_ms = metrics.total_commit.as_millis() as u64,
+            max_commit_ms = metrics.max_commit.as_millis() as u64,
+            final_message_budget = metrics.final_message_budget,
+            histogram = %metrics.histogram.summary(),
+            "adaptive commit batching summary"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_log2_ms() {
+        assert_eq!(
+            CommitDurationHistogram::bucket_index(Duration::from_micros(200)),
+            1,
+            "sub-millisecond commits land in the <2ms bucket (clamped to 1ms)"
+        );
+        assert_eq!(
+            CommitDurationHistogram::bucket_index(Duration::from_millis(1)),
+            1
+        );
+        assert_eq!(
+            CommitDurationHistogram::bucket_index(Duration::from_millis(3)),
+            2
+        );
+        assert_eq!(
+            CommitDurationHistogram::bucket_index(Duration::from_secs(60)),
+            HISTOGRAM_BUCKETS - 1,
+            "outliers collapse into the open-ended last bucket"
+        );
+
+        let mut histogram = CommitDurationHistogram::default();
+        histogram.record(Duration::from_millis(3));
+        histogram.record(Duration::from_millis(3));
+        histogram.record(Duration::from_secs(60));
+        assert_eq!(histogram.total(), 3);
+        assert_eq!(histogram.summary(), "<4ms:2 >=4096ms:1");
+    }
+
+    #[test]
+    fn chunks_group_small_conversations_and_isolate_giants() {
+        let batcher = AdaptiveCommitBatcher::new(Duration::from_millis(300), 16_000);
+        // 10 + 20 + 30 fit the 2000-message budget together.
+        let counts = [10, 20, 30, 5_000, 40];
+        assert_eq!(batcher.plan_chunk_end(&counts, 0, 64), 3);
+        // A single conversation over budget still gets its own commit.
+        assert_eq!(batcher.plan_chunk_end(&counts, 3, 64), 4);
+        assert_eq!(batcher.plan_chunk_end(&counts, 4, 64), 5);
+        // The conversation cap still applies.
+        assert_eq!(batcher.plan_chunk_end(&[1, 1, 1, 1], 0, 2), 2);
+    }
+
+    #[test]
+    fn budget_grows_after_consecutive_fast_full_commits() {
+        let mut batcher = AdaptiveCommitBatcher::new(Duration::from_millis(300), 16_000);
+        let full = batcher.message_budget();
+        batcher.record_commit(8, full, Duration::from_millis(10));
+        assert_eq!(batcher.message_budget(), full, "one fast commit is noise");
+        batcher.record_commit(8, full, Duration::from_millis(10));
+        assert_eq!(batcher.message_budget(), full * 2);
+
+        // Fast but half-empty commits must not grow the budget.
+        let mut lazy = AdaptiveCommitBatcher::new(Duration::from_millis(300), 16_000);
+        let initial = lazy.message_budget();
+        for _ in 0..4 {
+            lazy.record_commit(1, 10, Duration::from_millis(1));
+        }
+        assert_eq!(lazy.message_budget(), initial);
+    }
+
+    #[test]
+    fn budget_halves_immediately_on_slow_commit_and_respects_floor() {
+        let mut batcher = AdaptiveCommitBatcher::new(Duration::from_millis(300), 16_000);
+        let initial = batcher.message_budget();
+        batcher.record_commit(8, initial, Duration::from_secs(2));
+        assert_eq!(batcher.message_budget(), initial / 2);
+
+        for _ in 0..20 {
+            batcher.record_commit(8, batcher.message_budget(), Duration::from_secs(2));
+        }
+        assert_eq!(batcher.message_budget(), MIN_MESSAGE_BUDGET);
+    }
+
+    #[test]
+    fn metrics_accumulate_across_commits() {
+        let mut batcher = AdaptiveCommitBatcher::new(Duration::from_millis(300), 16_000);
+        batcher.record_commit(3, 120, Duration::from_millis(40));
+        batcher.record_commit(2, 80, Duration::from_millis(700));
+        let metrics = batcher.metrics();
+        assert_eq!(metrics.commits, 2);
+        assert_eq!(metrics.conversations, 5);
+        assert_eq!(metrics.messages, 200);
+        assert_eq!(metrics.max_commit, Duration::from_millis(700));
+        assert_eq!(metrics.histogram.total(), 2);
+        assert_eq!(metrics.final_message_budget, batcher.message_budget());
+    }
+}
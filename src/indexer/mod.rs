@@ -1,6 +1,9 @@
+pub(crate) mod commit_batching;
 pub(crate) mod lexical_generation;
 pub(crate) mod memoization;
+pub(crate) mod ocr;
 pub(crate) mod parallel_wal_shadow;
+pub(crate) mod plan_artifacts;
 pub mod quarantine;
 pub mod quarantine_retry;
 pub mod redact_secrets;
@@ -303,6 +306,127 @@ mod subagent_skip_tests {
     }
 }
 
+/// Whether `CASS_INDEX_SELF_OUTPUT` is enabled. The self-output guard below
+/// is on by default; this override re-admits cass's own files for the brave
+/// (or for tests that deliberately index an exported corpus). `0`/`false`/
+/// empty leaves the guard on.
+fn index_self_output_active() -> bool {
+    dotenvy::var("CASS_INDEX_SELF_OUTPUT")
+        .ok()
+        .map(|raw| {
+            let value = raw.trim();
+            !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false"))
+        })
+        .unwrap_or(false)
+}
+
+/// Bytes sniffed from the head of a source file when checking for the export
+/// marker; every exporter places it within the first few lines.
+const SELF_OUTPUT_MARKER_SNIFF_BYTES: usize = 4096;
+
+/// True when `source_path` is cass's own output: anything under the cass data
+/// dir (the canonical DB, backups, slices, raw mirror), a copy of the database
+/// file itself, or an exported file carrying [`crate::export::EXPORT_MARKER`].
+/// Exports default to user-chosen directories (Downloads), so they are
+/// recognized by the embedded marker rather than by location.
+fn conversation_source_is_self_output(data_dir: &Path, source_path: &Path) -> bool {
+    if source_path.starts_with(data_dir) {
+        return true;
+    }
+    if source_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("agent_search.db"))
+    {
+        return true;
+    }
+    source_head_has_export_marker(source_path)
+}
+
+/// Check the first [`SELF_OUTPUT_MARKER_SNIFF_BYTES`] of a file for the
+/// export marker. Unreadable files are not treated as self-output — the
+/// connector's own parsing will surface real errors.
+fn source_head_has_export_marker(source_path: &Path) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = fs::File::open(source_path) else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(SELF_OUTPUT_MARKER_SNIFF_BYTES);
+    if file
+        .take(SELF_OUTPUT_MARKER_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)
+        .is_err()
+    {
+        return false;
+    }
+    String::from_utf8_lossy(&head).contains(crate::export::EXPORT_MARKER)
+}
+
+/// Scan-time feedback-loop guard: drop cass's own database and exported
+/// files before they are ingested, so re-running `cass index` over a
+/// workspace that contains exported Markdown (or the DB itself) cannot
+/// re-ingest our own output. Applied at every connector scan callback;
+/// `CASS_INDEX_SELF_OUTPUT=1` disables it.
+fn should_skip_self_output_source(data_dir: &Path, source_path: &Path) -> bool {
+    if index_self_output_active() {
+        return false;
+    }
+    if !conversation_source_is_self_output(data_dir, source_path) {
+        return false;
+    }
+    tracing::info!(
+        source_path = %source_path.display(),
+        "skipping session source that is cass's own output"
+    );
+    true
+}
+
+#[cfg(test)]
+mod self_output_skip_tests {
+    use super::conversation_source_is_self_output;
+    use std::path::Path;
+
+    #[test]
+    fn data_dir_contents_and_db_copies_are_self_output() {
+        let data_dir = Path::new("/home/u/.local/share/cass");
+        assert!(conversation_source_is_self_output(
+            data_dir,
+            Path::new("/home/u/.local/share/cass/agent_search.db")
+        ));
+        assert!(conversation_source_is_self_output(
+            data_dir,
+            Path::new("/home/u/.local/share/cass/raw_mirror/blob.jsonl")
+        ));
+        // A stray DB copy outside the data dir still matches by name.
+        assert!(conversation_source_is_self_output(
+            data_dir,
+            Path::new("/home/u/dev/foo/agent_search.db-wal")
+        ));
+    }
+
+    #[test]
+    fn marker_in_file_head_flags_exports() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let exported = dir.path().join("results.md");
+        std::fs::write(
+            &exported,
+            format!(
+                "<!-- {} -->\n\n# Search Results\n",
+                crate::export::EXPORT_MARKER
+            ),
+        )
+        .expect("write export");
+        let session = dir.path().join("session.jsonl");
+        std::fs::write(&session, "{\"role\":\"user\",\"content\":\"hi\"}\n")
+            .expect("write session");
+
+        let data_dir = Path::new("/nonexistent/cass");
+        assert!(conversation_source_is_self_output(data_dir, &exported));
+        assert!(!conversation_source_is_self_output(data_dir, &session));
+    }
+}
+
 #[cfg(unix)]
 fn source_file_id(path: &Path) -> Option<SourceFileId> {
     use std::os::unix::fs::MetadataExt;
@@ -818,10 +942,112 @@ pub struct ConnectorStats {
     pub conversations: usize,
     pub messages: usize,
     pub scan_ms: u64,
+    /// Source files the connector's discovery pass reported for this run.
+    /// Zero when the connector lacks discovery support and the legacy
+    /// root-walk capture path ran instead.
+    pub files_discovered: usize,
+    /// Distinct source files that yielded at least one conversation.
+    pub files_parsed: usize,
+    /// Scan-scope parse failures recorded for this connector.
+    pub parse_failures: usize,
+    /// First few failure descriptions, so `cass index` output points at
+    /// concrete paths without log spelunking.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parse_failure_samples: Vec<String>,
+    /// Conversations whose message role distribution looks like a parse bug
+    /// rather than a real session (see [`role_distribution_anomaly`]).
+    pub role_anomalies: usize,
+    /// First few `path (reason)` descriptions of role-anomalous
+    /// conversations, so the report points at concrete files to inspect.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub role_anomaly_samples: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
+/// Cap on [`ConnectorStats::parse_failure_samples`] entries per connector.
+const PARSE_FAILURE_SAMPLE_LIMIT: usize = 3;
+
+/// Cap on [`ConnectorStats::role_anomaly_samples`] entries per connector
+/// (also the per-agent example cap in the doctor's post-hoc scan).
+pub(crate) const ROLE_ANOMALY_SAMPLE_LIMIT: usize = 3;
+
+/// Minimum message count before a one-sided role distribution counts as an
+/// anomaly. Interactive sessions legitimately start one-sided — a lone user
+/// prompt the agent never answered, or a short assistant-only warmup — so
+/// only conversations long enough that alternation should have happened get
+/// flagged.
+const ROLE_ANOMALY_MIN_MESSAGES: usize = 5;
+
+impl ConnectorStats {
+    fn record_parse_failure(&mut self, description: &str) {
+        self.parse_failures += 1;
+        if self.parse_failure_samples.len() < PARSE_FAILURE_SAMPLE_LIMIT {
+            self.parse_failure_samples.push(description.to_string());
+        }
+    }
+
+    fn record_role_anomaly(&mut self, conv: &NormalizedConversation, reason: &str) {
+        self.role_anomalies += 1;
+        if self.role_anomaly_samples.len() < ROLE_ANOMALY_SAMPLE_LIMIT {
+            self.role_anomaly_samples
+                .push(format!("{} ({reason})", conv.source_path.display()));
+        }
+    }
+}
+
+/// Whether a message role string names the human side of a conversation.
+pub(crate) fn role_is_user(role: &str) -> bool {
+    matches!(role, "user" | "human")
+}
+
+/// Whether a message role string names the agent side of a conversation.
+pub(crate) fn role_is_assistant(role: &str) -> bool {
+    matches!(role, "assistant" | "agent" | "model")
+}
+
+/// Classify a conversation's role counts, returning a short reason string
+/// when the distribution looks like a connector parse bug rather than a real
+/// session. Interactive agents alternate user and assistant turns, so a
+/// conversation of non-trivial length whose messages all sit on one side of
+/// that split usually means the connector dropped a role during extraction
+/// (e.g. a format change moved user turns into a field we no longer read).
+/// Precision beats recall here — the flag feeds "go look at this file"
+/// reporting, so short and tool/system-only shapes stay exempt. Shared by
+/// the per-run connector stats below and the doctor's post-hoc scan over
+/// stored messages.
+pub(crate) fn role_count_anomaly(
+    total_messages: usize,
+    user_turns: usize,
+    assistant_turns: usize,
+) -> Option<&'static str> {
+    if total_messages < ROLE_ANOMALY_MIN_MESSAGES {
+        return None;
+    }
+    if user_turns == 0 && assistant_turns > 0 {
+        Some("no user turns")
+    } else if assistant_turns == 0 && user_turns > 0 {
+        Some("no assistant turns")
+    } else {
+        None
+    }
+}
+
+/// [`role_count_anomaly`] applied to a freshly parsed conversation.
+pub(crate) fn role_distribution_anomaly(conv: &NormalizedConversation) -> Option<&'static str> {
+    let user_turns = conv
+        .messages
+        .iter()
+        .filter(|m| role_is_user(m.role.as_str()))
+        .count();
+    let assistant_turns = conv
+        .messages
+        .iter()
+        .filter(|m| role_is_assistant(m.role.as_str()))
+        .count();
+    role_count_anomaly(conv.messages.len(), user_turns, assistant_turns)
+}
+
 /// Structured lexical repair metadata for JSON output.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct LexicalRepairStats {
@@ -963,6 +1189,16 @@ pub struct IndexingProgress {
     pub last_error: Mutex<Option<String>>,
     /// Structured stats for JSON output (T7.4)
     pub stats: Mutex<IndexingStats>,
+    /// Live depth of the bounded streaming-ingest channel (parse producers →
+    /// single writer) for same-process progress output.
+    pub streaming_queue_depth: AtomicUsize,
+    /// High-water mark of the streaming-ingest channel depth for this run.
+    pub streaming_queue_high_water: AtomicUsize,
+    /// Producer batch sends that blocked on a full streaming channel — i.e.
+    /// backpressure events where the writer was the bottleneck.
+    pub streaming_send_wait_count: AtomicUsize,
+    /// Total milliseconds producers spent blocked on the full channel.
+    pub streaming_send_wait_ms: AtomicUsize,
     /// Live authoritative rebuild queue depth for same-process progress output.
     pub rebuild_pipeline_queue_depth: AtomicUsize,
     /// Live authoritative rebuild in-flight byte budget usage.
@@ -1143,6 +1379,10 @@ impl IndexingProgress {
             .map(|g| g.clone())
             .unwrap_or_default();
         let last_error: Option<String> = self.last_error.lock().ok().and_then(|g| g.clone());
+        let streaming_queue_depth = self.streaming_queue_depth.load(Ordering::Relaxed);
+        let streaming_queue_high_water = self.streaming_queue_high_water.load(Ordering::Relaxed);
+        let streaming_send_wait_count = self.streaming_send_wait_count.load(Ordering::Relaxed);
+        let streaming_send_wait_ms = self.streaming_send_wait_ms.load(Ordering::Relaxed);
         let rebuild_pipeline_queue_depth =
             self.rebuild_pipeline_queue_depth.load(Ordering::Relaxed);
         let rebuild_pipeline_inflight_message_bytes = self
@@ -1325,6 +1565,12 @@ impl IndexingProgress {
             "last_error": last_error,
             "quarantined_conversations": quarantined_conversations,
             "lexical_update_deferred": lexical_update_deferred,
+            "streaming_pipeline": {
+                "queue_depth": streaming_queue_depth,
+                "queue_depth_high_water": streaming_queue_high_water,
+                "send_wait_count": streaming_send_wait_count,
+                "send_wait_ms": streaming_send_wait_ms,
+            },
             "rebuild_pipeline": {
                 "queue_depth": rebuild_pipeline_queue_depth,
                 "inflight_message_bytes": rebuild_pipeline_inflight_message_bytes,
@@ -1458,6 +1704,8 @@ pub struct IndexOptions {
     /// Minimum interval (in seconds) between watch scan cycles. Prevents tight-loop
     /// CPU burn when filesystem events arrive continuously. Default: 30.
     pub watch_interval_secs: u64,
+    /// Pause watch scan cycles while the machine runs on battery power.
+    pub pause_on_battery: bool,
 }
 
 pub fn set_robot_trace_ingest_enabled(enabled: bool) -> bool {
@@ -3973,20 +4221,45 @@ impl LexicalRebuildConversationPacket {
             ) {
                 continue;
             }
-            docs.push(frankensearch::lexical::CassDocumentRef {
-                agent: self.identity.agent.as_str(),
-                workspace: self.identity.workspace.as_deref(),
-                workspace_original: None,
-                source_path: self.identity.source_path.as_str(),
-                msg_idx: message.idx.max(0) as u64,
-                created_at: message.created_at.or(self.identity.started_at),
-                title: self.identity.title.as_deref(),
-                content: message.content.as_str(),
-                source_id: self.provenance.source_id.as_str(),
-                origin_kind: self.provenance.origin_kind.as_str(),
-                origin_host: self.provenance.origin_host.as_deref(),
-                conversation_id: Some(conversation_id),
-            });
+            // Very long messages index as line-aligned chunk documents
+            // (src/search/message_chunking.rs); chunk text borrows
+            // straight out of the message content like the whole-message
+            // path does.
+            let msg_idx = message.idx.max(0) as u64;
+            let chunks: Vec<(u64, &str)> =
+                match crate::search::message_chunking::chunkable_parent_idx(msg_idx)
+                    .then(|| crate::search::message_chunking::chunk_spans(&message.content))
+                    .flatten()
+                {
+                    Some(spans) => spans
+                        .iter()
+                        .map(|span| {
+                            (
+                                crate::search::message_chunking::encode_chunk_msg_idx(
+                                    msg_idx, span.seq,
+                                ),
+                                &message.content[span.range.clone()],
+                            )
+                        })
+                        .collect(),
+                    None => vec![(msg_idx, message.content.as_str())],
+                };
+            for (msg_idx, content) in chunks {
+                docs.push(frankensearch::lexical::CassDocumentRef {
+                    agent: self.identity.agent.as_str(),
+                    workspace: self.identity.workspace.as_deref(),
+                    workspace_original: None,
+                    source_path: self.identity.source_path.as_str(),
+                    msg_idx,
+                    created_at: message.created_at.or(self.identity.started_at),
+                    title: self.identity.title.as_deref(),
+                    content,
+                    source_id: self.provenance.source_id.as_str(),
+                    origin_kind: self.provenance.origin_kind.as_str(),
+                    origin_host: self.provenance.origin_host.as_deref(),
+                    conversation_id: Some(conversation_id),
+                });
+            }
         }
         docs
     }
@@ -10873,6 +11146,8 @@ pub enum IndexMessage {
         is_discovered: bool,
         /// Whether every scan scope for this connector completed without error
         scan_succeeded: bool,
+        /// Source files reported by the connector's discovery pass (stats)
+        files_discovered: usize,
     },
 }
 
@@ -11242,6 +11517,62 @@ fn next_streaming_batch(
     Some((batch, total_messages))
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct StreamingQueueTelemetrySnapshot {
+    batches_sent: usize,
+    queue_depth_high_water: usize,
+    send_wait_count: usize,
+    send_wait_ms: usize,
+}
+
+/// Queue-depth and backpressure counters for the streaming ingest channel
+/// (parse producers → bounded channel → single writer). Producers record a
+/// sample after every batch send; the enclosing index run folds the final
+/// snapshot into the completion log and `IndexingProgress`, so a slow-disk
+/// writer shows up as wait counts instead of a mysteriously slow scan, and a
+/// high-water mark pinned at `STREAMING_CHANNEL_SIZE` flags the writer as the
+/// bottleneck even before producers start blocking.
+#[derive(Debug, Default)]
+struct StreamingQueueTelemetry {
+    batches_sent: AtomicUsize,
+    queue_depth_high_water: AtomicUsize,
+    send_wait_count: AtomicUsize,
+    send_wait_ms: AtomicUsize,
+}
+
+impl StreamingQueueTelemetry {
+    fn saturating_add(counter: &AtomicUsize, value: usize) {
+        let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+            Some(current.saturating_add(value))
+        });
+    }
+
+    /// Record one successful batch send: the channel depth observed just
+    /// after the send, and how long the send blocked on a full channel (if
+    /// it did).
+    fn record_send(&self, queue_depth: usize, blocked_for: Option<Duration>) {
+        Self::saturating_add(&self.batches_sent, 1);
+        self.queue_depth_high_water
+            .fetch_max(queue_depth, Ordering::Relaxed);
+        if let Some(blocked_for) = blocked_for {
+            Self::saturating_add(&self.send_wait_count, 1);
+            Self::saturating_add(
+                &self.send_wait_ms,
+                LexicalRebuildProducerTelemetry::duration_millis(blocked_for),
+            );
+        }
+    }
+
+    fn snapshot(&self) -> StreamingQueueTelemetrySnapshot {
+        StreamingQueueTelemetrySnapshot {
+            batches_sent: self.batches_sent.load(Ordering::Relaxed),
+            queue_depth_high_water: self.queue_depth_high_water.load(Ordering::Relaxed),
+            send_wait_count: self.send_wait_count.load(Ordering::Relaxed),
+            send_wait_ms: self.send_wait_ms.load(Ordering::Relaxed),
+        }
+    }
+}
+
 struct StreamingBatchSender<'a> {
     tx: &'a Sender<IndexMessage>,
     flow_limiter: Arc<StreamingByteLimiter>,
@@ -11251,6 +11582,7 @@ struct StreamingBatchSender<'a> {
     message_count: usize,
     char_count: usize,
     byte_reservation: usize,
+    queue_telemetry: Option<Arc<StreamingQueueTelemetry>>,
 }
 
 fn remember_discovered_connector(discovered_names: &mut Vec<String>, connector_name: &'static str) {
@@ -11275,9 +11607,16 @@ impl<'a> StreamingBatchSender<'a> {
             message_count: 0,
             char_count: 0,
             byte_reservation: 0,
+            queue_telemetry: None,
         }
     }
 
+    /// Attach queue telemetry shared across all producers of one index run.
+    fn with_queue_telemetry(mut self, telemetry: Arc<StreamingQueueTelemetry>) -> Self {
+        self.queue_telemetry = Some(telemetry);
+        self
+    }
+
     fn mark_next_batch_discovered(&mut self) {
         self.next_batch_is_discovered = true;
     }
@@ -11326,13 +11665,28 @@ impl<'a> StreamingBatchSender<'a> {
         let message_count = self.message_count;
         let byte_reservation = self.byte_reservation;
         let conversations = std::mem::take(&mut self.conversations);
-        if let Err(_send_error) = self.tx.send(IndexMessage::Batch {
+        let message = IndexMessage::Batch {
             connector_name: self.connector_name,
             conversations,
             is_discovered: self.next_batch_is_discovered,
             message_count,
             byte_reservation,
-        }) {
+        };
+        // Try a non-blocking send first so a full channel — the writer
+        // falling behind the parsers — is observable as a counted, timed
+        // backpressure wait instead of an indistinguishable slow scan.
+        let mut blocked_for = None;
+        let delivered = match self.tx.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(message)) => {
+                let wait_start = std::time::Instant::now();
+                let result = self.tx.send(message);
+                blocked_for = Some(wait_start.elapsed());
+                result.is_ok()
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        };
+        if !delivered {
             self.flow_limiter.release(byte_reservation);
             self.message_count = 0;
             self.char_count = 0;
@@ -11341,6 +11695,9 @@ impl<'a> StreamingBatchSender<'a> {
                 connector_name: self.connector_name,
             }));
         }
+        if let Some(telemetry) = &self.queue_telemetry {
+            telemetry.record_send(self.tx.len(), blocked_for);
+        }
         self.message_count = 0;
         self.char_count = 0;
         self.byte_reservation = 0;
@@ -11453,6 +11810,7 @@ struct StreamingProducerConfig {
     local_since_ts_by_connector: Arc<HashMap<&'static str, Option<i64>>>,
     progress: Option<Arc<IndexingProgress>>,
     active_source_filter: Arc<ActiveSessionSourceFilter>,
+    queue_telemetry: Arc<StreamingQueueTelemetry>,
 }
 
 /// Spawn a producer thread that scans a connector and sends batches through the channel.
@@ -11470,10 +11828,15 @@ fn spawn_connector_producer(
     thread::spawn(move || {
         let scan_start = std::time::Instant::now();
         let conn = factory();
-        let detect = conn.detect();
+        let detect = crate::connector_registry::cached_connector_detection(
+            &config.data_dir,
+            name,
+            conn.as_ref(),
+        );
         let was_detected = detect.detected;
         let mut is_discovered = false;
         let mut scan_succeeded = true;
+        let mut files_discovered = 0usize;
 
         if detect.detected {
             // Update discovered agents count immediately when detected
@@ -11495,14 +11858,15 @@ fn spawn_connector_producer(
             );
             let local_origin = Origin::local();
             let mut batch_sender =
-                StreamingBatchSender::new(&tx, config.flow_limiter.clone(), name, is_discovered);
+                StreamingBatchSender::new(&tx, config.flow_limiter.clone(), name, is_discovered)
+                    .with_queue_telemetry(config.queue_telemetry.clone());
             let fallback_roots: Vec<ScanRoot> = detect
                 .root_paths
                 .iter()
                 .cloned()
                 .map(ScanRoot::local)
                 .collect();
-            capture_connector_sources_before_parse(
+            files_discovered += capture_connector_sources_before_parse(
                 conn.as_ref(),
                 &ctx,
                 &config.data_dir,
@@ -11522,6 +11886,9 @@ fn spawn_connector_producer(
                 if should_skip_subagent_source(&conversation.source_path) {
                     return Ok(());
                 }
+                if should_skip_self_output_source(&config.data_dir, &conversation.source_path) {
+                    return Ok(());
+                }
                 prepare_conversation_for_ingest(
                     &config.data_dir,
                     name,
@@ -11573,6 +11940,7 @@ fn spawn_connector_producer(
 
         // Scan explicitly configured additional roots. These may be true remote
         // mirrors or machine-local backup directories wired through sources.toml.
+        let scan_ignore_overrides = crate::connectors::ProjectScanIgnoreOverrides::from_env();
         for root in &config.additional_scan_roots {
             let local_since_ts = config
                 .local_since_ts_by_connector
@@ -11588,19 +11956,39 @@ fn spawn_connector_producer(
                     "configured scan root is using a full root scan so already-mirrored sessions can be promoted to canonical"
                 );
             }
+            let preflight = crate::connectors::preflight_project_scan_roots(
+                name,
+                std::slice::from_ref(root),
+                root_since_ts,
+                &scan_ignore_overrides,
+            );
+            if preflight.explicit_file_roots > 0 || preflight.fallback_roots > 0 {
+                tracing::debug!(
+                    connector = name,
+                    root = %root.path.display(),
+                    explicit_file_roots = preflight.explicit_file_roots,
+                    fallback_roots = preflight.fallback_roots,
+                    "project scan-root preflight expanded configured root"
+                );
+            }
+            if preflight.scan_roots.is_empty() {
+                continue;
+            }
+            let scan_roots = preflight.scan_roots;
             let ctx = crate::connectors::ScanContext::with_roots(
                 root.path.clone(),
-                vec![root.clone()],
+                scan_roots.clone(),
                 root_since_ts,
             );
             let mut batch_sender =
-                StreamingBatchSender::new(&tx, config.flow_limiter.clone(), name, is_discovered);
-            capture_connector_sources_before_parse(
+                StreamingBatchSender::new(&tx, config.flow_limiter.clone(), name, is_discovered)
+                    .with_queue_telemetry(config.queue_telemetry.clone());
+            files_discovered += capture_connector_sources_before_parse(
                 conn.as_ref(),
                 &ctx,
                 &config.data_dir,
                 name,
-                std::slice::from_ref(root),
+                &scan_roots,
                 root_since_ts,
                 config.active_source_filter.as_ref(),
             );
@@ -11615,6 +12003,9 @@ fn spawn_connector_producer(
                 if should_skip_subagent_source(&conversation.source_path) {
                     return Ok(());
                 }
+                if should_skip_self_output_source(&config.data_dir, &conversation.source_path) {
+                    return Ok(());
+                }
                 prepare_conversation_for_ingest(
                     &config.data_dir,
                     name,
@@ -11705,6 +12096,7 @@ fn spawn_connector_producer(
             scan_ms,
             is_discovered,
             scan_succeeded,
+            files_discovered,
         });
     })
 }
@@ -11819,6 +12211,10 @@ fn run_streaming_consumer(
 
     // Per-connector stats tracking (T7.4)
     let mut connector_stats: HashMap<String, ConnectorStats> = HashMap::new();
+    // Distinct source files that yielded conversations, per connector, for
+    // the files-parsed column of the end-of-run summary.
+    let mut parsed_files_by_connector: HashMap<String, std::collections::HashSet<String>> =
+        HashMap::new();
     let mut failed_scan_connectors = BTreeSet::new();
 
     // Card 3 (flat combining, §14.2): when enabled and at least one
@@ -11857,6 +12253,16 @@ fn run_streaming_consumer(
                 message_count,
                 byte_reservation,
             }) => {
+                // Live queue gauge for same-process progress output: the
+                // depth left on the channel after taking this batch, plus
+                // one for the batch itself toward the high-water mark.
+                if let Some(p) = progress {
+                    let depth = rx.len();
+                    p.streaming_queue_depth.store(depth, Ordering::Relaxed);
+                    p.streaming_queue_high_water
+                        .fetch_max(depth.saturating_add(1), Ordering::Relaxed);
+                }
+
                 // Accumulators start with the first-received batch.
                 let mut combined_conversations: Vec<NormalizedConversation> = conversations;
                 let mut combined_message_count = message_count;
@@ -11878,6 +12284,15 @@ fn run_streaming_consumer(
                         });
                     stats.conversations += combined_batch_size;
                     stats.messages += message_count;
+                    let parsed = parsed_files_by_connector
+                        .entry(connector_name.to_string())
+                        .or_default();
+                    for conv in &combined_conversations {
+                        parsed.insert(conv.source_path.to_string_lossy().to_string());
+                        if let Some(reason) = role_distribution_anomaly(conv) {
+                            stats.record_role_anomaly(conv, reason);
+                        }
+                    }
                 }
 
                 // Switch to indexing phase on first batch (reset total/current for accurate progress)
@@ -11928,6 +12343,15 @@ fn run_streaming_consumer(
                                     });
                                 stats.conversations += extra_size;
                                 stats.messages += extra_msg_count;
+                                let parsed = parsed_files_by_connector
+                                    .entry(cname2.to_string())
+                                    .or_default();
+                                for conv in &extra_convs {
+                                    parsed.insert(conv.source_path.to_string_lossy().to_string());
+                                    if let Some(reason) = role_distribution_anomaly(conv) {
+                                        stats.record_role_anomaly(conv, reason);
+                                    }
+                                }
                                 if extra_discovered {
                                     remember_discovered_connector(&mut discovered_names, cname2);
                                 }
@@ -12022,6 +12446,7 @@ fn run_streaming_consumer(
                         name: connector_name.to_string(),
                         ..Default::default()
                     });
+                stats.record_parse_failure(&error);
                 stats.error = Some(error.clone());
 
                 tracing::warn!(
@@ -12036,6 +12461,7 @@ fn run_streaming_consumer(
                 scan_ms,
                 is_discovered,
                 scan_succeeded,
+                files_discovered,
             }) => {
                 active_producers -= 1;
                 let effective_scan_succeeded =
@@ -12049,6 +12475,7 @@ fn run_streaming_consumer(
                         ..Default::default()
                     });
                 stats.scan_ms = scan_ms;
+                stats.files_discovered = files_discovered;
 
                 if is_discovered {
                     remember_discovered_connector(&mut discovered_names, connector_name);
@@ -12129,6 +12556,11 @@ fn run_streaming_consumer(
     {
         stats.scan_ms = scan_ms;
         stats.index_ms = index_ms;
+        for (name, parsed) in &parsed_files_by_connector {
+            if let Some(connector) = connector_stats.get_mut(name) {
+                connector.files_parsed = parsed.len();
+            }
+        }
         stats.connectors = connector_stats.values().cloned().collect();
         stats.agents_discovered = discovered_names.clone();
         stats.total_conversations = total_conversations;
@@ -12183,7 +12615,7 @@ fn run_streaming_index(
 type ConnectorFactory = fn() -> Box<dyn Connector + Send>;
 
 fn configured_connector_factories() -> Vec<(&'static str, ConnectorFactory)> {
-    filter_disabled_connector_factories(get_connector_factories())
+    filter_disabled_connector_factories(crate::connector_registry::compiled_connector_factories())
 }
 
 fn filter_disabled_connector_factories(
@@ -12292,6 +12724,7 @@ fn run_streaming_index_with_connector_factories(
         active_source_filter: Arc::new(ActiveSessionSourceFilter::new(
             opts.watch && opts.watch_once_paths.as_ref().is_none_or(Vec::is_empty),
         )),
+        queue_telemetry: Arc::new(StreamingQueueTelemetry::default()),
     };
 
     // Spawn producer threads for each connector
@@ -12369,6 +12802,27 @@ fn run_streaming_index_with_connector_factories(
         names.extend(discovered_names);
     }
 
+    // Surface queue-depth / backpressure telemetry for the run. Wait counts
+    // mean the writer (DB + Tantivy) was the bottleneck; a high-water mark
+    // near `STREAMING_CHANNEL_SIZE` with no waits means it nearly was.
+    let queue = producer_config.queue_telemetry.snapshot();
+    tracing::info!(
+        batches_sent = queue.batches_sent,
+        queue_depth_high_water = queue.queue_depth_high_water,
+        send_wait_count = queue.send_wait_count,
+        send_wait_ms = queue.send_wait_ms,
+        "streaming_queue_telemetry"
+    );
+    if let Some(p) = &opts.progress {
+        p.streaming_queue_depth.store(0, Ordering::Relaxed);
+        p.streaming_queue_high_water
+            .fetch_max(queue.queue_depth_high_water, Ordering::Relaxed);
+        p.streaming_send_wait_count
+            .store(queue.send_wait_count, Ordering::Relaxed);
+        p.streaming_send_wait_ms
+            .store(queue.send_wait_ms, Ordering::Relaxed);
+    }
+
     Ok(ingest_outcome)
 }
 
@@ -12438,7 +12892,8 @@ fn run_batch_index_with_connector_factories(
         convs: Vec<NormalizedConversation>,
         is_discovered: bool,
         scan_succeeded: bool,
-        scan_error: Option<String>,
+        scan_errors: Vec<String>,
+        files_discovered: usize,
     }
 
     let progress_ref = opts.progress.as_ref();
@@ -12459,12 +12914,17 @@ fn run_batch_index_with_connector_factories(
             .into_par_iter()
             .filter_map(|(name, factory)| {
                 let conn = factory();
-                let detect = conn.detect();
+                let detect = crate::connector_registry::cached_connector_detection(
+                    &data_dir,
+                    name,
+                    conn.as_ref(),
+                );
                 let was_detected = detect.detected;
                 let mut convs = Vec::new();
                 let mut is_discovered = false;
                 let mut scan_succeeded = true;
                 let mut scan_errors = Vec::new();
+                let mut files_discovered = 0usize;
 
                 if detect.detected {
                     // Update discovered agents count immediately when detected
@@ -12489,7 +12949,7 @@ fn run_batch_index_with_connector_factories(
                         .cloned()
                         .map(ScanRoot::local)
                         .collect();
-                    capture_connector_sources_before_parse(
+                    files_discovered += capture_connector_sources_before_parse(
                         conn.as_ref(),
                         &ctx,
                         &data_dir,
@@ -12530,6 +12990,8 @@ fn run_batch_index_with_connector_factories(
                 }
 
                 if !additional_scan_roots.is_empty() {
+                    let scan_ignore_overrides =
+                        crate::connectors::ProjectScanIgnoreOverrides::from_env();
                     for root in &additional_scan_roots {
                         let local_since_ts = local_since_ts_by_connector
                             .get(name)
@@ -12545,17 +13007,36 @@ fn run_batch_index_with_connector_factories(
                                 "configured scan root is using a full root scan so already-mirrored sessions can be promoted to canonical"
                             );
                         }
+                        let preflight = crate::connectors::preflight_project_scan_roots(
+                            name,
+                            std::slice::from_ref(root),
+                            root_since_ts,
+                            &scan_ignore_overrides,
+                        );
+                        if preflight.explicit_file_roots > 0 || preflight.fallback_roots > 0 {
+                            tracing::debug!(
+                                connector = name,
+                                root = %root.path.display(),
+                                explicit_file_roots = preflight.explicit_file_roots,
+                                fallback_roots = preflight.fallback_roots,
+                                "project scan-root preflight expanded configured root"
+                            );
+                        }
+                        if preflight.scan_roots.is_empty() {
+                            continue;
+                        }
+                        let scan_roots = preflight.scan_roots;
                         let ctx = crate::connectors::ScanContext::with_roots(
                             root.path.clone(),
-                            vec![root.clone()],
+                            scan_roots.clone(),
                             root_since_ts,
                         );
-                        capture_connector_sources_before_parse(
+                        files_discovered += capture_connector_sources_before_parse(
                             conn.as_ref(),
                             &ctx,
                             &data_dir,
                             name,
-                            std::slice::from_ref(root),
+                            &scan_roots,
                             root_since_ts,
                             active_source_filter.as_ref(),
                         );
@@ -12620,13 +13101,13 @@ fn run_batch_index_with_connector_factories(
                     scan_succeeded,
                     "batch_scan_complete"
                 );
-                let scan_error = (!scan_errors.is_empty()).then(|| scan_errors.join("; "));
                 Some(PendingBatchScan {
                     name,
                     convs,
                     is_discovered,
                     scan_succeeded,
-                    scan_error,
+                    scan_errors,
+                    files_discovered,
                 })
             })
             .collect();
@@ -12665,16 +13146,38 @@ fn run_batch_index_with_connector_factories(
         .sum();
     let connector_stats: Vec<ConnectorStats> = pending_batches
         .iter()
-        .filter(|pending| !pending.convs.is_empty() || pending.scan_error.is_some())
+        .filter(|pending| !pending.convs.is_empty() || !pending.scan_errors.is_empty())
         .map(|pending| {
             let msgs: usize = pending.convs.iter().map(|c| c.messages.len()).sum();
-            ConnectorStats {
+            let files_parsed = pending
+                .convs
+                .iter()
+                .map(|c| c.source_path.to_string_lossy())
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            let mut stats = ConnectorStats {
                 name: pending.name.to_string(),
                 conversations: pending.convs.len(),
                 messages: msgs,
                 scan_ms,
-                error: pending.scan_error.clone(),
+                files_discovered: pending.files_discovered,
+                files_parsed,
+                parse_failures: pending.scan_errors.len(),
+                parse_failure_samples: pending
+                    .scan_errors
+                    .iter()
+                    .take(PARSE_FAILURE_SAMPLE_LIMIT)
+                    .cloned()
+                    .collect(),
+                error: (!pending.scan_errors.is_empty()).then(|| pending.scan_errors.join("; ")),
+                ..Default::default()
+            };
+            for conv in &pending.convs {
+                if let Some(reason) = role_distribution_anomaly(conv) {
+                    stats.record_role_anomaly(conv, reason);
+                }
             }
+            stats
         })
         .collect();
 
@@ -13446,10 +13949,23 @@ pub fn run_index(
                 })
                 .unwrap_or(false);
 
+        // A CJK segmenter change alters the indexed token stream exactly
+        // like a schema change: stale shards would silently miss matches.
+        let cjk_tokenizer_matches =
+            crate::search::tantivy::stored_cjk_tokenizer_matches(&index_path);
+        if schema_matches && !cjk_tokenizer_matches {
+            tracing::info!(
+                path = %index_path.display(),
+                active = crate::search::cjk::active_segmentation().id(),
+                "CJK segmenter changed since the index was built; forcing lexical rebuild"
+            );
+        }
+
         // Treat missing schema hash as rebuild (open_or_create will wipe/recreate).
         tantivy_requires_rebuild = opts.force_rebuild
             || !crate::search::tantivy::searchable_index_exists(&index_path)
-            || !schema_matches;
+            || !schema_matches
+            || !cjk_tokenizer_matches;
 
         // Preflight open: if the cass-compatible Tantivy reader can't open, force a
         // rebuild so we do a full scan and reindex messages into the new index
@@ -14678,7 +15194,21 @@ pub fn run_index(
             event_channel,
             stale_detector,
             opts.watch_interval_secs,
+            opts.pause_on_battery,
+            opts.data_dir.clone(),
             move |paths, roots, is_rebuild| {
+                // Capped copy of the changed paths before reindexing consumes
+                // them; failure notifications inspect these sessions after the
+                // cycle. Rebuilds and watch-once runs never notify.
+                let changed_paths: Vec<PathBuf> = if !is_rebuild && !watch_once_mode {
+                    let mut copy = paths.clone();
+                    copy.sort();
+                    copy.dedup();
+                    copy.truncate(20);
+                    copy
+                } else {
+                    Vec::new()
+                };
                 let mut semantic_delta = WatchSemanticDelta::default();
                 let indexed = if is_rebuild {
                     if let Ok(mut g) = state.lock() {
@@ -14826,6 +15356,28 @@ pub fn run_index(
                     }
                 }
 
+                // Token-usage refresh and monthly budget check per cycle so
+                // watch mode alerts too. The check is edge-triggered
+                // (before/after comparison inside), so cycles that ingest
+                // nothing new never re-fire the warning or the hook.
+                if indexed > 0
+                    && let Ok(guard) = storage_for_watch.lock()
+                {
+                    refresh_token_usage_and_check_budget(&guard);
+                }
+
+                // Desktop notification for sessions that just ended in what
+                // looks like a failure (panic, non-zero exit, agent refusal),
+                // so unattended runs surface quickly. Best-effort and gated
+                // behind CASS_WATCH_NOTIFY.
+                if indexed > 0
+                    && !changed_paths.is_empty()
+                    && crate::watch_notify::notifications_enabled()
+                    && let Ok(guard) = storage_for_watch.lock()
+                {
+                    crate::watch_notify::notify_failed_sessions(&guard, &changed_paths);
+                }
+
                 // CASS #163 item 3: Periodically recycle the long-lived read
                 // handle to shed accumulated MVCC snapshots when
                 // autocommit_retain could not be disabled.
@@ -14878,9 +15430,107 @@ pub fn run_index(
         return Ok(());
     }
 
+    // Refresh the cross-conversation boilerplate registry (derived asset,
+    // see `search::boilerplate`) so default ranking exclusion reflects this
+    // run's corpus. Best-effort: a failed refresh leaves the previous
+    // registry in place and must not fail an otherwise successful index.
+    match storage.refresh_boilerplate_contents() {
+        Ok(rows) => tracing::debug!(rows, "refreshed boilerplate content registry"),
+        Err(err) => tracing::warn!(error = %err, "boilerplate registry refresh failed"),
+    }
+
+    // Seal per-conversation integrity hashes for `cass verify`. Also
+    // best-effort: a failed seal pass leaves existing seals in place, and
+    // verify reports the affected conversations as unsealed rather than
+    // tampered.
+    match storage.refresh_conversation_integrity() {
+        Ok(sealed) => tracing::debug!(sealed, "refreshed conversation integrity seals"),
+        Err(err) => tracing::warn!(error = %err, "conversation integrity seal refresh failed"),
+    }
+
+    // Locate message-level source spans (`cass show --raw`) for newly
+    // ingested or appended messages. Best-effort for the same reason: spans
+    // are derived data and missing ones just mean no raw-record display.
+    match storage.refresh_message_provenance() {
+        Ok(spans) => tracing::debug!(spans, "refreshed message source provenance"),
+        Err(err) => tracing::warn!(error = %err, "message provenance refresh failed"),
+    }
+
+    // Score session usefulness (`cass sessions --min-quality` /
+    // `--sort-quality`, see `search::quality`). Best-effort like the passes
+    // above:
+    // a failed refresh just leaves stale or missing scores, and readers
+    // treat those as unscored.
+    match storage.refresh_conversation_quality() {
+        Ok(scored) => tracing::debug!(scored, "refreshed conversation quality scores"),
+        Err(err) => tracing::warn!(error = %err, "conversation quality refresh failed"),
+    }
+
+    // Classify headless/batch runs (`cass runs`, see `model::agent_run`).
+    // Best-effort as well: unclassified conversations just stay out of the
+    // runs listing until the next pass.
+    match storage.refresh_agent_runs() {
+        Ok(classified) => tracing::debug!(classified, "refreshed agent run classifications"),
+        Err(err) => tracing::warn!(error = %err, "agent run classification refresh failed"),
+    }
+
+    // Derive topic facets (`facet:` query terms, see `search::facets`) and
+    // PII classification tags (`pii-*` values, see `search::pii`; the
+    // customer-name dictionary comes from the `[pii]` config table).
+    // Best-effort too: a failed refresh leaves stale or missing facets, and
+    // `facet:` filters simply match fewer conversations until the next pass.
+    let pii_customer_names = crate::search_defaults::load_pii_defaults()
+        .map(|defaults| defaults.customer_names)
+        .unwrap_or_default();
+    match storage.refresh_conversation_facets(&pii_customer_names) {
+        Ok(derived) => tracing::debug!(derived, "refreshed conversation facets"),
+        Err(err) => tracing::warn!(error = %err, "conversation facet refresh failed"),
+    }
+
+    // Assign durable conversation UUIDs (`cass sessions --json`, external
+    // references). Best-effort too: conversations without one yet simply
+    // report no uuid until the next pass.
+    match storage.refresh_conversation_uuids() {
+        Ok(assigned) => tracing::debug!(assigned, "refreshed durable conversation uuids"),
+        Err(err) => tracing::warn!(error = %err, "conversation uuid refresh failed"),
+    }
+
+    // Attach Gemini/Antigravity plan and brainstorming artifacts written
+    // next to the chat logs (see `plan_artifacts`). Best-effort too: a
+    // failed pass just means the plans stay unsearchable until the next run.
+    match plan_artifacts::attach_plan_artifacts(&storage) {
+        Ok(attached) => tracing::debug!(attached, "attached plan artifacts"),
+        Err(err) => tracing::warn!(error = %err, "plan artifact attachment failed"),
+    }
+
+    // Extract exact token usage (see `token_budget`) and, when a monthly
+    // budget is configured, warn once if this run crossed its threshold.
+    // Best-effort like every pass above.
+    refresh_token_usage_and_check_budget(&storage);
+
     close_storage_after_index(storage, &opts.db_path, "index run")
 }
 
+/// Shared by the one-shot index tail and each watch cycle: run the exact
+/// token-usage refresh pass (see `token_budget`) and, when a monthly budget
+/// is configured, compare the current month's usage before and after so the
+/// warning and hook fire exactly when this run crosses the threshold.
+/// Best-effort throughout — budget alerts never fail an index run.
+fn refresh_token_usage_and_check_budget(storage: &FrankenStorage) {
+    let budget = crate::token_budget::resolve_budget_config();
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let used_before = budget
+        .as_ref()
+        .map(|_| crate::token_budget::month_usage_total(storage, now_ms));
+    match storage.refresh_token_usage() {
+        Ok(evaluated) => tracing::debug!(evaluated, "refreshed exact token usage"),
+        Err(err) => tracing::warn!(error = %err, "token usage refresh failed"),
+    }
+    if let (Some(config), Some(used_before)) = (budget, used_before) {
+        crate::token_budget::warn_if_crossed(&config, storage, used_before, now_ms);
+    }
+}
+
 fn close_storage_after_index(storage: FrankenStorage, db_path: &Path, context: &str) -> Result<()> {
     prepare_storage_for_final_checkpoint(&storage, db_path, context);
     storage.close().with_context(|| {
@@ -22300,6 +22950,8 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
     event_channel: Option<(Sender<IndexerEvent>, Receiver<IndexerEvent>)>,
     stale_detector: Arc<StaleDetector>,
     watch_interval_secs: u64,
+    pause_on_battery: bool,
+    data_dir: PathBuf,
     callback: F,
 ) -> Result<()> {
     if let Some(paths) = watch_once_paths {
@@ -22341,7 +22993,29 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
     let max_wait = Duration::from_secs(5);
     // Minimum interval between scan cycles to prevent tight-loop CPU burn
     // when filesystem events arrive continuously. Default: 30s. (Issue #129)
-    let min_scan_interval = Duration::from_secs(watch_interval_secs.max(1));
+    // Adaptive backoff widens the effective interval while nothing changes
+    // and snaps back to this base the moment files change.
+    let mut backoff = crate::watch_control::AdaptiveBackoff::new(watch_interval_secs.max(1));
+    // Pause state: polled from the control file written by `cass watch
+    // pause|resume` (no signal handler — AGENTS.md forbids them) and, when
+    // --pause-on-battery is set, from the platform power report.
+    let control_poll_interval = Duration::from_secs(5);
+    let mut last_control_poll: Option<Instant> = None;
+    // Hot config reload: re-stat cass.toml on the same cadence so config
+    // edits (excludes, ranking weights, ...) apply without restarting the
+    // daemon. An invalid version is rejected and the old settings stay in
+    // force; see `search_defaults::ConfigReloader`.
+    let mut config_reloader = crate::search_defaults::ConfigReloader::new();
+    // Soft real-time focus (`cass focus`): pending session files under this
+    // workspace skip the scan cooldown. Polled alongside pause/resume.
+    let mut focus_root: Option<PathBuf> = crate::focus::load_focus(&data_dir)
+        .workspace
+        .map(PathBuf::from);
+    let mut paused = false;
+    let mut pause_state = String::from("running");
+    let watch_started_at_ms = chrono::Utc::now().timestamp_millis();
+    let mut cycles_completed: u64 = 0;
+    let mut last_cycle_at_ms: Option<i64> = None;
     // Stale check interval: check every 5 minutes for quicker detection
     let stale_check_interval = Duration::from_secs(300);
     let mut pending: Vec<PathBuf> = Vec::new();
@@ -22353,7 +23027,7 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
     // If the full interval won't fit, try smaller values so the first scan
     // still fires quickly rather than waiting the full cooldown.
     let mut last_scan = [
-        min_scan_interval,
+        Duration::from_secs(backoff.current_secs()),
         Duration::from_secs(60),
         Duration::from_secs(1),
     ]
@@ -22367,24 +23041,122 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
     );
 
     loop {
+        // Refresh pause state + heartbeat on a short cadence so `cass watch
+        // pause` and `cass watch status` see effects within seconds.
+        if last_control_poll.is_none_or(|t| t.elapsed() >= control_poll_interval) {
+            last_control_poll = Some(Instant::now());
+            let control = crate::watch_control::load_control(&data_dir);
+            let on_battery =
+                pause_on_battery && crate::watch_control::on_battery().unwrap_or(false);
+            paused = control.paused || on_battery;
+            pause_state = if control.paused {
+                "paused".to_string()
+            } else if on_battery {
+                "paused-on-battery".to_string()
+            } else {
+                "running".to_string()
+            };
+            crate::watch_control::write_status(
+                &data_dir,
+                &crate::watch_control::WatchStatus {
+                    pid: std::process::id(),
+                    state: pause_state.clone(),
+                    started_at_ms: watch_started_at_ms,
+                    last_cycle_at_ms,
+                    cycles_completed,
+                    current_interval_secs: backoff.current_secs(),
+                    pause_on_battery,
+                    updated_at_ms: chrono::Utc::now().timestamp_millis(),
+                },
+            );
+            match config_reloader.poll() {
+                crate::search_defaults::ConfigReload::Applied(changes) => {
+                    tracing::info!(changes = %changes.join("; "), "config reloaded");
+                }
+                crate::search_defaults::ConfigReload::Rejected(error) => {
+                    tracing::warn!(
+                        %error,
+                        "invalid config update ignored; keeping previous settings"
+                    );
+                }
+                crate::search_defaults::ConfigReload::Unchanged => {}
+            }
+            focus_root = crate::focus::load_focus(&data_dir)
+                .workspace
+                .map(PathBuf::from);
+        }
+
+        // Effective interval after adaptive backoff for this iteration.
+        let min_scan_interval = Duration::from_secs(backoff.current_secs());
+
         // How much cooldown remains before we may fire the next callback.
         // Using this as recv_timeout lets us keep accumulating events
         // instead of blocking with thread::sleep (which would drop events
         // if the inotify buffer fills up).
-        let cooldown_remaining = min_scan_interval.saturating_sub(last_scan.elapsed());
+        // While paused we never fire, so report an effectively infinite
+        // cooldown and keep accumulating events for the resume.
+        let cooldown_remaining = if paused {
+            control_poll_interval
+        } else {
+            min_scan_interval.saturating_sub(last_scan.elapsed())
+        };
 
         // Calculate timeout: use stale check interval when idle, debounce when active
         let timeout = if pending.is_empty() {
-            stale_check_interval
+            // Wake at least once a minute so the idle backoff can widen and
+            // pause/resume requests are picked up promptly.
+            stale_check_interval.min(Duration::from_secs(60))
         } else {
             let now = Instant::now();
             let elapsed = now.duration_since(first_event.unwrap_or(now));
+            let focused_pending = !paused
+                && focus_root.as_deref().is_some_and(|root| {
+                    pending
+                        .iter()
+                        .any(|path| crate::focus::session_path_matches_workspace(path, root))
+                });
+            // Soft real-time focus: once the debounce settles, flush just the
+            // pending files that belong to the focused workspace even though
+            // the scan cooldown is still running. `last_scan` is deliberately
+            // left untouched so the bulk backfill keeps its regular schedule.
+            if focused_pending
+                && elapsed >= debounce
+                && !cooldown_remaining.is_zero()
+                && let Some(root) = focus_root.as_deref()
+            {
+                let (focused, rest): (Vec<PathBuf>, Vec<PathBuf>) = std::mem::take(&mut pending)
+                    .into_iter()
+                    .partition(|path| crate::focus::session_path_matches_workspace(path, root));
+                pending = rest;
+                tracing::debug!(
+                    focused = focused.len(),
+                    deferred = pending.len(),
+                    focus_root = %root.display(),
+                    "flushing focused workspace sessions ahead of scan cooldown"
+                );
+                if let Err(error) = callback(focused, &roots, false) {
+                    tracing::warn!(error = %error, "watch focus priority callback failed");
+                }
+                backoff.note_activity();
+                cycles_completed += 1;
+                last_cycle_at_ms = Some(chrono::Utc::now().timestamp_millis());
+                if pending.is_empty() {
+                    first_event = None;
+                }
+                continue;
+            }
             if elapsed >= max_wait {
-                if cooldown_remaining.is_zero() {
+                if cooldown_remaining.is_zero() && !paused {
                     // Cooldown elapsed and max_wait exceeded: fire now.
+                    if let Some(root) = focus_root.as_deref() {
+                        crate::focus::order_focused_first(&mut pending, root);
+                    }
                     if let Err(error) = callback(std::mem::take(&mut pending), &roots, false) {
                         tracing::warn!(error = %error, "watch incremental callback failed");
                     }
+                    backoff.note_activity();
+                    cycles_completed += 1;
+                    last_cycle_at_ms = Some(chrono::Utc::now().timestamp_millis());
                     last_scan = Instant::now();
                     first_event = None;
                     continue;
@@ -22396,7 +23168,14 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
                 let remaining = max_wait.saturating_sub(elapsed);
                 // Use the larger of (debounce, cooldown_remaining) to ensure
                 // we never fire the callback faster than min_scan_interval.
-                debounce.min(remaining).max(cooldown_remaining)
+                // Focused pending files ignore the cooldown floor so the loop
+                // wakes after the debounce and runs the priority flush above.
+                let effective_cooldown = if focused_pending {
+                    Duration::ZERO
+                } else {
+                    cooldown_remaining
+                };
+                debounce.min(remaining).max(effective_cooldown)
             }
         };
 
@@ -22409,6 +23188,12 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
             }
             Ok(IndexerEvent::Command(cmd)) => match cmd {
                 ReindexCommand::Full => {
+                    if paused {
+                        // Hold the rebuild until resume; the need_rescan
+                        // condition persists, so resuming re-triggers it.
+                        tracing::info!("watch paused: deferring full rebuild");
+                        continue;
+                    }
                     // Full rebuild commands bypass cooldown for responsive
                     // operator-initiated rebuilds.
                     if !pending.is_empty()
@@ -22419,18 +23204,30 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
                     if let Err(error) = callback(vec![], &roots, true) {
                         tracing::warn!(error = %error, "watch rebuild callback failed");
                     }
+                    backoff.note_activity();
+                    cycles_completed += 1;
+                    last_cycle_at_ms = Some(chrono::Utc::now().timestamp_millis());
                     last_scan = Instant::now();
                     first_event = None;
                 }
             },
             Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
                 // Process pending events only if cooldown has elapsed
-                if !pending.is_empty() && last_scan.elapsed() >= min_scan_interval {
+                if !paused && !pending.is_empty() && last_scan.elapsed() >= min_scan_interval {
+                    if let Some(root) = focus_root.as_deref() {
+                        crate::focus::order_focused_first(&mut pending, root);
+                    }
                     if let Err(error) = callback(std::mem::take(&mut pending), &roots, false) {
                         tracing::warn!(error = %error, "watch incremental callback failed");
                     }
+                    backoff.note_activity();
+                    cycles_completed += 1;
+                    last_cycle_at_ms = Some(chrono::Utc::now().timestamp_millis());
                     last_scan = Instant::now();
                     first_event = None;
+                } else if pending.is_empty() {
+                    // Nothing changed this wake-up: widen the idle interval.
+                    backoff.note_idle();
                 }
 
                 // Periodic stale check
@@ -22453,6 +23250,9 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) -> Resu
                                      CASS_WATCH_STALE_ACTION=rebuild for auto-recovery"
                                 );
                             }
+                            StaleAction::Rebuild if paused => {
+                                tracing::info!("watch paused: skipping stale-rebuild");
+                            }
                             StaleAction::Rebuild => {
                                 tracing::warn!(
                                     consecutive_zero_scans = stats.consecutive_zero_scans,
@@ -22649,7 +23449,7 @@ fn reindex_paths_with_semantic_delta(
             since_ts,
         );
 
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             conn.as_ref(),
             &ctx,
             &opts.data_dir,
@@ -22855,13 +23655,19 @@ fn reindex_paths_with_semantic_delta(
             stats.index_ms = stats.index_ms.saturating_add(index_ms);
             stats.total_conversations = stats.total_conversations.saturating_add(conv_count);
             stats.total_messages = stats.total_messages.saturating_add(inserted_messages);
-            stats.connectors.push(ConnectorStats {
+            let mut connector_entry = ConnectorStats {
                 name: connector_name.clone(),
                 conversations: conv_count,
                 messages: inserted_messages,
                 scan_ms,
-                error: None,
-            });
+                ..Default::default()
+            };
+            for conv in &convs {
+                if let Some(reason) = role_distribution_anomaly(conv) {
+                    connector_entry.record_role_anomaly(conv, reason);
+                }
+            }
+            stats.connectors.push(connector_entry);
             if !stats
                 .agents_discovered
                 .iter()
@@ -23756,10 +24562,15 @@ fn prepare_conversation_for_ingest(
     if let Some(root) = workspace_rewrite_root {
         apply_workspace_rewrite(conv, root);
     }
+    ocr::apply_attachment_ocr(conv);
     compact_large_connector_extras(connector_name, conv);
     attach_raw_mirror_capture(data_dir, conv);
 }
 
+/// Returns the number of source files the connector's discovery pass
+/// reported, so per-connector stats can show files discovered next to files
+/// parsed. Zero when discovery is unsupported/empty and the legacy
+/// root-walk fallback ran instead.
 fn capture_connector_sources_before_parse(
     connector: &(dyn crate::connectors::Connector + Send),
     ctx: &crate::connectors::ScanContext,
@@ -23768,7 +24579,7 @@ fn capture_connector_sources_before_parse(
     fallback_roots: &[ScanRoot],
     since_ts: Option<i64>,
     active_source_filter: &ActiveSessionSourceFilter,
-) {
+) -> usize {
     match connector.discover_source_files(ctx) {
         Ok(sources) if !sources.is_empty() => {
             let primary_source_count = sources
@@ -23787,6 +24598,7 @@ fn capture_connector_sources_before_parse(
                     "deferring large primary source raw-mirror capture to per-conversation streaming path"
                 );
             }
+            let discovered = sources.len();
             for source in sources {
                 if should_skip_active_session_source(
                     active_source_filter,
@@ -23802,6 +24614,7 @@ fn capture_connector_sources_before_parse(
                 }
                 capture_discovered_source_file_before_parse(data_dir, provider, &source);
             }
+            discovered
         }
         Ok(_) => {
             for root in fallback_roots {
@@ -23813,6 +24626,7 @@ fn capture_connector_sources_before_parse(
                     active_source_filter,
                 );
             }
+            0
         }
         Err(error) => {
             tracing::warn!(
@@ -23829,6 +24643,7 @@ fn capture_connector_sources_before_parse(
                     active_source_filter,
                 );
             }
+            0
         }
     }
 }
@@ -24315,9 +25130,7 @@ pub mod persist {
     use std::collections::{HashMap, HashSet};
     use std::ops::Range;
     use std::path::Path;
-    use std::time::Duration;
-    #[cfg(test)]
-    use std::time::Instant;
+    use std::time::{Duration, Instant};
 
     use anyhow::{Context, Result, anyhow};
     use frankensqlite::FrankenError;
@@ -24326,6 +25139,7 @@ pub mod persist {
     use rayon::prelude::*;
 
     use crate::connectors::NormalizedConversation;
+    use crate::indexer::commit_batching;
     use crate::indexer::semantic::{
         EmbeddingInput, packet_embedding_inputs_from_storage_for_message_ids,
     };
@@ -25802,14 +26616,31 @@ pub mod persist {
 
                 let refs: Vec<(i64, Option<i64>, &Conversation)> =
                     prepared.iter().map(|(a, w, c)| (*a, *w, c)).collect();
-                let chunk_size = serial_batch_chunk_size().min(refs.len().max(1));
+                let message_counts: Vec<usize> =
+                    refs.iter().map(|(_, _, c)| c.messages.len()).collect();
+                let conversation_cap = serial_batch_chunk_size().min(refs.len().max(1));
+                let mut batcher = commit_batching::AdaptiveCommitBatcher::from_env();
                 let mut outcomes = Vec::with_capacity(refs.len());
 
-                for start in (0..refs.len()).step_by(chunk_size) {
-                    let end = (start + chunk_size).min(refs.len());
+                // Adaptive commit batching: chunk by message budget (skewed
+                // corpora pack thousands of tiny conversations per commit
+                // instead of thrashing WAL one conversation at a time), and
+                // let observed commit latency steer the budget.
+                let mut start = 0;
+                while start < refs.len() {
+                    let end = batcher.plan_chunk_end(&message_counts, start, conversation_cap);
                     let chunk_refs = &refs[start..end];
+                    let chunk_messages: usize = message_counts[start..end].iter().sum();
+                    let commit_started = Instant::now();
                     outcomes.extend(writer.insert_conversations_batched(chunk_refs)?);
+                    batcher.record_commit(
+                        chunk_refs.len(),
+                        chunk_messages,
+                        commit_started.elapsed(),
+                    );
+                    start = end;
                 }
+                batcher.log_summary("serial batched indexing");
 
                 Ok(outcomes)
             },
@@ -26719,6 +27550,7 @@ pub mod persist {
                 embedder: "fnv1a-384".to_string(),
                 progress: None,
                 watch_interval_secs: 30,
+                pause_on_battery: false,
             }
         }
 
@@ -28854,7 +29686,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -28921,7 +29753,7 @@ mod tests {
         );
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -28969,7 +29801,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29013,7 +29845,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29062,7 +29894,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29122,7 +29954,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29258,7 +30090,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29305,7 +30137,7 @@ mod tests {
             crate::connectors::ScanContext::with_roots(temp.path().to_path_buf(), vec![root], None);
 
         let active_filter = ActiveSessionSourceFilter::default();
-        capture_connector_sources_before_parse(
+        let _ = capture_connector_sources_before_parse(
             &connector,
             &ctx,
             &data_dir,
@@ -29491,6 +30323,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_batch_index_with_connector_factories(
@@ -35818,6 +36651,7 @@ mod tests {
             scan_ms: 1,
             is_discovered,
             scan_succeeded: true,
+            files_discovered: 0,
         })
         .expect("done message should send");
     }
@@ -36159,6 +36993,73 @@ mod tests {
         assert!(next_streaming_batch(&mut iter, limits).is_none());
     }
 
+    #[test]
+    fn role_distribution_anomaly_flags_one_sided_long_conversations() {
+        let all_assistant = norm_conv(
+            Some("assistant-only"),
+            (0..6)
+                .map(|i| NormalizedMessage {
+                    role: "assistant".into(),
+                    ..norm_msg(i, 1_000 + i)
+                })
+                .collect(),
+        );
+        assert_eq!(
+            role_distribution_anomaly(&all_assistant),
+            Some("no user turns")
+        );
+
+        let all_user = norm_conv(
+            Some("user-only"),
+            (0..6).map(|i| norm_msg(i, 1_000 + i)).collect(),
+        );
+        assert_eq!(
+            role_distribution_anomaly(&all_user),
+            Some("no assistant turns")
+        );
+
+        let mixed = norm_conv(
+            Some("mixed"),
+            (0..6)
+                .map(|i| NormalizedMessage {
+                    role: if i % 2 == 0 { "user" } else { "assistant" }.into(),
+                    ..norm_msg(i, 1_000 + i)
+                })
+                .collect(),
+        );
+        assert_eq!(role_distribution_anomaly(&mixed), None);
+
+        let short = norm_conv(
+            Some("short"),
+            (0..2)
+                .map(|i| NormalizedMessage {
+                    role: "assistant".into(),
+                    ..norm_msg(i, 1_000 + i)
+                })
+                .collect(),
+        );
+        assert_eq!(
+            role_distribution_anomaly(&short),
+            None,
+            "conversations below the minimum length are exempt"
+        );
+
+        let tool_only = norm_conv(
+            Some("tool-only"),
+            (0..6)
+                .map(|i| NormalizedMessage {
+                    role: "tool".into(),
+                    ..norm_msg(i, 1_000 + i)
+                })
+                .collect(),
+        );
+        assert_eq!(
+            role_distribution_anomaly(&tool_only),
+            None,
+            "conversations with neither side present are not classified as one-sided"
+        );
+    }
+
     #[test]
     fn next_streaming_batch_keeps_single_oversized_conversation_isolated() {
         let limits = StreamingBatchLimits {
@@ -36361,6 +37262,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn streaming_batch_sender_records_queue_depth_and_backpressure_waits() {
+        let (tx, rx) = bounded(1);
+        let limiter = Arc::new(StreamingByteLimiter::new(STREAMING_MAX_BYTES_IN_FLIGHT));
+        let telemetry = Arc::new(StreamingQueueTelemetry::default());
+        let mut sender = StreamingBatchSender::new(&tx, limiter.clone(), "codex", true)
+            .with_queue_telemetry(telemetry.clone());
+
+        // First flush lands in the free slot without blocking.
+        sender
+            .push(norm_conv(Some("first"), vec![norm_msg(0, 1_000)]))
+            .unwrap();
+        sender.flush().unwrap();
+        let after_first = telemetry.snapshot();
+        assert_eq!(after_first.batches_sent, 1);
+        assert_eq!(after_first.queue_depth_high_water, 1);
+        assert_eq!(after_first.send_wait_count, 0);
+        assert_eq!(after_first.send_wait_ms, 0);
+
+        // Second flush finds the channel full and must block until the
+        // consumer drains a slot; that wait is a counted backpressure event.
+        sender
+            .push(norm_conv(Some("second"), vec![norm_msg(0, 2_000)]))
+            .unwrap();
+        let reader = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(25));
+            for _ in 0..2 {
+                match rx.recv().expect("sender should deliver both batches") {
+                    IndexMessage::Batch {
+                        byte_reservation, ..
+                    } => limiter.release(byte_reservation),
+                    other => panic!(
+                        "expected batch message, got {:?}",
+                        std::mem::discriminant(&other)
+                    ),
+                }
+            }
+        });
+        sender.flush().unwrap();
+        reader
+            .join()
+            .expect("reader thread should drain both batches");
+
+        let after_second = telemetry.snapshot();
+        assert_eq!(after_second.batches_sent, 2);
+        assert_eq!(after_second.send_wait_count, 1);
+        assert!(
+            after_second.send_wait_ms >= 1,
+            "blocked send should record a non-zero wait, got {}",
+            after_second.send_wait_ms
+        );
+    }
+
     #[test]
     fn streaming_byte_limiter_blocks_until_capacity_is_released() {
         let limiter = Arc::new(StreamingByteLimiter::new(64));
@@ -37221,6 +38175,7 @@ mod tests {
             scan_ms: 42,
             is_discovered: true,
             scan_succeeded: true,
+            files_discovered: 0,
         })
         .unwrap();
         drop(tx);
@@ -37252,6 +38207,63 @@ mod tests {
         assert!(!mutations.scan_had_errors);
     }
 
+    #[test]
+    fn streaming_consumer_records_per_connector_parsing_stats() {
+        let tmp = TempDir::new().unwrap();
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let db_path = data_dir.join("db.sqlite");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        ensure_fts_schema(&storage);
+        let mut index = TantivyIndex::open_or_create(&index_dir(&data_dir).unwrap()).unwrap();
+        let progress = Arc::new(IndexingProgress::default());
+        let (tx, rx) = bounded(4);
+
+        tx.send(IndexMessage::ScanError {
+            connector_name: "claude",
+            error: "parse failed: /sessions/broken.jsonl".to_string(),
+        })
+        .unwrap();
+        tx.send(IndexMessage::Done {
+            connector_name: "claude",
+            scan_ms: 42,
+            is_discovered: true,
+            scan_succeeded: false,
+            files_discovered: 7,
+        })
+        .unwrap();
+        drop(tx);
+
+        run_streaming_consumer(
+            rx,
+            1,
+            &storage,
+            &data_dir,
+            Some(&mut index),
+            Arc::new(StreamingByteLimiter::new(STREAMING_MAX_BYTES_IN_FLIGHT)),
+            &Some(progress.clone()),
+            LexicalPopulationStrategy::IncrementalInline,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let stats = progress.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let claude = stats
+            .connectors
+            .iter()
+            .find(|c| c.name == "claude")
+            .expect("claude connector stats");
+        assert_eq!(claude.files_discovered, 7);
+        assert_eq!(claude.files_parsed, 0);
+        assert_eq!(claude.parse_failures, 1);
+        assert_eq!(
+            claude.parse_failure_samples,
+            vec!["parse failed: /sessions/broken.jsonl".to_string()]
+        );
+    }
+
     #[test]
     fn streaming_consumer_does_not_watermark_failed_discovered_connector() -> Result<()> {
         let tmp = TempDir::new()?;
@@ -37276,6 +38288,7 @@ mod tests {
             scan_ms: 42,
             is_discovered: true,
             scan_succeeded: false,
+            files_discovered: 0,
         })
         .map_err(|_| anyhow::anyhow!("done message should send"))?;
         drop(tx);
@@ -37336,6 +38349,7 @@ mod tests {
             scan_ms: 42,
             is_discovered: true,
             scan_succeeded: true,
+            files_discovered: 0,
         })
         .map_err(|_| anyhow::anyhow!("done message should send"))?;
         drop(tx);
@@ -38923,6 +39937,7 @@ mod tests {
                 local_since_ts_by_connector: Arc::new(HashMap::new()),
                 progress: Some(progress.clone()),
                 active_source_filter: Arc::new(ActiveSessionSourceFilter::default()),
+                queue_telemetry: Arc::new(StreamingQueueTelemetry::default()),
             },
         );
 
@@ -38991,6 +40006,7 @@ mod tests {
             embedder: String::from("fastembed"),
             progress: Some(progress),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_streaming_index_with_connector_factories(
@@ -39076,6 +40092,7 @@ mod tests {
             embedder: String::from("fastembed"),
             progress: Some(progress),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_streaming_index_with_connector_factories(
@@ -39122,6 +40139,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: Some(progress.clone()),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let error = run_streaming_index_with_connector_factories(
@@ -39184,6 +40202,7 @@ mod tests {
             embedder: String::from("fastembed"),
             progress: Some(progress),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_batch_index_with_connector_factories(
@@ -39267,6 +40286,7 @@ mod tests {
             embedder: String::from("fastembed"),
             progress: Some(progress),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_batch_index_with_connector_factories(
@@ -39319,6 +40339,7 @@ mod tests {
                 embedder: String::from("fastembed"),
                 progress: Some(progress.clone()),
                 watch_interval_secs: 30,
+                pause_on_battery: false,
             };
 
             let mutations = run_batch_index_with_connector_factories(
@@ -39383,6 +40404,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: Some(progress.clone()),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let mutations = run_batch_index_with_connector_factories(
@@ -39457,6 +40479,7 @@ mod tests {
                 local_since_ts_by_connector: Arc::new(HashMap::new()),
                 progress: None,
                 active_source_filter: Arc::new(ActiveSessionSourceFilter::default()),
+                queue_telemetry: Arc::new(StreamingQueueTelemetry::default()),
             },
         );
 
@@ -40377,6 +41400,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         }
     }
 
@@ -44469,6 +45493,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
         let index_path = index_dir(&opts.data_dir).unwrap();
@@ -44530,6 +45555,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         run_index(opts(&data_dir, &session), None).unwrap();
@@ -44589,6 +45615,7 @@ mod tests {
             embedder: "hash".to_string(),
             progress: Some(progress),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         }
     }
 
@@ -45052,6 +46079,7 @@ mod tests {
             progress: None,
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         // Manually set up dependencies for reindex_paths
@@ -45144,6 +46172,7 @@ mod tests {
             progress: Some(progress.clone()),
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45241,6 +46270,7 @@ mod tests {
             progress: None,
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45336,6 +46366,7 @@ mod tests {
             progress: Some(progress.clone()),
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45421,6 +46452,7 @@ mod tests {
             progress: None,
             watch_once_paths: Some(vec![amp_file.clone()]),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45515,6 +46547,7 @@ mod tests {
             progress: None,
             watch_once_paths: Some(vec![amp_file.clone()]),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45605,6 +46638,7 @@ mod tests {
             progress: None,
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45733,6 +46767,7 @@ mod tests {
             progress: None,
             watch_once_paths: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45853,6 +46888,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -45918,6 +46954,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -46015,6 +47052,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: Some(progress.clone()),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -46089,6 +47127,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: Some(progress.clone()),
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -46165,6 +47204,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -46292,6 +47332,7 @@ mod tests {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
 
         let storage = FrankenStorage::open(&opts.db_path).unwrap();
@@ -0,0 +1,334 @@
+//! Opt-in OCR pass for image attachments (screenshot-only errors).
+//!
+//! Pasting a screenshot of a stack trace into an agent chat leaves the
+//! session unsearchable: the message body is empty or a placeholder and the
+//! actual error text lives in pixels. When `CASS_OCR=1` is set, ingest runs
+//! each image attachment through an external OCR command (`tesseract
+//! <image> stdout` by default, overridable via `CASS_OCR_COMMAND`) and
+//! stores the recognized text on the owning message — both under
+//! `extra.cass.ocr` for provenance and appended to the indexed content in a
+//! clearly labelled `[OCR: <file>]` block so lexical search matches it.
+//!
+//! The pass is strictly best-effort: a missing binary, unreadable file, or
+//! failed recognition logs at debug level and leaves the message untouched.
+//! It is off by default because shelling out per image is slow and tesseract
+//! is not a declared dependency — the hook contract is "any command that
+//! prints text to stdout".
+
+use crate::connectors::NormalizedConversation;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Opt-in switch; anything truthy enables the pass.
+const OCR_ENABLED_ENV: &str = "CASS_OCR";
+
+/// Command template override. Whitespace-split; `{input}` expands to the
+/// image path (appended as a final argument when the placeholder is absent).
+const OCR_COMMAND_ENV: &str = "CASS_OCR_COMMAND";
+
+/// Default OCR invocation when no override is configured.
+const DEFAULT_OCR_COMMAND: &str = "tesseract {input} stdout";
+
+/// Attachment extensions worth OCRing.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff"];
+
+/// Cap on recognized text retained per image; screenshots of logs can OCR
+/// into megabytes of garbage and the useful error text is always early.
+const MAX_OCR_TEXT_BYTES: usize = 16 * 1024;
+
+/// Cap on images OCRed per message.
+const MAX_IMAGES_PER_MESSAGE: usize = 4;
+
+/// Whether the OCR pass is enabled for this run.
+pub(crate) fn ocr_enabled() -> bool {
+    dotenvy::var(OCR_ENABLED_ENV)
+        .map(|v| {
+            let normalized = v.trim().to_ascii_lowercase();
+            !(normalized.is_empty()
+                || normalized == "0"
+                || normalized == "false"
+                || normalized == "off"
+                || normalized == "no")
+        })
+        .unwrap_or(false)
+}
+
+/// The configured OCR command, whitespace-split into argv form.
+fn command_template() -> Vec<String> {
+    let raw = dotenvy::var(OCR_COMMAND_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|_| DEFAULT_OCR_COMMAND.to_string());
+    raw.split_whitespace().map(ToString::to_string).collect()
+}
+
+/// Collect OCR-able image paths referenced by a message's `extra` payload.
+///
+/// Connectors record attachments in several shapes: a top-level
+/// `attachment_refs` / `attachments` array, or the compacted
+/// `cass.attachments` form; entries are either bare path strings or objects
+/// carrying a `path` / `file_path` field. Only absolute paths to existing
+/// files with an image extension qualify — everything else is silently
+/// skipped, matching the advisory nature of attachment metadata.
+pub(crate) fn attachment_image_paths(extra: &serde_json::Value) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let candidates = [
+        extra.get("attachment_refs"),
+        extra.get("attachments"),
+        extra.pointer("/cass/attachments"),
+    ];
+    for list in candidates.into_iter().flatten() {
+        let Some(entries) = list.as_array() else {
+            continue;
+        };
+        for entry in entries {
+            let raw_path = entry.as_str().or_else(|| {
+                entry
+                    .get("path")
+                    .or_else(|| entry.get("file_path"))
+                    .and_then(serde_json::Value::as_str)
+            });
+            let Some(raw_path) = raw_path else {
+                continue;
+            };
+            let path = PathBuf::from(raw_path);
+            if path.is_absolute()
+                && is_image_path(&path)
+                && !paths.contains(&path)
+                && path.is_file()
+            {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image_ext| ext.eq_ignore_ascii_case(image_ext))
+        })
+}
+
+/// Run the OCR command over one image. `None` on any failure — the pass
+/// never turns a broken OCR setup into an indexing error.
+fn run_ocr(template: &[String], image: &Path) -> Option<String> {
+    let (program, args) = template.split_first()?;
+    let image_arg = image.to_string_lossy();
+    let mut expanded: Vec<String> = args
+        .iter()
+        .map(|arg| arg.replace("{input}", image_arg.as_ref()))
+        .collect();
+    if !args.iter().any(|arg| arg.contains("{input}")) {
+        expanded.push(image_arg.into_owned());
+    }
+
+    let output = match Command::new(program).args(&expanded).output() {
+        Ok(output) => output,
+        Err(error) => {
+            tracing::debug!(
+                program,
+                image = %image.display(),
+                error = %error,
+                "OCR command failed to launch; skipping attachment"
+            );
+            return None;
+        }
+    };
+    if !output.status.success() {
+        tracing::debug!(
+            program,
+            image = %image.display(),
+            status = %output.status,
+            "OCR command exited nonzero; skipping attachment"
+        );
+        return None;
+    }
+
+    let mut text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+    if text.len() > MAX_OCR_TEXT_BYTES {
+        let mut cut = MAX_OCR_TEXT_BYTES;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        text.truncate(cut);
+    }
+    Some(text)
+}
+
+/// OCR every image attachment in `conv`, linking recognized text to the
+/// owning message. No-op unless [`ocr_enabled`].
+pub(crate) fn apply_attachment_ocr(conv: &mut NormalizedConversation) {
+    if !ocr_enabled() {
+        return;
+    }
+    let template = command_template();
+    if template.is_empty() {
+        return;
+    }
+    for message in &mut conv.messages {
+        ocr_message_attachments(message, &template);
+    }
+}
+
+fn ocr_message_attachments(
+    message: &mut crate::connectors::NormalizedMessage,
+    template: &[String],
+) {
+    // Idempotency: re-indexing a session that already carries OCR results
+    // must not re-run recognition or double-append the text blocks.
+    if message.extra.pointer("/cass/ocr").is_some() {
+        return;
+    }
+    let images = attachment_image_paths(&message.extra);
+    if images.is_empty() {
+        return;
+    }
+
+    let mut results = Vec::new();
+    for image in images.iter().take(MAX_IMAGES_PER_MESSAGE) {
+        if let Some(text) = run_ocr(template, image) {
+            results.push((image.clone(), text));
+        }
+    }
+    if results.is_empty() {
+        return;
+    }
+
+    for (image, text) in &results {
+        let label = image
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| image.display().to_string());
+        if !message.content.is_empty() {
+            message.content.push_str("\n\n");
+        }
+        message.content.push_str(&format!("[OCR: {label}]\n{text}"));
+    }
+
+    let entries: Vec<serde_json::Value> = results
+        .iter()
+        .map(|(image, text)| {
+            serde_json::json!({
+                "path": image.display().to_string(),
+                "text": text,
+            })
+        })
+        .collect();
+    if !message.extra.is_object() {
+        message.extra = serde_json::json!({});
+    }
+    if let Some(map) = message.extra.as_object_mut() {
+        let cass = map
+            .entry("cass".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(cass_map) = cass.as_object_mut() {
+            cass_map.insert("ocr".to_string(), serde_json::Value::Array(entries));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::NormalizedMessage;
+
+    fn message_with_extra(extra: serde_json::Value) -> NormalizedMessage {
+        NormalizedMessage {
+            idx: 0,
+            role: "user".to_string(),
+            author: None,
+            created_at: Some(1_000),
+            content: "see screenshot".to_string(),
+            extra,
+            snippets: Vec::new(),
+            invocations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn extracts_image_paths_from_all_attachment_shapes() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("error.png");
+        std::fs::write(&image, b"fake png").unwrap();
+        let image_str = image.display().to_string();
+
+        for extra in [
+            serde_json::json!({ "attachment_refs": [image_str] }),
+            serde_json::json!({ "attachments": [{ "path": image_str }] }),
+            serde_json::json!({ "cass": { "attachments": [{ "file_path": image_str }] } }),
+        ] {
+            assert_eq!(attachment_image_paths(&extra), vec![image.clone()]);
+        }
+
+        // Non-image, relative, and missing paths are all skipped.
+        let ignored = serde_json::json!({
+            "attachments": [
+                "notes.txt",
+                "relative.png",
+                dir.path().join("missing.png").display().to_string(),
+            ]
+        });
+        assert!(attachment_image_paths(&ignored).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ocr_appends_labelled_text_and_records_provenance() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("trace.png");
+        std::fs::write(&image, b"fake png").unwrap();
+
+        let mut message = message_with_extra(serde_json::json!({
+            "attachment_refs": [image.display().to_string()],
+        }));
+        // Stand-in OCR command: echoes a recognizable marker plus the path.
+        let template = vec![
+            "echo".to_string(),
+            "recognized".to_string(),
+            "{input}".to_string(),
+        ];
+        ocr_message_attachments(&mut message, &template);
+
+        assert!(
+            message
+                .content
+                .starts_with("see screenshot\n\n[OCR: trace.png]\n")
+        );
+        assert!(message.content.contains("recognized"));
+        let recorded = message
+            .extra
+            .pointer("/cass/ocr/0/text")
+            .and_then(serde_json::Value::as_str)
+            .unwrap();
+        assert!(recorded.starts_with("recognized"));
+
+        // Re-running must be a no-op.
+        let before = message.content.clone();
+        ocr_message_attachments(&mut message, &template);
+        assert_eq!(message.content, before);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_ocr_command_leaves_the_message_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("shot.png");
+        std::fs::write(&image, b"fake png").unwrap();
+
+        let mut message = message_with_extra(serde_json::json!({
+            "attachment_refs": [image.display().to_string()],
+        }));
+        let template = vec!["false".to_string(), "{input}".to_string()];
+        ocr_message_attachments(&mut message, &template);
+        assert_eq!(message.content, "see screenshot");
+        assert!(message.extra.pointer("/cass/ocr").is_none());
+    }
+}
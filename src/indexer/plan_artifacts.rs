@@ -0,0 +1,219 @@
+//! Attach Gemini / Antigravity plan and brainstorming artifacts to their
+//! sessions.
+//!
+//! Google's Gemini CLI and Antigravity tooling write brainstorming notes and
+//! implementation plans as standalone markdown files next to the chat logs,
+//! inside the same per-project hash directory (e.g.
+//! `~/.gemini/tmp/<hash>/plan.md` or an `artifacts/` subdirectory). The
+//! upstream connectors only parse the chat logs, so those design decisions
+//! never reach the index. This pass runs after connector ingestion and
+//! attaches each artifact to the most plausible session as a synthetic
+//! `system` message (author `plan-artifact`), making "why did we do it this
+//! way" searchable alongside the conversation itself.
+//!
+//! Matching is structural first, temporal second: an artifact can only
+//! attach to a conversation whose source file lives in the same hash
+//! directory, and when that directory holds several sessions the artifact
+//! goes to the one whose `[started_at, ended_at]` span is nearest to the
+//! artifact's mtime (within a slack window, since plans are often written
+//! minutes before or after the chat). Attachment is once per artifact path
+//! per conversation — the storage layer skips re-runs — so the pass is safe
+//! to repeat on every index.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::storage::sqlite::FrankenStorage;
+
+/// How far outside a conversation's `[started_at, ended_at]` span an
+/// artifact mtime may fall and still match (plans are commonly saved just
+/// before the session starts or shortly after it ends).
+const MATCH_SLACK_MS: i64 = 6 * 60 * 60 * 1000;
+
+/// Artifacts larger than this are skipped — plan documents are prose, and
+/// anything bigger is almost certainly a data dump that would bloat the
+/// index.
+const MAX_ARTIFACT_BYTES: u64 = 256 * 1024;
+
+/// Subdirectories of the hash dir that hold artifacts regardless of name.
+const ARTIFACT_SUBDIRS: &[&str] = &["artifacts", "brainstorming", "plans"];
+
+/// One session eligible for artifact attachment.
+struct Target {
+    conversation_id: i64,
+    started_at: Option<i64>,
+    ended_at: Option<i64>,
+}
+
+/// Scan the hash directories of indexed Gemini/Antigravity sessions for
+/// plan/brainstorming artifacts and attach new ones. Returns the number of
+/// artifacts attached this run.
+pub(crate) fn attach_plan_artifacts(storage: &FrankenStorage) -> Result<usize> {
+    let mut by_dir: BTreeMap<PathBuf, Vec<Target>> = BTreeMap::new();
+    for (conversation_id, source_path, started_at, ended_at) in storage.plan_artifact_targets()? {
+        let Some(dir) = Path::new(&source_path).parent() else {
+            continue;
+        };
+        by_dir.entry(dir.to_path_buf()).or_default().push(Target {
+            conversation_id,
+            started_at,
+            ended_at,
+        });
+    }
+
+    let mut attached = 0usize;
+    for (dir, targets) in &by_dir {
+        for artifact in artifact_files(dir) {
+            let Ok(metadata) = std::fs::metadata(&artifact) else {
+                continue;
+            };
+            if metadata.len() > MAX_ARTIFACT_BYTES {
+                continue;
+            }
+            let mtime_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            let Some(conversation_id) = match_conversation(targets, mtime_ms) else {
+                continue;
+            };
+            let Ok(text) = std::fs::read_to_string(&artifact) else {
+                continue;
+            };
+            let name = artifact
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let content = format!("[plan artifact] {name}\n\n{text}");
+            if storage.attach_plan_artifact_message(
+                conversation_id,
+                &artifact.to_string_lossy(),
+                mtime_ms,
+                &content,
+            )? {
+                attached += 1;
+            }
+        }
+    }
+    Ok(attached)
+}
+
+/// Artifact candidates for one hash directory: plan-named markdown files in
+/// the directory itself, plus every markdown file in the dedicated artifact
+/// subdirectories.
+fn artifact_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_markdown(dir, false, &mut files);
+    for sub in ARTIFACT_SUBDIRS {
+        collect_markdown(&dir.join(sub), true, &mut files);
+    }
+    files.sort();
+    files
+}
+
+fn collect_markdown(dir: &Path, any_name: bool, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if is_markdown(name) && (any_name || is_plan_artifact_name(name)) {
+            out.push(path);
+        }
+    }
+}
+
+fn is_markdown(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".markdown")
+}
+
+/// Whether a loose file in the hash dir reads as a plan/brainstorming
+/// artifact. Deliberately name-based: the hash dir also holds checkpoints
+/// and logs that must never be ingested as plans.
+fn is_plan_artifact_name(name: &str) -> bool {
+    let stem = name.to_ascii_lowercase();
+    ["plan", "brainstorm", "design", "spec"]
+        .iter()
+        .any(|marker| stem.contains(marker))
+}
+
+/// The conversation an artifact belongs to. A lone session in the hash dir
+/// claims every artifact (the common case — Gemini uses one dir per
+/// project/session); with several sessions the artifact goes to the one
+/// whose time span is nearest the mtime, and only within the slack window.
+fn match_conversation(targets: &[Target], mtime_ms: i64) -> Option<i64> {
+    match targets {
+        [] => None,
+        [only] => Some(only.conversation_id),
+        many => many
+            .iter()
+            .filter_map(|t| {
+                let start = t.started_at?;
+                let end = t.ended_at.unwrap_or(start);
+                let distance = if mtime_ms < start {
+                    start - mtime_ms
+                } else if mtime_ms > end {
+                    mtime_ms - end
+                } else {
+                    0
+                };
+                (distance <= MATCH_SLACK_MS).then_some((distance, t.conversation_id))
+            })
+            .min()
+            .map(|(_, id)| id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(conversation_id: i64, started_at: Option<i64>, ended_at: Option<i64>) -> Target {
+        Target {
+            conversation_id,
+            started_at,
+            ended_at,
+        }
+    }
+
+    #[test]
+    fn plan_names_match_and_logs_do_not() {
+        assert!(is_plan_artifact_name("implementation-plan.md"));
+        assert!(is_plan_artifact_name("Brainstorm Notes.md"));
+        assert!(is_plan_artifact_name("api_design.markdown"));
+        assert!(!is_plan_artifact_name("logs.md"));
+        assert!(!is_plan_artifact_name("checkpoint-2.md"));
+    }
+
+    #[test]
+    fn lone_session_claims_artifacts_without_a_timestamp_match() {
+        let targets = vec![target(7, None, None)];
+        assert_eq!(match_conversation(&targets, 0), Some(7));
+    }
+
+    #[test]
+    fn nearest_session_within_slack_wins_and_outside_slack_is_skipped() {
+        let hour = 60 * 60 * 1000;
+        let targets = vec![
+            target(1, Some(0), Some(hour)),
+            target(2, Some(10 * hour), Some(11 * hour)),
+        ];
+        // Two hours after session 1 ended: within slack, nearer than 2.
+        assert_eq!(match_conversation(&targets, 3 * hour), Some(1));
+        // Inside session 2's span.
+        assert_eq!(match_conversation(&targets, 10 * hour + 1), Some(2));
+        // A day past everything: no match rather than a wrong one.
+        assert_eq!(match_conversation(&targets, 40 * hour), None);
+    }
+}
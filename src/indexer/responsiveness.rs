@@ -1279,18 +1279,99 @@ static GOVERNOR: LazyLock<Arc<Governor>> = LazyLock::new(|| {
 /// static `GOVERNOR` is constructed at most once per process, but the
 /// disable signal is honored at every read site.
 pub(crate) fn current_capacity_pct() -> u32 {
+    let ceiling = capacity_ceiling_pct();
     if disabled_via_env() {
-        return 100;
+        return ceiling;
     }
     let g = GOVERNOR.clone();
     g.ensure_started();
-    g.current_capacity.load(Ordering::Relaxed)
+    g.current_capacity.load(Ordering::Relaxed).min(ceiling)
 }
 
 pub(crate) fn disabled_via_env() -> bool {
     env_bool_truthy("CASS_RESPONSIVENESS_DISABLE")
 }
 
+/// Capacity ceiling applied by unobtrusive ("nice") indexing mode when no
+/// explicit `--max-cpu` / `CASS_MAX_CPU_PCT` percentage is configured.
+const NICE_MODE_CAPACITY_PCT: u32 = 50;
+
+/// Hard capacity ceiling, as a percentage of the governed worker budget.
+/// 100 = no ceiling. Unlike the adaptive capacity above, this is a static
+/// user request ("stay quiet during my meeting"), so it caps the published
+/// capacity even when the governor itself is disabled via env.
+static CAPACITY_CEILING_PCT: AtomicU32 = AtomicU32::new(100);
+
+/// Configure the unobtrusive-mode capacity ceiling for this process.
+/// Precedence: an explicit `max_cpu_pct` (the `--max-cpu` flag), then the
+/// `CASS_MAX_CPU_PCT` config knob, then the fixed nice-mode default when
+/// `nice` is set. Percentages are clamped to `[10, 100]` so a typo cannot
+/// stall the pipeline. In nice mode the process priority is also lowered
+/// (best-effort, Unix only) so whatever workers do run yield readily to
+/// interactive work.
+pub(crate) fn configure_unobtrusive_mode(nice: bool, max_cpu_pct: Option<u32>) {
+    let Some(ceiling) = resolve_capacity_ceiling(nice, max_cpu_pct, env_u32("CASS_MAX_CPU_PCT"))
+    else {
+        return;
+    };
+    CAPACITY_CEILING_PCT.store(ceiling, Ordering::Relaxed);
+    tracing::info!(
+        ceiling_pct = ceiling,
+        nice,
+        "indexing capacity ceiling configured"
+    );
+    if nice {
+        lower_process_priority();
+    }
+}
+
+pub(crate) fn capacity_ceiling_pct() -> u32 {
+    CAPACITY_CEILING_PCT.load(Ordering::Relaxed).clamp(10, 100)
+}
+
+/// Pure precedence + clamping for the unobtrusive-mode ceiling: explicit
+/// flag, then config env, then the nice-mode default. `None` = no ceiling
+/// requested.
+fn resolve_capacity_ceiling(
+    nice: bool,
+    max_cpu_pct: Option<u32>,
+    env_max_cpu_pct: Option<u32>,
+) -> Option<u32> {
+    max_cpu_pct
+        .or(env_max_cpu_pct)
+        .or(nice.then_some(NICE_MODE_CAPACITY_PCT))
+        .map(|pct| pct.clamp(10, 100))
+}
+
+/// Best-effort `renice` of the current process so nice-mode worker threads
+/// lose CPU arbitration to interactive work. Shells out rather than taking
+/// a libc dependency for one syscall; failure (missing tool, permission)
+/// is logged at debug and ignored — the capacity ceiling alone already
+/// delivers most of the quieting.
+#[cfg(unix)]
+fn lower_process_priority() {
+    let pid = std::process::id().to_string();
+    match std::process::Command::new("renice")
+        .args(["-n", "10", "-p", &pid])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+    {
+        Ok(status) if status.success() => {
+            tracing::debug!("lowered process priority for nice-mode indexing");
+        }
+        Ok(status) => {
+            tracing::debug!(?status, "renice exited non-zero; keeping default priority");
+        }
+        Err(err) => {
+            tracing::debug!(error = %err, "renice unavailable; keeping default priority");
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn lower_process_priority() {}
+
 /// Scale a caller-requested worker count by the current governor capacity.
 /// Callers pass the *maximum* fan-out they would like (e.g. CPU count minus
 /// reserved cores); the governor returns a bounded count that respects the
@@ -1581,6 +1662,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn capacity_ceiling_precedence_is_flag_then_env_then_nice_default() {
+        // No nice mode and no percentage anywhere: no ceiling.
+        assert_eq!(resolve_capacity_ceiling(false, None, None), None);
+        // Nice mode alone falls back to the fixed default.
+        assert_eq!(
+            resolve_capacity_ceiling(true, None, None),
+            Some(NICE_MODE_CAPACITY_PCT)
+        );
+        // The config env overrides the nice default...
+        assert_eq!(resolve_capacity_ceiling(true, None, Some(80)), Some(80));
+        // ...and the explicit flag overrides both.
+        assert_eq!(resolve_capacity_ceiling(true, Some(30), Some(80)), Some(30));
+        // Out-of-range values clamp so a typo cannot stall the pipeline.
+        assert_eq!(resolve_capacity_ceiling(false, Some(1), None), Some(10));
+        assert_eq!(resolve_capacity_ceiling(false, Some(400), None), Some(100));
+    }
+
     #[test]
     fn macos_vm_stat_parser_defaults_page_size_and_rejects_empty() {
         // No recognizable page lines -> None (caller falls back to no throttle,
@@ -1,17 +1,29 @@
 #![recursion_limit = "256"]
+//! Unified search over local coding-agent histories.
+//!
+//! This crate is primarily the `cass` binary. For embedding search into other
+//! tools, use the [`api`] module — it is the only surface with a stability
+//! guarantee; everything else is internal and changes between releases.
 
 pub mod analytics;
+pub mod api;
+pub mod archive_import;
+pub mod audit;
 pub mod bakeoff;
 pub mod bookmarks;
 pub mod connector_ingest_diagnostics;
+pub mod connector_registry;
 pub mod connectors;
 pub mod context_pack;
+pub mod corrections;
 pub mod crash_replay;
 #[cfg(unix)]
 pub mod daemon;
 pub mod daemon_runtime_state;
+pub mod delta;
 pub mod dependency_drift;
 pub mod dependency_pin_correlation;
+pub mod distill;
 pub mod doctor;
 pub(crate) mod doctor_chokepoint;
 pub mod doctor_recover;
@@ -29,22 +41,33 @@ pub mod fleet_platform_compat;
 pub mod fleet_probe;
 pub mod fleet_upgrade_rehearsal;
 pub mod fleet_version_skew;
+pub mod focus;
 pub mod ftui_harness;
+pub mod git_activity;
 pub mod guide_planner;
 pub mod html_export;
 pub mod incident_discovery;
 pub mod indexer;
 pub mod lessons;
 pub mod lessons_extraction;
+pub mod lineage;
 pub mod metric_integrity;
 pub mod model;
+pub mod model_usage;
+pub mod native_export;
+pub mod ngrams;
+pub mod notes;
 pub mod pages;
 pub mod perf_evidence;
 pub mod policy_registry;
 pub mod privacy_exposure;
+pub mod profiles;
 pub mod proof_artifact;
 pub mod query_cost_planner;
+pub mod ranking_bench;
+pub mod ranking_script;
 pub mod raw_mirror;
+pub mod read_status;
 pub(crate) mod recipes_robot_docs;
 pub(crate) mod recovery_support_bundle;
 pub mod release_verify;
@@ -53,23 +76,40 @@ pub mod resource_plan;
 pub mod robot_budget_envelope;
 pub mod root_cause_projection;
 pub mod root_cause_taxonomy;
+pub mod schedule;
 pub mod search;
 pub mod search_defaults;
+pub mod search_profile;
 pub mod search_quality_eval;
+pub mod secrets;
+pub mod session_compare;
+pub mod session_metrics;
+pub mod share;
 pub mod source_doctor_health;
 pub mod source_onboarding;
 pub mod sources;
 pub mod storage;
+pub mod stream_import;
 pub mod subsystem_coverage_matrix;
 pub mod swarm_replay_fixture;
 pub mod swarm_status;
+pub mod title_heuristics;
+pub mod token_budget;
 pub mod top_session_summary;
 pub mod topology_budget;
 pub mod tui_asciicast;
 pub mod ui;
 pub mod update_check;
+pub mod user_meta;
+pub mod view_state;
+pub mod watch_control;
+pub mod watch_notify;
 pub mod workflow_analytics;
 pub mod workflow_macros;
+pub mod workspace_inference;
+pub mod workspace_keys;
+pub mod workspace_rollup;
+pub mod workspace_relink;
 
 use anyhow::Result;
 use base64::prelude::*;
@@ -264,6 +304,11 @@ pub struct Cli {
     #[arg(long, value_enum, global = true)]
     pub robot_format: Option<RobotFormat>,
 
+    /// Use a named profile (its own database, config, and connector set);
+    /// overrides CASS_PROFILE and the registry's active entry for this run
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -317,6 +362,19 @@ pub enum Commands {
         /// logged and the TUI opens on the existing index (non-fatal).
         #[arg(long, visible_alias = "catch-up", default_value_t = false)]
         refresh: bool,
+
+        /// Force ASCII-safe rendering: no box-drawing borders or decorative
+        /// Unicode glyphs (equivalent to CASS_ASCII=1; auto-enabled on
+        /// legacy Windows consoles)
+        #[arg(long, default_value_t = false)]
+        ascii: bool,
+
+        /// Theme preset for this run (equivalent to CASS_THEME; overrides
+        /// the persisted theme). Accepts e.g. dark, light, high-contrast,
+        /// solarized-dark, solarized-light, nord, dracula — F2 cycles the
+        /// full list in-app
+        #[arg(long)]
+        theme: Option<String>,
     },
     /// Run indexer
     Index {
@@ -342,6 +400,28 @@ pub enum Commands {
         #[arg(long, default_value_t = 30)]
         watch_interval: u64,
 
+        /// In watch mode, hold off on scan cycles while the machine runs on
+        /// battery power (Linux; platforms without power reporting never pause)
+        #[arg(long, default_value_t = false)]
+        pause_on_battery: bool,
+
+        /// Unobtrusive mode: cap worker fan-out and write throughput at half
+        /// the governed budget and lower process priority so indexing stays
+        /// quiet on a shared machine. Default in --watch; see --max-cpu to
+        /// pick the exact ceiling.
+        #[arg(long, default_value_t = false, conflicts_with = "no_nice")]
+        nice: bool,
+
+        /// Opt out of the watch-mode --nice default and index at full speed
+        #[arg(long, default_value_t = false)]
+        no_nice: bool,
+
+        /// Cap indexing CPU as a percentage of the governed worker budget
+        /// (e.g. `50` or `50%`; clamped to 10-100). Also honored via the
+        /// CASS_MAX_CPU_PCT env var; overrides the --nice default ceiling.
+        #[arg(long, value_name = "PERCENT")]
+        max_cpu: Option<String>,
+
         /// Build semantic vector index after text indexing
         #[arg(long)]
         semantic: bool,
@@ -387,6 +467,18 @@ pub enum Commands {
         #[arg(value_enum)]
         shell: clap_complete::Shell,
     },
+    /// Print indexed workspace paths, most recently active first, for shell
+    /// completion of `workspace:` / `--workspace` values
+    CompleteWorkspaces {
+        /// Only print workspaces starting with this prefix
+        prefix: Option<String>,
+        /// Override database path
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Max entries to print (0 = unlimited)
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
     /// Generate man page to stdout
     Man,
     /// Machine-focused docs for automation agents
@@ -397,7 +489,9 @@ pub enum Commands {
     },
     /// Run a one-off search and print results to stdout
     Search {
-        /// The query string
+        /// The query string. Supports AND/OR/NOT, quoted phrases, and inline
+        /// field terms with grouping, e.g.
+        /// `(agent:codex OR agent:claude) AND "segfault" NOT workspace:playground`.
         query: String,
         /// Filter by agent slug (can be specified multiple times)
         #[arg(long)]
@@ -455,15 +549,27 @@ pub enum Commands {
         #[arg(long)]
         week: bool,
         /// Filter to entries since ISO date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS),
-        /// keyword (`today`, `yesterday`, `now`), or relative offset
-        /// (`-7d`, `-24h`, `-30m`, `-1w`). `allow_hyphen_values` lets the
+        /// keyword (`today`, `yesterday`, `now`), relative offset
+        /// (`-7d`, `-24h`, `-30m`, `-1w`), or natural-language date
+        /// (`last monday`, `nov 3`). `allow_hyphen_values` lets the
         /// dash-prefixed forms pass clap without requiring the equals syntax
         /// (reality-check bead hr0z4).
         #[arg(long, allow_hyphen_values = true)]
         since: Option<String>,
-        /// Filter to entries until ISO date / keyword / relative offset.
+        /// Filter to entries until ISO date / keyword / relative offset /
+        /// natural-language date.
         #[arg(long, allow_hyphen_values = true)]
         until: Option<String>,
+        /// Interpret --since/--until dates without an explicit timezone as
+        /// UTC midnight instead of local midnight.
+        #[arg(long, default_value_t = false)]
+        utc: bool,
+        /// Time-travel cutoff: only match messages created at or before this
+        /// instant (ISO date/datetime, keyword, or relative offset), letting
+        /// you reconstruct "what did I know at time T". Tightens --until when
+        /// both are given.
+        #[arg(long, allow_hyphen_values = true)]
+        as_of: Option<String>,
         /// Server-side aggregation by field(s). Comma-separated: `agent,workspace,date,match_type`
         /// Returns buckets with counts instead of full results. Use with --limit to get both.
         #[arg(long, value_delimiter = ',')]
@@ -487,6 +593,13 @@ pub enum Commands {
         /// Enables chained searches: `cass search "query1" --robot-format sessions | cass search "query2" --sessions-from -`
         #[arg(long)]
         sessions_from: Option<String>,
+        /// Collapse message hits into conversations with per-conversation best
+        /// score and hit counts (aggregated in the search layer, not client side)
+        #[arg(long, value_enum)]
+        group_by: Option<crate::search::result_grouping::GroupBy>,
+        /// Result sort order: score (default), recent, messages, workspace
+        #[arg(long, value_enum)]
+        sort: Option<crate::search::result_grouping::ResultSort>,
         /// Search mode: hybrid-preferred (default), lexical, or semantic
         #[arg(long, value_enum)]
         mode: Option<crate::search::query::SearchMode>,
@@ -552,6 +665,35 @@ pub enum Commands {
         /// and the search runs against the existing index (non-fatal).
         #[arg(long, visible_alias = "catch-up", default_value_t = false)]
         refresh: bool,
+
+        /// Skip the configured user ranking script for this invocation and
+        /// use built-in ranking only (see `[search] ranking_script`).
+        #[arg(long, default_value_t = false)]
+        no_user_ranking: bool,
+
+        /// Include hits matching detected cross-conversation boilerplate
+        /// (repeated system prompts, environment dumps). Excluded from
+        /// ranking by default.
+        #[arg(long, default_value_t = false)]
+        include_boilerplate: bool,
+
+        /// Drop hits whose content is a copy of a file currently on disk in
+        /// the hit's workspace (whole files pasted into the chat). Such
+        /// hits are kept but down-ranked by default.
+        #[arg(long, default_value_t = false)]
+        exclude_pasted: bool,
+
+        /// Write the returned hit rows (score, agent, workspace, timestamp,
+        /// title, snippet, permalink) to a file for spreadsheet analysis.
+        /// Format follows the extension: `.csv` (RFC 4180 quoting) or
+        /// `.json` (array of row objects). Terminal output is unchanged.
+        #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+        out: Option<PathBuf>,
+
+        /// Include the full message body as a `content` column in the --out
+        /// rows. Bodies can be large, so they are omitted by default.
+        #[arg(long, default_value_t = false, requires = "out")]
+        out_content: bool,
     },
     /// Build a deterministic answer pack for agent handoffs
     Pack {
@@ -608,12 +750,18 @@ pub enum Commands {
         /// Filter to last 7 days
         #[arg(long)]
         week: bool,
-        /// Filter to entries since ISO date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS).
+        /// Filter to entries since ISO date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS),
+        /// keyword, relative offset, or natural-language date (`last monday`).
         #[arg(long, allow_hyphen_values = true)]
         since: Option<String>,
-        /// Filter to entries until ISO date / keyword / relative offset.
+        /// Filter to entries until ISO date / keyword / relative offset /
+        /// natural-language date.
         #[arg(long, allow_hyphen_values = true)]
         until: Option<String>,
+        /// Interpret --since/--until dates without an explicit timezone as
+        /// UTC midnight instead of local midnight.
+        #[arg(long, default_value_t = false)]
+        utc: bool,
         /// Filter by source: 'local', 'remote', 'all', or a specific source hostname.
         #[arg(long)]
         source: Option<String>,
@@ -790,6 +938,7 @@ pub enum Commands {
         json: bool,
     },
     /// View a source file at a specific line (follow up on search results)
+    #[command(visible_alias = "open")]
     View {
         /// Path to the source file
         path: PathBuf,
@@ -802,6 +951,15 @@ pub enum Commands {
         /// Number of context lines before/after
         #[arg(long, short = 'C', default_value_t = 5)]
         context: usize,
+        /// Time-travel cutoff: truncate the conversation to messages created
+        /// at or before this instant (ISO date/datetime, keyword, or
+        /// relative offset), reconstructing what was known at that moment.
+        #[arg(long, allow_hyphen_values = true)]
+        as_of: Option<String>,
+        /// Start at the bookmark set in the TUI viewer (key `m`), if one
+        /// exists for this conversation. Ignored when --line is given.
+        #[arg(long, default_value_t = false)]
+        at_bookmark: bool,
         /// Output as JSON (`--robot` also works)
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -1059,6 +1217,56 @@ pub enum Commands {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Trace a conversation's lineage: the sessions it descended from
+    /// (resume chains, Claude sidechain parents, Amp thread trees) and the
+    /// subtasks it spawned
+    Lineage {
+        /// Conversation to trace: row id, harness session/thread id, or
+        /// source path
+        id: String,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Compare two sessions side by side: prompt counts, tool calls, files
+    /// touched, duration, token estimates, and the prompts aligned by turn.
+    /// Useful after running two agents against the same task
+    Compare {
+        /// First conversation: row id, harness session/thread id, or source
+        /// path
+        id_a: String,
+        /// Second conversation, same forms as the first
+        id_b: String,
+        /// Maximum aligned prompt turns to show (default: 10)
+        #[arg(long, default_value_t = 10)]
+        turns: usize,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show recently viewed and recently active sessions ("what was I just
+    /// looking at yesterday?"). Viewed = opened in the TUI or fetched via
+    /// view/expand/get-context; active = latest message activity
+    Recent {
+        /// Maximum sessions per list (default: 10)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Only show unread sessions (activity since they were last opened)
+        #[arg(long)]
+        unread: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// List recent sessions, with optional workspace/current-session filtering
     Sessions {
         /// Filter to sessions for this workspace/project directory
@@ -1070,6 +1278,18 @@ pub enum Commands {
         /// Maximum sessions to return (defaults: 10, or 1 with --current)
         #[arg(long)]
         limit: Option<usize>,
+        /// Only show unread sessions (activity since they were last opened)
+        #[arg(long)]
+        unread: bool,
+        /// Only show sessions with a usefulness score at or above this
+        /// threshold (0.0-1.0). Scores are computed at index time; sessions
+        /// not yet scored are excluded by the filter
+        #[arg(long)]
+        min_quality: Option<f64>,
+        /// Sort by usefulness score (highest first) instead of recency, so
+        /// sessions that went somewhere outrank dead ends
+        #[arg(long, default_value_t = false)]
+        sort_quality: bool,
         /// Output as JSON (for automation)
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -1077,6 +1297,34 @@ pub enum Commands {
         #[arg(long)]
         data_dir: Option<PathBuf>,
     },
+    /// List recent automated (headless/batch) runs — `codex exec`,
+    /// `claude -p`, and similar invocations classified at index time —
+    /// separately from interactive sessions. Shows the derived exit status
+    /// when the transcript echoed one
+    Runs {
+        /// Maximum runs to return (default: 10)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Only show runs with a derivable non-zero exit status
+        #[arg(long, default_value_t = false)]
+        failed: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Mark every indexed session as read (clears the unread indicators
+    /// shown by `cass sessions` / `cass recent`)
+    MarkRead {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Resolve a session path into a ready-to-run resume command for
     /// its native harness (Claude Code, Codex, OpenCode, pi_agent, Gemini).
     ///
@@ -1151,6 +1399,35 @@ pub enum Commands {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Self-update from GitHub releases with channel selection.
+    ///
+    /// Downloads the checksum-verified installer for the selected channel
+    /// and replaces the current binary atomically. Unlike `cass upgrade`,
+    /// the installer runs as a child process, so after a successful install
+    /// cass offers to apply database migrations with the new binary.
+    ///
+    /// Examples:
+    ///
+    ///   cass self-update                      # stable channel, prompts y/N
+    ///   cass self-update --channel nightly    # newest prerelease build
+    ///   cass self-update --check --json       # status only, for scripts
+    #[command(name = "self-update")]
+    SelfUpdate {
+        /// Release channel to follow
+        #[arg(long, value_enum, default_value_t = crate::update_check::UpdateChannel::Stable)]
+        channel: crate::update_check::UpdateChannel,
+        /// Print current vs latest version and exit. No install.
+        /// Exits 0 when up to date, 1 when an update is available.
+        #[arg(long, default_value_t = false, conflicts_with = "yes")]
+        check: bool,
+        /// Skip the interactive prompts: install the update and apply
+        /// database migrations without asking.
+        #[arg(long, short = 'y', default_value_t = false)]
+        yes: bool,
+        /// Output as JSON (for automation)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Export a conversation to markdown or other formats
     Export {
         /// Path to session file
@@ -1176,6 +1453,32 @@ pub enum Commands {
         #[arg(long)]
         include_skills: bool,
     },
+    /// Render a cleaned, shareable transcript of a conversation
+    ///
+    /// Produces a redaction-aware Markdown or HTML transcript with tool
+    /// noise collapsed behind expandable sections — suitable for pasting
+    /// into a PR description or sending to a teammate.
+    Share {
+        /// Path to session file (as shown in search output)
+        path: PathBuf,
+        /// Exact source_id from search output (e.g. 'local', 'work-laptop')
+        #[arg(long, alias = "source-id")]
+        source: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = crate::share::ShareFormat::Md)]
+        format: crate::share::ShareFormat,
+        /// Scrub secrets from the transcript before rendering
+        #[arg(long, default_value_t = false)]
+        redact: bool,
+        /// Output file (stdout if not specified)
+        #[arg(long, short = 'o', conflicts_with = "clipboard")]
+        output: Option<PathBuf>,
+        /// Copy the rendered transcript to the system clipboard instead of
+        /// printing to stdout. Falls back to stdout with a stderr warning
+        /// when no clipboard tool is available (e.g. headless / SSH).
+        #[arg(long, short = 'c', default_value_t = false)]
+        clipboard: bool,
+    },
     /// Export session as beautiful, self-contained HTML (with optional encryption)
     #[command(name = "export-html")]
     ExportHtml {
@@ -1240,6 +1543,106 @@ pub enum Commands {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Distill durable facts from a workspace's sessions into its agent
+    /// memory file (AGENTS.md / CLAUDE.md)
+    Distill {
+        /// Workspace path (full, or a unique trailing fragment of one)
+        workspace: String,
+        /// Memory file format: 'agents-md' or 'claude-md'
+        #[arg(long, default_value = "agents-md")]
+        format: String,
+        /// Write to this file instead of the workspace's memory file
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Print the distilled block without writing any file
+        #[arg(long)]
+        dry_run: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Summarize a workspace's recent activity: sessions, files touched,
+    /// commands run, and errors hit (the pre-standup report)
+    Delta {
+        /// Workspace path (full, or a unique trailing fragment of one)
+        #[arg(long)]
+        workspace: String,
+        /// Window size looking back from now (e.g. 1d, 12h, 90m), or the
+        /// window's start as an absolute or natural-language date
+        /// (2024-11-03, `last monday`, `nov 3`)
+        #[arg(long, default_value = "1d")]
+        since: String,
+        /// Interpret --since dates without an explicit timezone as UTC
+        /// midnight instead of local midnight
+        #[arg(long, default_value_t = false)]
+        utc: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON instead of Markdown (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Prioritize one workspace during watch-mode indexing: its session
+    /// files skip the scan cooldown so in-progress work is searchable within
+    /// seconds, even while a bulk backfill runs. No arguments shows the
+    /// current focus.
+    Focus {
+        /// Workspace path (full, or a unique trailing fragment of one)
+        workspace: Option<String>,
+        /// Focus the most recently active workspace from the index
+        #[arg(long, conflicts_with_all = ["workspace", "clear"])]
+        auto: bool,
+        /// Clear the focus so all workspaces share the normal schedule
+        #[arg(long, conflicts_with = "workspace")]
+        clear: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show the most frequent phrases (1–3 grams, stopword-filtered) in your
+    /// prompts vs the agent's responses
+    Ngrams {
+        /// Workspace path (full, or a unique trailing fragment of one);
+        /// omit to count across every workspace
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Window size looking back from now (e.g. 30d, 12h, 90m)
+        #[arg(long, default_value = "30d")]
+        since: String,
+        /// Phrases to show per bucket
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Digest the corrections you issue to agents ("no, I meant…", "revert
+    /// that") and roll them up per agent — data for your instructions files
+    Corrections {
+        /// Workspace path (full, or a unique trailing fragment of one);
+        /// omit to scan every workspace
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Window size looking back from now (e.g. 90d, 12h)
+        #[arg(long, default_value = "90d")]
+        since: String,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Show messages around a specific line in a session file
     Expand {
         /// Path to session file
@@ -1257,16 +1660,90 @@ pub enum Commands {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Fetch a message window around an indexed message id (agent-facing
+    /// `get_context` tool: role labels + token estimates, no full-session dump)
+    GetContext {
+        /// Message row id from the index (`messages.id`; echoed back for each
+        /// window entry so follow-up calls can walk the conversation)
+        id: i64,
+        /// Messages to include before the target (default: 3)
+        #[arg(long, short = 'B', default_value_t = 3)]
+        before: usize,
+        /// Messages to include after the target (default: 3)
+        #[arg(long, short = 'A', default_value_t = 3)]
+        after: usize,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show one indexed message; `--raw` prints the original source record
+    /// via its stored byte/line provenance
+    Show {
+        /// Message row id from the index (`messages.id`, as printed by
+        /// get-context and robot search output)
+        id: i64,
+        /// Print the raw source record (exact bytes from the session file)
+        /// instead of the indexed content
+        #[arg(long)]
+        raw: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// View the audit trail of queries served to other tools
+    Audit {
+        /// Maximum entries to show (default: 50)
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Filter by command (e.g. 'search', 'get-context')
+        #[arg(long)]
+        command: Option<String>,
+        /// Apply retention now instead of waiting for the next recorded query
+        #[arg(long, default_value_t = false)]
+        prune: bool,
+        /// Retention window in days for --prune
+        /// (default: CASS_AUDIT_RETENTION_DAYS, then 30; 0 keeps forever)
+        #[arg(long)]
+        retention_days: Option<i64>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Verify conversation integrity seals against recomputation, reporting
+    /// any post-hoc modification of indexed content
+    Verify {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Show activity timeline for a time range
     Timeline {
         /// Start time (ISO date, 'today', 'yesterday', 'Nd' for N days ago,
-        /// or relative `-7d`/`-24h`/`-30m`/`-1w`). `allow_hyphen_values` lets
+        /// relative `-7d`/`-24h`/`-30m`/`-1w`, or a natural-language date
+        /// like `last monday` or `nov 3`). `allow_hyphen_values` lets
         /// dash-prefixed offsets pass clap (reality-check bead hr0z4).
         #[arg(long, allow_hyphen_values = true)]
         since: Option<String>,
-        /// End time (ISO date, keyword, or relative offset).
+        /// End time (ISO date, keyword, relative offset, or natural-language
+        /// date).
         #[arg(long, allow_hyphen_values = true)]
         until: Option<String>,
+        /// Interpret --since/--until dates without an explicit timezone as
+        /// UTC midnight instead of local midnight
+        #[arg(long, default_value_t = false)]
+        utc: bool,
         /// Show today only
         #[arg(long)]
         today: bool,
@@ -1288,6 +1765,54 @@ pub enum Commands {
         #[arg(long)]
         source: Option<String>,
     },
+    /// Render a random sample of recently indexed conversations per agent
+    /// for manual quality inspection (spot parsing regressions — garbled
+    /// roles, missing messages — after upgrading cass or the agent apps)
+    Sample {
+        /// Conversations to sample per agent
+        #[arg(long, default_value_t = 3)]
+        per_agent: usize,
+        /// Size of the recent-conversations pool each agent's sample is
+        /// drawn from (most recently active first)
+        #[arg(long, default_value_t = 50)]
+        pool: usize,
+        /// Filter by agent (can be repeated)
+        #[arg(long)]
+        agent: Vec<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = SampleFormat::Markdown)]
+        format: SampleFormat,
+        /// Seed the sampler for reproducible output
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Parse a directory of session files into an ephemeral in-memory index
+    /// and search it, never touching the persistent database (inspect a
+    /// colleague's exported logs or CI artifacts)
+    Scan {
+        /// Directory (or single file) of session files; connector formats
+        /// are auto-detected
+        path: PathBuf,
+        /// Query to run against the ephemeral index (same syntax as the
+        /// sqlite fallback search); omit to just list what was parsed
+        #[arg(long)]
+        query: Option<String>,
+        /// Only try these connectors (can be repeated; default tries all)
+        #[arg(long)]
+        connector: Vec<String>,
+        /// Max results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Offline quality benchmarks against built-in synthetic fixtures
+    #[command(subcommand)]
+    Bench(BenchCommand),
     /// Export encrypted searchable archive for static hosting (P4.x)
     Pages {
         /// Export only (skip wizard and encryption) to specified directory
@@ -1424,9 +1949,92 @@ pub enum Commands {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Re-derive titles for untitled or boilerplate-titled sessions (the ones
+    /// showing `<environment_context>` or pasted stack traces as their
+    /// headline). Dry-run by default; `--regenerate` to write the new titles
+    Retitle {
+        /// Actually write the derived titles. Without this, runs as a dry-run
+        /// showing what would change.
+        #[arg(long, default_value_t = false)]
+        regenerate: bool,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Inspect and prune raw-mirror evidence under explicit operator control
     #[command(subcommand)]
     Mirror(MirrorCommand),
+    /// Developer debugging utilities (replay parses from raw-mirror evidence)
+    #[command(subcommand)]
+    Debug(DebugCommand),
+    /// Manage credentials in the native OS keychain (tokens, API keys)
+    #[command(subcommand)]
+    Secrets(SecretsCommand),
+    /// Inspect and restore the recycle bin for destructive operations
+    #[command(subcommand)]
+    Trash(TrashCommand),
+    /// Restore the conversations deleted by one destructive operation
+    /// (shorthand for `cass trash restore <operation-id>`)
+    Undo {
+        /// Operation id reported by the destructive command (e.g. `cass forget`)
+        operation_id: String,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Database file maintenance (slice a trimmed copy for small devices)
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Attach freeform notes to conversations and messages
+    #[command(subcommand)]
+    Note(NoteCommand),
+    /// Relink workspaces whose directories moved or were renamed
+    #[command(subcommand)]
+    Workspace(WorkspaceCommand),
+    /// Bulk-apply conversation tags from a search query
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// Install and manage a native scheduler unit for periodic `cass index`
+    #[command(subcommand)]
+    Schedule(ScheduleCommand),
+    /// List known connectors with detection evidence and index activity
+    ///
+    /// Detection results come from the on-disk detection cache so repeated
+    /// runs skip the filesystem probes; `--refresh` forces fresh probes.
+    /// `--enable`/`--disable` flip a connector's indexing switch in
+    /// sources.toml — the same one `cass sources agents include/exclude`
+    /// manages.
+    Agents {
+        /// Force fresh detection probes, bypassing the cache
+        #[arg(long)]
+        refresh: bool,
+
+        /// Re-enable a connector for indexing (updates sources.toml)
+        #[arg(long, value_name = "SLUG")]
+        enable: Option<String>,
+
+        /// Disable a connector for indexing (updates sources.toml)
+        #[arg(long, value_name = "SLUG", conflicts_with = "enable")]
+        disable: Option<String>,
+
+        /// Override data dir (db + detection cache). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
     /// Manage remote sources (P5.x)
     #[command(subcommand)]
     Sources(SourcesCommand),
@@ -1436,6 +2044,9 @@ pub enum Commands {
     /// Fleet-safe upgrade rehearsal and bounded post-upgrade verification
     #[command(subcommand)]
     Fleet(FleetCommand),
+    /// Inspect and control a running `cass index --watch` daemon
+    #[command(subcommand)]
+    Watch(WatchCommand),
     /// Mine and query durable lessons from local evidence (commits, beads, proofs)
     #[command(subcommand)]
     Lessons(LessonsCommand),
@@ -1445,6 +2056,12 @@ pub enum Commands {
     /// Import data from external sources
     #[command(subcommand)]
     Import(ImportCommand),
+    /// Export/import user curation metadata (tags, bookmarks, saved views)
+    #[command(subcommand)]
+    Meta(MetaCommand),
+    /// Export/import search configuration as shareable named profiles
+    #[command(subcommand)]
+    Profile(ProfileCommand),
     /// Token usage, tool, and model analytics
     ///
     /// Subcommands: status, tokens, tools, models, rebuild, validate.
@@ -1619,6 +2236,330 @@ pub enum MirrorCommand {
     },
 }
 
+/// Developer debugging commands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum DebugCommand {
+    /// Rerun the current parser against the raw-mirror capture of an indexed
+    /// conversation. Reads the exact original bytes that produced the stored
+    /// rows, so parser changes can be validated without touching live sources.
+    Reparse {
+        /// Conversation row id, harness session id, or source path
+        conversation: String,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Run one connector's parser against a single session file and print the
+    /// normalized conversations as pretty JSON, without touching the main
+    /// database. Useful for triaging weird session files attached to issues.
+    Parse {
+        /// Session file (or provider db path) to parse
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+
+        /// Connector slug to parse with (see `cass capabilities` for the list)
+        #[arg(long)]
+        connector: String,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Credential storage commands. Values are stored in the native OS keychain
+/// (behind the `keychain` cargo feature) and resolved at runtime with env-var
+/// fallback; output paths always redact the value itself.
+#[derive(Subcommand, Debug, Clone)]
+pub enum SecretsCommand {
+    /// Store a secret in the OS keychain. Reads the value from stdin when not
+    /// given, so it stays out of shell history.
+    Set {
+        /// Secret name, e.g. server_token or openai_api_key
+        name: String,
+
+        /// Secret value; omit to read one line from stdin
+        value: Option<String>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show where a secret resolves from (keychain or env). Redacted unless
+    /// --reveal is passed.
+    Get {
+        /// Secret name
+        name: String,
+
+        /// Print the actual value instead of the redacted form
+        #[arg(long, default_value_t = false)]
+        reveal: bool,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// List known secret names, their purpose, and whether each resolves.
+    List {
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Remove a secret from the OS keychain (env-var fallbacks are untouched).
+    Delete {
+        /// Secret name
+        name: String,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Recycle-bin commands. Destructive operations (`cass forget --apply`)
+/// snapshot deleted conversations into shadow tables first; these commands
+/// inspect, restore, or drop those snapshots. Retention is
+/// `CASS_TRASH_RETENTION_DAYS` (default 30).
+#[derive(Subcommand, Debug, Clone)]
+pub enum TrashCommand {
+    /// List restorable operations, newest first.
+    List {
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Restore every conversation captured by one operation, then drop it
+    /// from the bin.
+    Restore {
+        /// Operation id from `cass trash list`
+        operation_id: String,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Drop recycle-bin snapshots. Dry-run by default; `--apply` deletes.
+    Empty {
+        /// Only drop operations older than a duration such as 7d or 24h
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Actually delete the snapshots. Without this, reports what would go.
+        #[arg(long, default_value_t = false)]
+        apply: bool,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Database file maintenance commands. These operate on the canonical
+/// database file as a whole rather than on individual conversations.
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbCommand {
+    /// Write a trimmed, fully-functional copy of the database containing
+    /// only recent conversations, with FTS and derived stats rebuilt to
+    /// match — a lightweight index to carry onto constrained devices.
+    Slice {
+        /// Keep conversations active within this window (e.g. 90d, 48h)
+        #[arg(long, default_value = "90d")]
+        since: String,
+
+        /// Output path for the sliced database (must not already exist)
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out: PathBuf,
+
+        /// Override db path
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Annotation commands. Notes are freeform text attached to a conversation
+/// or one of its messages, stored in `data_dir/notes.db` (see `crate::notes`)
+/// and searchable through the `note:` inline query field. The TUI viewer
+/// renders them inline and adds one with the `a` key.
+#[derive(Subcommand, Debug, Clone)]
+pub enum NoteCommand {
+    /// Attach a note to a conversation or message.
+    Add {
+        /// Conversation source path, optionally suffixed with the 1-based
+        /// message number shown in the viewer (e.g. `/path/sess.jsonl:14`)
+        target: String,
+
+        /// The note text
+        text: String,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// List notes, newest first.
+    List {
+        /// Only show notes for this conversation source path
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        source: Option<String>,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Remove a note by its id (from `cass note list`).
+    Remove {
+        /// Note id
+        id: i64,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Workspace maintenance commands. Renaming a project directory splits its
+/// history into two workspace rows (old sessions under the dead path, new
+/// ones under the live path); `relink` points the old history at the new
+/// directory and `detect` proposes likely pairs by scanning the index for
+/// dead paths that resemble a live one (see `crate::workspace_relink`).
+#[derive(Subcommand, Debug, Clone)]
+pub enum WorkspaceCommand {
+    /// Point a workspace's history at a moved or renamed directory.
+    ///
+    /// If the new path is not yet indexed this simply renames the workspace
+    /// row; if it is, the old workspace's conversations merge into it and
+    /// analytics/FTS are rebuilt so aggregates follow the move.
+    Relink {
+        /// Workspace path as currently recorded in the index
+        old: String,
+
+        /// Directory the workspace now lives at
+        new: String,
+
+        /// Path to database (defaults to standard location)
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Propose relinks for indexed workspaces whose directory no longer
+    /// exists (read-only; prints the `cass workspace relink` commands).
+    Detect {
+        /// Path to database (defaults to standard location)
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Bulk tagging commands. Tags live in the canonical DB (`tags` +
+/// `conversation_tags`, the same tables `cass meta export` snapshots); this
+/// applies one to every conversation matching a search query instead of one
+/// conversation at a time. Dry-run by default: without `--apply` it prints
+/// the matched-conversation count and a sample, mirroring `cass retitle`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum TagCommand {
+    /// Tag every conversation matching a search query.
+    ///
+    /// The query uses the same syntax as `cass search`, including inline
+    /// fields (e.g. `workspace:~/dev/foo "timeout"`).
+    Add {
+        /// Tag name to apply
+        tag: String,
+
+        /// Search query selecting the conversations to tag
+        #[arg(long)]
+        query: String,
+
+        /// Apply the tag (default is a preview of what would be tagged)
+        #[arg(long)]
+        apply: bool,
+
+        /// Override data dir (db + search index). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Path to database (defaults to standard location)
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Scheduled indexing commands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum ScheduleCommand {
+    /// Install (or replace) a native scheduler unit that runs `cass index`
+    /// periodically: a launchd agent on macOS, a systemd user timer on
+    /// Linux, a Task Scheduler entry on Windows.
+    Install {
+        /// Run interval (e.g. `30m`, `2h`; minimum one minute)
+        #[arg(long, default_value = "30m")]
+        every: String,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show whether the schedule is installed and what the native scheduler
+    /// reports about it.
+    Status {
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Remove the scheduled run.
+    Uninstall {
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
 /// Read-only swarm operations commands.
 #[derive(Subcommand, Debug, Clone)]
 pub enum SwarmCommand {
@@ -1917,6 +2858,194 @@ pub enum ImportCommand {
         #[arg(long)]
         output_dir: Option<PathBuf>,
     },
+    /// Import a ChatGPT data-export archive directly into the search index
+    ///
+    /// Accepts the export zip, the extracted export directory, or
+    /// conversations.json itself. Conversations are indexed under the
+    /// `chatgpt-export` agent slug with imported-origin provenance; no
+    /// separate `cass index` run is needed.
+    ChatgptExport {
+        /// Path to export zip, extracted export directory, or conversations.json
+        #[arg(value_hint = ValueHint::AnyPath)]
+        path: PathBuf,
+
+        /// Override data dir (db + index). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Import a Claude.ai data-export archive directly into the search index
+    ///
+    /// Accepts the export zip, the extracted export directory, or
+    /// conversations.json itself. Conversations are indexed under the
+    /// `claude-export` agent slug with imported-origin provenance.
+    ClaudeExport {
+        /// Path to export zip, extracted export directory, or conversations.json
+        #[arg(value_hint = ValueHint::AnyPath)]
+        path: PathBuf,
+
+        /// Override data dir (db + index). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Import NormalizedConversation JSON objects from an NDJSON stream
+    ///
+    /// Reads one conversation object per line from a file, or from stdin
+    /// when the path is `-`, and persists each through the same
+    /// dedupe/upsert path the connectors use. Re-importing the same stream
+    /// is idempotent, so scripts and CI agents can replay their history on
+    /// every run. Each object needs `agent_slug`, `messages`, and a stable
+    /// identity (`external_id` or `source_path`); bad lines are skipped and
+    /// reported rather than aborting the stream.
+    Ndjson {
+        /// NDJSON file to read, or `-` for stdin
+        #[arg(value_hint = ValueHint::FilePath)]
+        path: PathBuf,
+
+        /// Override data dir (db + index). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Export/import of user curation metadata (tags, bookmarks, saved views).
+#[derive(Subcommand, Debug, Clone)]
+pub enum MetaCommand {
+    /// Write a portable curation snapshot: conversation tags, bookmarks, and
+    /// saved views, keyed by stable conversation identity so the file
+    /// survives re-indexing and machine moves
+    Export {
+        /// Snapshot file to write (e.g. meta.json)
+        #[arg(value_hint = ValueHint::FilePath)]
+        output: PathBuf,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Merge a curation snapshot back in. Never removes existing tags,
+    /// bookmarks, or saved views; duplicates are skipped
+    Import {
+        /// Snapshot file written by `cass meta export`
+        #[arg(value_hint = ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Profile commands: named local profiles that scope the data dir and
+/// config to separate worlds (list/create/switch; see `crate::profiles`),
+/// plus export/import of search configuration as shareable profile files.
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProfileCommand {
+    /// List named local profiles, their data dirs, and which one is active
+    List {
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Register a new named local profile (does not switch to it)
+    Create {
+        /// Profile name (lowercase letters, digits, '-' or '_')
+        name: String,
+
+        /// Explicit data dir for this profile (default:
+        /// `<data dir>/profiles/<name>`)
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Make a profile the default for future runs (`default` to clear)
+    Switch {
+        /// Profile name, or `default` for the unscoped world
+        name: String,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Write the current search configuration — `[search]` and `[tui]`
+    /// defaults, the ranking script (by content), saved views, and disabled
+    /// agents — as one named, versioned profile file a teammate can import
+    Export {
+        /// Profile name (alphanumeric plus `- _ .`)
+        name: String,
+
+        /// Profile file to write (e.g. team-default.json)
+        #[arg(value_hint = ValueHint::FilePath)]
+        output: PathBuf,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Validate a profile file and merge it into the local setup. The
+    /// profile's tables overlay cass.toml (other tables survive), saved
+    /// views append, and disabled agents union; nothing is removed
+    Import {
+        /// Profile file written by `cass profile export`
+        #[arg(value_hint = ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// Validate and report without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// Offline quality benchmarks over built-in synthetic fixtures.
+#[derive(Subcommand, Debug, Clone)]
+pub enum BenchCommand {
+    /// Score the search ranking against the built-in relevance judgments
+    /// (NDCG@k and MRR over an ephemeral index of synthetic conversations),
+    /// so ranking, tokenization, and boost changes can be compared
+    /// quantitatively. Never touches the persistent database
+    Ranking {
+        /// Rank cutoff for NDCG@k
+        #[arg(long, default_value_t = 10)]
+        k: usize,
+
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
 }
 
 /// Subcommands for managing remote sources (P5.x)
@@ -2188,6 +3317,11 @@ pub enum ModelsCommand {
         /// Maximum canonical conversations to process in this batch
         #[arg(long, default_value_t = 64)]
         batch_conversations: usize,
+        /// Drop vectors built by an older revision of this embedder's model
+        /// before the batch runs, so the backfill re-embeds them with the
+        /// installed model instead of letting stale vectors coexist
+        #[arg(long)]
+        refresh_stale: bool,
         /// Apply idle/load scheduler gates before running this batch
         #[arg(long, visible_alias = "background")]
         scheduled: bool,
@@ -2222,6 +3356,21 @@ pub enum ModelsCommand {
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
+    /// Per-model usage statistics from message authors (share, response length, first/last seen)
+    Usage {
+        /// Maximum models to show (default 20)
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Override cass DB path
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
 }
 
 /// Subcommands for fleet-safe upgrade rehearsal and bounded post-upgrade
@@ -2333,6 +3482,38 @@ pub enum LessonsCommand {
     },
 }
 
+/// Subcommands for inspecting and controlling a running `cass index --watch`
+/// daemon. Control flows through `<data_dir>/watch_control.json` rather than
+/// signals (AGENTS.md forbids signal handlers); the watch loop polls the file
+/// every few seconds and writes a status heartbeat on the same cadence.
+#[derive(Subcommand, Debug, Clone)]
+pub enum WatchCommand {
+    /// Show the watch daemon's last heartbeat (state, cycles, interval)
+    Status {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON (`--robot` also works)
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Ask the watch daemon to hold off on scan cycles
+    Pause {
+        /// Optional reason, echoed in `watch status`
+        #[arg(long)]
+        reason: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Resume scan cycles after a pause
+    Resume {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+}
+
 /// Subcommands for managing path mappings (P6.3)
 #[derive(Subcommand, Debug, Clone)]
 pub enum MappingsAction {
@@ -2511,7 +3692,8 @@ pub struct AnalyticsCommon {
     #[arg(long)]
     pub agent: Vec<String>,
 
-    /// Filter by workspace path (can be specified multiple times)
+    /// Filter by workspace path; also matches workspaces nested under it,
+    /// so a monorepo root covers its sub-projects (repeatable)
     #[arg(long)]
     pub workspace: Vec<String>,
 
@@ -2643,6 +3825,9 @@ pub enum RobotFormat {
     Sessions,
     /// Token-Optimized Object Notation (encodes via toon crate)
     Toon,
+    /// MessagePack binary encoding of the compact payload (via rmp-serde).
+    /// Raw bytes on stdout; redirect or pipe, the terminal will show noise.
+    Msgpack,
 }
 
 /// Human-readable display format for CLI output (non-JSON)
@@ -2669,6 +3854,24 @@ pub enum ConvExportFormat {
     Json,
     /// HTML with styling
     Html,
+    /// Codex rollout JSONL (approximate native session format)
+    CodexRollout,
+    /// Claude Code session JSONL (approximate native session format)
+    ClaudeJsonl,
+}
+
+/// Sample rendering format (for sample command)
+#[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Markdown with role headers (alias: md)
+    #[default]
+    #[value(alias = "md")]
+    Markdown,
+    /// Plain text (alias: txt)
+    #[value(alias = "txt")]
+    Text,
+    /// JSON array of sampled conversations
+    Json,
 }
 
 /// Timeline grouping options
@@ -4353,7 +5556,10 @@ fn recover_drilldown_assignment_flags(rest: &mut Vec<String>, corrections: &mut
 }
 
 fn is_robot_format_alias_value(value: &str) -> bool {
-    matches!(value, "json" | "jsonl" | "compact" | "sessions" | "toon")
+    matches!(
+        value,
+        "json" | "jsonl" | "compact" | "sessions" | "toon" | "msgpack"
+    )
 }
 
 fn can_recover_format_alias_for_command(command: &str) -> bool {
@@ -4820,6 +6026,7 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "session",
         "line",
         "context",
+        "at-bookmark",
         "output",
         "output-format",
         "format",
@@ -4828,6 +6035,7 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         // Added flags
         "watch-once",
         "watch-interval",
+        "pause-on-battery",
         "semantic",
         "embedder",
         "idempotency-key",
@@ -4890,6 +6098,16 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "yes",
         "plan-fingerprint",
         "purge",
+        "prune",
+        "retention-days",
+        "command",
+        "no-user-ranking",
+        "include-boilerplate",
+        "turns",
+        "regenerate",
+        "reveal",
+        "older-than",
+        "every",
         "preset",
         "no-test",
         "no-index",
@@ -5952,6 +7170,7 @@ fn heuristic_parse_recovery(
                 "--cursor",
                 "--since",
                 "--until",
+                "--utc",
                 "--days",
                 "--today",
                 "--week",
@@ -6315,6 +7534,8 @@ async fn execute_cli(
                 record_macro: None,
                 play_macro: None,
                 refresh: false,
+                ascii: false,
+                theme: None,
             }
         }
     });
@@ -6398,11 +7619,38 @@ async fn execute_cli(
                 record_macro,
                 play_macro,
                 refresh,
+                ascii,
+                theme,
             } = command.clone()
             {
                 if refresh {
                     refresh_index_inline(cli.db.clone(), data_dir.clone());
                 }
+                if ascii {
+                    // The style system samples CASS_ASCII at startup; the
+                    // flag is sugar for exporting it. Safe: no other threads
+                    // are running this early in the TUI launch path.
+                    unsafe {
+                        std::env::set_var("CASS_ASCII", "1");
+                    }
+                }
+                if let Some(theme) = theme {
+                    if ui::style_system::UiThemePreset::parse(&theme).is_none() {
+                        return Err(CliError::usage(
+                            format!("Unknown theme preset: {theme}"),
+                            Some(
+                                "Try dark, light, high-contrast, solarized-dark, or \
+                                 solarized-light; F2 in the TUI cycles every preset."
+                                    .to_string(),
+                            ),
+                        ));
+                    }
+                    // Same sugar as --ascii: the style system samples
+                    // CASS_THEME at startup.
+                    unsafe {
+                        std::env::set_var("CASS_THEME", &theme);
+                    }
+                }
                 info!(once, inline, ui_height, %anchor, record_macro = ?record_macro, play_macro = ?play_macro, "launching ftui runtime");
 
                 let inline_config = if inline {
@@ -6518,6 +7766,10 @@ async fn execute_cli(
                     watch,
                     watch_once,
                     watch_interval,
+                    pause_on_battery,
+                    nice,
+                    no_nice,
+                    max_cpu,
                     data_dir,
                     semantic,
                     build_hnsw,
@@ -6536,6 +7788,10 @@ async fn execute_cli(
                         watch,
                         watch_once,
                         watch_interval,
+                        pause_on_battery,
+                        nice,
+                        no_nice,
+                        max_cpu,
                         data_dir,
                         semantic,
                         build_hnsw,
@@ -6569,6 +7825,8 @@ async fn execute_cli(
                     week,
                     since,
                     until,
+                    utc,
+                    as_of,
                     aggregate,
                     explain,
                     dry_run,
@@ -6576,6 +7834,8 @@ async fn execute_cli(
                     highlight,
                     source,
                     sessions_from,
+                    group_by,
+                    sort,
                     mode,
                     approximate,
                     model,
@@ -6587,6 +7847,11 @@ async fn execute_cli(
                     fast_only,
                     quality_only,
                     refresh,
+                    no_user_ranking,
+                    include_boilerplate,
+                    exclude_pasted,
+                    out,
+                    out_content,
                 } => {
                     // Validate mutually exclusive two-tier flags
                     let tier_count = [two_tier, fast_only, quality_only]
@@ -6693,7 +7958,9 @@ async fn execute_cli(
                             week,
                             since.as_deref(),
                             until.as_deref(),
-                        ),
+                            utc,
+                        )?
+                        .with_as_of(as_of.as_deref(), utc)?,
                         aggregate,
                         explain,
                         dry_run,
@@ -6701,8 +7968,15 @@ async fn execute_cli(
                         highlight,
                         source,
                         sessions_from,
+                        group_by,
+                        sort,
                         eff_mode,
                         semantic_opts,
+                        no_user_ranking,
+                        include_boilerplate,
+                        exclude_pasted,
+                        out.as_deref(),
+                        out_content,
                     )?;
                 }
                 Commands::Pack {
@@ -6726,6 +8000,7 @@ async fn execute_cli(
                     week,
                     since,
                     until,
+                    utc,
                     source,
                     sessions_from,
                     mode,
@@ -6765,7 +8040,8 @@ async fn execute_cli(
                             week,
                             since.as_deref(),
                             until.as_deref(),
-                        ),
+                            utc,
+                        )?,
                         source,
                         sessions_from,
                         eff_mode,
@@ -6870,6 +8146,8 @@ async fn execute_cli(
                     source,
                     line,
                     context,
+                    as_of,
+                    at_bookmark,
                     json,
                 } => {
                     let structured_format = resolve_subcommand_structured_format(cli, json);
@@ -6879,6 +8157,8 @@ async fn execute_cli(
                         source.as_deref(),
                         line,
                         context,
+                        as_of.as_deref(),
+                        at_bookmark,
                         structured_format,
                     )?;
                 }
@@ -7410,6 +8690,9 @@ async fn execute_cli(
                     let mut cmd = Cli::command();
                     clap_complete::generate(shell, &mut cmd, "cass", &mut std::io::stdout());
                 }
+                Commands::CompleteWorkspaces { prefix, db, limit } => {
+                    run_complete_workspaces(prefix.as_deref(), db, limit, cli)?;
+                }
                 Commands::Man => {
                     let cmd = Cli::command();
                     let man = clap_mangen::Man::new(cmd);
@@ -7787,10 +9070,43 @@ async fn execute_cli(
                         limit,
                     )?;
                 }
+                Commands::Lineage { id, data_dir, json } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_lineage(&id, &data_dir, cli.db.clone(), structured_format)?;
+                }
+                Commands::Compare {
+                    id_a,
+                    id_b,
+                    turns,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_compare(
+                        &id_a,
+                        &id_b,
+                        turns,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Recent {
+                    limit,
+                    unread,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_recent(limit, unread, &data_dir, cli.db.clone(), structured_format)?;
+                }
                 Commands::Sessions {
                     workspace,
                     current,
                     limit,
+                    unread,
+                    min_quality,
+                    sort_quality,
                     json,
                     data_dir,
                 } => {
@@ -7799,11 +9115,27 @@ async fn execute_cli(
                         workspace.as_ref(),
                         current,
                         limit,
+                        unread,
+                        min_quality,
+                        sort_quality,
                         &data_dir,
                         cli.db.clone(),
                         structured_format,
                     )?;
                 }
+                Commands::Runs {
+                    limit,
+                    failed,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_runs(limit, failed, &data_dir, cli.db.clone(), structured_format)?;
+                }
+                Commands::MarkRead { data_dir, json } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_mark_read(&data_dir, structured_format)?;
+                }
                 Commands::Resume {
                     path,
                     agent,
@@ -7823,6 +9155,15 @@ async fn execute_cli(
                     let structured_format = resolve_subcommand_structured_format(cli, json);
                     run_upgrade(check, force, yes, structured_format).await?;
                 }
+                Commands::SelfUpdate {
+                    channel,
+                    check,
+                    yes,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_self_update_command(channel, check, yes, structured_format).await?;
+                }
                 Commands::Export {
                     path,
                     source,
@@ -7843,6 +9184,24 @@ async fn execute_cli(
                         include_skills,
                     )?;
                 }
+                Commands::Share {
+                    path,
+                    source,
+                    format,
+                    redact,
+                    output,
+                    clipboard,
+                } => {
+                    run_share(
+                        &path,
+                        cli.db.clone(),
+                        source.as_deref(),
+                        format,
+                        redact,
+                        output.as_deref(),
+                        clipboard,
+                    )?;
+                }
                 Commands::ExportHtml {
                     session,
                     source,
@@ -7880,6 +9239,91 @@ async fn execute_cli(
                         structured_format,
                     )?;
                 }
+                Commands::Distill {
+                    workspace,
+                    format,
+                    output,
+                    dry_run,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_distill(
+                        &workspace,
+                        &format,
+                        output.as_deref(),
+                        dry_run,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Delta {
+                    workspace,
+                    since,
+                    utc,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_delta(
+                        &workspace,
+                        &since,
+                        utc,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Focus {
+                    workspace,
+                    auto,
+                    clear,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_focus(
+                        workspace.as_deref(),
+                        auto,
+                        clear,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Ngrams {
+                    workspace,
+                    since,
+                    top,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_ngrams(
+                        workspace.as_deref(),
+                        &since,
+                        top,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Corrections {
+                    workspace,
+                    since,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_corrections(
+                        workspace.as_deref(),
+                        &since,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
                 Commands::Expand {
                     path,
                     source,
@@ -7897,9 +9341,58 @@ async fn execute_cli(
                         structured_format,
                     )?;
                 }
+                Commands::GetContext {
+                    id,
+                    before,
+                    after,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_get_context(
+                        id,
+                        before,
+                        after,
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
+                Commands::Show {
+                    id,
+                    raw,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_show(id, raw, &data_dir, cli.db.clone(), structured_format)?;
+                }
+                Commands::Audit {
+                    limit,
+                    command,
+                    prune,
+                    retention_days,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_audit(
+                        limit,
+                        command.as_deref(),
+                        prune,
+                        retention_days,
+                        &data_dir,
+                        structured_format,
+                    )?;
+                }
+                Commands::Verify { data_dir, json } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_verify(&data_dir, cli.db.clone(), structured_format)?;
+                }
                 Commands::Timeline {
                     since,
                     until,
+                    utc,
                     today,
                     agent,
                     data_dir,
@@ -7911,6 +9404,7 @@ async fn execute_cli(
                     run_timeline(
                         since.as_deref(),
                         until.as_deref(),
+                        utc,
                         today,
                         &agent,
                         &data_dir,
@@ -7920,6 +9414,38 @@ async fn execute_cli(
                         source,
                     )?;
                 }
+                Commands::Sample {
+                    per_agent,
+                    pool,
+                    agent,
+                    format,
+                    seed,
+                    data_dir,
+                } => {
+                    run_sample(
+                        per_agent,
+                        pool,
+                        &agent,
+                        format,
+                        seed,
+                        &data_dir,
+                        cli.db.clone(),
+                    )?;
+                }
+                Commands::Scan {
+                    path,
+                    query,
+                    connector,
+                    limit,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_scan(&path, query.as_deref(), &connector, limit, structured_format)?;
+                }
+                Commands::Bench(BenchCommand::Ranking { k, json }) => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_bench_ranking(k, structured_format)?;
+                }
                 Commands::Quarantine(subcmd) => {
                     run_quarantine_command(subcmd, cli)?;
                 }
@@ -7932,12 +9458,69 @@ async fn execute_cli(
                     let structured_format = resolve_subcommand_structured_format(cli, json);
                     run_forget_command(source_glob, db, apply, cli, structured_format)?;
                 }
+                Commands::Retitle {
+                    regenerate,
+                    db,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_retitle(regenerate, db, cli, structured_format)?;
+                }
                 Commands::Mirror(subcmd) => {
                     run_mirror_command(subcmd, cli)?;
                 }
+                Commands::Debug(subcmd) => {
+                    run_debug_command(subcmd, cli)?;
+                }
+                Commands::Secrets(subcmd) => {
+                    run_secrets_command(subcmd, cli)?;
+                }
+                Commands::Trash(subcmd) => {
+                    run_trash_command(subcmd, cli)?;
+                }
+                Commands::Undo {
+                    operation_id,
+                    db,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_trash_restore(&operation_id, db, cli, structured_format)?;
+                }
+                Commands::Db(subcmd) => {
+                    run_db_command(subcmd, cli)?;
+                }
+                Commands::Note(subcmd) => {
+                    run_note_command(subcmd, cli)?;
+                }
+                Commands::Workspace(subcmd) => {
+                    run_workspace_command(subcmd, cli)?;
+                }
+                Commands::Tag(subcmd) => {
+                    run_tag_command(subcmd, cli)?;
+                }
+                Commands::Schedule(subcmd) => {
+                    run_schedule_command(subcmd, cli)?;
+                }
                 Commands::Swarm(subcmd) => {
                     run_swarm_command(subcmd, cli)?;
                 }
+                Commands::Agents {
+                    refresh,
+                    enable,
+                    disable,
+                    data_dir,
+                    json,
+                } => {
+                    let structured_format = resolve_subcommand_structured_format(cli, json);
+                    run_agents(
+                        refresh,
+                        enable.as_deref(),
+                        disable.as_deref(),
+                        &data_dir,
+                        cli.db.clone(),
+                        structured_format,
+                    )?;
+                }
                 Commands::Sources(subcmd) => {
                     run_sources_command(subcmd, cli)?;
                 }
@@ -7959,12 +9542,21 @@ async fn execute_cli(
                     .await;
                     result?;
                 }
+                Commands::Watch(subcmd) => {
+                    run_watch_command(subcmd, cli)?;
+                }
                 Commands::Lessons(subcmd) => {
                     run_lessons_command(subcmd, cli)?;
                 }
                 Commands::Import(subcmd) => {
                     handle_import(subcmd, cli).await?;
                 }
+                Commands::Meta(subcmd) => {
+                    run_meta_command(subcmd, cli)?;
+                }
+                Commands::Profile(subcmd) => {
+                    run_profile_command(subcmd, cli)?;
+                }
                 #[cfg(unix)]
                 Commands::Daemon {
                     socket,
@@ -7979,44 +9571,1749 @@ async fn execute_cli(
         }
     }
 
-    Ok(())
+    Ok(())
+}
+
+async fn handle_import(cmd: ImportCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        ImportCommand::Chatgpt { path, output_dir } => {
+            let structured_format = cli.robot_format.or_else(robot_format_from_env);
+            import_chatgpt_export(&path, output_dir.as_deref(), structured_format).await
+        }
+        ImportCommand::ChatgptExport {
+            path,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_export_archive_import(
+                crate::archive_import::ExportKind::Chatgpt,
+                &path,
+                data_dir,
+                structured_format,
+            )
+        }
+        ImportCommand::ClaudeExport {
+            path,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_export_archive_import(
+                crate::archive_import::ExportKind::Claude,
+                &path,
+                data_dir,
+                structured_format,
+            )
+        }
+        ImportCommand::Ndjson {
+            path,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_import_ndjson(&path, data_dir, structured_format)
+        }
+    }
+}
+
+/// `cass import chatgpt-export` / `cass import claude-export`: parse an
+/// official data-export archive into normalized conversations and persist
+/// them through the standard ingest path.
+fn run_export_archive_import(
+    kind: crate::archive_import::ExportKind,
+    path: &Path,
+    data_dir: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    if !path.exists() {
+        return Err(CliError {
+            code: 1,
+            kind: CliErrorKind::IoError.kind_str(),
+            message: format!("Export not found: {}", path.display()),
+            hint: Some(
+                "Provide the export zip, the extracted export directory, or conversations.json"
+                    .into(),
+            ),
+            retryable: false,
+        });
+    }
+
+    let data_dir = data_dir.unwrap_or_else(default_data_dir);
+    let report =
+        crate::archive_import::import_into_data_dir(kind, path, &data_dir).map_err(|err| {
+            CliError {
+                code: 1,
+                kind: CliErrorKind::ParseError.kind_str(),
+                message: format!("import failed: {err:#}"),
+                hint: Some(
+                    "If the archive is zip64 or non-standard, extract it and pass conversations.json directly.".into(),
+                ),
+                retryable: false,
+            }
+        })?;
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("schema_version".to_string(), serde_json::json!(1));
+            obj.insert(
+                "data_dir".to_string(),
+                serde_json::json!(data_dir.display().to_string()),
+            );
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Import complete!");
+    println!("  Format:              {}", report.format);
+    println!("  Agent slug:          {}", report.agent_slug);
+    println!("  Total conversations: {}", report.total);
+    println!("  Imported:            {}", report.imported);
+    println!("  Skipped (empty):     {}", report.empty);
+    println!("  Messages indexed:    {}", report.messages);
+    Ok(())
+}
+
+fn run_import_ndjson(
+    path: &Path,
+    data_dir: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir.unwrap_or_else(default_data_dir);
+    let from_stdin = path.as_os_str() == "-";
+    let report = if from_stdin {
+        let stdin = std::io::stdin();
+        crate::stream_import::import_reader(stdin.lock(), &data_dir)
+    } else {
+        let file = std::fs::File::open(path).map_err(|e| CliError {
+            code: 1,
+            kind: CliErrorKind::IoError.kind_str(),
+            message: format!("Cannot read {}: {e}", path.display()),
+            hint: Some("Pass an NDJSON file, or '-' to read from stdin".into()),
+            retryable: false,
+        })?;
+        crate::stream_import::import_reader(std::io::BufReader::new(file), &data_dir)
+    }
+    .map_err(|err| CliError {
+        code: 1,
+        kind: CliErrorKind::ParseError.kind_str(),
+        message: format!("import failed: {err:#}"),
+        hint: Some(
+            "Each line must be one NormalizedConversation JSON object; see `cass import ndjson --help`"
+                .into(),
+        ),
+        retryable: false,
+    })?;
+
+    let structured_format = output_format.map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("schema_version".to_string(), serde_json::json!(1));
+            obj.insert(
+                "data_dir".to_string(),
+                serde_json::json!(data_dir.display().to_string()),
+            );
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Import complete!");
+    println!("  Lines read:          {}", report.lines);
+    println!("  Imported:            {}", report.imported);
+    println!("  Messages indexed:    {}", report.messages);
+    println!("  Skipped:             {}", report.skipped);
+    for err in &report.errors {
+        println!("    line {}: {}", err.line, err.error);
+    }
+    if report.skipped > report.errors.len() {
+        println!(
+            "    ... and {} more (first {} shown)",
+            report.skipped - report.errors.len(),
+            report.errors.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_mirror_command(cmd: MirrorCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        MirrorCommand::Prune {
+            data_dir,
+            older_than,
+            max_size,
+            keep_tag,
+            safety_hold_down,
+            dry_run: _,
+            apply,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_mirror_prune(
+                data_dir,
+                older_than,
+                max_size,
+                keep_tag,
+                safety_hold_down,
+                apply,
+                structured_format,
+            )
+        }
+    }
+}
+
+fn run_debug_command(cmd: DebugCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        DebugCommand::Reparse {
+            conversation,
+            data_dir,
+            db,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_debug_reparse(&conversation, data_dir, db, structured_format)
+        }
+        DebugCommand::Parse {
+            file,
+            connector,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_debug_parse(&file, &connector, structured_format)
+        }
+    }
+}
+
+/// `cass debug parse`: run one connector's parser against an arbitrary session
+/// file and print what it normalizes to.
+///
+/// This never opens the main database: the file is handed straight to the
+/// connector as an explicit scan root, and the resulting conversations are
+/// printed as pretty JSON. Parse failures surface the connector's error chain,
+/// which carries the offending line or byte where the format parser reports
+/// one.
+fn run_debug_parse(
+    file: &Path,
+    connector_slug: &str,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    if !file.exists() {
+        return Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("no such file: {}", file.display()),
+            hint: Some("Pass a session file or provider db path to parse.".to_string()),
+            retryable: false,
+        });
+    }
+
+    let mut registry = crate::connector_registry::ConnectorRegistry::new();
+    let Some(connector) = registry.get(connector_slug) else {
+        let slugs = registry.slugs().join(", ");
+        return Err(CliError::usage(
+            format!("unknown connector '{connector_slug}'"),
+            Some(format!("Compiled-in connectors: {slugs}.")),
+        ));
+    };
+
+    let ctx = crate::connectors::ScanContext::with_roots(
+        file.to_path_buf(),
+        vec![crate::connectors::ScanRoot::local(file.to_path_buf())],
+        None,
+    );
+    let parsed = connector.scan(&ctx).map_err(|err| CliError {
+        code: 9,
+        kind: "parse",
+        message: format!(
+            "connector '{connector_slug}' failed to parse {}: {err:#}",
+            file.display()
+        ),
+        hint: Some(
+            "The error chain above includes the offending line/byte where the format \
+             parser reports one."
+                .to_string(),
+        ),
+        retryable: false,
+    })?;
+
+    let conversations: Vec<serde_json::Value> = parsed
+        .iter()
+        .map(normalized_conversation_debug_json)
+        .collect();
+    let message_count: usize = parsed.iter().map(|conv| conv.messages.len()).sum();
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "file": file.display().to_string(),
+                "connector": connector_slug,
+                "conversation_count": parsed.len(),
+                "message_count": message_count,
+                "conversations": conversations,
+            }),
+            fmt,
+        );
+    }
+
+    println!(
+        "Parsed {} with connector '{connector_slug}': {} conversation(s), {} message(s)",
+        file.display(),
+        parsed.len(),
+        message_count
+    );
+    let rendered = serde_json::to_string_pretty(&conversations)
+        .map_err(|e| CliError::unknown(format!("serialize parsed conversations: {e}")))?;
+    println!("{rendered}");
+    Ok(())
+}
+
+/// Debug-output projection of a [`crate::connectors::NormalizedConversation`].
+/// Spelled out field by field so the shape stays stable for issue triage even
+/// if the upstream struct grows internal bookkeeping.
+fn normalized_conversation_debug_json(
+    conv: &crate::connectors::NormalizedConversation,
+) -> serde_json::Value {
+    serde_json::json!({
+        "agent_slug": conv.agent_slug,
+        "external_id": conv.external_id,
+        "title": conv.title,
+        "workspace": conv.workspace,
+        "source_path": conv.source_path,
+        "started_at": conv.started_at,
+        "ended_at": conv.ended_at,
+        "metadata": conv.metadata,
+        "messages": conv.messages.iter().map(|msg| serde_json::json!({
+            "idx": msg.idx,
+            "role": msg.role,
+            "author": msg.author,
+            "created_at": msg.created_at,
+            "content": msg.content,
+            "extra": msg.extra,
+            "snippet_count": msg.snippets.len(),
+            "invocation_count": msg.invocations.len(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// `cass secrets`: keychain-backed credential management (src/secrets.rs).
+/// Every output path goes through `secrets::redact_for_display` unless the
+/// user explicitly passes `--reveal`; values are never logged.
+fn run_secrets_command(cmd: SecretsCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        SecretsCommand::Set { name, value, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_secrets_set(&name, value, structured_format)
+        }
+        SecretsCommand::Get { name, reveal, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_secrets_get(&name, reveal, structured_format)
+        }
+        SecretsCommand::List { json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_secrets_list(structured_format)
+        }
+        SecretsCommand::Delete { name, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_secrets_delete(&name, structured_format)
+        }
+    }
+}
+
+fn secrets_cli_error(err: anyhow::Error) -> CliError {
+    CliError {
+        code: 9,
+        kind: "secrets",
+        message: format!("{err:#}"),
+        hint: None,
+        retryable: false,
+    }
+}
+
+fn run_secrets_set(
+    name: &str,
+    value: Option<String>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            // Read one line from stdin so the secret stays out of shell
+            // history and process listings.
+            let mut line = String::new();
+            std::io::stdin()
+                .read_line(&mut line)
+                .map_err(|e| CliError::unknown(format!("read secret from stdin: {e}")))?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    crate::secrets::set(name, &value).map_err(secrets_cli_error)?;
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "name": name,
+                "stored": "keychain",
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Stored '{name}' in the OS keychain ({}).",
+        crate::secrets::redact_for_display(&value)
+    );
+    Ok(())
+}
+
+fn run_secrets_get(name: &str, reveal: bool, output_format: Option<RobotFormat>) -> CliResult<()> {
+    let resolved = crate::secrets::resolve(name).map_err(secrets_cli_error)?;
+    let Some(resolved) = resolved else {
+        let hint = match crate::secrets::known_secret(name) {
+            Some(secret) => format!(
+                "Set it with `cass secrets set {name}` or export {}.",
+                secret.env_vars.join(" / ")
+            ),
+            None => format!("Set it with `cass secrets set {name}`."),
+        };
+        return Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("secret '{name}' is not set"),
+            hint: Some(hint),
+            retryable: false,
+        });
+    };
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::json!({
+            "success": true,
+            "name": name,
+            "source": resolved.source.label(),
+            "redacted": crate::secrets::redact_for_display(&resolved.value),
+        });
+        if reveal {
+            payload["value"] = serde_json::Value::String(resolved.value.clone());
+        }
+        return output_structured_value(payload, fmt);
+    }
+    if reveal {
+        println!("{}", resolved.value);
+    } else {
+        println!(
+            "{name}: {} [{}]",
+            crate::secrets::redact_for_display(&resolved.value),
+            resolved.source.label()
+        );
+    }
+    Ok(())
+}
+
+fn run_secrets_list(output_format: Option<RobotFormat>) -> CliResult<()> {
+    let mut rows = Vec::new();
+    for secret in crate::secrets::KNOWN_SECRETS {
+        let resolved = crate::secrets::resolve(secret.name).map_err(secrets_cli_error)?;
+        rows.push((
+            secret.name,
+            secret.purpose,
+            resolved.map(|resolved| {
+                (
+                    resolved.source.label(),
+                    crate::secrets::redact_for_display(&resolved.value),
+                )
+            }),
+        ));
+    }
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "keychain_available": crate::secrets::keychain_available(),
+                "secrets": rows
+                    .iter()
+                    .map(|(name, purpose, state)| {
+                        serde_json::json!({
+                            "name": name,
+                            "purpose": purpose,
+                            "set": state.is_some(),
+                            "source": state.as_ref().map(|(source, _)| source.clone()),
+                            "redacted": state.as_ref().map(|(_, redacted)| redacted.clone()),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Keychain support: {}",
+        if crate::secrets::keychain_available() {
+            "available"
+        } else {
+            "not compiled in (env-var fallback only)"
+        }
+    );
+    for (name, purpose, state) in rows {
+        match state {
+            Some((source, redacted)) => println!("  {name}: {redacted} [{source}] — {purpose}"),
+            None => println!("  {name}: <not set> — {purpose}"),
+        }
+    }
+    Ok(())
+}
+
+fn run_secrets_delete(name: &str, output_format: Option<RobotFormat>) -> CliResult<()> {
+    let removed = crate::secrets::delete(name).map_err(secrets_cli_error)?;
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "name": name,
+                "removed": removed,
+            }),
+            fmt,
+        );
+    }
+    if removed {
+        println!("Removed '{name}' from the OS keychain.");
+    } else {
+        println!("No keychain entry for '{name}'.");
+    }
+    Ok(())
+}
+
+/// `cass trash` / `cass undo`: the recycle bin for destructive operations.
+/// `forget --apply` snapshots deleted conversations into shadow tables;
+/// restore replays them through the canonical insert path and rebuilds
+/// derived assets, so an accidental deletion is a two-command round trip.
+fn run_trash_command(cmd: TrashCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        TrashCommand::List { db, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_trash_list(db, cli, structured_format)
+        }
+        TrashCommand::Restore {
+            operation_id,
+            db,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_trash_restore(&operation_id, db, cli, structured_format)
+        }
+        TrashCommand::Empty {
+            older_than,
+            apply,
+            db,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_trash_empty(older_than, apply, db, cli, structured_format)
+        }
+    }
+}
+
+fn open_trash_storage(
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+) -> CliResult<(crate::storage::sqlite::FrankenStorage, PathBuf)> {
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(default_db_path);
+    if !db_path.is_file() {
+        return Err(CliError {
+            code: 5,
+            kind: "trash",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
+            retryable: false,
+        });
+    }
+    let storage = crate::storage::sqlite::FrankenStorage::open(&db_path).map_err(|e| CliError {
+        code: 5,
+        kind: "trash",
+        message: format!("failed to open canonical database: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+    Ok((storage, db_path))
+}
+
+fn trash_cli_error(err: anyhow::Error) -> CliError {
+    CliError {
+        code: 5,
+        kind: "trash",
+        message: format!("{err:#}"),
+        hint: Some("Run `cass trash list --json` to inspect the recycle bin.".to_string()),
+        retryable: false,
+    }
+}
+
+fn run_trash_list(
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let (storage, db_path) = open_trash_storage(db_override, cli)?;
+    let operations = storage.trash_list_operations().map_err(trash_cli_error)?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "db_path": db_path.display().to_string(),
+                "retention_ms": crate::storage::sqlite::trash_retention_ms(),
+                "operations": operations,
+            }),
+            fmt,
+        );
+    }
+
+    if operations.is_empty() {
+        println!("Recycle bin is empty.");
+        return Ok(());
+    }
+    println!("Restorable operations (newest first):");
+    for op in operations {
+        println!(
+            "  {}  {}  {} conversation(s){}",
+            op.operation_id,
+            op.kind,
+            op.conversation_count,
+            op.detail
+                .as_deref()
+                .map(|detail| format!("  [{detail}]"))
+                .unwrap_or_default()
+        );
+    }
+    println!("Restore one with `cass undo <operation-id>`.");
+    Ok(())
+}
+
+fn run_trash_restore(
+    operation_id: &str,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let (storage, db_path) = open_trash_storage(db_override, cli)?;
+    let result = storage
+        .trash_restore_operation(operation_id)
+        .map_err(trash_cli_error)?;
+
+    // Mirror the forget/purge paths: rebuild derived assets after mutating
+    // conversations so search and analytics stay consistent.
+    if result.conversations_restored > 0 {
+        if let Err(e) = storage.rebuild_fts() {
+            tracing::warn!(error = %e, "trash restore: failed to rebuild FTS");
+        }
+        if let Err(e) = storage.rebuild_analytics() {
+            tracing::warn!(error = %e, "trash restore: failed to rebuild analytics");
+        }
+        if let Err(e) = storage.rebuild_daily_stats() {
+            tracing::warn!(error = %e, "trash restore: failed to rebuild daily stats");
+        }
+    }
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "db_path": db_path.display().to_string(),
+                "restore": result,
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Restored {} conversation(s) from operation {}{}.",
+        result.conversations_restored,
+        result.operation_id,
+        if result.conversations_skipped > 0 {
+            format!(
+                " ({} unreadable snapshot(s) skipped)",
+                result.conversations_skipped
+            )
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
+
+fn run_trash_empty(
+    older_than: Option<String>,
+    apply: bool,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let (storage, db_path) = open_trash_storage(db_override, cli)?;
+    let older_than_ms = older_than
+        .as_deref()
+        .map(parse_duration_millis)
+        .transpose()?;
+
+    let dropped = if apply {
+        storage
+            .trash_empty(older_than_ms)
+            .map_err(trash_cli_error)?
+    } else {
+        // Dry-run: count what --apply would drop without touching anything.
+        let now = crate::storage::sqlite::FrankenStorage::now_millis();
+        storage
+            .trash_list_operations()
+            .map_err(trash_cli_error)?
+            .iter()
+            .filter(|op| match older_than_ms {
+                Some(age) => op.created_at < now.saturating_sub(age),
+                None => true,
+            })
+            .count()
+    };
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "db_path": db_path.display().to_string(),
+                "applied": apply,
+                "operations_dropped": dropped,
+            }),
+            fmt,
+        );
+    }
+    if apply {
+        println!("Dropped {dropped} operation(s) from the recycle bin.");
+    } else {
+        println!("Would drop {dropped} operation(s). Re-run with --apply to empty.");
+    }
+    Ok(())
+}
+
+fn run_note_command(cmd: NoteCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        NoteCommand::Add {
+            target,
+            text,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_note_add(&target, &text, data_dir, structured_format)
+        }
+        NoteCommand::List {
+            source,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_note_list(source, data_dir, structured_format)
+        }
+        NoteCommand::Remove { id, data_dir, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_note_remove(id, data_dir, structured_format)
+        }
+    }
+}
+
+fn note_cli_error(err: anyhow::Error) -> CliError {
+    CliError {
+        code: 5,
+        kind: "note",
+        message: format!("{err:#}"),
+        hint: None,
+        retryable: false,
+    }
+}
+
+fn note_db_path(data_dir_override: Option<PathBuf>) -> PathBuf {
+    data_dir_override
+        .unwrap_or_else(default_data_dir)
+        .join("notes.db")
+}
+
+/// Split a note target into source path and 0-based message index. The
+/// suffix is the 1-based message number the viewer and `cass view` robot
+/// output display; a bare path annotates the whole conversation. Windows
+/// drive letters survive because only an all-digit suffix is treated as a
+/// message number.
+fn parse_note_target(target: &str) -> CliResult<(String, Option<i64>)> {
+    if let Some((path, suffix)) = target.rsplit_once(':')
+        && !suffix.is_empty()
+        && suffix.chars().all(|ch| ch.is_ascii_digit())
+    {
+        let number: i64 = suffix.parse().map_err(|_| {
+            CliError::usage(
+                format!("message number '{suffix}' is out of range"),
+                Some("Use the 1-based message number shown in the viewer.".to_string()),
+            )
+        })?;
+        if number < 1 {
+            return Err(CliError::usage(
+                format!("message number must be >= 1, got {number}"),
+                Some("Message numbers are 1-based, as shown in the viewer.".to_string()),
+            ));
+        }
+        return Ok((path.to_string(), Some(number - 1)));
+    }
+    Ok((target.to_string(), None))
+}
+
+fn run_note_add(
+    target: &str,
+    text: &str,
+    data_dir_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(CliError::usage(
+            "note text must not be empty".to_string(),
+            Some("Pass the annotation as the second argument.".to_string()),
+        ));
+    }
+    let (source_path, message_idx) = parse_note_target(target)?;
+    // Store the canonical path when the file exists so notes line up with
+    // the absolute source paths the indexer records.
+    let source_path = std::fs::canonicalize(&source_path)
+        .map(|p| p.display().to_string())
+        .unwrap_or(source_path);
+
+    let store =
+        crate::notes::NoteStore::open(&note_db_path(data_dir_override)).map_err(note_cli_error)?;
+    let id = store
+        .add(&source_path, message_idx, text)
+        .map_err(note_cli_error)?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "id": id,
+                "source_path": source_path,
+                "message_idx": message_idx,
+            }),
+            fmt,
+        );
+    }
+    match message_idx {
+        Some(idx) => println!("Note {id} added to {source_path} (message {}).", idx + 1),
+        None => println!("Note {id} added to {source_path}."),
+    }
+    Ok(())
+}
+
+fn run_note_list(
+    source: Option<String>,
+    data_dir_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let store = crate::notes::NoteStore::open_existing(&note_db_path(data_dir_override))
+        .map_err(note_cli_error)?;
+    let notes = match (&store, &source) {
+        (Some(store), Some(source)) => store.notes_for_source(source).map_err(note_cli_error)?,
+        (Some(store), None) => store.list().map_err(note_cli_error)?,
+        (None, _) => Vec::new(),
+    };
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "notes": notes,
+            }),
+            fmt,
+        );
+    }
+    if notes.is_empty() {
+        println!("No notes.");
+        return Ok(());
+    }
+    for note in notes {
+        let location = match note.message_idx {
+            Some(idx) => format!("{}:{}", note.source_path, idx + 1),
+            None => note.source_path,
+        };
+        println!("  {}  {}  {}", note.id, location, note.text);
+    }
+    println!("Remove one with `cass note remove <id>`.");
+    Ok(())
+}
+
+fn run_note_remove(
+    id: i64,
+    data_dir_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let removed = crate::notes::NoteStore::open_existing(&note_db_path(data_dir_override))
+        .map_err(note_cli_error)?
+        .map(|store| store.remove(id))
+        .transpose()
+        .map_err(note_cli_error)?
+        .unwrap_or(false);
+    if !removed {
+        return Err(CliError {
+            code: 4,
+            kind: "note",
+            message: format!("no note with id {id}"),
+            hint: Some("Run `cass note list` to see note ids.".to_string()),
+            retryable: false,
+        });
+    }
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "id": id,
+                "removed": true,
+            }),
+            fmt,
+        );
+    }
+    println!("Note {id} removed.");
+    Ok(())
+}
+
+fn run_workspace_command(cmd: WorkspaceCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        WorkspaceCommand::Relink { old, new, db, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_workspace_relink(&old, &new, db, cli, structured_format)
+        }
+        WorkspaceCommand::Detect { db, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_workspace_detect(db, cli, structured_format)
+        }
+    }
+}
+
+fn workspace_cli_error(err: anyhow::Error) -> CliError {
+    CliError {
+        code: 5,
+        kind: "workspace",
+        message: format!("{err:#}"),
+        hint: None,
+        retryable: false,
+    }
 }
 
-async fn handle_import(cmd: ImportCommand, cli: &Cli) -> CliResult<()> {
-    match cmd {
-        ImportCommand::Chatgpt { path, output_dir } => {
-            let structured_format = cli.robot_format.or_else(robot_format_from_env);
-            import_chatgpt_export(&path, output_dir.as_deref(), structured_format).await
+fn open_workspace_storage(
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+) -> CliResult<crate::storage::sqlite::FrankenStorage> {
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(default_db_path);
+    if !db_path.is_file() {
+        return Err(CliError {
+            code: 5,
+            kind: "workspace",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
+            retryable: false,
+        });
+    }
+    crate::storage::sqlite::FrankenStorage::open(&db_path).map_err(|e| CliError {
+        code: 5,
+        kind: "workspace",
+        message: format!("failed to open canonical database: {e}"),
+        hint: None,
+        retryable: false,
+    })
+}
+
+fn run_workspace_relink(
+    old: &str,
+    new: &str,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let old_path = old.trim_end_matches('/');
+    let new_path = new.trim_end_matches('/');
+    if old_path == new_path {
+        return Err(CliError::usage(
+            "old and new workspace paths are the same".to_string(),
+            Some("Pass the dead path first, then the directory it moved to.".to_string()),
+        ));
+    }
+
+    let storage = open_workspace_storage(db_override, cli)?;
+    let outcome = storage
+        .relink_workspace(old_path, new_path)
+        .map_err(workspace_cli_error)?;
+
+    if outcome.merged {
+        // A merge re-points conversations at the surviving workspace id, so
+        // the per-workspace analytics rollups must be rebuilt to follow.
+        storage
+            .rebuild_analytics()
+            .map(|_| ())
+            .map_err(workspace_cli_error)?;
+        // FTS embeds workspace text at insert time; refresh it best-effort so
+        // `workspace:` filters match the new path without a full reindex.
+        if let Err(e) = storage.rebuild_fts() {
+            tracing::warn!(error = %e, "workspace relink: failed to rebuild FTS after merge");
         }
     }
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "schema_version": 1,
+                "old_path": old_path,
+                "new_path": new_path,
+                "merged": outcome.merged,
+                "conversations_moved": outcome.conversations_moved,
+            }),
+            fmt,
+        );
+    }
+    if outcome.merged {
+        println!(
+            "Merged {old_path} into {new_path} ({} conversation(s) moved); analytics rebuilt.",
+            outcome.conversations_moved
+        );
+    } else {
+        println!("Relinked {old_path} -> {new_path} (workspace renamed in place).");
+    }
+    Ok(())
 }
 
-fn run_mirror_command(cmd: MirrorCommand, cli: &Cli) -> CliResult<()> {
+fn run_workspace_detect(
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use crate::workspace_relink::{WorkspaceDirRecord, propose_relinks};
+    use frankensqlite::compat::{ConnectionExt, RowExt};
+
+    let storage = open_workspace_storage(db_override, cli)?;
+    let paths: Vec<String> = storage
+        .raw()
+        .query_map_collect(
+            "SELECT path FROM workspaces WHERE path IS NOT NULL ORDER BY path",
+            &[],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let records: Vec<WorkspaceDirRecord> = paths
+        .iter()
+        .map(|path| WorkspaceDirRecord::collect(path))
+        .collect();
+    let missing = records.iter().filter(|r| !r.exists).count();
+    let proposals = propose_relinks(&records);
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "schema_version": 1,
+                "workspaces": records.len(),
+                "missing_dirs": missing,
+                "proposals": proposals,
+            }),
+            fmt,
+        );
+    }
+
+    println!(
+        "Scanned {} workspace(s); {} with missing directories.",
+        records.len(),
+        missing
+    );
+    if proposals.is_empty() {
+        println!("No relink candidates found.");
+        return Ok(());
+    }
+    println!();
+    for proposal in &proposals {
+        println!("# {}", proposal.reason);
+        println!(
+            "cass workspace relink '{}' '{}'",
+            proposal.old_path, proposal.new_path
+        );
+        println!();
+    }
+    Ok(())
+}
+
+fn run_tag_command(cmd: TagCommand, cli: &Cli) -> CliResult<()> {
     match cmd {
-        MirrorCommand::Prune {
-            data_dir,
-            older_than,
-            max_size,
-            keep_tag,
-            safety_hold_down,
-            dry_run: _,
+        TagCommand::Add {
+            tag,
+            query,
             apply,
+            data_dir,
+            db,
             json,
         } => {
             let structured_format = resolve_subcommand_structured_format(cli, json);
-            run_mirror_prune(
-                data_dir,
-                older_than,
-                max_size,
-                keep_tag,
-                safety_hold_down,
-                apply,
-                structured_format,
+            run_tag_add(&tag, &query, apply, data_dir, db, cli, structured_format)
+        }
+    }
+}
+
+fn tag_cli_error(message: String) -> CliError {
+    CliError {
+        code: 5,
+        kind: "tag",
+        message,
+        hint: None,
+        retryable: false,
+    }
+}
+
+/// Cap on message hits fetched for one bulk-tag query. Hits collapse to
+/// distinct conversations, so this comfortably covers thousands of sessions.
+const TAG_QUERY_HIT_CAP: usize = 10_000;
+
+fn run_tag_add(
+    tag: &str,
+    query: &str,
+    apply: bool,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use crate::search::query::{FieldMask, SearchClient, SearchFilters};
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(CliError::usage(
+            "tag name is empty".to_string(),
+            Some("Pass a non-empty tag, e.g. `cass tag add incident-2025-12 --query ...`".to_string()),
+        ));
+    }
+    if query.trim().is_empty() {
+        return Err(CliError::usage(
+            "query is empty".to_string(),
+            Some("Pass --query with the same syntax as `cass search`.".to_string()),
+        ));
+    }
+
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let index_path = crate::search::tantivy::expected_index_dir(&data_dir);
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(|| data_dir.join("agent_search.db"));
+    if !db_path.is_file() {
+        return Err(CliError {
+            code: 5,
+            kind: "tag",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
+            retryable: false,
+        });
+    }
+
+    let client = SearchClient::open(&index_path, Some(&db_path))
+        .map_err(|e| tag_cli_error(format!("failed to open search index: {e}")))?
+        .ok_or_else(|| CliError {
+            code: 5,
+            kind: "tag",
+            message: format!("no search index at {}", index_path.display()),
+            hint: Some("Run `cass index` first.".to_string()),
+            retryable: false,
+        })?;
+    let hits = client
+        .search(
+            query,
+            SearchFilters::default(),
+            TAG_QUERY_HIT_CAP,
+            0,
+            FieldMask::FULL,
+        )
+        .map_err(|e| tag_cli_error(format!("search failed: {e}")))?;
+
+    let storage = crate::storage::sqlite::FrankenStorage::open(&db_path)
+        .map_err(|e| tag_cli_error(format!("failed to open canonical database: {e}")))?;
+    let conn = storage.raw();
+
+    let conversation_ids = tag_conversation_ids(conn, &hits)?;
+
+    const MAX_SAMPLES: usize = 10;
+    let mut samples: Vec<(String, String)> = Vec::new();
+    for conv_id in conversation_ids.iter().take(MAX_SAMPLES) {
+        let row: (Option<String>, String) = conn
+            .query_row_map(
+                "SELECT title, source_path FROM conversations WHERE id = ?",
+                &[ParamValue::from(*conv_id)],
+                |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?)),
+            )
+            .map_err(|e| tag_cli_error(format!("reading conversation: {e}")))?;
+        samples.push((row.0.unwrap_or_else(|| "(untitled)".to_string()), row.1));
+    }
+
+    let mut links_added = 0usize;
+    if apply && !conversation_ids.is_empty() {
+        links_added = apply_tag_links(conn, tag, &conversation_ids)?;
+    }
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "tag": tag,
+            "query": query,
+            "applied": apply,
+            "message_hits": hits.len(),
+            "matched_conversations": conversation_ids.len(),
+            "links_added": links_added,
+            "sample": samples
+                .iter()
+                .map(|(title, path)| serde_json::json!({ "title": title, "source_path": path }))
+                .collect::<Vec<_>>(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if apply {
+        println!(
+            "Tagged {} conversation(s) with '{tag}' ({links_added} new link(s)).",
+            conversation_ids.len()
+        );
+        return Ok(());
+    }
+    println!("Tag preview: '{tag}'");
+    println!("Query: {query}");
+    println!(
+        "Matched {} conversation(s) across {} message hit(s).",
+        conversation_ids.len(),
+        hits.len()
+    );
+    for (title, path) in &samples {
+        println!("  - {title} ({path})");
+    }
+    if conversation_ids.len() > samples.len() {
+        println!("  ... and {} more", conversation_ids.len() - samples.len());
+    }
+    if conversation_ids.is_empty() {
+        println!("Nothing to tag.");
+    } else {
+        println!(
+            "Re-run with --apply to tag {} conversation(s).",
+            conversation_ids.len()
+        );
+    }
+    Ok(())
+}
+
+/// Collapse message hits to distinct conversation row ids. Hits carry the
+/// conversation row id when the projection resolved one; otherwise fall
+/// back to every conversation stored under the hit's source path.
+fn tag_conversation_ids(
+    conn: &frankensqlite::Connection,
+    hits: &[crate::search::query::SearchHit],
+) -> CliResult<std::collections::BTreeSet<i64>> {
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    let mut conversation_ids = std::collections::BTreeSet::new();
+    for hit in hits {
+        if let Some(id) = hit.conversation_id {
+            conversation_ids.insert(id);
+            continue;
+        }
+        let ids: Vec<i64> = conn
+            .query_map_collect(
+                "SELECT id FROM conversations WHERE source_path = ?",
+                &[ParamValue::from(hit.source_path.clone())],
+                |r: &frankensqlite::Row| r.get_typed(0),
+            )
+            .map_err(|e| tag_cli_error(format!("resolving conversations: {e}")))?;
+        conversation_ids.extend(ids);
+    }
+    Ok(conversation_ids)
+}
+
+/// Create the tag row if missing and link each matched conversation,
+/// returning how many links were actually added. `INSERT OR IGNORE` on
+/// both statements makes re-running the same tag command idempotent.
+fn apply_tag_links(
+    conn: &frankensqlite::Connection,
+    tag: &str,
+    conversation_ids: &std::collections::BTreeSet<i64>,
+) -> CliResult<usize> {
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    conn.execute_compat(
+        "INSERT OR IGNORE INTO tags (name) VALUES (?1)",
+        frankensqlite::params![tag],
+    )
+    .map_err(|e| tag_cli_error(format!("creating tag: {e}")))?;
+    let tag_id: i64 = conn
+        .query_row_map(
+            "SELECT id FROM tags WHERE name = ?",
+            &[ParamValue::from(tag.to_string())],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .map_err(|e| tag_cli_error(format!("looking up tag id: {e}")))?;
+    let mut links_added = 0usize;
+    for conv_id in conversation_ids {
+        links_added += conn
+            .execute_compat(
+                "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag_id) VALUES (?1, ?2)",
+                frankensqlite::params![*conv_id, tag_id],
             )
+            .map_err(|e| tag_cli_error(format!("linking tag: {e}")))?;
+    }
+    Ok(links_added)
+}
+
+#[cfg(test)]
+mod tag_add_tests {
+    use super::{apply_tag_links, tag_conversation_ids};
+    use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+    use crate::search::query::SearchHit;
+    use crate::storage::sqlite::SqliteStorage;
+    use frankensqlite::compat::{ConnectionExt, RowExt};
+    use std::path::PathBuf;
+
+    fn storage_with_two_conversations(dir: &std::path::Path) -> SqliteStorage {
+        let storage = SqliteStorage::open(&dir.join("tags.db")).expect("open storage");
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "tester".into(),
+                name: "Tester".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .expect("ensure agent");
+        for (idx, source_path) in ["/logs/a.jsonl", "/logs/b.jsonl"].iter().enumerate() {
+            let conv = Conversation {
+                id: None,
+                agent_slug: "tester".into(),
+                workspace: Some(PathBuf::from("/workspace/demo")),
+                external_id: Some(format!("ext-{idx}")),
+                title: Some(format!("Demo {idx}")),
+                source_path: PathBuf::from(source_path),
+                started_at: Some(1),
+                ended_at: Some(2),
+                approx_tokens: None,
+                metadata_json: serde_json::json!({}),
+                messages: vec![Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: None,
+                    created_at: Some(1),
+                    content: format!("hello {idx}"),
+                    extra_json: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+                source_id: "local".to_string(),
+                origin_host: None,
+            };
+            storage
+                .insert_conversation_tree(agent_id, None, &conv)
+                .expect("insert conversation");
+        }
+        storage
+    }
+
+    fn id_for_path(conn: &frankensqlite::Connection, source_path: &str) -> i64 {
+        use frankensqlite::compat::ParamValue;
+        conn.query_row_map(
+            "SELECT id FROM conversations WHERE source_path = ?",
+            &[ParamValue::from(source_path.to_string())],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .expect("conversation id")
+    }
+
+    fn hit(conversation_id: Option<i64>, source_path: &str) -> SearchHit {
+        SearchHit {
+            title: "Demo".to_string(),
+            snippet: String::new(),
+            content: "hello".to_string(),
+            content_hash: 0,
+            conversation_id,
+            score: 1.0,
+            source_path: source_path.to_string(),
+            agent: "tester".to_string(),
+            workspace: "/workspace/demo".to_string(),
+            workspace_original: None,
+            created_at: Some(1),
+            line_number: Some(1),
+            match_type: Default::default(),
+            source_id: "local".to_string(),
+            origin_kind: "local".to_string(),
+            origin_host: None,
+        }
+    }
+
+    fn count(conn: &frankensqlite::Connection, sql: &str) -> i64 {
+        conn.query_row_map(sql, &[], |r: &frankensqlite::Row| r.get_typed(0))
+            .expect("count query")
+    }
+
+    #[test]
+    fn hits_without_conversation_id_fall_back_to_source_path() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let storage = storage_with_two_conversations(tmp.path());
+        let conn = storage.raw();
+
+        let id_a = id_for_path(conn, "/logs/a.jsonl");
+        let id_b = id_for_path(conn, "/logs/b.jsonl");
+
+        // One hit resolved a row id, the other only knows its source path;
+        // both conversations must be matched, without duplicates.
+        let hits = vec![
+            hit(Some(id_a), "/logs/a.jsonl"),
+            hit(None, "/logs/b.jsonl"),
+            hit(None, "/logs/b.jsonl"),
+        ];
+        let ids = tag_conversation_ids(conn, &hits).expect("collapse hits");
+        assert_eq!(ids, std::collections::BTreeSet::from([id_a, id_b]));
+
+        // A path the canonical DB has never seen matches nothing.
+        let ids =
+            tag_conversation_ids(conn, &[hit(None, "/logs/missing.jsonl")]).expect("collapse hits");
+        assert!(ids.is_empty());
+    }
+
+    #[test]
+    fn preview_computes_matches_without_writing_tag_rows() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let storage = storage_with_two_conversations(tmp.path());
+        let conn = storage.raw();
+
+        // The dry-run half of `cass tag add` only collapses hits; nothing
+        // may touch the tags tables until --apply.
+        let id_a = id_for_path(conn, "/logs/a.jsonl");
+        let ids =
+            tag_conversation_ids(conn, &[hit(Some(id_a), "/logs/a.jsonl")]).expect("collapse");
+        assert_eq!(ids.len(), 1);
+        assert_eq!(count(conn, "SELECT COUNT(*) FROM tags"), 0);
+        assert_eq!(count(conn, "SELECT COUNT(*) FROM conversation_tags"), 0);
+    }
+
+    #[test]
+    fn apply_links_conversations_and_is_idempotent() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let storage = storage_with_two_conversations(tmp.path());
+        let conn = storage.raw();
+
+        let ids = std::collections::BTreeSet::from([
+            id_for_path(conn, "/logs/a.jsonl"),
+            id_for_path(conn, "/logs/b.jsonl"),
+        ]);
+        let added = apply_tag_links(conn, "incident-2025-12", &ids).expect("apply links");
+        assert_eq!(added, 2);
+        assert_eq!(count(conn, "SELECT COUNT(*) FROM tags"), 1);
+        assert_eq!(count(conn, "SELECT COUNT(*) FROM conversation_tags"), 2);
+
+        // Re-applying the same tag adds no duplicate rows or links.
+        let added = apply_tag_links(conn, "incident-2025-12", &ids).expect("re-apply links");
+        assert_eq!(added, 0);
+        assert_eq!(count(conn, "SELECT COUNT(*) FROM conversation_tags"), 2);
+    }
+}
+
+fn run_schedule_command(cmd: ScheduleCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        ScheduleCommand::Install { every, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_schedule_install(&every, structured_format)
+        }
+        ScheduleCommand::Status { json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_schedule_status(structured_format)
+        }
+        ScheduleCommand::Uninstall { json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_schedule_uninstall(structured_format)
         }
     }
 }
 
+fn schedule_cli_error(err: anyhow::Error) -> CliError {
+    CliError {
+        code: 5,
+        kind: "schedule",
+        message: format!("{err:#}"),
+        hint: None,
+        retryable: false,
+    }
+}
+
+fn run_schedule_install(every: &str, output_format: Option<RobotFormat>) -> CliResult<()> {
+    let interval_millis = parse_duration_millis(every)?;
+    let interval_secs = u64::try_from(interval_millis / 1000).unwrap_or(0);
+    if interval_secs < 60 {
+        return Err(CliError::usage(
+            format!("--every {every} is below the one-minute minimum"),
+            Some("Native schedulers tick at minute granularity; try `--every 5m`.".to_string()),
+        ));
+    }
+    let report = crate::schedule::install(interval_secs).map_err(schedule_cli_error)?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "report": report,
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Scheduled `cass index` every {}s via {}.",
+        report.interval_secs,
+        report.units.scheduler.label()
+    );
+    for path in &report.units.paths {
+        println!("  wrote {}", path.display());
+    }
+    println!("  registered with: {}", report.register_command);
+    Ok(())
+}
+
+fn run_schedule_status(output_format: Option<RobotFormat>) -> CliResult<()> {
+    let status = crate::schedule::status().map_err(schedule_cli_error)?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "status": status,
+            }),
+            fmt,
+        );
+    }
+    if !status.installed {
+        println!(
+            "No schedule installed ({}). Install one with `cass schedule install --every 30m`.",
+            status.scheduler.label()
+        );
+        return Ok(());
+    }
+    println!("Schedule installed via {}.", status.scheduler.label());
+    for path in &status.units.paths {
+        println!("  {}", path.display());
+    }
+    match status.native_state {
+        Some(state) => println!("  scheduler reports: {state}"),
+        None => println!("  scheduler reports: not registered"),
+    }
+    Ok(())
+}
+
+fn run_schedule_uninstall(output_format: Option<RobotFormat>) -> CliResult<()> {
+    let removed = crate::schedule::uninstall().map_err(schedule_cli_error)?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "removed": removed,
+            }),
+            fmt,
+        );
+    }
+    if removed {
+        println!("Schedule removed.");
+    } else {
+        println!("No schedule was installed.");
+    }
+    Ok(())
+}
+
+/// `cass debug reparse`: replay the current connector parser against the
+/// raw-mirror capture of an indexed conversation.
+///
+/// The raw mirror stores verbatim copies of every scanned source file keyed by
+/// content hash, with manifests linking captures back to conversation rows.
+/// This command resolves the conversation, locates its newest capture, copies
+/// the blob into a scratch file carrying the original file name (connectors
+/// key off names and extensions), and runs the recorded provider's connector
+/// over it — then reports parsed counts next to what the database holds, so a
+/// parser regression or fix shows up as a count or title drift.
+fn run_debug_reparse(
+    conversation: &str,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
+
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let conn = open_franken_analytics_db(&data_dir_override, db_override.as_ref())?;
+    let conv_id = resolve_conversation_arg(&conn, conversation)?.ok_or_else(|| CliError {
+        code: 4,
+        kind: CliErrorKind::NotFound.kind_str(),
+        message: format!("No conversation matches '{conversation}'"),
+        hint: Some(
+            "Pass a conversation row id, a harness session id, or a source path \
+             from search output."
+                .to_string(),
+        ),
+        retryable: false,
+    })?;
+
+    let (agent_slug, stored_title, stored_source_path): (String, Option<String>, String) = conn
+        .query_row_map(
+            "SELECT COALESCE(a.slug, 'unknown'), c.title, c.source_path
+             FROM conversations c
+             LEFT JOIN agents a ON c.agent_id = a.id
+             WHERE c.id = ?",
+            &[ParamValue::from(conv_id)],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+        )
+        .optional()
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?
+        .expect("conversation id came from resolve_conversation_arg");
+    let stored_message_count: i64 = conn
+        .query_row_map(
+            "SELECT COUNT(*) FROM messages WHERE conversation_id = ?",
+            &[ParamValue::from(conv_id)],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let capture = crate::raw_mirror::find_capture_for_conversation(&data_dir, conv_id)
+        .map_err(|err| CliError {
+            code: 9,
+            kind: "raw-mirror",
+            message: format!("raw-mirror lookup failed: {err}"),
+            hint: None,
+            retryable: false,
+        })?
+        .ok_or_else(|| CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!(
+                "no raw-mirror capture references conversation {conv_id} (source {stored_source_path})"
+            ),
+            hint: Some(
+                "Captures are written during indexing; rerun `cass index` while the original \
+                 source file still exists, or check `cass mirror prune` retention."
+                    .to_string(),
+            ),
+            retryable: false,
+        })?;
+
+    // Stage the blob under the original file name: connectors route on file
+    // names and extensions (e.g. Codex `rollout-*.jsonl`), and the blob store
+    // names everything `<hash>.raw`.
+    let scratch = tempfile::tempdir()
+        .map_err(|e| CliError::unknown(format!("failed to create reparse scratch dir: {e}")))?;
+    let original_name = Path::new(&capture.original_path)
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| std::ffi::OsString::from("capture.jsonl"));
+    let staged_path = scratch.path().join(original_name);
+    std::fs::copy(&capture.blob_path, &staged_path).map_err(|e| {
+        CliError::unknown(format!(
+            "failed to stage raw-mirror blob {}: {e}",
+            capture.blob_path.display()
+        ))
+    })?;
+
+    let mut registry = crate::connector_registry::ConnectorRegistry::new();
+    let connector = registry.get(&capture.provider).ok_or_else(|| CliError {
+        code: 2,
+        kind: CliErrorKind::Usage.kind_str(),
+        message: format!(
+            "connector '{}' is not compiled into this binary",
+            capture.provider
+        ),
+        hint: Some(
+            "Rebuild with the matching connector feature enabled (see `cass capabilities`)."
+                .to_string(),
+        ),
+        retryable: false,
+    })?;
+    let ctx = crate::connectors::ScanContext::with_roots(
+        staged_path.clone(),
+        vec![crate::connectors::ScanRoot::local(staged_path.clone())],
+        None,
+    );
+    let parsed = connector.scan(&ctx).map_err(|err| CliError {
+        code: 9,
+        kind: "reparse",
+        message: format!(
+            "connector '{}' failed to parse capture {}: {err}",
+            capture.provider, capture.manifest_id
+        ),
+        hint: None,
+        retryable: false,
+    })?;
+
+    let parsed_conversations = parsed.len();
+    let parsed_messages: usize = parsed.iter().map(|conv| conv.messages.len()).sum();
+    let parsed_titles: Vec<Option<String>> = parsed.iter().map(|conv| conv.title.clone()).collect();
+    let message_count_matches = parsed_messages == stored_message_count.max(0) as usize;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "conversation_id": conv_id,
+                "agent": agent_slug,
+                "stored": {
+                    "title": stored_title,
+                    "message_count": stored_message_count,
+                    "source_path": stored_source_path,
+                },
+                "capture": {
+                    "manifest_id": capture.manifest_id,
+                    "blob_blake3": capture.blob_blake3,
+                    "blob_size_bytes": capture.blob_size_bytes,
+                    "original_path": capture.original_path,
+                    "captured_at_ms": capture.captured_at_ms,
+                },
+                "reparse": {
+                    "provider": capture.provider,
+                    "conversations": parsed_conversations,
+                    "messages": parsed_messages,
+                    "titles": parsed_titles,
+                    "message_count_matches": message_count_matches,
+                },
+            }),
+            fmt,
+        );
+    }
+
+    println!("Reparse of conversation {conv_id} ({agent_slug})");
+    println!("  Stored source: {stored_source_path}");
+    println!(
+        "  Stored title: {}",
+        stored_title.as_deref().unwrap_or("<none>")
+    );
+    println!("  Stored messages: {stored_message_count}");
+    println!("  Capture: {}", capture.manifest_id);
+    println!("  Blob blake3: {}", capture.blob_blake3);
+    println!("  Blob bytes: {}", capture.blob_size_bytes);
+    println!("  Parsed conversations: {parsed_conversations}");
+    println!("  Parsed messages: {parsed_messages}");
+    for title in &parsed_titles {
+        println!("  Parsed title: {}", title.as_deref().unwrap_or("<none>"));
+    }
+    if message_count_matches {
+        println!("  Message counts match.");
+    } else {
+        println!("  Message counts differ (parser drift or partial original capture).");
+    }
+    Ok(())
+}
+
 /// `cass quarantine` (#292 ask #3): inspect and manage the
 /// conversation-ingest quarantine without hand-editing
 /// `quarantine_state.json`. `list` is read-only; `clear` is dry-run by
@@ -8356,6 +11653,149 @@ fn run_quarantine_clear(
     Ok(())
 }
 
+fn run_db_command(cmd: DbCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        DbCommand::Slice {
+            since,
+            out,
+            db,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_db_slice(&since, &out, db, cli, structured_format)
+        }
+    }
+}
+
+/// `cass db slice --since 90d --out small.db`: write a trimmed standalone
+/// copy of the canonical database containing only recently active
+/// conversations, for carrying onto constrained devices. The source database
+/// is never mutated; FTS and derived stats are rebuilt inside the copy so it
+/// works everywhere the full database does.
+fn run_db_slice(
+    since: &str,
+    out: &Path,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let window_ms = parse_duration_millis(since)?;
+    let cutoff_ms = chrono::Utc::now()
+        .timestamp_millis()
+        .saturating_sub(window_ms);
+
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(default_db_path);
+    if !db_path.is_file() {
+        return Err(CliError {
+            code: 5,
+            kind: "db",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
+            retryable: false,
+        });
+    }
+    if out.exists() {
+        return Err(CliError::usage(
+            format!("slice output path already exists: {}", out.display()),
+            Some("Pass a fresh --out path, or delete the existing file first.".to_string()),
+        ));
+    }
+
+    let report =
+        crate::storage::sqlite::slice_database(&db_path, out, cutoff_ms).map_err(|e| CliError {
+            code: 5,
+            kind: "db",
+            message: format!("slice failed: {e:#}"),
+            hint: None,
+            retryable: false,
+        })?;
+
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("schema_version".to_string(), serde_json::json!(1));
+            obj.insert("since".to_string(), serde_json::json!(since));
+            obj.insert(
+                "db_path".to_string(),
+                serde_json::json!(db_path.display().to_string()),
+            );
+            obj.insert(
+                "out_path".to_string(),
+                serde_json::json!(out.display().to_string()),
+            );
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("CASS DB Slice (trimmed copy of recent history)");
+    println!("==============================================");
+    println!();
+    println!("Source:   {}", db_path.display());
+    println!("Output:   {}", out.display());
+    println!("Window:   {since} (cutoff {cutoff_ms})");
+    println!();
+    println!(
+        "Kept {} of {} conversation(s) ({} message(s)); dropped {}.",
+        report.conversations_kept,
+        report.conversations_total,
+        report.messages_kept,
+        report.conversations_dropped,
+    );
+    println!(
+        "Slice size: {:.1} MiB",
+        report.output_bytes as f64 / (1024.0 * 1024.0)
+    );
+    println!();
+    println!("Use it with: cass search <query> --db {}", out.display());
+    Ok(())
+}
+
+/// `cass complete-workspaces [prefix]`: dynamic completion data for
+/// `workspace:` / `--workspace` values. Prints one indexed workspace path per
+/// line, most recently active first, optionally filtered to a prefix and
+/// capped to `--limit` entries. Meant to be wired into shell completion
+/// functions (the static `cass completions` scripts can only enumerate flags,
+/// not values), so every failure mode — no database yet, unreadable database —
+/// degrades to empty output and exit 0 rather than an error message that
+/// would garble the user's completion attempt.
+fn run_complete_workspaces(
+    prefix: Option<&str>,
+    db_override: Option<PathBuf>,
+    limit: usize,
+    cli: &Cli,
+) -> CliResult<()> {
+    use crate::storage::sqlite::FrankenStorage;
+
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(default_db_path);
+    if !db_path.is_file() {
+        return Ok(());
+    }
+    let Ok(storage) = FrankenStorage::open(&db_path) else {
+        return Ok(());
+    };
+    let Ok(paths) = storage.list_workspace_paths_by_recency() else {
+        return Ok(());
+    };
+
+    let prefix = prefix.unwrap_or("").trim();
+    let mut printed = 0usize;
+    for path in paths {
+        if !prefix.is_empty() && !path.starts_with(prefix) {
+            continue;
+        }
+        println!("{path}");
+        printed += 1;
+        if limit != 0 && printed >= limit {
+            break;
+        }
+    }
+    Ok(())
+}
+
 /// `cass forget --source-glob <pat>`: prune an already-indexed subset of
 /// conversations by source-path glob (#292 ask #2). Dry-run by default;
 /// `--apply` deletes the matching rows from the canonical DB and rebuilds the
@@ -8475,6 +11915,9 @@ fn run_forget_command(
             "Deleted {} conversation(s) from the canonical DB and rebuilt derived assets.",
             report.conversations_deleted
         );
+        if let Some(operation_id) = &report.trash_operation_id {
+            println!("Undo within the retention window: cass undo {operation_id}");
+        }
     } else if report.conversations_matched > 0 {
         println!("Re-run with --apply to delete these conversations.");
     } else {
@@ -8483,6 +11926,785 @@ fn run_forget_command(
     Ok(())
 }
 
+fn run_retitle(
+    regenerate: bool,
+    db_override: Option<PathBuf>,
+    cli: &Cli,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use crate::storage::sqlite::FrankenStorage;
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    let db_path = db_override
+        .or_else(|| cli.db.clone())
+        .unwrap_or_else(default_db_path);
+    if !db_path.is_file() {
+        return Err(CliError {
+            code: 5,
+            kind: "retitle",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
+            retryable: false,
+        });
+    }
+
+    let storage = FrankenStorage::open(&db_path).map_err(|e| CliError {
+        code: 5,
+        kind: "retitle",
+        message: format!("failed to open canonical database: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+    let conn = storage.raw();
+
+    let rows: Vec<(i64, Option<String>)> = conn
+        .query_map_collect(
+            "SELECT id, title FROM conversations ORDER BY id",
+            &[],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?)),
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let total = rows.len();
+    let mut candidates = 0usize;
+    let mut retitled = 0usize;
+    let mut unresolved = 0usize;
+    let mut samples: Vec<(i64, String, String)> = Vec::new();
+    const MAX_SAMPLES: usize = 10;
+    // Cap how much content the heuristic reads per conversation; the opening
+    // request is always near the front.
+    const MAX_USER_MESSAGES: i64 = 5;
+
+    for (conv_id, title) in &rows {
+        if !crate::title_heuristics::title_needs_regeneration(title.as_deref()) {
+            continue;
+        }
+        candidates += 1;
+        let user_contents: Vec<String> = conn
+            .query_map_collect(
+                "SELECT content FROM messages
+                 WHERE conversation_id = ? AND role = 'user'
+                 ORDER BY idx LIMIT ?",
+                &[
+                    ParamValue::from(*conv_id),
+                    ParamValue::from(MAX_USER_MESSAGES),
+                ],
+                |r: &frankensqlite::Row| r.get_typed(0),
+            )
+            .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+        let Some(new_title) =
+            crate::title_heuristics::derive_title(user_contents.iter().map(String::as_str))
+        else {
+            unresolved += 1;
+            continue;
+        };
+        if title.as_deref() == Some(new_title.as_str()) {
+            continue;
+        }
+        if samples.len() < MAX_SAMPLES {
+            samples.push((
+                *conv_id,
+                title.clone().unwrap_or_else(|| "(none)".to_string()),
+                new_title.clone(),
+            ));
+        }
+        if regenerate {
+            conn.execute_compat(
+                "UPDATE conversations SET title = ? WHERE id = ?",
+                frankensqlite::params![new_title, *conv_id],
+            )
+            .map_err(|e| CliError::unknown(format!("update: {e}")))?;
+        }
+        retitled += 1;
+    }
+
+    // Titles feed the FTS/lexical indexes; rebuild DB-resident FTS now so the
+    // new titles are searchable without waiting for the next full index.
+    if regenerate
+        && retitled > 0
+        && let Err(e) = storage.rebuild_fts()
+    {
+        tracing::warn!(error = %e, "retitle: failed to rebuild FTS after title updates");
+    }
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "applied": regenerate,
+            "total_conversations": total,
+            "candidates": candidates,
+            "retitled": retitled,
+            "unresolved": unresolved,
+            "samples": samples
+                .iter()
+                .map(|(id, old, new)| {
+                    serde_json::json!({ "id": id, "old": old, "new": new })
+                })
+                .collect::<Vec<_>>(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("CASS Retitle (heuristic titles for untitled sessions)");
+    println!("======================================================");
+    println!();
+    println!(
+        "Mode: {}",
+        if regenerate {
+            "REGENERATE (mutating)"
+        } else {
+            "dry-run (inspect only)"
+        }
+    );
+    println!();
+    println!("Conversations scanned:  {total}");
+    println!("Needing a better title: {candidates}");
+    println!(
+        "{} {retitled}",
+        if regenerate {
+            "Retitled:              "
+        } else {
+            "Would retitle:         "
+        }
+    );
+    if unresolved > 0 {
+        println!("No derivable title:     {unresolved}");
+    }
+    if !samples.is_empty() {
+        println!();
+        println!("Sample changes:");
+        for (id, old, new) in &samples {
+            println!("  #{id}: {old} \u{2192} {new}");
+        }
+    }
+    println!();
+    if regenerate {
+        println!("Done. New titles are live in search.");
+    } else if retitled > 0 {
+        println!("Re-run with --regenerate to write these titles.");
+    } else {
+        println!("Nothing to retitle.");
+    }
+    Ok(())
+}
+
+fn run_meta_command(cmd: MetaCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        MetaCommand::Export {
+            output,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_meta_export(output, data_dir, cli.db.clone(), structured_format)
+        }
+        MetaCommand::Import {
+            input,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_meta_import(input, data_dir, cli.db.clone(), structured_format)
+        }
+    }
+}
+
+fn meta_error(message: String, hint: Option<String>) -> CliError {
+    CliError {
+        code: 5,
+        kind: "meta",
+        message,
+        hint,
+        retryable: false,
+    }
+}
+
+/// `cass meta export`: write a portable curation snapshot (tags, bookmarks,
+/// saved views) keyed by stable conversation identity.
+fn run_meta_export(
+    output: PathBuf,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let conn = open_franken_analytics_db(&data_dir_override, db_override.as_ref())?;
+
+    let tags = crate::user_meta::collect_tagged_conversations(&conn)
+        .map_err(|e| meta_error(format!("collecting conversation tags: {e}"), None))?;
+    let device_id = crate::user_meta::local_device_id(&data_dir);
+    let mut bookmarks = crate::bookmarks::BookmarkStore::open(&data_dir.join("bookmarks.db"))
+        .and_then(|store| store.list(None))
+        .map_err(|e| meta_error(format!("reading bookmarks: {e}"), None))?;
+    // Annotations written before per-row device tracking carry an empty id;
+    // stamp them as ours so the importing side can order them.
+    for bookmark in &mut bookmarks {
+        if bookmark.device_id.is_empty() {
+            bookmark.device_id = device_id.clone();
+        }
+    }
+    let saved_views = crate::user_meta::read_saved_views(&data_dir.join("tui_state.json"))
+        .map_err(|e| meta_error(format!("reading saved views: {e}"), None))?;
+
+    let snapshot = crate::user_meta::MetaSnapshot {
+        schema_version: crate::user_meta::META_SNAPSHOT_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        device_id,
+        tags,
+        bookmarks,
+        saved_views,
+    };
+    let payload = serde_json::to_vec_pretty(&snapshot)
+        .map_err(|e| meta_error(format!("serializing snapshot: {e}"), None))?;
+    std::fs::write(&output, payload).map_err(|e| {
+        meta_error(
+            format!("writing {}: {e}", output.display()),
+            Some("Check that the target directory exists and is writable.".to_string()),
+        )
+    })?;
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "output": output.display().to_string(),
+            "tagged_conversations": snapshot.tags.len(),
+            "bookmarks": snapshot.bookmarks.len(),
+            "saved_views": snapshot.saved_views.len(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Exported curation snapshot to {}", output.display());
+    println!("  tagged conversations: {}", snapshot.tags.len());
+    println!("  bookmarks:            {}", snapshot.bookmarks.len());
+    println!("  saved views:          {}", snapshot.saved_views.len());
+    println!();
+    println!(
+        "Import on another machine with: cass meta import {}",
+        output.display()
+    );
+    Ok(())
+}
+
+/// `cass meta import`: merge a curation snapshot into the local data dir.
+fn run_meta_import(
+    input: PathBuf,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let snapshot = crate::user_meta::read_snapshot(&input).map_err(|e| {
+        meta_error(
+            format!("{e:#}"),
+            Some("Pass a file written by `cass meta export`.".to_string()),
+        )
+    })?;
+
+    let db_path = analytics_db_path(&data_dir_override, db_override.as_ref());
+    if !db_path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: CliErrorKind::MissingDb.kind_str(),
+            message: format!(
+                "Database not found at {}. Run 'cass index --full' first.",
+                db_path.display()
+            ),
+            hint: Some("Index this machine's sessions, then re-run the import.".into()),
+            retryable: true,
+        });
+    }
+    let conn = frankensqlite::Connection::open(db_path.to_string_lossy().as_ref())
+        .map_err(|e| meta_error(format!("opening {}: {e}", db_path.display()), None))?;
+    conn.execute_batch("PRAGMA busy_timeout = 5000;")
+        .map_err(|e| meta_error(format!("configuring db: {e}"), None))?;
+
+    let mut report = crate::user_meta::MetaImportReport::default();
+    let (applied, matched, unmatched) =
+        crate::user_meta::apply_tagged_conversations(&conn, &snapshot.tags)
+            .map_err(|e| meta_error(format!("applying tags: {e}"), None))?;
+    report.tags_applied = applied;
+    report.conversations_matched = matched;
+    report.conversations_unmatched = unmatched;
+
+    if !snapshot.bookmarks.is_empty() {
+        let mut incoming = snapshot.bookmarks.clone();
+        // Rows from pre-device snapshots inherit the snapshot-level id so the
+        // last-writer-wins tie-break stays deterministic.
+        for bookmark in &mut incoming {
+            if bookmark.device_id.is_empty() {
+                bookmark.device_id = snapshot.device_id.clone();
+            }
+        }
+        let outcome = crate::bookmarks::BookmarkStore::open(&data_dir.join("bookmarks.db"))
+            .and_then(|store| store.merge_annotations(&incoming))
+            .map_err(|e| meta_error(format!("importing bookmarks: {e}"), None))?;
+        report.bookmarks_imported = outcome.imported;
+        report.bookmarks_merged = outcome.merged;
+        report.bookmark_conflicts = outcome.conflicts;
+    }
+    report.saved_views_added = crate::user_meta::merge_saved_views(
+        &data_dir.join("tui_state.json"),
+        &snapshot.saved_views,
+    )
+    .map_err(|e| meta_error(format!("merging saved views: {e}"), None))?;
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("schema_version".to_string(), serde_json::json!(1));
+            obj.insert(
+                "input".to_string(),
+                serde_json::json!(input.display().to_string()),
+            );
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Imported curation snapshot from {}", input.display());
+    println!(
+        "  tags applied:         {} ({} conversations matched)",
+        report.tags_applied, report.conversations_matched
+    );
+    if report.conversations_unmatched > 0 {
+        println!(
+            "  unmatched entries:    {} (not indexed here yet; re-import after `cass index`)",
+            report.conversations_unmatched
+        );
+    }
+    println!("  bookmarks imported:   {}", report.bookmarks_imported);
+    if report.bookmarks_merged > 0 {
+        println!("  bookmarks merged:     {}", report.bookmarks_merged);
+    }
+    for conflict in &report.bookmark_conflicts {
+        let location = match conflict.line_number {
+            Some(line) => format!("{}:{line}", conflict.source_path),
+            None => conflict.source_path.clone(),
+        };
+        println!(
+            "  note conflict:        {location} (kept {} note; local device {}, imported device {})",
+            conflict.kept, conflict.local_device, conflict.imported_device
+        );
+    }
+    println!("  saved views added:    {}", report.saved_views_added);
+    Ok(())
+}
+
+fn run_profile_command(cmd: ProfileCommand, cli: &Cli) -> CliResult<()> {
+    match cmd {
+        ProfileCommand::List { json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_profile_list(structured_format)
+        }
+        ProfileCommand::Create {
+            name,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_profile_create(&name, data_dir, structured_format)
+        }
+        ProfileCommand::Switch { name, json } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_profile_switch(&name, structured_format)
+        }
+        ProfileCommand::Export {
+            name,
+            output,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_profile_export(name, output, data_dir, structured_format)
+        }
+        ProfileCommand::Import {
+            input,
+            dry_run,
+            data_dir,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_profile_import(input, dry_run, data_dir, structured_format)
+        }
+    }
+}
+
+fn profile_error(message: String, hint: Option<String>) -> CliError {
+    CliError {
+        code: 5,
+        kind: "profile",
+        message,
+        hint,
+        retryable: false,
+    }
+}
+
+/// `cass profile list`: known named profiles plus the implicit `default`,
+/// with data dirs and the active marker.
+fn run_profile_list(output_format: Option<RobotFormat>) -> CliResult<()> {
+    let registry = crate::profiles::ProfilesConfig::load()
+        .map_err(|e| profile_error(format!("loading profiles registry: {e}"), None))?;
+    let running = crate::profiles::active_profile_name();
+    let configured_active = registry.active.clone();
+
+    let mut rows: Vec<serde_json::Value> = vec![serde_json::json!({
+        "name": crate::profiles::DEFAULT_PROFILE,
+        "data_dir": default_data_dir().display().to_string(),
+        "active": configured_active.is_none(),
+        "running": running.is_none(),
+    })];
+    for profile in &registry.profiles {
+        rows.push(serde_json::json!({
+            "name": profile.name,
+            "data_dir": crate::profiles::profile_data_dir(profile).display().to_string(),
+            "active": configured_active.as_deref() == Some(profile.name.as_str()),
+            "running": running.as_deref() == Some(profile.name.as_str()),
+        }));
+    }
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "profiles": rows,
+                "registry": crate::profiles::registry_path()
+                    .map(|p| p.display().to_string())
+                    .ok(),
+            }),
+            fmt,
+        );
+    }
+    println!("Profiles:");
+    for row in rows {
+        let marker = if row["active"].as_bool() == Some(true) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "  {marker} {:<16} {}",
+            row["name"].as_str().unwrap_or("?"),
+            row["data_dir"].as_str().unwrap_or("?"),
+        );
+    }
+    println!();
+    println!(
+        "* = active default; this run used '{}'.",
+        running
+            .as_deref()
+            .unwrap_or(crate::profiles::DEFAULT_PROFILE)
+    );
+    Ok(())
+}
+
+/// `cass profile create`: register a named profile without switching to it.
+fn run_profile_create(
+    name: &str,
+    data_dir: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let mut registry = crate::profiles::ProfilesConfig::load()
+        .map_err(|e| profile_error(format!("loading profiles registry: {e}"), None))?;
+    let resolved_dir = {
+        let profile = registry
+            .create(name, data_dir)
+            .map_err(|e| profile_error(e.to_string(), None))?;
+        crate::profiles::profile_data_dir(profile)
+    };
+    registry
+        .save()
+        .map_err(|e| profile_error(format!("saving profiles registry: {e}"), None))?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "name": name,
+                "data_dir": resolved_dir.display().to_string(),
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Created profile '{name}' (data dir: {}).",
+        resolved_dir.display()
+    );
+    println!(
+        "Use it with `cass --profile {name} …` or make it the default with `cass profile switch {name}`."
+    );
+    Ok(())
+}
+
+/// `cass profile switch`: make a profile the default for future runs.
+fn run_profile_switch(name: &str, output_format: Option<RobotFormat>) -> CliResult<()> {
+    let mut registry = crate::profiles::ProfilesConfig::load()
+        .map_err(|e| profile_error(format!("loading profiles registry: {e}"), None))?;
+    registry.switch(name).map_err(|e| {
+        let known: Vec<&str> = registry.profiles.iter().map(|p| p.name.as_str()).collect();
+        profile_error(
+            e.to_string(),
+            Some(if known.is_empty() {
+                "No profiles exist yet; create one with `cass profile create <name>`.".to_string()
+            } else {
+                format!("Known profiles: default, {}", known.join(", "))
+            }),
+        )
+    })?;
+    registry
+        .save()
+        .map_err(|e| profile_error(format!("saving profiles registry: {e}"), None))?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "active": registry.active,
+            }),
+            fmt,
+        );
+    }
+    match &registry.active {
+        Some(active) => println!("Switched to profile '{active}'."),
+        None => println!("Switched to the default (unscoped) profile."),
+    }
+    Ok(())
+}
+
+/// `cass profile export`: bundle the current search configuration into a
+/// named, versioned profile file (see `crate::search_profile`).
+fn run_profile_export(
+    name: String,
+    output: PathBuf,
+    data_dir_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let search = crate::search_defaults::load_search_defaults()
+        .map_err(|e| profile_error(format!("loading search defaults: {e}"), None))?;
+    let tui = crate::search_defaults::load_tui_defaults()
+        .map_err(|e| profile_error(format!("loading tui defaults: {e}"), None))?;
+    // Inline the ranking script by content so the profile is self-contained.
+    let ranking_script = search
+        .ranking_script
+        .as_deref()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| profile_error(format!("reading ranking script {path}: {e}"), None))
+        })
+        .transpose()?;
+    let saved_views = crate::user_meta::read_saved_views(&data_dir.join("tui_state.json"))
+        .map_err(|e| profile_error(format!("reading saved views: {e}"), None))?;
+    let disabled_agents = crate::sources::config::SourcesConfig::load()
+        .map(|cfg| cfg.disabled_agents)
+        .unwrap_or_default();
+
+    let profile = crate::search_profile::SearchProfile {
+        format_version: crate::search_profile::PROFILE_FORMAT_VERSION,
+        name,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        search,
+        tui,
+        ranking_script,
+        saved_views,
+        disabled_agents,
+    };
+    profile.validate().map_err(|e| {
+        profile_error(
+            format!("refusing to export an invalid profile: {e}"),
+            Some("Fix the local configuration, then re-export.".to_string()),
+        )
+    })?;
+    let payload = serde_json::to_vec_pretty(&profile)
+        .map_err(|e| profile_error(format!("serializing profile: {e}"), None))?;
+    std::fs::write(&output, payload).map_err(|e| {
+        profile_error(
+            format!("writing {}: {e}", output.display()),
+            Some("Check that the target directory exists and is writable.".to_string()),
+        )
+    })?;
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "name": profile.name,
+            "output": output.display().to_string(),
+            "format_version": profile.format_version,
+            "has_ranking_script": profile.ranking_script.is_some(),
+            "saved_views": profile.saved_views.len(),
+            "disabled_agents": profile.disabled_agents.len(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!(
+        "Exported profile '{}' to {}",
+        profile.name,
+        output.display()
+    );
+    println!(
+        "  ranking script:  {}",
+        if profile.ranking_script.is_some() {
+            "inlined"
+        } else {
+            "none"
+        }
+    );
+    println!("  saved views:     {}", profile.saved_views.len());
+    println!("  disabled agents: {}", profile.disabled_agents.len());
+    println!();
+    println!(
+        "Import on another machine with: cass profile import {}",
+        output.display()
+    );
+    Ok(())
+}
+
+/// `cass profile import`: validate a profile file and merge it into the
+/// local configuration. Overlay semantics — fields the profile leaves unset
+/// keep their local values, and nothing is ever removed.
+fn run_profile_import(
+    input: PathBuf,
+    dry_run: bool,
+    data_dir_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let profile = crate::search_profile::read_profile(&input).map_err(|e| {
+        profile_error(
+            format!("{e:#}"),
+            Some("Pass a file written by `cass profile export`.".to_string()),
+        )
+    })?;
+
+    let config_path = crate::search_defaults::config_path().ok_or_else(|| {
+        profile_error(
+            "could not resolve the cass config directory".to_string(),
+            Some("Set XDG_CONFIG_HOME or HOME so cass.toml has a place to live.".to_string()),
+        )
+    })?;
+
+    // The inlined ranking script lands next to cass.toml, named after the
+    // profile, and the merged config points at that copy.
+    let script_path = profile.ranking_script.as_ref().map(|_| {
+        config_path
+            .with_file_name("profiles")
+            .join(format!("{}.rank", profile.name))
+    });
+
+    let existing = if config_path.exists() {
+        std::fs::read_to_string(&config_path)
+            .map_err(|e| profile_error(format!("reading {}: {e}", config_path.display()), None))?
+    } else {
+        String::new()
+    };
+    let merged = crate::search_profile::merged_config_toml(
+        &existing,
+        &profile,
+        script_path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .as_deref(),
+    )
+    .map_err(|e| profile_error(e, None))?;
+
+    let mut saved_views_added = 0;
+    let mut agents_disabled = 0;
+    if !dry_run {
+        if let (Some(script), Some(path)) = (profile.ranking_script.as_deref(), &script_path) {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    profile_error(format!("creating {}: {e}", parent.display()), None)
+                })?;
+            }
+            std::fs::write(path, script)
+                .map_err(|e| profile_error(format!("writing {}: {e}", path.display()), None))?;
+        }
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| profile_error(format!("creating {}: {e}", parent.display()), None))?;
+        }
+        std::fs::write(&config_path, &merged)
+            .map_err(|e| profile_error(format!("writing {}: {e}", config_path.display()), None))?;
+
+        saved_views_added = crate::user_meta::merge_saved_views(
+            &data_dir.join("tui_state.json"),
+            &profile.saved_views,
+        )
+        .map_err(|e| profile_error(format!("merging saved views: {e}"), None))?;
+
+        if !profile.disabled_agents.is_empty() {
+            let mut sources = crate::sources::config::SourcesConfig::load()
+                .map_err(|e| profile_error(format!("loading sources.toml: {e}"), None))?;
+            for agent in &profile.disabled_agents {
+                if sources.exclude_agent_from_indexing(agent).unwrap_or(false) {
+                    agents_disabled += 1;
+                }
+            }
+            if agents_disabled > 0 {
+                sources
+                    .save()
+                    .map_err(|e| profile_error(format!("saving sources.toml: {e}"), None))?;
+            }
+        }
+    }
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "input": input.display().to_string(),
+            "name": profile.name,
+            "dry_run": dry_run,
+            "config_path": config_path.display().to_string(),
+            "ranking_script_path": script_path.as_ref().map(|p| p.display().to_string()),
+            "saved_views_added": saved_views_added,
+            "agents_disabled": agents_disabled,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if dry_run {
+        println!(
+            "Profile '{}' is valid (dry run; nothing written)",
+            profile.name
+        );
+    } else {
+        println!("Imported profile '{}'", profile.name);
+        println!("  config updated:  {}", config_path.display());
+        if let Some(path) = &script_path {
+            println!("  ranking script:  {}", path.display());
+        }
+        println!("  saved views added: {saved_views_added}");
+        if agents_disabled > 0 {
+            println!("  agents disabled:   {agents_disabled}");
+        }
+    }
+    Ok(())
+}
+
 fn run_mirror_prune(
     data_dir_override: Option<PathBuf>,
     older_than: Option<String>,
@@ -16077,10 +20299,17 @@ fn resolve_analytics_workspace_ids(
 
     let mut workspace_ids = std::collections::BTreeSet::new();
     for workspace in requested_paths {
+        // Hierarchical match: the path itself plus workspaces nested under it
+        // on whole path components, so filtering on a monorepo root covers
+        // its sub-projects (`repo` matches `repo/services/api`, not `repo2`).
+        let nested = format!("{}/%", workspace.trim_end_matches('/'));
         let ids: Vec<i64> = conn
             .query_map_collect(
-                "SELECT id FROM workspaces WHERE path = ?1",
-                &[ParamValue::from(workspace.as_str())],
+                "SELECT id FROM workspaces WHERE path = ?1 OR path LIKE ?2",
+                &[
+                    ParamValue::from(workspace.as_str()),
+                    ParamValue::from(nested.as_str()),
+                ],
                 |row: &frankensqlite::Row| row.get_typed(0),
             )
             .map_err(|e| CliError {
@@ -19454,6 +23683,7 @@ fn describe_command(cli: &Cli) -> String {
         Some(Commands::SupportBundle { .. }) => "support-bundle".to_string(),
         Some(Commands::View { .. }) => "view".to_string(),
         Some(Commands::Completions { .. }) => "completions".to_string(),
+        Some(Commands::CompleteWorkspaces { .. }) => "complete-workspaces".to_string(),
         Some(Commands::Man) => "man".to_string(),
         Some(Commands::Capabilities { .. }) => "capabilities".to_string(),
         Some(Commands::ApiVersion { .. }) => "api-version".to_string(),
@@ -19466,25 +23696,58 @@ fn describe_command(cli: &Cli) -> String {
         Some(Commands::Guide { .. }) => "guide".to_string(),
         Some(Commands::Doctor { .. }) => "doctor".to_string(),
         Some(Commands::Context { .. }) => "context".to_string(),
+        Some(Commands::Lineage { .. }) => "lineage".to_string(),
+        Some(Commands::Compare { .. }) => "compare".to_string(),
+        Some(Commands::Recent { .. }) => "recent".to_string(),
         Some(Commands::Sessions { .. }) => "sessions".to_string(),
+        Some(Commands::Runs { .. }) => "runs".to_string(),
+        Some(Commands::MarkRead { .. }) => "mark-read".to_string(),
         Some(Commands::Resume { .. }) => "resume".to_string(),
         Some(Commands::Upgrade { .. }) => "upgrade".to_string(),
+        Some(Commands::SelfUpdate { .. }) => "self-update".to_string(),
         Some(Commands::Export { .. }) => "export".to_string(),
+        Some(Commands::Share { .. }) => "share".to_string(),
         Some(Commands::ExportHtml { .. }) => "export-html".to_string(),
+        Some(Commands::Distill { .. }) => "distill".to_string(),
+        Some(Commands::Delta { .. }) => "delta".to_string(),
+        Some(Commands::Focus { .. }) => "focus".to_string(),
+        Some(Commands::Ngrams { .. }) => "ngrams".to_string(),
+        Some(Commands::Corrections { .. }) => "corrections".to_string(),
         Some(Commands::Expand { .. }) => "expand".to_string(),
+        Some(Commands::GetContext { .. }) => "get-context".to_string(),
+        Some(Commands::Show { .. }) => "show".to_string(),
+        Some(Commands::Audit { .. }) => "audit".to_string(),
+        Some(Commands::Verify { .. }) => "verify".to_string(),
+        Some(Commands::Agents { .. }) => "agents".to_string(),
         Some(Commands::Timeline { .. }) => "timeline".to_string(),
+        Some(Commands::Sample { .. }) => "sample".to_string(),
+        Some(Commands::Scan { .. }) => "scan".to_string(),
+        Some(Commands::Bench(..)) => "bench".to_string(),
         Some(Commands::Quarantine(..)) => "quarantine".to_string(),
         Some(Commands::Forget { .. }) => "forget".to_string(),
+        Some(Commands::Retitle { .. }) => "retitle".to_string(),
         Some(Commands::Mirror(..)) => "mirror".to_string(),
+        Some(Commands::Debug(..)) => "debug".to_string(),
+        Some(Commands::Secrets(..)) => "secrets".to_string(),
+        Some(Commands::Trash(..)) => "trash".to_string(),
+        Some(Commands::Db(..)) => "db".to_string(),
+        Some(Commands::Undo { .. }) => "undo".to_string(),
+        Some(Commands::Note(..)) => "note".to_string(),
+        Some(Commands::Workspace(..)) => "workspace".to_string(),
+        Some(Commands::Tag(..)) => "tag".to_string(),
+        Some(Commands::Schedule(..)) => "schedule".to_string(),
         Some(Commands::Sources(..)) => "sources".to_string(),
         Some(Commands::Models(..)) => "models".to_string(),
         Some(Commands::Fleet(..)) => "fleet".to_string(),
+        Some(Commands::Watch(..)) => "watch".to_string(),
         Some(Commands::Lessons(..)) => "lessons".to_string(),
         Some(Commands::Swarm(..)) => "swarm".to_string(),
         Some(Commands::Pages { .. }) => "pages".to_string(),
         #[cfg(unix)]
         Some(Commands::Daemon { .. }) => "daemon".to_string(),
         Some(Commands::Import(..)) => "import".to_string(),
+        Some(Commands::Meta(..)) => "meta".to_string(),
+        Some(Commands::Profile(..)) => "profile".to_string(),
         Some(Commands::Analytics(..)) => "analytics".to_string(),
         None => "(default)".to_string(),
     }
@@ -19739,10 +24002,14 @@ fn is_robot_mode(command: &Commands, cli: &Cli) -> bool {
         Commands::Upgrade { json, .. } => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
+        Commands::SelfUpdate { json, .. } => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
         Commands::ExportHtml { json, .. } => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
         Commands::Stats { json, .. }
+        | Commands::Scan { json, .. }
         | Commands::Diag { json, .. }
         | Commands::Storage { json, .. }
         | Commands::Dedup { json, .. }
@@ -19756,7 +24023,22 @@ fn is_robot_mode(command: &Commands, cli: &Cli) -> bool {
         | Commands::Capabilities { json }
         | Commands::Introspect { json }
         | Commands::Context { json, .. }
-        | Commands::Expand { json, .. } => {
+        | Commands::Distill { json, .. }
+        | Commands::Expand { json, .. }
+        | Commands::GetContext { json, .. }
+        | Commands::Show { json, .. }
+        | Commands::Audit { json, .. }
+        | Commands::Verify { json, .. }
+        | Commands::Agents { json, .. }
+        | Commands::Delta { json, .. }
+        | Commands::Ngrams { json, .. }
+        | Commands::Corrections { json, .. }
+        | Commands::Focus { json, .. }
+        | Commands::Lineage { json, .. }
+        | Commands::Compare { json, .. }
+        | Commands::MarkRead { json, .. }
+        | Commands::Recent { json, .. }
+        | Commands::Runs { json, .. } => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
         Commands::Doctor { json, .. } => resolve_subcommand_structured_format(cli, *json).is_some(),
@@ -19766,7 +24048,48 @@ fn is_robot_mode(command: &Commands, cli: &Cli) -> bool {
         Commands::Mirror(MirrorCommand::Prune { json, .. }) => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
+        Commands::Bench(BenchCommand::Ranking { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
+        Commands::Debug(DebugCommand::Reparse { json, .. })
+        | Commands::Debug(DebugCommand::Parse { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
+        Commands::Secrets(
+            SecretsCommand::Set { json, .. }
+            | SecretsCommand::Get { json, .. }
+            | SecretsCommand::List { json }
+            | SecretsCommand::Delete { json, .. },
+        ) => resolve_subcommand_structured_format(cli, *json).is_some(),
+        Commands::Trash(
+            TrashCommand::List { json, .. }
+            | TrashCommand::Restore { json, .. }
+            | TrashCommand::Empty { json, .. },
+        )
+        | Commands::Undo { json, .. } => resolve_subcommand_structured_format(cli, *json).is_some(),
+        Commands::Db(DbCommand::Slice { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
+        Commands::Note(
+            NoteCommand::Add { json, .. }
+            | NoteCommand::List { json, .. }
+            | NoteCommand::Remove { json, .. },
+        ) => resolve_subcommand_structured_format(cli, *json).is_some(),
+        Commands::Workspace(
+            WorkspaceCommand::Relink { json, .. } | WorkspaceCommand::Detect { json, .. },
+        ) => resolve_subcommand_structured_format(cli, *json).is_some(),
+        Commands::Tag(TagCommand::Add { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
+        Commands::Schedule(
+            ScheduleCommand::Install { json, .. }
+            | ScheduleCommand::Status { json }
+            | ScheduleCommand::Uninstall { json },
+        ) => resolve_subcommand_structured_format(cli, *json).is_some(),
         Commands::Forget { json, .. } => resolve_subcommand_structured_format(cli, *json).is_some(),
+        Commands::Retitle { json, .. } => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
         Commands::Sources(SourcesCommand::List { json, .. }) => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
@@ -19845,9 +24168,15 @@ fn is_robot_mode(command: &Commands, cli: &Cli) -> bool {
         Commands::Models(ModelsCommand::CheckUpdate { json, .. }) => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
+        Commands::Models(ModelsCommand::Usage { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
         Commands::Fleet(FleetCommand::UpgradeRehearsal { json, .. }) => {
             resolve_subcommand_structured_format(cli, *json).is_some()
         }
+        Commands::Watch(WatchCommand::Status { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
         Commands::Lessons(
             LessonsCommand::List { json, .. }
             | LessonsCommand::Search { json, .. }
@@ -19859,13 +24188,65 @@ fn is_robot_mode(command: &Commands, cli: &Cli) -> bool {
         ) => resolve_subcommand_structured_format(cli, *json).is_some(),
         Commands::Import(cmd) => match cmd {
             ImportCommand::Chatgpt { .. } => cli.robot_format.is_some() || env_robot_mode,
+            ImportCommand::ChatgptExport { json, .. }
+            | ImportCommand::ClaudeExport { json, .. }
+            | ImportCommand::Ndjson { json, .. } => {
+                resolve_subcommand_structured_format(cli, *json).is_some()
+            }
         },
+        Commands::Meta(MetaCommand::Export { json, .. } | MetaCommand::Import { json, .. }) => {
+            resolve_subcommand_structured_format(cli, *json).is_some()
+        }
+        Commands::Profile(
+            ProfileCommand::Export { json, .. }
+            | ProfileCommand::Import { json, .. }
+            | ProfileCommand::List { json }
+            | ProfileCommand::Create { json, .. }
+            | ProfileCommand::Switch { json, .. },
+        ) => resolve_subcommand_structured_format(cli, *json).is_some(),
         Commands::Models(_) => cli.robot_format.is_some() || env_robot_mode,
         Commands::Analytics(cmd) => analytics_requests_structured_output(cmd, cli),
         _ => false,
     }
 }
 
+/// Terminal width for snippet sizing when `--wrap` was not passed, from the
+/// `COLUMNS` variable most shells export. Probing the tty itself (stty) is
+/// deliberately avoided on the search hot path.
+fn detected_terminal_columns() -> Option<usize> {
+    dotenvy::var("COLUMNS")
+        .ok()?
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|cols| *cols > 0)
+}
+
+/// Heuristic: does a snippet look like code? Code layout carries meaning, so
+/// the human printer preserves its line breaks and wraps each line instead of
+/// flattening the whole snippet into one prose line.
+fn snippet_looks_like_code(snippet: &str) -> bool {
+    if snippet.contains("```") {
+        return true;
+    }
+    let mut lines = 0usize;
+    let mut code_lines = 0usize;
+    for line in snippet.lines() {
+        let trimmed = line.trim_end();
+        if trimmed.trim_start().is_empty() {
+            continue;
+        }
+        lines += 1;
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        let punctuated = trimmed.ends_with('{') || trimmed.ends_with('}') || trimmed.ends_with(';');
+        if indented || punctuated {
+            code_lines += 1;
+        }
+    }
+    // Majority vote: prose with one trailing semicolon stays on one line.
+    lines > 1 && code_lines * 2 >= lines
+}
+
 fn apply_wrap(line: &str, wrap: WrapConfig) -> String {
     let width = wrap.effective_width();
     if line.trim().is_empty() || width.is_none() {
@@ -19893,6 +24274,32 @@ fn apply_wrap(line: &str, wrap: WrapConfig) -> String {
     out
 }
 
+#[cfg(test)]
+mod snippet_render_tests {
+    use super::snippet_looks_like_code;
+
+    #[test]
+    fn fenced_blocks_and_braced_lines_count_as_code() {
+        assert!(snippet_looks_like_code("run it:\n```\ncargo test\n```"));
+        assert!(snippet_looks_like_code(
+            "fn main() {\n    println!(\"hi\");\n}"
+        ));
+    }
+
+    #[test]
+    fn prose_stays_prose() {
+        assert!(!snippet_looks_like_code(
+            "The indexer walks every source directory and parses what it finds."
+        ));
+        // A single trailing semicolon in one-line prose is not code.
+        assert!(!snippet_looks_like_code("and then we ran the migration;"));
+        // Mostly-prose multi-line snippets stay flattened too.
+        assert!(!snippet_looks_like_code(
+            "First paragraph about results.\nSecond paragraph about caveats.\nThird, a conclusion."
+        ));
+    }
+}
+
 fn lowercase_with_map(text: &str) -> (String, Vec<usize>, Vec<(usize, usize)>) {
     let mut lower = String::with_capacity(text.len());
     let mut lower_starts = Vec::new();
@@ -20240,10 +24647,12 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  cass models remove [--model NAME]  Remove an installed semantic model from disk.".to_string(),
             "  cass models verify [--json]      Per-file SHA-256 verification of the installed model.".to_string(),
             "  cass models check-update [--json]  Compare installed revision against the pinned registry revision.".to_string(),
-            "  cass models backfill             Re-embed conversations against a newly acquired model.".to_string(),
+            "  cass models backfill [--refresh-stale]  Re-embed conversations against a newly acquired model; --refresh-stale drops vectors from older model revisions first.".to_string(),
             "  cass expand <path> --line N [-C CONTEXT] [--json]  Show messages around a specific line in a session.".to_string(),
             "  cass resume <path> [--shell]     Resolve a session path into its native-harness resume command.".to_string(),
             "  cass timeline [--since DATE] [--until DATE] [--json]  Activity timeline over a time range.".to_string(),
+            "  cass sample [--per-agent N] [--format md|text|json] [--seed N]  Random sample of recent conversations per agent for parse-quality spot checks.".to_string(),
+            "  cass scan <path> [--query Q] [--json]     Parse an ad-hoc directory of session files into an ephemeral in-memory index and search it (persistent DB untouched).".to_string(),
             "  cass mirror prune [--older-than 90d] [--max-size 100GB] [--keep-tag important] [--apply] [--json]  Plan or apply raw-mirror retention with an audit log.".to_string(),
             "  cass context <path> [--json]     Find related sessions for a given source path.".to_string(),
             "  cass export <path> [--format markdown] [--output FILE]  Export a conversation to markdown / other formats.".to_string(),
@@ -20255,6 +24664,7 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  cass import [...]                Import conversation data from external sources.".to_string(),
             "  cass daemon [...]                Run the semantic model daemon (Unix only).".to_string(),
             "  cass completions <shell>         Emit shell completion script for bash | zsh | fish | powershell.".to_string(),
+            "  cass complete-workspaces [PREFIX] [--limit N]  Indexed workspace paths, most recently active first, for shell completion of workspace: values.".to_string(),
             "  cass man                         Emit man page (roff) for the cass binary.".to_string(),
         ],
         RobotTopic::Env => vec![
@@ -20262,8 +24672,10 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  CODING_AGENT_SEARCH_NO_UPDATE_PROMPT=1   skip update prompt".to_string(),
             "  TUI_HEADLESS=1                           skip update prompt".to_string(),
             "  CASS_DATA_DIR                            override data dir".to_string(),
-            "  CASS_DB_PATH                             override db path".to_string(),
-            "  CASS_OUTPUT_FORMAT=json|jsonl|compact|sessions|toon  default structured output".to_string(),
+            "  CASS_DB                                  override db path (CASS_DB_PATH accepted as alias)".to_string(),
+            "  CASS_CONFIG_DIR                          override config dir holding cass.toml/sources.toml".to_string(),
+            "  CASS_PROFILE                             named profile to scope data/config to".to_string(),
+            "  CASS_OUTPUT_FORMAT=json|jsonl|compact|sessions|toon|msgpack  default structured output".to_string(),
             "  CASS_SEARCH_TIMEOUT_MS=<N>               default `cass search`/`pack` timeout in ms (--timeout overrides; 0=none)".to_string(),
             "  CASS_SEARCH_LIMIT=<N>                    default search/pack limit (--limit overrides; 0=no limit)".to_string(),
             "  CASS_SEARCH_MODE=lexical|semantic|hybrid default search/pack mode (--mode overrides)".to_string(),
@@ -20322,7 +24734,7 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
         RobotTopic::Guide => vec![
             "guide:".to_string(),
             "  Robot-mode handbook: docs/ROBOT_MODE.md (automation quickstart)".to_string(),
-            "  Output: --robot/--json; formats via --robot-format json|jsonl|compact|toon (--format/--output aliases accepted)".to_string(),
+            "  Output: --robot/--json; formats via --robot-format json|jsonl|compact|toon|msgpack (--format/--output aliases accepted)".to_string(),
             "  Logging: INFO auto-suppressed in robot mode; add -v to re-enable".to_string(),
             "  Search contract: SQLite is source of truth; lexical is the required self-healing fast path; semantic is opportunistic enrichment.".to_string(),
             "  Pack contract: `cass pack \"query\" --robot` returns extractive, cited handoff evidence selected from search results; it does not call an external model or mutate source logs.".to_string(),
@@ -20382,6 +24794,8 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  cass search \"your query\" --robot".to_string(),
             "  # Default is hybrid-preferred; add --robot-meta to see realized mode and lexical fallback reasons.".to_string(),
             "  cass search \"your query\" --robot --robot-meta".to_string(),
+            "# Export hit rows to a file for spreadsheet analysis".to_string(),
+            "  cass search \"auth\" --out results.csv  # .json also works; add --out-content for full message bodies".to_string(),
             "# Deterministic handoff pack for another agent".to_string(),
             "  cass pack \"why did checkout fail\" --robot --max-tokens 12000 --limit 40".to_string(),
             "  cass search \"checkout\" --robot-format sessions | cass pack \"checkout failure\" --robot --sessions-from -".to_string(),
@@ -20841,6 +25255,10 @@ pub struct SemanticSearchOptions {
 }
 
 impl TimeFilter {
+    /// Build the filter from the convenience flags and explicit
+    /// `--since`/`--until` strings. An explicit string that fails to parse
+    /// is a usage error rather than a silently ignored filter; `utc`
+    /// anchors date-only values at UTC midnight instead of local midnight.
     pub fn new(
         days: Option<u32>,
         today: bool,
@@ -20848,7 +25266,8 @@ impl TimeFilter {
         week: bool,
         since_str: Option<&str>,
         until_str: Option<&str>,
-    ) -> Self {
+        utc: bool,
+    ) -> CliResult<Self> {
         use chrono::{Datelike, Duration, Local, TimeZone};
 
         let now = Local::now();
@@ -20875,18 +25294,118 @@ impl TimeFilter {
             (None, None)
         };
 
-        // Explicit --since/--until override convenience flags when they parse successfully
-        let since = since_str.and_then(parse_datetime_str).or(since);
-        let until = until_str.and_then(parse_datetime_str).or(until);
+        // Explicit --since/--until override the convenience flags.
+        let since = match since_str {
+            Some(raw) => Some(
+                parse_datetime_str_tz(raw, utc)
+                    .ok_or_else(|| unparseable_time_error("--since", raw))?,
+            ),
+            None => since,
+        };
+        let until = match until_str {
+            Some(raw) => Some(
+                parse_datetime_str_tz(raw, utc)
+                    .ok_or_else(|| unparseable_time_error("--until", raw))?,
+            ),
+            None => until,
+        };
+
+        Ok(TimeFilter { since, until })
+    }
+
+    /// Apply an `--as-of` time-travel cutoff: restrict results to messages
+    /// created at or before the given instant. Tightens (never widens) an
+    /// existing `until` bound so `--until` and `--as-of` compose safely.
+    /// Like `--since`/`--until`, an unparseable value is a usage error —
+    /// silently searching past the cutoff would defeat the point of a
+    /// "what did I know at time T" query — and `utc` anchors date-only
+    /// values at UTC midnight so all three flags agree on the timezone.
+    pub fn with_as_of(mut self, as_of_str: Option<&str>, utc: bool) -> CliResult<Self> {
+        if let Some(raw) = as_of_str {
+            let as_of = parse_datetime_str_tz(raw, utc)
+                .ok_or_else(|| unparseable_time_error("--as-of", raw))?;
+            self.until = Some(match self.until {
+                Some(existing) => existing.min(as_of),
+                None => as_of,
+            });
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod as_of_cutoff_tests {
+    use super::TimeFilter;
+
+    #[test]
+    fn as_of_sets_until_when_absent() {
+        let filter = TimeFilter::default()
+            .with_as_of(Some("2024-01-15T12:00:00"), false)
+            .expect("valid as-of should parse");
+        assert!(filter.until.is_some());
+        assert!(filter.since.is_none());
+    }
 
-        TimeFilter { since, until }
+    #[test]
+    fn as_of_tightens_but_never_widens_until() {
+        let loose = TimeFilter {
+            since: None,
+            until: Some(i64::MAX),
+        }
+        .with_as_of(Some("2024-01-15T12:00:00"), false)
+        .expect("valid as-of should parse");
+        assert!(
+            loose.until.unwrap() < i64::MAX,
+            "as-of should tighten until"
+        );
+
+        let tight = TimeFilter {
+            since: None,
+            until: Some(0),
+        }
+        .with_as_of(Some("2024-01-15T12:00:00"), false)
+        .expect("valid as-of should parse");
+        assert_eq!(
+            tight.until,
+            Some(0),
+            "as-of must not widen an earlier until"
+        );
+    }
+
+    #[test]
+    fn unparseable_as_of_is_a_usage_error() {
+        let err = TimeFilter::default()
+            .with_as_of(Some("not-a-date"), false)
+            .expect_err("garbage as-of must not silently search the whole corpus");
+        assert!(err.message.contains("--as-of"));
+    }
+
+    #[test]
+    fn as_of_respects_utc_anchor_for_date_only_values() {
+        let local = TimeFilter::default()
+            .with_as_of(Some("2024-01-15"), false)
+            .expect("valid as-of should parse");
+        let utc = TimeFilter::default()
+            .with_as_of(Some("2024-01-15"), true)
+            .expect("valid as-of should parse");
+        // Both anchor at midnight of the same date; they only differ by the
+        // local UTC offset (zero when the host runs in UTC).
+        let offset_ms = (local.until.unwrap() - utc.until.unwrap()).abs();
+        assert!(offset_ms <= 24 * 60 * 60 * 1000);
     }
 }
 
 fn parse_datetime_str(s: &str) -> Option<i64> {
-    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+    parse_datetime_str_tz(s, false)
+}
 
-    fn local_from_naive(dt: NaiveDateTime) -> i64 {
+fn parse_datetime_str_tz(s: &str, utc: bool) -> Option<i64> {
+    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+    fn from_naive(dt: NaiveDateTime, utc: bool) -> i64 {
+        if utc {
+            return Utc.from_utc_datetime(&dt).timestamp_millis();
+        }
         match Local.from_local_datetime(&dt) {
             chrono::LocalResult::Single(local) => local.timestamp_millis(),
             chrono::LocalResult::Ambiguous(local, _) => local.timestamp_millis(),
@@ -20894,22 +25413,34 @@ fn parse_datetime_str(s: &str) -> Option<i64> {
         }
     }
 
-    fn local_midnight_ts(date: NaiveDate) -> Option<i64> {
+    fn midnight_ts(date: NaiveDate, utc: bool) -> Option<i64> {
         let dt = date.and_hms_opt(0, 0, 0)?;
-        Some(local_from_naive(dt))
+        Some(from_naive(dt, utc))
     }
 
     // Try full datetime first: YYYY-MM-DDTHH:MM:SS
     if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
-        return Some(local_from_naive(dt));
+        return Some(from_naive(dt, utc));
     }
 
     // Try date only: YYYY-MM-DD
     if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-        return local_midnight_ts(date);
+        return midnight_ts(date, utc);
     }
 
-    crate::ui::time_parser::parse_time_input(s)
+    crate::ui::time_parser::parse_time_input_tz(s, utc)
+}
+
+/// Usage error for a `--since`/`--until`-style value no parser recognized,
+/// naming the accepted forms so the user doesn't have to guess the grammar.
+fn unparseable_time_error(flag: &str, raw: &str) -> CliError {
+    CliError::usage(
+        format!("could not parse {flag} value `{raw}` as a date or time"),
+        Some(format!(
+            "Accepted forms: {}. Add --utc to anchor date-only values at UTC midnight.",
+            crate::ui::time_parser::SUPPORTED_FORMS
+        )),
+    )
 }
 
 /// Compute aggregations from search hits
@@ -22338,6 +26869,246 @@ fn search_mode_from_canonical_str(s: &str) -> Option<crate::search::query::Searc
     }
 }
 
+/// File format for `cass search --out`, chosen by the output extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchExportFormat {
+    Csv,
+    Json,
+}
+
+/// Map a `--out` path to its export format. Resolved before the search runs
+/// so a typo'd extension fails fast instead of after a multi-second query.
+fn search_export_format(path: &Path) -> CliResult<SearchExportFormat> {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("csv") => Ok(SearchExportFormat::Csv),
+        Some("json") => Ok(SearchExportFormat::Json),
+        _ => Err(CliError::usage(
+            format!("cannot infer an export format from {}", path.display()),
+            Some("Use a .csv or .json extension for --out.".to_string()),
+        )),
+    }
+}
+
+/// Quote one CSV field per RFC 4180: a field containing a comma, double
+/// quote, or line break is wrapped in double quotes with embedded quotes
+/// doubled; anything else passes through unchanged.
+fn csv_escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Grep-style `path:line` address for an exported hit — the 1-indexed source
+/// line where the matched message starts, so spreadsheet rows stay clickable
+/// in editors. A hit with no line number exports the bare session path.
+fn search_hit_permalink(hit: &crate::search::query::SearchHit) -> String {
+    match hit.line_number {
+        Some(line) => format!("{}:{line}", hit.source_path),
+        None => hit.source_path.clone(),
+    }
+}
+
+/// Column order for `cass search --out` rows; `content` is appended only
+/// when `--out-content` asks for full message bodies.
+const SEARCH_EXPORT_COLUMNS: [&str; 7] = [
+    "score",
+    "agent",
+    "workspace",
+    "timestamp",
+    "title",
+    "snippet",
+    "permalink",
+];
+
+/// Render exported hit rows as CSV with a header row.
+fn render_search_export_csv(
+    hits: &[crate::search::query::SearchHit],
+    include_content: bool,
+) -> String {
+    let mut body = SEARCH_EXPORT_COLUMNS.join(",");
+    if include_content {
+        body.push_str(",content");
+    }
+    body.push('\n');
+    for hit in hits {
+        let timestamp = hit
+            .created_at
+            .and_then(format_timestamp_millis_rfc3339)
+            .unwrap_or_default();
+        let mut row = vec![
+            format!("{:.4}", hit.score),
+            csv_escape_field(&hit.agent),
+            csv_escape_field(&hit.workspace),
+            timestamp,
+            csv_escape_field(&hit.title),
+            csv_escape_field(&hit.snippet),
+            csv_escape_field(&search_hit_permalink(hit)),
+        ];
+        if include_content {
+            row.push(csv_escape_field(&hit.content));
+        }
+        body.push_str(&row.join(","));
+        body.push('\n');
+    }
+    body
+}
+
+/// Render exported hit rows as a JSON array with the same fields the CSV
+/// columns carry, so both formats load identically into analysis tools.
+fn render_search_export_json(
+    hits: &[crate::search::query::SearchHit],
+    include_content: bool,
+) -> String {
+    let rows: Vec<serde_json::Value> = hits
+        .iter()
+        .map(|hit| {
+            let mut row = serde_json::json!({
+                "score": safe_robot_score_value(hit.score),
+                "agent": hit.agent,
+                "workspace": hit.workspace,
+                "timestamp": hit.created_at.and_then(format_timestamp_millis_rfc3339),
+                "title": hit.title,
+                "snippet": hit.snippet,
+                "permalink": search_hit_permalink(hit),
+            });
+            if include_content && let Some(obj) = row.as_object_mut() {
+                obj.insert("content".to_string(), serde_json::json!(hit.content));
+            }
+            row
+        })
+        .collect();
+    let mut body = serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string());
+    body.push('\n');
+    body
+}
+
+/// Write the displayed hit rows to the `--out` file in the resolved format.
+fn write_search_export(
+    path: &Path,
+    format: SearchExportFormat,
+    hits: &[crate::search::query::SearchHit],
+    include_content: bool,
+) -> CliResult<()> {
+    let body = match format {
+        SearchExportFormat::Csv => render_search_export_csv(hits, include_content),
+        SearchExportFormat::Json => render_search_export_json(hits, include_content),
+    };
+    std::fs::write(path, body).map_err(|e| CliError {
+        code: 9,
+        kind: CliErrorKind::FileWrite.kind_str(),
+        message: format!("failed to write export {}: {e}", path.display()),
+        hint: Some("Check that the parent directory exists and is writable.".to_string()),
+        retryable: false,
+    })
+}
+
+#[cfg(test)]
+mod search_export_tests {
+    use super::{
+        SearchExportFormat, csv_escape_field, render_search_export_csv, render_search_export_json,
+        search_export_format, search_hit_permalink,
+    };
+    use crate::search::query::{MatchType, SearchHit};
+    use std::path::Path;
+
+    fn export_hit() -> SearchHit {
+        SearchHit {
+            title: "Fix \"auth\" bug".to_string(),
+            snippet: "line one\nline two, with comma".to_string(),
+            content: "full message body".to_string(),
+            content_hash: 0,
+            conversation_id: Some(1),
+            score: 1.5,
+            source_path: "/tmp/session.jsonl".to_string(),
+            agent: "codex".to_string(),
+            workspace: "/tmp/project".to_string(),
+            workspace_original: None,
+            created_at: Some(1_733_000_000_000),
+            line_number: Some(42),
+            match_type: MatchType::Exact,
+            source_id: "local".to_string(),
+            origin_kind: "local".to_string(),
+            origin_host: None,
+        }
+    }
+
+    #[test]
+    fn export_format_follows_the_extension() {
+        assert_eq!(
+            search_export_format(Path::new("results.csv")).unwrap(),
+            SearchExportFormat::Csv
+        );
+        assert_eq!(
+            search_export_format(Path::new("results.JSON")).unwrap(),
+            SearchExportFormat::Json
+        );
+        let err = search_export_format(Path::new("results.txt")).unwrap_err();
+        assert_eq!(err.code, 2);
+    }
+
+    #[test]
+    fn csv_escaping_quotes_commas_newlines_and_doubles_quotes() {
+        assert_eq!(csv_escape_field("plain"), "plain");
+        assert_eq!(csv_escape_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape_field("a\nb"), "\"a\nb\"");
+        assert_eq!(csv_escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn permalink_is_path_colon_line_or_bare_path() {
+        let mut hit = export_hit();
+        assert_eq!(search_hit_permalink(&hit), "/tmp/session.jsonl:42");
+        hit.line_number = None;
+        assert_eq!(search_hit_permalink(&hit), "/tmp/session.jsonl");
+    }
+
+    #[test]
+    fn csv_rows_are_escaped_and_content_is_opt_in() {
+        let hits = vec![export_hit()];
+        let csv = render_search_export_csv(&hits, false);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("score,agent,workspace,timestamp,title,snippet,permalink")
+        );
+        assert!(csv.contains("\"Fix \"\"auth\"\" bug\""));
+        assert!(!csv.contains("full message body"));
+
+        let with_content = render_search_export_csv(&hits, true);
+        assert!(
+            with_content
+                .starts_with("score,agent,workspace,timestamp,title,snippet,permalink,content\n")
+        );
+        assert!(with_content.contains("full message body"));
+    }
+
+    #[test]
+    fn json_rows_mirror_the_csv_columns() {
+        let hits = vec![export_hit()];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&render_search_export_json(&hits, true)).expect("valid json");
+        let row = &parsed[0];
+        assert_eq!(row["agent"], "codex");
+        assert_eq!(row["permalink"], "/tmp/session.jsonl:42");
+        assert_eq!(row["content"], "full message body");
+        assert!(
+            row["timestamp"]
+                .as_str()
+                .is_some_and(|t| t.starts_with("2024-"))
+        );
+
+        let without = render_search_export_json(&hits, false);
+        assert!(!without.contains("full message body"));
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_cli_search(
     query: &str,
@@ -22367,8 +27138,15 @@ fn run_cli_search(
     highlight: bool,
     source: Option<String>,
     sessions_from: Option<String>,
+    group_by: Option<crate::search::result_grouping::GroupBy>,
+    sort: Option<crate::search::result_grouping::ResultSort>,
     mode: Option<crate::search::query::SearchMode>,
     semantic_opts: SemanticSearchOptions,
+    no_user_ranking: bool,
+    include_boilerplate: bool,
+    exclude_pasted: bool,
+    out: Option<&Path>,
+    out_content: bool,
 ) -> CliResult<()> {
     use crate::search::model_manager::{
         load_hash_semantic_context, load_semantic_context, load_semantic_context_for_embedder,
@@ -22389,6 +27167,19 @@ fn run_cli_search(
     let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
     let db_exists = db_path.exists();
 
+    // Resolve the --out export format up front so a typo'd extension (or a
+    // combination with --group-by, which collapses the flat hit rows the
+    // export writes) fails before any search work happens.
+    let export_format = out.map(search_export_format).transpose()?;
+    if out.is_some() && group_by.is_some() {
+        return Err(CliError::usage(
+            "--out exports flat hit rows, which --group-by collapses",
+            Some(
+                "Drop --group-by when exporting, or run the grouped query separately.".to_string(),
+            ),
+        ));
+    }
+
     let mut filters = SearchFilters::default();
     if !agents.is_empty() {
         filters.agents = HashSet::from_iter(agents.iter().cloned());
@@ -22398,6 +27189,8 @@ fn run_cli_search(
     }
     filters.created_from = time_filter.since;
     filters.created_to = time_filter.until;
+    filters.include_boilerplate = include_boilerplate;
+    filters.exclude_pasted = exclude_pasted;
 
     // Apply source filter (P3.1)
     if let Some(ref source_str) = source {
@@ -22456,8 +27249,33 @@ fn run_cli_search(
         } else {
             None
         });
+
+    // Optional hard cap on machine-requested page sizes: an agent loop that
+    // asks for limit 0 ("everything") or an absurd page gets a structured
+    // limit-exceeded error instead of streaming the corpus into its context
+    // window. Interactive use is never capped.
+    if effective_robot.is_some()
+        && let Ok(val) = dotenvy::var("CASS_ROBOT_MAX_LIMIT")
+        && let Ok(cap) = val.trim().parse::<usize>()
+        && cap > 0
+        && (limit_val == 0 || limit_val > cap)
+    {
+        return Err(CliError {
+            code: 2,
+            kind: CliErrorKind::LimitExceeded.kind_str(),
+            message: format!(
+                "requested limit {limit_val} exceeds the configured cap of {cap}"
+            ),
+            hint: Some(format!(
+                "Pass --limit {cap} or lower and paginate with --cursor; \
+                 the cap is set via CASS_ROBOT_MAX_LIMIT."
+            )),
+            retryable: false,
+        });
+    }
+
     let field_mask_visible_limit = token_budget_field_mask_visible_limit(max_tokens, limit_val);
-    let field_mask = resolve_field_mask(
+    let mut field_mask = resolve_field_mask(
         &fields,
         max_content_length,
         max_tokens,
@@ -22466,6 +27284,28 @@ fn run_cli_search(
         display_format,
     );
 
+    // Human output sizes snippets to the terminal: a narrow pane gets a
+    // shorter window, an ultrawide gets more context, and multi-term queries
+    // get room to show every highlighted term. Machine formats keep the fixed
+    // default so agent-facing output stays stable across environments. Bounds
+    // come from `[search].snippet_{min,max}_chars` in cass.toml; config errors
+    // were already surfaced by `resolve_search_defaults` upstream.
+    if effective_robot.is_none() {
+        let defaults = crate::search_defaults::load_search_defaults().unwrap_or_default();
+        let (snippet_min, snippet_max) = crate::search_defaults::resolve_snippet_bounds(
+            defaults.snippet_min_chars,
+            defaults.snippet_max_chars,
+        );
+        let columns = wrap.effective_width().or_else(detected_terminal_columns);
+        field_mask =
+            field_mask.with_snippet_chars(Some(crate::search::query::adaptive_snippet_chars(
+                columns,
+                query.split_whitespace().count(),
+                snippet_min,
+                snippet_max,
+            )));
+    }
+
     // Parse aggregate fields if provided
     let agg_fields = aggregate
         .as_ref()
@@ -23131,6 +27971,125 @@ fn run_cli_search(
         0
     };
 
+    // Apply the requested server-side sort before pagination so
+    // --offset/--limit page through the sorted order instead of re-sorting
+    // a single page client side.
+    // Workspace recency boost (opt-in, see `git_activity`): lift hits from
+    // workspaces with recent local git commits. Applied before the user
+    // ranking script and explicit --sort/--group-by, so all of those can
+    // still override it.
+    let result = if crate::git_activity::boost_enabled() {
+        let mut result = result;
+        crate::git_activity::apply_recency_boost(
+            &mut result.hits,
+            crate::git_activity::ACTIVE_WINDOW_DAYS,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        result
+    } else {
+        result
+    };
+
+    // User ranking hook: when a script is configured, re-score and re-sort
+    // the hits with it before explicit --sort/--group-by run, so those still
+    // win. A broken script is a usage error (fix it or pass
+    // --no-user-ranking), not a silently unranked result set.
+    let result = if no_user_ranking {
+        result
+    } else {
+        match crate::ranking_script::load_user_ranking() {
+            Ok(Some(program)) => {
+                let mut result = result;
+                crate::ranking_script::apply_user_ranking(&mut result.hits, &program);
+                result
+            }
+            Ok(None) => result,
+            Err(e) => {
+                return Err(CliError::usage(
+                    e,
+                    Some(
+                        "Fix the script, unset [search] ranking_script / CASS_RANKING_SCRIPT, \
+                         or rerun with --no-user-ranking."
+                            .to_string(),
+                    ),
+                ));
+            }
+        }
+    };
+
+    let result = if let Some(sort) = sort {
+        let mut result = result;
+        crate::search::result_grouping::sort_hits(&mut result.hits, sort);
+        result
+    } else {
+        result
+    };
+
+    // Audit trail: robot-mode queries are another tool reading the corpus, so
+    // record them before any of the output paths below return (best-effort,
+    // never fails the search).
+    crate::audit::record_query(
+        &data_dir,
+        "search",
+        Some(query),
+        Some(result.hits.len() as i64),
+        effective_robot.is_some(),
+    );
+
+    // --group-by conversation: collapse message hits into per-conversation
+    // rows (best score + hit count) in the aggregation layer and emit the
+    // grouped shape directly; flat-hit truncation/pagination metadata does
+    // not apply to groups.
+    if let Some(crate::search::result_grouping::GroupBy::Conversation) = group_by {
+        let mut groups =
+            crate::search::result_grouping::group_hits_by_conversation(&result.hits);
+        if let Some(sort) = sort {
+            crate::search::result_grouping::sort_groups(&mut groups, sort);
+        }
+        let total_groups = groups.len();
+        let effective_limit = if limit_val == 0 { usize::MAX } else { limit_val };
+        let groups: Vec<_> = groups
+            .into_iter()
+            .skip(offset_val)
+            .take(effective_limit)
+            .collect();
+
+        if let Some(format) = effective_robot {
+            let format = if matches!(format, RobotFormat::Sessions) {
+                RobotFormat::Compact
+            } else {
+                format
+            };
+            let payload = serde_json::json!({
+                "schema_version": 1,
+                "query": query,
+                "group_by": "conversation",
+                "sort": sort.unwrap_or_default(),
+                "total_groups": total_groups,
+                "offset": offset_val,
+                "groups": groups,
+            });
+            return output_structured_value(payload, format);
+        }
+
+        if groups.is_empty() {
+            eprintln!("No results found.");
+            return Ok(());
+        }
+        for group in &groups {
+            println!("----------------------------------------------------------------");
+            println!(
+                "Score: {:.2} | Hits: {} | Agent: {} | WS: {}",
+                group.best_score, group.hit_count, group.agent, group.workspace
+            );
+            println!("Path: {}", group.source_path);
+            let snippet = group.top_snippet.replace('\n', " ");
+            println!("Snippet: {}", apply_wrap(&snippet, wrap));
+        }
+        println!("----------------------------------------------------------------");
+        return Ok(());
+    }
+
     // Check if search exceeded timeout - return partial results with timeout indicator
     let timed_out = timeout_duration.is_some_and(|t| start_time.elapsed() > t);
 
@@ -23138,7 +28097,8 @@ fn run_cli_search(
     let explanation = if explain {
         Some(
             QueryExplanation::analyze(query, &filters)
-                .with_wildcard_fallback(result.wildcard_fallback),
+                .with_wildcard_fallback(result.wildcard_fallback)
+                .with_shard_timings(client.last_shard_timings()),
         )
     } else {
         None
@@ -23211,6 +28171,18 @@ fn run_cli_search(
             )
         };
 
+    // Write the displayed rows to --out before rendering: the export either
+    // succeeds or fails independent of the terminal format chosen, and the
+    // confirmation goes to stderr so stdout stays results-only.
+    if let (Some(out_path), Some(format)) = (out, export_format) {
+        write_search_export(out_path, format, &display_result.hits, out_content)?;
+        eprintln!(
+            "Wrote {} hit row(s) to {}",
+            display_result.hits.len(),
+            out_path.display()
+        );
+    }
+
     let elapsed_ms = start_time.elapsed().as_millis() as u64;
 
     // Derive per-field budgets, preferring snippet > content > title
@@ -23384,6 +28356,20 @@ fn run_cli_search(
         )?;
     } else if display_result.hits.is_empty() {
         eprintln!("No results found.");
+        if !display_result.suggestions.is_empty() {
+            eprintln!("Try instead:");
+            for (idx, suggestion) in display_result.suggestions.iter().enumerate() {
+                match suggestion.suggested_query.as_deref() {
+                    Some(suggested) => eprintln!(
+                        "  {}. {}  (cass search \"{}\")",
+                        idx + 1,
+                        suggestion.message,
+                        suggested
+                    ),
+                    None => eprintln!("  {}. {}", idx + 1, suggestion.message),
+                }
+            }
+        }
     } else if let Some(display) = display_format {
         // Human-readable display formats
         output_display_results(&display_result.hits, display, wrap, query, highlight)?;
@@ -23396,13 +28382,30 @@ fn run_cli_search(
                 hit.score, hit.agent, hit.workspace
             );
             println!("Path: {}", hit.source_path);
-            let snippet = hit.snippet.replace('\n', " ");
-            let snippet = if highlight {
-                highlight_matches(&snippet, query, "**", "**")
+            if snippet_looks_like_code(&hit.snippet) {
+                // Code-looking content keeps its line structure (wrapped, not
+                // flattened); continuation lines align under the label.
+                let rendered = if highlight {
+                    highlight_matches(&hit.snippet, query, "**", "**")
+                } else {
+                    hit.snippet.clone()
+                };
+                let mut snippet_lines = rendered.lines();
+                if let Some(first) = snippet_lines.next() {
+                    println!("Snippet: {}", apply_wrap(first, wrap));
+                }
+                for line in snippet_lines {
+                    println!("         {}", apply_wrap(line, wrap));
+                }
             } else {
-                snippet
-            };
-            println!("Snippet: {}", apply_wrap(&snippet, wrap));
+                let snippet = hit.snippet.replace('\n', " ");
+                let snippet = if highlight {
+                    highlight_matches(&snippet, query, "**", "**")
+                } else {
+                    snippet
+                };
+                println!("Snippet: {}", apply_wrap(&snippet, wrap));
+            }
         }
         println!("----------------------------------------------------------------");
     }
@@ -23806,6 +28809,16 @@ fn resolve_pack_render_format(
         Some(RobotFormat::Compact) => Ok(PackRenderFormat::CompactJson),
         Some(RobotFormat::Jsonl) => Ok(PackRenderFormat::Jsonl),
         Some(RobotFormat::Toon) => Ok(PackRenderFormat::Toon),
+        Some(RobotFormat::Msgpack) => Err(CliError {
+            code: 2,
+            kind: CliErrorKind::PackUnsupportedFormat.kind_str(),
+            message: "cass pack does not support --robot-format msgpack".to_string(),
+            hint: Some(
+                "Use --robot-format compact for pack output; msgpack is available on `cass search`."
+                    .to_string(),
+            ),
+            retryable: false,
+        }),
         Some(RobotFormat::Sessions) => Err(CliError {
             code: 2,
             kind: CliErrorKind::PackUnsupportedFormat.kind_str(),
@@ -25035,6 +30048,7 @@ fn robot_format_from_env() -> Option<RobotFormat> {
             "compact" => Some(RobotFormat::Compact),
             "sessions" => Some(RobotFormat::Sessions),
             "toon" => Some(RobotFormat::Toon),
+            "msgpack" => Some(RobotFormat::Msgpack),
             _ => None,
         })
         .or_else(|| {
@@ -25150,6 +30164,36 @@ fn toon_encode_options_from_env() -> toon::EncodeOptions {
     }
 }
 
+/// Write a payload to stdout as raw MessagePack bytes (no trailing newline).
+///
+/// The object shape is identical to the `compact` JSON format, so consumers
+/// negotiate the encoding with `--robot-format msgpack` (or
+/// `CASS_OUTPUT_FORMAT=msgpack`) without changing their field handling. For
+/// large result sets the binary form serializes faster and is substantially
+/// smaller over a pipe than JSON; `benches/export_perf.rs` tracks both.
+fn output_msgpack_value(payload: &serde_json::Value) -> CliResult<()> {
+    use std::io::Write;
+
+    let bytes = rmp_serde::to_vec(payload).map_err(|e| CliError {
+        code: 9,
+        kind: CliErrorKind::EncodeJson.kind_str(),
+        message: format!("failed to encode msgpack: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+    let mut stdout = std::io::stdout().lock();
+    stdout
+        .write_all(&bytes)
+        .and_then(|()| stdout.flush())
+        .map_err(|e| CliError {
+            code: 9,
+            kind: CliErrorKind::EncodeJson.kind_str(),
+            message: format!("failed to write msgpack output: {e}"),
+            hint: None,
+            retryable: false,
+        })
+}
+
 fn output_structured_value(payload: serde_json::Value, format: RobotFormat) -> CliResult<()> {
     match format {
         RobotFormat::Json => {
@@ -25165,6 +30209,9 @@ fn output_structured_value(payload: serde_json::Value, format: RobotFormat) -> C
             let toon_str = toon::encode(payload, Some(toon_encode_options_from_env()));
             print!("{toon_str}");
         }
+        RobotFormat::Msgpack => {
+            output_msgpack_value(&payload)?;
+        }
     }
     Ok(())
 }
@@ -26263,8 +31310,9 @@ fn output_robot_results(
                 retryable: false,
             })?;
         }
-        RobotFormat::Compact => {
-            // Single-line compact JSON
+        RobotFormat::Compact | RobotFormat::Msgpack => {
+            // Single-line compact JSON, or the same payload as MessagePack
+            // bytes when a binary wire format was negotiated.
             let mut payload = serde_json::json!({
                 "query": query,
                 "limit": limit,
@@ -26387,14 +31435,18 @@ fn output_robot_results(
                 }
             }
 
-            let out = serde_json::to_string(&payload).map_err(|e| CliError {
-                code: 9,
-                kind: CliErrorKind::EncodeJson.kind_str(),
-                message: format!("failed to encode json: {e}"),
-                hint: None,
-                retryable: false,
-            })?;
-            println!("{out}");
+            if matches!(format, RobotFormat::Msgpack) {
+                output_msgpack_value(&payload)?;
+            } else {
+                let out = serde_json::to_string(&payload).map_err(|e| CliError {
+                    code: 9,
+                    kind: CliErrorKind::EncodeJson.kind_str(),
+                    message: format!("failed to encode json: {e}"),
+                    hint: None,
+                    retryable: false,
+                })?;
+                println!("{out}");
+            }
         }
         RobotFormat::Toon => {
             // TOON: Token-Optimized Object Notation
@@ -26769,7 +31821,7 @@ fn run_stats(
             Ok((r.get_typed::<i64>(0)?, r.get_typed::<i64>(1)?))
         })
         .map_err(|e| CliError::unknown(format!("query: {e}")))?;
-    let ws_rows: Vec<(String, i64)> = workspace_count_rows
+    let all_ws_rows: Vec<(String, i64)> = workspace_count_rows
         .into_iter()
         .filter_map(|(workspace_id, count)| {
             workspace_lookup
@@ -26777,6 +31829,19 @@ fn run_stats(
                 .cloned()
                 .map(|path| (path, count))
         })
+        .collect();
+    let ws_rows: Vec<(String, i64)> = all_ws_rows.iter().take(10).cloned().collect();
+
+    // Hierarchical rollup for monorepos: nested workspaces grouped under
+    // their repo root (path prefixes plus local git-toplevel detection).
+    // Only interesting when something actually nests.
+    let ws_rollup: Vec<crate::workspace_rollup::WorkspaceRollup> =
+        crate::workspace_rollup::rollup_counts(
+            &all_ws_rows,
+            &crate::workspace_rollup::is_git_toplevel,
+        )
+        .into_iter()
+        .filter(|root| !root.children.is_empty())
         .take(10)
         .collect();
 
@@ -26798,6 +31863,30 @@ fn run_stats(
         .unwrap_or((None, None));
     let raw_mirror_summary = crate::raw_mirror::storage_summary(&data_dir);
 
+    // Session-length / response-latency metrics derived from message
+    // timestamps. Best-effort: legacy databases without usable timestamps
+    // simply report zero sessions.
+    let session_summary = crate::session_metrics::aggregate_session_metrics(&conn)
+        .unwrap_or_default();
+
+    // Topic facets derived at index time (`facet:` query terms, see
+    // `search::facets`). Best-effort: pre-v28 databases have no facets
+    // table and simply omit the section.
+    let facet_rows: Vec<(String, String, i64)> = franken_query_map_collect_retry(
+        &conn,
+        "SELECT kind, value, COUNT(DISTINCT conversation_id) AS convs
+         FROM facets GROUP BY kind, value ORDER BY convs DESC, value LIMIT 15",
+        &[],
+        |r| {
+            Ok((
+                r.get_typed::<String>(0)?,
+                r.get_typed::<String>(1)?,
+                r.get_typed::<i64>(2)?,
+            ))
+        },
+    )
+    .unwrap_or_default();
+
     // Get per-source breakdown if requested (P3.7)
     let source_rows: Vec<(String, i64, i64)> = if by_source {
         let normalized_source_sql =
@@ -26836,11 +31925,14 @@ fn run_stats(
             "messages": message_count,
             "by_agent": agent_rows.iter().map(|(a, c)| serde_json::json!({"agent": a, "count": c})).collect::<Vec<_>>(),
             "top_workspaces": ws_rows.iter().map(|(w, c)| serde_json::json!({"workspace": w, "count": c})).collect::<Vec<_>>(),
+            "workspace_rollup": &ws_rollup,
             "date_range": {
                 "oldest": oldest.and_then(|ts| chrono::DateTime::from_timestamp_millis(ts).map(|d| d.to_rfc3339())),
                 "newest": newest.and_then(|ts| chrono::DateTime::from_timestamp_millis(ts).map(|d| d.to_rfc3339())),
             },
             "raw_mirror": &raw_mirror_summary,
+            "session_metrics": &session_summary,
+            "top_facets": facet_rows.iter().map(|(kind, value, convs)| serde_json::json!({"kind": kind, "value": value, "conversations": convs})).collect::<Vec<_>>(),
             "db_path": db_path.display().to_string(),
         });
 
@@ -26919,6 +32011,36 @@ fn run_stats(
         println!("  Status: not initialized");
     }
     println!();
+    if session_summary.sessions_with_timestamps > 0 {
+        println!("Session Metrics:");
+        println!(
+            "  Sessions with timestamps: {}",
+            session_summary.sessions_with_timestamps
+        );
+        if let Some(avg) = session_summary.avg_duration_ms {
+            println!(
+                "  Avg duration: {}",
+                crate::session_metrics::format_duration_ms(avg)
+            );
+        }
+        if let Some(max) = session_summary.max_duration_ms {
+            println!(
+                "  Longest session: {}",
+                crate::session_metrics::format_duration_ms(max)
+            );
+        }
+        if let Some(rate) = session_summary.avg_messages_per_hour {
+            println!("  Avg messages/hour: {rate:.1}");
+        }
+        if let Some(latency) = session_summary.avg_response_latency_ms {
+            println!(
+                "  Avg assistant response latency: {} ({} samples)",
+                crate::session_metrics::format_duration_ms(latency),
+                session_summary.latency_samples
+            );
+        }
+        println!();
+    }
     println!("By Agent:");
     for (agent, count) in &agent_rows {
         println!("  {agent}: {count}");
@@ -26931,6 +32053,27 @@ fn run_stats(
         }
         println!();
     }
+    if !ws_rollup.is_empty() {
+        println!("Workspace Rollup (monorepo roots):");
+        for root in &ws_rollup {
+            println!("  {}: {} ({} direct)", root.path, root.total, root.direct);
+            for child in &root.children {
+                let sub = child
+                    .path
+                    .strip_prefix(root.path.as_str())
+                    .map_or(child.path.as_str(), |rest| rest.trim_start_matches('/'));
+                println!("    {sub}: {}", child.count);
+            }
+        }
+        println!();
+    }
+    if !facet_rows.is_empty() {
+        println!("Top Facets (filter with facet:value):");
+        for (kind, value, convs) in &facet_rows {
+            println!("  {value} [{kind}]: {convs}");
+        }
+        println!();
+    }
     if let (Some(old), Some(new)) = (oldest, newest)
         && let (Some(old_dt), Some(new_dt)) = (
             chrono::DateTime::from_timestamp_millis(old),
@@ -27534,6 +32677,24 @@ fn run_diag(
     let quarantine_report =
         quarantine.then(|| collect_diag_quarantine_report(&data_dir, &index_path));
 
+    // Resolved config file plus any path overrides in force, so sandboxed /
+    // relocated setups can confirm which environment variable or `[paths]`
+    // entry actually won (see `default_data_dir` / `default_db_path`).
+    let config_file_path = crate::search_defaults::config_path();
+    let path_overrides: Vec<(&str, String)> = [
+        "CASS_DATA_DIR",
+        "CASS_DB",
+        "CASS_DB_PATH",
+        "CASS_CONFIG_DIR",
+    ]
+    .into_iter()
+    .filter_map(|var| {
+        let value = dotenvy::var(var).ok()?;
+        let trimmed = value.trim();
+        (!trimmed.is_empty()).then(|| (var, trimmed.to_string()))
+    })
+    .collect();
+
     let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
         if matches!(fmt, RobotFormat::Sessions) {
             RobotFormat::Compact
@@ -27550,6 +32711,11 @@ fn run_diag(
                 "data_dir": data_dir.display().to_string(),
                 "db_path": db_path.display().to_string(),
                 "index_path": index_path.display().to_string(),
+                "config_path": config_file_path.as_ref().map(|p| p.display().to_string()),
+                "env_overrides": path_overrides
+                    .iter()
+                    .map(|(var, value)| serde_json::json!({ "var": var, "value": value }))
+                    .collect::<Vec<_>>(),
             },
             "database": {
                 "exists": db_exists,
@@ -27585,6 +32751,13 @@ fn run_diag(
     println!("  Data directory: {}", data_dir.display());
     println!("  Database: {}", db_path.display());
     println!("  Tantivy index: {}", index_path.display());
+    match config_file_path.as_ref() {
+        Some(path) => println!("  Config file: {}", path.display()),
+        None => println!("  Config file: (no config directory resolvable)"),
+    }
+    for (var, value) in &path_overrides {
+        println!("  Override {var}={value}");
+    }
     println!();
     println!("Database Status:");
     if db_exists {
@@ -72838,6 +78011,93 @@ fn run_bounded_doctor_archive_db_probe(
     }
 }
 
+/// Per-agent finding from the doctor's message-role distribution scan.
+#[derive(Debug)]
+struct DoctorRoleDistributionFinding {
+    agent_slug: String,
+    flagged: usize,
+    total: usize,
+    samples: Vec<String>,
+}
+
+/// Scan stored messages for conversations whose role distribution looks like
+/// a parse bug (see `indexer::role_count_anomaly`) and aggregate per agent.
+/// This mirrors the per-run check `cass index` performs on freshly parsed
+/// conversations, but runs over everything already in the archive, so a
+/// connector regression that predates the current binary still surfaces.
+/// Read-only; returns `None` when a query fails so the caller can skip the
+/// check instead of failing the doctor run over an optional heuristic.
+fn doctor_role_distribution_findings(
+    conn: &frankensqlite::Connection,
+    max_samples: usize,
+) -> Option<Vec<DoctorRoleDistributionFinding>> {
+    // Single-table GROUP BY (no JOIN) to stay off frankensqlite's
+    // materialization fallback; the user/assistant split is folded in Rust
+    // so the role vocabulary lives in exactly one place
+    // (`indexer::role_is_user` / `role_is_assistant`).
+    let role_rows: Vec<(i64, String, i64)> = franken_query_map_collect_retry(
+        conn,
+        "SELECT conversation_id, role, COUNT(*) FROM messages GROUP BY conversation_id, role",
+        &[],
+        |r| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+    )
+    .ok()?;
+    let mut counts: HashMap<i64, (usize, usize, usize)> = HashMap::new();
+    for (conversation_id, role, count) in role_rows {
+        let count = count.max(0) as usize;
+        let entry = counts.entry(conversation_id).or_default();
+        entry.0 += count;
+        if crate::indexer::role_is_user(&role) {
+            entry.1 += count;
+        } else if crate::indexer::role_is_assistant(&role) {
+            entry.2 += count;
+        }
+    }
+
+    let conv_rows: Vec<(i64, String, String)> = franken_query_map_collect_retry(
+        conn,
+        "SELECT c.id,
+                COALESCE((SELECT a.slug FROM agents a WHERE a.id = c.agent_id), 'unknown'),
+                c.source_path
+         FROM conversations c
+         ORDER BY c.id DESC",
+        &[],
+        |r| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+    )
+    .ok()?;
+
+    let mut by_agent: std::collections::BTreeMap<String, DoctorRoleDistributionFinding> =
+        std::collections::BTreeMap::new();
+    for (id, agent_slug, source_path) in conv_rows {
+        let finding =
+            by_agent
+                .entry(agent_slug.clone())
+                .or_insert_with(|| DoctorRoleDistributionFinding {
+                    agent_slug,
+                    flagged: 0,
+                    total: 0,
+                    samples: Vec::new(),
+                });
+        finding.total += 1;
+        let (total, user_turns, assistant_turns) = counts.get(&id).copied().unwrap_or_default();
+        if let Some(reason) = crate::indexer::role_count_anomaly(total, user_turns, assistant_turns)
+        {
+            finding.flagged += 1;
+            // `ORDER BY c.id DESC` above skews the examples toward recent
+            // conversations, which is where an active parse bug shows up.
+            if finding.samples.len() < max_samples {
+                finding.samples.push(format!("{source_path} ({reason})"));
+            }
+        }
+    }
+    Some(
+        by_agent
+            .into_values()
+            .filter(|finding| finding.flagged > 0)
+            .collect(),
+    )
+}
+
 /// Internal doctor executor reached through the typed `doctor` module.
 /// CRITICAL: This function NEVER deletes user data. It only rebuilds derived data (index, db)
 /// from source session files. This is essential because users may have only one copy of their
@@ -73402,6 +78662,67 @@ pub(crate) fn run_doctor_impl(
         vec!["archive DB open, row counts, and integrity-style checks completed or were skipped by state".to_string()],
     );
 
+    // Content sanity: message role distribution per conversation. A healthy
+    // database can still hold garbage if a connector silently dropped one
+    // side of the dialogue, so flag one-sided conversations as likely parse
+    // bugs with example source paths to inspect. Best-effort: only runs when
+    // the bounded probe above found a readable, non-empty database, and a
+    // failed scan skips the check rather than failing the doctor run.
+    if db_ok && db_messages.is_some_and(|count| count > 0) {
+        match open_franken_cli_read_db_with_hard_timeout(
+            db_path.to_path_buf(),
+            "doctor role distribution",
+            Duration::from_secs(30),
+        ) {
+            Ok(conn) => {
+                if let Some(findings) = doctor_role_distribution_findings(
+                    &conn,
+                    crate::indexer::ROLE_ANOMALY_SAMPLE_LIMIT,
+                ) {
+                    if findings.is_empty() {
+                        add_check!(
+                            "role_distribution",
+                            "pass",
+                            "Message role distribution looks healthy across all agents",
+                            false
+                        );
+                    } else {
+                        let details = findings
+                            .iter()
+                            .map(|finding| {
+                                format!(
+                                    "{}: {} of {} conversation(s) one-sided, e.g. {}",
+                                    finding.agent_slug,
+                                    finding.flagged,
+                                    finding.total,
+                                    finding.samples.join(", ")
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("; ");
+                        add_check!(
+                            "role_distribution",
+                            "warn",
+                            format!(
+                                "Suspicious message role distribution (likely parse bug) - {details}"
+                            ),
+                            false
+                        );
+                    }
+                } else {
+                    tracing::debug!("doctor role distribution scan failed; skipping check");
+                }
+                let _ = close_franken_cli_read_db(conn, &db_path, "doctor role distribution");
+            }
+            Err(err) => {
+                tracing::debug!(
+                    error = %err.message,
+                    "doctor role distribution scan could not open database; skipping check"
+                );
+            }
+        }
+    }
+
     // 4. Check Tantivy index exists and is readable
     let lexical_probe_started = Instant::now();
     if crate::search::tantivy::searchable_index_exists(&index_path) {
@@ -74715,6 +80036,7 @@ pub(crate) fn run_doctor_impl(
                     embedder: "fastembed".to_string(),
                     progress: Some(progress.clone()),
                     watch_interval_secs: 30,
+                    pause_on_battery: false,
                 };
 
                 let rebuild_handle = std::thread::spawn(move || {
@@ -75642,9 +80964,12 @@ struct SessionSummaryRecord {
     origin_host: Option<String>,
     started_at: Option<i64>,
     modified_at: Option<i64>,
+    last_activity_at: Option<i64>,
     size_bytes: Option<u64>,
     message_count: i64,
     human_turns: i64,
+    quality: Option<f64>,
+    uuid: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -75659,6 +80984,15 @@ struct SessionSummaryEntry {
     size_bytes: Option<u64>,
     message_count: i64,
     human_turns: i64,
+    /// Heuristic usefulness score (0-1), absent until the next index run
+    /// computes it (see `search::quality`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<f64>,
+    /// Durable conversation UUID, stable across database rebuilds and
+    /// merges; absent until the next index run assigns one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<String>,
+    unread: bool,
 }
 
 fn normalize_session_filter_path(path: &Path) -> CliResult<PathBuf> {
@@ -75696,6 +81030,9 @@ fn run_sessions(
     workspace: Option<&PathBuf>,
     current: bool,
     limit: Option<usize>,
+    unread_only: bool,
+    min_quality: Option<f64>,
+    sort_quality: bool,
     data_dir_override: &Option<PathBuf>,
     db_override: Option<PathBuf>,
     output_format: Option<RobotFormat>,
@@ -75703,6 +81040,12 @@ fn run_sessions(
     use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
 
     let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let view_times = load_conversation_view_times(&conn);
+    let quality_scores = load_conversation_quality_scores(&conn);
+    let conversation_uuids = load_conversation_uuids(&conn);
+    let read_marker = crate::read_status::load_marker(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+    );
     let target_workspace = match (workspace, current) {
         (Some(path), _) => Some(normalize_session_filter_path(path)?),
         (None, true) => Some(normalize_session_filter_path(
@@ -75732,6 +81075,7 @@ fn run_sessions(
         Option<String>,
         Option<String>,
         Option<i64>,
+        Option<i64>,
         i64,
         i64,
     )> = conn
@@ -75748,6 +81092,7 @@ fn run_sessions(
                     c.origin_host,
                     s.kind,
                     c.started_at,
+                    COALESCE(c.last_message_created_at, c.ended_at, c.started_at) AS last_activity,
                     COUNT(m.id) AS message_count,
                     COALESCE(SUM(CASE WHEN m.role = 'user' THEN 1 ELSE 0 END), 0) AS human_turns
              FROM conversations c
@@ -75755,7 +81100,7 @@ fn run_sessions(
              LEFT JOIN workspaces w ON c.workspace_id = w.id
              LEFT JOIN sources s ON c.source_id = s.id
              LEFT JOIN messages m ON m.conversation_id = c.id
-             GROUP BY c.id, COALESCE(a.slug, 'unknown'), w.path, c.title, c.source_path, COALESCE(c.source_id, 'local'), c.origin_host, s.kind, c.started_at
+             GROUP BY c.id, COALESCE(a.slug, 'unknown'), w.path, c.title, c.source_path, COALESCE(c.source_id, 'local'), c.origin_host, s.kind, c.started_at, COALESCE(c.last_message_created_at, c.ended_at, c.started_at)
              ORDER BY CASE WHEN c.started_at IS NULL THEN 1 ELSE 0 END, c.started_at DESC, c.id DESC",
             params,
             |row: &frankensqlite::Row| {
@@ -75770,6 +81115,7 @@ fn run_sessions(
                     row.get_typed(7)?,
                     row.get_typed(8)?,
                     row.get_typed(9)?,
+                    row.get_typed(10)?,
                 ))
             },
         )
@@ -75793,6 +81139,7 @@ fn run_sessions(
                 origin_host,
                 origin_kind,
                 started_at,
+                last_activity_at,
                 message_count,
                 human_turns,
             )| {
@@ -75813,6 +81160,8 @@ fn run_sessions(
                     .and_then(|m| m.modified().ok())
                     .map(|ts| chrono::DateTime::<Utc>::from(ts).timestamp_millis());
 
+                let quality = quality_scores.get(&source_path).copied();
+                let uuid = conversation_uuids.get(&source_path).cloned();
                 SessionSummaryRecord {
                     agent,
                     workspace: workspace.map(PathBuf::from),
@@ -75823,9 +81172,12 @@ fn run_sessions(
                     origin_host,
                     started_at,
                     modified_at,
+                    last_activity_at,
                     size_bytes: metadata.as_ref().map(std::fs::Metadata::len),
                     message_count,
                     human_turns,
+                    quality,
+                    uuid,
                 }
             },
         )
@@ -75839,15 +81191,44 @@ fn run_sessions(
         sessions.retain(|session| session.workspace_match_distance.is_some());
     }
 
+    if let Some(threshold) = min_quality {
+        // Unscored sessions (pre-v26 database, or indexed since the last
+        // quality refresh) fail the filter: the caller asked for sessions
+        // known to have gone somewhere.
+        sessions.retain(|session| session.quality.is_some_and(|score| score >= threshold));
+    }
+
     sessions.sort_by(|left, right| {
         left.workspace_match_distance
             .unwrap_or(usize::MAX)
             .cmp(&right.workspace_match_distance.unwrap_or(usize::MAX))
+            .then_with(|| {
+                if sort_quality {
+                    right
+                        .quality
+                        .unwrap_or(0.0)
+                        .total_cmp(&left.quality.unwrap_or(0.0))
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
             .then_with(|| right.modified_at.cmp(&left.modified_at))
             .then_with(|| right.started_at.cmp(&left.started_at))
             .then_with(|| left.source_path.cmp(&right.source_path))
     });
 
+    if unread_only {
+        sessions.retain(|session| {
+            crate::read_status::is_unread(
+                session.last_activity_at,
+                view_times
+                    .get(session.source_path.to_string_lossy().as_ref())
+                    .copied(),
+                &read_marker,
+            )
+        });
+    }
+
     if let Some(limit) = effective_limit {
         sessions.truncate(limit);
     }
@@ -75855,6 +81236,13 @@ fn run_sessions(
     let entries: Vec<SessionSummaryEntry> = sessions
         .into_iter()
         .map(|session| SessionSummaryEntry {
+            unread: crate::read_status::is_unread(
+                session.last_activity_at,
+                view_times
+                    .get(session.source_path.to_string_lossy().as_ref())
+                    .copied(),
+                &read_marker,
+            ),
             path: session.source_path.to_string_lossy().into_owned(),
             workspace: session
                 .workspace
@@ -75869,6 +81257,8 @@ fn run_sessions(
             size_bytes: session.size_bytes,
             message_count: session.message_count,
             human_turns: session.human_turns,
+            quality: session.quality,
+            uuid: session.uuid,
         })
         .collect();
 
@@ -75904,13 +81294,20 @@ fn run_sessions(
             .origin_host
             .as_deref()
             .unwrap_or(session.source_id.as_str());
+        let unread_mark = if session.unread { "\u{25cf} " } else { "" };
+        let quality_label = session
+            .quality
+            .map(|score| format!("  q={score:.2}"))
+            .unwrap_or_default();
         println!(
-            "{:>2}. [{}] {}  {} msgs / {} human",
+            "{:>2}. {}[{}] {}  {} msgs / {} human{}",
             idx + 1,
+            unread_mark,
             modified,
             session.agent,
             session.message_count,
-            session.human_turns
+            session.human_turns,
+            quality_label
         );
         println!("    workspace: {}", workspace);
         println!("    source: {}", source_label);
@@ -76002,6 +81399,773 @@ fn find_context_source_conversation(
     .ok()
 }
 
+/// `cass lineage`: print a conversation's ancestors and descendants using
+/// the parent references harnesses leave in session metadata (see
+/// `crate::lineage`). The target may be a conversation row id, a harness
+/// session/thread id, or a source path.
+fn run_lineage(
+    raw_id: &str,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+
+    let Some(target_id) = resolve_conversation_arg(&conn, raw_id)? else {
+        return Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("No conversation matches '{raw_id}'"),
+            hint: Some(
+                "Pass a conversation row id, a harness session id, or a source path \
+                 from search output."
+                    .to_string(),
+            ),
+            retryable: false,
+        });
+    };
+
+    let graph = crate::lineage::load_lineage_graph(&conn).map_err(|e| CliError {
+        code: 1,
+        kind: CliErrorKind::DbQuery.kind_str(),
+        message: format!("failed to build lineage graph: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    let target = graph
+        .node(target_id)
+        .expect("target id came from the same table the graph was built from");
+    // `ancestors` walks parent-first; render root-first like a path.
+    let ancestors: Vec<_> = graph.ancestors(target_id).into_iter().rev().collect();
+    let descendants = graph.descendants(target_id);
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "target": target,
+            "ancestors": ancestors
+                .iter()
+                .filter_map(|id| graph.node(*id))
+                .collect::<Vec<_>>(),
+            "descendants": descendants
+                .iter()
+                .filter_map(|(id, depth)| {
+                    graph.node(*id).map(|node| {
+                        serde_json::json!({ "depth": depth, "conversation": node })
+                    })
+                })
+                .collect::<Vec<_>>(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    let format_node = |node: &crate::lineage::LineageNode| -> String {
+        use chrono::TimeZone;
+        let day = node
+            .started_at
+            .and_then(|ts| chrono::Utc.timestamp_millis_opt(ts).single())
+            .map_or_else(|| "????-??-??".to_string(), |dt| {
+                dt.format("%Y-%m-%d").to_string()
+            });
+        let title = node.title.as_deref().unwrap_or("(untitled)");
+        format!("#{}  {}  {} ({})", node.id, day, title, node.agent)
+    };
+
+    println!("\n🌳 Lineage for {}\n", format_node(target));
+
+    if ancestors.is_empty() {
+        println!("Ancestors: none (this is a root session)");
+    } else {
+        println!("Ancestors (root first):");
+        for (depth, id) in ancestors.iter().enumerate() {
+            if let Some(node) = graph.node(*id) {
+                println!("{}└─ {}", "   ".repeat(depth), format_node(node));
+            }
+        }
+    }
+
+    println!();
+    if descendants.is_empty() {
+        println!("Descendants: none");
+    } else {
+        println!("Descendants:");
+        for (id, depth) in &descendants {
+            if let Some(node) = graph.node(*id) {
+                println!("{}└─ {}", "   ".repeat(depth - 1), format_node(node));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a user-supplied conversation reference (row id, harness
+/// session/thread id, or source path) to a `conversations` row id.
+fn resolve_conversation_arg(
+    conn: &frankensqlite::Connection,
+    raw_id: &str,
+) -> CliResult<Option<i64>> {
+    use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
+
+    let lookup = |sql: &str, param: ParamValue| -> CliResult<Option<i64>> {
+        conn.query_row_map(sql, &[param], |r: &frankensqlite::Row| r.get_typed(0))
+            .optional()
+            .map_err(|e| CliError::unknown(format!("query: {e}")))
+    };
+
+    if let Ok(numeric) = raw_id.parse::<i64>()
+        && let Some(id) = lookup(
+            "SELECT id FROM conversations WHERE id = ?",
+            ParamValue::from(numeric),
+        )?
+    {
+        return Ok(Some(id));
+    }
+    if let Some(id) = lookup(
+        "SELECT id FROM conversations WHERE external_id = ?",
+        ParamValue::from(raw_id.to_string()),
+    )? {
+        return Ok(Some(id));
+    }
+    lookup(
+        "SELECT id FROM conversations WHERE source_path = ? ORDER BY id DESC LIMIT 1",
+        ParamValue::from(raw_id.to_string()),
+    )
+}
+
+/// `cass compare`: structured side-by-side report over two indexed sessions.
+fn run_compare(
+    raw_a: &str,
+    raw_b: &str,
+    turns: usize,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use crate::session_compare::{
+        CompareMessage, SessionStats, align_prompts, compute_session_stats,
+    };
+    use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+
+    let resolve = |raw: &str| -> CliResult<i64> {
+        resolve_conversation_arg(&conn, raw)?.ok_or_else(|| CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("No conversation matches '{raw}'"),
+            hint: Some(
+                "Pass a conversation row id, a harness session id, or a source path \
+                 from search output."
+                    .to_string(),
+            ),
+            retryable: false,
+        })
+    };
+    let id_a = resolve(raw_a)?;
+    let id_b = resolve(raw_b)?;
+
+    let load_side = |conv_id: i64| -> CliResult<(SessionStats, Vec<CompareMessage>)> {
+        let (agent, title, source_path): (String, Option<String>, String) = conn
+            .query_row_map(
+                "SELECT COALESCE(a.slug, 'unknown'), c.title, c.source_path
+                 FROM conversations c
+                 LEFT JOIN agents a ON c.agent_id = a.id
+                 WHERE c.id = ?",
+                &[ParamValue::from(conv_id)],
+                |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+            )
+            .optional()
+            .map_err(|e| CliError::unknown(format!("query: {e}")))?
+            .expect("conversation id came from resolve_conversation_arg");
+        let messages: Vec<CompareMessage> = conn
+            .query_map_collect(
+                "SELECT role, content, created_at FROM messages
+                 WHERE conversation_id = ? ORDER BY idx",
+                &[ParamValue::from(conv_id)],
+                |r: &frankensqlite::Row| {
+                    Ok(CompareMessage {
+                        role: r.get_typed(0)?,
+                        content: r.get_typed(1)?,
+                        created_at: r.get_typed(2)?,
+                    })
+                },
+            )
+            .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+        let mut stats = compute_session_stats(&messages);
+        stats.conversation_id = conv_id;
+        stats.agent = agent;
+        stats.title = title;
+        stats.source_path = source_path;
+        Ok((stats, messages))
+    };
+    let (stats_a, messages_a) = load_side(id_a)?;
+    let (stats_b, messages_b) = load_side(id_b)?;
+    let aligned = align_prompts(&messages_a, &messages_b, turns);
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    crate::audit::record_query(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+        "compare",
+        Some(format!("{raw_a} vs {raw_b}").as_str()),
+        Some(2),
+        structured_format.is_some(),
+    );
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "a": stats_a,
+            "b": stats_b,
+            "aligned_prompts": aligned
+                .iter()
+                .enumerate()
+                .map(|(turn, (a, b))| {
+                    serde_json::json!({ "turn": turn + 1, "a": a, "b": b })
+                })
+                .collect::<Vec<_>>(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    let label = |stats: &SessionStats| -> String {
+        format!(
+            "#{} {} ({})",
+            stats.conversation_id,
+            stats.title.as_deref().unwrap_or("(untitled)"),
+            stats.agent
+        )
+    };
+    let duration = |stats: &SessionStats| -> String {
+        stats.duration_ms.map_or_else(
+            || "n/a".to_string(),
+            crate::session_metrics::format_duration_ms,
+        )
+    };
+    let top_tools = |stats: &SessionStats| -> String {
+        if stats.tools.is_empty() {
+            return "none".to_string();
+        }
+        stats
+            .tools
+            .iter()
+            .take(3)
+            .map(|t| format!("{}\u{00d7}{}", t.name, t.calls))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    println!("\n⚖️  Session comparison\n");
+    println!("A: {}", label(&stats_a));
+    println!("B: {}", label(&stats_b));
+    println!();
+    println!("{:<16} {:>14} {:>14}", "", "A", "B");
+    let row = |name: &str, a: String, b: String| {
+        println!("{name:<16} {a:>14} {b:>14}");
+    };
+    row(
+        "messages",
+        stats_a.message_count.to_string(),
+        stats_b.message_count.to_string(),
+    );
+    row(
+        "prompts",
+        stats_a.prompt_count.to_string(),
+        stats_b.prompt_count.to_string(),
+    );
+    row(
+        "tool calls",
+        stats_a.tool_call_count.to_string(),
+        stats_b.tool_call_count.to_string(),
+    );
+    row(
+        "files touched",
+        stats_a.files_touched.len().to_string(),
+        stats_b.files_touched.len().to_string(),
+    );
+    row("duration", duration(&stats_a), duration(&stats_b));
+    row(
+        "~tokens",
+        stats_a.content_tokens_est.to_string(),
+        stats_b.content_tokens_est.to_string(),
+    );
+    println!();
+    println!("Top tools A: {}", top_tools(&stats_a));
+    println!("Top tools B: {}", top_tools(&stats_b));
+
+    if !aligned.is_empty() {
+        println!("\nPrompts by turn:");
+        for (turn, (a, b)) in aligned.iter().enumerate() {
+            println!(
+                "  {:>2}. A: {}",
+                turn + 1,
+                a.as_deref().unwrap_or("\u{2014}")
+            );
+            println!("      B: {}", b.as_deref().unwrap_or("\u{2014}"));
+        }
+    }
+
+    let shared: Vec<&String> = stats_a
+        .files_touched
+        .iter()
+        .filter(|path| stats_b.files_touched.contains(*path))
+        .collect();
+    if !shared.is_empty() {
+        println!("\nFiles touched by both:");
+        for path in shared {
+            println!("  {path}");
+        }
+    }
+    Ok(())
+}
+
+/// Best-effort view tracking for follow-up commands (`view`, `expand`,
+/// `get-context`). Failures are swallowed by design: a pre-v22 schema or a
+/// read-only DB must never break the read path, view history is a
+/// convenience. Skips entirely when no index exists yet so tracking never
+/// creates a database.
+fn record_conversation_view_best_effort(db_path: &Path, source_path: &str) {
+    use frankensqlite::compat::ConnectionExt;
+
+    if !db_path.exists() {
+        return;
+    }
+    let Ok(conn) = frankensqlite::Connection::open(db_path) else {
+        return;
+    };
+    let _ = conn.execute_batch("PRAGMA busy_timeout = 2000;");
+    let _ = conn.execute_compat(
+        "INSERT INTO conversation_views (source_path, last_viewed_at, view_count)
+         VALUES (?1, ?2, 1)
+         ON CONFLICT(source_path) DO UPDATE SET
+             view_count = view_count + 1,
+             last_viewed_at = excluded.last_viewed_at",
+        frankensqlite::params![source_path, chrono::Utc::now().timestamp_millis()],
+    );
+}
+
+/// Last-viewed instant per source path from `conversation_views`. A pre-v22
+/// database has no such table; treat that as "nothing viewed yet" so the
+/// unread computation degrades to "everything with activity is unread".
+fn load_conversation_view_times(
+    conn: &frankensqlite::Connection,
+) -> std::collections::HashMap<String, i64> {
+    use frankensqlite::compat::{ConnectionExt, RowExt};
+
+    conn.query_map_collect(
+        "SELECT source_path, MAX(last_viewed_at) FROM conversation_views GROUP BY source_path",
+        &[],
+        |r: &frankensqlite::Row| Ok((r.get_typed::<String>(0)?, r.get_typed::<i64>(1)?)),
+    )
+    .map(Vec::into_iter)
+    .map(std::collections::HashMap::from_iter)
+    .unwrap_or_default()
+}
+
+/// Usefulness scores keyed by source path. Best-effort: a pre-v26 database
+/// has no `conversation_quality` table until the next write-path open runs
+/// migrations, which just means every session reads as unscored.
+fn load_conversation_quality_scores(
+    conn: &frankensqlite::Connection,
+) -> std::collections::HashMap<String, f64> {
+    use frankensqlite::compat::{ConnectionExt, RowExt};
+
+    conn.query_map_collect(
+        "SELECT c.source_path, q.score
+         FROM conversation_quality q
+         JOIN conversations c ON c.id = q.conversation_id",
+        &[],
+        |r: &frankensqlite::Row| Ok((r.get_typed::<String>(0)?, r.get_typed::<f64>(1)?)),
+    )
+    .map(Vec::into_iter)
+    .map(std::collections::HashMap::from_iter)
+    .unwrap_or_default()
+}
+
+/// Durable conversation UUIDs keyed by source path. Empty on pre-v30
+/// databases (no table yet) so session listings degrade gracefully.
+fn load_conversation_uuids(
+    conn: &frankensqlite::Connection,
+) -> std::collections::HashMap<String, String> {
+    use frankensqlite::compat::{ConnectionExt, RowExt};
+
+    conn.query_map_collect(
+        "SELECT c.source_path, u.uuid
+         FROM conversation_uuids u
+         JOIN conversations c ON c.id = u.conversation_id",
+        &[],
+        |r: &frankensqlite::Row| Ok((r.get_typed::<String>(0)?, r.get_typed::<String>(1)?)),
+    )
+    .map(Vec::into_iter)
+    .map(std::collections::HashMap::from_iter)
+    .unwrap_or_default()
+}
+
+fn run_recent(
+    limit: usize,
+    unread_only: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let view_times = load_conversation_view_times(&conn);
+    let read_marker = crate::read_status::load_marker(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+    );
+    let limit = limit.max(1) as i64;
+
+    // Recently viewed. A pre-v22 database has no conversation_views table
+    // until the next write-path open runs migrations; treat that as "nothing
+    // viewed yet" rather than failing the whole command.
+    let viewed: Vec<(String, i64, i64, Option<String>, Option<String>)> = conn
+        .query_map_collect(
+            "SELECT v.source_path, v.last_viewed_at, MAX(v.view_count), c.title, a.slug
+             FROM conversation_views v
+             LEFT JOIN conversations c ON c.source_path = v.source_path
+             LEFT JOIN agents a ON c.agent_id = a.id
+             GROUP BY v.source_path
+             ORDER BY v.last_viewed_at DESC
+             LIMIT ?",
+            &[ParamValue::from(limit)],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                    r.get_typed(4)?,
+                ))
+            },
+        )
+        .unwrap_or_default();
+
+    // Recently active, by latest message activity regardless of whether the
+    // session was ever opened.
+    let mut active: Vec<(String, i64, Option<String>, Option<String>)> = conn
+        .query_map_collect(
+            "SELECT c.source_path,
+                    MAX(COALESCE(c.last_message_created_at, c.ended_at, c.started_at)) AS activity,
+                    c.title, a.slug
+             FROM conversations c
+             LEFT JOIN agents a ON c.agent_id = a.id
+             WHERE COALESCE(c.last_message_created_at, c.ended_at, c.started_at) IS NOT NULL
+             GROUP BY c.source_path
+             ORDER BY activity DESC
+             LIMIT ?",
+            &[ParamValue::from(limit)],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                ))
+            },
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let entry_unread = |source_path: &str, activity: i64| {
+        crate::read_status::is_unread(
+            Some(activity),
+            view_times.get(source_path).copied(),
+            &read_marker,
+        )
+    };
+    if unread_only {
+        active.retain(|(source_path, activity, _, _)| entry_unread(source_path, *activity));
+    }
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    crate::audit::record_query(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+        "recent",
+        None,
+        Some((viewed.len() + active.len()) as i64),
+        structured_format.is_some(),
+    );
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "recently_viewed": viewed
+                .iter()
+                .map(|(source_path, last_viewed_at, view_count, title, agent)| {
+                    serde_json::json!({
+                        "source_path": source_path,
+                        "last_viewed_at": last_viewed_at,
+                        "view_count": view_count,
+                        "title": title,
+                        "agent": agent,
+                    })
+                })
+                .collect::<Vec<_>>(),
+            "recently_active": active
+                .iter()
+                .map(|(source_path, last_activity_at, title, agent)| {
+                    serde_json::json!({
+                        "source_path": source_path,
+                        "last_activity_at": last_activity_at,
+                        "title": title,
+                        "agent": agent,
+                        "unread": entry_unread(source_path, *last_activity_at),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    let ago = |ts_ms: i64| -> String {
+        let age = (chrono::Utc::now().timestamp_millis().saturating_sub(ts_ms)) / 1000;
+        if age < 60 {
+            format!("{age}s ago")
+        } else if age < 3600 {
+            format!("{}m ago", age / 60)
+        } else if age < 86400 {
+            format!("{}h ago", age / 3600)
+        } else {
+            format!("{}d ago", age / 86400)
+        }
+    };
+    let line = |title: &Option<String>, agent: &Option<String>, source_path: &str| -> String {
+        format!(
+            "[{}] {} \u{2014} {}",
+            agent.as_deref().unwrap_or("?"),
+            title.as_deref().unwrap_or("(untitled)"),
+            source_path
+        )
+    };
+
+    if !unread_only {
+        println!("\n🕐 Recently viewed:");
+        if viewed.is_empty() {
+            println!("  (nothing yet \u{2014} open sessions in the TUI or via 'cass view')");
+        }
+        for (source_path, last_viewed_at, view_count, title, agent) in &viewed {
+            let views = if *view_count == 1 {
+                String::new()
+            } else {
+                format!(", {view_count} views")
+            };
+            println!(
+                "  {:>8}  {}{}",
+                ago(*last_viewed_at),
+                line(title, agent, source_path),
+                views
+            );
+        }
+    }
+
+    if unread_only {
+        println!("\n📬 Unread:");
+        if active.is_empty() {
+            println!("  (inbox zero \u{2014} nothing new since you last looked)");
+        }
+    } else {
+        println!("\n⚡ Recently active:");
+    }
+    for (source_path, last_activity_at, title, agent) in &active {
+        let unread_mark = if entry_unread(source_path, *last_activity_at) {
+            "\u{25cf} "
+        } else {
+            ""
+        };
+        println!(
+            "  {:>8}  {}{}",
+            ago(*last_activity_at),
+            unread_mark,
+            line(title, agent, source_path)
+        );
+    }
+    Ok(())
+}
+
+/// `cass runs`: list sessions classified at index time as automated
+/// (headless/batch) invocations, newest first. Classification lives in the
+/// `agent_runs` table (see `model::agent_run`); a database that has never
+/// been indexed on schema v27+ simply lists nothing.
+fn run_runs(
+    limit: usize,
+    failed_only: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ConnectionExt, ParamValue, RowExt};
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let limit = limit.max(1) as i64;
+
+    // A pre-v27 database has no agent_runs table until the next write-path
+    // open runs migrations; treat that as "no runs classified yet" rather
+    // than failing the whole command.
+    #[allow(clippy::type_complexity)]
+    let mut runs: Vec<(
+        String,
+        Option<String>,
+        Option<String>,
+        Option<i64>,
+        i64,
+        i64,
+        Option<i64>,
+    )> = conn
+        .query_map_collect(
+            "SELECT c.source_path, c.title, a.slug, c.started_at,
+                    r.message_count, r.exec_heavy, r.exit_code
+             FROM agent_runs r
+             JOIN conversations c ON c.id = r.conversation_id
+             LEFT JOIN agents a ON c.agent_id = a.id
+             WHERE r.is_run = 1
+             ORDER BY CASE WHEN c.started_at IS NULL THEN 1 ELSE 0 END,
+                      c.started_at DESC, c.id DESC
+             LIMIT ?",
+            &[ParamValue::from(limit)],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                    r.get_typed(4)?,
+                    r.get_typed(5)?,
+                    r.get_typed(6)?,
+                ))
+            },
+        )
+        .unwrap_or_default();
+
+    if failed_only {
+        runs.retain(|(_, _, _, _, _, _, exit_code)| exit_code.is_some_and(|code| code != 0));
+    }
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    crate::audit::record_query(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+        "runs",
+        None,
+        Some(runs.len() as i64),
+        structured_format.is_some(),
+    );
+
+    if let Some(fmt) = structured_format {
+        let mut run_objects = Vec::with_capacity(runs.len());
+        for (source_path, title, agent, started_at, message_count, exec_heavy, exit_code) in &runs {
+            run_objects.push(serde_json::json!({
+                "source_path": source_path,
+                "title": title,
+                "agent": agent,
+                "started_at": started_at,
+                "message_count": message_count,
+                "exec_heavy": *exec_heavy != 0,
+                "exit_code": exit_code,
+            }));
+        }
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "runs": run_objects,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Automated Runs");
+    println!("{}", "─".repeat(72));
+    if runs.is_empty() {
+        println!("  No automated runs classified yet (run `cass index` first).");
+        return Ok(());
+    }
+    for (idx, (source_path, title, agent, started_at, message_count, exec_heavy, exit_code)) in
+        runs.iter().enumerate()
+    {
+        let started = started_at
+            .and_then(chrono::DateTime::<Utc>::from_timestamp_millis)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| "-".to_string());
+        let status = match exit_code {
+            Some(0) => "exit 0".to_string(),
+            Some(code) => format!("exit {code} ✗"),
+            None => "exit ?".to_string(),
+        };
+        let exec_label = if *exec_heavy != 0 { "  exec-heavy" } else { "" };
+        println!(
+            "{:>2}. [{}] {}  {} msgs  {}{}",
+            idx + 1,
+            started,
+            agent.as_deref().unwrap_or("?"),
+            message_count,
+            status,
+            exec_label
+        );
+        if let Some(title) = title {
+            println!("    title: {title}");
+        }
+        println!("    path: {source_path}");
+    }
+    Ok(())
+}
+
+/// `cass mark-read`: move the mark-all-read watermark to now so every indexed
+/// session counts as read. Only writes the data-dir control file — view
+/// counts and `cass recent` history are untouched.
+fn run_mark_read(
+    data_dir_override: &Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let marker = crate::read_status::mark_all_read(&data_dir, now_ms).map_err(|e| CliError {
+        code: 1,
+        kind: CliErrorKind::FileWrite.kind_str(),
+        message: format!("Failed to write read marker: {e}"),
+        hint: None,
+        retryable: true,
+    })?;
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "schema_version": 1,
+                "success": true,
+                "all_read_before_ms": marker.all_read_before_ms,
+            }),
+            fmt,
+        );
+    }
+    println!("All sessions marked read. New activity will show as unread again.");
+    Ok(())
+}
+
 fn run_context(
     path: &Path,
     source_id: Option<&str>,
@@ -76729,11 +82893,26 @@ fn build_env_var_capabilities() -> Vec<EnvVarCapability> {
             "Set to 1 for headless test runs and prompt-free TUI behavior.",
         ),
         env_var_capability("CASS_DATA_DIR", None, "Override the cass data directory."),
+        env_var_capability(
+            "CASS_DB",
+            None,
+            "Override the SQLite database path (CASS_DB_PATH accepted as an alias).",
+        ),
         env_var_capability("CASS_DB_PATH", None, "Override the SQLite database path."),
+        env_var_capability(
+            "CASS_CONFIG_DIR",
+            None,
+            "Override the config directory holding cass.toml and sources.toml.",
+        ),
+        env_var_capability(
+            "CASS_PROFILE",
+            None,
+            "Named profile to scope the data dir and config to (see `cass profile`).",
+        ),
         env_var_capability(
             "CASS_OUTPUT_FORMAT",
             None,
-            "Default structured output format: json, jsonl, compact, sessions, or toon.",
+            "Default structured output format: json, jsonl, compact, sessions, toon, or msgpack.",
         ),
         env_var_capability(
             "CASS_SEARCH_TIMEOUT_MS",
@@ -77439,7 +83618,7 @@ fn run_introspect(output_format: Option<RobotFormat>) -> CliResult<()> {
                 let _ = serde_json::to_writer(&mut stdout, &response);
                 let _ = writeln!(stdout);
             }
-            RobotFormat::Toon => {
+            RobotFormat::Toon | RobotFormat::Msgpack => {
                 let payload = serde_json::to_value(&response).unwrap_or_default();
                 return output_structured_value(payload, fmt);
             }
@@ -82066,7 +88245,18 @@ fn build_response_schemas() -> std::collections::BTreeMap<String, serde_json::Va
                     "properties": {
                         "data_dir": { "type": "string" },
                         "db_path": { "type": "string" },
-                        "index_path": { "type": "string" }
+                        "index_path": { "type": "string" },
+                        "config_path": { "type": ["string", "null"] },
+                        "env_overrides": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "var": { "type": "string" },
+                                    "value": { "type": "string" }
+                                }
+                            }
+                        }
                     }
                 },
                 "database": {
@@ -83992,7 +90182,7 @@ fn try_load_indexed_conversation_from_db_with_source(
     if !db_path.exists() {
         return None;
     }
-    let storage = crate::storage::sqlite::FrankenStorage::open(db_path).ok()?;
+    let storage = crate::storage::sqlite::FrankenStorage::open_rw_or_readonly(db_path).ok()?;
     let source_path = source_path.to_string_lossy();
     let source_id = canonical_followup_source_id(source_id);
     if let Some(source_id) = source_id.as_deref() {
@@ -84130,12 +90320,30 @@ fn try_load_indexed_conversation_from_db(
     try_load_indexed_conversation_from_db_with_source(source_path, db_path, None)
 }
 
+/// Resolve a `--at-bookmark` request to a 1-based line for `run_view`.
+///
+/// Indexed views render one line per message (`serialize_indexed_view_lines`),
+/// so the bookmark's stored message index maps to line `idx + 1`. For raw
+/// JSONL sources this is approximate but lands on the bookmarked message.
+fn bookmark_line_for_view(path: &Path) -> Option<usize> {
+    let store = crate::view_state::ViewStateStore::open_existing(
+        &crate::view_state::default_view_state_path(),
+    )
+    .ok()
+    .flatten()?;
+    let state = store.get(&path.to_string_lossy()).ok().flatten()?;
+    state.bookmark_message_idx.map(|idx| idx + 1)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_view(
     path: &Path,
     db_override: Option<PathBuf>,
     source_id: Option<&str>,
     line: Option<usize>,
     context: usize,
+    as_of: Option<&str>,
+    at_bookmark: bool,
     output_format: Option<RobotFormat>,
 ) -> CliResult<()> {
     // Bounded-budget signal (uojcg.2.6 / 2.2): the report saw `cass view` fail
@@ -84154,8 +90362,26 @@ fn run_view(
     let normalized_source_id = canonical_followup_source_id(source_id);
     let source_id = normalized_source_id.as_deref();
 
+    // --line wins over --at-bookmark; a missing bookmark degrades to the top
+    // of the conversation rather than failing the view.
+    let line = if at_bookmark && line.is_none() {
+        let resolved = bookmark_line_for_view(path);
+        if resolved.is_none() {
+            tracing::debug!(
+                path = %path.display(),
+                "--at-bookmark: no bookmark recorded for this conversation"
+            );
+        }
+        resolved
+    } else {
+        line
+    };
+
     let db_path = db_override.unwrap_or_else(default_db_path);
     let indexed_view = try_load_indexed_conversation_from_db_with_source(path, &db_path, source_id);
+    if indexed_view.is_some() {
+        record_conversation_view_best_effort(&db_path, &path.to_string_lossy());
+    }
     let allow_direct_file = followup_source_is_local(source_id) || source_id.is_none();
 
     let prefer_direct_file = prefers_direct_view_file(path, source_id);
@@ -84221,6 +90447,34 @@ fn run_view(
     };
     let archive_only = archive_used && !source_exists;
 
+    // Time-travel cutoff (--as-of): drop messages created after the cutoff so
+    // the rendered conversation reflects what was known at that moment. Lines
+    // without a recognizable timestamp are kept — dropping them would silently
+    // hide content the cutoff cannot reason about.
+    let lines = if let Some(as_of_str) = as_of {
+        let cutoff = parse_datetime_str(as_of_str).ok_or_else(|| CliError {
+            code: 2,
+            kind: CliErrorKind::Usage.kind_str(),
+            message: format!("Could not parse --as-of value: {as_of_str}"),
+            hint: Some(
+                "Use ISO date/datetime (2024-01-15 or 2024-01-15T12:00:00), a keyword (today, yesterday), or a relative offset (-7d, -24h)."
+                    .to_string(),
+            ),
+            retryable: false,
+        })?;
+        lines
+            .into_iter()
+            .filter(|line| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|msg| extract_message_timestamp(&msg))
+                    .is_none_or(|ts| ts <= cutoff)
+            })
+            .collect()
+    } else {
+        lines
+    };
+
     if lines.is_empty() {
         return Err(CliError {
             code: 9,
@@ -84371,6 +90625,7 @@ fn refresh_index_inline(db_override: Option<PathBuf>, data_dir_override: Option<
         embedder: "fastembed".to_string(),
         progress: Some(Arc::clone(&progress)),
         watch_interval_secs: 30,
+        pause_on_battery: false,
     };
     eprintln!("Refreshing index...");
 
@@ -85252,6 +91507,10 @@ fn run_index_with_data(
     watch: bool,
     watch_once: Option<Vec<PathBuf>>,
     watch_interval: u64,
+    pause_on_battery: bool,
+    nice: bool,
+    no_nice: bool,
+    max_cpu: Option<String>,
     data_dir_override: Option<PathBuf>,
     semantic: bool,
     build_hnsw: bool,
@@ -85270,6 +91529,28 @@ fn run_index_with_data(
     let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
     let embedder = resolve_semantic_index_embedder(&embedder);
 
+    // Unobtrusive-mode throttle: watch runs default to --nice (a background
+    // watcher should never peg a laptop); one-shot runs opt in. The ceiling
+    // feeds the responsiveness governor, so worker fan-out and in-flight
+    // write budgets scale down together.
+    let max_cpu_pct = match max_cpu.as_deref() {
+        None => None,
+        Some(raw) => match raw.trim().trim_end_matches('%').trim().parse::<u32>() {
+            Ok(pct) if pct > 0 => Some(pct),
+            _ => {
+                return Err(CliError::usage(
+                    format!("Invalid --max-cpu value: {raw}"),
+                    Some(
+                        "Pass a percentage between 10 and 100, e.g. --max-cpu 50 or --max-cpu 50%"
+                            .to_string(),
+                    ),
+                ));
+            }
+        },
+    };
+    let effective_nice = nice || ((watch || watch_once.is_some()) && !no_nice);
+    crate::indexer::responsiveness::configure_unobtrusive_mode(effective_nice, max_cpu_pct);
+
     let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
         if matches!(fmt, RobotFormat::Sessions) {
             RobotFormat::Compact
@@ -85482,6 +91763,7 @@ fn run_index_with_data(
         embedder: embedder.clone(),
         progress: Some(index_progress.clone()),
         watch_interval_secs: watch_interval,
+        pause_on_battery,
     };
 
     // Set up progress display
@@ -85847,6 +92129,12 @@ fn run_index_with_data(
         }
     }
 
+    // Compact per-connector table for human runs; the JSON report carries the
+    // same data under indexing_stats.connectors.
+    if structured_format.is_none() && !watch && res.is_ok() {
+        print_per_connector_summary(&index_progress);
+    }
+
     let elapsed_ms = start.elapsed().as_millis();
 
     if let Err(err) = &res {
@@ -85987,6 +92275,54 @@ fn run_index_with_data(
     }
 }
 
+/// Print the end-of-run per-connector parsing table to stderr (human runs
+/// only; JSON runs get the same numbers in `indexing_stats.connectors`).
+fn print_per_connector_summary(progress: &indexer::IndexingProgress) {
+    let Ok(stats) = progress.stats.lock() else {
+        return;
+    };
+    if stats.connectors.is_empty() {
+        return;
+    }
+    let mut connectors: Vec<_> = stats.connectors.iter().collect();
+    connectors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let name_width = connectors
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("connector".len());
+    eprintln!("Per-connector summary:");
+    eprintln!(
+        "  {:<name_width$}  {:>10}  {:>7}  {:>6}  {:>8}  {:>8}",
+        "connector", "discovered", "parsed", "convs", "messages", "failures"
+    );
+    for connector in connectors {
+        eprintln!(
+            "  {:<name_width$}  {:>10}  {:>7}  {:>6}  {:>8}  {:>8}",
+            connector.name,
+            connector.files_discovered,
+            connector.files_parsed,
+            connector.conversations,
+            connector.messages,
+            connector.parse_failures,
+        );
+        for sample in &connector.parse_failure_samples {
+            eprintln!("  {:<name_width$}  \u{21b3} {sample}", "");
+        }
+        if connector.role_anomalies > 0 {
+            eprintln!(
+                "  {:<name_width$}  \u{26a0} {} conversation(s) with one-sided role distribution (likely parse bug):",
+                "", connector.role_anomalies,
+            );
+            for sample in &connector.role_anomaly_samples {
+                eprintln!("  {:<name_width$}  \u{21b3} {sample}", "");
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct IndexingExclusionNotice {
     config_path: String,
@@ -86116,10 +92452,65 @@ fn emit_indexing_exclusion_notice(notice: &IndexingExclusionNotice, structured_o
     }
 }
 
+/// Resolve and export the active profile (`--profile` flag, then
+/// `CASS_PROFILE`, then the registry's active entry) so every command,
+/// config read, and subprocess inherits its scope via `CASS_PROFILE` and
+/// `CASS_DATA_DIR`. An explicit operator `CASS_DATA_DIR` is respected
+/// unless the profile was named on the command line, where the flag is the
+/// later, more specific instruction.
+///
+/// Must run at single-threaded program startup: it mutates the process
+/// environment, which is only sound before any other thread can read it.
+pub fn apply_active_profile(profile_flag: Option<&str>) -> CliResult<()> {
+    let resolved = crate::profiles::resolve_active(profile_flag).map_err(|e| CliError {
+        code: 5,
+        kind: "profile",
+        message: e.to_string(),
+        hint: Some(
+            "List profiles with `cass profile list`; create one with `cass profile create <name>`."
+                .to_string(),
+        ),
+        retryable: false,
+    })?;
+    let Some(profile) = resolved else {
+        return Ok(());
+    };
+    let keep_operator_data_dir =
+        profile_flag.is_none() && std::env::var_os("CASS_DATA_DIR").is_some();
+    // SAFETY: called from main before the async runtime (or any other
+    // thread) exists; see `apply_default_fsqlite_read_witness_cap` for the
+    // same startup-env pattern.
+    unsafe {
+        std::env::set_var(crate::profiles::PROFILE_ENV, &profile.name);
+        if !keep_operator_data_dir {
+            std::env::set_var("CASS_DATA_DIR", &profile.data_dir);
+        }
+    }
+    Ok(())
+}
+
+/// Default database path: `CASS_DB` env var (long-documented `CASS_DB_PATH`
+/// accepted as an alias), then the `[paths] db` entry in `cass.toml`, then
+/// `agent_search.db` inside the resolved data dir.
 pub fn default_db_path() -> PathBuf {
+    for var in ["CASS_DB", "CASS_DB_PATH"] {
+        if let Ok(path) = dotenvy::var(var) {
+            let trimmed = path.trim();
+            if !trimmed.is_empty() {
+                return PathBuf::from(trimmed);
+            }
+        }
+    }
+    if let Some(path) = configured_path_override(|paths| paths.db) {
+        return path;
+    }
     default_data_dir().join("agent_search.db")
 }
 
+/// Default data directory: `CASS_DATA_DIR` env var, then the `[paths]
+/// data_dir` entry in `cass.toml`, then `XDG_DATA_HOME`, then the platform
+/// ProjectDirs location — so sandboxed and NixOS setups can relocate state
+/// either per-invocation (env) or persistently (config).
 pub fn default_data_dir() -> PathBuf {
     if let Ok(dir) = dotenvy::var("CASS_DATA_DIR") {
         let trimmed = dir.trim();
@@ -86127,6 +92518,9 @@ pub fn default_data_dir() -> PathBuf {
             return PathBuf::from(trimmed);
         }
     }
+    if let Some(dir) = configured_path_override(|paths| paths.data_dir) {
+        return dir;
+    }
     if let Ok(dir) = dotenvy::var("XDG_DATA_HOME") {
         let trimmed = dir.trim();
         if !trimmed.is_empty() {
@@ -86139,6 +92533,37 @@ pub fn default_data_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("./data"))
 }
 
+/// One `[paths]` override from `cass.toml`, trimmed and non-empty. A missing
+/// or broken config file yields `None` — path resolution must never fail
+/// just because the config is bad, since `cass doctor` (which diagnoses bad
+/// configs) needs these paths too.
+fn configured_path_override(
+    pick: fn(crate::search_defaults::PathsDefaults) -> Option<String>,
+) -> Option<PathBuf> {
+    let paths = crate::search_defaults::load_paths_defaults()
+        .ok()
+        .unwrap_or_default();
+    let value = pick(paths)?;
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(trimmed))
+}
+
+/// Whether the data dir accepts writes right now. Some setups keep the
+/// data dir on a mount that is read-only outside maintenance windows;
+/// read surfaces (TUI, search) probe this once at startup and degrade
+/// write-backed conveniences to disabled instead of erroring on each
+/// attempt. A missing dir counts as writable only if it can be created.
+#[must_use]
+pub fn data_dir_is_writable(dir: &Path) -> bool {
+    if !dir.exists() {
+        return std::fs::create_dir_all(dir).is_ok();
+    }
+    crate::storage::sqlite::directory_writable(dir)
+}
+
 /// Read session paths from a file or stdin (when path is "-").
 /// Returns a HashSet of session paths for filtering.
 fn read_session_paths(source: &str) -> Result<std::collections::HashSet<String>, std::io::Error> {
@@ -87327,6 +93752,170 @@ async fn run_upgrade(
     crate::update_check::run_self_update(&info.tag_name);
 }
 
+/// `cass self-update` — channel-aware release check plus checksum-verified
+/// install. Unlike [`run_upgrade`], the installer runs as a child process so
+/// we regain control afterwards and can offer a database-migration pass with
+/// the freshly installed binary (opening the DB applies pending migrations).
+async fn run_self_update_command(
+    channel: crate::update_check::UpdateChannel,
+    check_only: bool,
+    yes: bool,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let current = env!("CARGO_PKG_VERSION");
+
+    let Some(info) = crate::update_check::force_check_channel(current, channel).await else {
+        if let Some(fmt) = output_format {
+            let payload = serde_json::json!({
+                "current_version": current,
+                "channel": channel.as_str(),
+                "latest_version": null,
+                "is_newer": false,
+                "checked": false,
+                "reason": "github_api_unreachable_or_disabled",
+            });
+            output_structured_value(payload, fmt)?;
+        } else {
+            eprintln!(
+                "Could not reach GitHub or update checks are disabled. Try again with network access."
+            );
+        }
+        return Ok(());
+    };
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "current_version": current,
+            "channel": channel.as_str(),
+            "latest_version": info.latest_version,
+            "tag_name": info.tag_name,
+            "is_newer": info.is_newer,
+            "release_url": info.release_url,
+            "checked": true,
+        });
+        output_structured_value(payload, fmt)?;
+        if check_only && info.is_newer {
+            std::process::exit(1);
+        }
+        // Structured mode only proceeds to the install side effect with an
+        // explicit `--yes` — prompting would corrupt the JSON stream.
+        if check_only || !info.is_newer || !yes {
+            return Ok(());
+        }
+    } else {
+        if !info.is_newer {
+            println!(
+                "cass {current} is up to date on the {} channel (latest release: {}).",
+                channel.as_str(),
+                info.tag_name
+            );
+            return Ok(());
+        }
+        if check_only {
+            println!(
+                "Update available on the {} channel: {current} → {}.\nView the release notes: {}",
+                channel.as_str(),
+                info.tag_name,
+                info.release_url,
+            );
+            std::process::exit(1);
+        }
+    }
+
+    let should_install = if yes {
+        true
+    } else if !io::stdin().is_terminal() {
+        eprintln!(
+            "Update available: {current} → {}, but no TTY is attached so I can't ask.\nRe-run with `--yes` to install non-interactively, or `--check` to print status.",
+            info.tag_name
+        );
+        return Ok(());
+    } else {
+        print!(
+            "A newer version is available on the {} channel: current v{current}, latest {}. Update now? (y/N): ",
+            channel.as_str(),
+            info.tag_name
+        );
+        io::stdout().flush().ok();
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_err() {
+            return Ok(());
+        }
+        matches!(input.trim(), "y" | "Y" | "yes" | "YES")
+    };
+    if !should_install {
+        return Ok(());
+    }
+
+    info!(
+        target: "upgrade",
+        "starting self-update from {current} to {} ({} channel)",
+        info.tag_name,
+        channel.as_str()
+    );
+    let status = crate::update_check::run_self_update_blocking(&info.tag_name).map_err(|e| {
+        CliError {
+            code: 1,
+            kind: CliErrorKind::Download.kind_str(),
+            message: format!("Failed to run installer: {e}"),
+            hint: None,
+            retryable: true,
+        }
+    })?;
+    if !status.success() {
+        return Err(CliError {
+            code: status.code().unwrap_or(1),
+            kind: CliErrorKind::Download.kind_str(),
+            message: format!(
+                "Installer exited with {} before the binary was replaced.",
+                status
+            ),
+            hint: Some("Re-run `cass self-update`; the old binary is untouched on failure.".into()),
+            retryable: true,
+        });
+    }
+    eprintln!("Installed {}.", info.tag_name);
+
+    // Post-install migration pass. The installer replaced the binary behind
+    // `current_exe`, so spawning it runs the NEW version's migrations.
+    let migrate = if yes {
+        true
+    } else if !io::stdin().is_terminal() {
+        false
+    } else {
+        print!("Apply database migrations with the new binary now? (y/N): ");
+        io::stdout().flush().ok();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).is_ok()
+            && matches!(input.trim(), "y" | "Y" | "yes" | "YES")
+    };
+    if migrate {
+        match std::env::current_exe().map(|exe| {
+            std::process::Command::new(exe)
+                .args(["stats", "--json"])
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+        }) {
+            Ok(Ok(status)) if status.success() => {
+                eprintln!("Database migrations applied.");
+            }
+            Ok(Ok(status)) => {
+                eprintln!(
+                    "warning: migration pass exited with {status}; run any cass command to retry."
+                );
+            }
+            Ok(Err(e)) | Err(e) => {
+                eprintln!("warning: could not run migration pass: {e}");
+            }
+        }
+    } else {
+        eprintln!("Migrations will run automatically the next time cass opens the database.");
+    }
+
+    Ok(())
+}
+
 /// `cass resume <path>` — resolve and optionally execute the native
 /// harness resume command for a session.
 fn run_resume(
@@ -87683,6 +94272,15 @@ fn run_export(
         ConvExportFormat::Html => {
             format_as_html(&messages, &session_title, session_start, include_tools)
         }
+        ConvExportFormat::CodexRollout => crate::native_export::format_as_codex_rollout(
+            &native_export_messages(&messages),
+            session_start,
+        ),
+        ConvExportFormat::ClaudeJsonl => crate::native_export::format_as_claude_jsonl(
+            &native_export_messages(&messages),
+            &session_title,
+            session_start,
+        ),
     };
 
     if let Some(out_path) = output {
@@ -87923,6 +94521,81 @@ mod clipboard_helper_tests {
 
 /// Export a session as a beautiful, self-contained HTML file with optional encryption.
 #[allow(clippy::too_many_arguments)]
+/// `cass share`: render a cleaned, shareable transcript of an indexed
+/// conversation. Unlike `cass export`, this always reads the indexed
+/// conversation (so redaction and tool collapsing operate on normalized
+/// messages) and is tuned for pasting into PRs / chat rather than resuming.
+fn run_share(
+    path: &Path,
+    db_override: Option<PathBuf>,
+    source_id: Option<&str>,
+    format: crate::share::ShareFormat,
+    redact: bool,
+    output: Option<&Path>,
+    clipboard: bool,
+) -> CliResult<()> {
+    use std::fs::File;
+    use std::io::Write;
+
+    if let Some(source_id) = source_id {
+        validate_followup_source_id(source_id, "cass share")?;
+    }
+
+    let db_path = db_override.unwrap_or_else(default_db_path);
+    let view = try_load_indexed_conversation_from_db_with_source(path, &db_path, source_id)
+        .ok_or_else(|| CliError {
+            code: 3,
+            kind: CliErrorKind::FileNotFound.kind_str(),
+            message: format!("No indexed conversation found for: {}", path.display()),
+            hint: Some(
+                "Use 'cass search' to find session paths, and run 'cass index' if the session is new."
+                    .to_string(),
+            ),
+            retryable: false,
+        })?;
+
+    let opts = crate::share::ShareOptions { redact };
+    let formatted = match format {
+        crate::share::ShareFormat::Md => crate::share::render_markdown(&view, opts),
+        crate::share::ShareFormat::Html => crate::share::render_html(&view, opts),
+    };
+
+    if let Some(out_path) = output {
+        let mut out_file = File::create(out_path).map_err(|e| CliError {
+            code: 9,
+            kind: CliErrorKind::FileCreate.kind_str(),
+            message: format!("Failed to create output file: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        out_file
+            .write_all(formatted.as_bytes())
+            .map_err(|e| CliError {
+                code: 9,
+                kind: CliErrorKind::FileWrite.kind_str(),
+                message: format!("Failed to write output: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
+        println!("Shared transcript written to: {}", out_path.display());
+    } else if clipboard {
+        match copy_to_system_clipboard(&formatted) {
+            Ok(tool) => {
+                let bytes = formatted.len();
+                eprintln!("Copied {bytes} bytes to clipboard via {tool}.");
+            }
+            Err(err) => {
+                eprintln!("warning: clipboard not available ({err}); falling back to stdout.");
+                println!("{formatted}");
+            }
+        }
+    } else {
+        println!("{formatted}");
+    }
+
+    Ok(())
+}
+
 fn run_export_html(
     session_path: &Path,
     db_override: Option<PathBuf>,
@@ -90766,6 +97439,7 @@ mod indexed_conversation_fallback_tests {
             None,
             Some(2),
             0,
+            None,
             Some(RobotFormat::Json),
         )
         .expect("view should prefer the local JSONL file over stale indexed content");
@@ -90829,6 +97503,7 @@ local second line
             None,
             Some(2),
             0,
+            None,
             Some(RobotFormat::Json),
         )
         .expect("view should prefer the local markdown file over stale indexed content");
@@ -90897,6 +97572,94 @@ local second line
         .expect("expand should prefer the local JSONL file over stale indexed content");
     }
 
+    #[test]
+    fn run_get_context_returns_window_around_indexed_message() {
+        use frankensqlite::compat::{ConnectionExt, RowExt};
+
+        let tmp = TempDir::new().expect("temp dir");
+        let db_path = tmp.path().join("agent_search.db");
+        let storage = SqliteStorage::open(&db_path).expect("open sqlite");
+
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".to_string(),
+            name: "Claude Code".to_string(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).expect("ensure agent");
+
+        let messages: Vec<Message> = (0..5)
+            .map(|idx| Message {
+                id: None,
+                idx,
+                role: if idx % 2 == 0 {
+                    MessageRole::User
+                } else {
+                    MessageRole::Agent
+                },
+                author: None,
+                created_at: Some(1_733_000_000_000 + i64::from(idx) * 1_000),
+                content: format!("message number {idx}"),
+                extra_json: serde_json::json!({}),
+                snippets: Vec::new(),
+            })
+            .collect();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".to_string(),
+            workspace: Some(PathBuf::from("/tmp/ws")),
+            external_id: Some("ctx-window".to_string()),
+            title: Some("Context Window".to_string()),
+            source_path: tmp.path().join("session.jsonl"),
+            started_at: Some(1_733_000_000_000),
+            ended_at: Some(1_733_000_004_000),
+            approx_tokens: None,
+            metadata_json: serde_json::json!({}),
+            messages,
+            source_id: "local".to_string(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .expect("insert conversation");
+
+        let middle_id: i64 = storage
+            .raw()
+            .query_row_map("SELECT id FROM messages WHERE idx = 2", &[], |r| {
+                r.get_typed(0)
+            })
+            .expect("middle message id");
+
+        run_get_context(
+            middle_id,
+            1,
+            1,
+            &Some(tmp.path().to_path_buf()),
+            Some(db_path),
+            Some(RobotFormat::Json),
+        )
+        .expect("get-context should resolve a window around an indexed message");
+    }
+
+    #[test]
+    fn run_get_context_reports_not_found_for_unknown_id() {
+        let tmp = TempDir::new().expect("temp dir");
+        let db_path = tmp.path().join("agent_search.db");
+        let _storage = SqliteStorage::open(&db_path).expect("open sqlite");
+
+        let err = run_get_context(
+            9_999,
+            3,
+            3,
+            &Some(tmp.path().to_path_buf()),
+            Some(db_path),
+            Some(RobotFormat::Json),
+        )
+        .expect_err("get-context should fail for an id that is not indexed");
+        assert_eq!(err.kind, "not-found");
+    }
+
     #[test]
     fn run_expand_prefers_indexed_conversation_for_local_markdown_file() {
         let tmp = TempDir::new().expect("temp dir");
@@ -91348,6 +98111,7 @@ This should stay behind the indexed html export.
             None,
             Some(1),
             0,
+            None,
             Some(RobotFormat::Json),
         )
         .expect("view should fall back to indexed content when the local JSONL cannot be read");
@@ -91463,6 +98227,7 @@ This should stay behind the indexed html export.
             None,
             Some(1),
             0,
+            None,
             Some(RobotFormat::Json),
         )
         .expect("view should prefer indexed conversation over unreadable backing file");
@@ -92165,6 +98930,21 @@ mod message_grouping_tests {
     }
 }
 
+/// Flatten raw export messages into the role/content/timestamp rows the
+/// native-format renderers in [`crate::native_export`] consume.
+fn native_export_messages(
+    messages: &[serde_json::Value],
+) -> Vec<crate::native_export::NativeExportMessage> {
+    messages
+        .iter()
+        .map(|msg| crate::native_export::NativeExportMessage {
+            role: extract_role(msg),
+            content: extract_text_content(msg),
+            created_at: extract_message_timestamp(msg),
+        })
+        .collect()
+}
+
 fn format_as_markdown(
     messages: &[serde_json::Value],
     title: &Option<String>,
@@ -92447,145 +99227,1246 @@ fn format_as_html(
             }
         }
 
-        html.push_str("</div>\n    </div>\n");
+        html.push_str("</div>\n    </div>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Show messages around a specific line in a session file
+fn run_expand(
+    path: &Path,
+    db_override: Option<PathBuf>,
+    source_id: Option<&str>,
+    line: usize,
+    context: usize,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    if let Some(source_id) = source_id {
+        validate_followup_source_id(source_id, "cass expand")?;
+    }
+    let normalized_source_id = canonical_followup_source_id(source_id);
+    let source_id = normalized_source_id.as_deref();
+    if line == 0 {
+        return Err(CliError {
+            code: 2,
+            kind: CliErrorKind::InvalidLine.kind_str(),
+            message: "Line numbers start at 1, not 0".to_string(),
+            hint: Some("Use -n 1 for the first line".to_string()),
+            retryable: false,
+        });
+    }
+
+    let db_path = db_override.unwrap_or_else(default_db_path);
+    let indexed_view = try_load_indexed_conversation_from_db_with_source(path, &db_path, source_id);
+    if indexed_view.is_some() {
+        record_conversation_view_best_effort(&db_path, &path.to_string_lossy());
+    }
+    let allow_direct_file = followup_source_is_local(source_id) || source_id.is_none();
+
+    let prefer_direct_file = prefers_direct_jsonl_file(path, source_id);
+
+    let raw_lines: Vec<String> = if prefer_direct_file {
+        match read_followup_file_lines(path) {
+            Ok(lines) => lines,
+            Err(err) => {
+                if let Some(view) = indexed_view.as_ref() {
+                    serialize_indexed_view_lines(view)?
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+    } else if let Some(view) = indexed_view.as_ref() {
+        serialize_indexed_view_lines(view)?
+    } else if allow_direct_file && path.exists() {
+        return Err(CliError {
+            code: 9,
+            kind: CliErrorKind::IndexedSessionRequired.kind_str(),
+            message: format!(
+                "Local expand for '{}' requires an indexed conversation or a JSONL session",
+                path.display()
+            ),
+            hint: Some(
+                "Run 'cass index --full' first, or expand directly from a JSONL session."
+                    .to_string(),
+            ),
+            retryable: false,
+        });
+    } else {
+        return Err(CliError {
+            code: 3,
+            kind: CliErrorKind::FileNotFound.kind_str(),
+            message: match source_id {
+                Some(source_id) => format!(
+                    "No indexed session found for source '{}' at {}",
+                    source_id,
+                    path.display()
+                ),
+                None => format!("Session file not found: {}", path.display()),
+            },
+            hint: Some(match source_id {
+                Some(_) => "Use the exact source_id from search output or omit --source to prefer the local file/path.".to_string(),
+                None => "Use 'cass search' to find session paths".to_string(),
+            }),
+            retryable: false,
+        });
+    };
+
+    let mut messages: Vec<(usize, serde_json::Value)> = Vec::new();
+    let mut target_msg_idx: Option<usize> = None;
+    let mut current_line: usize = 0;
+
+    for raw_line in raw_lines {
+        current_line += 1;
+        if raw_line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(msg) = serde_json::from_str::<serde_json::Value>(&raw_line) {
+            if current_line == line {
+                target_msg_idx = Some(messages.len());
+            }
+            messages.push((current_line, msg));
+        }
+    }
+
+    if target_msg_idx.is_none() && line > 0 {
+        for (idx, (msg_line, _)) in messages.iter().enumerate() {
+            if *msg_line >= line {
+                target_msg_idx = Some(idx);
+                break;
+            }
+        }
+        if target_msg_idx.is_none() && !messages.is_empty() {
+            target_msg_idx = Some(messages.len() - 1);
+        }
+    }
+
+    let target_idx = target_msg_idx.ok_or_else(|| CliError {
+        code: 2,
+        kind: CliErrorKind::LineNotFound.kind_str(),
+        message: format!("No message found at or near line {}", line),
+        hint: Some(format!("File has {} messages", messages.len())),
+        retryable: false,
+    })?;
+
+    let start = target_idx.saturating_sub(context);
+    let end = (target_idx + context + 1).min(messages.len());
+
+    let context_messages: Vec<_> = messages[start..end]
+        .iter()
+        .enumerate()
+        .map(|(i, (line_num, msg))| {
+            let is_target = start + i == target_idx;
+            (line_num, msg, is_target)
+        })
+        .collect();
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let output: Vec<serde_json::Value> = context_messages
+            .iter()
+            .map(|(line_num, msg, is_target)| {
+                let role = extract_role(msg);
+                let content = extract_text_content(msg);
+                serde_json::json!({
+                    "line": line_num,
+                    "role": role,
+                    "is_target": is_target,
+                    "content": content,
+                })
+            })
+            .collect();
+        return output_structured_value(serde_json::Value::Array(output), fmt);
+    }
+
+    println!("\n📍 Context around line {} in {}\n", line, path.display());
+    println!("{}", "─".repeat(60));
+
+    for (line_num, msg, is_target) in context_messages {
+        let role = extract_role(msg);
+        let content = extract_text_content(msg);
+        let preview: String = content.chars().take(300).collect();
+        let marker = if is_target { ">>>" } else { "   " };
+        let role_icon = match role.as_str() {
+            "user" => "👤",
+            "assistant" => "🤖",
+            _ => "📝",
+        };
+
+        println!(
+            "{} L{:>4} {} {}",
+            marker,
+            line_num,
+            role_icon,
+            role.to_uppercase()
+        );
+        println!("        {}", preview.replace('\n', " "));
+        if content.len() > 300 {
+            println!("        ... ({} more chars)", content.len() - 300);
+        }
+        println!();
+    }
+
+    println!("{}", "─".repeat(60));
+    println!(
+        "Showing messages {} to {} of {} total",
+        start + 1,
+        end,
+        messages.len()
+    );
+    Ok(())
+}
+
+/// `cass distill`: mine durable facts (setup commands that worked, agreed
+/// conventions, gotchas) from a workspace's indexed sessions and write them
+/// under the managed marker block in the workspace's AGENTS.md / CLAUDE.md.
+/// Extraction and rendering are pure (`crate::distill`); this handler owns the
+/// workspace lookup, the message read, and the idempotent file update.
+fn run_distill(
+    workspace: &str,
+    format: &str,
+    output: Option<&Path>,
+    dry_run: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let memory_format = crate::distill::MemoryFormat::parse(format).ok_or_else(|| {
+        CliError::usage(
+            format!("Unknown memory format '{format}'"),
+            Some("Use --format agents-md or --format claude-md.".to_string()),
+        )
+    })?;
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let suffix = format!("%{workspace}");
+    let candidates: Vec<(i64, String)> = franken_query_map_collect_retry(
+        &conn,
+        "SELECT id, path FROM workspaces WHERE path = ?1 OR path LIKE ?2 ORDER BY path",
+        &[
+            ParamValue::from(workspace),
+            ParamValue::from(suffix.as_str()),
+        ],
+        |row: &frankensqlite::Row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+    )
+    .map_err(|e| CliError::unknown(format!("Failed to resolve workspace '{workspace}': {e}")))?;
+
+    let (workspace_id, workspace_path) = match candidates.as_slice() {
+        [] => {
+            return Err(CliError {
+                code: 4,
+                kind: CliErrorKind::NotFound.kind_str(),
+                message: format!("No indexed workspace matches '{workspace}'"),
+                hint: Some(
+                    "Pass a workspace path as shown in search results, or a unique trailing \
+                     fragment of one."
+                        .to_string(),
+                ),
+                retryable: false,
+            });
+        }
+        [only] => only.clone(),
+        many => match many.iter().find(|(_, path)| path == workspace) {
+            Some(exact) => exact.clone(),
+            None => {
+                let paths: Vec<&str> = many.iter().map(|(_, path)| path.as_str()).collect();
+                return Err(CliError::usage(
+                    format!("Workspace '{workspace}' is ambiguous"),
+                    Some(format!("Matches: {}", paths.join(", "))),
+                ));
+            }
+        },
+    };
+
+    let messages: Vec<(String, String)> = franken_query_map_collect_retry(
+        &conn,
+        "SELECT m.role, m.content FROM messages m \
+         JOIN conversations c ON m.conversation_id = c.id \
+         WHERE c.workspace_id = ?1 ORDER BY c.started_at, c.id, m.idx",
+        &[ParamValue::from(workspace_id)],
+        |row: &frankensqlite::Row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+    )
+    .map_err(|e| {
+        CliError::unknown(format!(
+            "Failed to read messages for workspace '{workspace_path}': {e}"
+        ))
+    })?;
+
+    let facts = crate::distill::extract_facts(&messages);
+    let block = crate::distill::render_block(&workspace_path, &facts);
+    let target = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(&workspace_path).join(memory_format.file_name()));
+
+    let mut written = false;
+    if !dry_run {
+        let existing = match std::fs::read_to_string(&target) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(CliError::unknown(format!(
+                    "Failed to read {}: {e}",
+                    target.display()
+                )));
+            }
+        };
+        let updated = crate::distill::upsert_block(&existing, &block);
+        if updated != existing {
+            std::fs::write(&target, updated).map_err(|e| CliError {
+                code: 9,
+                kind: CliErrorKind::Unknown.kind_str(),
+                message: format!("Failed to write {}: {e}", target.display()),
+                hint: Some(
+                    "Check that the workspace directory exists, or pass --output.".to_string(),
+                ),
+                retryable: false,
+            })?;
+            written = true;
+        }
+    }
+
+    let counts = |kind: crate::distill::FactKind| facts.iter().filter(|f| f.kind == kind).count();
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "workspace": workspace_path,
+                "format": memory_format.label(),
+                "output": target.display().to_string(),
+                "dry_run": dry_run,
+                "written": written,
+                "counts": {
+                    "setup_commands": counts(crate::distill::FactKind::SetupCommand),
+                    "conventions": counts(crate::distill::FactKind::Convention),
+                    "gotchas": counts(crate::distill::FactKind::Gotcha),
+                },
+                "facts": facts,
+            }),
+            fmt,
+        );
+    }
+
+    if dry_run {
+        println!("{block}");
+        return Ok(());
+    }
+    println!(
+        "Distilled {} fact(s) from {} ({} setup, {} conventions, {} gotchas).",
+        facts.len(),
+        workspace_path,
+        counts(crate::distill::FactKind::SetupCommand),
+        counts(crate::distill::FactKind::Convention),
+        counts(crate::distill::FactKind::Gotcha),
+    );
+    if written {
+        println!("Updated {}", target.display());
+    } else {
+        println!("{} already up to date", target.display());
+    }
+    Ok(())
+}
+
+/// `cass verify`: recompute every sealed conversation's integrity hash chain
+/// and compare it against the seal recorded at index time (see the
+/// `conversation_integrity` table). Exits non-zero when any seal mismatches,
+/// so compliance jobs can gate on it directly.
+/// `cass delta`: approximate "what changed since …" report for one
+/// workspace. Resolves the workspace the same way `cass distill` does, pulls
+/// the sessions and messages active inside the window, and hands them to the
+/// pure extractor in [`crate::delta`].
+fn run_delta(
+    workspace: &str,
+    since: &str,
+    utc: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let until_ms = chrono::Utc::now().timestamp_millis();
+    // --since is a look-back window (1d, 12h) first; anything else falls
+    // through to the shared date parser so absolute and natural-language
+    // starts ("2024-11-03", "last monday") work too.
+    let since_ms = match parse_duration_millis(since) {
+        Ok(window_ms) => until_ms.saturating_sub(window_ms),
+        Err(_) => match crate::ui::time_parser::parse_time_input_tz(since, utc) {
+            Some(ts) if ts <= until_ms => ts,
+            Some(_) => {
+                return Err(CliError::usage(
+                    format!("--since value `{since}` resolves to a future time"),
+                    Some("The delta window looks backward from now.".to_string()),
+                ));
+            }
+            None => return Err(unparseable_time_error("--since", since)),
+        },
+    };
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let suffix = format!("%{workspace}");
+    let candidates: Vec<(i64, String)> = franken_query_map_collect_retry(
+        &conn,
+        "SELECT id, path FROM workspaces WHERE path = ?1 OR path LIKE ?2 ORDER BY path",
+        &[
+            ParamValue::from(workspace),
+            ParamValue::from(suffix.as_str()),
+        ],
+        |row: &frankensqlite::Row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+    )
+    .map_err(|e| CliError::unknown(format!("Failed to resolve workspace '{workspace}': {e}")))?;
+
+    let (workspace_id, workspace_path) = match candidates.as_slice() {
+        [] => {
+            return Err(CliError {
+                code: 4,
+                kind: CliErrorKind::NotFound.kind_str(),
+                message: format!("No indexed workspace matches '{workspace}'"),
+                hint: Some(
+                    "Pass a workspace path as shown in search results, or a unique trailing \
+                     fragment of one."
+                        .to_string(),
+                ),
+                retryable: false,
+            });
+        }
+        [only] => only.clone(),
+        many => match many.iter().find(|(_, path)| path == workspace) {
+            Some(exact) => exact.clone(),
+            None => {
+                let paths: Vec<&str> = many.iter().map(|(_, path)| path.as_str()).collect();
+                return Err(CliError::usage(
+                    format!("Workspace '{workspace}' is ambiguous"),
+                    Some(format!("Matches: {}", paths.join(", "))),
+                ));
+            }
+        },
+    };
+
+    // Sessions active inside the window, most recent first. A conversation
+    // with no timestamps at all cannot be placed in the window and is left
+    // out rather than guessed at.
+    let session_rows: Vec<(i64, String, Option<String>, String, Option<i64>)> =
+        franken_query_map_collect_retry(
+            &conn,
+            "SELECT c.id, COALESCE(a.slug, 'unknown'), c.title, c.source_path, c.started_at \
+             FROM conversations c \
+             LEFT JOIN agents a ON c.agent_id = a.id \
+             WHERE c.workspace_id = ?1 AND COALESCE(c.ended_at, c.started_at) >= ?2 \
+             ORDER BY COALESCE(c.ended_at, c.started_at) DESC",
+            &[ParamValue::from(workspace_id), ParamValue::from(since_ms)],
+            |row: &frankensqlite::Row| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                    row.get_typed(4)?,
+                ))
+            },
+        )
+        .map_err(|e| {
+            CliError::unknown(format!(
+                "Failed to read sessions for workspace '{workspace_path}': {e}"
+            ))
+        })?;
+
+    // Messages inside the window, in conversation/message order. Untimestamped
+    // messages inherit their conversation's window membership.
+    let message_rows: Vec<(i64, String, String)> = franken_query_map_collect_retry(
+        &conn,
+        "SELECT m.conversation_id, m.role, m.content FROM messages m \
+         JOIN conversations c ON m.conversation_id = c.id \
+         WHERE c.workspace_id = ?1 AND COALESCE(c.ended_at, c.started_at) >= ?2 \
+           AND (m.created_at IS NULL OR m.created_at >= ?2) \
+         ORDER BY c.started_at, c.id, m.idx",
+        &[ParamValue::from(workspace_id), ParamValue::from(since_ms)],
+        |row: &frankensqlite::Row| Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?)),
+    )
+    .map_err(|e| {
+        CliError::unknown(format!(
+            "Failed to read messages for workspace '{workspace_path}': {e}"
+        ))
+    })?;
+
+    let mut per_conversation: std::collections::HashMap<i64, usize> =
+        std::collections::HashMap::new();
+    for (conversation_id, _, _) in &message_rows {
+        *per_conversation.entry(*conversation_id).or_default() += 1;
+    }
+    let sessions: Vec<crate::delta::DeltaSession> = session_rows
+        .into_iter()
+        .map(
+            |(id, agent, title, source_path, started_at)| crate::delta::DeltaSession {
+                agent,
+                title,
+                source_path,
+                started_at,
+                messages: per_conversation.get(&id).copied().unwrap_or(0),
+            },
+        )
+        .collect();
+    let messages: Vec<(String, String)> = message_rows
+        .into_iter()
+        .map(|(_, role, content)| (role, content))
+        .collect();
+
+    let delta = crate::delta::extract_activity(since_ms, until_ms, sessions, &messages);
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::to_value(&delta).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("success".to_string(), serde_json::json!(true));
+            obj.insert("workspace".to_string(), serde_json::json!(workspace_path));
+        }
+        return output_structured_value(payload, fmt);
+    }
+    print!("{}", delta.render_markdown(&workspace_path));
+    Ok(())
+}
+
+/// `cass focus`: set, clear, or show the workspace whose session files get
+/// soft real-time priority in watch mode. Resolves an explicit argument the
+/// same way `cass distill` does (full path or unique trailing fragment, with
+/// a fallback to any directory that exists on disk); `--auto` picks the most
+/// recently active workspace from the index. The result is just a control
+/// file — the watch daemon picks it up within one poll interval.
+fn run_focus(
+    workspace: Option<&str>,
+    auto: bool,
+    clear: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+
+    if clear {
+        crate::focus::write_focus(&data_dir, None, false).map_err(|e| CliError {
+            code: 1,
+            kind: CliErrorKind::FileWrite.kind_str(),
+            message: format!("Failed to write focus control file: {e}"),
+            hint: None,
+            retryable: true,
+        })?;
+        if let Some(fmt) = output_format {
+            return output_structured_value(
+                serde_json::json!({
+                    "schema_version": 1,
+                    "success": true,
+                    "focused_workspace": serde_json::Value::Null,
+                }),
+                fmt,
+            );
+        }
+        println!("Focus cleared. All workspaces share the normal watch schedule.");
+        return Ok(());
+    }
+
+    let resolved: Option<String> = if auto {
+        let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+        let params: [ParamValue; 0] = [];
+        let recent: Vec<String> = franken_query_map_collect_retry(
+            &conn,
+            "SELECT w.path FROM workspaces w \
+             JOIN conversations c ON c.workspace_id = w.id \
+             GROUP BY w.id \
+             ORDER BY MAX(COALESCE(c.ended_at, c.started_at, 0)) DESC \
+             LIMIT 1",
+            &params,
+            |row: &frankensqlite::Row| row.get_typed::<String>(0),
+        )
+        .map_err(|e| CliError::unknown(format!("Failed to find recent workspace: {e}")))?;
+        let path = recent.into_iter().next().ok_or_else(|| CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: "No indexed workspace to auto-focus".to_string(),
+            hint: Some("Run `cass index` first, or pass a workspace path.".to_string()),
+            retryable: false,
+        })?;
+        Some(path)
+    } else if let Some(workspace) = workspace {
+        let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+        let suffix = format!("%{workspace}");
+        let candidates: Vec<String> = franken_query_map_collect_retry(
+            &conn,
+            "SELECT path FROM workspaces WHERE path = ?1 OR path LIKE ?2 ORDER BY path",
+            &[
+                ParamValue::from(workspace),
+                ParamValue::from(suffix.as_str()),
+            ],
+            |row: &frankensqlite::Row| row.get_typed::<String>(0),
+        )
+        .map_err(|e| {
+            CliError::unknown(format!("Failed to resolve workspace '{workspace}': {e}"))
+        })?;
+        match candidates.as_slice() {
+            [] => {
+                // Not indexed yet is fine for focus: the whole point is to
+                // index a brand-new workspace's sessions first.
+                let literal = Path::new(workspace);
+                if literal.is_dir() {
+                    Some(literal.to_string_lossy().into_owned())
+                } else {
+                    return Err(CliError {
+                        code: 4,
+                        kind: CliErrorKind::NotFound.kind_str(),
+                        message: format!(
+                            "No indexed workspace matches '{workspace}' and it is not a directory"
+                        ),
+                        hint: Some(
+                            "Pass a workspace path as shown in search results, or an existing \
+                             directory."
+                                .to_string(),
+                        ),
+                        retryable: false,
+                    });
+                }
+            }
+            [only] => Some(only.clone()),
+            many => match many.iter().find(|path| path.as_str() == workspace) {
+                Some(exact) => Some(exact.clone()),
+                None => {
+                    return Err(CliError::usage(
+                        format!("Workspace '{workspace}' is ambiguous"),
+                        Some(format!("Matches: {}", many.join(", "))),
+                    ));
+                }
+            },
+        }
+    } else {
+        None
+    };
+
+    match resolved {
+        Some(path) => {
+            crate::focus::write_focus(&data_dir, Some(Path::new(&path)), auto).map_err(|e| {
+                CliError {
+                    code: 1,
+                    kind: CliErrorKind::FileWrite.kind_str(),
+                    message: format!("Failed to write focus control file: {e}"),
+                    hint: None,
+                    retryable: true,
+                }
+            })?;
+            if let Some(fmt) = output_format {
+                return output_structured_value(
+                    serde_json::json!({
+                        "schema_version": 1,
+                        "success": true,
+                        "focused_workspace": path,
+                        "auto_detected": auto,
+                    }),
+                    fmt,
+                );
+            }
+            println!("Focused on {path}.");
+            println!("A running watch daemon prioritizes its sessions within a few seconds.");
+            Ok(())
+        }
+        None => {
+            // No arguments: show the current focus.
+            let control = crate::focus::load_focus(&data_dir);
+            if let Some(fmt) = output_format {
+                return output_structured_value(
+                    serde_json::json!({
+                        "schema_version": 1,
+                        "success": true,
+                        "focused_workspace": control.workspace,
+                        "auto_detected": control.auto_detected,
+                        "set_at_ms": control.set_at_ms,
+                    }),
+                    fmt,
+                );
+            }
+            match control.workspace {
+                Some(path) if control.auto_detected => {
+                    println!("Focused on {path} (auto-detected).");
+                }
+                Some(path) => println!("Focused on {path}."),
+                None => {
+                    println!("No focus set. Use `cass focus <workspace>` or `cass focus --auto`.")
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Resolve a workspace argument (a full path or a unique trailing fragment)
+/// to its `(id, path)` row. Shared by the corpus-analysis commands
+/// (`ngrams`, `corrections`); errors mirror search's not-found/ambiguous
+/// vocabulary.
+fn resolve_workspace_scope(
+    conn: &frankensqlite::Connection,
+    workspace: &str,
+) -> CliResult<(i64, String)> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let suffix = format!("%{workspace}");
+    let candidates: Vec<(i64, String)> = franken_query_map_collect_retry(
+        conn,
+        "SELECT id, path FROM workspaces WHERE path = ?1 OR path LIKE ?2 ORDER BY path",
+        &[
+            ParamValue::from(workspace),
+            ParamValue::from(suffix.as_str()),
+        ],
+        |row: &frankensqlite::Row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+    )
+    .map_err(|e| CliError::unknown(format!("Failed to resolve workspace '{workspace}': {e}")))?;
+    match candidates.as_slice() {
+        [] => Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("No indexed workspace matches '{workspace}'"),
+            hint: Some(
+                "Pass a workspace path as shown in search results, or a unique \
+                 trailing fragment of one."
+                    .to_string(),
+            ),
+            retryable: false,
+        }),
+        [only] => Ok(only.clone()),
+        many => match many.iter().find(|(_, path)| path == workspace) {
+            Some(exact) => Ok(exact.clone()),
+            None => {
+                let paths: Vec<&str> = many.iter().map(|(_, path)| path.as_str()).collect();
+                Err(CliError::usage(
+                    format!("Workspace '{workspace}' is ambiguous"),
+                    Some(format!("Matches: {}", paths.join(", "))),
+                ))
+            }
+        },
+    }
+}
+
+/// `cass ngrams`: most frequent stopword-filtered 1–3 grams in user prompts
+/// vs agent responses inside a time window. Resolves the workspace the same
+/// way `cass distill` does (when one is given), pulls the windowed messages,
+/// and hands them to the pure counter in [`crate::ngrams`].
+fn run_ngrams(
+    workspace: Option<&str>,
+    since: &str,
+    top: usize,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let window_ms = parse_duration_millis(since)?;
+    let until_ms = chrono::Utc::now().timestamp_millis();
+    let since_ms = until_ms.saturating_sub(window_ms);
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let workspace_scope = match workspace {
+        None => None,
+        Some(workspace) => Some(resolve_workspace_scope(&conn, workspace)?),
+    };
+
+    // Windowed messages, same membership rule as `cass delta`: the
+    // conversation must reach into the window and untimestamped messages
+    // inherit their conversation's membership.
+    let mut sql = "SELECT m.role, m.content FROM messages m \
+                   JOIN conversations c ON m.conversation_id = c.id \
+                   WHERE COALESCE(c.ended_at, c.started_at) >= ?1 \
+                     AND (m.created_at IS NULL OR m.created_at >= ?1)"
+        .to_string();
+    let mut params = vec![ParamValue::from(since_ms)];
+    if let Some((workspace_id, _)) = &workspace_scope {
+        sql.push_str(" AND c.workspace_id = ?2");
+        params.push(ParamValue::from(*workspace_id));
+    }
+    sql.push_str(" ORDER BY c.started_at, c.id, m.idx");
+    let messages: Vec<(String, String)> =
+        franken_query_map_collect_retry(&conn, &sql, &params, |row: &frankensqlite::Row| {
+            Ok((row.get_typed(0)?, row.get_typed(1)?))
+        })
+        .map_err(|e| CliError::unknown(format!("Failed to read windowed messages: {e}")))?;
+
+    let report = crate::ngrams::extract_ngrams(&messages, top);
+    let workspace_label = workspace_scope.map(|(_, path)| path);
+
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("success".to_string(), serde_json::json!(true));
+            obj.insert("since".to_string(), serde_json::json!(since));
+            obj.insert("workspace".to_string(), serde_json::json!(workspace_label));
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    let title = match &workspace_label {
+        Some(path) => format!("CASS Phrase Frequencies ({path}, last {since})"),
+        None => format!("CASS Phrase Frequencies (all workspaces, last {since})"),
+    };
+    println!("{title}");
+    println!("{}", "=".repeat(title.len()));
+    println!();
+    for (heading, messages_counted, rows) in [
+        ("Your prompts", report.user_messages, &report.user),
+        ("Agent responses", report.agent_messages, &report.agent),
+    ] {
+        println!("{heading} ({messages_counted} message(s)):");
+        if rows.is_empty() {
+            println!("  (no phrases in this window)");
+        }
+        for row in rows {
+            println!("  {:>6}  {}", row.count, row.phrase);
+        }
+        println!();
     }
-    html.push_str("</body>\n</html>\n");
-    html
+    Ok(())
 }
 
-fn html_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#39;")
+/// `cass corrections`: roll up the correction turns you issue to agents
+/// ("no, I meant…", "revert that") into per-agent category tallies. Pulls
+/// windowed messages grouped by conversation with the owning agent's name
+/// and hands them to the pure classifier in [`crate::corrections`].
+fn run_corrections(
+    workspace: Option<&str>,
+    since: &str,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ParamValue, RowExt};
+
+    let window_ms = parse_duration_millis(since)?;
+    let until_ms = chrono::Utc::now().timestamp_millis();
+    let since_ms = until_ms.saturating_sub(window_ms);
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let workspace_scope = match workspace {
+        None => None,
+        Some(workspace) => Some(resolve_workspace_scope(&conn, workspace)?),
+    };
+
+    // Same window-membership rule as `cass ngrams`; rows arrive ordered by
+    // conversation so grouping is a single pass over adjacent ids.
+    let mut sql = "SELECT m.conversation_id, COALESCE(a.name, a.slug, 'unknown'), \
+                   m.role, m.content FROM messages m \
+                   JOIN conversations c ON m.conversation_id = c.id \
+                   LEFT JOIN agents a ON c.agent_id = a.id \
+                   WHERE COALESCE(c.ended_at, c.started_at) >= ?1 \
+                     AND (m.created_at IS NULL OR m.created_at >= ?1)"
+        .to_string();
+    let mut params = vec![ParamValue::from(since_ms)];
+    if let Some((workspace_id, _)) = &workspace_scope {
+        sql.push_str(" AND c.workspace_id = ?2");
+        params.push(ParamValue::from(*workspace_id));
+    }
+    sql.push_str(" ORDER BY c.id, m.idx");
+    let rows: Vec<(i64, String, String, String)> =
+        franken_query_map_collect_retry(&conn, &sql, &params, |row: &frankensqlite::Row| {
+            Ok((
+                row.get_typed(0)?,
+                row.get_typed(1)?,
+                row.get_typed(2)?,
+                row.get_typed(3)?,
+            ))
+        })
+        .map_err(|e| CliError::unknown(format!("Failed to read windowed messages: {e}")))?;
+
+    let mut conversations: Vec<crate::corrections::CorrectionConversation> = Vec::new();
+    let mut current_id: Option<i64> = None;
+    for (conversation_id, agent, role, content) in rows {
+        if current_id != Some(conversation_id) {
+            current_id = Some(conversation_id);
+            conversations.push(crate::corrections::CorrectionConversation {
+                agent,
+                messages: Vec::new(),
+            });
+        }
+        if let Some(conversation) = conversations.last_mut() {
+            conversation.messages.push((role, content));
+        }
+    }
+
+    let report = crate::corrections::extract_corrections(&conversations);
+    let workspace_label = workspace_scope.map(|(_, path)| path);
+
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::to_value(&report).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("success".to_string(), serde_json::json!(true));
+            obj.insert("since".to_string(), serde_json::json!(since));
+            obj.insert("workspace".to_string(), serde_json::json!(workspace_label));
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    let title = match &workspace_label {
+        Some(path) => format!("CASS Correction Digest ({path}, last {since})"),
+        None => format!("CASS Correction Digest (all workspaces, last {since})"),
+    };
+    println!("{title}");
+    println!("{}", "=".repeat(title.len()));
+    println!();
+    if report.agents.is_empty() {
+        println!("No conversations in this window.");
+        return Ok(());
+    }
+    for agent in &report.agents {
+        println!(
+            "{} — {} correction(s) across {} follow-up(s):",
+            agent.agent, agent.corrections, agent.user_followups
+        );
+        if agent.categories.is_empty() {
+            println!("  (no corrections detected)");
+        }
+        for category in &agent.categories {
+            println!("  {:>6}  {}", category.count, category.category);
+            for example in &category.examples {
+                println!("          \"{example}\"");
+            }
+        }
+        println!();
+    }
+    Ok(())
 }
 
-/// Show messages around a specific line in a session file
-fn run_expand(
-    path: &Path,
+fn run_verify(
+    data_dir_override: &Option<PathBuf>,
     db_override: Option<PathBuf>,
-    source_id: Option<&str>,
-    line: usize,
-    context: usize,
     output_format: Option<RobotFormat>,
 ) -> CliResult<()> {
-    if let Some(source_id) = source_id {
-        validate_followup_source_id(source_id, "cass expand")?;
-    }
-    let normalized_source_id = canonical_followup_source_id(source_id);
-    let source_id = normalized_source_id.as_deref();
-    if line == 0 {
+    use crate::storage::sqlite::FrankenStorage;
+
+    let db_path = analytics_db_path(data_dir_override, db_override.as_ref());
+    if !db_path.is_file() {
         return Err(CliError {
-            code: 2,
-            kind: CliErrorKind::InvalidLine.kind_str(),
-            message: "Line numbers start at 1, not 0".to_string(),
-            hint: Some("Use -n 1 for the first line".to_string()),
+            code: 5,
+            kind: "verify",
+            message: format!("no canonical database at {}", db_path.display()),
+            hint: Some("Run `cass index` first, or pass --db <path>.".to_string()),
             retryable: false,
         });
     }
+    let storage = FrankenStorage::open(&db_path).map_err(|e| CliError {
+        code: 5,
+        kind: "verify",
+        message: format!("failed to open canonical database: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+    let report = storage
+        .verify_conversation_integrity()
+        .map_err(|e| CliError {
+            code: 5,
+            kind: "verify",
+            message: format!("integrity verification failed: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
 
-    let db_path = db_override.unwrap_or_else(default_db_path);
-    let indexed_view = try_load_indexed_conversation_from_db_with_source(path, &db_path, source_id);
-    let allow_direct_file = followup_source_is_local(source_id) || source_id.is_none();
-
-    let prefer_direct_file = prefers_direct_jsonl_file(path, source_id);
-
-    let raw_lines: Vec<String> = if prefer_direct_file {
-        match read_followup_file_lines(path) {
-            Ok(lines) => lines,
-            Err(err) => {
-                if let Some(view) = indexed_view.as_ref() {
-                    serialize_indexed_view_lines(view)?
-                } else {
-                    return Err(err);
-                }
-            }
-        }
-    } else if let Some(view) = indexed_view.as_ref() {
-        serialize_indexed_view_lines(view)?
-    } else if allow_direct_file && path.exists() {
+    let tampered = !report.mismatched.is_empty();
+    if let Some(fmt) = output_format {
+        // The payload carries the verdict (`success: false` on mismatch);
+        // robot callers read that rather than a second error envelope.
+        return output_structured_value(
+            serde_json::json!({
+                "success": !tampered,
+                "checked": report.checked,
+                "verified": report.verified,
+                "unsealed": report.unsealed,
+                "mismatched": report.mismatched,
+            }),
+            fmt,
+        );
+    }
+    println!(
+        "Checked {} sealed conversation(s): {} verified, {} unsealed.",
+        report.checked, report.verified, report.unsealed
+    );
+    if report.unsealed > 0 {
+        println!("Run `cass index` to seal unsealed conversations.");
+    }
+    for m in &report.mismatched {
+        println!(
+            "TAMPERED: conversation {} ({}) — stored {}… != recomputed {}…",
+            m.conversation_id,
+            m.source_path,
+            &m.expected[..16.min(m.expected.len())],
+            &m.actual[..16.min(m.actual.len())],
+        );
+    }
+    if tampered {
         return Err(CliError {
-            code: 9,
-            kind: CliErrorKind::IndexedSessionRequired.kind_str(),
+            code: 1,
+            kind: "verify",
             message: format!(
-                "Local expand for '{}' requires an indexed conversation or a JSONL session",
-                path.display()
+                "{} conversation(s) changed after indexing",
+                report.mismatched.len()
             ),
             hint: Some(
-                "Run 'cass index --full' first, or expand directly from a JSONL session."
+                "Indexed content no longer matches its integrity seal; inspect the listed \
+                 conversations, then `cass index --full` to re-seal intentional changes."
                     .to_string(),
             ),
             retryable: false,
         });
-    } else {
-        return Err(CliError {
-            code: 3,
-            kind: CliErrorKind::FileNotFound.kind_str(),
-            message: match source_id {
-                Some(source_id) => format!(
-                    "No indexed session found for source '{}' at {}",
-                    source_id,
-                    path.display()
-                ),
-                None => format!("Session file not found: {}", path.display()),
-            },
-            hint: Some(match source_id {
-                Some(_) => "Use the exact source_id from search output or omit --source to prefer the local file/path.".to_string(),
-                None => "Use 'cass search' to find session paths".to_string(),
-            }),
-            retryable: false,
-        });
-    };
+    }
+    Ok(())
+}
 
-    let mut messages: Vec<(usize, serde_json::Value)> = Vec::new();
-    let mut target_msg_idx: Option<usize> = None;
-    let mut current_line: usize = 0;
+/// `cass agents`: every connector compiled into this binary with its cached
+/// detection verdict and evidence, its sources.toml indexing switch, and
+/// index activity (conversation count, last-seen) from the canonical DB when
+/// one exists. Detection goes through the TTL'd on-disk cache so repeated
+/// runs stay cheap; `--refresh` forces fresh probes.
+fn run_agents(
+    refresh: bool,
+    enable: Option<&str>,
+    disable: Option<&str>,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use crate::sources::config::SourcesConfig;
 
-    for raw_line in raw_lines {
-        current_line += 1;
-        if raw_line.trim().is_empty() {
-            continue;
+    let mut config = SourcesConfig::load().map_err(|e| CliError {
+        code: 5,
+        kind: "agents",
+        message: format!("failed to load sources.toml: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+    let mut toggled: Option<String> = None;
+    if let Some(slug) = enable.or(disable) {
+        let enabling = enable.is_some();
+        let changed = if enabling {
+            config.include_agent_in_indexing(slug)
+        } else {
+            config.exclude_agent_from_indexing(slug)
         }
-        if let Ok(msg) = serde_json::from_str::<serde_json::Value>(&raw_line) {
-            if current_line == line {
-                target_msg_idx = Some(messages.len());
-            }
-            messages.push((current_line, msg));
+        .map_err(|e| CliError::usage(format!("cannot toggle '{slug}': {e}"), None))?;
+        if changed {
+            config.save().map_err(|e| CliError {
+                code: 5,
+                kind: "agents",
+                message: format!("failed to save sources.toml: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
         }
+        toggled = Some(format!(
+            "{} {slug}{}",
+            if enabling { "enabled" } else { "disabled" },
+            if changed { "" } else { " (no change)" }
+        ));
     }
 
-    if target_msg_idx.is_none() && line > 0 {
-        for (idx, (msg_line, _)) in messages.iter().enumerate() {
-            if *msg_line >= line {
-                target_msg_idx = Some(idx);
-                break;
-            }
+    // Index activity per agent slug, when a canonical DB exists. A missing
+    // DB just means blank counts — `cass agents` should work pre-index.
+    let mut activity: std::collections::HashMap<String, (i64, Option<i64>)> =
+        std::collections::HashMap::new();
+    let db_path = analytics_db_path(data_dir_override, db_override.as_ref());
+    if db_path.is_file() {
+        let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+        let rows: Vec<(String, i64, Option<i64>)> = franken_query_map_collect_retry(
+            &conn,
+            "SELECT a.slug, COUNT(c.id), MAX(COALESCE(c.ended_at, c.started_at))
+             FROM agents a
+             LEFT JOIN conversations c ON c.agent_id = a.id
+             GROUP BY a.slug",
+            &[],
+            |row: &frankensqlite::Row| {
+                use frankensqlite::compat::RowExt;
+                Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?))
+            },
+        )
+        .map_err(|e| CliError {
+            code: 5,
+            kind: "agents",
+            message: format!("failed to query index activity: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        for (slug, count, last_seen) in rows {
+            activity.insert(slug, (count, last_seen));
         }
-        if target_msg_idx.is_none() && !messages.is_empty() {
-            target_msg_idx = Some(messages.len() - 1);
+    }
+
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let mut registry = crate::connector_registry::ConnectorRegistry::new();
+    let slugs = registry.slugs();
+    let mut agents: Vec<serde_json::Value> = Vec::with_capacity(slugs.len());
+    for slug in slugs {
+        let Some(connector) = registry.get(slug) else {
+            continue;
+        };
+        let detection = if refresh {
+            crate::connector_registry::refresh_connector_detection(&data_dir, slug, connector)
+        } else {
+            crate::connector_registry::cached_connector_detection(&data_dir, slug, connector)
+        };
+        let index_slug = index_agent_slug_for_connector_notice(slug);
+        let (conversations, last_seen_ms) = activity.get(index_slug).copied().unwrap_or((0, None));
+        agents.push(serde_json::json!({
+            "slug": slug,
+            "index_slug": index_slug,
+            "enabled": !config.is_agent_disabled(slug),
+            "detected": detection.detected,
+            "evidence": detection.evidence,
+            "root_paths": detection.root_paths,
+            "conversations": conversations,
+            "last_seen_ms": last_seen_ms,
+        }));
+    }
+
+    if let Some(fmt) = output_format {
+        return output_structured_value(
+            serde_json::json!({
+                "success": true,
+                "toggled": toggled,
+                "agents": agents,
+            }),
+            fmt,
+        );
+    }
+
+    if let Some(msg) = &toggled {
+        println!("{msg}");
+    }
+    for agent in &agents {
+        let slug = agent["slug"].as_str().unwrap_or("?");
+        let detected = agent["detected"].as_bool().unwrap_or(false);
+        let enabled = agent["enabled"].as_bool().unwrap_or(true);
+        let conversations = agent["conversations"].as_i64().unwrap_or(0);
+        let last_seen = agent["last_seen_ms"].as_i64().map_or_else(
+            || "-".to_string(),
+            |ms| {
+                use chrono::TimeZone;
+                chrono::Utc
+                    .timestamp_millis_opt(ms)
+                    .single()
+                    .map_or_else(|| "?".to_string(), |dt| dt.format("%Y-%m-%d").to_string())
+            },
+        );
+        println!(
+            "{slug:<14} {:<10} {:<9} {conversations:>6} conv   last seen {last_seen}",
+            if detected { "detected" } else { "not found" },
+            if enabled { "enabled" } else { "disabled" },
+        );
+        if detected {
+            for evidence in agent["evidence"].as_array().into_iter().flatten() {
+                if let Some(text) = evidence.as_str() {
+                    println!("  evidence: {text}");
+                }
+            }
+            for root in agent["root_paths"].as_array().into_iter().flatten() {
+                if let Some(path) = root.as_str() {
+                    println!("  root: {path}");
+                }
+            }
         }
     }
+    println!("\nToggle indexing with: cass agents --enable <slug> / --disable <slug>");
+    Ok(())
+}
 
-    let target_idx = target_msg_idx.ok_or_else(|| CliError {
-        code: 2,
-        kind: CliErrorKind::LineNotFound.kind_str(),
-        message: format!("No message found at or near line {}", line),
-        hint: Some(format!("File has {} messages", messages.len())),
-        retryable: false,
-    })?;
+/// `cass get-context`: return a bounded message window around an indexed
+/// message id, with role labels and chars/4 token estimates per entry. This is
+/// the agent-facing `get_context` tool — a caller holding one hit can pull
+/// just enough surrounding turns to ground it instead of dumping the whole
+/// conversation into its context window. Unlike `cass expand` it reads the
+/// index only, so it works for remote-source sessions with no local file.
+fn run_get_context(
+    id: i64,
+    before: usize,
+    after: usize,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
 
-    let start = target_idx.saturating_sub(context);
-    let end = (target_idx + context + 1).min(messages.len());
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
 
-    let context_messages: Vec<_> = messages[start..end]
-        .iter()
-        .enumerate()
-        .map(|(i, (line_num, msg))| {
-            let is_target = start + i == target_idx;
-            (line_num, msg, is_target)
-        })
-        .collect();
+    let target: Option<(i64, i64)> = conn
+        .query_row_map(
+            "SELECT conversation_id, idx FROM messages WHERE id = ?",
+            &[ParamValue::from(id)],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?)),
+        )
+        .optional()
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let Some((conversation_id, target_idx)) = target else {
+        return Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("No indexed message with id {id}"),
+            hint: Some(
+                "Message ids come from get-context output itself or the messages table; \
+                 for path+line addressing use 'cass expand'."
+                    .to_string(),
+            ),
+            retryable: false,
+        });
+    };
+
+    let (source_path, title, agent_slug): (String, Option<String>, String) = conn
+        .query_row_map(
+            "SELECT c.source_path, c.title, COALESCE(a.slug, 'unknown')
+             FROM conversations c
+             LEFT JOIN agents a ON c.agent_id = a.id
+             WHERE c.id = ?",
+            &[ParamValue::from(conversation_id)],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    record_conversation_view_best_effort(
+        &analytics_db_path(data_dir_override, db_override.as_ref()),
+        &source_path,
+    );
+
+    let window_start = target_idx.saturating_sub(before as i64);
+    let window_end = target_idx.saturating_add(after as i64);
+    let rows: Vec<(i64, i64, String, Option<String>, Option<i64>, String)> = conn
+        .query_map_collect(
+            "SELECT id, idx, role, author, created_at, content
+             FROM messages
+             WHERE conversation_id = ? AND idx BETWEEN ? AND ?
+             ORDER BY idx",
+            &[
+                ParamValue::from(conversation_id),
+                ParamValue::from(window_start),
+                ParamValue::from(window_end),
+            ],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                    r.get_typed(4)?,
+                    r.get_typed(5)?,
+                ))
+            },
+        )
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
 
     let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
         if matches!(fmt, RobotFormat::Sessions) {
@@ -92595,58 +100476,330 @@ fn run_expand(
         }
     });
 
+    crate::audit::record_query(
+        &data_dir_override.clone().unwrap_or_else(default_data_dir),
+        "get-context",
+        None,
+        Some(rows.len() as i64),
+        structured_format.is_some(),
+    );
+
     if let Some(fmt) = structured_format {
-        let output: Vec<serde_json::Value> = context_messages
+        let messages: Vec<serde_json::Value> = rows
             .iter()
-            .map(|(line_num, msg, is_target)| {
-                let role = extract_role(msg);
-                let content = extract_text_content(msg);
+            .map(|(msg_id, idx, role, author, created_at, content)| {
                 serde_json::json!({
-                    "line": line_num,
+                    "id": msg_id,
+                    "idx": idx,
                     "role": role,
-                    "is_target": is_target,
+                    "author": author,
+                    "created_at": created_at,
+                    "is_target": *msg_id == id,
+                    "content_tokens_est": content.chars().count() / 4,
                     "content": content,
                 })
             })
             .collect();
-        return output_structured_value(serde_json::Value::Array(output), fmt);
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "conversation_id": conversation_id,
+            "source_path": source_path,
+            "title": title,
+            "agent": agent_slug,
+            "target_id": id,
+            "messages": messages,
+        });
+        return output_structured_value(payload, fmt);
     }
 
-    println!("\n📍 Context around line {} in {}\n", line, path.display());
+    println!(
+        "\n📍 Context around message {} in {}\n",
+        id, source_path
+    );
     println!("{}", "─".repeat(60));
-
-    for (line_num, msg, is_target) in context_messages {
-        let role = extract_role(msg);
-        let content = extract_text_content(msg);
+    for (msg_id, _idx, role, _author, _created_at, content) in &rows {
+        let marker = if *msg_id == id { ">>>" } else { "   " };
+        let tokens_est = content.chars().count() / 4;
         let preview: String = content.chars().take(300).collect();
-        let marker = if is_target { ">>>" } else { "   " };
-        let role_icon = match role.as_str() {
-            "user" => "👤",
-            "assistant" => "🤖",
-            _ => "📝",
-        };
-
         println!(
-            "{} L{:>4} {} {}",
+            "{} #{:<6} {} (~{} tokens)",
             marker,
-            line_num,
-            role_icon,
-            role.to_uppercase()
+            msg_id,
+            role.to_uppercase(),
+            tokens_est
         );
         println!("        {}", preview.replace('\n', " "));
-        if content.len() > 300 {
-            println!("        ... ({} more chars)", content.len() - 300);
+        if content.chars().count() > 300 {
+            println!(
+                "        ... ({} more chars)",
+                content.chars().count() - 300
+            );
         }
         println!();
     }
+    println!("{}", "─".repeat(60));
+    println!("Showing {} messages from {}", rows.len(), agent_slug);
+    Ok(())
+}
+
+/// `cass show`: print one indexed message, or with `--raw` the exact bytes of
+/// the source record it was parsed from, located via the `message_provenance`
+/// spans filled in at index time. A message without a span (non-JSONL source,
+/// pre-provenance index, unlocatable record) reports that instead of guessing.
+fn run_show(
+    id: i64,
+    raw: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use frankensqlite::compat::{OptionalExtension, ParamValue, RowExt};
+
+    let conn = open_franken_analytics_db(data_dir_override, db_override.as_ref())?;
+    let row: Option<(
+        i64,
+        i64,
+        String,
+        Option<String>,
+        Option<i64>,
+        String,
+        String,
+    )> = conn
+        .query_row_map(
+            "SELECT m.conversation_id, m.idx, m.role, m.author, m.created_at, m.content,
+                    c.source_path
+             FROM messages m
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE m.id = ?",
+            &[ParamValue::from(id)],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                    r.get_typed(4)?,
+                    r.get_typed(5)?,
+                    r.get_typed(6)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+    let Some((_conversation_id, idx, role, author, created_at, content, source_path)) = row else {
+        return Err(CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("No indexed message with id {id}"),
+            hint: Some(
+                "Message ids come from get-context and robot search output; \
+                 for path+line addressing use 'cass expand'."
+                    .to_string(),
+            ),
+            retryable: false,
+        });
+    };
+
+    // Provenance span, tolerating pre-v25 databases where the table is absent.
+    let span: Option<(i64, i64, i64)> = conn
+        .query_row_map(
+            "SELECT start_byte, end_byte, line_no FROM message_provenance WHERE message_id = ?",
+            &[ParamValue::from(id)],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?)),
+        )
+        .optional()
+        .unwrap_or(None);
+
+    let raw_record = if raw {
+        let Some((start_byte, end_byte, _)) = span else {
+            return Err(CliError {
+                code: 4,
+                kind: CliErrorKind::NotFound.kind_str(),
+                message: format!("No source provenance recorded for message {id}"),
+                hint: Some(
+                    "Provenance is filled in by `cass index` for JSONL-backed sessions; \
+                     re-run indexing, or drop --raw to see the indexed content."
+                        .to_string(),
+                ),
+                retryable: false,
+            });
+        };
+        let bytes = std::fs::read(&source_path).map_err(|e| CliError {
+            code: 4,
+            kind: CliErrorKind::NotFound.kind_str(),
+            message: format!("Failed to read source file {source_path}: {e}"),
+            hint: Some("The session file may have been moved or deleted.".to_string()),
+            retryable: false,
+        })?;
+        let (start, end) = (start_byte as usize, (end_byte as usize).min(bytes.len()));
+        if start > end {
+            return Err(CliError::unknown(format!(
+                "Stale provenance span {start_byte}..{end_byte} for {source_path}"
+            )));
+        }
+        Some(String::from_utf8_lossy(&bytes[start..end]).into_owned())
+    } else {
+        None
+    };
+
+    if let Some(fmt) = output_format {
+        let mut payload = serde_json::json!({
+            "id": id,
+            "idx": idx,
+            "role": role,
+            "author": author,
+            "created_at": created_at,
+            "source_path": source_path,
+            "content": content,
+        });
+        if let Some((start_byte, end_byte, line_no)) = span {
+            payload["provenance"] = serde_json::json!({
+                "start_byte": start_byte,
+                "end_byte": end_byte,
+                "line_no": line_no,
+            });
+        }
+        if let Some(record) = &raw_record {
+            payload["raw_record"] = serde_json::json!(record);
+        }
+        return output_structured_value(payload, fmt);
+    }
 
+    if let Some(record) = raw_record {
+        println!("{record}");
+        return Ok(());
+    }
+    println!("Message #{id} ({role}) in {source_path}");
+    if let Some((_, _, line_no)) = span {
+        println!("Source record: line {line_no}");
+    }
     println!("{}", "─".repeat(60));
+    println!("{content}");
+    Ok(())
+}
+
+/// `cass audit`: inspect (and prune) the trail of queries other tools have
+/// run against the index. Entries are written by the robot-mode query paths
+/// (see `crate::audit`); this command only reads the `audit.db` sidecar.
+fn run_audit(
+    limit: usize,
+    command: Option<&str>,
+    prune: bool,
+    retention_days: Option<i64>,
+    data_dir_override: &Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let db_path = crate::audit::audit_db_path(&data_dir);
+
+    let structured_format = output_format.or_else(robot_format_from_env).map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if !db_path.exists() {
+        if let Some(fmt) = structured_format {
+            let payload = serde_json::json!({
+                "schema_version": 1,
+                "total": 0,
+                "entries": [],
+            });
+            return output_structured_value(payload, fmt);
+        }
+        println!(
+            "No audit log yet. Robot-mode queries are recorded automatically; \
+             set CASS_AUDIT_LOG=1 to also record interactive ones."
+        );
+        return Ok(());
+    }
+
+    let log = crate::audit::AuditLog::open(&db_path).map_err(|e| CliError {
+        code: 1,
+        kind: CliErrorKind::DbOpen.kind_str(),
+        message: format!("failed to open audit log: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    if prune {
+        let days = retention_days.unwrap_or_else(crate::audit::configured_retention_days);
+        let removed = log.prune(days).map_err(|e| CliError {
+            code: 1,
+            kind: CliErrorKind::DbQuery.kind_str(),
+            message: format!("failed to prune audit log: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        if let Some(fmt) = structured_format {
+            let payload = serde_json::json!({
+                "schema_version": 1,
+                "pruned": removed,
+                "retention_days": days,
+            });
+            return output_structured_value(payload, fmt);
+        }
+        println!("Pruned {removed} audit entries older than {days} days");
+        return Ok(());
+    }
+
+    let entries = log.recent(limit, command).map_err(|e| CliError {
+        code: 1,
+        kind: CliErrorKind::DbQuery.kind_str(),
+        message: format!("failed to read audit log: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "total": entries.len(),
+            "entries": entries,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if entries.is_empty() {
+        println!("No audit entries recorded yet.");
+        return Ok(());
+    }
+
+    let format_ts = |ts: i64| -> String {
+        use chrono::TimeZone;
+        chrono::Utc
+            .timestamp_millis_opt(ts)
+            .single()
+            .map_or_else(|| "?".to_string(), |dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+    };
+
+    println!("\n🔍 Audit Log ({} entries)\n", entries.len());
     println!(
-        "Showing messages {} to {} of {} total",
-        start + 1,
-        end,
-        messages.len()
+        "{:<20} {:<14} {:<12} {:>7}  {}",
+        "Timestamp", "Actor", "Command", "Results", "Query"
     );
+    println!("{}", "─".repeat(80));
+    for entry in &entries {
+        let query: String = entry
+            .query
+            .as_deref()
+            .unwrap_or("-")
+            .chars()
+            .take(40)
+            .collect();
+        println!(
+            "{:<20} {:<14} {:<12} {:>7}  {}",
+            format_ts(entry.ts),
+            entry.actor.chars().take(14).collect::<String>(),
+            entry.command,
+            entry
+                .result_count
+                .map_or_else(|| "-".to_string(), |n| n.to_string()),
+            query.replace('\n', " ")
+        );
+    }
     Ok(())
 }
 
@@ -92852,6 +101005,7 @@ fn smart_truncate(s: &str, max_len: usize) -> String {
 fn run_timeline(
     since: Option<&str>,
     until: Option<&str>,
+    utc: bool,
     today: bool,
     agents: &[String],
     data_dir: &Option<PathBuf>,
@@ -92884,12 +101038,18 @@ fn run_timeline(
         };
         (local_start.timestamp_millis(), now.timestamp_millis())
     } else {
-        let start = since
-            .and_then(parse_datetime_flexible)
-            .unwrap_or_else(|| (now - chrono::Duration::days(7)).timestamp_millis());
-        let end = until
-            .and_then(parse_datetime_flexible)
-            .unwrap_or_else(|| now.timestamp_millis());
+        // An explicit value that fails to parse is a usage error, not a
+        // silent fall-through to the default window.
+        let start = match since {
+            Some(raw) => parse_datetime_flexible(raw, utc)
+                .ok_or_else(|| unparseable_time_error("--since", raw))?,
+            None => (now - chrono::Duration::days(7)).timestamp_millis(),
+        };
+        let end = match until {
+            Some(raw) => parse_datetime_flexible(raw, utc)
+                .ok_or_else(|| unparseable_time_error("--until", raw))?,
+            None => now.timestamp_millis(),
+        };
         (start, end)
     };
 
@@ -93207,6 +101367,724 @@ fn run_timeline(
     Ok(())
 }
 
+/// Render a random sample of recently indexed conversations per agent so a
+/// human can eyeball parsing quality (garbled roles, missing messages)
+/// after upgrading cass or the agent apps.
+fn run_sample(
+    per_agent: usize,
+    pool: usize,
+    agents: &[String],
+    format: SampleFormat,
+    seed: Option<u64>,
+    data_dir: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+) -> CliResult<()> {
+    let conn = open_franken_analytics_db(data_dir, db_override.as_ref())?;
+    let rendered = render_conversation_sample(&conn, per_agent, pool, agents, format, seed)?;
+    print!("{rendered}");
+    Ok(())
+}
+
+/// Build the sample report. Split from [`run_sample`] so tests can assert on
+/// the rendered output with a seeded sampler.
+fn render_conversation_sample(
+    conn: &frankensqlite::Connection,
+    per_agent: usize,
+    pool: usize,
+    agents: &[String],
+    format: SampleFormat,
+    seed: Option<u64>,
+) -> CliResult<String> {
+    use frankensqlite::compat::{OptionalExtension, ParamValue, RowExt};
+    use rand::SeedableRng;
+    use rand::seq::SliceRandom;
+
+    if per_agent == 0 {
+        return Err(CliError::usage(
+            "--per-agent must be at least 1",
+            Some("Use --per-agent 5 to sample five conversations per agent.".to_string()),
+        ));
+    }
+    if pool < per_agent {
+        return Err(CliError::usage(
+            "--pool must be at least as large as --per-agent",
+            Some(
+                "Each agent's sample is drawn from its --pool most recently active conversations."
+                    .to_string(),
+            ),
+        ));
+    }
+
+    // Same LEFT JOIN + COALESCE shape as timeline/agents so legacy
+    // conversations with NULL agent_id sample under 'unknown' instead of
+    // silently dropping out.
+    let mut slugs: Vec<String> = franken_query_map_collect_retry(
+        conn,
+        "SELECT COALESCE(a.slug, 'unknown') AS slug, COUNT(c.id) AS n
+         FROM conversations c
+         LEFT JOIN agents a ON c.agent_id = a.id
+         GROUP BY slug
+         ORDER BY n DESC, slug",
+        &[],
+        |row: &frankensqlite::Row| Ok(row.get_typed(0)?),
+    )
+    .map_err(|e| CliError {
+        code: 5,
+        kind: "sample",
+        message: format!("failed to query agents: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    if !agents.is_empty() {
+        let wanted: std::collections::HashSet<&str> = agents.iter().map(String::as_str).collect();
+        slugs.retain(|slug| wanted.contains(slug.as_str()));
+        if slugs.is_empty() {
+            return Err(CliError::usage(
+                format!(
+                    "no indexed conversations for agent(s): {}",
+                    agents.join(", ")
+                ),
+                Some("Use 'cass agents' to list agent slugs with indexed activity.".to_string()),
+            ));
+        }
+    }
+
+    let mut rng = match seed {
+        Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+        None => rand::rngs::StdRng::from_os_rng(),
+    };
+
+    let mut sampled_json: Vec<serde_json::Value> = Vec::new();
+    let mut out = String::new();
+    let mut total = 0usize;
+
+    for slug in &slugs {
+        let mut pool_ids: Vec<i64> = franken_query_map_collect_retry(
+            conn,
+            "SELECT c.id
+             FROM conversations c
+             LEFT JOIN agents a ON c.agent_id = a.id
+             WHERE COALESCE(a.slug, 'unknown') = ?1
+             ORDER BY COALESCE(c.ended_at, c.started_at) DESC, c.id DESC
+             LIMIT ?2",
+            &[
+                ParamValue::from(slug.as_str()),
+                ParamValue::from(pool as i64),
+            ],
+            |row: &frankensqlite::Row| Ok(row.get_typed(0)?),
+        )
+        .map_err(|e| CliError {
+            code: 5,
+            kind: "sample",
+            message: format!("failed to query conversations for '{slug}': {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+
+        pool_ids.shuffle(&mut rng);
+        pool_ids.truncate(per_agent);
+        let take = pool_ids.len();
+
+        for (i, convo_id) in pool_ids.into_iter().enumerate() {
+            let header: Option<(Option<String>, String, Option<i64>)> = conn
+                .query_row_map(
+                    "SELECT title, source_path, started_at FROM conversations WHERE id = ?",
+                    &[ParamValue::from(convo_id)],
+                    |r: &frankensqlite::Row| {
+                        Ok((r.get_typed(0)?, r.get_typed(1)?, r.get_typed(2)?))
+                    },
+                )
+                .optional()
+                .map_err(|e| CliError {
+                    code: 5,
+                    kind: "sample",
+                    message: format!("failed to load conversation {convo_id}: {e}"),
+                    hint: None,
+                    retryable: false,
+                })?;
+            let Some((title, source_path, started_at)) = header else {
+                continue;
+            };
+
+            // Raw-message shape matches conversation_view_to_raw_messages so
+            // the export renderers can be reused as-is ("agent" in the DB is
+            // "assistant" in export vocabulary).
+            let messages: Vec<serde_json::Value> = franken_query_map_collect_retry(
+                conn,
+                "SELECT role, author, created_at, content
+                 FROM messages WHERE conversation_id = ?1 ORDER BY idx",
+                &[ParamValue::from(convo_id)],
+                |row: &frankensqlite::Row| {
+                    let role: String = row.get_typed(0)?;
+                    let author: Option<String> = row.get_typed(1)?;
+                    let created_at: Option<i64> = row.get_typed(2)?;
+                    let content: String = row.get_typed(3)?;
+                    let mut msg = serde_json::json!({
+                        "role": if role == "agent" {
+                            "assistant".to_string()
+                        } else {
+                            role
+                        },
+                        "content": content,
+                    });
+                    if let Some(ts) = created_at {
+                        msg["timestamp"] = serde_json::json!(ts);
+                    }
+                    if let Some(author) = author {
+                        msg["author"] = serde_json::json!(author);
+                    }
+                    Ok(msg)
+                },
+            )
+            .map_err(|e| CliError {
+                code: 5,
+                kind: "sample",
+                message: format!("failed to load messages for conversation {convo_id}: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
+
+            total += 1;
+            match format {
+                SampleFormat::Json => sampled_json.push(serde_json::json!({
+                    "agent": slug,
+                    "conversation_id": convo_id,
+                    "title": title,
+                    "source_path": source_path,
+                    "started_at": started_at,
+                    "message_count": messages.len(),
+                    "messages": messages,
+                })),
+                SampleFormat::Markdown => {
+                    out.push_str(&format!(
+                        "**{slug} · sample {}/{take} · {} messages · {source_path}**\n\n",
+                        i + 1,
+                        messages.len(),
+                    ));
+                    out.push_str(&format_as_markdown(&messages, &title, started_at, false));
+                    out.push('\n');
+                }
+                SampleFormat::Text => {
+                    out.push_str(&format!(
+                        "===== {slug} sample {}/{take}: {source_path} ({} messages) =====\n\n",
+                        i + 1,
+                        messages.len(),
+                    ));
+                    out.push_str(&format_as_text(&messages, false));
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    if matches!(format, SampleFormat::Json) {
+        let value = serde_json::json!({
+            "success": true,
+            "per_agent": per_agent,
+            "pool": pool,
+            "seed": seed,
+            "sampled": sampled_json,
+        });
+        let mut rendered = serde_json::to_string_pretty(&value).map_err(|e| CliError {
+            code: 5,
+            kind: "sample",
+            message: format!("failed to serialize sample: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        rendered.push('\n');
+        return Ok(rendered);
+    }
+
+    if total == 0 {
+        return Ok("No indexed conversations to sample. Run 'cass index' first.\n".to_string());
+    }
+    Ok(out)
+}
+
+fn scan_error(message: String) -> CliError {
+    CliError {
+        code: 5,
+        kind: "scan",
+        message,
+        hint: None,
+        retryable: false,
+    }
+}
+
+/// Run `cass scan`: parse an ad-hoc directory of session files into an
+/// ephemeral in-memory index and optionally search it. The persistent
+/// database is never opened — the input is typically a colleague's exported
+/// logs or CI artifacts that must not pollute this machine's archive.
+fn run_scan(
+    path: &Path,
+    query: Option<&str>,
+    connectors: &[String],
+    limit: usize,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    let root = path.canonicalize().map_err(|e| {
+        CliError::usage(
+            format!("cannot scan {}: {e}", path.display()),
+            Some("pass a readable directory (or file) of session files".to_string()),
+        )
+    })?;
+
+    let factories = crate::indexer::get_connector_factories();
+    if let Some(unknown) = connectors
+        .iter()
+        .find(|wanted| !factories.iter().any(|(name, _)| name == wanted))
+    {
+        let known = factories
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(CliError::usage(
+            format!("unknown connector '{unknown}'"),
+            Some(format!("known connectors: {known}")),
+        ));
+    }
+
+    let storage = crate::storage::sqlite::FrankenStorage::open_in_memory()
+        .map_err(|e| scan_error(format!("failed to open in-memory index: {e}")))?;
+
+    // Every connector gets a shot at the directory; formats are recognized
+    // by each parser, so connectors that find nothing simply contribute
+    // nothing. Scan errors are expected here (the directory is arbitrary)
+    // and only demoted to debug logging.
+    let mut parsed: Vec<(String, usize)> = Vec::new();
+    let mut total_messages = 0usize;
+    let mut agent_ids: HashMap<String, i64> = HashMap::new();
+    let mut workspace_ids: HashMap<PathBuf, i64> = HashMap::new();
+    for (name, factory) in factories {
+        if !connectors.is_empty() && !connectors.iter().any(|wanted| wanted == name) {
+            continue;
+        }
+        let connector = factory();
+        let ctx = crate::connectors::ScanContext::with_roots(
+            root.clone(),
+            vec![crate::connectors::ScanRoot::local(root.clone())],
+            None,
+        );
+        let convs = match connector.scan(&ctx) {
+            Ok(convs) => convs,
+            Err(err) => {
+                tracing::debug!(connector = name, error = %err, "ad-hoc scan failed");
+                continue;
+            }
+        };
+        if convs.is_empty() {
+            continue;
+        }
+        let mut inserted = 0usize;
+        for conv in &convs {
+            let internal = crate::indexer::persist::map_to_internal(conv);
+            let agent_id = match agent_ids.get(&internal.agent_slug) {
+                Some(id) => *id,
+                None => {
+                    let agent = crate::model::types::Agent {
+                        id: None,
+                        slug: internal.agent_slug.clone(),
+                        name: internal.agent_slug.clone(),
+                        version: None,
+                        kind: crate::model::types::AgentKind::Cli,
+                    };
+                    let id = storage
+                        .ensure_agent(&agent)
+                        .map_err(|e| scan_error(format!("failed to record agent: {e}")))?;
+                    agent_ids.insert(internal.agent_slug.clone(), id);
+                    id
+                }
+            };
+            let workspace_id = match &internal.workspace {
+                Some(ws) => Some(match workspace_ids.get(ws) {
+                    Some(id) => *id,
+                    None => {
+                        let id = storage
+                            .ensure_workspace(ws, None)
+                            .map_err(|e| scan_error(format!("failed to record workspace: {e}")))?;
+                        workspace_ids.insert(ws.clone(), id);
+                        id
+                    }
+                }),
+                None => None,
+            };
+            storage
+                .insert_conversation_tree(agent_id, workspace_id, &internal)
+                .map_err(|e| scan_error(format!("failed to index conversation: {e}")))?;
+            inserted += 1;
+            total_messages += internal.messages.len();
+        }
+        parsed.push((name.to_string(), inserted));
+    }
+
+    let hits: Option<Vec<crate::storage::sqlite::EphemeralSearchHit>> = match query {
+        Some(raw) => {
+            let match_expr = crate::search::query::transpile_to_fts5(raw)
+                .filter(|expr| !expr.trim().is_empty())
+                .ok_or_else(|| {
+                    CliError::usage(
+                        format!("cannot interpret query '{raw}'"),
+                        Some("use words, quoted phrases, and AND/OR/NOT".to_string()),
+                    )
+                })?;
+            storage
+                .rebuild_fts_via_frankensqlite()
+                .map_err(|e| scan_error(format!("failed to build ephemeral index: {e}")))?;
+            Some(
+                storage
+                    .search_messages_fts(&match_expr, limit.max(1))
+                    .map_err(|e| scan_error(format!("query failed: {e}")))?,
+            )
+        }
+        None => None,
+    };
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "success": true,
+            "path": root.display().to_string(),
+            "parsed": parsed
+                .iter()
+                .map(|(connector, conversations)| {
+                    serde_json::json!({"connector": connector, "conversations": conversations})
+                })
+                .collect::<Vec<_>>(),
+            "conversations": parsed.iter().map(|(_, n)| n).sum::<usize>(),
+            "messages": total_messages,
+            "query": query,
+            "results": hits,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if parsed.is_empty() {
+        println!(
+            "No session files recognized under {} ({} connectors tried).",
+            root.display(),
+            if connectors.is_empty() {
+                "all".to_string()
+            } else {
+                connectors.len().to_string()
+            },
+        );
+        return Ok(());
+    }
+    println!("Parsed from {}:", root.display());
+    for (connector, conversations) in &parsed {
+        println!("  {connector}: {conversations} conversation(s)");
+    }
+    println!("  {total_messages} message(s) total (ephemeral; nothing written to disk)");
+    if let Some(hits) = hits {
+        println!();
+        if hits.is_empty() {
+            println!("No matches.");
+        } else {
+            for (i, hit) in hits.iter().enumerate() {
+                let title = hit.title.as_deref().unwrap_or("(untitled)");
+                println!(
+                    "{}. [{}] {} — {} (message {})",
+                    i + 1,
+                    hit.agent,
+                    title,
+                    hit.source_path,
+                    hit.message_idx,
+                );
+                let one_line = hit.content.split_whitespace().collect::<Vec<_>>().join(" ");
+                let snippet: String = one_line.chars().take(200).collect();
+                println!("   {snippet}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bench_error(message: String) -> CliError {
+    CliError {
+        code: 5,
+        kind: "bench",
+        message,
+        hint: None,
+        retryable: false,
+    }
+}
+
+/// Run `cass bench ranking`: index the built-in synthetic fixtures into an
+/// ephemeral in-memory database, run every judged query through the same
+/// FTS transpiler and match pipeline as real searches, and score the
+/// returned orderings with NDCG@k and MRR against the maintained relevance
+/// judgments (see `ranking_bench`).
+fn run_bench_ranking(k: usize, output_format: Option<RobotFormat>) -> CliResult<()> {
+    let k = k.max(1);
+    let storage = crate::storage::sqlite::FrankenStorage::open_in_memory()
+        .map_err(|e| bench_error(format!("failed to open in-memory index: {e}")))?;
+
+    let mut agent_ids: HashMap<&str, i64> = HashMap::new();
+    let base_ts = 1_700_000_000_000i64;
+    for fixture in crate::ranking_bench::FIXTURES {
+        let agent_id = match agent_ids.get(fixture.agent) {
+            Some(id) => *id,
+            None => {
+                let agent = crate::model::types::Agent {
+                    id: None,
+                    slug: fixture.agent.to_string(),
+                    name: fixture.agent.to_string(),
+                    version: None,
+                    kind: crate::model::types::AgentKind::Cli,
+                };
+                let id = storage
+                    .ensure_agent(&agent)
+                    .map_err(|e| bench_error(format!("failed to record agent: {e}")))?;
+                agent_ids.insert(fixture.agent, id);
+                id
+            }
+        };
+        let messages = fixture
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(idx, (role, content))| crate::model::types::Message {
+                id: None,
+                idx: idx as i64,
+                role: if *role == "user" {
+                    crate::model::types::MessageRole::User
+                } else {
+                    crate::model::types::MessageRole::Agent
+                },
+                author: None,
+                created_at: Some(base_ts + idx as i64),
+                content: (*content).to_string(),
+                extra_json: serde_json::Value::Null,
+                snippets: Vec::new(),
+            })
+            .collect();
+        let conversation = crate::model::types::Conversation {
+            id: None,
+            agent_slug: fixture.agent.to_string(),
+            workspace: None,
+            external_id: Some(format!("bench-{}", fixture.slug)),
+            title: Some(fixture.title.to_string()),
+            source_path: PathBuf::from(crate::ranking_bench::fixture_source_path(fixture.slug)),
+            started_at: Some(base_ts),
+            ended_at: Some(base_ts + 10_000),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages,
+            source_id: crate::sources::provenance::LOCAL_SOURCE_ID.to_string(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .map_err(|e| bench_error(format!("failed to index fixture: {e}")))?;
+    }
+    storage
+        .rebuild_fts_via_frankensqlite()
+        .map_err(|e| bench_error(format!("failed to build ephemeral index: {e}")))?;
+
+    let mut scores: Vec<crate::ranking_bench::QueryScore> = Vec::new();
+    for judged in crate::ranking_bench::JUDGED_QUERIES {
+        let match_expr = crate::search::query::transpile_to_fts5(judged.query)
+            .filter(|expr| !expr.trim().is_empty())
+            .ok_or_else(|| {
+                bench_error(format!("judged query '{}' did not transpile", judged.query))
+            })?;
+        let hits = storage
+            .search_messages_fts(&match_expr, k.saturating_mul(4))
+            .map_err(|e| bench_error(format!("query '{}' failed: {e}", judged.query)))?;
+        // Hits are per-message; the judgments are per-conversation, so
+        // collapse to the first (best-ranked) hit of each fixture.
+        let mut ranked: Vec<String> = Vec::new();
+        for hit in &hits {
+            if let Some(slug) = crate::ranking_bench::slug_from_source_path(&hit.source_path)
+                && !ranked.iter().any(|seen| seen == slug)
+            {
+                ranked.push(slug.to_string());
+            }
+        }
+        let ranked_refs: Vec<&str> = ranked.iter().map(String::as_str).collect();
+        scores.push(crate::ranking_bench::QueryScore {
+            query: judged.query.to_string(),
+            ndcg: crate::ranking_bench::ndcg_at(&ranked_refs, judged, k),
+            reciprocal_rank: crate::ranking_bench::reciprocal_rank(&ranked_refs, judged),
+            ranked,
+        });
+    }
+
+    let mean = |f: fn(&crate::ranking_bench::QueryScore) -> f64| -> f64 {
+        if scores.is_empty() {
+            0.0
+        } else {
+            scores.iter().map(f).sum::<f64>() / scores.len() as f64
+        }
+    };
+    let mean_ndcg = mean(|s| s.ndcg);
+    let mrr = mean(|s| s.reciprocal_rank);
+
+    if let Some(fmt) = output_format {
+        let payload = serde_json::json!({
+            "success": true,
+            "k": k,
+            "queries": scores.len(),
+            "fixtures": crate::ranking_bench::FIXTURES.len(),
+            "mean_ndcg": mean_ndcg,
+            "mrr": mrr,
+            "per_query": scores,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!(
+        "Ranking benchmark: {} judged queries over {} fixtures (NDCG@{k})",
+        scores.len(),
+        crate::ranking_bench::FIXTURES.len(),
+    );
+    println!();
+    for score in &scores {
+        println!(
+            "  ndcg {:.3}  rr {:.3}  {}",
+            score.ndcg, score.reciprocal_rank, score.query
+        );
+        if score.ndcg < 1.0 {
+            println!("             returned: {}", score.ranked.join(", "));
+        }
+    }
+    println!();
+    println!("  mean NDCG@{k}: {mean_ndcg:.3}");
+    println!("  MRR:          {mrr:.3}");
+    println!();
+    println!("Compare these numbers before and after a ranking change; update");
+    println!("the judgments in ranking_bench.rs (with rationale) only when a");
+    println!("reordering is intended.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod conversation_sample_tests {
+    use super::*;
+    use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+    use crate::storage::sqlite::SqliteStorage;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn insert_sample_conversation(storage: &SqliteStorage, slug: &str, n: usize, base_ts: i64) {
+        let agent = Agent {
+            id: None,
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).expect("ensure agent");
+        let conversation = Conversation {
+            id: None,
+            agent_slug: slug.to_string(),
+            workspace: Some(PathBuf::from("/tmp/ws")),
+            external_id: Some(format!("{slug}-{n}")),
+            title: Some(format!("{slug} session {n}")),
+            source_path: PathBuf::from(format!("/tmp/{slug}/session-{n}.jsonl")),
+            started_at: Some(base_ts),
+            ended_at: Some(base_ts + 10_000),
+            approx_tokens: None,
+            metadata_json: serde_json::json!({}),
+            messages: vec![
+                Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: Some("me".to_string()),
+                    created_at: Some(base_ts),
+                    content: format!("question {n}"),
+                    extra_json: serde_json::json!({}),
+                    snippets: Vec::new(),
+                },
+                Message {
+                    id: None,
+                    idx: 1,
+                    role: MessageRole::Agent,
+                    author: None,
+                    created_at: Some(base_ts + 1_000),
+                    content: format!("answer {n}"),
+                    extra_json: serde_json::json!({}),
+                    snippets: Vec::new(),
+                },
+            ],
+            source_id: "local".to_string(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .expect("insert conversation");
+    }
+
+    #[test]
+    fn seeded_sample_is_deterministic_and_caps_per_agent() {
+        let tmp = TempDir::new().expect("temp dir");
+        let db_path = tmp.path().join("agent_search.db");
+        let storage = SqliteStorage::open(&db_path).expect("open sqlite");
+        for n in 0..3 {
+            insert_sample_conversation(&storage, "claude_code", n, 1_733_000_000_000 + n as i64);
+        }
+        insert_sample_conversation(&storage, "codex", 0, 1_733_000_100_000);
+        drop(storage);
+
+        let conn = open_franken_analytics_db(&None, Some(&db_path)).expect("open conn");
+        let first = render_conversation_sample(&conn, 2, 50, &[], SampleFormat::Markdown, Some(7))
+            .expect("render sample");
+        let second = render_conversation_sample(&conn, 2, 50, &[], SampleFormat::Markdown, Some(7))
+            .expect("render sample again");
+        assert_eq!(first, second, "seeded sampling must be reproducible");
+        assert_eq!(
+            first.matches("\u{b7} sample ").count(),
+            3,
+            "2 of 3 claude_code conversations plus the single codex one: {first}"
+        );
+        assert!(
+            first.contains("## 👤 User") && first.contains("## 🤖 Assistant"),
+            "db 'agent' role must render under the assistant header: {first}"
+        );
+    }
+
+    #[test]
+    fn sample_validates_flags_and_honors_agent_filter_in_json() {
+        let tmp = TempDir::new().expect("temp dir");
+        let db_path = tmp.path().join("agent_search.db");
+        let storage = SqliteStorage::open(&db_path).expect("open sqlite");
+        insert_sample_conversation(&storage, "codex", 0, 1_733_000_000_000);
+        drop(storage);
+
+        let conn = open_franken_analytics_db(&None, Some(&db_path)).expect("open conn");
+        let err = render_conversation_sample(&conn, 0, 50, &[], SampleFormat::Markdown, None)
+            .expect_err("zero per-agent is a usage error");
+        assert_eq!(err.code, 2);
+        let err = render_conversation_sample(
+            &conn,
+            1,
+            50,
+            &["nope".to_string()],
+            SampleFormat::Markdown,
+            None,
+        )
+        .expect_err("unknown agent filter is a usage error");
+        assert_eq!(err.code, 2);
+
+        let rendered = render_conversation_sample(
+            &conn,
+            1,
+            50,
+            &["codex".to_string()],
+            SampleFormat::Json,
+            Some(1),
+        )
+        .expect("render json sample");
+        let value: serde_json::Value = serde_json::from_str(&rendered).expect("valid json");
+        assert_eq!(value["sampled"].as_array().map(Vec::len), Some(1));
+        assert_eq!(value["sampled"][0]["agent"], "codex");
+        assert_eq!(value["sampled"][0]["message_count"], 2);
+    }
+}
+
 /// Handle sources subcommands (P5.x)
 fn run_sources_command(cmd: SourcesCommand, cli: &Cli) -> CliResult<()> {
     match cmd {
@@ -95124,6 +104002,10 @@ fn run_sources_sync(
             false,          // watch
             None,           // watch_once
             30,             // watch_interval (default)
+            false,          // pause_on_battery
+            false,          // nice
+            false,          // no_nice
+            None,           // max_cpu
             Some(data_dir), // data_dir
             false,          // semantic
             false,          // build_hnsw
@@ -95275,6 +104157,10 @@ fn run_sources_reingest(
         false,                  // watch
         None,                   // watch_once
         30,                     // watch_interval (default)
+        false,                  // pause_on_battery
+        false,                  // nice
+        false,                  // no_nice
+        None,                   // max_cpu
         Some(data_dir.clone()), // data_dir (existing mirror root is discovered here)
         false,                  // semantic
         false,                  // build_hnsw
@@ -95568,6 +104454,7 @@ fn run_models_command(cmd: ModelsCommand, cli: &Cli) -> CliResult<()> {
             tier,
             embedder,
             batch_conversations,
+            refresh_stale,
             scheduled,
             data_dir,
             db,
@@ -95578,6 +104465,7 @@ fn run_models_command(cmd: ModelsCommand, cli: &Cli) -> CliResult<()> {
                 &tier,
                 embedder.as_deref(),
                 batch_conversations,
+                refresh_stale,
                 scheduled,
                 data_dir,
                 db.or_else(|| cli.db.clone()),
@@ -95593,6 +104481,107 @@ fn run_models_command(cmd: ModelsCommand, cli: &Cli) -> CliResult<()> {
             let structured_format = resolve_subcommand_structured_format(cli, json);
             run_models_check_update(structured_format, data_dir)
         }
+        ModelsCommand::Usage {
+            limit,
+            data_dir,
+            db,
+            json,
+        } => {
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            run_models_usage(
+                limit,
+                data_dir,
+                db.or_else(|| cli.db.clone()),
+                structured_format,
+            )
+        }
+    }
+}
+
+/// Dispatch the `cass watch` subcommand group: status heartbeat queries and
+/// pause/resume requests for a running `cass index --watch` daemon.
+fn run_watch_command(cmd: WatchCommand, cli: &Cli) -> CliResult<()> {
+    let resolve_data_dir =
+        |data_dir: Option<PathBuf>| data_dir.unwrap_or_else(default_data_dir);
+    match cmd {
+        WatchCommand::Status { data_dir, json } => {
+            let data_dir = resolve_data_dir(data_dir);
+            let structured_format = resolve_subcommand_structured_format(cli, json);
+            let status = crate::watch_control::load_status(&data_dir);
+            let control = crate::watch_control::load_control(&data_dir);
+
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::json!({
+                    "schema_version": 1,
+                    "data_dir": data_dir.display().to_string(),
+                    "requested_pause": control.paused,
+                    "pause_reason": control.reason,
+                    "heartbeat": status,
+                });
+                return output_structured_value(payload, fmt);
+            }
+
+            match status {
+                Some(status) => {
+                    let now = chrono::Utc::now().timestamp_millis();
+                    let age_secs = (now - status.updated_at_ms).max(0) / 1000;
+                    println!("Watch daemon (pid {}): {}", status.pid, status.state);
+                    println!("  Last heartbeat: {age_secs}s ago");
+                    println!("  Cycles completed: {}", status.cycles_completed);
+                    println!(
+                        "  Effective scan interval: {}s",
+                        status.current_interval_secs
+                    );
+                    if status.pause_on_battery {
+                        println!("  Pause-on-battery: enabled");
+                    }
+                    if age_secs > 120 {
+                        println!(
+                            "  Note: heartbeat is stale; the daemon may have stopped."
+                        );
+                    }
+                }
+                None => {
+                    println!(
+                        "No watch heartbeat found in {}. Is `cass index --watch` running?",
+                        data_dir.display()
+                    );
+                }
+            }
+            if control.paused {
+                match control.reason {
+                    Some(reason) => println!("Pause requested: {reason}"),
+                    None => println!("Pause requested."),
+                }
+            }
+            Ok(())
+        }
+        WatchCommand::Pause { reason, data_dir } => {
+            let data_dir = resolve_data_dir(data_dir);
+            crate::watch_control::write_control(&data_dir, true, reason.as_deref()).map_err(
+                |e| CliError {
+                    code: 1,
+                    kind: CliErrorKind::FileWrite.kind_str(),
+                    message: format!("Failed to write watch control file: {e}"),
+                    hint: None,
+                    retryable: true,
+                },
+            )?;
+            println!("Pause requested. The watch daemon picks it up within a few seconds.");
+            Ok(())
+        }
+        WatchCommand::Resume { data_dir } => {
+            let data_dir = resolve_data_dir(data_dir);
+            crate::watch_control::write_control(&data_dir, false, None).map_err(|e| CliError {
+                code: 1,
+                kind: CliErrorKind::FileWrite.kind_str(),
+                message: format!("Failed to write watch control file: {e}"),
+                hint: None,
+                retryable: true,
+            })?;
+            println!("Resume requested.");
+            Ok(())
+        }
     }
 }
 
@@ -96966,10 +105955,12 @@ fn resolve_semantic_index_embedder(raw: &str) -> String {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_models_backfill(
     tier_raw: &str,
     embedder_override: Option<&str>,
     batch_conversations: usize,
+    refresh_stale: bool,
     scheduled: bool,
     data_dir_override: Option<PathBuf>,
     db_override: Option<PathBuf>,
@@ -96981,7 +105972,7 @@ fn run_models_backfill(
     };
     use crate::search::model_download::ModelManifest;
     use crate::search::policy::{CliSemanticOverrides, SemanticPolicy};
-    use crate::search::semantic_manifest::{SemanticManifest, TierKind};
+    use crate::search::semantic_manifest::{SemanticManifest, SemanticShardManifest, TierKind};
     use crate::storage::sqlite::FrankenStorage;
     use colored::Colorize;
 
@@ -97145,6 +106136,49 @@ fn run_models_backfill(
         retryable: embedder_type != "hash",
     })?;
 
+    // --refresh-stale: model-drift management. The invalidation policy
+    // keeps vectors from an older revision of the same embedder queryable
+    // (RebuildInBackground), but nothing drains them on its own, so after a
+    // model upgrade old and new vectors silently coexist. Dropping the stale
+    // artifact, its accelerator, shard records, and the tier checkpoint here
+    // makes the bounded backfill below restart the tier and re-embed it with
+    // the installed model — resumable batch by batch, with the usual
+    // progress reporting.
+    let mut stale_artifacts_dropped = 0usize;
+    let mut stale_shards_dropped = 0usize;
+    if refresh_stale {
+        stale_artifacts_dropped =
+            manifest.refresh_stale_model_artifacts(tier, indexer.embedder_id(), &model_revision);
+        if stale_artifacts_dropped > 0 {
+            manifest.save(&data_dir).map_err(|e| CliError {
+                code: 5,
+                kind: CliErrorKind::SemanticManifest.kind_str(),
+                message: format!("Failed to save semantic manifest after stale refresh: {e}"),
+                hint: Some("Check permissions under the cass data directory".into()),
+                retryable: true,
+            })?;
+        }
+        let mut shard_manifest =
+            SemanticShardManifest::load_or_default(&data_dir).map_err(|e| CliError {
+                code: 5,
+                kind: CliErrorKind::SemanticManifest.kind_str(),
+                message: format!("Failed to load semantic shard manifest: {e}"),
+                hint: Some("Check permissions under the cass data directory".into()),
+                retryable: true,
+            })?;
+        stale_shards_dropped =
+            shard_manifest.purge_stale_model_shards(tier, indexer.embedder_id(), &model_revision);
+        if stale_shards_dropped > 0 {
+            shard_manifest.save(&data_dir).map_err(|e| CliError {
+                code: 5,
+                kind: CliErrorKind::SemanticManifest.kind_str(),
+                message: format!("Failed to save semantic shard manifest after stale refresh: {e}"),
+                hint: Some("Check permissions under the cass data directory".into()),
+                retryable: true,
+            })?;
+        }
+    }
+
     // Sub-fix 1 for cass#257: open a JSONL progress sink whose
     // destination is taken from `CASS_SEMANTIC_PROGRESS_JSONL`. The
     // sink is silent when the env var is unset, so behaviour for
@@ -97220,6 +106254,9 @@ fn run_models_backfill(
                 "batch_conversations_limit": effective_batch_conversations,
                 "requested_batch_conversations_limit": batch_conversations,
                 "scheduler": scheduler_decision,
+                "refresh_stale": refresh_stale,
+                "stale_artifacts_dropped": stale_artifacts_dropped,
+                "stale_shards_dropped": stale_shards_dropped,
                 "embedded_docs": outcome.embedded_docs,
                 "conversations_processed": outcome.conversations_processed,
                 "total_conversations": outcome.total_conversations,
@@ -97238,6 +106275,12 @@ fn run_models_backfill(
         println!("  Status: {}", status);
         println!("  Tier: {}", outcome.tier.as_str());
         println!("  Embedder: {}", outcome.embedder_id);
+        if refresh_stale {
+            println!(
+                "  Stale model vectors dropped: {} artifact(s), {} shard record(s)",
+                stale_artifacts_dropped, stale_shards_dropped
+            );
+        }
         println!("  Embedded docs: {}", outcome.embedded_docs);
         println!(
             "  Conversations: {}/{} ({:.1}%)",
@@ -97435,6 +106478,121 @@ fn run_models_check_update(
     Ok(())
 }
 
+/// Report per-model usage from message authors and refresh the `models`
+/// dimension table (`cass models usage`).
+fn run_models_usage(
+    limit: usize,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    output_format: Option<RobotFormat>,
+) -> CliResult<()> {
+    use colored::Colorize;
+
+    let db_path = db_override.unwrap_or_else(|| {
+        data_dir_override
+            .map(|d| d.join("agent_search.db"))
+            .unwrap_or_else(default_db_path)
+    });
+    if !db_path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: CliErrorKind::MissingDb.kind_str(),
+            message: format!(
+                "Database not found at {}. Run 'cass index --full' first.",
+                db_path.display()
+            ),
+            hint: Some("Run 'cass index --full' to create the database.".into()),
+            retryable: true,
+        });
+    }
+
+    let storage = crate::storage::sqlite::FrankenStorage::open(&db_path).map_err(|e| CliError {
+        code: 9,
+        kind: CliErrorKind::DbOpen.kind_str(),
+        message: format!("Failed to open database at {}: {e}", db_path.display()),
+        hint: None,
+        retryable: true,
+    })?;
+
+    let rows = crate::model_usage::aggregate_model_usage(storage.raw()).map_err(|e| CliError {
+        code: 1,
+        kind: CliErrorKind::DbQuery.kind_str(),
+        message: format!("Model usage aggregation failed: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    // The dimension table is derived data; a refresh failure shouldn't block
+    // the report itself.
+    if let Err(e) = crate::model_usage::refresh_models_dimension(storage.raw(), &rows) {
+        tracing::warn!("failed to refresh models dimension table: {e}");
+    }
+
+    let total: u64 = rows.iter().map(|r| r.message_count).sum();
+    let shown: Vec<_> = rows.iter().take(limit).collect();
+
+    let structured_format = output_format.map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    });
+
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "schema_version": 1,
+            "total_messages": total,
+            "total_models": rows.len(),
+            "models": shown,
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if rows.is_empty() {
+        println!("No model-attributed messages found.");
+        return Ok(());
+    }
+
+    println!(
+        "{} models across {} attributed messages",
+        rows.len().to_string().cyan().bold(),
+        total
+    );
+    let format_day = |ts: i64| -> String {
+        use chrono::TimeZone;
+        chrono::Utc
+            .timestamp_millis_opt(ts)
+            .single()
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    };
+    for row in &shown {
+        let first = row
+            .first_seen
+            .map(format_day)
+            .unwrap_or_else(|| "unknown".to_string());
+        let last = row
+            .last_seen
+            .map(format_day)
+            .unwrap_or_else(|| "unknown".to_string());
+        println!(
+            "  {:<32} {:>6.1}%  {:>7} msgs  avg {:>6.0} chars  {} → {}",
+            row.model.yellow(),
+            row.usage_share * 100.0,
+            row.message_count,
+            row.avg_response_chars,
+            first,
+            last
+        );
+    }
+    if rows.len() > shown.len() {
+        println!("  ... and {} more (use --limit)", rows.len() - shown.len());
+    }
+
+    Ok(())
+}
+
 fn run_agents_command(action: AgentsAction, cli: &Cli) -> CliResult<()> {
     match action {
         AgentsAction::List { json } => {
@@ -98044,10 +107202,13 @@ fn run_mappings_test(source_name: &str, path: &str, agent: Option<&str>) -> CliR
     Ok(())
 }
 
-fn parse_datetime_flexible(s: &str) -> Option<i64> {
-    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+fn parse_datetime_flexible(s: &str, utc: bool) -> Option<i64> {
+    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
-    fn local_from_naive(dt: NaiveDateTime) -> i64 {
+    fn from_naive(dt: NaiveDateTime, utc: bool) -> i64 {
+        if utc {
+            return Utc.from_utc_datetime(&dt).timestamp_millis();
+        }
         match Local.from_local_datetime(&dt) {
             chrono::LocalResult::Single(local) => local.timestamp_millis(),
             chrono::LocalResult::Ambiguous(local, _) => local.timestamp_millis(),
@@ -98055,9 +107216,9 @@ fn parse_datetime_flexible(s: &str) -> Option<i64> {
         }
     }
 
-    fn local_midnight_ts(date: NaiveDate) -> Option<i64> {
+    fn midnight_ts(date: NaiveDate, utc: bool) -> Option<i64> {
         let dt = date.and_hms_opt(0, 0, 0)?;
-        Some(local_from_naive(dt))
+        Some(from_naive(dt, utc))
     }
 
     fn relative_past_days(now: &chrono::DateTime<Local>, days: i64) -> Option<i64> {
@@ -98082,7 +107243,7 @@ fn parse_datetime_flexible(s: &str) -> Option<i64> {
     }
 
     if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d")
-        && let Some(ts) = local_midnight_ts(date)
+        && let Some(ts) = midnight_ts(date, utc)
     {
         return Some(ts);
     }
@@ -98090,12 +107251,20 @@ fn parse_datetime_flexible(s: &str) -> Option<i64> {
     let now = Local::now();
     match s.to_lowercase().as_str() {
         "today" => {
-            let date = now.date_naive();
-            local_midnight_ts(date)
+            let date = if utc {
+                Utc::now().date_naive()
+            } else {
+                now.date_naive()
+            };
+            midnight_ts(date, utc)
         }
         "yesterday" => {
-            let yesterday = (now - chrono::Duration::days(1)).date_naive();
-            local_midnight_ts(yesterday)
+            let today = if utc {
+                Utc::now().date_naive()
+            } else {
+                now.date_naive()
+            };
+            midnight_ts(today - chrono::Duration::days(1), utc)
         }
         _ => {
             // Both `7d` and `-7d` mean "7 days ago": a leading '-' is an
@@ -98112,7 +107281,9 @@ fn parse_datetime_flexible(s: &str) -> Option<i64> {
             {
                 return relative_past_hours(&now, hours);
             }
-            None
+            // Everything else goes through the shared natural-language
+            // parser ("last monday", "nov 3", "-1w", unix timestamps, …).
+            crate::ui::time_parser::parse_time_input_tz(s, utc)
         }
     }
 }
@@ -98123,8 +107294,8 @@ mod timeline_datetime_parse_tests {
 
     #[test]
     fn signed_relative_offsets_use_magnitude() {
-        let positive = parse_datetime_flexible("7d").expect("positive relative days parse");
-        let negative = parse_datetime_flexible("-7d").expect("negative relative days parse");
+        let positive = parse_datetime_flexible("7d", false).expect("positive relative days parse");
+        let negative = parse_datetime_flexible("-7d", false).expect("negative relative days parse");
 
         assert!(
             positive.abs_diff(negative) < 2_000,
@@ -98134,8 +107305,22 @@ mod timeline_datetime_parse_tests {
 
     #[test]
     fn overflowing_relative_offsets_are_rejected_without_panicking() {
-        assert!(parse_datetime_flexible("-9223372036854775808d").is_none());
-        assert!(parse_datetime_flexible("9223372036854775807h").is_none());
+        assert!(parse_datetime_flexible("-9223372036854775808d", false).is_none());
+        assert!(parse_datetime_flexible("9223372036854775807h", false).is_none());
+    }
+
+    #[test]
+    fn natural_language_dates_fall_through_to_the_shared_parser() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let monday = parse_datetime_flexible("last monday", false).expect("weekday parses");
+        assert!(monday < now, "a weekday must resolve into the past");
+
+        // UTC anchoring pins a date-only value exactly.
+        assert_eq!(
+            parse_datetime_flexible("nov 3 2024", true),
+            Some(1_730_592_000_000)
+        );
+        assert!(parse_datetime_flexible("not a date", false).is_none());
     }
 }
 
@@ -0,0 +1,286 @@
+//! Conversation lineage: parent/child relationships between sessions.
+//!
+//! Agent harnesses record how sessions relate — Claude Code sidechains carry
+//! a parent session uuid, Amp threads form trees, and resumed sessions point
+//! back at the conversation they continued. The connectors preserve that
+//! metadata verbatim in `conversations.metadata_json`, but under
+//! harness-specific key names. This module normalizes those keys into a
+//! single parent edge per conversation, builds the resulting forest, and
+//! answers the two questions `cass lineage` asks: who are my ancestors, and
+//! what did I spawn.
+//!
+//! Parent references are external ids (session uuids/thread ids), resolved
+//! against `conversations.external_id`. A reference to a session that was
+//! never indexed simply yields no edge — lineage is best-effort metadata,
+//! never load-bearing.
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::ConnectionExt;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Metadata keys that harnesses use for "the session this one came from".
+/// Checked in order; first non-empty string wins.
+const PARENT_KEYS: &[&str] = &[
+    "parentUuid",
+    "parent_uuid",
+    "parentSessionId",
+    "parent_session_id",
+    "parentThreadId",
+    "parent_thread_id",
+    "resumeSessionId",
+    "resume_session_id",
+    "resumedFrom",
+    "resumed_from",
+    "parentId",
+    "parent_id",
+];
+
+/// One conversation in the lineage graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageNode {
+    /// Conversation row id.
+    pub id: i64,
+    /// Harness session/thread id, when the connector recorded one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Conversation title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Agent slug.
+    pub agent: String,
+    /// Source session file.
+    pub source_path: String,
+    /// Session start (unix millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<i64>,
+    /// Raw parent reference extracted from metadata, before resolution.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_external_id: Option<String>,
+}
+
+/// Forest of conversations linked by resolved parent edges.
+#[derive(Debug, Default)]
+pub struct LineageGraph {
+    nodes: HashMap<i64, LineageNode>,
+    parent: HashMap<i64, i64>,
+    children: HashMap<i64, Vec<i64>>,
+}
+
+impl LineageGraph {
+    /// Build the graph, resolving each node's `parent_external_id` against
+    /// the other nodes' external ids. Unresolvable references are dropped.
+    #[must_use]
+    pub fn build(nodes: Vec<LineageNode>) -> Self {
+        let by_external: HashMap<String, i64> = nodes
+            .iter()
+            .filter_map(|n| n.external_id.clone().map(|ext| (ext, n.id)))
+            .collect();
+
+        let ordering: HashMap<i64, i64> = nodes
+            .iter()
+            .map(|n| (n.id, n.started_at.unwrap_or(i64::MAX)))
+            .collect();
+
+        let mut graph = Self::default();
+        for node in &nodes {
+            if let Some(parent_ext) = node.parent_external_id.as_deref()
+                && let Some(&parent_id) = by_external.get(parent_ext)
+                && parent_id != node.id
+            {
+                graph.parent.insert(node.id, parent_id);
+                graph.children.entry(parent_id).or_default().push(node.id);
+            }
+        }
+        // Siblings in chronological order so descendant listings read as a
+        // timeline of spawned subtasks.
+        for children in graph.children.values_mut() {
+            children.sort_by_key(|id| ordering.get(id).copied().unwrap_or(i64::MAX));
+        }
+        graph.nodes = nodes.into_iter().map(|n| (n.id, n)).collect();
+        graph
+    }
+
+    /// Look up a node.
+    #[must_use]
+    pub fn node(&self, id: i64) -> Option<&LineageNode> {
+        self.nodes.get(&id)
+    }
+
+    /// Chain of ancestors from the immediate parent up to the root.
+    /// Cycle-safe: a malformed self-referential chain terminates.
+    #[must_use]
+    pub fn ancestors(&self, id: i64) -> Vec<i64> {
+        let mut seen = HashSet::from([id]);
+        let mut chain = Vec::new();
+        let mut current = id;
+        while let Some(&parent) = self.parent.get(&current) {
+            if !seen.insert(parent) {
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        chain
+    }
+
+    /// Descendants in depth-first pre-order, each with its depth below the
+    /// starting node (direct children are depth 1). Cycle-safe.
+    #[must_use]
+    pub fn descendants(&self, id: i64) -> Vec<(i64, usize)> {
+        let mut seen = HashSet::from([id]);
+        let mut out = Vec::new();
+        let mut stack: Vec<(i64, usize)> = self
+            .children
+            .get(&id)
+            .map(|c| c.iter().rev().map(|&child| (child, 1)).collect())
+            .unwrap_or_default();
+        while let Some((node, depth)) = stack.pop() {
+            if !seen.insert(node) {
+                continue;
+            }
+            out.push((node, depth));
+            if let Some(children) = self.children.get(&node) {
+                for &child in children.iter().rev() {
+                    stack.push((child, depth + 1));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Pull a parent session reference out of conversation metadata. Checks the
+/// known harness keys at the top level and one level down (some connectors
+/// nest the original header under a `raw`/`metadata` envelope).
+#[must_use]
+pub fn extract_parent_external_id(metadata: &serde_json::Value) -> Option<String> {
+    fn from_object(value: &serde_json::Value) -> Option<String> {
+        for key in PARENT_KEYS {
+            if let Some(parent) = value.get(key).and_then(|v| v.as_str())
+                && !parent.trim().is_empty()
+            {
+                return Some(parent.trim().to_string());
+            }
+        }
+        None
+    }
+
+    if let Some(parent) = from_object(metadata) {
+        return Some(parent);
+    }
+    for envelope in ["raw", "metadata", "header"] {
+        if let Some(inner) = metadata.get(envelope)
+            && let Some(parent) = from_object(inner)
+        {
+            return Some(parent);
+        }
+    }
+    None
+}
+
+/// Load every conversation's lineage-relevant fields and build the graph.
+pub fn load_lineage_graph(conn: &Connection) -> Result<LineageGraph> {
+    let nodes: Vec<LineageNode> = conn
+        .query_map_collect(
+            "SELECT c.id, c.external_id, c.title, COALESCE(a.slug, 'unknown'),
+                    c.source_path, c.started_at, c.metadata_json, c.metadata_bin
+             FROM conversations c
+             LEFT JOIN agents a ON c.agent_id = a.id",
+            &[],
+            |row| {
+                use frankensqlite::compat::RowExt;
+                let metadata =
+                    crate::storage::sqlite::franken_read_metadata_compat(row, 6, 7);
+                Ok(LineageNode {
+                    id: row.get_typed(0)?,
+                    external_id: row.get_typed(1)?,
+                    title: row.get_typed(2)?,
+                    agent: row.get_typed(3)?,
+                    source_path: row.get_typed(4)?,
+                    started_at: row.get_typed(5)?,
+                    parent_external_id: extract_parent_external_id(&metadata),
+                })
+            },
+        )
+        .context("querying conversations for lineage graph")?;
+
+    Ok(LineageGraph::build(nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i64, external: &str, parent: Option<&str>, started_at: i64) -> LineageNode {
+        LineageNode {
+            id,
+            external_id: Some(external.to_string()),
+            title: None,
+            agent: "claude_code".to_string(),
+            source_path: format!("/tmp/{external}.jsonl"),
+            started_at: Some(started_at),
+            parent_external_id: parent.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn extracts_parent_across_harness_key_spellings() {
+        for key in ["parentUuid", "parent_session_id", "resumedFrom", "parentThreadId"] {
+            let metadata = serde_json::json!({ key: "abc-123" });
+            assert_eq!(
+                extract_parent_external_id(&metadata).as_deref(),
+                Some("abc-123"),
+                "key {key} should be recognized"
+            );
+        }
+        // Nested envelope and absent/blank values.
+        let nested = serde_json::json!({ "raw": { "parentUuid": "xyz" } });
+        assert_eq!(extract_parent_external_id(&nested).as_deref(), Some("xyz"));
+        assert!(extract_parent_external_id(&serde_json::json!({})).is_none());
+        assert!(
+            extract_parent_external_id(&serde_json::json!({ "parentUuid": "  " })).is_none()
+        );
+    }
+
+    #[test]
+    fn ancestors_walk_to_root_and_descendants_cover_subtree() {
+        // root -> a -> b, root -> c
+        let graph = LineageGraph::build(vec![
+            node(1, "root", None, 100),
+            node(2, "a", Some("root"), 200),
+            node(3, "b", Some("a"), 300),
+            node(4, "c", Some("root"), 250),
+        ]);
+
+        assert_eq!(graph.ancestors(3), vec![2, 1]);
+        assert!(graph.ancestors(1).is_empty());
+
+        let descendants = graph.descendants(1);
+        assert_eq!(descendants, vec![(2, 1), (3, 2), (4, 1)]);
+        assert_eq!(graph.descendants(3), Vec::<(i64, usize)>::new());
+    }
+
+    #[test]
+    fn unresolvable_and_self_references_yield_no_edges() {
+        let graph = LineageGraph::build(vec![
+            node(1, "a", Some("never-indexed"), 100),
+            node(2, "b", Some("b"), 200),
+        ]);
+        assert!(graph.ancestors(1).is_empty());
+        assert!(graph.ancestors(2).is_empty());
+    }
+
+    #[test]
+    fn cycles_terminate() {
+        let graph = LineageGraph::build(vec![
+            node(1, "a", Some("b"), 100),
+            node(2, "b", Some("a"), 200),
+        ]);
+        // Both directions terminate instead of looping forever.
+        assert_eq!(graph.ancestors(1), vec![2]);
+        assert_eq!(graph.ancestors(2), vec![1]);
+        assert_eq!(graph.descendants(1), vec![(2, 1)]);
+    }
+}
@@ -244,6 +244,13 @@ fn main() -> anyhow::Result<()> {
         Err(err) => handle_fatal_error(err),
     };
 
+    // Export the active profile scope (CASS_PROFILE + CASS_DATA_DIR) while
+    // the process is still single-threaded; everything downstream — the
+    // Health fast path included — resolves paths through those vars.
+    if let Err(err) = coding_agent_search::apply_active_profile(parsed.cli.profile.as_deref()) {
+        handle_fatal_error(err);
+    }
+
     let parsed = match coding_agent_search::try_run_with_parsed_fast(parsed) {
         Ok(result) => {
             return match result {
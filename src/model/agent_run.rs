@@ -0,0 +1,172 @@
+//! Classification of headless/batch agent invocations ("runs").
+//!
+//! `codex exec`, `claude -p`, and similar batch invocations produce session
+//! files indistinguishable from interactive chats: one user prompt, then a
+//! stream of agent and tool turns with nobody typing. This module decides
+//! whether a conversation looks like such a run — no user turns after the
+//! first message, exec-heavy traffic — and extracts the run's exit status
+//! when the transcript makes it derivable (harnesses echo `exit code N` /
+//! `exited with status N` in their final tool output).
+//!
+//! The classification is a derived asset stored in the `agent_runs` table
+//! and refreshed at the end of each non-watch index run
+//! (`FrankenStorage::refresh_agent_runs`), the same lifecycle as the quality
+//! score and integrity seal. `cass runs` lists the conversations classified
+//! as automated, keeping them out of the way of interactive-session
+//! browsing without hiding them from search.
+
+/// Non-user messages required before a prompt-plus-replies transcript is
+/// treated as automated rather than a short interactive exchange that the
+/// human simply abandoned. Exec-heavy traffic or a derivable exit status
+/// overrides this floor.
+const MIN_UNATTENDED_REPLIES: usize = 3;
+
+/// Tool-role turns at or above which a transcript counts as exec-heavy.
+const EXEC_HEAVY_TOOL_TURNS: usize = 2;
+
+/// How one conversation was classified, plus what could be derived about
+/// the run itself. Stored per conversation so the refresh pass stays
+/// incremental; `is_run == false` rows record "looked at it, it's a chat".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunClassification {
+    /// Whether the conversation looks like a headless/batch invocation.
+    pub is_run: bool,
+    /// Whether tool traffic dominated the transcript.
+    pub exec_heavy: bool,
+    /// Exit status echoed in the transcript, when derivable.
+    pub exit_code: Option<i64>,
+}
+
+/// Classify one conversation from `(role, content)` pairs in message order.
+/// Pure; the caller supplies rows from storage.
+#[must_use]
+pub fn classify_run(messages: &[(String, String)]) -> RunClassification {
+    let user_after_first = messages.iter().skip(1).any(|(role, _)| role == "user");
+    let tool_turns = messages.iter().filter(|(role, _)| role == "tool").count();
+    let non_user = messages.iter().filter(|(role, _)| role != "user").count();
+    let exec_heavy =
+        tool_turns >= EXEC_HEAVY_TOOL_TURNS || (tool_turns > 0 && tool_turns * 2 >= messages.len());
+    let exit_code = derive_exit_code(messages);
+
+    // A run has nobody typing after the opening prompt, plus at least one
+    // signal that an automation harness (not a human who wandered off) was
+    // driving: exec-heavy traffic, an echoed exit status, or a reply volume
+    // no single prompt-and-answer chat produces.
+    let is_run = !user_after_first
+        && messages.len() >= 2
+        && (exec_heavy || exit_code.is_some() || non_user >= MIN_UNATTENDED_REPLIES);
+
+    RunClassification {
+        is_run,
+        exec_heavy,
+        exit_code,
+    }
+}
+
+/// Scan the transcript tail-first for an echoed exit status. Recognized
+/// forms: `exit code N`, `exit code: N`, `exit status N`, `exited with
+/// code N`, `exited with status N` (case-insensitive). The last echo wins
+/// since harnesses print the final status last.
+fn derive_exit_code(messages: &[(String, String)]) -> Option<i64> {
+    messages
+        .iter()
+        .rev()
+        .find_map(|(_, content)| parse_exit_code(content))
+}
+
+fn parse_exit_code(content: &str) -> Option<i64> {
+    let lowered = content.to_ascii_lowercase();
+    let mut best = None;
+    for marker in [
+        "exit code",
+        "exit status",
+        "exited with code",
+        "exited with status",
+    ] {
+        let mut search_from = 0;
+        while let Some(found) = lowered[search_from..].find(marker) {
+            let after = search_from + found + marker.len();
+            search_from = after;
+            let rest = lowered[after..].trim_start_matches([':', ' ', '=']);
+            let digits: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '-')
+                .collect();
+            if let Ok(code) = digits.parse::<i64>() {
+                // Later echoes in the same body override earlier ones.
+                best = Some(code);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn exec_heavy_single_prompt_session_is_a_run() {
+        let messages = vec![
+            msg("user", "run the test suite and summarize failures"),
+            msg("agent", "running tests"),
+            msg("tool", "cargo test ... FAILED"),
+            msg("tool", "exit code 101"),
+        ];
+        let classification = classify_run(&messages);
+        assert!(classification.is_run);
+        assert!(classification.exec_heavy);
+        assert_eq!(classification.exit_code, Some(101));
+    }
+
+    #[test]
+    fn interactive_back_and_forth_is_not_a_run() {
+        let messages = vec![
+            msg("user", "what does this error mean?"),
+            msg("agent", "it means the borrow checker is unhappy"),
+            msg("user", "ok, how do I fix it?"),
+            msg("agent", "clone the value before the loop"),
+        ];
+        assert!(!classify_run(&messages).is_run);
+    }
+
+    #[test]
+    fn short_abandoned_chat_is_not_a_run() {
+        // One prompt, one answer, no tool traffic and no exit status: this
+        // is an abandoned chat, not a batch invocation.
+        let messages = vec![
+            msg("user", "hello?"),
+            msg("agent", "hi, what can I do for you?"),
+        ];
+        assert!(!classify_run(&messages).is_run);
+    }
+
+    #[test]
+    fn headless_print_mode_with_long_reply_stream_is_a_run() {
+        let mut messages = vec![msg("user", "refactor the parser module")];
+        for i in 0..4 {
+            messages.push(msg("agent", &format!("step {i}")));
+        }
+        let classification = classify_run(&messages);
+        assert!(classification.is_run);
+        assert!(!classification.exec_heavy);
+        assert_eq!(classification.exit_code, None);
+    }
+
+    #[test]
+    fn exit_code_parsing_handles_common_forms() {
+        assert_eq!(parse_exit_code("process exited with code 0"), Some(0));
+        assert_eq!(parse_exit_code("Exit Code: 2"), Some(2));
+        assert_eq!(parse_exit_code("command exit status -1"), Some(-1));
+        assert_eq!(
+            parse_exit_code("first exit code 1 then exit code 0"),
+            Some(0)
+        );
+        assert_eq!(parse_exit_code("no status here"), None);
+        assert_eq!(parse_exit_code("exit code unknown"), None);
+    }
+}
@@ -1,4 +1,5 @@
 //! Domain models for normalized entities.
+pub mod agent_run;
 pub mod cli_error_kind;
 pub mod conversation_packet;
 pub mod packet_audit;
@@ -3,6 +3,84 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Unix timestamp in **milliseconds**.
+///
+/// The ms-vs-seconds mismatch is a recurring bug class (timeline filters
+/// comparing seconds against ms), so conversions in and out of this type are
+/// explicit: construct via [`Timestamp::from_millis`] /
+/// [`Timestamp::from_seconds`], or [`Timestamp::from_unix_auto`] at ingest
+/// boundaries where the source unit is untrusted. The canonical DB stores
+/// milliseconds everywhere; [`normalize_unix_millis`] is the storage-boundary
+/// helper that coerces raw values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Positive values below this are not plausible as milliseconds: 1e11 ms
+    /// is March 1973, before any agent session existed, while 1e11 seconds is
+    /// the year 5138. Values under the threshold are treated as seconds.
+    pub const MIN_PLAUSIBLE_MILLIS: i64 = 100_000_000_000;
+    /// Values at or above this (1e14 ms ≈ year 5138) can only be microseconds.
+    pub const MAX_PLAUSIBLE_MILLIS: i64 = 100_000_000_000_000;
+
+    /// Wrap a value already in milliseconds. Debug builds reject magnitudes
+    /// that are obviously seconds or microseconds; zero and negative values
+    /// pass through untouched (connectors use them as "unknown").
+    #[must_use]
+    pub fn from_millis(millis: i64) -> Self {
+        debug_assert!(
+            millis <= 0
+                || (Self::MIN_PLAUSIBLE_MILLIS..Self::MAX_PLAUSIBLE_MILLIS).contains(&millis),
+            "timestamp {millis} does not look like unix milliseconds; \
+             use Timestamp::from_seconds or Timestamp::from_unix_auto"
+        );
+        Self(millis)
+    }
+
+    /// Convert a value in whole seconds.
+    #[must_use]
+    pub fn from_seconds(seconds: i64) -> Self {
+        Self(seconds.saturating_mul(1000))
+    }
+
+    /// Normalize a raw unix value of unknown unit by magnitude: values under
+    /// [`Self::MIN_PLAUSIBLE_MILLIS`] are seconds, values at or above
+    /// [`Self::MAX_PLAUSIBLE_MILLIS`] are microseconds, everything between is
+    /// already milliseconds. Zero and negative values pass through untouched.
+    #[must_use]
+    pub fn from_unix_auto(raw: i64) -> Self {
+        if raw <= 0 {
+            Self(raw)
+        } else if raw < Self::MIN_PLAUSIBLE_MILLIS {
+            Self::from_seconds(raw)
+        } else if raw >= Self::MAX_PLAUSIBLE_MILLIS {
+            Self(raw / 1000)
+        } else {
+            Self(raw)
+        }
+    }
+
+    /// Milliseconds since the unix epoch.
+    #[must_use]
+    pub fn as_millis(self) -> i64 {
+        self.0
+    }
+
+    /// Whole seconds since the unix epoch (truncating).
+    #[must_use]
+    pub fn as_seconds(self) -> i64 {
+        self.0.div_euclid(1000)
+    }
+}
+
+/// Coerce a raw timestamp of unknown unit to milliseconds (storage boundary
+/// helper; see [`Timestamp::from_unix_auto`] for the magnitude rules).
+#[must_use]
+pub fn normalize_unix_millis(raw: i64) -> i64 {
+    Timestamp::from_unix_auto(raw).as_millis()
+}
+
 /// Roles seen across source agents.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum MessageRole {
@@ -138,6 +216,55 @@ mod tests {
         }
     }
 
+    // =========================
+    // Timestamp Tests
+    // =========================
+
+    #[test]
+    fn timestamp_explicit_conversions() {
+        let ts = Timestamp::from_seconds(1_700_000_000);
+        assert_eq!(ts.as_millis(), 1_700_000_000_000);
+        assert_eq!(ts.as_seconds(), 1_700_000_000);
+        assert_eq!(Timestamp::from_millis(1_700_000_000_000), ts);
+    }
+
+    #[test]
+    fn timestamp_from_unix_auto_detects_units_by_magnitude() {
+        // Seconds, milliseconds, microseconds for the same instant.
+        let expected = 1_700_000_000_000;
+        assert_eq!(
+            Timestamp::from_unix_auto(1_700_000_000).as_millis(),
+            expected
+        );
+        assert_eq!(
+            Timestamp::from_unix_auto(1_700_000_000_000).as_millis(),
+            expected
+        );
+        assert_eq!(
+            Timestamp::from_unix_auto(1_700_000_000_000_000).as_millis(),
+            expected
+        );
+        // Zero / negative sentinel values pass through untouched.
+        assert_eq!(Timestamp::from_unix_auto(0).as_millis(), 0);
+        assert_eq!(Timestamp::from_unix_auto(-1).as_millis(), -1);
+        assert_eq!(normalize_unix_millis(1_700_000_000), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not look like unix milliseconds")]
+    #[cfg(debug_assertions)]
+    fn timestamp_from_millis_rejects_seconds_magnitude_in_debug() {
+        let _ = Timestamp::from_millis(1_700_000_000);
+    }
+
+    #[test]
+    fn timestamp_serde_is_transparent() {
+        let ts = Timestamp::from_millis(1_700_000_000_000);
+        assert_eq!(to_value(ts).unwrap(), json!(1_700_000_000_000_i64));
+        let parsed: Timestamp = from_value(json!(1_700_000_000_000_i64)).unwrap();
+        assert_eq!(parsed, ts);
+    }
+
     // =========================
     // MessageRole Tests
     // =========================
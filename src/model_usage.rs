@@ -0,0 +1,315 @@
+//! Per-model usage statistics derived from message authors.
+//!
+//! Many connectors record the producing model in the message `author` column
+//! (`claude-opus-4-5-20251101`, `gpt-5-codex`, `gemini-2.0-flash`, ...), but
+//! the raw strings are noisy: the same model shows up with date stamps,
+//! provider prefixes, and `-latest` aliases. This module canonicalizes those
+//! names, aggregates agent-authored messages per canonical model (usage share,
+//! average response length, first/last seen), and materializes the result into
+//! a `models` dimension table so other queries can join against stable names.
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::ConnectionExt;
+use frankensqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Schema for the `models` dimension table. Refreshed wholesale on each
+/// aggregation pass (it is derived data, like the analytics rollups), so the
+/// table carries no state of its own beyond the last refresh.
+pub const MODELS_DIM_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS models (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    family TEXT,
+    provider TEXT,
+    message_count INTEGER NOT NULL,
+    total_response_chars INTEGER NOT NULL,
+    first_seen INTEGER,
+    last_seen INTEGER,
+    refreshed_at INTEGER NOT NULL
+);
+";
+
+/// Aggregate usage for one canonical model name.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelUsageRow {
+    /// Canonical model name (date stamps and aliases stripped).
+    pub model: String,
+    /// Model family from the connector-level normalizer (e.g. "claude").
+    pub family: String,
+    /// Provider from the connector-level normalizer (e.g. "anthropic").
+    pub provider: String,
+    /// Agent-authored messages attributed to this model.
+    pub message_count: u64,
+    /// Fraction of all model-attributed messages (0.0–1.0).
+    pub usage_share: f64,
+    /// Mean response length in characters.
+    pub avg_response_chars: f64,
+    /// Earliest message timestamp (unix millis), if any message carried one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<i64>,
+    /// Latest message timestamp (unix millis), if any message carried one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<i64>,
+    /// Raw author strings that collapsed into this canonical name.
+    pub raw_names: Vec<String>,
+}
+
+/// Canonicalize a raw model/author string into a stable model name.
+///
+/// Lowercases, strips a provider prefix (`anthropic/claude-...`), drops a
+/// trailing `-latest` alias, and removes a trailing `-YYYYMMDD` date stamp so
+/// `claude-opus-4-5-20251101` and `claude-opus-4-5` land in the same bucket.
+#[must_use]
+pub fn canonical_model_name(raw: &str) -> String {
+    let mut name = raw.trim().to_lowercase();
+    if let Some((_, rest)) = name.rsplit_once('/') {
+        name = rest.to_string();
+    }
+    if let Some(stripped) = name.strip_suffix("-latest") {
+        name = stripped.to_string();
+    }
+    if let Some((head, tail)) = name.rsplit_once('-')
+        && tail.len() == 8
+        && tail.starts_with("20")
+        && tail.chars().all(|c| c.is_ascii_digit())
+    {
+        name = head.to_string();
+    }
+    name
+}
+
+/// Aggregate agent-authored messages per canonical model name.
+///
+/// Rows come back sorted by message count descending; ties break on name so
+/// the ordering is deterministic. Messages without an author are excluded —
+/// the share is over model-attributed messages only.
+pub fn aggregate_model_usage(conn: &Connection) -> Result<Vec<ModelUsageRow>> {
+    struct RawRow {
+        author: String,
+        count: i64,
+        chars: i64,
+        first_seen: Option<i64>,
+        last_seen: Option<i64>,
+    }
+
+    let raw_rows: Vec<RawRow> = conn
+        .query_map_collect(
+            "SELECT author, COUNT(*), COALESCE(SUM(LENGTH(content)), 0),
+                    MIN(created_at), MAX(created_at)
+             FROM messages
+             WHERE role = 'agent' AND author IS NOT NULL AND TRIM(author) != ''
+             GROUP BY author",
+            &[],
+            |row| {
+                Ok(RawRow {
+                    author: row.get_typed(0)?,
+                    count: row.get_typed(1)?,
+                    chars: row.get_typed(2)?,
+                    first_seen: row.get_typed(3)?,
+                    last_seen: row.get_typed(4)?,
+                })
+            },
+        )
+        .context("aggregating message authors")?;
+
+    let mut by_model: HashMap<String, ModelUsageRow> = HashMap::new();
+    let mut total_chars: HashMap<String, i64> = HashMap::new();
+
+    for raw in &raw_rows {
+        let model = canonical_model_name(&raw.author);
+        if model.is_empty() {
+            continue;
+        }
+        *total_chars.entry(model.clone()).or_default() += raw.chars;
+        let entry = by_model.entry(model.clone()).or_insert_with(|| {
+            let info = crate::connectors::normalize_model(&raw.author);
+            ModelUsageRow {
+                model,
+                family: info.family,
+                provider: info.provider,
+                message_count: 0,
+                usage_share: 0.0,
+                avg_response_chars: 0.0,
+                first_seen: None,
+                last_seen: None,
+                raw_names: Vec::new(),
+            }
+        });
+        entry.message_count += raw.count.max(0) as u64;
+        entry.first_seen = match (entry.first_seen, raw.first_seen) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        entry.last_seen = entry.last_seen.max(raw.last_seen);
+        entry.raw_names.push(raw.author.clone());
+    }
+
+    let total_messages: u64 = by_model.values().map(|r| r.message_count).sum();
+    let mut rows: Vec<ModelUsageRow> = by_model.into_values().collect();
+    for row in &mut rows {
+        if total_messages > 0 {
+            row.usage_share = row.message_count as f64 / total_messages as f64;
+        }
+        if row.message_count > 0 {
+            let chars = total_chars.get(&row.model).copied().unwrap_or(0);
+            row.avg_response_chars = chars as f64 / row.message_count as f64;
+        }
+        row.raw_names.sort();
+    }
+    rows.sort_by(|a, b| {
+        b.message_count
+            .cmp(&a.message_count)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    Ok(rows)
+}
+
+/// Replace the `models` dimension table with the given aggregation pass.
+pub fn refresh_models_dimension(conn: &Connection, rows: &[ModelUsageRow]) -> Result<()> {
+    conn.execute_batch(MODELS_DIM_SCHEMA)
+        .context("creating models dimension table")?;
+    conn.execute_compat("DELETE FROM models", params![])
+        .context("clearing models dimension table")?;
+
+    let refreshed_at = chrono::Utc::now().timestamp_millis();
+    for row in rows {
+        let total_chars = (row.avg_response_chars * row.message_count as f64).round() as i64;
+        conn.execute_compat(
+            "INSERT INTO models
+                (name, family, provider, message_count, total_response_chars,
+                 first_seen, last_seen, refreshed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.model.as_str(),
+                row.family.as_str(),
+                row.provider.as_str(),
+                row.message_count as i64,
+                total_chars,
+                row.first_seen,
+                row.last_seen,
+                refreshed_at
+            ],
+        )
+        .context("inserting models dimension row")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_name_strips_date_stamp() {
+        assert_eq!(
+            canonical_model_name("claude-opus-4-5-20251101"),
+            "claude-opus-4-5"
+        );
+        assert_eq!(canonical_model_name("claude-opus-4-5"), "claude-opus-4-5");
+    }
+
+    #[test]
+    fn canonical_name_strips_provider_prefix_and_latest() {
+        assert_eq!(
+            canonical_model_name("anthropic/claude-sonnet-4-latest"),
+            "claude-sonnet-4"
+        );
+        assert_eq!(canonical_model_name("  GPT-5-Codex "), "gpt-5-codex");
+    }
+
+    #[test]
+    fn canonical_name_keeps_non_date_numeric_suffixes() {
+        // gemini-2.0 has no 8-digit date chunk; versions must survive.
+        assert_eq!(canonical_model_name("gemini-2.0-flash"), "gemini-2.0-flash");
+        assert_eq!(canonical_model_name("gpt-4-0613"), "gpt-4-0613");
+    }
+
+    fn seeded_conn() -> Connection {
+        let conn = Connection::open(":memory:").expect("open in-memory db");
+        conn.execute_batch(
+            "CREATE TABLE messages (
+                id INTEGER PRIMARY KEY,
+                conversation_id INTEGER,
+                idx INTEGER,
+                role TEXT,
+                author TEXT,
+                created_at INTEGER,
+                content TEXT,
+                extra_json TEXT
+            );",
+        )
+        .expect("create messages table");
+        let rows: &[(&str, Option<&str>, Option<i64>, &str)] = &[
+            ("agent", Some("claude-opus-4-5-20251101"), Some(1000), "aaaa"),
+            ("agent", Some("claude-opus-4-5"), Some(3000), "bbbbbbbb"),
+            ("agent", Some("gpt-5-codex"), Some(2000), "cc"),
+            ("agent", None, Some(2500), "no author"),
+            ("user", Some("claude-opus-4-5"), Some(500), "user text"),
+        ];
+        for (i, (role, author, ts, content)) in rows.iter().enumerate() {
+            conn.execute_compat(
+                "INSERT INTO messages (conversation_id, idx, role, author, created_at, content)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+                params![i as i64, *role, *author, *ts, *content],
+            )
+            .expect("insert message");
+        }
+        conn
+    }
+
+    #[test]
+    fn aggregation_merges_date_stamped_variants() {
+        let conn = seeded_conn();
+        let rows = aggregate_model_usage(&conn).expect("aggregate");
+        assert_eq!(rows.len(), 2);
+
+        let opus = &rows[0];
+        assert_eq!(opus.model, "claude-opus-4-5");
+        assert_eq!(opus.message_count, 2);
+        assert_eq!(opus.first_seen, Some(1000));
+        assert_eq!(opus.last_seen, Some(3000));
+        assert_eq!(opus.raw_names.len(), 2);
+        // 4 + 8 chars over 2 messages.
+        assert!((opus.avg_response_chars - 6.0).abs() < f64::EPSILON);
+        assert!((opus.usage_share - 2.0 / 3.0).abs() < 1e-9);
+
+        let codex = &rows[1];
+        assert_eq!(codex.model, "gpt-5-codex");
+        assert_eq!(codex.message_count, 1);
+    }
+
+    #[test]
+    fn refresh_writes_dimension_rows() {
+        let conn = seeded_conn();
+        let rows = aggregate_model_usage(&conn).expect("aggregate");
+        refresh_models_dimension(&conn, &rows).expect("refresh");
+
+        let count: i64 = conn
+            .query_row_map("SELECT COUNT(*) FROM models", params![], |row| {
+                row.get_typed(0)
+            })
+            .expect("count models rows");
+        assert_eq!(count, 2);
+
+        // A second refresh replaces, not appends.
+        refresh_models_dimension(&conn, &rows).expect("second refresh");
+        let count: i64 = conn
+            .query_row_map("SELECT COUNT(*) FROM models", params![], |row| {
+                row.get_typed(0)
+            })
+            .expect("count after second refresh");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn empty_db_yields_no_rows() {
+        let conn = Connection::open(":memory:").expect("open in-memory db");
+        conn.execute_batch("CREATE TABLE messages (id INTEGER PRIMARY KEY, role TEXT, author TEXT, created_at INTEGER, content TEXT);")
+            .expect("create table");
+        let rows = aggregate_model_usage(&conn).expect("aggregate empty");
+        assert!(rows.is_empty());
+    }
+}
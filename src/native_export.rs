@@ -0,0 +1,269 @@
+//! Re-export of indexed conversations into agent-native session formats.
+//!
+//! `cass export --format codex-rollout` / `--format claude-jsonl` converts a
+//! conversation back into an approximation of the originating agent's own
+//! on-disk session layout, so history indexed on one machine (or pulled from
+//! a remote source) can seed a resumable session for that agent elsewhere.
+//!
+//! The output is deliberately an *approximation*: cass normalizes messages
+//! at index time and does not retain every native field, so the emitted
+//! records carry only what the formats structurally require — roles, text
+//! content, timestamps, and (for Claude Code) the uuid parent chain. IDs are
+//! derived deterministically from message content so repeated exports of the
+//! same conversation are byte-identical and diffable.
+//!
+//! Formatting is pure: the caller (the `cass export` handler in `lib.rs`)
+//! owns conversation loading and role/content extraction and hands in
+//! already-flattened [`NativeExportMessage`] rows.
+
+use chrono::{SecondsFormat, TimeZone, Utc};
+use serde_json::json;
+
+use crate::search::query::stable_content_hash;
+
+/// One message flattened for native re-export: the role and text content as
+/// cass indexed them, plus the original timestamp when one survived.
+#[derive(Debug, Clone)]
+pub struct NativeExportMessage {
+    /// Normalized role (`user`, `assistant`, `tool`, ...).
+    pub role: String,
+    /// Flattened text content.
+    pub content: String,
+    /// Message timestamp in epoch milliseconds, if known.
+    pub created_at: Option<i64>,
+}
+
+/// Render messages as a Codex rollout file: one `session_meta` line followed
+/// by `response_item` message lines. Roles other than `user` and `assistant`
+/// (tool output, system prompts) are skipped — a seeded session needs the
+/// turns, not the transcript noise.
+#[must_use]
+pub fn format_as_codex_rollout(
+    messages: &[NativeExportMessage],
+    session_start: Option<i64>,
+) -> String {
+    let start_ms = effective_session_start(messages, session_start);
+    let session_id = derived_session_id(messages, start_ms);
+    let mut out = String::new();
+    push_jsonl_line(
+        &mut out,
+        &json!({
+            "timestamp": iso_timestamp(start_ms),
+            "type": "session_meta",
+            "payload": {
+                "id": session_id,
+                "timestamp": iso_timestamp(start_ms),
+                "originator": "cass-export",
+            },
+        }),
+    );
+    for message in messages {
+        let Some(role) = turn_role(&message.role) else {
+            continue;
+        };
+        let content_type = if role == "user" {
+            "input_text"
+        } else {
+            "output_text"
+        };
+        push_jsonl_line(
+            &mut out,
+            &json!({
+                "timestamp": iso_timestamp(message.created_at.unwrap_or(start_ms)),
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": role,
+                    "content": [{"type": content_type, "text": message.content}],
+                },
+            }),
+        );
+    }
+    out
+}
+
+/// Render messages as Claude Code session JSONL: an optional leading
+/// `summary` line carrying the title, then `user`/`assistant` records with
+/// nested `message` objects and a deterministic `uuid`/`parentUuid` chain.
+/// Roles other than `user` and `assistant` are skipped.
+#[must_use]
+pub fn format_as_claude_jsonl(
+    messages: &[NativeExportMessage],
+    title: &Option<String>,
+    session_start: Option<i64>,
+) -> String {
+    let start_ms = effective_session_start(messages, session_start);
+    let session_id = derived_session_id(messages, start_ms);
+    let mut out = String::new();
+    if let Some(title) = title.as_deref().filter(|t| !t.trim().is_empty()) {
+        push_jsonl_line(&mut out, &json!({"type": "summary", "summary": title}));
+    }
+    let mut parent_uuid: Option<String> = None;
+    for (idx, message) in messages.iter().enumerate() {
+        let Some(role) = turn_role(&message.role) else {
+            continue;
+        };
+        let uuid = derived_uuid(&message.content, idx as u64 + 1);
+        push_jsonl_line(
+            &mut out,
+            &json!({
+                "type": role,
+                "message": {
+                    "role": role,
+                    "content": [{"type": "text", "text": message.content}],
+                },
+                "uuid": uuid,
+                "parentUuid": parent_uuid,
+                "sessionId": session_id,
+                "timestamp": iso_timestamp(message.created_at.unwrap_or(start_ms)),
+            }),
+        );
+        parent_uuid = Some(uuid);
+    }
+    out
+}
+
+/// Map a normalized role onto the `user`/`assistant` pair both native
+/// formats use for conversational turns. Agent responses are stored under a
+/// few aliases; everything else is dropped.
+fn turn_role(role: &str) -> Option<&'static str> {
+    match role {
+        "user" => Some("user"),
+        "assistant" | "agent" => Some("assistant"),
+        _ => None,
+    }
+}
+
+/// Session start for timestamp fallback: the explicit start when known,
+/// otherwise the first message timestamp, otherwise zero (exports never
+/// invent wall-clock time — a fake "now" would defeat determinism).
+fn effective_session_start(messages: &[NativeExportMessage], session_start: Option<i64>) -> i64 {
+    session_start
+        .or_else(|| messages.iter().find_map(|m| m.created_at))
+        .unwrap_or(0)
+}
+
+fn iso_timestamp(ms: i64) -> String {
+    Utc.timestamp_millis_opt(ms)
+        .single()
+        .unwrap_or_else(|| Utc.timestamp_millis_opt(0).single().expect("epoch"))
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Deterministic uuid-shaped id for the whole session, hashed from the
+/// start timestamp and first message.
+fn derived_session_id(messages: &[NativeExportMessage], start_ms: i64) -> String {
+    let seed = format!(
+        "cass-session:{start_ms}:{}",
+        messages.first().map(|m| m.content.as_str()).unwrap_or("")
+    );
+    derived_uuid(&seed, 0)
+}
+
+/// Deterministic uuid-shaped id derived from `text` and a per-message salt.
+/// Not a real UUID — just 128 stable bits laid out in the 8-4-4-4-12 shape
+/// the native formats expect.
+fn derived_uuid(text: &str, salt: u64) -> String {
+    let hi = stable_content_hash(text) ^ salt.rotate_left(17);
+    let lo = stable_content_hash(&format!("{salt}:{text}"));
+    let bytes = format!("{hi:016x}{lo:016x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &bytes[0..8],
+        &bytes[8..12],
+        &bytes[12..16],
+        &bytes[16..20],
+        &bytes[20..32]
+    )
+}
+
+fn push_jsonl_line(out: &mut String, value: &serde_json::Value) {
+    out.push_str(&serde_json::to_string(value).unwrap_or_default());
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str, created_at: Option<i64>) -> NativeExportMessage {
+        NativeExportMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+            created_at,
+        }
+    }
+
+    fn parse_lines(output: &str) -> Vec<serde_json::Value> {
+        output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid JSONL line"))
+            .collect()
+    }
+
+    #[test]
+    fn codex_rollout_starts_with_session_meta_and_keeps_turn_roles() {
+        let output = format_as_codex_rollout(
+            &[
+                msg("user", "fix the parser", Some(1_700_000_000_000)),
+                msg("assistant", "done", Some(1_700_000_060_000)),
+                msg("tool", "[tool output]", None),
+            ],
+            Some(1_700_000_000_000),
+        );
+        let lines = parse_lines(&output);
+        assert_eq!(lines.len(), 3, "tool message must be skipped: {output}");
+        assert_eq!(lines[0]["type"], "session_meta");
+        assert_eq!(lines[1]["payload"]["role"], "user");
+        assert_eq!(lines[1]["payload"]["content"][0]["type"], "input_text");
+        assert_eq!(lines[2]["payload"]["content"][0]["type"], "output_text");
+        assert_eq!(lines[2]["payload"]["content"][0]["text"], "done");
+    }
+
+    #[test]
+    fn claude_jsonl_chains_parent_uuids_and_carries_the_title() {
+        let output = format_as_claude_jsonl(
+            &[
+                msg("user", "add a test", Some(1_700_000_000_000)),
+                msg("agent", "added", Some(1_700_000_060_000)),
+            ],
+            &Some("Test session".to_string()),
+            None,
+        );
+        let lines = parse_lines(&output);
+        assert_eq!(lines[0]["type"], "summary");
+        assert_eq!(lines[0]["summary"], "Test session");
+        assert_eq!(lines[1]["type"], "user");
+        assert_eq!(lines[1]["parentUuid"], serde_json::Value::Null);
+        assert_eq!(lines[2]["type"], "assistant");
+        assert_eq!(lines[2]["parentUuid"], lines[1]["uuid"]);
+        assert_eq!(lines[1]["sessionId"], lines[2]["sessionId"]);
+        assert_eq!(lines[2]["message"]["role"], "assistant");
+    }
+
+    #[test]
+    fn exports_are_deterministic_across_runs() {
+        let messages = vec![
+            msg("user", "same input", Some(1_700_000_000_000)),
+            msg("assistant", "same output", None),
+        ];
+        assert_eq!(
+            format_as_codex_rollout(&messages, None),
+            format_as_codex_rollout(&messages, None)
+        );
+        assert_eq!(
+            format_as_claude_jsonl(&messages, &None, None),
+            format_as_claude_jsonl(&messages, &None, None)
+        );
+    }
+
+    #[test]
+    fn timestamps_round_trip_through_the_connector_parser() {
+        let output = format_as_codex_rollout(&[msg("user", "hi", Some(1_700_000_000_000))], None);
+        let lines = parse_lines(&output);
+        assert_eq!(
+            crate::connectors::parse_timestamp(&lines[1]["timestamp"]),
+            Some(1_700_000_000_000)
+        );
+    }
+}
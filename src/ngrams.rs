@@ -0,0 +1,244 @@
+//! Word and phrase frequencies across indexed conversations.
+//!
+//! `cass ngrams --workspace ~/dev/foo --since 30d` answers "what do I keep
+//! asking for?": it counts stopword-filtered 1–3 grams separately for user
+//! prompts and agent responses, so recurring requests ("add a unit test",
+//! "update the changelog") surface as prime candidates for snippets or
+//! memory files. Counting is textual and deliberately simple — phrases are
+//! lowercased token windows, fenced code blocks are skipped, and a phrase
+//! only counts when it starts and ends on a content word.
+//!
+//! ## Pure and deterministic
+//!
+//! Extraction ([`extract_ngrams`]) does no I/O: the caller supplies
+//! already-fetched `(role, content)` pairs and the same input always yields
+//! the same report. The CLI handler in `lib.rs` owns the database read, the
+//! workspace resolution, and the time-window resolution.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Longest phrase counted, in tokens.
+const MAX_GRAM: usize = 3;
+
+/// Shortest unigram worth counting; "ok" and "so" are noise even when they
+/// dodge the stopword list.
+const MIN_UNIGRAM_CHARS: usize = 3;
+
+/// Function words excluded from counting. A phrase is kept only when its
+/// first and last tokens are absent from this list; interior stopwords are
+/// fine ("state of the art"). Sorted so membership is a binary search.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "but", "by", "can", "could", "did", "do", "does",
+    "doing", "don", "down", "during", "each", "few", "for", "from", "further", "had", "has",
+    "have", "having", "he", "her", "here", "hers", "him", "his", "how", "i", "if", "in", "into",
+    "is", "it", "its", "just", "let", "like", "me", "more", "most", "my", "no", "nor", "not",
+    "now", "of", "off", "on", "once", "only", "or", "other", "our", "out", "over", "own", "re",
+    "s", "same", "she", "should", "so", "some", "such", "t", "than", "that", "the", "their",
+    "them", "then", "there", "these", "they", "this", "those", "through", "to", "too", "under",
+    "until", "up", "ve", "very", "was", "we", "were", "what", "when", "where", "which", "while",
+    "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// One counted phrase.
+#[derive(Debug, Clone, Serialize)]
+pub struct NgramRow {
+    /// Lowercased phrase, tokens joined with single spaces.
+    pub phrase: String,
+    /// Phrase length in tokens (1–3).
+    pub n: usize,
+    /// Occurrences across the selected messages.
+    pub count: u64,
+}
+
+/// Top phrases for one corpus slice, plus enough context to read the counts.
+#[derive(Debug, Clone, Serialize)]
+pub struct NgramReport {
+    /// Top phrases in user prompts, highest count first.
+    pub user: Vec<NgramRow>,
+    /// Top phrases in agent responses, highest count first.
+    pub agent: Vec<NgramRow>,
+    /// User messages that contributed tokens.
+    pub user_messages: usize,
+    /// Agent messages that contributed tokens.
+    pub agent_messages: usize,
+}
+
+/// Count stopword-filtered 1–3 grams in `(role, content)` pairs, split into
+/// user-prompt and agent-response buckets. Roles other than `user` and
+/// `agent` (tool output, system prompts) are ignored — they would drown the
+/// prose in command transcripts. Each bucket returns at most `top` rows,
+/// sorted by count descending with ties broken by phrase so the ordering is
+/// deterministic.
+#[must_use]
+pub fn extract_ngrams(messages: &[(String, String)], top: usize) -> NgramReport {
+    let mut user_counts: HashMap<String, (usize, u64)> = HashMap::new();
+    let mut agent_counts: HashMap<String, (usize, u64)> = HashMap::new();
+    let mut user_messages = 0usize;
+    let mut agent_messages = 0usize;
+
+    for (role, content) in messages {
+        let counts = match role.as_str() {
+            "user" => {
+                user_messages += 1;
+                &mut user_counts
+            }
+            "agent" => {
+                agent_messages += 1;
+                &mut agent_counts
+            }
+            _ => continue,
+        };
+        let tokens = tokenize(content);
+        for n in 1..=MAX_GRAM {
+            for window in tokens.windows(n) {
+                if !keep_phrase(window) {
+                    continue;
+                }
+                let phrase = window.join(" ");
+                let entry = counts.entry(phrase).or_insert((n, 0));
+                entry.1 += 1;
+            }
+        }
+    }
+
+    NgramReport {
+        user: top_rows(user_counts, top),
+        agent: top_rows(agent_counts, top),
+        user_messages,
+        agent_messages,
+    }
+}
+
+/// Lowercased word tokens from the prose portions of a message. Fenced code
+/// blocks are skipped entirely — `let mut self` frequencies say nothing about
+/// what the user keeps asking for. Pure numbers are dropped; interior
+/// apostrophes and hyphens keep words like "don't" and "re-run" whole.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for raw in line.split(|ch: char| !(ch.is_alphanumeric() || ch == '\'' || ch == '-')) {
+            let word = raw
+                .trim_matches(|ch: char| ch == '\'' || ch == '-')
+                .to_lowercase();
+            if word.is_empty() || word.chars().all(|ch| ch.is_ascii_digit()) {
+                continue;
+            }
+            tokens.push(word);
+        }
+    }
+    tokens
+}
+
+/// A phrase counts when it starts and ends on a content word; unigrams must
+/// also clear the length floor.
+fn keep_phrase(window: &[String]) -> bool {
+    let (Some(first), Some(last)) = (window.first(), window.last()) else {
+        return false;
+    };
+    if is_stopword(first) || is_stopword(last) {
+        return false;
+    }
+    window.len() > 1 || first.chars().count() >= MIN_UNIGRAM_CHARS
+}
+
+pub(crate) fn is_stopword(word: &str) -> bool {
+    STOPWORDS.binary_search(&word).is_ok()
+}
+
+/// Collapse a count map into the top `top` rows, count descending, phrase
+/// ascending on ties.
+fn top_rows(counts: HashMap<String, (usize, u64)>, top: usize) -> Vec<NgramRow> {
+    let mut rows: Vec<NgramRow> = counts
+        .into_iter()
+        .map(|(phrase, (n, count))| NgramRow { phrase, n, count })
+        .collect();
+    rows.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.phrase.cmp(&b.phrase)));
+    rows.truncate(top);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn stopwords_are_sorted_for_binary_search() {
+        let mut sorted = STOPWORDS.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(STOPWORDS, sorted.as_slice());
+    }
+
+    #[test]
+    fn tokenizer_skips_fenced_code_and_numbers() {
+        let tokens = tokenize("fix the bug\n```rust\nlet mut retries = 3;\n```\nin 2 places");
+        assert_eq!(tokens, vec!["fix", "the", "bug", "in", "places"]);
+    }
+
+    #[test]
+    fn phrases_must_start_and_end_on_content_words() {
+        let report = extract_ngrams(
+            &[
+                msg("user", "please add a unit test"),
+                msg("user", "add a unit test for the parser"),
+            ],
+            50,
+        );
+        let phrases: Vec<&str> = report.user.iter().map(|r| r.phrase.as_str()).collect();
+        assert!(phrases.contains(&"unit test"));
+        // "test for the" ends on a stopword; "a unit" starts on one.
+        assert!(!phrases.contains(&"test for the"));
+        assert!(!phrases.contains(&"a unit"));
+    }
+
+    #[test]
+    fn user_and_agent_buckets_stay_separate() {
+        let report = extract_ngrams(
+            &[
+                msg("user", "update the changelog"),
+                msg("agent", "updated the changelog entry"),
+                msg("tool", "changelog changelog changelog"),
+            ],
+            50,
+        );
+        assert_eq!(report.user_messages, 1);
+        assert_eq!(report.agent_messages, 1);
+        let user_changelog = report
+            .user
+            .iter()
+            .find(|r| r.phrase == "changelog")
+            .map(|r| r.count);
+        // Tool output must not inflate either bucket.
+        assert_eq!(user_changelog, Some(1));
+        assert!(report.agent.iter().any(|r| r.phrase == "changelog entry"));
+    }
+
+    #[test]
+    fn rows_sort_by_count_then_phrase_and_respect_top() {
+        let report = extract_ngrams(
+            &[
+                msg("user", "refactor refactor refactor"),
+                msg("user", "benchmark benchmark"),
+                msg("user", "alpha beta"),
+            ],
+            2,
+        );
+        assert_eq!(report.user.len(), 2);
+        assert_eq!(report.user[0].phrase, "refactor");
+        assert_eq!(report.user[0].count, 3);
+        assert_eq!(report.user[1].phrase, "benchmark");
+    }
+}
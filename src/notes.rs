@@ -0,0 +1,347 @@
+//! Freeform annotations attached to conversations and individual messages.
+//!
+//! Tags classify; notes explain. A note like "this was the actual fix" on
+//! message 14 is knowledge the session log itself can never carry, so it
+//! lives in a separate `SQLite` database (`data_dir/notes.db`) keyed by the
+//! conversation's source path — the same stable identity the bookmark and
+//! view-state stores use — and survives re-indexing unchanged.
+//!
+//! `message_idx` is the 0-based position of the message within its
+//! conversation (matching the TUI's message offsets); CLI permalinks use the
+//! 1-based message number shown in the viewer and convert at the edge. A
+//! `None` index annotates the conversation as a whole.
+//!
+//! Note text is searchable through the `note:` inline query field (see
+//! `search::structured_query`), which narrows results to conversations whose
+//! notes match.
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::{ConnectionExt, OptionalExtension, RowExt};
+use frankensqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single annotation on a conversation or message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    /// Unique note ID
+    pub id: i64,
+    /// Source path of the annotated conversation
+    pub source_path: String,
+    /// 0-based message position, or `None` for a conversation-level note
+    pub message_idx: Option<i64>,
+    /// The annotation text
+    pub text: String,
+    /// When the note was created (unix millis)
+    pub created_at: i64,
+    /// When the note was last updated (unix millis)
+    pub updated_at: i64,
+}
+
+/// Storage backend for notes using `SQLite`
+pub struct NoteStore {
+    conn: Connection,
+}
+
+impl NoteStore {
+    /// Open or create a note store at the given path
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating notes directory {}", parent.display()))?;
+        }
+
+        let conn = Connection::open(path.to_string_lossy().as_ref())
+            .with_context(|| format!("opening notes db at {}", path.display()))?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Read-only open that leaves no file behind: `Ok(None)` when the store
+    /// was never created. Used by the search-time `note:` filter and the TUI
+    /// renderer, which must not create `notes.db` on a pure read.
+    pub fn open_existing(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::open(path).map(Some)
+    }
+
+    /// Open the note store at the default location (`data_dir/notes.db`)
+    pub fn open_default() -> Result<Self> {
+        let path = default_notes_path();
+        Self::open(&path)
+    }
+
+    /// Add a note, returning its ID.
+    pub fn add(&self, source_path: &str, message_idx: Option<i64>, text: &str) -> Result<i64> {
+        let now = current_timestamp();
+        self.conn.execute_compat(
+            "INSERT INTO notes (source_path, message_idx, text, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![source_path, message_idx, text, now, now],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Remove a note by ID
+    pub fn remove(&self, id: i64) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute_compat("DELETE FROM notes WHERE id = ?1", params![id])?;
+        Ok(rows > 0)
+    }
+
+    /// Get a note by ID
+    pub fn get(&self, id: i64) -> Result<Option<Note>> {
+        self.conn
+            .query_row_map(
+                "SELECT id, source_path, message_idx, text, created_at, updated_at
+                 FROM notes WHERE id = ?1",
+                params![id],
+                row_to_note,
+            )
+            .optional()
+            .context("querying note by id")
+    }
+
+    /// List all notes, newest first
+    pub fn list(&self) -> Result<Vec<Note>> {
+        self.conn
+            .query_map_collect(
+                "SELECT id, source_path, message_idx, text, created_at, updated_at
+                 FROM notes ORDER BY created_at DESC",
+                &[],
+                row_to_note,
+            )
+            .context("listing notes")
+    }
+
+    /// Notes for one conversation, conversation-level first then in message
+    /// order — the order the viewer renders them.
+    pub fn notes_for_source(&self, source_path: &str) -> Result<Vec<Note>> {
+        self.conn
+            .query_map_collect(
+                "SELECT id, source_path, message_idx, text, created_at, updated_at
+                 FROM notes WHERE source_path = ?1
+                 ORDER BY message_idx IS NOT NULL, message_idx, created_at",
+                params![source_path],
+                row_to_note,
+            )
+            .context("listing notes for source")
+    }
+
+    /// Search note text (case-insensitive substring), newest first
+    pub fn search(&self, query: &str) -> Result<Vec<Note>> {
+        // Escape SQL LIKE wildcards so they are matched literally
+        let escaped = query
+            .to_lowercase()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = format!("%{escaped}%");
+        self.conn
+            .query_map_collect(
+                "SELECT id, source_path, message_idx, text, created_at, updated_at
+                 FROM notes WHERE LOWER(text) LIKE ?1 ESCAPE '\\'
+                 ORDER BY created_at DESC",
+                params![pattern],
+                row_to_note,
+            )
+            .context("searching notes")
+    }
+
+    /// Source paths of conversations with a note matching `query` — the
+    /// resolution step behind the `note:` search field.
+    pub fn source_paths_matching(&self, query: &str) -> Result<HashSet<String>> {
+        Ok(self
+            .search(query)?
+            .into_iter()
+            .map(|note| note.source_path)
+            .collect())
+    }
+
+    /// Count total notes
+    pub fn count(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row_map(
+            "SELECT COUNT(*) FROM notes",
+            &[],
+            |row: &frankensqlite::Row| row.get_typed(0),
+        )?;
+        usize::try_from(count).context("note count is out of range")
+    }
+}
+
+/// Convert a database row to a Note
+fn row_to_note(row: &frankensqlite::Row) -> Result<Note, frankensqlite::FrankenError> {
+    Ok(Note {
+        id: row.get_typed(0)?,
+        source_path: row.get_typed(1)?,
+        message_idx: row.get_typed(2)?,
+        text: row.get_typed(3)?,
+        created_at: row.get_typed(4)?,
+        updated_at: row.get_typed(5)?,
+    })
+}
+
+/// Get the default notes database path
+pub fn default_notes_path() -> PathBuf {
+    crate::default_data_dir().join("notes.db")
+}
+
+/// SQL schema for the notes database
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS notes (
+    id INTEGER PRIMARY KEY,
+    source_path TEXT NOT NULL,
+    message_idx INTEGER,
+    text TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_notes_source ON notes(source_path, message_idx);
+CREATE INDEX IF NOT EXISTS idx_notes_created ON notes(created_at DESC);
+";
+
+fn current_timestamp() -> i64 {
+    i64::try_from(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+    )
+    .unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_store() -> (NoteStore, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test_notes.db");
+        let store = NoteStore::open(&path).unwrap();
+        (store, dir)
+    }
+
+    #[test]
+    fn test_add_and_get() {
+        let (store, _dir) = test_store();
+        let id = store
+            .add("/sessions/a.jsonl", Some(3), "this was the actual fix")
+            .unwrap();
+        assert!(id > 0);
+
+        let note = store.get(id).unwrap().unwrap();
+        assert_eq!(note.source_path, "/sessions/a.jsonl");
+        assert_eq!(note.message_idx, Some(3));
+        assert_eq!(note.text, "this was the actual fix");
+    }
+
+    #[test]
+    fn test_conversation_level_note_has_no_message_idx() {
+        let (store, _dir) = test_store();
+        let id = store
+            .add("/sessions/a.jsonl", None, "whole session was a dead end")
+            .unwrap();
+        assert_eq!(store.get(id).unwrap().unwrap().message_idx, None);
+    }
+
+    #[test]
+    fn test_remove() {
+        let (store, _dir) = test_store();
+        let id = store.add("/sessions/a.jsonl", None, "gone soon").unwrap();
+        assert_eq!(store.count().unwrap(), 1);
+        assert!(store.remove(id).unwrap());
+        assert!(!store.remove(id).unwrap());
+        assert_eq!(store.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_notes_for_source_orders_conversation_level_first() {
+        let (store, _dir) = test_store();
+        store.add("/sessions/a.jsonl", Some(7), "late").unwrap();
+        store.add("/sessions/a.jsonl", None, "overview").unwrap();
+        store.add("/sessions/a.jsonl", Some(2), "early").unwrap();
+        store
+            .add("/sessions/b.jsonl", Some(0), "other file")
+            .unwrap();
+
+        let notes = store.notes_for_source("/sessions/a.jsonl").unwrap();
+        let texts: Vec<&str> = notes.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, vec!["overview", "early", "late"]);
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive() {
+        let (store, _dir) = test_store();
+        store
+            .add("/sessions/a.jsonl", Some(1), "Race condition in watcher")
+            .unwrap();
+        store.add("/sessions/b.jsonl", None, "unrelated").unwrap();
+
+        let hits = store.search("race").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, "/sessions/a.jsonl");
+    }
+
+    #[test]
+    fn test_search_treats_like_metacharacters_literally() {
+        let (store, _dir) = test_store();
+        store
+            .add("/sessions/a.jsonl", None, "done 100% sure")
+            .unwrap();
+        store
+            .add("/sessions/b.jsonl", None, "auth_token rotation")
+            .unwrap();
+
+        let percent = store.search("%").unwrap();
+        assert_eq!(percent.len(), 1);
+        assert_eq!(percent[0].source_path, "/sessions/a.jsonl");
+
+        let underscore = store.search("_").unwrap();
+        assert_eq!(underscore.len(), 1);
+        assert_eq!(underscore[0].source_path, "/sessions/b.jsonl");
+    }
+
+    #[test]
+    fn test_source_paths_matching_deduplicates() {
+        let (store, _dir) = test_store();
+        store.add("/sessions/a.jsonl", Some(1), "fix here").unwrap();
+        store
+            .add("/sessions/a.jsonl", Some(5), "fix again")
+            .unwrap();
+        store
+            .add("/sessions/b.jsonl", None, "fix elsewhere")
+            .unwrap();
+
+        let paths = store.source_paths_matching("fix").unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains("/sessions/a.jsonl"));
+        assert!(paths.contains("/sessions/b.jsonl"));
+    }
+
+    #[test]
+    fn test_open_existing_does_not_create_the_db() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.db");
+        assert!(NoteStore::open_existing(&path).unwrap().is_none());
+        assert!(!path.exists());
+
+        NoteStore::open(&path).unwrap();
+        assert!(NoteStore::open_existing(&path).unwrap().is_some());
+    }
+}
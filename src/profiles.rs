@@ -0,0 +1,366 @@
+//! Named profiles: fully separate cass worlds on one machine.
+//!
+//! `cass --profile work search …` keeps work and personal histories apart:
+//! each profile owns its own data dir (database, derived caches, TUI state)
+//! and its own config scope (`sources.toml`, and with it the connector set),
+//! so nothing indexed under one profile is visible from another.
+//!
+//! ## How a profile takes effect
+//!
+//! Profiles piggyback on the two resolution roots every command already
+//! respects: `CASS_DATA_DIR` for storage and the `cass/` config directory
+//! for configuration. At single-threaded startup (`main`, before the async
+//! runtime exists) the active profile — `--profile` flag, then the
+//! `CASS_PROFILE` env var, then the registry's `active` entry — is resolved
+//! and exported as `CASS_PROFILE` plus a profile-scoped `CASS_DATA_DIR`.
+//! From there every command, subprocess, and config read inherits the scope
+//! without any per-command plumbing. The reserved name `default` means "no
+//! profile": the original unscoped paths.
+//!
+//! The registry itself lives *outside* any profile, at
+//! `<config dir>/cass/profiles.toml`, so switching is always possible no
+//! matter which profile is active. Per-profile config lives at
+//! `<config dir>/cass/profiles/<name>/` (see
+//! [`scope_config_path`]), and per-profile data defaults to
+//! `<base data dir>/profiles/<name>`.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Reserved profile name for the unscoped world. Never stored in the
+/// registry; passing it to `--profile` or `cass profile switch` selects the
+/// original paths.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Env var carrying the active profile name; exported at startup so
+/// subprocesses and config reads inherit the scope.
+pub const PROFILE_ENV: &str = "CASS_PROFILE";
+
+/// Errors from registry I/O and profile resolution.
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("Failed to read profiles registry: {0}")]
+    Read(#[from] std::io::Error),
+
+    #[error("Failed to parse profiles registry: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Failed to serialize profiles registry: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Could not determine config directory")]
+    NoConfigDir,
+
+    #[error("Unknown profile '{0}'")]
+    Unknown(String),
+
+    #[error("{0}")]
+    Validation(String),
+}
+
+/// The on-disk registry: which profiles exist and which one is active when
+/// neither the `--profile` flag nor `CASS_PROFILE` says otherwise.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfilesConfig {
+    /// Active profile name; absent means the default (unscoped) world.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+
+    /// Known profiles, in creation order.
+    #[serde(default)]
+    pub profiles: Vec<ProfileDefinition>,
+}
+
+/// One named profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileDefinition {
+    /// Profile name (lowercase alphanumeric plus `-`/`_`).
+    pub name: String,
+
+    /// Explicit data dir override; omitted means
+    /// `<base data dir>/profiles/<name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
+}
+
+/// A resolved profile ready to export: its name and the data dir commands
+/// should scope to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedProfile {
+    pub name: String,
+    pub data_dir: PathBuf,
+}
+
+impl ProfilesConfig {
+    /// Load the registry, treating a missing file as empty.
+    pub fn load() -> Result<Self, ProfileError> {
+        Self::load_from(&registry_path()?)
+    }
+
+    /// Load from an explicit path (tests point this at a temp dir).
+    pub fn load_from(path: &Path) -> Result<Self, ProfileError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Persist the registry, creating parent directories as needed.
+    pub fn save(&self) -> Result<(), ProfileError> {
+        self.save_to(&registry_path()?)
+    }
+
+    /// Persist to an explicit path (tests point this at a temp dir).
+    pub fn save_to(&self, path: &Path) -> Result<(), ProfileError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Find a profile by name.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&ProfileDefinition> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Register a new profile. Rejects invalid names, the reserved
+    /// `default`, and duplicates.
+    pub fn create(
+        &mut self,
+        name: &str,
+        data_dir: Option<PathBuf>,
+    ) -> Result<&ProfileDefinition, ProfileError> {
+        validate_name(name)?;
+        if self.find(name).is_some() {
+            return Err(ProfileError::Validation(format!(
+                "Profile '{name}' already exists"
+            )));
+        }
+        self.profiles.push(ProfileDefinition {
+            name: name.to_string(),
+            data_dir,
+        });
+        Ok(self.profiles.last().expect("just pushed"))
+    }
+
+    /// Mark a profile active (or `default` to clear the scope). The target
+    /// must exist in the registry.
+    pub fn switch(&mut self, name: &str) -> Result<(), ProfileError> {
+        if name == DEFAULT_PROFILE {
+            self.active = None;
+            return Ok(());
+        }
+        if self.find(name).is_none() {
+            return Err(ProfileError::Unknown(name.to_string()));
+        }
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+}
+
+/// Path of the profiles registry: a sibling of `sources.toml` in the shared
+/// (profile-independent) `cass/` config dir, so switching works no matter
+/// which profile is active.
+pub fn registry_path() -> Result<PathBuf, ProfileError> {
+    let sources = crate::sources::config::SourcesConfig::config_path()
+        .map_err(|_| ProfileError::NoConfigDir)?;
+    // `config_path` is profile-scoped once a profile is active; the registry
+    // must not be, so walk back up to the shared `cass/` dir.
+    let mut dir = sources
+        .parent()
+        .ok_or(ProfileError::NoConfigDir)?
+        .to_path_buf();
+    while dir
+        .file_name()
+        .is_some_and(|n| n != std::ffi::OsStr::new("cass"))
+    {
+        let Some(parent) = dir.parent() else { break };
+        dir = parent.to_path_buf();
+    }
+    Ok(dir.join("profiles.toml"))
+}
+
+/// Scope a config file path to the active profile: `…/cass/sources.toml`
+/// becomes `…/cass/profiles/<name>/sources.toml`. Paths pass through
+/// untouched when no profile is active.
+#[must_use]
+pub fn scope_config_path(base: PathBuf) -> PathBuf {
+    let Some(name) = active_profile_name() else {
+        return base;
+    };
+    let Some(file_name) = base.file_name().map(std::ffi::OsStr::to_os_string) else {
+        return base;
+    };
+    match base.parent() {
+        Some(parent) => parent.join("profiles").join(name).join(file_name),
+        None => base,
+    }
+}
+
+/// The profile name currently exported via [`PROFILE_ENV`], if any. The
+/// reserved `default` and empty values read as "no profile".
+#[must_use]
+pub fn active_profile_name() -> Option<String> {
+    let name = dotenvy::var(PROFILE_ENV).ok()?;
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == DEFAULT_PROFILE {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+/// Resolve the profile a run should use: the `--profile` flag wins, then
+/// `CASS_PROFILE`, then the registry's `active` entry. Flag and env values
+/// must name a registered profile (or `default`); a stale registry `active`
+/// entry is ignored rather than bricking every command.
+pub fn resolve_active(flag: Option<&str>) -> Result<Option<AppliedProfile>, ProfileError> {
+    let registry = ProfilesConfig::load()?;
+    let requested = flag
+        .map(str::to_string)
+        .or_else(|| dotenvy::var(PROFILE_ENV).ok().map(|v| v.trim().to_string()))
+        .filter(|name| !name.is_empty());
+    match requested {
+        Some(name) if name == DEFAULT_PROFILE => Ok(None),
+        Some(name) => match registry.find(&name) {
+            Some(profile) => Ok(Some(applied(profile))),
+            None => Err(ProfileError::Unknown(name)),
+        },
+        None => Ok(registry
+            .active
+            .as_deref()
+            .and_then(|name| registry.find(name))
+            .map(applied)),
+    }
+}
+
+/// Data dir for a profile: its explicit override, else
+/// `<base data dir>/profiles/<name>`.
+#[must_use]
+pub fn profile_data_dir(profile: &ProfileDefinition) -> PathBuf {
+    profile
+        .data_dir
+        .clone()
+        .unwrap_or_else(|| base_data_dir().join("profiles").join(&profile.name))
+}
+
+fn applied(profile: &ProfileDefinition) -> AppliedProfile {
+    AppliedProfile {
+        name: profile.name.clone(),
+        data_dir: profile_data_dir(profile),
+    }
+}
+
+/// The unscoped data root: `default_data_dir` minus its `CASS_DATA_DIR`
+/// branch, so per-profile defaults never nest inside an already-applied
+/// profile's dir.
+fn base_data_dir() -> PathBuf {
+    if let Ok(dir) = dotenvy::var("XDG_DATA_HOME") {
+        let trimmed = dir.trim();
+        if !trimmed.is_empty() {
+            return PathBuf::from(trimmed).join("coding-agent-search");
+        }
+    }
+    directories::ProjectDirs::from("com", "coding-agent-search", "coding-agent-search")
+        .map(|p| p.data_dir().to_path_buf())
+        .or_else(|| dirs::home_dir().map(|h| h.join(".coding-agent-search")))
+        .unwrap_or_else(|| PathBuf::from("./data"))
+}
+
+/// Profile names keep to lowercase alphanumerics plus `-`/`_`: they become
+/// directory components and env values, so anything fancier is asking for
+/// quoting bugs.
+fn validate_name(name: &str) -> Result<(), ProfileError> {
+    if name.is_empty() {
+        return Err(ProfileError::Validation(
+            "Profile name cannot be empty".to_string(),
+        ));
+    }
+    if name == DEFAULT_PROFILE {
+        return Err(ProfileError::Validation(format!(
+            "'{DEFAULT_PROFILE}' is reserved for the unscoped profile"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+    {
+        return Err(ProfileError::Validation(format!(
+            "Invalid profile name '{name}': use lowercase letters, digits, '-' or '_'"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_rejects_reserved_invalid_and_duplicate_names() {
+        let mut config = ProfilesConfig::default();
+        assert!(config.create("default", None).is_err());
+        assert!(config.create("Work Laptop", None).is_err());
+        assert!(config.create("", None).is_err());
+        config.create("work", None).expect("valid name");
+        assert!(matches!(
+            config.create("work", None),
+            Err(ProfileError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn switch_requires_a_registered_profile_and_default_clears() {
+        let mut config = ProfilesConfig::default();
+        config.create("work", None).expect("create");
+        assert!(matches!(
+            config.switch("personal"),
+            Err(ProfileError::Unknown(_))
+        ));
+        config.switch("work").expect("switch to registered");
+        assert_eq!(config.active.as_deref(), Some("work"));
+        config.switch(DEFAULT_PROFILE).expect("switch to default");
+        assert_eq!(config.active, None);
+    }
+
+    #[test]
+    fn registry_round_trips_through_toml() {
+        let tmp = tempfile::TempDir::new().expect("temp dir");
+        let path = tmp.path().join("cass").join("profiles.toml");
+        let mut config = ProfilesConfig::default();
+        config
+            .create("work", Some(PathBuf::from("/srv/cass-work")))
+            .expect("create");
+        config.switch("work").expect("switch");
+        config.save_to(&path).expect("save");
+
+        let loaded = ProfilesConfig::load_from(&path).expect("load");
+        assert_eq!(loaded.active.as_deref(), Some("work"));
+        assert_eq!(
+            loaded.find("work").and_then(|p| p.data_dir.clone()),
+            Some(PathBuf::from("/srv/cass-work"))
+        );
+        // Missing file reads as the empty registry, not an error.
+        let empty = ProfilesConfig::load_from(&tmp.path().join("nope.toml")).expect("missing file");
+        assert!(empty.profiles.is_empty());
+    }
+
+    #[test]
+    fn profile_data_dir_prefers_the_explicit_override() {
+        let explicit = ProfileDefinition {
+            name: "work".to_string(),
+            data_dir: Some(PathBuf::from("/srv/cass-work")),
+        };
+        assert_eq!(profile_data_dir(&explicit), PathBuf::from("/srv/cass-work"));
+        let derived = ProfileDefinition {
+            name: "personal".to_string(),
+            data_dir: None,
+        };
+        let dir = profile_data_dir(&derived);
+        assert!(dir.ends_with("profiles/personal"), "got {}", dir.display());
+    }
+}
@@ -0,0 +1,338 @@
+//! Built-in relevance corpus for `cass bench ranking`.
+//!
+//! Ranking tweaks — tokenization changes, new boosts, bm25 weight shifts —
+//! have historically been judged by eyeballing a few searches. This module
+//! pins down a small maintained set of synthetic fixture conversations plus
+//! graded (query → conversation) relevance judgments, so a change can be
+//! evaluated with NDCG and MRR before and after instead of by vibes.
+//!
+//! The corpus is deliberately synthetic and checked in: scores must be
+//! reproducible on any machine without a real session archive, and the
+//! fixtures are written to exercise known ranking traps (snake_case
+//! identifiers, hyphenated terms, a shared term appearing in an unrelated
+//! conversation, short-vs-long document length effects). When a ranking
+//! change legitimately reorders a judged query, update the judgment here in
+//! the same commit and say why — the corpus is the regression baseline, not
+//! an oracle.
+
+/// One synthetic conversation in the benchmark corpus. `slug` is the stable
+/// identity judgments refer to; the bench indexes it under
+/// `/bench/<slug>.jsonl`.
+pub struct BenchFixture {
+    pub slug: &'static str,
+    pub agent: &'static str,
+    pub title: &'static str,
+    /// `(role, content)` pairs; role is `"user"` or `"agent"`.
+    pub messages: &'static [(&'static str, &'static str)],
+}
+
+/// One judged query: graded relevance per fixture slug (3 = exactly what
+/// the searcher wanted, 1 = tangentially useful). Fixtures not listed are
+/// grade 0.
+pub struct JudgedQuery {
+    pub query: &'static str,
+    pub judgments: &'static [(&'static str, u32)],
+}
+
+/// The fixture conversations the benchmark indexes.
+pub const FIXTURES: &[BenchFixture] = &[
+    BenchFixture {
+        slug: "borrow-checker",
+        agent: "claude_code",
+        title: "Borrow checker fight in the watcher",
+        messages: &[
+            (
+                "user",
+                "the borrow checker rejects this: cannot borrow `*self` as mutable \
+                 more than once at a time (E0499) in the watcher loop",
+            ),
+            (
+                "agent",
+                "split the method: take the pending paths out with mem::take before \
+                 iterating, then the second mutable borrow disappears",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "pool-exhausted",
+        agent: "codex",
+        title: "Requests hang when the pool runs dry",
+        messages: &[
+            (
+                "user",
+                "under load every request hangs; the log says connection pool \
+                 exhausted and then deadline exceeded",
+            ),
+            (
+                "agent",
+                "the handler holds its pooled connection across the outbound call; \
+                 drop it before awaiting and the connection pool stops starving",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "pool-tuning",
+        agent: "claude_code",
+        title: "Sizing the database connection pool",
+        messages: &[
+            (
+                "user",
+                "what should max_connections be for the connection pool on an 8-core box?",
+            ),
+            (
+                "agent",
+                "start at cores * 2 for the connection pool and raise it only if the \
+                 pool wait histogram shows queueing; a huge pool just moves the \
+                 contention into the database",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "emoji-panic",
+        agent: "claude_code",
+        title: "Tokenizer panics on emoji input",
+        messages: &[
+            (
+                "user",
+                "the tokenizer panics with byte index not a char boundary whenever \
+                 the input contains emoji",
+            ),
+            (
+                "agent",
+                "the offset math assumes one byte per char; walk char_indices and \
+                 clamp the byte offset to the previous boundary",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "rate-limit",
+        agent: "codex",
+        title: "Retrying through API rate limits",
+        messages: &[
+            (
+                "user",
+                "the sync job dies on HTTP 429 rate limit responses from the API",
+            ),
+            (
+                "agent",
+                "honor Retry-After when present, otherwise exponential backoff with \
+                 jitter; without jitter every worker retries the rate limit in \
+                 lockstep and you get a thundering herd",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "graph-jitter",
+        agent: "claude_code",
+        title: "Latency graph jitter on the dashboard",
+        messages: &[
+            (
+                "user",
+                "the p99 latency graph shows constant jitter even when traffic is flat",
+            ),
+            (
+                "agent",
+                "the panel samples a 10s window against a 15s scrape interval; align \
+                 the two and the jitter in the graph smooths out",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "schema-backfill",
+        agent: "codex",
+        title: "Migration with a derived-table backfill",
+        messages: &[
+            (
+                "user",
+                "I need a schema migration that adds a derived table and backfills \
+                 it from existing rows without blocking writers",
+            ),
+            (
+                "agent",
+                "bump schema_version, create the table in the migration, and do the \
+                 backfill in batches from application code so the migration itself \
+                 stays fast",
+            ),
+        ],
+    },
+    BenchFixture {
+        slug: "cjk-alignment",
+        agent: "gemini",
+        title: "Table columns misalign on CJK text",
+        messages: &[
+            (
+                "user",
+                "table column alignment breaks when a cell contains CJK characters",
+            ),
+            (
+                "agent",
+                "pad by display width, not char count: use unicode-width per \
+                 grapheme cluster so double-width characters count as two columns",
+            ),
+        ],
+    },
+];
+
+/// The maintained judgments. Keep queries phrased the way a user would type
+/// them; every ranking trap a fixture encodes should have at least one
+/// query exercising it.
+pub const JUDGED_QUERIES: &[JudgedQuery] = &[
+    JudgedQuery {
+        query: "borrow checker mutable borrow",
+        judgments: &[("borrow-checker", 3)],
+    },
+    JudgedQuery {
+        query: "connection pool exhausted",
+        judgments: &[("pool-exhausted", 3), ("pool-tuning", 2)],
+    },
+    JudgedQuery {
+        query: "connection pool sizing",
+        judgments: &[("pool-tuning", 3), ("pool-exhausted", 1)],
+    },
+    JudgedQuery {
+        query: "tokenizer panic emoji",
+        judgments: &[("emoji-panic", 3)],
+    },
+    JudgedQuery {
+        query: "rate limit backoff jitter",
+        judgments: &[("rate-limit", 3), ("graph-jitter", 1)],
+    },
+    JudgedQuery {
+        query: "latency graph jitter",
+        judgments: &[("graph-jitter", 3), ("rate-limit", 1)],
+    },
+    JudgedQuery {
+        query: "schema migration backfill",
+        judgments: &[("schema-backfill", 3)],
+    },
+    JudgedQuery {
+        query: "unicode width alignment",
+        judgments: &[("cjk-alignment", 3), ("emoji-panic", 1)],
+    },
+];
+
+/// Source path a fixture is indexed under; hits are mapped back to slugs by
+/// [`slug_from_source_path`].
+#[must_use]
+pub fn fixture_source_path(slug: &str) -> String {
+    format!("/bench/{slug}.jsonl")
+}
+
+/// Inverse of [`fixture_source_path`].
+#[must_use]
+pub fn slug_from_source_path(path: &str) -> Option<&str> {
+    path.strip_prefix("/bench/")?.strip_suffix(".jsonl")
+}
+
+impl JudgedQuery {
+    fn gain(&self, slug: &str) -> u32 {
+        self.judgments
+            .iter()
+            .find(|(judged, _)| *judged == slug)
+            .map_or(0, |(_, gain)| *gain)
+    }
+}
+
+/// NDCG@k for one judged query over `ranked` (conversation slugs, best
+/// first). Uses the graded formulation `(2^gain - 1) / log2(rank + 1)`;
+/// the ideal ordering is the query's judgments sorted by gain. 1.0 means
+/// the ranking matched the judgments exactly; a query with no judged
+/// results returned scores 0.
+#[must_use]
+pub fn ndcg_at(ranked: &[&str], judged: &JudgedQuery, k: usize) -> f64 {
+    let dcg: f64 = ranked
+        .iter()
+        .take(k)
+        .enumerate()
+        .map(|(i, slug)| discounted_gain(judged.gain(slug), i))
+        .sum();
+    let mut ideal: Vec<u32> = judged.judgments.iter().map(|(_, gain)| *gain).collect();
+    ideal.sort_unstable_by(|a, b| b.cmp(a));
+    let idcg: f64 = ideal
+        .iter()
+        .take(k)
+        .enumerate()
+        .map(|(i, gain)| discounted_gain(*gain, i))
+        .sum();
+    if idcg == 0.0 { 0.0 } else { dcg / idcg }
+}
+
+fn discounted_gain(gain: u32, zero_based_rank: usize) -> f64 {
+    if gain == 0 {
+        return 0.0;
+    }
+    (2f64.powi(gain as i32) - 1.0) / ((zero_based_rank as f64) + 2.0).log2()
+}
+
+/// Reciprocal rank of the first relevant (gain > 0) slug in `ranked`, or
+/// 0 when none surfaced.
+#[must_use]
+pub fn reciprocal_rank(ranked: &[&str], judged: &JudgedQuery) -> f64 {
+    ranked
+        .iter()
+        .position(|slug| judged.gain(slug) > 0)
+        .map_or(0.0, |i| 1.0 / ((i as f64) + 1.0))
+}
+
+/// Per-query scores plus the observed ranking, for the report.
+#[derive(Debug, serde::Serialize)]
+pub struct QueryScore {
+    pub query: String,
+    pub ndcg: f64,
+    pub reciprocal_rank: f64,
+    /// Judged-corpus slugs in the order the engine returned them.
+    pub ranked: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_judgments_reference_real_fixtures() {
+        for judged in JUDGED_QUERIES {
+            for (slug, gain) in judged.judgments {
+                assert!(
+                    FIXTURES.iter().any(|f| f.slug == *slug),
+                    "query {:?} judges unknown fixture {slug:?}",
+                    judged.query
+                );
+                assert!(*gain > 0, "grade-0 judgments should be omitted");
+            }
+        }
+        for fixture in FIXTURES {
+            assert_eq!(
+                slug_from_source_path(&fixture_source_path(fixture.slug)),
+                Some(fixture.slug)
+            );
+        }
+    }
+
+    #[test]
+    fn ndcg_rewards_judged_order_and_penalizes_swaps() {
+        let judged = &JudgedQuery {
+            query: "q",
+            judgments: &[("best", 3), ("ok", 1)],
+        };
+        let perfect = ndcg_at(&["best", "ok"], judged, 10);
+        assert!((perfect - 1.0).abs() < 1e-9);
+
+        let swapped = ndcg_at(&["ok", "best"], judged, 10);
+        assert!(swapped < perfect && swapped > 0.0);
+
+        assert_eq!(ndcg_at(&["other"], judged, 10), 0.0);
+        // k truncation: a relevant hit below the cutoff contributes nothing.
+        assert_eq!(ndcg_at(&["other", "best"], judged, 1), 0.0);
+    }
+
+    #[test]
+    fn reciprocal_rank_is_one_over_first_relevant_position() {
+        let judged = &JudgedQuery {
+            query: "q",
+            judgments: &[("hit", 2)],
+        };
+        assert_eq!(reciprocal_rank(&["hit"], judged), 1.0);
+        assert_eq!(reciprocal_rank(&["miss", "hit"], judged), 0.5);
+        assert_eq!(reciprocal_rank(&["miss"], judged), 0.0);
+    }
+}
@@ -0,0 +1,487 @@
+//! User-defined scoring hook for search results.
+//!
+//! Advanced users can point `[search] ranking_script` in
+//! `~/.config/cass/cass.toml` (or `CASS_RANKING_SCRIPT`) at a small script
+//! file whose single expression computes each hit's final score from named
+//! features. The expression is re-evaluated per hit and the result list is
+//! re-sorted by the returned score, so ranking can be tuned without
+//! recompiling cass.
+//!
+//! The script language is a deliberately tiny arithmetic expression dialect
+//! evaluated in-process (`+ - * /`, parentheses, `min`/`max`/`abs`/`log`,
+//! numeric literals, feature identifiers) rather than an embedded Rhai/Lua
+//! interpreter: the hook runs once per hit on the hot search path, and a
+//! dependency-free evaluator keeps it sandboxed by construction — no I/O, no
+//! loops, no way for a script to hang a search. Unknown identifiers evaluate
+//! to `0.0` so a script written for a newer cass degrades instead of erroring.
+//!
+//! Available features per hit:
+//!
+//! - `score` — the built-in ranking score the hit arrived with
+//! - `age_days` — days since the hit's message timestamp (0 when unknown)
+//! - `tokens` — chars/4 estimate of the hit's content
+//! - `line` — line number in the source file (0 when unknown)
+//! - `agent_<slug>` — 1 for the hit's agent, e.g. `agent_claude_code`
+//!
+//! Example script: `score * 0.7 + max(0, 5 - age_days) * 0.2 + agent_codex`
+//!
+//! The built-in ranking is the safe default — no script configured means no
+//! behavior change — and `--no-user-ranking` skips a configured script for
+//! one invocation (e.g. to compare rankings or bypass a broken script).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::search::query::SearchHit;
+
+/// A compiled user ranking expression.
+#[derive(Debug, Clone)]
+pub struct RankingProgram {
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+impl RankingProgram {
+    /// Compile a script source (comments starting with `#` and blank lines
+    /// are stripped; the remainder must be one expression).
+    pub fn compile(source: &str) -> Result<Self, String> {
+        let code: String = source
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut parser = Parser::new(&code)?;
+        let expr = parser.parse_expr()?;
+        parser.expect_end()?;
+        Ok(Self { expr })
+    }
+
+    /// Evaluate the expression against one hit's features.
+    #[must_use]
+    pub fn score(&self, features: &HashMap<String, f64>) -> f64 {
+        eval(&self.expr, features)
+    }
+}
+
+fn eval(expr: &Expr, vars: &HashMap<String, f64>) -> f64 {
+    match expr {
+        Expr::Number(n) => *n,
+        Expr::Var(name) => vars.get(name).copied().unwrap_or(0.0),
+        Expr::Neg(inner) => -eval(inner, vars),
+        Expr::Add(a, b) => eval(a, vars) + eval(b, vars),
+        Expr::Sub(a, b) => eval(a, vars) - eval(b, vars),
+        Expr::Mul(a, b) => eval(a, vars) * eval(b, vars),
+        Expr::Div(a, b) => {
+            let denom = eval(b, vars);
+            if denom == 0.0 { 0.0 } else { eval(a, vars) / denom }
+        }
+        Expr::Call(name, args) => {
+            let vals: Vec<f64> = args.iter().map(|a| eval(a, vars)).collect();
+            match (name.as_str(), vals.as_slice()) {
+                ("min", [a, b]) => a.min(*b),
+                ("max", [a, b]) => a.max(*b),
+                ("abs", [a]) => a.abs(),
+                ("log", [a]) if *a > 0.0 => a.ln(),
+                _ => 0.0,
+            }
+        }
+    }
+}
+
+/// Nesting cap for the recursive-descent parser. Ranking scripts come
+/// from a user config file, so a runaway `((((…` or `----…` chain is a
+/// typo or a hostile paste, not a real formula; reject it with a normal
+/// parse error instead of overflowing the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl Parser {
+    fn new(code: &str) -> Result<Self, String> {
+        let mut tokens = Vec::new();
+        let mut chars = code.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            match c {
+                ' ' | '\t' | '\n' | '\r' => {
+                    chars.next();
+                }
+                '+' => {
+                    chars.next();
+                    tokens.push(Token::Plus);
+                }
+                '-' => {
+                    chars.next();
+                    tokens.push(Token::Minus);
+                }
+                '*' => {
+                    chars.next();
+                    tokens.push(Token::Star);
+                }
+                '/' => {
+                    chars.next();
+                    tokens.push(Token::Slash);
+                }
+                '(' => {
+                    chars.next();
+                    tokens.push(Token::LParen);
+                }
+                ')' => {
+                    chars.next();
+                    tokens.push(Token::RParen);
+                }
+                ',' => {
+                    chars.next();
+                    tokens.push(Token::Comma);
+                }
+                '0'..='9' | '.' => {
+                    let mut num = String::new();
+                    while let Some(&d) = chars.peek() {
+                        if d.is_ascii_digit() || d == '.' {
+                            num.push(d);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    let value = num
+                        .parse::<f64>()
+                        .map_err(|_| format!("invalid number '{num}'"))?;
+                    tokens.push(Token::Number(value));
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut ident = String::new();
+                    while let Some(&d) = chars.peek() {
+                        if d.is_ascii_alphanumeric() || d == '_' {
+                            ident.push(d);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    tokens.push(Token::Ident(ident));
+                }
+                other => return Err(format!("unexpected character '{other}'")),
+            }
+        }
+        Ok(Self {
+            tokens,
+            pos: 0,
+            depth: 0,
+        })
+    }
+
+    /// Guard for the points where the grammar nests (negation, `(…)`
+    /// groups, call arguments): error out past [`MAX_NESTING_DEPTH`]
+    /// levels instead of recursing unbounded.
+    fn descend<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(format!(
+                "expression nests deeper than {MAX_NESTING_DEPTH} levels"
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_term()?;
+        while let Some(op) = self.peek().cloned() {
+            match op {
+                Token::Plus => {
+                    self.next();
+                    lhs = Expr::Add(Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                Token::Minus => {
+                    self.next();
+                    lhs = Expr::Sub(Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_factor()?;
+        while let Some(op) = self.peek().cloned() {
+            match op {
+                Token::Star => {
+                    self.next();
+                    lhs = Expr::Mul(Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                Token::Slash => {
+                    self.next();
+                    lhs = Expr::Div(Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_factor(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Minus) => Ok(Expr::Neg(Box::new(self.descend(Self::parse_factor)?))),
+            Some(Token::LParen) => {
+                let inner = self.descend(Self::parse_expr)?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("missing closing ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.next();
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.descend(Self::parse_expr)?);
+                            match self.next() {
+                                Some(Token::Comma) => {}
+                                Some(Token::RParen) => break,
+                                _ => return Err(format!("unterminated call to '{name}'")),
+                            }
+                        }
+                    } else {
+                        self.next();
+                    }
+                    Ok(Expr::Call(name, args))
+                } else {
+                    Ok(Expr::Var(name))
+                }
+            }
+            other => Err(format!("unexpected token {other:?}")),
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), String> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(format!("trailing input starting at {token:?}")),
+        }
+    }
+}
+
+/// Features exposed to the script for one hit.
+#[must_use]
+pub fn features_for_hit(hit: &SearchHit, now_ms: i64) -> HashMap<String, f64> {
+    let mut features = HashMap::new();
+    features.insert("score".to_string(), f64::from(hit.score));
+    let age_days = hit
+        .created_at
+        .map(|ts| ((now_ms - ts).max(0) as f64) / 86_400_000.0)
+        .unwrap_or(0.0);
+    features.insert("age_days".to_string(), age_days);
+    features.insert(
+        "tokens".to_string(),
+        (hit.content.chars().count() / 4) as f64,
+    );
+    features.insert(
+        "line".to_string(),
+        hit.line_number.map(|n| n as f64).unwrap_or(0.0),
+    );
+    let agent_slug: String = hit
+        .agent
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    features.insert(format!("agent_{agent_slug}"), 1.0);
+    features
+}
+
+/// Re-score and re-sort hits with a compiled program. A hit whose script
+/// result is non-finite keeps its built-in score.
+pub fn apply_user_ranking(hits: &mut [SearchHit], program: &RankingProgram) {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    for hit in hits.iter_mut() {
+        let scored = program.score(&features_for_hit(hit, now_ms));
+        if scored.is_finite() {
+            hit.score = scored as f32;
+        }
+    }
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Resolve the configured script path: `CASS_RANKING_SCRIPT` (empty string
+/// disables), then `[search] ranking_script` in `cass.toml`.
+#[must_use]
+pub fn configured_script_path() -> Option<PathBuf> {
+    if let Ok(value) = dotenvy::var("CASS_RANKING_SCRIPT") {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        return Some(PathBuf::from(trimmed));
+    }
+    crate::search_defaults::load_search_defaults()
+        .ok()
+        .and_then(|defaults| defaults.ranking_script)
+        .map(PathBuf::from)
+}
+
+/// Load and compile the configured script, if any. Errors carry the path so
+/// the caller can surface a usable message.
+pub fn load_user_ranking() -> Result<Option<RankingProgram>, String> {
+    let Some(path) = configured_script_path() else {
+        return Ok(None);
+    };
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read ranking script {}: {e}", path.display()))?;
+    let program = RankingProgram::compile(&source)
+        .map_err(|e| format!("invalid ranking script {}: {e}", path.display()))?;
+    Ok(Some(program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), *v))
+            .collect()
+    }
+
+    #[test]
+    fn arithmetic_precedence_and_parens() {
+        let program = RankingProgram::compile("1 + 2 * 3").unwrap();
+        assert!((program.score(&HashMap::new()) - 7.0).abs() < 1e-9);
+
+        let program = RankingProgram::compile("(1 + 2) * 3").unwrap();
+        assert!((program.score(&HashMap::new()) - 9.0).abs() < 1e-9);
+
+        let program = RankingProgram::compile("-score + 1").unwrap();
+        assert!((program.score(&vars(&[("score", 2.5)])) + 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn functions_and_unknown_identifiers() {
+        let program = RankingProgram::compile("max(score, 3) + min(1, 2) + abs(-2)").unwrap();
+        assert!((program.score(&vars(&[("score", 5.0)])) - 10.0).abs() < 1e-9);
+
+        // Unknown identifiers are 0, not errors (forward compatibility).
+        let program = RankingProgram::compile("score + some_future_feature").unwrap();
+        assert!((program.score(&vars(&[("score", 1.0)])) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn comments_and_division_by_zero_are_safe() {
+        let program = RankingProgram::compile(
+            "# weight recency\nscore / age_days  # both lines commented\n",
+        )
+        .unwrap();
+        // age_days missing => division by zero => 0, not inf.
+        assert_eq!(program.score(&vars(&[("score", 4.0)])), 0.0);
+    }
+
+    #[test]
+    fn compile_errors_are_reported() {
+        assert!(RankingProgram::compile("score +").is_err());
+        assert!(RankingProgram::compile("score ^ 2").is_err());
+        assert!(RankingProgram::compile("(score").is_err());
+        assert!(RankingProgram::compile("score 2").is_err());
+    }
+
+    #[test]
+    fn pathological_nesting_is_a_parse_error_not_a_crash() {
+        let parens = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        assert!(RankingProgram::compile(&parens).is_err());
+
+        let negs = format!("{}1", "-".repeat(10_000));
+        assert!(RankingProgram::compile(&negs).is_err());
+
+        // Reasonable nesting stays well under the cap.
+        let sane = format!("{}1{}", "(".repeat(16), ")".repeat(16));
+        assert!(RankingProgram::compile(&sane).is_ok());
+    }
+
+    #[test]
+    fn ranking_rescores_and_resorts_hits() {
+        let mut hits = vec![
+            hit_with("old", 0.9, Some(0)),
+            hit_with("new", 0.5, Some(chrono::Utc::now().timestamp_millis())),
+        ];
+        let program = RankingProgram::compile("score + max(0, 10 - age_days)").unwrap();
+        apply_user_ranking(&mut hits, &program);
+        // The fresh hit wins despite the lower built-in score.
+        assert_eq!(hits[0].title, "new");
+    }
+
+    fn hit_with(title: &str, score: f32, created_at: Option<i64>) -> SearchHit {
+        SearchHit {
+            title: title.to_string(),
+            snippet: String::new(),
+            content: "body".to_string(),
+            content_hash: 0,
+            conversation_id: None,
+            score,
+            source_path: "/tmp/s.jsonl".to_string(),
+            agent: "claude_code".to_string(),
+            workspace: "/tmp/ws".to_string(),
+            workspace_original: None,
+            created_at,
+            line_number: Some(1),
+            match_type: Default::default(),
+            source_id: "local".to_string(),
+            origin_kind: "local".to_string(),
+            origin_host: None,
+        }
+    }
+}
@@ -973,6 +973,97 @@ pub fn merge_manifest_db_links(
     merge_raw_mirror_manifest_db_links(&root, &manifest_path, links, None)
 }
 
+/// A raw-mirror capture resolved back to on-disk evidence for one conversation.
+///
+/// Produced by [`find_capture_for_conversation`]; `blob_path` points at the
+/// verbatim original bytes (captures are stored uncompressed and unencrypted),
+/// so debug tooling can replay the current parser against them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RawMirrorLocatedCapture {
+    pub manifest_id: String,
+    pub blob_path: PathBuf,
+    pub blob_blake3: String,
+    pub blob_size_bytes: u64,
+    pub provider: String,
+    pub source_id: String,
+    pub original_path: String,
+    pub captured_at_ms: i64,
+    pub source_mtime_ms: Option<i64>,
+    pub linked_message_count: Option<usize>,
+}
+
+/// Find the newest raw-mirror capture whose `db_links` reference
+/// `conversation_id`.
+///
+/// Manifests that fail to parse, whose blob file is missing, or that record a
+/// non-verbatim blob (compressed/encrypted envelopes) are skipped rather than
+/// surfaced as errors: the caller wants the best replayable evidence, and a
+/// damaged sibling manifest should not block that. Returns `Ok(None)` when the
+/// mirror is uninitialized or holds no capture for the conversation.
+pub fn find_capture_for_conversation(
+    data_dir: &Path,
+    conversation_id: i64,
+) -> Result<Option<RawMirrorLocatedCapture>> {
+    let root = raw_mirror_root(data_dir);
+    let manifests_dir = root.join("manifests");
+    let entries = match fs::read_dir(&manifests_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("list raw mirror manifests {}", manifests_dir.display()));
+        }
+    };
+
+    let mut best: Option<RawMirrorLocatedCapture> = None;
+    for entry in entries {
+        let Ok(entry) = entry else { continue };
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(manifest) = read_raw_mirror_manifest(&path) else {
+            continue;
+        };
+        let Some(link) = manifest
+            .db_links
+            .iter()
+            .find(|link| link.conversation_id == Some(conversation_id))
+        else {
+            continue;
+        };
+        if manifest.compression.state != "none" || manifest.encryption.state != "none" {
+            continue;
+        }
+        let Some(blob_relative_path) = raw_mirror_blob_relative_path(&manifest.blob_blake3) else {
+            continue;
+        };
+        let blob_path = root.join(blob_relative_path);
+        if !blob_path.is_file() {
+            continue;
+        }
+        if best
+            .as_ref()
+            .is_some_and(|current| current.captured_at_ms >= manifest.captured_at_ms)
+        {
+            continue;
+        }
+        best = Some(RawMirrorLocatedCapture {
+            manifest_id: manifest.manifest_id,
+            blob_path,
+            blob_blake3: manifest.blob_blake3,
+            blob_size_bytes: manifest.blob_size_bytes,
+            provider: manifest.provider,
+            source_id: manifest.source_id,
+            original_path: manifest.original_path,
+            captured_at_ms: manifest.captured_at_ms,
+            source_mtime_ms: manifest.source_mtime_ms,
+            linked_message_count: link.message_count,
+        });
+    }
+    Ok(best)
+}
+
 struct CopyToTempResult {
     temp_path: PathBuf,
     blob_blake3: String,
@@ -2869,4 +2960,158 @@ mod tests {
             "unexpected error: {err:#}"
         );
     }
+
+    /// Capture `contents` under `file_name` with a single db-link pointing at
+    /// `conversation_id`, for `find_capture_for_conversation` tests.
+    fn capture_linked_to_conversation(
+        temp: &Path,
+        data_dir: &Path,
+        file_name: &str,
+        contents: &[u8],
+        conversation_id: i64,
+        message_count: usize,
+    ) -> RawMirrorCaptureRecord {
+        let source_path = temp.join(file_name);
+        fs::write(&source_path, contents).expect("write source");
+        capture_source_file(RawMirrorCaptureInput {
+            data_dir,
+            provider: "codex",
+            source_id: "local",
+            origin_kind: "local",
+            origin_host: None,
+            source_path: &source_path,
+            db_links: &[RawMirrorDbLink {
+                conversation_id: Some(conversation_id),
+                message_count: Some(message_count),
+                source_path: Some(source_path.display().to_string()),
+                started_at_ms: Some(1_733_000_000_000),
+            }],
+        })
+        .expect("capture source")
+    }
+
+    /// Edit a published manifest in place, bypassing the capture path, to
+    /// simulate manifests written by other (e.g. future compressed) writers.
+    fn rewrite_manifest_json(
+        data_dir: &Path,
+        manifest_relative_path: &str,
+        edit: impl FnOnce(&mut Value),
+    ) {
+        let manifest_path = data_dir
+            .join(RAW_MIRROR_ROOT_DIR)
+            .join(RAW_MIRROR_VERSION_DIR)
+            .join(manifest_relative_path);
+        let mut manifest: Value =
+            serde_json::from_slice(&fs::read(&manifest_path).expect("read manifest"))
+                .expect("parse manifest");
+        edit(&mut manifest);
+        fs::write(
+            &manifest_path,
+            serde_json::to_vec_pretty(&manifest).expect("serialize manifest"),
+        )
+        .expect("rewrite manifest");
+    }
+
+    #[test]
+    fn find_capture_returns_none_for_uninitialized_mirror() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let data_dir = temp.path().join("cass-data");
+        let found = find_capture_for_conversation(&data_dir, 42).expect("lookup");
+        assert!(found.is_none(), "no mirror means no capture, not an error");
+    }
+
+    #[test]
+    fn find_capture_prefers_the_newest_capture() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let data_dir = temp.path().join("cass-data");
+        let older =
+            capture_linked_to_conversation(temp.path(), &data_dir, "old.jsonl", b"old\n", 7, 1);
+        let newer =
+            capture_linked_to_conversation(temp.path(), &data_dir, "new.jsonl", b"new\n", 7, 2);
+        // Captures within the same test run share a wall-clock millisecond;
+        // pin distinct timestamps so "newest" is unambiguous.
+        rewrite_manifest_json(&data_dir, &older.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(1_000);
+        });
+        rewrite_manifest_json(&data_dir, &newer.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(2_000);
+        });
+
+        let found = find_capture_for_conversation(&data_dir, 7)
+            .expect("lookup")
+            .expect("capture exists");
+        assert_eq!(found.manifest_id, newer.manifest_id);
+        assert_eq!(found.captured_at_ms, 2_000);
+        assert_eq!(found.blob_blake3, newer.blob_blake3);
+        assert_eq!(found.linked_message_count, Some(2));
+        assert_eq!(fs::read(&found.blob_path).expect("blob bytes"), b"new\n");
+
+        let other = find_capture_for_conversation(&data_dir, 8).expect("lookup");
+        assert!(other.is_none(), "unlinked conversations find no capture");
+    }
+
+    #[test]
+    fn find_capture_skips_compressed_and_encrypted_manifests() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let data_dir = temp.path().join("cass-data");
+        let verbatim =
+            capture_linked_to_conversation(temp.path(), &data_dir, "plain.jsonl", b"plain\n", 9, 1);
+        let compressed =
+            capture_linked_to_conversation(temp.path(), &data_dir, "zstd.jsonl", b"zstd\n", 9, 1);
+        let encrypted =
+            capture_linked_to_conversation(temp.path(), &data_dir, "aes.jsonl", b"aes\n", 9, 1);
+        rewrite_manifest_json(&data_dir, &verbatim.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(1_000);
+        });
+        rewrite_manifest_json(&data_dir, &compressed.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(2_000);
+            manifest["compression"]["state"] = json!("compressed");
+            manifest["compression"]["algorithm"] = json!("zstd");
+        });
+        rewrite_manifest_json(&data_dir, &encrypted.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(3_000);
+            manifest["encryption"]["state"] = json!("encrypted");
+            manifest["encryption"]["algorithm"] = json!("aes-256-gcm");
+        });
+
+        let found = find_capture_for_conversation(&data_dir, 9)
+            .expect("lookup")
+            .expect("verbatim capture exists");
+        assert_eq!(
+            found.manifest_id, verbatim.manifest_id,
+            "only verbatim blobs are replayable evidence; newer non-verbatim manifests must be skipped"
+        );
+    }
+
+    #[test]
+    fn find_capture_skips_manifests_with_missing_blobs() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let data_dir = temp.path().join("cass-data");
+        let older =
+            capture_linked_to_conversation(temp.path(), &data_dir, "kept.jsonl", b"kept\n", 11, 1);
+        let newer =
+            capture_linked_to_conversation(temp.path(), &data_dir, "gone.jsonl", b"gone\n", 11, 1);
+        rewrite_manifest_json(&data_dir, &older.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(1_000);
+        });
+        rewrite_manifest_json(&data_dir, &newer.manifest_relative_path, |manifest| {
+            manifest["captured_at_ms"] = json!(2_000);
+        });
+        let root = data_dir
+            .join(RAW_MIRROR_ROOT_DIR)
+            .join(RAW_MIRROR_VERSION_DIR);
+        fs::remove_file(root.join(&newer.blob_relative_path)).expect("remove newer blob");
+
+        let found = find_capture_for_conversation(&data_dir, 11)
+            .expect("lookup")
+            .expect("older capture still has its blob");
+        assert_eq!(found.manifest_id, older.manifest_id);
+
+        fs::remove_file(root.join(&older.blob_relative_path)).expect("remove older blob");
+        let none = find_capture_for_conversation(&data_dir, 11).expect("lookup");
+        assert!(
+            none.is_none(),
+            "manifests whose blobs are gone are not evidence"
+        );
+    }
 }
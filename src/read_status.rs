@@ -0,0 +1,128 @@
+//! Read/unread status for conversations.
+//!
+//! A conversation is "unread" when it has message activity the user has not
+//! looked at: its latest message timestamp is newer than its
+//! `conversation_views.last_viewed_at` row (written whenever a session is
+//! opened in the TUI or via `cass view` / `cass show`), and newer than the
+//! mark-all-read watermark. With watch mode indexing overnight agent runs,
+//! `cass sessions --unread` becomes an inbox of what happened while you were
+//! away.
+//!
+//! The watermark lives in a small JSON file under the data dir, like the
+//! watch and focus control files, so `cass mark-read` never needs write
+//! access to the canonical DB and never inflates view counts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Read-marker file name under the data dir.
+const READ_MARKER_FILE: &str = "read_marker.json";
+
+/// Mark-all-read watermark, written by `cass mark-read`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ReadMarker {
+    /// Conversations whose last activity is at or before this instant
+    /// (unix millis) count as read even if they were never opened.
+    #[serde(default)]
+    pub all_read_before_ms: i64,
+}
+
+/// Path of the read-marker file under `data_dir`.
+#[must_use]
+pub fn marker_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(READ_MARKER_FILE)
+}
+
+/// Read the current watermark. A missing or unparseable file means "never
+/// marked" — like the other control files, this is advisory.
+#[must_use]
+pub fn load_marker(data_dir: &Path) -> ReadMarker {
+    std::fs::read_to_string(marker_path(data_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Mark everything with activity up to `now_ms` as read. The watermark only
+/// moves forward: re-running `cass mark-read` after a restore from an old
+/// backup cannot resurrect already-dismissed sessions as unread.
+pub fn mark_all_read(data_dir: &Path, now_ms: i64) -> Result<ReadMarker> {
+    let marker = ReadMarker {
+        all_read_before_ms: load_marker(data_dir).all_read_before_ms.max(now_ms),
+    };
+    let path = marker_path(data_dir);
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("creating data dir {}", data_dir.display()))?;
+    std::fs::write(&path, serde_json::to_string_pretty(&marker)?)
+        .with_context(|| format!("writing read marker {}", path.display()))?;
+    Ok(marker)
+}
+
+/// Whether a conversation counts as unread given its latest activity, its
+/// last-viewed instant (from `conversation_views`), and the watermark.
+///
+/// A conversation with no recorded timestamps is never unread: without a
+/// clock there is nothing to compare, and flagging the entire legacy corpus
+/// as unread would make the indicator useless.
+#[must_use]
+pub fn is_unread(
+    last_activity_ms: Option<i64>,
+    last_viewed_at_ms: Option<i64>,
+    marker: &ReadMarker,
+) -> bool {
+    let Some(activity) = last_activity_ms else {
+        return false;
+    };
+    if activity <= marker.all_read_before_ms {
+        return false;
+    }
+    match last_viewed_at_ms {
+        Some(viewed) => activity > viewed,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn never_opened_sessions_with_activity_are_unread() {
+        let marker = ReadMarker::default();
+        assert!(is_unread(Some(1_000), None, &marker));
+        assert!(!is_unread(None, None, &marker), "no clock, no inbox");
+    }
+
+    #[test]
+    fn opening_after_the_last_message_marks_read() {
+        let marker = ReadMarker::default();
+        assert!(!is_unread(Some(1_000), Some(2_000), &marker));
+        assert!(
+            is_unread(Some(3_000), Some(2_000), &marker),
+            "new activity after the last open is unread again"
+        );
+    }
+
+    #[test]
+    fn watermark_covers_unopened_sessions() {
+        let dir = tempdir().unwrap();
+        assert_eq!(load_marker(dir.path()).all_read_before_ms, 0);
+
+        let marker = mark_all_read(dir.path(), 5_000).unwrap();
+        assert!(!is_unread(Some(4_000), None, &marker));
+        assert!(is_unread(Some(6_000), None, &marker));
+
+        // The watermark never moves backwards.
+        let marker = mark_all_read(dir.path(), 1_000).unwrap();
+        assert_eq!(marker.all_read_before_ms, 5_000);
+    }
+
+    #[test]
+    fn corrupt_marker_means_never_marked() {
+        let dir = tempdir().unwrap();
+        std::fs::write(marker_path(dir.path()), "{not json").unwrap();
+        assert_eq!(load_marker(dir.path()).all_read_before_ms, 0);
+    }
+}
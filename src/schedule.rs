@@ -0,0 +1,460 @@
+//! Built-in scheduler integration for periodic `cass index` runs.
+//!
+//! Writing a launchd plist or systemd user timer by hand is the kind of
+//! platform trivia this tool should absorb. `cass schedule install --every
+//! 30m` generates the platform-appropriate unit pointing at the current
+//! binary and registers it with the native scheduler:
+//!
+//! - macOS: a LaunchAgent plist under `~/Library/LaunchAgents`, loaded with
+//!   `launchctl`.
+//! - Linux: a systemd *user* service + timer under
+//!   `~/.config/systemd/user`, enabled with `systemctl --user`.
+//! - Windows: a Task Scheduler entry registered with `schtasks`.
+//!
+//! Unit rendering is pure (and tested); only install/uninstall/status shell
+//! out to the platform tools, and those calls surface stderr verbatim so a
+//! refusal (e.g. no systemd user session over ssh) is diagnosable.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Label used for the launchd job and Windows task; systemd units derive
+/// `cass-index.service` / `cass-index.timer` from the same stem.
+pub const SCHEDULE_LABEL: &str = "com.cass.index";
+
+/// Stem for systemd user units.
+pub const SYSTEMD_UNIT_STEM: &str = "cass-index";
+
+/// Which native scheduler this build targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulerKind {
+    Launchd,
+    SystemdUser,
+    WindowsTaskScheduler,
+}
+
+impl SchedulerKind {
+    /// The scheduler for the running platform.
+    #[must_use]
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            SchedulerKind::Launchd
+        } else if cfg!(windows) {
+            SchedulerKind::WindowsTaskScheduler
+        } else {
+            SchedulerKind::SystemdUser
+        }
+    }
+
+    /// Human-readable name for status output.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            SchedulerKind::Launchd => "launchd",
+            SchedulerKind::SystemdUser => "systemd (user)",
+            SchedulerKind::WindowsTaskScheduler => "Windows Task Scheduler",
+        }
+    }
+}
+
+/// Files (or task name) a schedule install touches.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleUnits {
+    pub scheduler: SchedulerKind,
+    /// Unit files written to disk. Empty on Windows, where the task lives
+    /// inside Task Scheduler rather than a user-visible file.
+    pub paths: Vec<PathBuf>,
+    /// Task name as the native scheduler knows it.
+    pub name: String,
+}
+
+/// Outcome of `cass schedule install`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallReport {
+    pub units: ScheduleUnits,
+    pub interval_secs: u64,
+    /// Command invoked to register the unit, for transparency.
+    pub register_command: String,
+}
+
+/// Outcome of `cass schedule status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleStatus {
+    pub scheduler: SchedulerKind,
+    pub installed: bool,
+    pub units: ScheduleUnits,
+    /// Raw scheduler-reported state (e.g. `systemctl --user is-active`),
+    /// when the scheduler could be queried.
+    pub native_state: Option<String>,
+}
+
+/// Resolve the unit paths / task name for the current platform.
+pub fn schedule_units() -> Result<ScheduleUnits> {
+    schedule_units_for(SchedulerKind::current())
+}
+
+fn schedule_units_for(scheduler: SchedulerKind) -> Result<ScheduleUnits> {
+    let home = || dirs::home_dir().context("cannot resolve home directory");
+    let (paths, name) = match scheduler {
+        SchedulerKind::Launchd => (
+            vec![
+                home()?
+                    .join("Library/LaunchAgents")
+                    .join(format!("{SCHEDULE_LABEL}.plist")),
+            ],
+            SCHEDULE_LABEL.to_string(),
+        ),
+        SchedulerKind::SystemdUser => {
+            let unit_dir = home()?.join(".config/systemd/user");
+            (
+                vec![
+                    unit_dir.join(format!("{SYSTEMD_UNIT_STEM}.service")),
+                    unit_dir.join(format!("{SYSTEMD_UNIT_STEM}.timer")),
+                ],
+                format!("{SYSTEMD_UNIT_STEM}.timer"),
+            )
+        }
+        SchedulerKind::WindowsTaskScheduler => (Vec::new(), SYSTEMD_UNIT_STEM.to_string()),
+    };
+    Ok(ScheduleUnits {
+        scheduler,
+        paths,
+        name,
+    })
+}
+
+/// Render the launchd LaunchAgent plist.
+fn render_launchd_plist(exe: &str, interval_secs: u64) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{SCHEDULE_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{exe}</string>
+        <string>index</string>
+    </array>
+    <key>StartInterval</key>
+    <integer>{interval_secs}</integer>
+    <key>RunAtLoad</key>
+    <false/>
+    <key>ProcessType</key>
+    <string>Background</string>
+</dict>
+</plist>
+"#,
+        exe = xml_escape(exe),
+    )
+}
+
+/// Render the systemd user service unit (oneshot; the timer drives it).
+fn render_systemd_service(exe: &str) -> String {
+    format!(
+        "[Unit]\n\
+         Description=cass index (coding agent session search)\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={}\n\
+         Nice=10\n",
+        systemd_exec_quote(exe)
+    )
+}
+
+/// Render the systemd user timer unit.
+fn render_systemd_timer(interval_secs: u64) -> String {
+    format!(
+        "[Unit]\n\
+         Description=Periodic cass index\n\
+         \n\
+         [Timer]\n\
+         OnBootSec=2min\n\
+         OnUnitActiveSec={interval_secs}s\n\
+         Persistent=true\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n"
+    )
+}
+
+/// systemd `ExecStart` line: the executable path quoted (paths with spaces),
+/// followed by the subcommand.
+fn systemd_exec_quote(exe: &str) -> String {
+    if exe.contains(' ') {
+        format!("\"{exe}\" index")
+    } else {
+        format!("{exe} index")
+    }
+}
+
+fn xml_escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Install (or replace) the scheduled `cass index` run.
+pub fn install(interval_secs: u64) -> Result<InstallReport> {
+    if interval_secs < 60 {
+        bail!("interval must be at least 60 seconds, got {interval_secs}s");
+    }
+    let exe = std::env::current_exe().context("cannot resolve the cass executable path")?;
+    let exe = exe.display().to_string();
+    let units = schedule_units()?;
+
+    for path in &units.paths {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+    }
+
+    let register_command = match units.scheduler {
+        SchedulerKind::Launchd => {
+            let plist = &units.paths[0];
+            std::fs::write(plist, render_launchd_plist(&exe, interval_secs))
+                .with_context(|| format!("writing {}", plist.display()))?;
+            // Unload first so a re-install with a new interval takes effect;
+            // failure just means it was not loaded yet.
+            let _ = run_scheduler_tool("launchctl", &["unload".as_ref(), plist.as_os_str()]);
+            run_scheduler_tool(
+                "launchctl",
+                &["load".as_ref(), "-w".as_ref(), plist.as_os_str()],
+            )?
+        }
+        SchedulerKind::SystemdUser => {
+            std::fs::write(&units.paths[0], render_systemd_service(&exe))
+                .with_context(|| format!("writing {}", units.paths[0].display()))?;
+            std::fs::write(&units.paths[1], render_systemd_timer(interval_secs))
+                .with_context(|| format!("writing {}", units.paths[1].display()))?;
+            run_scheduler_tool("systemctl", &["--user".as_ref(), "daemon-reload".as_ref()])?;
+            run_scheduler_tool(
+                "systemctl",
+                &[
+                    "--user".as_ref(),
+                    "enable".as_ref(),
+                    "--now".as_ref(),
+                    units.name.as_ref(),
+                ],
+            )?
+        }
+        SchedulerKind::WindowsTaskScheduler => {
+            let minutes = (interval_secs / 60).max(1).to_string();
+            let action = format!("\"{exe}\" index");
+            run_scheduler_tool(
+                "schtasks",
+                &[
+                    "/Create".as_ref(),
+                    "/F".as_ref(),
+                    "/SC".as_ref(),
+                    "MINUTE".as_ref(),
+                    "/MO".as_ref(),
+                    minutes.as_ref(),
+                    "/TN".as_ref(),
+                    units.name.as_ref(),
+                    "/TR".as_ref(),
+                    action.as_ref(),
+                ],
+            )?
+        }
+    };
+
+    Ok(InstallReport {
+        units,
+        interval_secs,
+        register_command,
+    })
+}
+
+/// Report whether the schedule is installed and what the native scheduler
+/// says about it.
+pub fn status() -> Result<ScheduleStatus> {
+    let units = schedule_units()?;
+    let (installed, native_state) = match units.scheduler {
+        SchedulerKind::Launchd => {
+            let installed = units.paths.iter().all(|path| path.is_file());
+            let state =
+                query_scheduler_tool("launchctl", &["list".as_ref(), SCHEDULE_LABEL.as_ref()])
+                    .map(|_| "loaded".to_string());
+            (installed, state)
+        }
+        SchedulerKind::SystemdUser => {
+            let installed = units.paths.iter().all(|path| path.is_file());
+            let state = query_scheduler_tool(
+                "systemctl",
+                &["--user".as_ref(), "is-active".as_ref(), units.name.as_ref()],
+            );
+            (installed, state)
+        }
+        SchedulerKind::WindowsTaskScheduler => {
+            let state = query_scheduler_tool(
+                "schtasks",
+                &["/Query".as_ref(), "/TN".as_ref(), units.name.as_ref()],
+            );
+            (state.is_some(), state.map(|_| "registered".to_string()))
+        }
+    };
+    Ok(ScheduleStatus {
+        scheduler: units.scheduler,
+        installed,
+        units,
+        native_state,
+    })
+}
+
+/// Remove the scheduled run. Returns `false` when nothing was installed.
+pub fn uninstall() -> Result<bool> {
+    let units = schedule_units()?;
+    let mut removed = false;
+    match units.scheduler {
+        SchedulerKind::Launchd => {
+            let plist = &units.paths[0];
+            if plist.is_file() {
+                let _ = run_scheduler_tool("launchctl", &["unload".as_ref(), plist.as_os_str()]);
+                std::fs::remove_file(plist)
+                    .with_context(|| format!("removing {}", plist.display()))?;
+                removed = true;
+            }
+        }
+        SchedulerKind::SystemdUser => {
+            if units.paths.iter().any(|path| path.is_file()) {
+                let _ = run_scheduler_tool(
+                    "systemctl",
+                    &[
+                        "--user".as_ref(),
+                        "disable".as_ref(),
+                        "--now".as_ref(),
+                        units.name.as_ref(),
+                    ],
+                );
+                for path in &units.paths {
+                    if path.is_file() {
+                        std::fs::remove_file(path)
+                            .with_context(|| format!("removing {}", path.display()))?;
+                    }
+                }
+                let _ =
+                    run_scheduler_tool("systemctl", &["--user".as_ref(), "daemon-reload".as_ref()]);
+                removed = true;
+            }
+        }
+        SchedulerKind::WindowsTaskScheduler => {
+            removed = run_scheduler_tool(
+                "schtasks",
+                &[
+                    "/Delete".as_ref(),
+                    "/F".as_ref(),
+                    "/TN".as_ref(),
+                    units.name.as_ref(),
+                ],
+            )
+            .is_ok();
+        }
+    }
+    Ok(removed)
+}
+
+/// Run a native scheduler tool, returning the rendered command line on
+/// success. Idempotent cleanup steps (unload/disable before re-install)
+/// ignore the result with `let _ =`.
+fn run_scheduler_tool(program: &str, args: &[&std::ffi::OsStr]) -> Result<String> {
+    let rendered = format!(
+        "{program} {}",
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("running `{rendered}` (is {program} on PATH?)"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("`{rendered}` failed ({}): {}", output.status, stderr.trim());
+    }
+    Ok(rendered)
+}
+
+/// Query variant: `None` when the tool is missing or the unit is unknown.
+fn query_scheduler_tool(program: &str, args: &[&std::ffi::OsStr]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next().unwrap_or("").trim().to_string();
+    Some(if first_line.is_empty() {
+        "ok".to_string()
+    } else {
+        first_line
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launchd_plist_embeds_label_interval_and_escaped_exe() {
+        let plist = render_launchd_plist("/opt/my tools/<cass>", 1800);
+        assert!(plist.contains("<string>com.cass.index</string>"));
+        assert!(plist.contains("<integer>1800</integer>"));
+        assert!(plist.contains("/opt/my tools/&lt;cass&gt;"));
+        assert!(!plist.contains("<cass>"));
+    }
+
+    #[test]
+    fn systemd_timer_uses_seconds_and_persists_missed_runs() {
+        let timer = render_systemd_timer(1800);
+        assert!(timer.contains("OnUnitActiveSec=1800s"));
+        assert!(timer.contains("Persistent=true"));
+        assert!(timer.contains("WantedBy=timers.target"));
+    }
+
+    #[test]
+    fn systemd_service_quotes_paths_with_spaces() {
+        assert_eq!(
+            render_systemd_service("/usr/local/bin/cass")
+                .lines()
+                .find(|line| line.starts_with("ExecStart="))
+                .unwrap(),
+            "ExecStart=/usr/local/bin/cass index"
+        );
+        assert_eq!(
+            render_systemd_service("/opt/my tools/cass")
+                .lines()
+                .find(|line| line.starts_with("ExecStart="))
+                .unwrap(),
+            "ExecStart=\"/opt/my tools/cass\" index"
+        );
+    }
+
+    #[test]
+    fn unit_paths_match_the_platform_conventions() {
+        let launchd = schedule_units_for(SchedulerKind::Launchd).unwrap();
+        assert_eq!(launchd.paths.len(), 1);
+        assert!(launchd.paths[0].ends_with("Library/LaunchAgents/com.cass.index.plist"));
+
+        let systemd = schedule_units_for(SchedulerKind::SystemdUser).unwrap();
+        assert_eq!(systemd.paths.len(), 2);
+        assert!(systemd.paths[0].ends_with(".config/systemd/user/cass-index.service"));
+        assert!(systemd.paths[1].ends_with(".config/systemd/user/cass-index.timer"));
+        assert_eq!(systemd.name, "cass-index.timer");
+
+        let windows = schedule_units_for(SchedulerKind::WindowsTaskScheduler).unwrap();
+        assert!(windows.paths.is_empty());
+    }
+
+    #[test]
+    fn install_rejects_sub_minute_intervals() {
+        let err = install(30).expect_err("sub-minute interval must be rejected");
+        assert!(err.to_string().contains("at least 60 seconds"));
+    }
+}
@@ -0,0 +1,359 @@
+//! Corpus-level boilerplate detection for repeated agent preamble.
+//!
+//! Agents resend the same giant system prompt, environment context, or MCP
+//! tool manifest at the start of every session. Those blocks index fine but
+//! pollute search: a query matching a term inside the preamble surfaces the
+//! identical wall of text once per conversation. This module detects content
+//! repeated across conversations — exact repeats by a whitespace-normalized
+//! hash, near-identical variants (timestamps, session ids) by MinHash over
+//! word shingles — and records it in the `boilerplate_contents` table so
+//! ranking can exclude it by default (`cass search --include-boilerplate`
+//! restores the old behavior).
+//!
+//! The table is a derived asset, refreshed at the end of each non-watch index
+//! run (`FrankenStorage::refresh_boilerplate_contents`); message storage
+//! itself stays denormalized so every existing `SELECT content` consumer is
+//! untouched.
+
+use std::collections::{HashMap, HashSet};
+
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+/// Content shorter than this never counts as boilerplate; short repeated
+/// messages ("ok", "continue") are handled by the acknowledgement noise
+/// filter and are too small to bloat anything.
+pub const MIN_CONTENT_CHARS: usize = 200;
+
+/// Distinct conversations a block (or near-identical cluster) must appear in
+/// before it is classified as boilerplate.
+pub const MIN_CONVERSATIONS: i64 = 3;
+
+/// MinHash signature width. 16 hashes gives a similarity estimate with
+/// ~±0.12 standard error, plenty to separate "same prompt, new timestamp"
+/// (>0.95) from merely similar prose (<0.7).
+pub const SIGNATURE_HASHES: usize = 16;
+
+/// Words per shingle for the MinHash signature.
+const SHINGLE_WORDS: usize = 3;
+
+/// Estimated Jaccard similarity above which two blocks are treated as the
+/// same boilerplate variant.
+pub const NEAR_DUP_THRESHOLD: f32 = 0.9;
+
+/// Whitespace-normalized content fingerprint. Token-joined with single
+/// spaces so indentation and line-wrapping differences between connectors
+/// do not split otherwise identical blocks.
+#[must_use]
+pub fn content_fingerprint(content: &str) -> u64 {
+    use xxhash_rust::xxh3::Xxh3;
+    let mut hasher = Xxh3::new();
+    let mut first = true;
+    for token in content.split_whitespace() {
+        if !first {
+            hasher.update(b" ");
+        }
+        hasher.update(token.as_bytes());
+        first = false;
+    }
+    hasher.digest()
+}
+
+/// MinHash signature over word shingles: one minimum per seeded hash
+/// function. Content with fewer words than one shingle hashes each word
+/// individually so very terse blocks still get a usable signature.
+#[must_use]
+pub fn minhash_signature(content: &str) -> [u64; SIGNATURE_HASHES] {
+    let words: Vec<&str> = content.split_whitespace().collect();
+    let mut signature = [u64::MAX; SIGNATURE_HASHES];
+    let mut update = |shingle: &str| {
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            let h = xxh3_64_with_seed(shingle.as_bytes(), seed as u64);
+            if h < *slot {
+                *slot = h;
+            }
+        }
+    };
+    if words.len() < SHINGLE_WORDS {
+        for word in &words {
+            update(word);
+        }
+    } else {
+        let mut shingle = String::new();
+        for window in words.windows(SHINGLE_WORDS) {
+            shingle.clear();
+            shingle.push_str(window[0]);
+            for word in &window[1..] {
+                shingle.push(' ');
+                shingle.push_str(word);
+            }
+            update(&shingle);
+        }
+    }
+    signature
+}
+
+/// Estimated Jaccard similarity: fraction of matching signature slots.
+#[must_use]
+pub fn signature_similarity(a: &[u64; SIGNATURE_HASHES], b: &[u64; SIGNATURE_HASHES]) -> f32 {
+    let matching = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+    matching as f32 / SIGNATURE_HASHES as f32
+}
+
+/// One detected boilerplate block, ready to persist.
+#[derive(Debug, Clone)]
+pub struct BoilerplateRow {
+    /// [`content_fingerprint`] of the block.
+    pub fingerprint: u64,
+    /// [`minhash_signature`] of the block.
+    pub signature: [u64; SIGNATURE_HASHES],
+    /// Distinct conversations the block's cluster appeared in.
+    pub conversations: i64,
+    /// Character length of the block.
+    pub chars: i64,
+}
+
+/// Classify candidate blocks into boilerplate rows.
+///
+/// `candidates` are `(content, distinct_conversation_count)` pairs for
+/// messages long enough to matter that already repeat in at least two
+/// conversations (the SQL pre-filter). Exact repeats carry their own count;
+/// near-identical variants are clustered by MinHash and their counts summed
+/// — variants of the same prompt essentially never co-occur within one
+/// conversation, so the sum is a faithful distinct-conversation estimate.
+#[must_use]
+pub fn detect_boilerplate(candidates: &[(String, i64)]) -> Vec<BoilerplateRow> {
+    struct Cluster {
+        representative: [u64; SIGNATURE_HASHES],
+        members: Vec<usize>,
+        conversations: i64,
+    }
+
+    let mut rows: Vec<BoilerplateRow> = Vec::new();
+    for (content, conversations) in candidates {
+        if content.chars().count() < MIN_CONTENT_CHARS {
+            continue;
+        }
+        rows.push(BoilerplateRow {
+            fingerprint: content_fingerprint(content),
+            signature: minhash_signature(content),
+            conversations: *conversations,
+            chars: content.chars().count() as i64,
+        });
+    }
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for (idx, row) in rows.iter().enumerate() {
+        let found = clusters.iter_mut().find(|cluster| {
+            signature_similarity(&cluster.representative, &row.signature) >= NEAR_DUP_THRESHOLD
+        });
+        match found {
+            Some(cluster) => {
+                cluster.members.push(idx);
+                cluster.conversations += row.conversations;
+            }
+            None => clusters.push(Cluster {
+                representative: row.signature,
+                members: vec![idx],
+                conversations: row.conversations,
+            }),
+        }
+    }
+
+    let mut cluster_total: HashMap<usize, i64> = HashMap::new();
+    for cluster in &clusters {
+        for &member in &cluster.members {
+            cluster_total.insert(member, cluster.conversations);
+        }
+    }
+
+    let mut out: Vec<BoilerplateRow> = rows
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, mut row)| {
+            let total = cluster_total
+                .get(&idx)
+                .copied()
+                .unwrap_or(row.conversations);
+            if total < MIN_CONVERSATIONS {
+                return None;
+            }
+            row.conversations = total;
+            Some(row)
+        })
+        .collect();
+    out.sort_by(|a, b| b.conversations.cmp(&a.conversations));
+    out
+}
+
+/// In-memory boilerplate lookup used at ranking time.
+///
+/// Exact fingerprints cover the common case in O(1); the signature scan only
+/// runs for content long enough to plausibly be boilerplate, and the table
+/// is small (one row per repeated block, not per message).
+#[derive(Debug, Default)]
+pub struct BoilerplateIndex {
+    exact: HashSet<u64>,
+    signatures: Vec<[u64; SIGNATURE_HASHES]>,
+}
+
+impl BoilerplateIndex {
+    /// Load from `boilerplate_contents`, returning an empty index when the
+    /// table does not exist yet (pre-v23 database) or cannot be read —
+    /// exclusion is best-effort and must never fail a search.
+    #[must_use]
+    pub fn load(conn: &frankensqlite::Connection) -> Self {
+        use frankensqlite::compat::{ConnectionExt, RowExt};
+
+        let rows: Vec<(i64, Vec<u8>)> = conn
+            .query_map_collect(
+                "SELECT content_hash, minhash FROM boilerplate_contents",
+                &[],
+                |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?)),
+            )
+            .unwrap_or_default();
+
+        let mut index = Self::default();
+        for (hash, blob) in rows {
+            index.exact.insert(hash as u64);
+            if let Some(signature) = signature_from_blob(&blob) {
+                index.signatures.push(signature);
+            }
+        }
+        index
+    }
+
+    /// True when nothing was detected (or the table is absent); callers can
+    /// skip the per-hit check entirely.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty()
+    }
+
+    /// Whether `content` is a known boilerplate block or a near-identical
+    /// variant of one.
+    #[must_use]
+    pub fn matches(&self, content: &str) -> bool {
+        if self.exact.is_empty() || content.chars().count() < MIN_CONTENT_CHARS {
+            return false;
+        }
+        if self.exact.contains(&content_fingerprint(content)) {
+            return true;
+        }
+        let signature = minhash_signature(content);
+        self.signatures
+            .iter()
+            .any(|known| signature_similarity(known, &signature) >= NEAR_DUP_THRESHOLD)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_rows(rows: &[BoilerplateRow]) -> Self {
+        let mut index = Self::default();
+        for row in rows {
+            index.exact.insert(row.fingerprint);
+            index.signatures.push(row.signature);
+        }
+        index
+    }
+}
+
+/// Serialize a signature as little-endian bytes for the `minhash` BLOB.
+#[must_use]
+pub fn signature_to_blob(signature: &[u64; SIGNATURE_HASHES]) -> Vec<u8> {
+    let mut blob = Vec::with_capacity(SIGNATURE_HASHES * 8);
+    for value in signature {
+        blob.extend_from_slice(&value.to_le_bytes());
+    }
+    blob
+}
+
+fn signature_from_blob(blob: &[u8]) -> Option<[u64; SIGNATURE_HASHES]> {
+    if blob.len() != SIGNATURE_HASHES * 8 {
+        return None;
+    }
+    let mut signature = [0u64; SIGNATURE_HASHES];
+    for (slot, chunk) in signature.iter_mut().zip(blob.chunks_exact(8)) {
+        *slot = u64::from_le_bytes(chunk.try_into().ok()?);
+    }
+    Some(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(variant: &str) -> String {
+        format!(
+            "You are a coding agent operating in a sandboxed environment. \
+             Follow the workspace conventions, run the tests before committing, \
+             and never push directly to the default branch. Available tools: \
+             bash, read_file, write_file, search. The user's instructions take \
+             precedence over these defaults. Session: {variant}"
+        )
+    }
+
+    #[test]
+    fn fingerprint_ignores_whitespace_layout() {
+        assert_eq!(
+            content_fingerprint("alpha beta  gamma"),
+            content_fingerprint("alpha\n  beta\tgamma")
+        );
+        assert_ne!(
+            content_fingerprint("alpha beta gamma"),
+            content_fingerprint("alpha beta delta")
+        );
+    }
+
+    #[test]
+    fn near_identical_prompts_have_similar_signatures() {
+        let a = minhash_signature(&prompt("2024-01-01T00:00:00Z"));
+        let b = minhash_signature(&prompt("2024-06-15T12:34:56Z"));
+        assert!(signature_similarity(&a, &b) >= NEAR_DUP_THRESHOLD);
+
+        let unrelated = minhash_signature(
+            "please add retry logic to the uploader and surface transient \
+             network failures as warnings rather than hard errors in the log",
+        );
+        assert!(signature_similarity(&a, &unrelated) < NEAR_DUP_THRESHOLD);
+    }
+
+    #[test]
+    fn detect_requires_enough_conversations() {
+        let rows = detect_boilerplate(&[(prompt("only-twice"), 2)]);
+        assert!(rows.is_empty());
+
+        let rows = detect_boilerplate(&[(prompt("everywhere"), 5)]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].conversations, 5);
+    }
+
+    #[test]
+    fn near_duplicate_variants_pool_their_conversation_counts() {
+        // Each variant alone is under the threshold; the cluster clears it.
+        let rows = detect_boilerplate(&[(prompt("variant-a"), 2), (prompt("variant-b"), 2)]);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.conversations == 4));
+    }
+
+    #[test]
+    fn index_matches_exact_and_near_duplicate_content() {
+        let rows = detect_boilerplate(&[(prompt("seen"), 4)]);
+        let index = BoilerplateIndex::from_rows(&rows);
+        assert!(index.matches(&prompt("seen")));
+        assert!(index.matches(&prompt("unseen-variant")));
+        assert!(!index.matches("short message"));
+        assert!(!index.matches(
+            "a completely different long message about database migrations, \
+             connection pooling, and the exact sequence of steps needed to \
+             reproduce the deadlock we keep hitting in the nightly integration \
+             environment when two writers race on the same advisory lock"
+        ));
+    }
+
+    #[test]
+    fn signature_blob_round_trips() {
+        let signature = minhash_signature(&prompt("blob"));
+        let blob = signature_to_blob(&signature);
+        assert_eq!(signature_from_blob(&blob), Some(signature));
+        assert_eq!(signature_from_blob(&blob[1..]), None);
+    }
+}
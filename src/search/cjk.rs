@@ -0,0 +1,267 @@
+//! CJK pre-segmentation for the lexical index.
+//!
+//! The default tokenizers treat an unbroken Han/kana run as a single token,
+//! so a Japanese query like `関数` never matches `この関数はクラッシュする`.
+//! Rather than forking the engine's schema, cass segments CJK runs *before*
+//! text reaches the index (see `cass_document_for_message`) and applies the
+//! same transform to queries, so both sides agree on token boundaries:
+//!
+//! - Han runs are cut with jieba when the `cjk` cargo feature is compiled
+//!   in, falling back to overlapping bigrams otherwise.
+//! - Hiragana/katakana runs always use bigrams (jieba is a Chinese
+//!   segmenter and performs poorly on kana).
+//! - Hangul is left alone: Korean text is already space-delimited.
+//!
+//! The knob `CASS_CJK_SEGMENTATION` selects `off`, `bigram`, `jieba`, or
+//! `auto` (the default: jieba-or-off depending on the feature, so default
+//! builds keep their exact legacy token stream). Because changing the
+//! segmenter changes indexed tokens, the active id is recorded next to the
+//! index schema hash and a mismatch forces a lexical rebuild — see
+//! `stored_cjk_tokenizer_matches` in `search::tantivy`.
+//!
+//! Trade-off: segmentation inserts real spaces, so a stored preview of CJK
+//! content can render with extra spacing. Full hit content is hydrated from
+//! the canonical database and is never altered.
+
+use std::borrow::Cow;
+
+/// Active segmentation strategy, resolved from the cargo feature set and
+/// the `CASS_CJK_SEGMENTATION` environment knob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CjkSegmentation {
+    /// Legacy behavior: text passes through untouched.
+    Off,
+    /// Dependency-free overlapping character bigrams for Han and kana runs.
+    Bigram,
+    /// jieba word segmentation for Han runs, bigrams for kana (requires
+    /// the `cjk` cargo feature; degrades to `Bigram` without it).
+    Jieba,
+}
+
+impl CjkSegmentation {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" | "0" | "false" => Some(Self::Off),
+            "bigram" => Some(Self::Bigram),
+            "jieba" | "icu" => Some(Self::effective_jieba()),
+            "auto" | "" => None,
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "cjk")]
+    fn effective_jieba() -> Self {
+        Self::Jieba
+    }
+
+    #[cfg(not(feature = "cjk"))]
+    fn effective_jieba() -> Self {
+        Self::Bigram
+    }
+
+    /// Marker written into `schema_hash.json`; a stored marker that differs
+    /// from the active one invalidates the lexical index.
+    #[must_use]
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Bigram => "bigram",
+            Self::Jieba => "jieba",
+        }
+    }
+}
+
+/// Resolve the active segmentation mode. `auto` keeps default builds
+/// byte-identical to the legacy pipeline (no surprise reindex for users
+/// who never opted in) and enables jieba when the feature is compiled in.
+#[must_use]
+pub fn active_segmentation() -> CjkSegmentation {
+    if let Ok(raw) = dotenvy::var("CASS_CJK_SEGMENTATION")
+        && let Some(mode) = CjkSegmentation::parse(&raw)
+    {
+        return mode;
+    }
+    if cfg!(feature = "cjk") {
+        CjkSegmentation::effective_jieba()
+    } else {
+        CjkSegmentation::Off
+    }
+}
+
+/// Segment CJK runs in `text` per the active mode. Returns the input
+/// unchanged (borrowed) when segmentation is off or no CJK is present.
+#[must_use]
+pub fn segment(text: &str) -> Cow<'_, str> {
+    segment_with_mode(text, active_segmentation())
+}
+
+fn segment_with_mode(text: &str, mode: CjkSegmentation) -> Cow<'_, str> {
+    if mode == CjkSegmentation::Off || !text.chars().any(needs_segmentation) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut run = String::new();
+    let mut run_class = RunClass::None;
+    for c in text.chars() {
+        let class = classify(c);
+        if class != run_class {
+            flush_run(&mut out, &mut run, run_class, mode);
+            run_class = class;
+        }
+        if class == RunClass::None {
+            out.push(c);
+        } else {
+            run.push(c);
+        }
+    }
+    flush_run(&mut out, &mut run, run_class, mode);
+    Cow::Owned(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunClass {
+    None,
+    Han,
+    Kana,
+}
+
+fn classify(c: char) -> RunClass {
+    match c as u32 {
+        // CJK Unified Ideographs (+ Ext A) and compatibility ideographs.
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FA1F => RunClass::Han,
+        // Hiragana, katakana, and halfwidth katakana.
+        0x3040..=0x30FF | 0xFF66..=0xFF9D => RunClass::Kana,
+        _ => RunClass::None,
+    }
+}
+
+fn needs_segmentation(c: char) -> bool {
+    classify(c) != RunClass::None
+}
+
+fn flush_run(out: &mut String, run: &mut String, class: RunClass, mode: CjkSegmentation) {
+    if run.is_empty() {
+        return;
+    }
+    if !out.is_empty() && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    match class {
+        RunClass::None => out.push_str(run),
+        RunClass::Han if mode == CjkSegmentation::Jieba => segment_han_jieba(run, out),
+        RunClass::Han | RunClass::Kana => push_bigrams(run, out),
+    }
+    out.push(' ');
+    run.clear();
+}
+
+/// Overlapping character bigrams: `関数名` → `関数 数名`. Single-character
+/// runs pass through whole so short queries still match.
+fn push_bigrams(run: &str, out: &mut String) {
+    let chars: Vec<char> = run.chars().collect();
+    if chars.len() < 2 {
+        out.extend(chars);
+        return;
+    }
+    for (i, pair) in chars.windows(2).enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.extend(pair.iter());
+    }
+}
+
+#[cfg(feature = "cjk")]
+fn segment_han_jieba(run: &str, out: &mut String) {
+    use once_cell::sync::Lazy;
+    static JIEBA: Lazy<jieba_rs::Jieba> = Lazy::new(jieba_rs::Jieba::new);
+    let mut first = true;
+    for word in JIEBA.cut(run, false) {
+        if !first {
+            out.push(' ');
+        }
+        out.push_str(word);
+        first = false;
+    }
+}
+
+#[cfg(not(feature = "cjk"))]
+fn segment_han_jieba(run: &str, out: &mut String) {
+    // Unreachable in practice: `parse`/`active_segmentation` degrade Jieba
+    // to Bigram without the feature, but stay total rather than panic.
+    push_bigrams(run, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn off_mode_passes_through_borrowed() {
+        let text = "この関数はクラッシュする";
+        assert!(matches!(
+            segment_with_mode(text, CjkSegmentation::Off),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn ascii_text_is_untouched_in_any_mode() {
+        let text = "fn main() { panic!(); }";
+        assert_eq!(segment_with_mode(text, CjkSegmentation::Bigram), text);
+    }
+
+    #[test]
+    fn han_run_becomes_bigrams() {
+        assert_eq!(
+            segment_with_mode("関数名", CjkSegmentation::Bigram),
+            "関数 数名 "
+        );
+    }
+
+    #[test]
+    fn single_cjk_char_stays_whole() {
+        assert_eq!(segment_with_mode("木", CjkSegmentation::Bigram), "木 ");
+    }
+
+    #[test]
+    fn mixed_ascii_and_kana_get_boundaries() {
+        let segmented = segment_with_mode("rustでパニック", CjkSegmentation::Bigram);
+        assert_eq!(segmented, "rust でパ パニ ニッ ック ");
+    }
+
+    #[test]
+    fn hangul_is_left_alone() {
+        let text = "함수가 실패했다";
+        assert_eq!(segment_with_mode(text, CjkSegmentation::Bigram), text);
+    }
+
+    #[test]
+    fn kana_and_han_runs_segment_independently() {
+        let segmented = segment_with_mode("関数がクラッシュ", CjkSegmentation::Bigram);
+        assert_eq!(segmented, "関数 がク クラ ラッ ッシ シュ ");
+    }
+
+    #[test]
+    fn knob_values_parse() {
+        assert_eq!(CjkSegmentation::parse("off"), Some(CjkSegmentation::Off));
+        assert_eq!(
+            CjkSegmentation::parse("BIGRAM"),
+            Some(CjkSegmentation::Bigram)
+        );
+        assert_eq!(CjkSegmentation::parse("auto"), None);
+        assert_eq!(CjkSegmentation::parse("bogus"), None);
+    }
+
+    #[test]
+    fn query_and_document_agree_on_tokens() {
+        // The same transform runs on both sides, so a sub-run of the
+        // document produces a subset of the document's bigrams.
+        let doc = segment_with_mode("この関数はクラッシュする", CjkSegmentation::Bigram);
+        let query = segment_with_mode("関数", CjkSegmentation::Bigram);
+        for token in query.split_whitespace() {
+            assert!(doc.contains(token), "query token {token} missing from doc");
+        }
+    }
+}
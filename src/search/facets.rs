@@ -0,0 +1,316 @@
+//! Conversation facet extraction.
+//!
+//! "That session where we fought with webpack" is rarely findable by full-text
+//! query alone: the tool names are buried under thousands of lines of
+//! unrelated output. This module derives a small set of coarse facets per
+//! conversation — languages seen in fenced code blocks, frameworks named in
+//! the discussion, and package-manager commands that were run — so sessions
+//! can be filtered by what they were *about* rather than what they happened
+//! to print.
+//!
+//! Facets are a derived asset stored in the `facets` table and refreshed at
+//! the end of each non-watch index run
+//! (`FrankenStorage::refresh_conversation_facets`), mirroring the quality
+//! score and run classification passes. Readers reach them through inline
+//! `facet:rust` / `facet:django` query terms (see
+//! [`crate::search::structured_query`]) and the `cass stats` facet summary.
+//!
+//! The vocabulary is deliberately curated: only known languages, frameworks,
+//! and package managers become facets. An open vocabulary would turn every
+//! typo'd fence info string into a filter value, and the point of a facet is
+//! that the same value reliably means the same thing across sessions.
+
+use std::collections::BTreeSet;
+
+/// Facet categories. Stored in the `kind` column so chips and stats can
+/// group by category without re-deriving it from the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FacetKind {
+    /// Language of a fenced code block (` ```rust `).
+    Language,
+    /// Framework or major library named in the discussion.
+    Framework,
+    /// Package manager / build tool whose command was run.
+    PackageManager,
+    /// PII category detected in message text (see `search::pii`). Derived
+    /// from a separate, privacy-focused extraction pass but stored and
+    /// queried through the same facets machinery.
+    Pii,
+}
+
+impl FacetKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Language => "language",
+            Self::Framework => "framework",
+            Self::PackageManager => "package-manager",
+            Self::Pii => "pii",
+        }
+    }
+}
+
+/// Fence info strings mapped to canonical language facet values. Aliases
+/// collapse (`py` and `python` are the same session topic); unknown info
+/// strings produce no facet.
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("bash", "shell"),
+    ("c", "c"),
+    ("c#", "csharp"),
+    ("c++", "cpp"),
+    ("cpp", "cpp"),
+    ("cs", "csharp"),
+    ("csharp", "csharp"),
+    ("css", "css"),
+    ("dart", "dart"),
+    ("dockerfile", "dockerfile"),
+    ("elixir", "elixir"),
+    ("erlang", "erlang"),
+    ("go", "go"),
+    ("golang", "go"),
+    ("haskell", "haskell"),
+    ("html", "html"),
+    ("java", "java"),
+    ("javascript", "javascript"),
+    ("js", "javascript"),
+    ("json", "json"),
+    ("jsx", "javascript"),
+    ("kotlin", "kotlin"),
+    ("kt", "kotlin"),
+    ("lua", "lua"),
+    ("markdown", "markdown"),
+    ("md", "markdown"),
+    ("php", "php"),
+    ("py", "python"),
+    ("python", "python"),
+    ("rb", "ruby"),
+    ("rs", "rust"),
+    ("ruby", "ruby"),
+    ("rust", "rust"),
+    ("scala", "scala"),
+    ("sh", "shell"),
+    ("shell", "shell"),
+    ("sql", "sql"),
+    ("swift", "swift"),
+    ("toml", "toml"),
+    ("ts", "typescript"),
+    ("tsx", "typescript"),
+    ("typescript", "typescript"),
+    ("xml", "xml"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("zig", "zig"),
+    ("zsh", "shell"),
+];
+
+/// Framework and major-library names matched as whole words (case-insensitive)
+/// anywhere in message text. Whole-word matching keeps "guardrails" from
+/// becoming a Rails session.
+const FRAMEWORK_NAMES: &[&str] = &[
+    "actix",
+    "angular",
+    "axum",
+    "babel",
+    "django",
+    "express",
+    "fastapi",
+    "flask",
+    "flutter",
+    "jest",
+    "jquery",
+    "laravel",
+    "next.js",
+    "numpy",
+    "pandas",
+    "playwright",
+    "pytest",
+    "pytorch",
+    "rails",
+    "react",
+    "rollup",
+    "spring",
+    "svelte",
+    "symfony",
+    "tailwind",
+    "tensorflow",
+    "tokio",
+    "vite",
+    "vue",
+    "webpack",
+];
+
+/// Command-line prefixes mapped to package-manager facet values. Matched at
+/// the start of a (trimmed) line, optionally behind a `$`/`%`/`>` shell
+/// prompt, so prose like "npm is slow" does not count as having *run* npm.
+const PACKAGE_MANAGER_PREFIXES: &[(&str, &str)] = &[
+    ("./gradlew ", "gradle"),
+    ("apt ", "apt"),
+    ("apt-get ", "apt"),
+    ("brew ", "brew"),
+    ("bundle ", "bundler"),
+    ("cargo ", "cargo"),
+    ("composer ", "composer"),
+    ("conda ", "conda"),
+    ("gem ", "gem"),
+    ("go get ", "go"),
+    ("go install ", "go"),
+    ("gradle ", "gradle"),
+    ("mvn ", "maven"),
+    ("npm ", "npm"),
+    ("npx ", "npm"),
+    ("pip ", "pip"),
+    ("pip3 ", "pip"),
+    ("pnpm ", "pnpm"),
+    ("poetry ", "poetry"),
+    ("uv ", "uv"),
+    ("yarn ", "yarn"),
+];
+
+/// Extract the facet set for one conversation from `(role, content)` pairs
+/// in message order. Pure; the caller supplies rows from storage. Values are
+/// canonical lowercase so `facet:` lookups are exact matches.
+#[must_use]
+pub fn extract_facets(messages: &[(String, String)]) -> BTreeSet<(FacetKind, String)> {
+    let mut facets = BTreeSet::new();
+    for (_, content) in messages {
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            if let Some(language) = fence_language(trimmed) {
+                facets.insert((FacetKind::Language, language.to_string()));
+            }
+            if let Some(manager) = package_manager_command(trimmed) {
+                facets.insert((FacetKind::PackageManager, manager.to_string()));
+            }
+        }
+        let lowered = content.to_lowercase();
+        for name in FRAMEWORK_NAMES {
+            if contains_word(&lowered, name) {
+                facets.insert((FacetKind::Framework, canonical_framework(name)));
+            }
+        }
+    }
+    facets
+}
+
+/// Canonical language for a fence-opening line, if the info string names one.
+fn fence_language(line: &str) -> Option<&'static str> {
+    let info = line.strip_prefix("```")?.trim_start_matches('`');
+    let tag = info
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+        .next()
+        .unwrap_or("");
+    if tag.is_empty() {
+        return None;
+    }
+    let tag = tag.to_ascii_lowercase();
+    LANGUAGE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == tag)
+        .map(|(_, canonical)| *canonical)
+}
+
+/// Package manager for a line that runs one of its commands, if any.
+fn package_manager_command(line: &str) -> Option<&'static str> {
+    let command = line
+        .strip_prefix("$ ")
+        .or_else(|| line.strip_prefix("% "))
+        .or_else(|| line.strip_prefix("> "))
+        .unwrap_or(line);
+    PACKAGE_MANAGER_PREFIXES
+        .iter()
+        .find(|(prefix, _)| command.starts_with(prefix))
+        .map(|(_, manager)| *manager)
+}
+
+/// Whole-word containment: `needle` occurs in `haystack` with no adjacent
+/// alphanumeric characters. `haystack` must already be lowercase. Shared
+/// with the PII pass (`search::pii`) for customer-name dictionary matching.
+pub(super) fn contains_word(haystack: &str, needle: &str) -> bool {
+    let mut search_from = 0;
+    while let Some(offset) = haystack[search_from..].find(needle) {
+        let start = search_from + offset;
+        let end = start + needle.len();
+        let before_ok = haystack[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = haystack[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return true;
+        }
+        search_from = end;
+    }
+    false
+}
+
+/// Facet value for a matched framework name. Only `next.js` needs rewriting;
+/// a dot inside a `facet:` query term would read as part of the value, so the
+/// stored form drops it.
+fn canonical_framework(name: &str) -> String {
+    if name == "next.js" {
+        "nextjs".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    fn values(facets: &BTreeSet<(FacetKind, String)>, kind: FacetKind) -> Vec<&str> {
+        facets
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn fence_languages_are_normalized_through_aliases() {
+        let facets = extract_facets(&[
+            msg("agent", "```rs\nfn main() {}\n```"),
+            msg("agent", "  ```python\nprint('hi')\n```"),
+            msg("agent", "```made-up-lang\nnope\n```"),
+        ]);
+        assert_eq!(values(&facets, FacetKind::Language), vec!["python", "rust"]);
+    }
+
+    #[test]
+    fn framework_mentions_match_whole_words_only() {
+        let facets = extract_facets(&[
+            msg("user", "webpack keeps rebuilding the React bundle"),
+            msg("agent", "added guardrails around the config"),
+        ]);
+        assert_eq!(
+            values(&facets, FacetKind::Framework),
+            vec!["react", "webpack"]
+        );
+    }
+
+    #[test]
+    fn package_manager_commands_require_line_start() {
+        let facets = extract_facets(&[
+            msg("agent", "$ npm install left-pad"),
+            msg("agent", "cargo build --release"),
+            msg("user", "I think npm is the problem"),
+        ]);
+        assert_eq!(
+            values(&facets, FacetKind::PackageManager),
+            vec!["cargo", "npm"]
+        );
+    }
+
+    #[test]
+    fn next_js_stores_a_colon_safe_value() {
+        let facets = extract_facets(&[msg("user", "the Next.js build is broken")]);
+        assert_eq!(values(&facets, FacetKind::Framework), vec!["nextjs"]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Chunking of very long messages for the lexical index.
+//!
+//! A single message carrying 200 KB of tool output used to become one
+//! Tantivy document: BM25 length normalization buried it, and snippet
+//! generation had to re-tokenize the whole blob for every query that
+//! touched it. Messages above [`CHUNK_THRESHOLD_CHARS`] are instead
+//! indexed as several documents of roughly [`CHUNK_TARGET_CHARS`] each,
+//! split at line boundaries so a chunk never starts mid-line.
+//!
+//! The index schema is fixed upstream (frankensearch `CassDocument`), so
+//! the parent reference rides in the stored `msg_idx` field: the low 32
+//! bits keep the real message index, the high 32 bits carry the chunk
+//! ordinal. [`decode_chunk_msg_idx`] recovers both at read time — hit
+//! hydration and the viewer jump use the parent index, exactly as for an
+//! unchunked document, while the snippet comes from the matched chunk's
+//! own (small) stored content. Messages whose index would not fit in 32
+//! bits are left unchunked rather than risking a corrupt reference.
+
+use std::ops::Range;
+
+/// Messages at or below this many bytes are indexed as one document.
+pub const CHUNK_THRESHOLD_CHARS: usize = 32 * 1024;
+
+/// Approximate chunk size; actual chunks run to the next line boundary.
+pub const CHUNK_TARGET_CHARS: usize = 16 * 1024;
+
+/// High bits of the stored `msg_idx` carry the chunk ordinal.
+const CHUNK_SEQ_SHIFT: u32 = 32;
+const PARENT_IDX_MASK: u64 = u32::MAX as u64;
+
+/// One indexed segment of a long message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSpan {
+    /// Chunk ordinal within the message (0-based).
+    pub seq: u32,
+    /// Byte range of the chunk within the original content. Spans
+    /// partition the content exactly: concatenating them reproduces it.
+    pub range: Range<usize>,
+}
+
+/// Split `content` into chunk spans, or `None` when it is short enough
+/// to index whole. Boundaries land just after a newline where one exists
+/// within reach of the target size; a pathological single line (minified
+/// JSON, base64) falls back to the nearest UTF-8 character boundary.
+#[must_use]
+pub fn chunk_spans(content: &str) -> Option<Vec<ChunkSpan>> {
+    if content.len() <= CHUNK_THRESHOLD_CHARS {
+        return None;
+    }
+    let mut spans = Vec::with_capacity(content.len() / CHUNK_TARGET_CHARS + 1);
+    let mut start = 0usize;
+    while start < content.len() {
+        let mut end = start.saturating_add(CHUNK_TARGET_CHARS).min(content.len());
+        if end < content.len() {
+            match content[end..]
+                .bytes()
+                .take(CHUNK_TARGET_CHARS)
+                .position(|b| b == b'\n')
+            {
+                Some(pos) => end += pos + 1,
+                None => {
+                    while !content.is_char_boundary(end) {
+                        end += 1;
+                    }
+                }
+            }
+        }
+        let seq = u32::try_from(spans.len()).unwrap_or(u32::MAX);
+        spans.push(ChunkSpan {
+            seq,
+            range: start..end,
+        });
+        start = end;
+    }
+    Some(spans)
+}
+
+/// True when a message index leaves the high bits free for a chunk
+/// ordinal; callers skip chunking otherwise.
+#[must_use]
+pub fn chunkable_parent_idx(msg_idx: u64) -> bool {
+    msg_idx <= PARENT_IDX_MASK
+}
+
+/// Stored `msg_idx` for one chunk of the message at `parent_idx`.
+#[must_use]
+pub fn encode_chunk_msg_idx(parent_idx: u64, seq: u32) -> u64 {
+    debug_assert!(chunkable_parent_idx(parent_idx));
+    (u64::from(seq) << CHUNK_SEQ_SHIFT) | (parent_idx & PARENT_IDX_MASK)
+}
+
+/// Split a stored `msg_idx` into `(parent message index, chunk ordinal)`.
+/// Unchunked documents decode to ordinal 0, same as a first chunk.
+#[must_use]
+pub fn decode_chunk_msg_idx(stored: u64) -> (u64, u32) {
+    (stored & PARENT_IDX_MASK, (stored >> CHUNK_SEQ_SHIFT) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_messages_are_not_chunked() {
+        assert_eq!(chunk_spans("hello"), None);
+        assert_eq!(chunk_spans(&"x".repeat(CHUNK_THRESHOLD_CHARS)), None);
+    }
+
+    #[test]
+    fn spans_partition_content_and_break_after_newlines() {
+        let line = format!("{}\n", "tool output ".repeat(50));
+        let content = line.repeat(CHUNK_THRESHOLD_CHARS / line.len() + 10);
+        let spans = chunk_spans(&content).expect("long content chunks");
+        assert!(spans.len() > 1);
+        let mut rebuilt = String::new();
+        for (i, span) in spans.iter().enumerate() {
+            assert_eq!(span.seq as usize, i);
+            let chunk = &content[span.range.clone()];
+            if i + 1 < spans.len() {
+                assert!(chunk.ends_with('\n'), "interior chunk ends at a line");
+                assert!(chunk.len() >= CHUNK_TARGET_CHARS);
+            }
+            rebuilt.push_str(chunk);
+        }
+        assert_eq!(rebuilt, content);
+    }
+
+    #[test]
+    fn newline_free_content_splits_at_char_boundaries() {
+        // Multi-byte characters with no newlines anywhere.
+        let content = "\u{00e9}".repeat(CHUNK_THRESHOLD_CHARS);
+        let spans = chunk_spans(&content).expect("chunks");
+        assert!(spans.len() > 1);
+        for span in &spans {
+            assert!(content.is_char_boundary(span.range.start));
+            assert!(content.is_char_boundary(span.range.end));
+        }
+    }
+
+    #[test]
+    fn msg_idx_encoding_round_trips() {
+        assert_eq!(decode_chunk_msg_idx(encode_chunk_msg_idx(42, 0)), (42, 0));
+        assert_eq!(decode_chunk_msg_idx(encode_chunk_msg_idx(42, 7)), (42, 7));
+        // An unchunked document's stored index decodes to ordinal 0.
+        assert_eq!(decode_chunk_msg_idx(42), (42, 0));
+        assert!(chunkable_parent_idx(u64::from(u32::MAX)));
+        assert!(!chunkable_parent_idx(u64::from(u32::MAX) + 1));
+    }
+}
@@ -17,14 +17,22 @@
 //! - **[`policy`]**: Semantic policy contract: model defaults, tiers, budgets, invalidation.
 //! - **[`semantic_manifest`]**: Durable semantic asset manifests, backlog ledger, and checkpoints.
 //! - **[`canonicalize`]**: Text preprocessing for consistent embedding input.
+//! - **[`cjk`]**: CJK pre-segmentation so Han/kana runs tokenize into searchable units.
+//! - **[`message_chunking`]**: Long messages indexed as line-aligned chunks with parent references.
 //! - **[`ann_index`]**: HNSW-based approximate nearest neighbor index (Opt 9).
+//! - **[`boilerplate`]**: Cross-conversation repeated-content detection (default ranking exclusion).
+//! - **[`pasted_file`]**: Query-time detection of content pasted from files on disk (down-rank/exclude).
+//! - **[`related_terms`]**: Synonym, corpus-spelling, and co-occurrence suggestion sources.
+//! - **[`structured_query`]**: Inline `field:value` terms and grouping hoisted into search filters.
 //! - **[`two_tier_search`]**: Two-tier progressive search with fast/quality embeddings (bd-3dcw).
 //! - **[`pack_planner`]**: Deterministic answer-pack evidence selection core.
 
 pub mod ann_index;
 pub mod asset_state;
+pub mod boilerplate;
 pub(crate) mod bounded_discovery;
 pub mod canonicalize;
+pub mod cjk;
 pub(crate) mod command_envelope;
 pub(crate) mod contention_diagnostics;
 pub mod daemon_client;
@@ -32,6 +40,7 @@ pub(crate) mod drill_down;
 pub(crate) mod e2e_scenarios;
 pub mod embedder;
 pub mod embedder_registry;
+pub(crate) mod facets;
 pub mod fastembed_embedder;
 pub mod fastembed_reranker;
 pub(crate) mod fleet_cheap_probes;
@@ -40,13 +49,17 @@ pub(crate) mod human_readiness_summary;
 pub(crate) mod incident_categories;
 pub(crate) mod incident_redaction;
 pub(crate) mod liveness_fixtures;
+pub mod message_chunking;
 pub(crate) mod model_acquisition;
 pub mod model_download;
 pub mod model_manager;
 pub mod pack_planner;
+pub mod pasted_file;
+pub(crate) mod pii;
 pub mod policy;
 pub(crate) mod progress_contract;
 pub(crate) mod proof_log;
+pub(crate) mod quality;
 pub(crate) mod quarantine_status;
 pub mod query;
 pub(crate) mod readiness;
@@ -54,8 +67,10 @@ pub(crate) mod readiness_fixtures;
 pub(crate) mod readiness_projection;
 pub(crate) mod recovery_journeys;
 pub(crate) mod regression_corpus;
+pub mod related_terms;
 pub mod reranker;
 pub mod reranker_registry;
+pub mod result_grouping;
 pub mod runtime_optimizations;
 pub(crate) mod salvage_ledger;
 pub(crate) mod search_mode_metadata;
@@ -65,6 +80,7 @@ pub(crate) mod semantic_readiness;
 pub(crate) mod source_provenance;
 pub(crate) mod storage_integrity;
 pub(crate) mod storage_salvage;
+pub mod structured_query;
 pub mod tantivy;
 pub mod trust_correlation;
 pub mod trust_scoring;
@@ -0,0 +1,229 @@
+//! Pasted-file detection against files currently on disk in a workspace.
+//!
+//! Agents routinely paste whole files into the chat, and those messages index
+//! like any other content — so a query matching a term in the file surfaces
+//! the user's own source code instead of the discussion about it. This module
+//! fingerprints the files currently on disk in a workspace (the same
+//! whitespace-normalized hash plus MinHash shingling the [`boilerplate`]
+//! detector uses) so ranking can recognize a hit whose content is a pasted
+//! copy of a file. Such hits are down-ranked by default and dropped entirely
+//! with `cass search --exclude-pasted`.
+//!
+//! The check runs at query time against the live filesystem — files move and
+//! change after indexing, so nothing is persisted. The per-workspace index is
+//! bounded (file count, file size, skip lists for VCS/build directories) and
+//! cached per [`SearchClient`](crate::search::query::SearchClient).
+//!
+//! [`boilerplate`]: crate::search::boilerplate
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::search::boilerplate::{
+    NEAR_DUP_THRESHOLD, SIGNATURE_HASHES, content_fingerprint, minhash_signature,
+    signature_similarity,
+};
+
+/// Message content shorter than this is never checked against workspace
+/// files; small snippets quoted from a file are legitimate discussion.
+pub const MIN_PASTED_CHARS: usize = 400;
+
+/// Score multiplier applied to a hit recognized as a pasted file copy.
+pub const PASTED_FILE_SCORE_PENALTY: f32 = 0.5;
+
+/// Hard cap on files fingerprinted per workspace, so a pathological
+/// directory tree cannot stall a search.
+const MAX_FILES_PER_WORKSPACE: usize = 2_000;
+
+/// Files larger than this are skipped; nobody pastes a 256 KiB file into a
+/// chat and expects the discussion around it to stay searchable.
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+/// Directory names never descended into: VCS metadata, dependency caches,
+/// and build output are not files a user pastes from.
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+
+/// Fingerprints of the files currently on disk under one workspace root.
+#[derive(Debug, Default)]
+pub struct WorkspaceFileIndex {
+    exact: HashSet<u64>,
+    signatures: Vec<[u64; SIGNATURE_HASHES]>,
+}
+
+impl WorkspaceFileIndex {
+    /// Fingerprint the text files under `workspace`, bounded by
+    /// [`MAX_FILES_PER_WORKSPACE`] and [`MAX_FILE_BYTES`]. A missing or
+    /// unreadable workspace yields an empty index — detection is
+    /// best-effort and must never fail a search.
+    #[must_use]
+    pub fn build(workspace: &Path) -> Self {
+        let mut index = Self::default();
+        if !workspace.is_dir() {
+            return index;
+        }
+        let mut pending = vec![workspace.to_path_buf()];
+        let mut files_seen = 0usize;
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if files_seen >= MAX_FILES_PER_WORKSPACE {
+                    return index;
+                }
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let name = entry.file_name();
+                if file_type.is_dir() {
+                    let skip = name
+                        .to_str()
+                        .is_none_or(|n| SKIP_DIRS.contains(&n) || n.starts_with('.'));
+                    if !skip {
+                        pending.push(entry.path());
+                    }
+                    continue;
+                }
+                if !file_type.is_file() {
+                    // Symlinks are skipped: they can point outside the
+                    // workspace or form cycles.
+                    continue;
+                }
+                files_seen += 1;
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                // UTF-8 never has fewer bytes than characters, so a file
+                // below the character floor can be skipped without reading.
+                if meta.len() < MIN_PASTED_CHARS as u64 || meta.len() > MAX_FILE_BYTES {
+                    continue;
+                }
+                // read_to_string doubles as the binary filter: non-UTF-8
+                // content fails the read and is ignored.
+                let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                    continue;
+                };
+                if content.chars().count() < MIN_PASTED_CHARS {
+                    continue;
+                }
+                index.exact.insert(content_fingerprint(&content));
+                index.signatures.push(minhash_signature(&content));
+            }
+        }
+        index
+    }
+
+    /// True when no files were fingerprinted; callers can skip the per-hit
+    /// check entirely.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty()
+    }
+
+    /// Whether `content` is a copy of a fingerprinted workspace file — exact
+    /// up to whitespace layout, or near-identical by MinHash (a paste with a
+    /// line or two edited).
+    #[must_use]
+    pub fn matches(&self, content: &str) -> bool {
+        if self.exact.is_empty() || content.chars().count() < MIN_PASTED_CHARS {
+            return false;
+        }
+        if self.exact.contains(&content_fingerprint(content)) {
+            return true;
+        }
+        let signature = minhash_signature(content);
+        self.signatures
+            .iter()
+            .any(|known| signature_similarity(known, &signature) >= NEAR_DUP_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file with high shingle diversity, so a one-token edit perturbs a
+    /// vanishingly small fraction of the MinHash signature.
+    fn source_file_body() -> String {
+        let mut body = String::from("//! Generated opcode table; do not edit by hand.\n");
+        for i in 0..300 {
+            body.push_str(&format!("pub const OPCODE_{i:03}: u16 = {};\n", 0x1000 + i));
+        }
+        body
+    }
+
+    #[test]
+    fn pasted_copy_of_a_workspace_file_matches() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(temp.path().join("src")).expect("mkdir");
+        std::fs::write(temp.path().join("src/opcodes.rs"), source_file_body()).expect("write");
+
+        let index = WorkspaceFileIndex::build(temp.path());
+        assert!(!index.is_empty());
+        assert!(index.matches(&source_file_body()));
+        // Whitespace layout differences do not break the exact match.
+        let reflowed = source_file_body()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(index.matches(&reflowed));
+    }
+
+    #[test]
+    fn near_identical_paste_matches_via_minhash() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("opcodes.rs"), source_file_body()).expect("write");
+
+        let index = WorkspaceFileIndex::build(temp.path());
+        let mut pasted = source_file_body();
+        pasted.push_str("// EOF\n");
+        assert!(index.matches(&pasted));
+    }
+
+    #[test]
+    fn unrelated_discussion_and_short_quotes_do_not_match() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("opcodes.rs"), source_file_body()).expect("write");
+
+        let index = WorkspaceFileIndex::build(temp.path());
+        assert!(!index.matches("pub const OPCODE_017: u16 = 4119;"));
+        assert!(!index.matches(&format!(
+            "The opcode table keeps drifting from the spec, which means the \
+             decoder mislabels instructions after every regeneration. {}",
+            "We should check the generated table into review, diff it against \
+             the published ISA manual, and fail CI when an opcode moves. "
+                .repeat(4)
+        )));
+    }
+
+    #[test]
+    fn vcs_and_build_directories_are_not_fingerprinted() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        for dir in [".git", "target", "node_modules"] {
+            let path = temp.path().join(dir);
+            std::fs::create_dir(&path).expect("mkdir");
+            std::fs::write(path.join("blob.rs"), source_file_body()).expect("write");
+        }
+
+        let index = WorkspaceFileIndex::build(temp.path());
+        assert!(index.is_empty());
+        assert!(!index.matches(&source_file_body()));
+    }
+
+    #[test]
+    fn missing_workspace_yields_an_empty_index() {
+        let index = WorkspaceFileIndex::build(Path::new("/nonexistent/workspace/path"));
+        assert!(index.is_empty());
+    }
+}
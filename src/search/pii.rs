@@ -0,0 +1,249 @@
+//! Index-time PII classification tags.
+//!
+//! Secret redaction (see `indexer::redact_secrets`) keeps credentials out of
+//! the index, but credentials are not the only sensitive payload agents get
+//! fed: pasted support tickets carry customer email addresses, phone numbers,
+//! and real names. Those are legitimate index content — the operator may need
+//! to find that session again — but they must be *visible as a category*, so
+//! a whole class of conversations can be excluded from exports and shares
+//! wholesale and audited after the fact.
+//!
+//! This module classifies message text into coarse `pii-*` facet values that
+//! ride the existing facets machinery (`search::facets`, the `facets` table,
+//! and `facet:` query terms):
+//!
+//! - `pii-email` — an email address appears in the conversation.
+//! - `pii-phone` — a phone number appears in the conversation.
+//! - `pii-customer` — a name from the operator's configured dictionary
+//!   appears (see the `[pii]` table of `cass.toml`,
+//!   `search_defaults::PiiDefaults`).
+//!
+//! The values are deliberately coarse: the facet records *that* a
+//! conversation holds an email address, never *which* one, so the facets
+//! table itself stays free of the data it flags. Detection favors precision
+//! over recall, in the same spirit as the curated facet vocabulary — a false
+//! `pii-phone` on every ISO date would make the tag useless as an exclusion
+//! filter, while a missed exotic phone format just means one conversation
+//! needs manual review.
+
+use std::collections::BTreeSet;
+
+use super::facets::{FacetKind, contains_word};
+
+/// Facet value for conversations containing an email address.
+pub const EMAIL_FACET: &str = "pii-email";
+
+/// Facet value for conversations containing a phone number.
+pub const PHONE_FACET: &str = "pii-phone";
+
+/// Facet value for conversations mentioning a configured customer name.
+pub const CUSTOMER_FACET: &str = "pii-customer";
+
+/// Classify `(role, content)` messages into the `pii-*` facet set. Pure; the
+/// caller supplies rows from storage and the customer-name dictionary from
+/// config. Dictionary entries shorter than two characters are ignored — a
+/// one-letter "name" would tag nearly every conversation.
+#[must_use]
+pub fn extract_pii_facets(
+    messages: &[(String, String)],
+    customer_names: &[String],
+) -> BTreeSet<(FacetKind, String)> {
+    let names: Vec<String> = customer_names
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| name.chars().count() >= 2)
+        .collect();
+
+    let mut email = false;
+    let mut phone = false;
+    let mut customer = false;
+    for (_, content) in messages {
+        email = email || contains_email(content);
+        phone = phone || content.lines().any(contains_phone);
+        if !customer && !names.is_empty() {
+            let lowered = content.to_lowercase();
+            customer = names.iter().any(|name| contains_word(&lowered, name));
+        }
+    }
+
+    let mut facets = BTreeSet::new();
+    if email {
+        facets.insert((FacetKind::Pii, EMAIL_FACET.to_string()));
+    }
+    if phone {
+        facets.insert((FacetKind::Pii, PHONE_FACET.to_string()));
+    }
+    if customer {
+        facets.insert((FacetKind::Pii, CUSTOMER_FACET.to_string()));
+    }
+    facets
+}
+
+/// Whether the text contains something shaped like an email address: a
+/// non-empty local part of address characters, an `@`, and a dotted domain
+/// ending in an alphabetic TLD. `user@host` without a dot (ssh targets,
+/// decorators) does not count.
+fn contains_email(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    for (at, _) in text.match_indices('@') {
+        // Local part: walk back over address characters.
+        let mut start = at;
+        while start > 0 && is_local_char(bytes[start - 1] as char) {
+            start -= 1;
+        }
+        if start == at {
+            continue;
+        }
+        // Domain: walk forward over labels, requiring at least one dot and a
+        // trailing alphabetic TLD of two or more characters.
+        let mut end = at + 1;
+        while end < bytes.len() && is_domain_char(bytes[end] as char) {
+            end += 1;
+        }
+        let domain = &text[at + 1..end];
+        let Some((_, tld)) = domain.rsplit_once('.') else {
+            continue;
+        };
+        if tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()) {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_local_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn is_domain_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '.' || c == '-'
+}
+
+/// Whether a line contains something shaped like a phone number. Two shapes
+/// count, chosen so ISO dates, timestamps and version numbers do not:
+///
+/// - an international form — `+` followed by 10–15 digits with optional
+///   space/dash/dot/paren separators;
+/// - a North-American grouped form — digit groups of 3, 3, 4 (optionally
+///   with a parenthesized area code), e.g. `555-123-4567` or
+///   `(555) 123-4567`.
+fn contains_phone(line: &str) -> bool {
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '+' && c != '(' && !c.is_ascii_digit() {
+            continue;
+        }
+        // A candidate run: digits plus phone punctuation. Stop where the run
+        // stops; the outer loop resumes after it.
+        let mut end = start;
+        for (idx, c) in line[start..].char_indices() {
+            if c.is_ascii_digit() || matches!(c, '+' | '(' | ')' | '-' | '.' | ' ') {
+                end = start + idx + c.len_utf8();
+            } else {
+                break;
+            }
+        }
+        let run = line[start..end].trim_end();
+        if phone_run_matches(run) {
+            return true;
+        }
+        while chars.peek().is_some_and(|(idx, _)| *idx < end) {
+            chars.next();
+        }
+    }
+    false
+}
+
+/// Whether one punctuation-delimited run reads as a phone number.
+fn phone_run_matches(run: &str) -> bool {
+    let digits = run.chars().filter(char::is_ascii_digit).count();
+    if run.starts_with('+') {
+        return (10..=15).contains(&digits);
+    }
+    // Grouped form: split on separators and compare the group lengths. An
+    // area code in parens arrives as its own group once the parens are
+    // stripped.
+    let groups: Vec<usize> = run
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|g| !g.is_empty())
+        .map(str::len)
+        .collect();
+    groups == [3, 3, 4]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    fn values(facets: &BTreeSet<(FacetKind, String)>) -> Vec<&str> {
+        facets.iter().map(|(_, v)| v.as_str()).collect()
+    }
+
+    #[test]
+    fn email_addresses_tag_but_ssh_targets_do_not() {
+        let tagged = extract_pii_facets(&[msg("user", "ticket from jane.doe+vip@example.co")], &[]);
+        assert_eq!(values(&tagged), vec![EMAIL_FACET]);
+
+        let clean = extract_pii_facets(
+            &[msg(
+                "agent",
+                "ran ssh deploy@prod-web and @staticmethod fixes",
+            )],
+            &[],
+        );
+        assert!(clean.is_empty(), "{clean:?}");
+    }
+
+    #[test]
+    fn phone_shapes_match_but_dates_and_versions_do_not() {
+        for text in [
+            "call them at 555-123-4567",
+            "support line (555) 123-4567 ext 2",
+            "intl contact +44 20 7946 0958",
+        ] {
+            let facets = extract_pii_facets(&[msg("user", text)], &[]);
+            assert_eq!(values(&facets), vec![PHONE_FACET], "{text}");
+        }
+        for text in [
+            "deployed 2026-09-01 12:34:56",
+            "bumped to version 1.2.3456",
+            "timestamp 1700000000000 in the log",
+        ] {
+            let facets = extract_pii_facets(&[msg("user", text)], &[]);
+            assert!(facets.is_empty(), "{text}: {facets:?}");
+        }
+    }
+
+    #[test]
+    fn customer_dictionary_matches_whole_words_case_insensitively() {
+        let names = vec!["Acme Corp".to_string(), "Jane Doe".to_string()];
+        let tagged = extract_pii_facets(
+            &[msg("user", "the acme corp invoice pipeline is broken")],
+            &names,
+        );
+        assert_eq!(values(&tagged), vec![CUSTOMER_FACET]);
+
+        // Substrings of longer words must not count, and an empty dictionary
+        // never tags.
+        let clean = extract_pii_facets(&[msg("user", "the acmecorporate module")], &names);
+        assert!(clean.is_empty(), "{clean:?}");
+        let no_dict = extract_pii_facets(&[msg("user", "ask Jane Doe")], &[]);
+        assert!(no_dict.is_empty());
+    }
+
+    #[test]
+    fn categories_accumulate_across_messages() {
+        let facets = extract_pii_facets(
+            &[
+                msg("user", "forward this to ops@example.com"),
+                msg("user", "their number is 555-123-4567"),
+            ],
+            &[],
+        );
+        assert_eq!(values(&facets), vec![EMAIL_FACET, PHONE_FACET]);
+    }
+}
@@ -0,0 +1,203 @@
+//! Heuristic session-quality scoring.
+//!
+//! Not every indexed session went anywhere: a large fraction of any agent
+//! corpus is one-prompt dead ends, abandoned retries, and loops where the
+//! agent hit the same compile error twenty times and the human gave up.
+//! This module computes a cheap 0..1 "usefulness" score per conversation
+//! from signals that correlate with a session having produced something:
+//! enough messages to constitute real work, a back-and-forth between user
+//! and assistant rather than a monologue, evidence of applied diffs, and
+//! the absence of repeated-error loops.
+//!
+//! The score is a derived asset stored in the `conversation_quality` table
+//! and refreshed at the end of each non-watch index run
+//! (`FrankenStorage::refresh_conversation_quality`), mirroring the
+//! boilerplate registry and integrity seal passes. It is deliberately a
+//! coarse prior, not a ranking function: `cass sessions --min-quality` and
+//! `--sort-quality` use it to push dead ends below sessions that actually
+//! went somewhere.
+
+/// Message count at which the length component reaches 0.5. Sessions around
+/// a dozen messages are where real work starts; a two-message dead end
+/// scores near zero, and the component saturates instead of rewarding
+/// sheer bulk.
+const LENGTH_MIDPOINT_MESSAGES: f64 = 12.0;
+
+/// Occurrences of the same error-bearing message body before the session is
+/// treated as stuck in a loop rather than merely encountering errors.
+const ERROR_LOOP_REPEATS: usize = 3;
+
+/// Component weights. Length and balance dominate because they are the most
+/// reliable signals across harnesses; diff presence and error-loop absence
+/// refine the ordering among sessions of similar shape.
+const WEIGHT_LENGTH: f64 = 0.35;
+const WEIGHT_BALANCE: f64 = 0.30;
+const WEIGHT_DIFF: f64 = 0.15;
+const WEIGHT_NO_ERROR_LOOP: f64 = 0.20;
+
+/// Markers indicating a diff was produced or applied during the session.
+/// Matched against message bodies verbatim; connectors preserve tool output
+/// inline so applied patches reliably carry one of these.
+const DIFF_MARKERS: &[&str] = &[
+    "diff --git",
+    "+++ b/",
+    "*** Begin Patch",
+    "Applied patch",
+    "applied patch",
+];
+
+/// Markers indicating a message body reports an error. Deliberately loud
+/// forms only — a prose mention of the word "error" does not qualify.
+const ERROR_MARKERS: &[&str] = &[
+    "error:",
+    "Error:",
+    "ERROR",
+    "panicked at",
+    "Traceback (most recent call last)",
+    "FAILED",
+    "Exception",
+];
+
+/// Compute the usefulness score for one conversation from `(role, content)`
+/// pairs in message order. Pure; the caller supplies rows from storage.
+#[must_use]
+pub fn score_conversation(messages: &[(String, String)]) -> f64 {
+    if messages.is_empty() {
+        return 0.0;
+    }
+
+    let total = messages.len() as f64;
+    let length = total / (total + LENGTH_MIDPOINT_MESSAGES);
+
+    let user_turns = messages.iter().filter(|(role, _)| role == "user").count() as f64;
+    // Storage writes assistant turns as "agent"; some connectors preserve
+    // the raw "assistant" role string.
+    let assistant_turns = messages
+        .iter()
+        .filter(|(role, _)| role == "assistant" || role == "agent")
+        .count() as f64;
+    // Perfect alternation scores 1.0; a monologue (either direction) scores
+    // 0.0 because nothing was asked or nothing came back.
+    let balance = if user_turns + assistant_turns > 0.0 {
+        2.0 * user_turns.min(assistant_turns) / (user_turns + assistant_turns)
+    } else {
+        0.0
+    };
+
+    let has_diff = messages
+        .iter()
+        .any(|(_, content)| DIFF_MARKERS.iter().any(|marker| content.contains(marker)));
+
+    let no_error_loop = 1.0 - error_loop_factor(messages);
+
+    let score = WEIGHT_LENGTH * length
+        + WEIGHT_BALANCE * balance
+        + WEIGHT_DIFF * f64::from(has_diff)
+        + WEIGHT_NO_ERROR_LOOP * no_error_loop;
+    score.clamp(0.0, 1.0)
+}
+
+/// How error-loop-like the session is, 0..1. The base is the fraction of
+/// messages carrying an error marker; a session where the *same*
+/// error-bearing body recurs [`ERROR_LOOP_REPEATS`]+ times — the classic
+/// retry loop — is pushed to at least 0.75 regardless of session length,
+/// since long loops would otherwise dilute their own fraction.
+fn error_loop_factor(messages: &[(String, String)]) -> f64 {
+    let mut error_count = 0usize;
+    let mut seen: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
+    let mut looping = false;
+    for (_, content) in messages {
+        if !ERROR_MARKERS.iter().any(|marker| content.contains(marker)) {
+            continue;
+        }
+        error_count += 1;
+        let fingerprint = super::boilerplate::content_fingerprint(content);
+        let repeats = seen.entry(fingerprint).or_insert(0);
+        *repeats += 1;
+        if *repeats >= ERROR_LOOP_REPEATS {
+            looping = true;
+        }
+    }
+    let fraction = error_count as f64 / messages.len() as f64;
+    if looping {
+        fraction.max(0.75)
+    } else {
+        fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn empty_conversation_scores_zero() {
+        assert_eq!(score_conversation(&[]), 0.0);
+    }
+
+    #[test]
+    fn productive_session_outscores_dead_end() {
+        let productive: Vec<_> = (0..8)
+            .flat_map(|i| {
+                vec![
+                    msg("user", &format!("please fix issue {i}")),
+                    msg("assistant", "diff --git a/src/main.rs b/src/main.rs\ndone"),
+                ]
+            })
+            .collect();
+        let dead_end = vec![msg("user", "hello?")];
+        assert!(score_conversation(&productive) > score_conversation(&dead_end) + 0.4);
+    }
+
+    #[test]
+    fn monologue_scores_below_balanced_exchange() {
+        let monologue: Vec<_> = (0..10)
+            .map(|i| msg("assistant", &format!("thinking step {i}")))
+            .collect();
+        let balanced: Vec<_> = (0..5)
+            .flat_map(|i| {
+                vec![
+                    msg("user", &format!("question {i}")),
+                    msg("assistant", &format!("answer {i}")),
+                ]
+            })
+            .collect();
+        assert!(score_conversation(&balanced) > score_conversation(&monologue));
+    }
+
+    #[test]
+    fn repeated_error_loop_is_penalized() {
+        let looping: Vec<_> = (0..6)
+            .flat_map(|_| {
+                vec![
+                    msg("user", "try again"),
+                    msg("assistant", "error: cannot borrow `x` as mutable"),
+                ]
+            })
+            .collect();
+        let clean: Vec<_> = (0..6)
+            .flat_map(|i| {
+                vec![
+                    msg("user", "next step"),
+                    msg("assistant", &format!("step {i} complete")),
+                ]
+            })
+            .collect();
+        assert!(score_conversation(&clean) > score_conversation(&looping) + 0.1);
+    }
+
+    #[test]
+    fn distinct_errors_are_milder_than_a_loop() {
+        let loop_messages: Vec<_> = (0..4)
+            .map(|_| msg("assistant", "error: mismatched types"))
+            .collect();
+        let distinct: Vec<_> = (0..4)
+            .map(|i| msg("assistant", &format!("error: problem number {i}")))
+            .collect();
+        assert!(error_loop_factor(&loop_messages) > error_loop_factor(&distinct));
+    }
+}
@@ -148,6 +148,26 @@ fn open_search_hydration_sqlite(path: &Path, timeout: Duration) -> Result<Connec
     Ok(conn)
 }
 
+/// Narrow `filters.session_paths` to the `matched` set (intersecting with
+/// any existing restriction). An empty result inserts `sentinel` — a string
+/// containing NUL, which is invalid in file paths on every supported
+/// platform and so can never collide with a real source path — so the
+/// constraint truthfully matches nothing instead of being silently dropped.
+/// Shared by the `note:` and `facet:` term resolvers.
+fn narrow_session_paths(filters: &mut SearchFilters, matched: HashSet<String>, sentinel: &str) {
+    if matched.is_empty() {
+        filters.session_paths.clear();
+        filters.session_paths.insert(sentinel.to_string());
+    } else if filters.session_paths.is_empty() {
+        filters.session_paths = matched;
+    } else {
+        filters.session_paths.retain(|path| matched.contains(path));
+        if filters.session_paths.is_empty() {
+            filters.session_paths.insert(sentinel.to_string());
+        }
+    }
+}
+
 /// NFC-normalize a query string before sanitization so that decomposed
 /// Unicode (NFD — common on macOS keyboard input) matches NFC-indexed content
 /// produced by `DefaultCanonicalizer`.
@@ -375,6 +395,31 @@ pub struct SearchFilters {
     /// Filter to specific session source paths (for chained searches)
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     pub session_paths: HashSet<String>,
+    /// Agents excluded via inline `NOT agent:…` query terms
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub excluded_agents: HashSet<String>,
+    /// Workspaces excluded via inline `NOT workspace:…` query terms
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub excluded_workspaces: HashSet<String>,
+    /// Session source paths excluded via inline `NOT facet:…` query terms
+    /// (resolved against the facets table; the motivating case is dropping
+    /// PII-tagged conversations, see `search::pii`)
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub excluded_session_paths: HashSet<String>,
+    /// Keep hits matching the detected cross-conversation boilerplate
+    /// registry (`--include-boilerplate`); excluded by default.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub include_boilerplate: bool,
+    /// Drop hits whose content is a copy of a file currently on disk in the
+    /// hit's workspace (`--exclude-pasted`); such hits are down-ranked but
+    /// kept by default.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_pasted: bool,
+    /// Keep at most this many hits per conversation (`None` = uncapped).
+    /// Hits arrive in score order, so the survivors are each conversation's
+    /// strongest; repeats a chatty agent generated are collapsed away.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_hits_per_conversation: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, clap::ValueEnum)]
@@ -530,6 +575,29 @@ fn should_allow_automatic_wildcard_fallback(
     max_docs_for_automatic_wildcard > 0 && index_doc_count <= max_docs_for_automatic_wildcard
 }
 
+/// Search-time HNSW `ef` floor — the recall/latency tradeoff knob for
+/// approximate (ANN) semantic search. Raise it (e.g. 256, 512) to recover
+/// recall on large corpora at the cost of per-query latency, or lower it to
+/// shave milliseconds when rough ranking is acceptable. The effective `ef`
+/// is still raised to the candidate count at the call site because a `knn`
+/// search cannot return more hits than it explores.
+///
+/// Override with `CASS_ANN_EF_SEARCH=<n>`; unset or unparsable values fall
+/// back to frankensearch's default.
+fn ann_ef_search_floor() -> usize {
+    static EF: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+    *EF.get_or_init(|| ann_ef_search_floor_from(dotenvy::var("CASS_ANN_EF_SEARCH").ok().as_deref()))
+}
+
+/// Pure version of the `ef` floor resolution, with the env value passed in
+/// so unit tests can drive it without mutating process-global env.
+fn ann_ef_search_floor_from(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(FS_HNSW_DEFAULT_EF_SEARCH)
+}
+
 fn available_memory_bytes() -> Option<u64> {
     let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
     for line in meminfo.lines() {
@@ -779,6 +847,10 @@ pub struct QueryExplanation {
     pub filters_summary: FiltersSummary,
     /// Any issues or suggestions
     pub warnings: Vec<String>,
+    /// Per-shard latency breakdown when the query ran against a federated
+    /// (multi-generation) index; empty for single-index searches.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shard_timings: Vec<FederatedShardTiming>,
 }
 
 /// Summary of active filters for explanation
@@ -894,6 +966,7 @@ impl QueryExplanation {
             estimated_cost,
             filters_summary,
             warnings,
+            shard_timings: Vec::new(),
         }
     }
 
@@ -1112,6 +1185,13 @@ impl QueryExplanation {
         }
         self
     }
+
+    /// Attach per-shard timings from the most recent federated search so
+    /// `--explain` shows which attached shard dominated query latency.
+    pub fn with_shard_timings(mut self, timings: Vec<FederatedShardTiming>) -> Self {
+        self.shard_timings = timings;
+        self
+    }
 }
 
 /// Indicates how a search result matched the query.
@@ -1162,6 +1242,10 @@ pub enum SuggestionKind {
     AlternateAgent,
     /// Broaden date range
     BroaderDateRange,
+    /// Dev-term thesaurus synonym ("env var" ↔ "environment variable")
+    Synonym,
+    /// Term that frequently co-occurs with the query terms in the corpus
+    RelatedTerm,
 }
 
 /// A "did-you-mean" suggestion when search returns zero hits.
@@ -1227,16 +1311,42 @@ impl QuerySuggestion {
         }
     }
 
+    fn synonym(rewrite: &crate::search::related_terms::SynonymRewrite) -> Self {
+        Self {
+            kind: SuggestionKind::Synonym,
+            message: format!("Try synonym: \"{}\" → \"{}\"", rewrite.from, rewrite.to),
+            suggested_query: Some(rewrite.query.clone()),
+            suggested_filters: None,
+            shortcut: None,
+        }
+    }
+
+    fn related_term(term: &str) -> Self {
+        Self {
+            kind: SuggestionKind::RelatedTerm,
+            message: format!("Related term: \"{term}\""),
+            suggested_query: Some(term.to_string()),
+            suggested_filters: None,
+            shortcut: None,
+        }
+    }
+
     fn with_shortcut(mut self, key: u8) -> Self {
         self.shortcut = Some(key);
         self
     }
 }
 
+/// Fixed snippet window used when no adaptive budget has been supplied,
+/// preserving the historical behavior for callers that never look at the
+/// terminal (robot formats, tests).
+pub const DEFAULT_SNIPPET_CHARS: usize = 160;
+
 #[derive(Debug, Clone, Copy)]
 pub struct FieldMask {
     flags: u8,
     preview_content_chars: Option<usize>,
+    snippet_chars: Option<usize>,
 }
 
 impl FieldMask {
@@ -1248,6 +1358,7 @@ impl FieldMask {
     pub const FULL: Self = Self {
         flags: Self::CONTENT | Self::SNIPPET | Self::TITLE | Self::CACHE,
         preview_content_chars: None,
+        snippet_chars: None,
     };
 
     pub fn new(
@@ -1272,6 +1383,7 @@ impl FieldMask {
         Self {
             flags,
             preview_content_chars: None,
+            snippet_chars: None,
         }
     }
 
@@ -1283,6 +1395,18 @@ impl FieldMask {
         self
     }
 
+    /// Override the snippet character window (see [`adaptive_snippet_chars`]).
+    ///
+    /// `None` keeps [`DEFAULT_SNIPPET_CHARS`]. Unlike
+    /// [`Self::with_preview_content_limit`] this does not disable the prefix
+    /// cache: cached hits keep the budget in effect when they were filled, and
+    /// both frontends re-wrap for display, so a resize only changes freshly
+    /// fetched snippets rather than invalidating every typed prefix.
+    pub fn with_snippet_chars(mut self, max_chars: Option<usize>) -> Self {
+        self.snippet_chars = max_chars;
+        self
+    }
+
     pub fn needs_content(self) -> bool {
         self.flags & Self::CONTENT != 0
     }
@@ -1302,6 +1426,11 @@ impl FieldMask {
     pub fn preview_content_limit(self) -> Option<usize> {
         self.preview_content_chars
     }
+
+    /// The effective snippet window in characters.
+    pub fn snippet_char_budget(self) -> usize {
+        self.snippet_chars.unwrap_or(DEFAULT_SNIPPET_CHARS)
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -1353,7 +1482,9 @@ fn effective_field_mask(field_mask: FieldMask) -> FieldMask {
     if *LAZY_FIELDS_ENABLED {
         field_mask
     } else {
-        FieldMask::FULL
+        // Disabling lazy fields widens the projection to FULL but keeps the
+        // caller's snippet sizing.
+        FieldMask::FULL.with_snippet_chars(field_mask.snippet_chars)
     }
 }
 
@@ -1597,6 +1728,22 @@ impl PartialOrd for SearchHitKey {
 
 const FEDERATED_RRF_K: f32 = 60.0;
 
+/// Timing and hit counts for one shard of a federated lexical fan-out.
+/// Surfaced through `--explain` so operators can tell which attached shard
+/// dominates p95 latency as the corpus grows across generations.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FederatedShardTiming {
+    /// Position of the shard in the federated reader set.
+    pub shard_index: usize,
+    /// Wall-clock time the shard's query took, in milliseconds.
+    pub elapsed_ms: u64,
+    /// Hits the shard contributed before rank fusion and dedup.
+    pub hit_count: usize,
+    /// Exact total from the shard when cheap to collect, mirroring
+    /// `SearchResult::total_count` semantics.
+    pub total_count: Option<usize>,
+}
+
 #[derive(Debug)]
 struct FederatedRankedHit {
     hit: SearchHit,
@@ -2538,11 +2685,23 @@ pub struct SearchClient {
     metrics: Metrics,
     cache_namespace: String,
     semantic: Mutex<Option<SemanticSearchState>>,
+    /// Lazily loaded cross-conversation boilerplate registry (v23 table);
+    /// `None` until the first search that needs it. Loaded once per client —
+    /// the registry only changes at index time, matching client lifetime.
+    boilerplate: Mutex<Option<Arc<crate::search::boilerplate::BoilerplateIndex>>>,
+    /// Per-workspace pasted-file fingerprint indexes, built lazily at query
+    /// time against the live filesystem (files move and change after
+    /// indexing) and cached for the client lifetime.
+    pasted_files: Mutex<HashMap<String, Arc<crate::search::pasted_file::WorkspaceFileIndex>>>,
     /// Exact total from the most recent Tantivy query when collecting it was
     /// cheap enough. Large saturated pages leave this as `None` so robot output
     /// can truthfully report lower-bound count precision without blocking the
     /// top-N result path.
     last_tantivy_total_count: Mutex<Option<usize>>,
+    /// Per-shard timings from the most recent federated lexical fan-out.
+    /// Empty when the last lexical query ran against a single index, so
+    /// `--explain` never reports stale shard breakdowns.
+    last_shard_timings: Mutex<Vec<FederatedShardTiming>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -3036,7 +3195,7 @@ static SEARCH_CLIENT_INSTANCE_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 /// Calculate Levenshtein edit distance between two strings.
 /// Used for typo detection in did-you-mean suggestions.
-fn levenshtein_distance(a: &str, b: &str) -> usize {
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
     let a_chars: Vec<char> = a.chars().collect();
     let b_chars: Vec<char> = b.chars().collect();
     let a_len = a_chars.len();
@@ -3357,7 +3516,7 @@ fn snippet_from_preview_without_full_content(
         return None;
     }
 
-    cached_prefix_snippet(stored_preview, query, 160)
+    cached_prefix_snippet(stored_preview, query, field_mask.snippet_char_budget())
 }
 
 fn stored_preview_is_complete_content(stored_preview: &str) -> bool {
@@ -3455,7 +3614,10 @@ impl SearchClient {
             metrics,
             cache_namespace,
             semantic: Mutex::new(None),
+            boilerplate: Mutex::new(None),
+            pasted_files: Mutex::new(HashMap::new()),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         }))
     }
 
@@ -3498,6 +3660,22 @@ impl SearchClient {
         // matching the NFC-indexed content.
         use unicode_normalization::UnicodeNormalization;
         let query: String = query.nfc().collect();
+        // Inline field syntax (`agent:codex`, `NOT workspace:…`, grouped
+        // with parens) is hoisted into the filter set before the engines
+        // see the query; plain queries pass through untouched.
+        let (query, filters) = match crate::search::structured_query::parse(&query) {
+            Some(structured) => {
+                let mut filters = filters;
+                structured.apply_to_filters(&mut filters);
+                self.apply_note_terms(&structured, &mut filters);
+                self.apply_facet_terms(&structured, &mut filters);
+                (structured.text, filters)
+            }
+            None => (query, filters),
+        };
+        // Mirror the index-time CJK pre-segmentation so query tokens line
+        // up with indexed tokens (no-op when segmentation is off).
+        let query: String = crate::search::cjk::segment(&query).into_owned();
         let query: &str = &query;
         let sanitized = nfc_sanitize_query(query);
         let field_mask = effective_field_mask(field_mask);
@@ -3521,6 +3699,12 @@ impl SearchClient {
             self.track_generation(signature);
         }
 
+        // Reset shard timings so --explain only reports a breakdown when this
+        // query actually fanned out across federated shards.
+        if let Ok(mut timings) = self.last_shard_timings.lock() {
+            timings.clear();
+        }
+
         // Fast path: reuse cached prefix when user is typing forward (offset 0 only).
         // Only use cache for simple queries (no wildcards, no boolean operators) because
         // the cache matching logic enforces strict prefix AND semantics which is incorrect
@@ -3539,6 +3723,9 @@ impl SearchClient {
                     .filter(|h| hit_matches_query_cached_precomputed(h, &query_terms))
                     .map(|c| c.hit.clone())
                     .collect();
+                if let Some(max) = filters.max_hits_per_conversation.filter(|max| *max > 0) {
+                    crate::search::result_grouping::cap_hits_per_conversation(&mut filtered, max);
+                }
                 if filtered.len() >= limit {
                     filtered.truncate(limit);
                     self.metrics.inc_cache_hits();
@@ -4338,7 +4525,7 @@ impl SearchClient {
                 .fetch_limit
                 .saturating_mul(ANN_CANDIDATE_MULTIPLIER)
                 .max(request.fetch_limit);
-            let ef = FS_HNSW_DEFAULT_EF_SEARCH.max(candidate);
+            let ef = ann_ef_search_floor().max(candidate);
             let (ann_results, search_stats) =
                 ann.knn_search_with_stats(embedding, candidate, ef)
                     .map_err(|err| anyhow!("frankensearch approximate search failed: {err}"))?;
@@ -5441,6 +5628,20 @@ impl SearchClient {
         Option<crate::search::ann_index::AnnSearchStats>,
     )> {
         let field_mask = effective_field_mask(field_mask);
+        // Hoist inline field terms so the embedding only sees real query
+        // text and the constraints ride the filter machinery (same rewrite
+        // as the lexical path).
+        let (query, filters) = match crate::search::structured_query::parse(query) {
+            Some(structured) => {
+                let mut filters = filters;
+                structured.apply_to_filters(&mut filters);
+                self.apply_note_terms(&structured, &mut filters);
+                self.apply_facet_terms(&structured, &mut filters);
+                (structured.text, filters)
+            }
+            None => (query.to_string(), filters),
+        };
+        let query: &str = &query;
         let canonical = canonicalize_for_embedding(query);
         if canonical.trim().is_empty() {
             return Ok((Vec::new(), None));
@@ -5588,6 +5789,141 @@ impl SearchClient {
             .map(|rows| rows.into_iter().map(|(_, hit)| hit).collect())
     }
 
+    /// Lazily load the boilerplate registry, caching the (possibly empty)
+    /// result so a missing table is probed at most once per client.
+    fn boilerplate_index(&self) -> Arc<crate::search::boilerplate::BoilerplateIndex> {
+        if let Ok(mut guard) = self.boilerplate.lock() {
+            if let Some(index) = guard.as_ref() {
+                return Arc::clone(index);
+            }
+            let loaded = match self.sqlite_guard() {
+                Ok(sqlite) => sqlite
+                    .as_ref()
+                    .map(|conn| crate::search::boilerplate::BoilerplateIndex::load(&conn.0))
+                    .unwrap_or_default(),
+                Err(_) => crate::search::boilerplate::BoilerplateIndex::default(),
+            };
+            let loaded = Arc::new(loaded);
+            *guard = Some(Arc::clone(&loaded));
+            return loaded;
+        }
+        Arc::new(crate::search::boilerplate::BoilerplateIndex::default())
+    }
+
+    /// Lazily build the pasted-file fingerprint index for one workspace,
+    /// caching the (possibly empty) result so each workspace is walked at
+    /// most once per client.
+    fn workspace_file_index(
+        &self,
+        workspace: &str,
+    ) -> Arc<crate::search::pasted_file::WorkspaceFileIndex> {
+        if let Ok(mut guard) = self.pasted_files.lock() {
+            if let Some(index) = guard.get(workspace) {
+                return Arc::clone(index);
+            }
+            let built = Arc::new(crate::search::pasted_file::WorkspaceFileIndex::build(
+                Path::new(workspace),
+            ));
+            guard.insert(workspace.to_string(), Arc::clone(&built));
+            return built;
+        }
+        Arc::new(crate::search::pasted_file::WorkspaceFileIndex::default())
+    }
+
+    /// Resolve hoisted `note:` terms against the notes database and narrow
+    /// the session-path filter to conversations whose notes match (any-of
+    /// semantics, same as the other include sets). Source paths are STORED
+    /// but not indexed, so the existing post-search `session_paths` machinery
+    /// is the natural enforcement point. When no note matches — including
+    /// when `notes.db` was never created — the filter collapses to a
+    /// never-matching sentinel so `note:xyz` truthfully returns nothing
+    /// instead of silently ignoring the constraint.
+    fn apply_note_terms(
+        &self,
+        structured: &crate::search::structured_query::StructuredQuery,
+        filters: &mut SearchFilters,
+    ) {
+        if structured.notes.is_empty() {
+            return;
+        }
+        let notes_db = self
+            .sqlite_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.join("notes.db"))
+            .unwrap_or_else(crate::notes::default_notes_path);
+        let mut matched: HashSet<String> = HashSet::new();
+        match crate::notes::NoteStore::open_existing(&notes_db) {
+            Ok(Some(store)) => {
+                for term in &structured.notes {
+                    match store.source_paths_matching(term) {
+                        Ok(paths) => matched.extend(paths),
+                        Err(err) => {
+                            tracing::debug!(error = %err, term, "note filter lookup failed");
+                        }
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::debug!(error = %err, path = %notes_db.display(), "notes db open failed");
+            }
+        }
+        narrow_session_paths(filters, matched, "\u{0}note:no-match");
+    }
+
+    /// Resolve hoisted `facet:` terms against the facets table (see
+    /// `search::facets`) and narrow the session-path filter to conversations
+    /// carrying one of the facets (any-of semantics). Same enforcement point
+    /// as `note:`: when no facet matches — including on pre-v28 databases
+    /// with no facets table — the filter collapses to a never-matching
+    /// sentinel so `facet:xyz` truthfully returns nothing. `NOT facet:`
+    /// terms resolve through the same lookup but feed the excluded-path set
+    /// instead, so tagged conversations — `NOT facet:pii-email` being the
+    /// motivating case — drop out of results wholesale.
+    fn apply_facet_terms(
+        &self,
+        structured: &crate::search::structured_query::StructuredQuery,
+        filters: &mut SearchFilters,
+    ) {
+        if structured.facets.is_empty() && structured.excluded_facets.is_empty() {
+            return;
+        }
+        let mut matched: HashSet<String> = HashSet::new();
+        let mut excluded: HashSet<String> = HashSet::new();
+        if let Ok(guard) = self.sqlite_guard()
+            && let Some(conn) = guard.as_ref()
+        {
+            let mut lookup = |term: &String, into: &mut HashSet<String>| {
+                // Facet values are stored lowercase; accept `facet:Django`.
+                let value = term.to_lowercase();
+                match franken_query_map_collect_retry(
+                    conn,
+                    "SELECT DISTINCT c.source_path FROM facets f
+                     JOIN conversations c ON c.id = f.conversation_id
+                     WHERE f.value = ?1",
+                    &[ParamValue::from(value.as_str())],
+                    |row| row.get_typed::<String>(0),
+                ) {
+                    Ok(paths) => into.extend(paths),
+                    Err(err) => {
+                        tracing::debug!(error = %err, term, "facet filter lookup failed");
+                    }
+                }
+            };
+            for term in &structured.facets {
+                lookup(term, &mut matched);
+            }
+            for term in &structured.excluded_facets {
+                lookup(term, &mut excluded);
+            }
+        }
+        filters.excluded_session_paths.extend(excluded);
+        if !structured.facets.is_empty() {
+            narrow_session_paths(filters, matched, "\u{0}facet:no-match");
+        }
+    }
+
     fn postprocess_hits_page(
         &self,
         hits: Vec<SearchHit>,
@@ -5600,6 +5936,53 @@ impl SearchClient {
         if !filters.session_paths.is_empty() {
             hits.retain(|hit| filters.session_paths.contains(&hit.source_path));
         }
+        if !filters.excluded_session_paths.is_empty() {
+            hits.retain(|hit| !filters.excluded_session_paths.contains(&hit.source_path));
+        }
+        if !filters.excluded_agents.is_empty() {
+            hits.retain(|hit| !filters.excluded_agents.contains(&hit.agent));
+        }
+        if !filters.excluded_workspaces.is_empty() {
+            hits.retain(|hit| !filters.excluded_workspaces.contains(&hit.workspace));
+        }
+        if !filters.include_boilerplate {
+            let boilerplate = self.boilerplate_index();
+            if !boilerplate.is_empty() {
+                hits.retain(|hit| !boilerplate.matches(hit_content_for_noise_check(hit)));
+            }
+        }
+        // Pasted-file echoes: a hit whose content is a copy of a file
+        // currently on disk in its workspace is the user's own source code,
+        // not the discussion around it. Down-rank such hits so discussion
+        // outranks the echo; `--exclude-pasted` drops them entirely. The
+        // per-workspace fingerprint index is bounded and cached, and only
+        // hits long enough to plausibly be a whole pasted file are checked.
+        {
+            use crate::search::pasted_file::{MIN_PASTED_CHARS, PASTED_FILE_SCORE_PENALTY};
+            let mut down_ranked = false;
+            hits.retain_mut(|hit| {
+                let is_pasted = {
+                    let content = hit_content_for_noise_check(hit);
+                    content.chars().count() >= MIN_PASTED_CHARS
+                        && self.workspace_file_index(&hit.workspace).matches(content)
+                };
+                if !is_pasted {
+                    return true;
+                }
+                if filters.exclude_pasted {
+                    return false;
+                }
+                hit.score *= PASTED_FILE_SCORE_PENALTY;
+                down_ranked = true;
+                true
+            });
+            if down_ranked {
+                hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(CmpOrdering::Equal));
+            }
+        }
+        if let Some(max) = filters.max_hits_per_conversation.filter(|max| *max > 0) {
+            crate::search::result_grouping::cap_hits_per_conversation(&mut hits, max);
+        }
         let available_hits = hits.len();
         let paged_hits = hits.into_iter().skip(offset).take(limit).collect();
         (available_hits, paged_hits)
@@ -5627,9 +6010,11 @@ impl SearchClient {
             .ok()
             .and_then(|guard| *guard);
 
-        // Check if we should try wildcard fallback
+        // Check if we should try wildcard fallback. Field syntax counts as
+        // structured: wrapping `agent:codex` in `*…*` would destroy it.
         let query_has_wildcards = query.contains('*');
-        let has_boolean_or_phrase = fs_cass_has_boolean_operators(query);
+        let has_boolean_or_phrase = fs_cass_has_boolean_operators(query)
+            || crate::search::structured_query::has_field_syntax(query);
         let is_sparse = should_try_wildcard_fallback(hits.len(), limit, offset, sparse_threshold);
         let total_docs = self.total_docs();
         let automatic_wildcard_allowed = should_allow_automatic_wildcard_fallback(
@@ -5897,7 +6282,52 @@ impl SearchClient {
             }
         }
 
-        // 4. Suggest alternative agents if SQLite is already open and no agent
+        // 4. Synonym rewrite from the built-in dev-term thesaurus.
+        if suggestions.len() < 3
+            && let Some(rewrite) = crate::search::related_terms::synonym_rewrites(query)
+                .into_iter()
+                .next()
+        {
+            suggestions.push(QuerySuggestion::synonym(&rewrite));
+        }
+
+        // 5. Corpus-backed alternates, under the same already-open-SQLite
+        // rule as the alternate-agent probe below: spelling fixes drawn from
+        // the words that actually occur in recent messages, then terms that
+        // co-occur with the query terms.
+        if suggestions.len() < 3
+            && let Ok(sqlite_guard) = self.sqlite.lock()
+            && let Some(conn) = sqlite_guard.as_ref()
+            && let Ok(sample) = conn.query_map_collect(
+                "SELECT m.role, m.content FROM messages m ORDER BY m.id DESC LIMIT 400",
+                &[],
+                |row: &frankensqlite::Row| {
+                    Ok((row.get_typed::<String>(0)?, row.get_typed::<String>(1)?))
+                },
+            )
+        {
+            let vocab = crate::search::related_terms::vocabulary(&sample, 512);
+            for term in query_lower.split_whitespace() {
+                if suggestions.len() >= 3 {
+                    break;
+                }
+                if let Some(fixed) = crate::search::related_terms::spelling_fix(term, &vocab) {
+                    let corrected = query_lower.replacen(term, &fixed, 1);
+                    suggestions.push(QuerySuggestion::spelling(query, &corrected));
+                    break;
+                }
+            }
+            let query_terms: Vec<String> =
+                query_lower.split_whitespace().map(str::to_string).collect();
+            for term in crate::search::related_terms::cooccurring_terms(&query_terms, &sample, 2) {
+                if suggestions.len() >= 3 {
+                    break;
+                }
+                suggestions.push(QuerySuggestion::related_term(&term));
+            }
+        }
+
+        // 6. Suggest alternative agents if SQLite is already open and no agent
         // filter is set. Avoid lazy-opening storage solely for no-hit advice:
         // large read-only frankensqlite opens can dominate fast lexical misses.
         if filters.agents.is_empty()
@@ -6022,6 +6452,15 @@ impl SearchClient {
         *guard = Some(generation);
     }
 
+    /// Per-shard timings from the most recent federated lexical fan-out.
+    /// Empty when the last query ran against a single index.
+    pub fn last_shard_timings(&self) -> Vec<FederatedShardTiming> {
+        self.last_shard_timings
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
     fn hydrate_tantivy_hit_contents(
         &self,
         exact_keys: &[TantivyContentExactKey],
@@ -6224,6 +6663,7 @@ impl SearchClient {
         let mut pending_hits = Vec::with_capacity(top_docs.hits.len());
         let mut missing_exact_content_keys = Vec::new();
         let mut missing_fallback_content_keys = Vec::new();
+        let mut seen_message_keys: HashSet<(String, Option<usize>)> = HashSet::new();
 
         for ranked_hit in top_docs.hits {
             let score = ranked_hit.bm25_score;
@@ -6267,9 +6707,14 @@ impl SearchClient {
                 .filter(|s| !s.is_empty())
                 .map(String::from);
             let created_at = doc.get_first(fields.created_at).and_then(|v| v.as_i64());
+            // Long messages index as chunk documents whose stored msg_idx
+            // carries the chunk ordinal in its high bits
+            // (src/search/message_chunking.rs); hydration keys and the
+            // viewer jump both want the parent message index.
             let line_number = doc
                 .get_first(fields.msg_idx)
                 .and_then(|v| v.as_u64())
+                .map(|stored| crate::search::message_chunking::decode_chunk_msg_idx(stored).0)
                 .and_then(|i| usize::try_from(i).ok())
                 .map(|i| i.saturating_add(1));
             let raw_source_id = doc
@@ -6301,6 +6746,13 @@ impl SearchClient {
                 origin_host.as_deref(),
             );
 
+            // Several chunks of one long message can match the same query;
+            // docs arrive in rank order, so keep only the best-ranked chunk
+            // per message.
+            if !seen_message_keys.insert((source_path.clone(), line_number)) {
+                continue;
+            }
+
             let preview_satisfies_bounded_content =
                 field_mask.preview_content_limit().is_some() && !stored_preview.is_empty();
             let preview_satisfies_full_content = field_mask.needs_content()
@@ -6361,9 +6813,10 @@ impl SearchClient {
             && pending_hits
                 .iter()
                 .any(|pending| pending.stored_preview_snippet.is_none());
+        let snippet_budget = field_mask.snippet_char_budget();
         let snippet_generator = if needs_tantivy_snippet_generator {
             let snippet_cfg = FsSnippetConfig {
-                max_chars: 160,
+                max_chars: snippet_budget,
                 highlight_prefix: "<b>".to_string(),
                 highlight_postfix: "</b>".to_string(),
             };
@@ -6419,16 +6872,26 @@ impl SearchClient {
                     };
                     rendered
                         .map(|html| html.replace("<b>", "**").replace("</b>", "**"))
-                        .or_else(|| cached_prefix_snippet(&effective_content, sanitized_query, 160))
+                        .or_else(|| {
+                            cached_prefix_snippet(
+                                &effective_content,
+                                sanitized_query,
+                                snippet_budget,
+                            )
+                        })
                         .unwrap_or_else(|| {
-                            quick_prefix_snippet(&effective_content, sanitized_query, 160)
+                            quick_prefix_snippet(
+                                &effective_content,
+                                sanitized_query,
+                                snippet_budget,
+                            )
                         })
                 } else if let Some(sn) =
-                    cached_prefix_snippet(&effective_content, sanitized_query, 160)
+                    cached_prefix_snippet(&effective_content, sanitized_query, snippet_budget)
                 {
                     sn
                 } else {
-                    quick_prefix_snippet(&effective_content, sanitized_query, 160)
+                    quick_prefix_snippet(&effective_content, sanitized_query, snippet_budget)
                 }
             } else {
                 String::new()
@@ -6481,11 +6944,14 @@ impl SearchClient {
         limit: usize,
         field_mask: FieldMask,
     ) -> Result<(Vec<SearchHit>, Option<usize>)> {
-        let mut ranked_hits = Vec::new();
-        let mut total_count = Some(0usize);
-
-        for (shard_index, shard) in readers.iter().enumerate() {
-            let (shard_hits, shard_total_count) = self.search_tantivy(
+        // Fan shards out across scoped threads so federated latency tracks
+        // the slowest shard instead of the sum of all shards. A single shard
+        // keeps the direct call path to avoid thread overhead.
+        let fan_out_start = Instant::now();
+        let filters = &filters;
+        let run_shard = |shard: &FederatedIndexReader| {
+            let shard_start = Instant::now();
+            let outcome = self.search_tantivy(
                 &shard.reader,
                 &shard.fields,
                 raw_query,
@@ -6494,7 +6960,43 @@ impl SearchClient {
                 limit,
                 0,
                 field_mask,
-            )?;
+            );
+            (shard_start.elapsed(), outcome)
+        };
+        let shard_outcomes: Vec<(Duration, Result<(Vec<SearchHit>, Option<usize>)>)> =
+            if readers.len() > 1 {
+                std::thread::scope(|scope| {
+                    let handles: Vec<_> = readers
+                        .iter()
+                        .map(|shard| scope.spawn(move || run_shard(shard)))
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| match handle.join() {
+                            Ok(outcome) => outcome,
+                            Err(_) => (
+                                Duration::ZERO,
+                                Err(anyhow!("federated shard search thread panicked")),
+                            ),
+                        })
+                        .collect()
+                })
+            } else {
+                readers.iter().map(run_shard).collect()
+            };
+
+        let mut ranked_hits = Vec::new();
+        let mut total_count = Some(0usize);
+        let mut shard_timings = Vec::with_capacity(shard_outcomes.len());
+
+        for (shard_index, (shard_elapsed, outcome)) in shard_outcomes.into_iter().enumerate() {
+            let (shard_hits, shard_total_count) = outcome?;
+            shard_timings.push(FederatedShardTiming {
+                shard_index,
+                elapsed_ms: shard_elapsed.as_millis() as u64,
+                hit_count: shard_hits.len(),
+                total_count: shard_total_count,
+            });
             total_count = match (total_count, shard_total_count) {
                 (Some(total), Some(shard_total)) => Some(total.saturating_add(shard_total)),
                 _ => None,
@@ -6508,6 +7010,9 @@ impl SearchClient {
                 });
             }
         }
+        if let Ok(mut guard) = self.last_shard_timings.lock() {
+            *guard = shard_timings;
+        }
 
         let raw_hit_count = ranked_hits.len();
         let generation_signature = self.federated_generation_signature(readers);
@@ -6519,6 +7024,7 @@ impl SearchClient {
             total_count,
             raw_hit_count,
             returned_hit_count = combined_hits.len(),
+            fan_out_ms = fan_out_start.elapsed().as_millis() as u64,
             merge_policy = "rrf_rank_then_stable_hit_key",
             "federated lexical search merged shard results"
         );
@@ -6714,6 +7220,9 @@ impl SearchClient {
             || filters.created_to.is_some()
             || !filters.source_filter.is_all()
             || !filters.session_paths.is_empty()
+            || !filters.excluded_agents.is_empty()
+            || !filters.excluded_workspaces.is_empty()
+            || !filters.excluded_session_paths.is_empty()
     }
 
     fn sqlite_fts5_hit_matches_filters(hit: &SearchHit, filters: &SearchFilters) -> bool {
@@ -6723,6 +7232,11 @@ impl SearchClient {
         if !filters.workspaces.is_empty() && !filters.workspaces.contains(&hit.workspace) {
             return false;
         }
+        if filters.excluded_agents.contains(&hit.agent)
+            || filters.excluded_workspaces.contains(&hit.workspace)
+        {
+            return false;
+        }
         if filters.created_from.is_some() || filters.created_to.is_some() {
             let Some(created_at) = hit.created_at else {
                 return false;
@@ -6741,6 +7255,9 @@ impl SearchClient {
         if !filters.session_paths.is_empty() && !filters.session_paths.contains(&hit.source_path) {
             return false;
         }
+        if filters.excluded_session_paths.contains(&hit.source_path) {
+            return false;
+        }
 
         match &filters.source_filter {
             SourceFilter::All => true,
@@ -7667,7 +8184,7 @@ pub fn fuzz_transpile_to_fts5(raw_query: &str) -> Option<String> {
 /// Transpile a raw query string into an FTS5-compatible query string.
 /// Preserves custom precedence (OR > AND) by adding parentheses.
 /// Returns None if the query contains features unsupported by FTS5 (e.g. leading wildcards).
-fn transpile_to_fts5(raw_query: &str) -> Option<String> {
+pub(crate) fn transpile_to_fts5(raw_query: &str) -> Option<String> {
     let tokens = fs_cass_parse_boolean_query(raw_query);
     if tokens.is_empty() {
         return Some("".to_string());
@@ -8142,6 +8659,43 @@ fn is_prefix_only(query: &str) -> bool {
     tokens[0].chars().all(char::is_alphanumeric)
 }
 
+/// Columns a result row spends on its gutter (the `Snippet: ` label in the
+/// CLI, selection marker and padding in the TUI list) before snippet text
+/// starts.
+const SNIPPET_GUTTER_COLS: usize = 10;
+
+/// Extra window characters granted per additional query term, so multi-term
+/// queries have room to show every highlighted term instead of cutting off
+/// after the first.
+const SNIPPET_PER_TERM_BONUS: usize = 24;
+
+/// Compute the snippet character window for a display surface.
+///
+/// The base budget is two display lines of content at the given terminal
+/// width (minus the row gutter), widened slightly per additional query term
+/// and clamped to the configured `[min_chars, max_chars]` bounds (see
+/// `crate::search_defaults::resolve_snippet_bounds`). When the terminal width
+/// is unknown the base falls back to [`DEFAULT_SNIPPET_CHARS`], so
+/// non-interactive callers keep the historical fixed window.
+pub fn adaptive_snippet_chars(
+    terminal_width: Option<usize>,
+    query_terms: usize,
+    min_chars: usize,
+    max_chars: usize,
+) -> usize {
+    let (min_chars, max_chars) = if min_chars <= max_chars {
+        (min_chars, max_chars)
+    } else {
+        (max_chars, min_chars)
+    };
+    let base = match terminal_width {
+        Some(width) => width.saturating_sub(SNIPPET_GUTTER_COLS).saturating_mul(2),
+        None => DEFAULT_SNIPPET_CHARS,
+    };
+    let bonus = query_terms.saturating_sub(1).min(4) * SNIPPET_PER_TERM_BONUS;
+    (base + bonus).clamp(min_chars, max_chars)
+}
+
 fn quick_prefix_snippet(content: &str, query: &str, max_chars: usize) -> String {
     // Handle empty query case first
     if query.is_empty() {
@@ -9657,6 +10211,7 @@ mod tests {
             cache_namespace: format!("v{}|schema:{}", CACHE_KEY_VERSION, FS_CASS_SCHEMA_HASH),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
         let semantic_embedder: Arc<dyn Embedder> = fast_embedder;
         client.set_semantic_context(
@@ -10428,6 +10983,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         // Wildcard query should skip cache logic entirely (no miss recorded)
@@ -10478,6 +11034,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = vec![SearchHit {
@@ -10598,6 +11155,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
         let field_mask = FieldMask::new(false, true, true, true);
         let lexical_hit = SearchHit {
@@ -11107,6 +11665,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search("*handler", SearchFilters::default(), 5, 0, FieldMask::FULL)?;
@@ -11186,6 +11745,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search("auth", SearchFilters::default(), 5, 0, FieldMask::FULL)?;
@@ -11272,6 +11832,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search("auth", SearchFilters::default(), 5, 0, FieldMask::FULL)?;
@@ -11367,6 +11928,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let sqlite_hits = client.search_sqlite_fts5(
@@ -11471,6 +12033,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let guard = client
@@ -11623,6 +12186,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let guard = client.sqlite_guard()?;
@@ -11786,6 +12350,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
         let direct_hits = client.search_sqlite_fts5(
             Path::new(":memory:"),
@@ -11922,6 +12487,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let fallback_key = (
@@ -12055,6 +12621,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search("delta", SearchFilters::default(), 5, 0, FieldMask::FULL)?;
@@ -12153,6 +12720,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let local_hits = client.browse_by_date(
@@ -12257,6 +12825,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let remote_hits = client.search(
@@ -12379,6 +12948,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search(
@@ -12497,6 +13067,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.browse_by_date(
@@ -12589,6 +13160,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.hydrate_semantic_hits_with_ids(
@@ -12669,6 +13241,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.hydrate_semantic_hits_with_ids(
@@ -12741,6 +13314,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.hydrate_semantic_hits_with_ids(
@@ -12829,6 +13403,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let first_hit = SearchHit {
@@ -12945,6 +13520,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.hydrate_semantic_hits_with_ids(
@@ -13030,6 +13606,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let first_hit = SearchHit {
@@ -13122,6 +13699,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13204,6 +13782,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13286,6 +13865,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13369,6 +13949,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13465,6 +14046,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.browse_by_date(
@@ -13590,6 +14172,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13648,6 +14231,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -13702,6 +14286,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         client.metrics.inc_cache_hits();
@@ -13740,6 +14325,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
         let mut filters = SearchFilters::default();
         filters.workspaces.insert("/tmp/cass-workspace".into());
@@ -13803,6 +14389,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
         let filters = SearchFilters::default();
 
@@ -13844,6 +14431,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hit = SearchHit {
@@ -14055,6 +14643,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         // Large content to exceed byte cap quickly
@@ -15004,6 +15593,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn adaptive_snippet_chars_scales_with_width_within_bounds() {
+        // Unknown width keeps the historical fixed window.
+        assert_eq!(
+            adaptive_snippet_chars(None, 1, 80, 400),
+            DEFAULT_SNIPPET_CHARS
+        );
+        // A narrow pane clamps to the floor; an ultrawide clamps to the cap.
+        assert_eq!(adaptive_snippet_chars(Some(40), 1, 80, 400), 80);
+        assert_eq!(adaptive_snippet_chars(Some(400), 1, 80, 400), 400);
+        // In between, the window is roughly two lines of content.
+        assert_eq!(adaptive_snippet_chars(Some(100), 1, 80, 400), 180);
+    }
+
+    #[test]
+    fn adaptive_snippet_chars_widens_for_multi_term_queries() {
+        let one = adaptive_snippet_chars(Some(100), 1, 80, 400);
+        let three = adaptive_snippet_chars(Some(100), 3, 80, 400);
+        assert!(three > one, "extra terms should widen the window");
+        // The per-term bonus is capped so pathological queries stay bounded.
+        assert_eq!(
+            adaptive_snippet_chars(Some(100), 5, 80, 4000),
+            adaptive_snippet_chars(Some(100), 50, 80, 4000)
+        );
+    }
+
+    #[test]
+    fn adaptive_snippet_chars_tolerates_inverted_bounds() {
+        // Swapped min/max from a hand-edited config must not panic in clamp.
+        assert_eq!(adaptive_snippet_chars(Some(40), 1, 400, 80), 80);
+    }
+
+    #[test]
+    fn field_mask_snippet_budget_defaults_and_overrides() {
+        assert_eq!(FieldMask::FULL.snippet_char_budget(), DEFAULT_SNIPPET_CHARS);
+        let sized = FieldMask::new(false, true, true, true).with_snippet_chars(Some(240));
+        assert_eq!(sized.snippet_char_budget(), 240);
+        assert!(
+            sized.allows_cache(),
+            "snippet sizing must not disable the prefix cache"
+        );
+    }
+
     #[test]
     fn search_with_fallback_returns_exact_when_sufficient() -> Result<()> {
         let dir = TempDir::new()?;
@@ -15375,6 +16007,7 @@ mod tests {
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let result = client.search_with_fallback(
@@ -15467,6 +16100,7 @@ mod tests {
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let result = client.search_with_fallback(
@@ -15511,6 +16145,7 @@ mod tests {
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let mut filters = SearchFilters::default();
@@ -15633,6 +16268,101 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn generate_suggestions_offers_synonyms_and_corpus_alternates() -> Result<()> {
+        let dir = TempDir::new()?;
+        let db_path = dir.path().join("cass.db");
+        let storage = FrankenStorage::open(&db_path)?;
+        let workspace_id = storage.ensure_workspace(dir.path(), None)?;
+        let agent = Agent {
+            id: None,
+            slug: "codex".to_string(),
+            name: "codex".to_string(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent)?;
+        let base_ts = 1_700_000_020_000_i64;
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "codex".to_string(),
+            workspace: Some(dir.path().to_path_buf()),
+            external_id: Some("related-terms".to_string()),
+            title: Some("tokenizer work".to_string()),
+            source_path: dir.path().join("codex.jsonl"),
+            started_at: Some(base_ts),
+            ended_at: Some(base_ts),
+            approx_tokens: Some(8),
+            metadata_json: json!({}),
+            messages: vec![
+                Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: Some("user".into()),
+                    created_at: Some(base_ts),
+                    content: "the tokenizer panicked during commit".to_string(),
+                    extra_json: json!({}),
+                    snippets: Vec::new(),
+                },
+                Message {
+                    id: None,
+                    idx: 1,
+                    role: MessageRole::Agent,
+                    author: Some("codex".into()),
+                    created_at: Some(base_ts + 1),
+                    content: "tokenizer rebuilt; commit retried".to_string(),
+                    extra_json: json!({}),
+                    snippets: Vec::new(),
+                },
+            ],
+            source_id: crate::sources::provenance::LOCAL_SOURCE_ID.to_string(),
+            origin_host: None,
+        };
+        storage.insert_conversation_tree(agent_id, Some(workspace_id), &conversation)?;
+        drop(storage);
+
+        let client = SearchClient::open(dir.path(), Some(&db_path))?.expect("db-backed client");
+
+        // A typo of a corpus word gets a vocabulary-backed spelling fix.
+        let result = client.search_with_fallback(
+            "tokenzer",
+            SearchFilters::default(),
+            5,
+            0,
+            3,
+            FieldMask::FULL,
+        )?;
+        assert!(
+            result.suggestions.iter().any(|s| {
+                matches!(s.kind, SuggestionKind::SpellingFix)
+                    && s.suggested_query.as_deref() == Some("tokenizer")
+            }),
+            "corpus vocabulary should correct the typo: {:?}",
+            result.suggestions
+        );
+
+        // A thesaurus phrase gets a synonym rewrite.
+        let result = client.search_with_fallback(
+            "env var handling",
+            SearchFilters::default(),
+            5,
+            0,
+            3,
+            FieldMask::FULL,
+        )?;
+        assert!(
+            result.suggestions.iter().any(|s| {
+                matches!(s.kind, SuggestionKind::Synonym)
+                    && s.suggested_query.as_deref() == Some("environment variable handling")
+            }),
+            "thesaurus should rewrite env var: {:?}",
+            result.suggestions
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn sanitize_query_preserves_wildcards() {
         // Wildcards should be preserved
@@ -16504,6 +17234,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let filters_empty = SearchFilters::default();
@@ -16700,6 +17431,7 @@ mod tests {
             cache_namespace: "fts5-disabled".to_string(),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let hits = client.search_sqlite_fts5(
@@ -16840,6 +17572,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:k0e5p"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         // Hit-key tuple: (source_path, line_number) is the stable
@@ -17598,6 +18331,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         // Initial metrics should be zero
@@ -17636,6 +18370,7 @@ mod tests {
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
             last_tantivy_total_count: Mutex::new(None),
+            last_shard_timings: Mutex::new(Vec::new()),
         };
 
         let filters1 = SearchFilters::default();
@@ -18018,6 +18753,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn search_note_field_filters_to_annotated_sessions() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+
+        let paths = [
+            dir.path().join("session-a.jsonl"),
+            dir.path().join("session-b.jsonl"),
+        ];
+        for (i, path) in paths.iter().enumerate() {
+            let conv = NormalizedConversation {
+                agent_slug: "claude".into(),
+                external_id: None,
+                title: Some(format!("session-{}", i)),
+                workspace: Some(std::path::PathBuf::from("/ws")),
+                source_path: path.clone(),
+                started_at: Some(100 + i as i64),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(100 + i as i64),
+                    content: format!("needle content for session {}", i),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                    invocations: Vec::new(),
+                }],
+            };
+            index.add_conversation(&conv)?;
+        }
+        index.commit()?;
+
+        // notes.db lives next to the hydration sqlite (the data dir).
+        let notes = crate::notes::NoteStore::open(&dir.path().join("notes.db"))?;
+        notes.add(
+            paths[1].to_string_lossy().as_ref(),
+            Some(0),
+            "this was the actual fix",
+        )?;
+
+        let db_path = dir.path().join("cass.db");
+        let client = SearchClient::open(dir.path(), Some(&db_path))?.expect("index present");
+
+        let hits = client.search(
+            "needle note:fix",
+            SearchFilters::default(),
+            10,
+            0,
+            FieldMask::FULL,
+        )?;
+        assert_eq!(hits.len(), 1, "only the annotated session should match");
+        assert_eq!(hits[0].source_path, paths[1].to_string_lossy());
+
+        // A note term with no match must return nothing, not everything.
+        let none = client.search(
+            "needle note:zzz-absent",
+            SearchFilters::default(),
+            10,
+            0,
+            FieldMask::FULL,
+        )?;
+        assert!(none.is_empty(), "unmatched note term should yield no hits");
+
+        Ok(())
+    }
+
     #[test]
     fn lexical_session_paths_filter_retries_past_initial_page() -> Result<()> {
         let dir = TempDir::new()?;
@@ -18253,6 +19056,78 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn federated_search_records_per_shard_timings() -> Result<()> {
+        let root = TempDir::new()?;
+        let shard_a = root.path().join("shard-a");
+        let shard_b = root.path().join("shard-b");
+        let published = root.path().join("published");
+
+        let make_conv = |external_id: &str, source_path: &str, tag: &str| NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: Some(external_id.into()),
+            title: Some(format!("Timing {tag}")),
+            workspace: Some(std::path::PathBuf::from("/ws")),
+            source_path: std::path::PathBuf::from(source_path),
+            started_at: Some(1_700_000_200_000),
+            ended_at: Some(1_700_000_200_100),
+            metadata: json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1_700_000_200_010),
+                content: format!("timed federated needle {tag}"),
+                extra: json!({}),
+                snippets: vec![],
+                invocations: Vec::new(),
+            }],
+        };
+
+        let mut shard_a_index = TantivyIndex::open_or_create(&shard_a)?;
+        let mut shard_b_index = TantivyIndex::open_or_create(&shard_b)?;
+        shard_a_index.add_conversation(&make_conv(
+            "fed-time-a",
+            "/tmp/fed-time-a.jsonl",
+            "alpha",
+        ))?;
+        shard_b_index.add_conversation(&make_conv(
+            "fed-time-b",
+            "/tmp/fed-time-b.jsonl",
+            "beta",
+        ))?;
+        shard_a_index.commit()?;
+        shard_b_index.commit()?;
+        drop(shard_a_index);
+        drop(shard_b_index);
+
+        crate::search::tantivy::publish_federated_searchable_index_directories(
+            &published,
+            &[&shard_a, &shard_b],
+        )?;
+
+        let client = SearchClient::open(&published, None)?.expect("federated index present");
+        let hits = client.search(
+            "timed federated needle",
+            SearchFilters::default(),
+            10,
+            0,
+            FieldMask::FULL,
+        )?;
+        assert_eq!(hits.len(), 2);
+
+        let timings = client.last_shard_timings();
+        assert_eq!(timings.len(), 2, "one timing entry per federated shard");
+        assert_eq!(timings[0].shard_index, 0);
+        assert_eq!(timings[1].shard_index, 1);
+        assert!(
+            timings.iter().all(|timing| timing.hit_count == 1),
+            "each shard should contribute exactly one hit: {timings:?}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn semantic_search_session_paths_filter_retries_past_initial_candidates() -> Result<()> {
         let fixture = build_semantic_test_fixture()?;
@@ -18571,6 +19446,24 @@ mod tests {
         assert!(!should_allow_automatic_wildcard_fallback(usize::MAX, 0));
     }
 
+    #[test]
+    fn ann_ef_search_floor_defaults_and_parses_overrides() {
+        // Driven via the pure `*_from` helper so we can't race with other
+        // concurrent tests that read the real env.
+        assert_eq!(ann_ef_search_floor_from(None), FS_HNSW_DEFAULT_EF_SEARCH);
+        assert_eq!(ann_ef_search_floor_from(Some("512")), 512);
+        // Zero and garbage both fall back to the default rather than
+        // silently disabling the ANN candidate exploration.
+        assert_eq!(
+            ann_ef_search_floor_from(Some("0")),
+            FS_HNSW_DEFAULT_EF_SEARCH
+        );
+        assert_eq!(
+            ann_ef_search_floor_from(Some("fast")),
+            FS_HNSW_DEFAULT_EF_SEARCH
+        );
+    }
+
     #[test]
     fn compute_no_limit_result_cap_uses_meminfo_when_no_env_override() {
         // 128 GiB available → 128 / 16 = 8 GiB budget (under the 16 GiB
@@ -0,0 +1,250 @@
+//! Contextual related-search suggestions for sparse or zero-hit queries.
+//!
+//! The did-you-mean machinery in [`crate::search::query`] historically knew
+//! three tricks: wildcards, filter removal, and agent-name typos. This
+//! module adds the content-aware alternates it feeds from:
+//!
+//! * **Synonyms** from a small built-in dev-term thesaurus ("env var" ↔
+//!   "environment variable"), applied as whole-word rewrites of the query.
+//! * **Spelling fixes** against the corpus vocabulary — the words that
+//!   actually appear in indexed messages — rather than a fixed word list.
+//! * **Co-occurring terms**: words that frequently appear in the same
+//!   messages as the query terms, for queries that match *something* but
+//!   not what the user called it.
+//!
+//! Everything here is pure and deterministic over a caller-supplied
+//! `(role, content)` message sample (the [`crate::ngrams`] contract); the
+//! suggestion generator owns the database read and only samples when a
+//! connection is already open.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ngrams::{is_stopword, tokenize};
+use crate::search::query::levenshtein_distance;
+
+/// Equivalence groups of developer phrasing. Lowercase; multi-word phrases
+/// allowed. Deliberately small — a synonym suggestion is only useful when
+/// the pairing is near-certain.
+const THESAURUS: &[&[&str]] = &[
+    &["env var", "environment variable", "envvar"],
+    &["dir", "directory", "folder"],
+    &["auth", "authentication"],
+    &["config", "configuration"],
+    &["db", "database"],
+    &["repo", "repository"],
+    &["arg", "argument", "param", "parameter"],
+    &["docs", "documentation"],
+    &["perf", "performance"],
+    &["regex", "regular expression"],
+    &["k8s", "kubernetes"],
+    &["segfault", "segmentation fault"],
+    &["ci", "continuous integration"],
+    &["fn", "function"],
+    &["cmd", "command"],
+    &["msg", "message"],
+    &["dep", "dependency"],
+    &["error", "exception"],
+];
+
+/// Vocabulary words shorter than this are never offered as spelling fixes;
+/// short words have too many accidental near-neighbors.
+const MIN_SPELLING_TERM_CHARS: usize = 4;
+
+/// One thesaurus-driven rewrite of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SynonymRewrite {
+    /// The phrase found in the query.
+    pub from: String,
+    /// The synonym it was replaced with.
+    pub to: String,
+    /// The full query after replacement.
+    pub query: String,
+}
+
+/// All whole-word thesaurus rewrites of `query`, in thesaurus order.
+/// Callers typically take the first one or two.
+#[must_use]
+pub fn synonym_rewrites(query: &str) -> Vec<SynonymRewrite> {
+    let lower = query.to_lowercase();
+    // Byte offsets found in the lowercase copy are only safe to splice into
+    // the original when lowercasing didn't change byte lengths.
+    if lower.len() != query.len() {
+        return Vec::new();
+    }
+    let mut rewrites = Vec::new();
+    for group in THESAURUS {
+        for phrase in *group {
+            let Some(at) = find_whole_phrase(&lower, phrase) else {
+                continue;
+            };
+            for synonym in *group {
+                if synonym == phrase {
+                    continue;
+                }
+                let mut rewritten = String::with_capacity(query.len());
+                rewritten.push_str(&query[..at]);
+                rewritten.push_str(synonym);
+                rewritten.push_str(&query[at + phrase.len()..]);
+                rewrites.push(SynonymRewrite {
+                    from: (*phrase).to_string(),
+                    to: (*synonym).to_string(),
+                    query: rewritten,
+                });
+            }
+            break;
+        }
+    }
+    rewrites
+}
+
+/// Byte offset of `phrase` in `haystack` where both ends land on word
+/// boundaries, or `None`.
+fn find_whole_phrase(haystack: &str, phrase: &str) -> Option<usize> {
+    let mut search_from = 0usize;
+    while let Some(found) = haystack[search_from..].find(phrase) {
+        let at = search_from + found;
+        let end = at + phrase.len();
+        let before_ok = at == 0
+            || haystack[..at]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = haystack[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return Some(at);
+        }
+        search_from = at + 1;
+    }
+    None
+}
+
+/// Word frequencies over a message sample: stopword-filtered prose tokens
+/// from user and agent messages, highest count first (ties broken by word
+/// so the ordering is deterministic), truncated to `cap` entries.
+#[must_use]
+pub fn vocabulary(messages: &[(String, String)], cap: usize) -> Vec<(String, u64)> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for (role, content) in messages {
+        if role != "user" && role != "agent" {
+            continue;
+        }
+        for token in tokenize(content) {
+            if token.chars().count() < 3 || is_stopword(&token) {
+                continue;
+            }
+            *counts.entry(token).or_insert(0) += 1;
+        }
+    }
+    let mut rows: Vec<(String, u64)> = counts.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows.truncate(cap);
+    rows
+}
+
+/// The most frequent vocabulary word within a small edit distance of
+/// `term`, or `None`. Distance 1 for short terms, 2 from seven characters
+/// up — the same ramp a typo plausibly follows.
+#[must_use]
+pub fn spelling_fix(term: &str, vocabulary: &[(String, u64)]) -> Option<String> {
+    let term = term.to_lowercase();
+    if term.chars().count() < MIN_SPELLING_TERM_CHARS {
+        return None;
+    }
+    let max_distance = if term.chars().count() >= 7 { 2 } else { 1 };
+    vocabulary
+        .iter()
+        .find(|(word, _)| {
+            word.chars().count() >= MIN_SPELLING_TERM_CHARS
+                && *word != term
+                && levenshtein_distance(word, &term) <= max_distance
+        })
+        .map(|(word, _)| word.clone())
+}
+
+/// Words that co-occur with the query terms, by document frequency: each
+/// message containing any query term contributes its other distinct prose
+/// words once. Top `top` words, count descending, word ascending on ties.
+#[must_use]
+pub fn cooccurring_terms(
+    query_terms: &[String],
+    messages: &[(String, String)],
+    top: usize,
+) -> Vec<String> {
+    let needles: HashSet<String> = query_terms.iter().map(|t| t.to_lowercase()).collect();
+    if needles.is_empty() {
+        return Vec::new();
+    }
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for (role, content) in messages {
+        if role != "user" && role != "agent" {
+            continue;
+        }
+        let tokens = tokenize(content);
+        if !tokens.iter().any(|t| needles.contains(t)) {
+            continue;
+        }
+        let distinct: HashSet<&String> = tokens
+            .iter()
+            .filter(|t| t.chars().count() >= 3 && !is_stopword(t) && !needles.contains(*t))
+            .collect();
+        for token in distinct {
+            *counts.entry(token.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut rows: Vec<(String, u64)> = counts.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows.truncate(top);
+    rows.into_iter().map(|(word, _)| word).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn synonym_rewrites_replace_whole_phrases_only() {
+        let rewrites = synonym_rewrites("set the env var for the daemon");
+        assert!(rewrites.iter().any(|r| r.to == "environment variable"
+            && r.query == "set the environment variable for the daemon"));
+        // "dirty" must not trigger the "dir" group.
+        assert!(synonym_rewrites("dirty worktree").is_empty());
+        // Case-insensitive match, original casing outside the phrase kept.
+        let upper = synonym_rewrites("DB migration");
+        assert!(upper.iter().any(|r| r.query == "database migration"));
+    }
+
+    #[test]
+    fn spelling_fix_prefers_frequent_corpus_words() {
+        let sample = vec![
+            msg("user", "the tokenizer panicked again"),
+            msg("agent", "the tokenizer needs a rebuild"),
+            msg("user", "tokenizer tokenizer"),
+        ];
+        let vocab = vocabulary(&sample, 64);
+        assert_eq!(
+            spelling_fix("tokenzer", &vocab).as_deref(),
+            Some("tokenizer")
+        );
+        assert_eq!(spelling_fix("db", &vocab), None, "short terms never match");
+        assert_eq!(spelling_fix("tokenizer", &vocab), None, "exact is no typo");
+    }
+
+    #[test]
+    fn cooccurring_terms_count_document_frequency() {
+        let sample = vec![
+            msg("user", "the indexer panicked during commit"),
+            msg("agent", "indexer commit retried"),
+            msg("user", "unrelated message about breakfast"),
+            msg("tool", "indexer indexer commit"),
+        ];
+        let related = cooccurring_terms(&["indexer".to_string()], &sample, 2);
+        assert_eq!(related, vec!["commit".to_string(), "panicked".to_string()]);
+    }
+}
@@ -0,0 +1,351 @@
+//! Server-side result sorting and conversation grouping.
+//!
+//! Search returns flat message hits; agents and the TUI both want a
+//! conversation-level view ("which sessions matched, and how strongly")
+//! without re-aggregating client side. This module owns that aggregation:
+//! hits collapse into [`ConversationGroup`]s keyed by `(source_id,
+//! source_path)` with a per-conversation best score and hit count, and both
+//! flat hits and groups support the `--sort score|recent|messages|workspace`
+//! orders.
+
+use crate::search::query::SearchHit;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Sort orders for search results (flat hits or conversation groups).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultSort {
+    /// Relevance score, best first (the engine's native order)
+    #[default]
+    Score,
+    /// Most recently created message first
+    Recent,
+    /// Conversations with the most matching messages first
+    Messages,
+    /// Workspace path, ascending, then score within a workspace
+    Workspace,
+}
+
+/// Grouping modes for search output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// Collapse message hits into one row per conversation
+    Conversation,
+}
+
+/// One conversation's aggregate across its matching messages.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationGroup {
+    /// Conversation title (from the best-scoring hit).
+    pub title: String,
+    /// Source path identifying the conversation.
+    pub source_path: String,
+    /// Agent slug.
+    pub agent: String,
+    /// Workspace path.
+    pub workspace: String,
+    /// Source identifier (e.g. "local", "work-laptop").
+    pub source_id: String,
+    /// Best score among the conversation's hits.
+    pub best_score: f32,
+    /// Number of matching messages in this conversation.
+    pub hit_count: usize,
+    /// Most recent `created_at` among the hits (unix millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_created_at: Option<i64>,
+    /// Snippet from the best-scoring hit, for display.
+    pub top_snippet: String,
+    /// Line number of the best-scoring hit (1-indexed).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_line_number: Option<usize>,
+}
+
+/// Collapse flat hits into per-conversation groups. Groups come back in best
+/// score order; apply [`sort_groups`] for other orders.
+#[must_use]
+pub fn group_hits_by_conversation(hits: &[SearchHit]) -> Vec<ConversationGroup> {
+    let mut by_conversation: HashMap<(String, String), ConversationGroup> = HashMap::new();
+
+    for hit in hits {
+        let key = (hit.source_id.clone(), hit.source_path.clone());
+        match by_conversation.get_mut(&key) {
+            Some(group) => {
+                group.hit_count += 1;
+                if hit.created_at > group.latest_created_at {
+                    group.latest_created_at = hit.created_at;
+                }
+                if hit.score > group.best_score {
+                    group.best_score = hit.score;
+                    group.title = hit.title.clone();
+                    group.top_snippet = hit.snippet.clone();
+                    group.top_line_number = hit.line_number;
+                }
+            }
+            None => {
+                by_conversation.insert(
+                    key,
+                    ConversationGroup {
+                        title: hit.title.clone(),
+                        source_path: hit.source_path.clone(),
+                        agent: hit.agent.clone(),
+                        workspace: hit.workspace.clone(),
+                        source_id: hit.source_id.clone(),
+                        best_score: hit.score,
+                        hit_count: 1,
+                        latest_created_at: hit.created_at,
+                        top_snippet: hit.snippet.clone(),
+                        top_line_number: hit.line_number,
+                    },
+                );
+            }
+        }
+    }
+
+    let mut groups: Vec<ConversationGroup> = by_conversation.into_values().collect();
+    sort_groups(&mut groups, ResultSort::Score);
+    groups
+}
+
+/// Keep at most `max` hits per conversation, preserving hit order, and
+/// report how many were dropped per `(source_id, source_path)` key.
+///
+/// This is the "agent repeated itself 50 times" guard: hits arrive in score
+/// order, so the kept hits are each conversation's strongest and the
+/// suppressed count feeds the TUI's `+N more matches in this conversation`
+/// expander. `max == 0` means uncapped and returns an empty map.
+pub fn cap_hits_per_conversation(
+    hits: &mut Vec<SearchHit>,
+    max: usize,
+) -> HashMap<(String, String), usize> {
+    let mut suppressed: HashMap<(String, String), usize> = HashMap::new();
+    if max == 0 {
+        return suppressed;
+    }
+    let mut kept: HashMap<(String, String), usize> = HashMap::new();
+    hits.retain(|hit| {
+        let key = (hit.source_id.clone(), hit.source_path.clone());
+        let count = kept.entry(key.clone()).or_insert(0);
+        if *count < max {
+            *count += 1;
+            true
+        } else {
+            *suppressed.entry(key).or_default() += 1;
+            false
+        }
+    });
+    suppressed
+}
+
+/// Sort conversation groups by the requested order. Ties break on best score
+/// so ordering stays deterministic for equal keys.
+pub fn sort_groups(groups: &mut [ConversationGroup], sort: ResultSort) {
+    match sort {
+        ResultSort::Score => {
+            groups.sort_by(|a, b| {
+                b.best_score
+                    .total_cmp(&a.best_score)
+                    .then_with(|| a.source_path.cmp(&b.source_path))
+            });
+        }
+        ResultSort::Recent => {
+            groups.sort_by(|a, b| {
+                b.latest_created_at
+                    .cmp(&a.latest_created_at)
+                    .then_with(|| b.best_score.total_cmp(&a.best_score))
+            });
+        }
+        ResultSort::Messages => {
+            groups.sort_by(|a, b| {
+                b.hit_count
+                    .cmp(&a.hit_count)
+                    .then_with(|| b.best_score.total_cmp(&a.best_score))
+            });
+        }
+        ResultSort::Workspace => {
+            groups.sort_by(|a, b| {
+                a.workspace
+                    .cmp(&b.workspace)
+                    .then_with(|| b.best_score.total_cmp(&a.best_score))
+            });
+        }
+    }
+}
+
+/// Sort flat hits by the requested order. `Messages` ranks hits by how many
+/// hits their conversation contributed (busiest conversations first), which
+/// keeps the flat view consistent with the grouped one.
+pub fn sort_hits(hits: &mut [SearchHit], sort: ResultSort) {
+    match sort {
+        ResultSort::Score => {
+            hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        }
+        ResultSort::Recent => {
+            hits.sort_by(|a, b| {
+                b.created_at
+                    .cmp(&a.created_at)
+                    .then_with(|| b.score.total_cmp(&a.score))
+            });
+        }
+        ResultSort::Messages => {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for hit in hits.iter() {
+                *counts.entry(hit.source_path.as_str()).or_default() += 1;
+            }
+            let counts: HashMap<String, usize> = counts
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect();
+            hits.sort_by(|a, b| {
+                counts
+                    .get(b.source_path.as_str())
+                    .cmp(&counts.get(a.source_path.as_str()))
+                    .then_with(|| b.score.total_cmp(&a.score))
+            });
+        }
+        ResultSort::Workspace => {
+            hits.sort_by(|a, b| {
+                a.workspace
+                    .cmp(&b.workspace)
+                    .then_with(|| b.score.total_cmp(&a.score))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(source_path: &str, score: f32, created_at: i64, workspace: &str) -> SearchHit {
+        SearchHit {
+            title: format!("title {source_path}"),
+            snippet: format!("snippet {score}"),
+            content: String::new(),
+            content_hash: 0,
+            conversation_id: None,
+            score,
+            source_path: source_path.to_string(),
+            agent: "claude-code".to_string(),
+            workspace: workspace.to_string(),
+            workspace_original: None,
+            created_at: Some(created_at),
+            line_number: Some(1),
+            match_type: Default::default(),
+            source_id: "local".to_string(),
+            origin_kind: "local".to_string(),
+            origin_host: None,
+        }
+    }
+
+    #[test]
+    fn groups_collapse_hits_with_best_score_and_counts() {
+        let hits = vec![
+            hit("/a.jsonl", 0.5, 100, "/ws/a"),
+            hit("/a.jsonl", 0.9, 200, "/ws/a"),
+            hit("/b.jsonl", 0.7, 300, "/ws/b"),
+        ];
+        let groups = group_hits_by_conversation(&hits);
+        assert_eq!(groups.len(), 2);
+
+        let a = groups.iter().find(|g| g.source_path == "/a.jsonl").unwrap();
+        assert_eq!(a.hit_count, 2);
+        assert!((a.best_score - 0.9).abs() < f32::EPSILON);
+        assert_eq!(a.latest_created_at, Some(200));
+        assert_eq!(a.top_snippet, "snippet 0.9");
+    }
+
+    #[test]
+    fn default_group_order_is_best_score_first() {
+        let hits = vec![
+            hit("/low.jsonl", 0.2, 100, "/ws"),
+            hit("/high.jsonl", 0.9, 50, "/ws"),
+        ];
+        let groups = group_hits_by_conversation(&hits);
+        assert_eq!(groups[0].source_path, "/high.jsonl");
+    }
+
+    #[test]
+    fn sort_groups_recent_and_messages() {
+        let hits = vec![
+            hit("/old-busy.jsonl", 0.3, 100, "/ws"),
+            hit("/old-busy.jsonl", 0.4, 110, "/ws"),
+            hit("/old-busy.jsonl", 0.5, 120, "/ws"),
+            hit("/new-quiet.jsonl", 0.9, 900, "/ws"),
+        ];
+        let mut groups = group_hits_by_conversation(&hits);
+
+        sort_groups(&mut groups, ResultSort::Recent);
+        assert_eq!(groups[0].source_path, "/new-quiet.jsonl");
+
+        sort_groups(&mut groups, ResultSort::Messages);
+        assert_eq!(groups[0].source_path, "/old-busy.jsonl");
+    }
+
+    #[test]
+    fn sort_groups_workspace_is_ascending() {
+        let hits = vec![
+            hit("/z.jsonl", 0.9, 100, "/ws/zeta"),
+            hit("/a.jsonl", 0.1, 100, "/ws/alpha"),
+        ];
+        let mut groups = group_hits_by_conversation(&hits);
+        sort_groups(&mut groups, ResultSort::Workspace);
+        assert_eq!(groups[0].workspace, "/ws/alpha");
+    }
+
+    #[test]
+    fn sort_hits_messages_ranks_busy_conversations_first() {
+        let mut hits = vec![
+            hit("/quiet.jsonl", 0.9, 100, "/ws"),
+            hit("/busy.jsonl", 0.3, 100, "/ws"),
+            hit("/busy.jsonl", 0.2, 100, "/ws"),
+        ];
+        sort_hits(&mut hits, ResultSort::Messages);
+        assert_eq!(hits[0].source_path, "/busy.jsonl");
+        assert_eq!(hits[2].source_path, "/quiet.jsonl");
+    }
+
+    #[test]
+    fn cap_keeps_strongest_hits_and_counts_the_rest() {
+        let mut hits = vec![
+            hit("/busy.jsonl", 0.9, 100, "/ws"),
+            hit("/busy.jsonl", 0.8, 100, "/ws"),
+            hit("/busy.jsonl", 0.7, 100, "/ws"),
+            hit("/quiet.jsonl", 0.5, 100, "/ws"),
+        ];
+        let suppressed = cap_hits_per_conversation(&mut hits, 2);
+        assert_eq!(hits.len(), 3);
+        assert!((hits[0].score - 0.9).abs() < f32::EPSILON);
+        assert!((hits[1].score - 0.8).abs() < f32::EPSILON);
+        assert_eq!(hits[2].source_path, "/quiet.jsonl");
+        assert_eq!(
+            suppressed
+                .get(&("local".to_string(), "/busy.jsonl".to_string()))
+                .copied(),
+            Some(1)
+        );
+        assert_eq!(suppressed.len(), 1);
+    }
+
+    #[test]
+    fn cap_zero_means_uncapped() {
+        let mut hits = vec![
+            hit("/a.jsonl", 0.9, 100, "/ws"),
+            hit("/a.jsonl", 0.8, 100, "/ws"),
+        ];
+        let suppressed = cap_hits_per_conversation(&mut hits, 0);
+        assert_eq!(hits.len(), 2);
+        assert!(suppressed.is_empty());
+    }
+
+    #[test]
+    fn remote_and_local_conversations_with_same_path_stay_distinct() {
+        let mut remote = hit("/same.jsonl", 0.5, 100, "/ws");
+        remote.source_id = "work-laptop".to_string();
+        let hits = vec![hit("/same.jsonl", 0.6, 100, "/ws"), remote];
+        let groups = group_hits_by_conversation(&hits);
+        assert_eq!(groups.len(), 2);
+    }
+}
@@ -582,6 +582,32 @@ impl SemanticShardManifest {
         before.saturating_sub(self.shards.len())
     }
 
+    /// Drop shard records for this tier/embedder whose vectors were built by
+    /// a different model revision than the one currently installed. Returns
+    /// the number of records dropped.
+    ///
+    /// [`SemanticShardRecord::matches_generation`] keys a generation on tier,
+    /// embedder, and DB fingerprint — deliberately not model revision, so
+    /// old-revision vectors stay queryable while a rebuild catches up. That
+    /// means rows from two revisions of the *same* embedder silently coexist
+    /// until something drains the old ones; `cass models backfill
+    /// --refresh-stale` calls this so semantic search only mounts shards
+    /// compatible with the installed model.
+    pub fn purge_stale_model_shards(
+        &mut self,
+        tier: TierKind,
+        embedder_id: &str,
+        current_model_revision: &str,
+    ) -> usize {
+        let before = self.shards.len();
+        self.shards.retain(|shard| {
+            !(shard.tier == tier
+                && shard.embedder_id == embedder_id
+                && shard.model_revision != current_model_revision)
+        });
+        before.saturating_sub(self.shards.len())
+    }
+
     pub fn total_size_bytes(&self) -> u64 {
         self.shards
             .iter()
@@ -1115,6 +1141,60 @@ impl SemanticManifest {
         count
     }
 
+    /// Drop the artifact for `tier` (plus its dependent HNSW accelerator and
+    /// build checkpoint) when its vectors were built by a different model
+    /// revision than the one currently installed. Returns the number of
+    /// records dropped.
+    ///
+    /// This is the explicit, operator-driven counterpart to
+    /// [`InvalidationAction::RebuildInBackground`]: the soft policy keeps
+    /// old-revision vectors queryable until a rebuild replaces them, but
+    /// nothing schedules that rebuild on its own, so vectors from the old
+    /// model silently coexist with new ones indefinitely. `cass models
+    /// backfill --refresh-stale` calls this first; with the artifact and
+    /// checkpoint gone, the bounded backfill restarts from offset zero and
+    /// re-embeds the tier with the installed model.
+    pub fn refresh_stale_model_artifacts(
+        &mut self,
+        tier: TierKind,
+        embedder_id: &str,
+        current_model_revision: &str,
+    ) -> usize {
+        let mut count = 0;
+
+        let slot = match tier {
+            TierKind::Fast => &mut self.fast_tier,
+            TierKind::Quality => &mut self.quality_tier,
+        };
+        if slot.as_ref().is_some_and(|artifact| {
+            artifact.embedder_id == embedder_id && artifact.model_revision != current_model_revision
+        }) {
+            *slot = None;
+            count += 1;
+        }
+        if count == 0 {
+            return 0;
+        }
+
+        // The accelerator indexes into the dropped base artifact.
+        if self
+            .hnsw
+            .as_ref()
+            .is_some_and(|hnsw| hnsw.base_tier == tier)
+        {
+            self.hnsw = None;
+            count += 1;
+        }
+        // A checkpoint for this tier tracked progress against the stale
+        // artifact's generation; clearing it makes the next batch restart
+        // the tier instead of resuming past already-stale rows.
+        if self.checkpoint.as_ref().is_some_and(|cp| cp.tier == tier) {
+            self.checkpoint = None;
+        }
+
+        count
+    }
+
     /// Total disk usage of all semantic artifacts (bytes).
     pub fn total_size_bytes(&self) -> u64 {
         let fast = self.fast_tier.as_ref().map_or(0, |a| a.size_bytes);
@@ -1942,6 +2022,59 @@ mod tests {
         assert!(manifest.quality_tier.is_some());
     }
 
+    // ── Stale model refresh ────────────────────────────────────────────
+
+    #[test]
+    fn refresh_stale_model_artifacts_drops_old_revision_tier_and_dependents() {
+        let mut stale = test_artifact(TierKind::Quality, true);
+        stale.model_revision = "old-revision".to_owned();
+        let mut manifest = SemanticManifest {
+            fast_tier: Some(test_artifact(TierKind::Fast, true)),
+            quality_tier: Some(stale),
+            hnsw: Some(test_hnsw()), // depends on quality tier
+            checkpoint: Some(test_checkpoint(TierKind::Quality)),
+            ..Default::default()
+        };
+
+        let count =
+            manifest.refresh_stale_model_artifacts(TierKind::Quality, "minilm-384", "abc123");
+
+        assert_eq!(count, 2); // quality + hnsw
+        assert!(manifest.quality_tier.is_none());
+        assert!(manifest.hnsw.is_none());
+        assert!(
+            manifest.checkpoint.is_none(),
+            "checkpoint for the refreshed tier must be cleared so the backfill restarts"
+        );
+        assert!(
+            manifest.fast_tier.is_some(),
+            "the other tier is untouched by a quality refresh"
+        );
+    }
+
+    #[test]
+    fn refresh_stale_model_artifacts_keeps_current_revision_and_other_embedders() {
+        let mut manifest = SemanticManifest {
+            quality_tier: Some(test_artifact(TierKind::Quality, true)),
+            checkpoint: Some(test_checkpoint(TierKind::Quality)),
+            ..Default::default()
+        };
+
+        // Same revision → nothing to refresh.
+        assert_eq!(
+            manifest.refresh_stale_model_artifacts(TierKind::Quality, "minilm-384", "abc123"),
+            0
+        );
+        // Different embedder entirely → out of scope for a revision refresh
+        // (embedder changes go through DiscardAndRebuild invalidation).
+        assert_eq!(
+            manifest.refresh_stale_model_artifacts(TierKind::Quality, "snowflake-384", "zzz999"),
+            0
+        );
+        assert!(manifest.quality_tier.is_some());
+        assert!(manifest.checkpoint.is_some());
+    }
+
     // ── Legacy adoption ────────────────────────────────────────────────
 
     #[test]
@@ -2321,4 +2454,29 @@ mod tests {
         assert_eq!(shards.shards.len(), 1);
         assert_eq!(shards.total_size_bytes(), 4096);
     }
+
+    #[test]
+    fn shard_manifest_purges_stale_model_revision_shards() {
+        let mut stale = test_shard(0, 2, true);
+        stale.model_revision = "old-revision".to_owned();
+        let mut other_embedder = test_shard(1, 2, true);
+        other_embedder.embedder_id = "minilm-384".to_owned();
+        other_embedder.model_revision = "old-revision".to_owned();
+        let mut shards = SemanticShardManifest {
+            shards: vec![stale, test_shard(1, 2, true), other_embedder],
+            ..Default::default()
+        };
+
+        let purged = shards.purge_stale_model_shards(TierKind::Fast, "fnv1a-384", "hash");
+
+        assert_eq!(purged, 1, "only the old-revision shard of this embedder");
+        assert_eq!(shards.shards.len(), 2);
+        assert!(
+            shards
+                .shards
+                .iter()
+                .all(|shard| shard.model_revision == "hash" || shard.embedder_id == "minilm-384"),
+            "current-revision and other-embedder shards survive"
+        );
+    }
 }
@@ -0,0 +1,595 @@
+//! Inline field filters and grouping in the query language.
+//!
+//! Lets a single query string combine boolean operators with field
+//! constraints, e.g.:
+//!
+//! ```text
+//! (agent:codex OR agent:claude) AND "segfault" NOT workspace:playground
+//! ```
+//!
+//! The boolean text operators (`AND`/`OR`/`NOT`, `&&`/`||`/`!`, quoted
+//! phrases) are already understood by the lexical engine; this module layers
+//! `field:value` terms and parenthesized grouping on top. Field constraints
+//! are *hoisted* out of the expression into [`SearchFilters`] — where the
+//! existing agent/workspace machinery (Tantivy facet filters, SQLite WHERE
+//! clauses, post-hydration checks) applies them efficiently — and the
+//! remaining text terms are re-rendered as a flat boolean query for the
+//! engine. Because the rewrite happens inside `SearchClient`, the CLI, TUI,
+//! and robot/server modes all share the same syntax for free.
+//!
+//! Hoisting rules, applied along the top-level AND spine:
+//!
+//! - `field:value` narrows the corresponding include set.
+//! - `NOT field:value` adds to the corresponding exclude set.
+//! - An OR group whose branches are all the *same* field
+//!   (`agent:a OR agent:b`) unions into the include set, which is exactly
+//!   OR semantics for a set-membership filter.
+//! - Anything else stays in the residual text query. A field term trapped
+//!   inside a mixed OR group (`crash OR agent:codex`) cannot be expressed
+//!   as a filter and degrades to a plain text term — a documented
+//!   approximation rather than a parse error.
+
+use crate::search::query::SearchFilters;
+use crate::sources::provenance::SourceFilter;
+use std::collections::HashSet;
+
+/// Field keys recognized in `field:value` terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKey {
+    Agent,
+    Workspace,
+    Source,
+    Note,
+    Facet,
+}
+
+impl FieldKey {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "agent" | "provider" | "tool" => Some(Self::Agent),
+            "workspace" | "ws" | "project" => Some(Self::Workspace),
+            "source" => Some(Self::Source),
+            "note" => Some(Self::Note),
+            "facet" => Some(Self::Facet),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Term(String),
+    Phrase(String),
+    Field(FieldKey, String),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+}
+
+/// A query decomposed into hoisted field constraints plus residual text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructuredQuery {
+    /// Flat boolean text query for the lexical/semantic engines. May be
+    /// empty when the input was pure field constraints.
+    pub text: String,
+    pub agents: HashSet<String>,
+    pub workspaces: HashSet<String>,
+    pub excluded_agents: HashSet<String>,
+    pub excluded_workspaces: HashSet<String>,
+    pub source: Option<String>,
+    /// `note:` terms — a hit matches when any of its conversation's notes
+    /// contains one of these substrings. Unlike the other fields this needs
+    /// the notes database to resolve, so `SearchClient` turns it into a
+    /// session-path filter rather than [`apply_to_filters`].
+    pub notes: HashSet<String>,
+    /// `facet:` terms — topic chips derived at index time (languages,
+    /// frameworks, package managers, PII tags; see `search::facets`).
+    /// Resolved against the facets table into a session-path filter the same
+    /// way `note:` is.
+    pub facets: HashSet<String>,
+    /// `NOT facet:` terms — conversations carrying any of these facets are
+    /// dropped from results. Added so PII-tagged sessions (`NOT
+    /// facet:pii-email`, see `search::pii`) can be excluded wholesale when
+    /// selecting material to export or share.
+    pub excluded_facets: HashSet<String>,
+}
+
+impl StructuredQuery {
+    /// Merge the hoisted constraints into an existing filter set. Include
+    /// sets extend (an explicit `--agent` flag and an inline `agent:` term
+    /// union, both being allow-lists); the source filter only overrides
+    /// when the caller left it at "all".
+    pub fn apply_to_filters(&self, filters: &mut SearchFilters) {
+        filters.agents.extend(self.agents.iter().cloned());
+        filters.workspaces.extend(self.workspaces.iter().cloned());
+        filters
+            .excluded_agents
+            .extend(self.excluded_agents.iter().cloned());
+        filters
+            .excluded_workspaces
+            .extend(self.excluded_workspaces.iter().cloned());
+        if let Some(source) = &self.source
+            && filters.source_filter.is_all()
+        {
+            filters.source_filter = SourceFilter::parse(source);
+        }
+    }
+}
+
+/// Cheap pre-check: does the query use `field:value` syntax at all? Used
+/// both to gate the full parse and to suppress automatic wildcard fallback
+/// (wrapping `agent:codex` in `*…*` would destroy the field term).
+#[must_use]
+pub fn has_field_syntax(raw: &str) -> bool {
+    raw.split_whitespace().any(|word| {
+        let word = word.trim_start_matches(['(', '!', '-']);
+        word.split_once(':')
+            .is_some_and(|(name, value)| FieldKey::parse(name).is_some() && !value.is_empty())
+    })
+}
+
+/// Parse a query with inline field terms. Returns `None` when the query
+/// contains no field syntax, so the legacy path stays byte-for-byte
+/// untouched for plain queries.
+#[must_use]
+pub fn parse(raw: &str) -> Option<StructuredQuery> {
+    if !has_field_syntax(raw) {
+        return None;
+    }
+    let tokens = tokenize(raw);
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        depth: 0,
+    };
+    let expr = parser.parse_or()?;
+    let mut out = StructuredQuery::default();
+    let residual = hoist(expr, &mut out);
+    out.text = residual.map(|expr| render(&expr)).unwrap_or_default();
+    Some(out)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Phrase(String),
+    Field(FieldKey, String),
+    Word(String),
+}
+
+fn tokenize(raw: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '"' => {
+                chars.next();
+                let mut phrase = String::new();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    phrase.push(c);
+                }
+                tokens.push(Token::Phrase(phrase));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.as_str() {
+                    "AND" | "&&" => Token::And,
+                    "OR" | "||" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => word_token(word),
+                });
+            }
+        }
+    }
+    tokens
+}
+
+fn word_token(word: String) -> Token {
+    if let Some((name, value)) = word.split_once(':')
+        && let Some(key) = FieldKey::parse(name)
+        && !value.is_empty()
+    {
+        return Token::Field(key, value.to_string());
+    }
+    Token::Word(word)
+}
+
+/// Nesting cap for the recursive-descent parser. Queries are typed or
+/// pasted, so anything approaching this depth is garbage input — and the
+/// grammar already degrades gracefully on malformed queries (unbalanced
+/// parens, dangling operators), so a pasted `((((…` wall must bail to
+/// `None` like they do rather than overflow the stack and abort the
+/// whole process.
+const MAX_NESTING_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Guard for the two points where the grammar actually nests
+    /// (`(…)` groups and `NOT` chains): bail past
+    /// [`MAX_NESTING_DEPTH`] levels instead of recursing unbounded.
+    fn descend<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_or(&mut self) -> Option<Expr> {
+        let mut branches = vec![self.parse_and()?];
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.pos += 1;
+            branches.push(self.parse_and()?);
+        }
+        Some(if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Expr::Or(branches)
+        })
+    }
+
+    fn parse_and(&mut self) -> Option<Expr> {
+        let mut branches = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                // Explicit AND, or implicit AND on simple adjacency.
+                Some(Token::And) => {
+                    self.pos += 1;
+                    branches.push(self.parse_unary()?);
+                }
+                Some(Token::Or | Token::Close) | None => break,
+                Some(_) => branches.push(self.parse_unary()?),
+            }
+        }
+        Some(if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Expr::And(branches)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Option<Expr> {
+        match self.peek()? {
+            Token::Not => {
+                self.pos += 1;
+                let inner = self.descend(Self::parse_unary)?;
+                Some(Expr::Not(Box::new(inner)))
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Option<Expr> {
+        let token = self.peek()?.clone();
+        self.pos += 1;
+        match token {
+            Token::Open => {
+                let inner = self.descend(Self::parse_or)?;
+                // Tolerate a missing close paren at end of input; a TUI
+                // query is often mid-keystroke.
+                if matches!(self.peek(), Some(Token::Close)) {
+                    self.pos += 1;
+                }
+                Some(inner)
+            }
+            Token::Phrase(p) => Some(Expr::Phrase(p)),
+            Token::Field(key, value) => Some(Expr::Field(key, value)),
+            Token::Word(w) => Some(Expr::Term(w)),
+            // Dangling operator or stray close paren: nothing to parse.
+            Token::Close | Token::And | Token::Or | Token::Not => None,
+        }
+    }
+}
+
+/// Pull field constraints out of conjunctive positions, returning the
+/// residual text expression (if any survives).
+fn hoist(expr: Expr, out: &mut StructuredQuery) -> Option<Expr> {
+    match expr {
+        Expr::Field(key, value) => {
+            add_include(out, key, value);
+            None
+        }
+        Expr::Not(inner) => match *inner {
+            Expr::Field(key, value) => {
+                add_exclude(out, key, value);
+                None
+            }
+            other => Some(Expr::Not(Box::new(other))),
+        },
+        Expr::And(branches) => {
+            let residual: Vec<Expr> = branches
+                .into_iter()
+                .filter_map(|branch| hoist(branch, out))
+                .collect();
+            collapse(residual, Expr::And)
+        }
+        Expr::Or(branches) => {
+            if let Some((key, values)) = uniform_field_group(&branches) {
+                for value in values {
+                    add_include(out, key, value);
+                }
+                return None;
+            }
+            // Mixed OR group: field terms cannot become filters here, so
+            // they degrade to their bare value as a text term.
+            let residual: Vec<Expr> = branches.into_iter().map(degrade_fields).collect();
+            collapse(residual, Expr::Or)
+        }
+        other => Some(other),
+    }
+}
+
+/// If every branch is a field term on the same key, return the key and
+/// the owned values.
+fn uniform_field_group(branches: &[Expr]) -> Option<(FieldKey, Vec<String>)> {
+    let mut key = None;
+    let mut values = Vec::with_capacity(branches.len());
+    for branch in branches {
+        let Expr::Field(branch_key, value) = branch else {
+            return None;
+        };
+        if *key.get_or_insert(*branch_key) != *branch_key {
+            return None;
+        }
+        values.push(value.clone());
+    }
+    key.map(|key| (key, values))
+}
+
+fn degrade_fields(expr: Expr) -> Expr {
+    match expr {
+        Expr::Field(_, value) => Expr::Term(value),
+        Expr::And(branches) => Expr::And(branches.into_iter().map(degrade_fields).collect()),
+        Expr::Or(branches) => Expr::Or(branches.into_iter().map(degrade_fields).collect()),
+        Expr::Not(inner) => Expr::Not(Box::new(degrade_fields(*inner))),
+        other => other,
+    }
+}
+
+fn collapse(mut branches: Vec<Expr>, combine: fn(Vec<Expr>) -> Expr) -> Option<Expr> {
+    match branches.len() {
+        0 => None,
+        1 => branches.pop(),
+        _ => Some(combine(branches)),
+    }
+}
+
+fn add_include(out: &mut StructuredQuery, key: FieldKey, value: String) {
+    match key {
+        FieldKey::Agent => {
+            out.agents.insert(value);
+        }
+        FieldKey::Workspace => {
+            out.workspaces.insert(value);
+        }
+        FieldKey::Source => out.source = Some(value),
+        FieldKey::Note => {
+            out.notes.insert(value);
+        }
+        FieldKey::Facet => {
+            out.facets.insert(value);
+        }
+    }
+}
+
+fn add_exclude(out: &mut StructuredQuery, key: FieldKey, value: String) {
+    match key {
+        FieldKey::Agent => {
+            out.excluded_agents.insert(value);
+        }
+        FieldKey::Workspace => {
+            out.excluded_workspaces.insert(value);
+        }
+        FieldKey::Facet => {
+            out.excluded_facets.insert(value);
+        }
+        // `NOT source:x` / `NOT note:x` have no exclusion representation;
+        // drop them rather than silently matching nothing.
+        FieldKey::Source | FieldKey::Note => {}
+    }
+}
+
+/// Render the residual expression as a flat boolean query the lexical
+/// engine's token stream understands. Grouping was already resolved during
+/// hoisting, so parentheses are not re-emitted.
+fn render(expr: &Expr) -> String {
+    match expr {
+        Expr::Term(t) => t.clone(),
+        Expr::Phrase(p) => format!("\"{p}\""),
+        Expr::Field(_, value) => value.clone(),
+        Expr::And(branches) => branches
+            .iter()
+            .map(render)
+            .collect::<Vec<_>>()
+            .join(" AND "),
+        Expr::Or(branches) => branches.iter().map(render).collect::<Vec<_>>().join(" OR "),
+        Expr::Not(inner) => format!("NOT {}", render(inner)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_queries_are_untouched() {
+        assert_eq!(parse("segfault in parser"), None);
+        assert_eq!(parse("foo AND bar OR baz"), None);
+        // A colon without a known field name is just text.
+        assert_eq!(parse("error: something broke"), None);
+    }
+
+    #[test]
+    fn single_field_term_becomes_filter() {
+        let structured = parse("segfault agent:codex").unwrap();
+        assert_eq!(structured.text, "segfault");
+        assert!(structured.agents.contains("codex"));
+        assert!(structured.workspaces.is_empty());
+    }
+
+    #[test]
+    fn readme_example_compiles_to_filters_and_phrase() {
+        let structured =
+            parse("(agent:codex OR agent:claude) AND \"segfault\" NOT workspace:playground")
+                .unwrap();
+        assert_eq!(structured.text, "\"segfault\"");
+        assert_eq!(
+            structured.agents,
+            HashSet::from(["codex".to_string(), "claude".to_string()])
+        );
+        assert_eq!(
+            structured.excluded_workspaces,
+            HashSet::from(["playground".to_string()])
+        );
+        assert!(structured.excluded_agents.is_empty());
+    }
+
+    #[test]
+    fn not_field_excludes_and_bang_is_not() {
+        let structured = parse("panic !agent:gemini").unwrap();
+        assert_eq!(structured.text, "panic");
+        assert_eq!(
+            structured.excluded_agents,
+            HashSet::from(["gemini".to_string()])
+        );
+    }
+
+    #[test]
+    fn mixed_or_group_degrades_field_to_term() {
+        let structured = parse("(crash OR agent:codex) workspace:myproj").unwrap();
+        assert_eq!(structured.text, "crash OR codex");
+        assert!(structured.agents.is_empty());
+        assert!(structured.workspaces.contains("myproj"));
+    }
+
+    #[test]
+    fn mixed_key_or_group_is_not_hoisted() {
+        let structured = parse("(agent:codex OR workspace:playground) boom").unwrap();
+        assert_eq!(structured.text, "codex OR playground AND boom");
+        assert!(structured.agents.is_empty());
+        assert!(structured.workspaces.is_empty());
+    }
+
+    #[test]
+    fn field_only_query_leaves_empty_text() {
+        let structured = parse("agent:codex workspace:api").unwrap();
+        assert_eq!(structured.text, "");
+        assert!(structured.agents.contains("codex"));
+        assert!(structured.workspaces.contains("api"));
+    }
+
+    #[test]
+    fn field_aliases_and_source_are_recognized() {
+        let structured = parse("ws:frontend provider:claude source:local oops").unwrap();
+        assert_eq!(structured.text, "oops");
+        assert!(structured.workspaces.contains("frontend"));
+        assert!(structured.agents.contains("claude"));
+        assert_eq!(structured.source.as_deref(), Some("local"));
+    }
+
+    #[test]
+    fn note_terms_are_hoisted_not_searched_as_text() {
+        let structured = parse("note:fix segfault NOT note:wrong").unwrap();
+        assert_eq!(structured.text, "segfault");
+        assert_eq!(structured.notes, HashSet::from(["fix".to_string()]));
+    }
+
+    #[test]
+    fn facet_terms_are_hoisted_not_searched_as_text() {
+        let structured = parse("facet:webpack rebuild").unwrap();
+        assert_eq!(structured.text, "rebuild");
+        assert_eq!(structured.facets, HashSet::from(["webpack".to_string()]));
+    }
+
+    #[test]
+    fn not_facet_terms_become_exclusions() {
+        let structured = parse("NOT facet:pii-email outage").unwrap();
+        assert_eq!(structured.text, "outage");
+        assert_eq!(
+            structured.excluded_facets,
+            HashSet::from(["pii-email".to_string()])
+        );
+        assert!(structured.facets.is_empty());
+    }
+
+    #[test]
+    fn unterminated_group_still_parses() {
+        let structured = parse("(agent:codex OR agent:claude").unwrap();
+        assert_eq!(structured.text, "");
+        assert_eq!(structured.agents.len(), 2);
+    }
+
+    #[test]
+    fn moderate_nesting_still_parses() {
+        let raw = format!("{}agent:codex{}", "(".repeat(16), ")".repeat(16));
+        let structured = parse(&raw).unwrap();
+        assert!(structured.agents.contains("codex"));
+    }
+
+    #[test]
+    fn pathological_nesting_bails_instead_of_overflowing() {
+        let parens = format!("{}agent:codex", "(".repeat(10_000));
+        assert_eq!(parse(&parens), None);
+
+        let nots = format!("{}agent:codex", "NOT ".repeat(10_000));
+        assert_eq!(parse(&nots), None);
+    }
+
+    #[test]
+    fn apply_to_filters_merges_with_explicit_flags() {
+        let structured = parse("agent:codex NOT workspace:scratch hit").unwrap();
+        let mut filters = SearchFilters::default();
+        filters.agents.insert("claude".to_string());
+        structured.apply_to_filters(&mut filters);
+        assert_eq!(filters.agents.len(), 2);
+        assert!(filters.excluded_workspaces.contains("scratch"));
+    }
+
+    #[test]
+    fn has_field_syntax_matches_parse_activation() {
+        assert!(has_field_syntax("(agent:codex)"));
+        assert!(has_field_syntax("!workspace:tmp"));
+        assert!(!has_field_syntax("plain text query"));
+        assert!(!has_field_syntax("agent: codex"));
+    }
+}
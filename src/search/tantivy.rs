@@ -290,7 +290,10 @@ fn cass_document_for_message(
         msg_idx: msg.idx.max(0) as u64,
         created_at: msg.created_at.or(context.started_at_fallback),
         title: context.title.clone(),
-        content: msg.content.clone(),
+        // CJK runs are pre-segmented so the tokenizer sees word/bigram
+        // boundaries; queries get the same transform (src/search/cjk.rs).
+        // A borrow-and-pass-through unless segmentation is active.
+        content: crate::search::cjk::segment(&msg.content).into_owned(),
         conversation_id: context.conversation_id,
         source_id: context.source_id.clone(),
         origin_kind: context.origin_kind.clone(),
@@ -303,8 +306,43 @@ fn push_cass_document_into_pending(
     pending_chars: &mut usize,
     doc: FsCassDocument,
 ) {
-    *pending_chars = pending_chars.saturating_add(doc.content.len());
-    docs.push(doc);
+    for doc in expand_long_message_documents(doc) {
+        *pending_chars = pending_chars.saturating_add(doc.content.len());
+        docs.push(doc);
+    }
+}
+
+/// Split a very long message document into chunk documents (see
+/// `src/search/message_chunking.rs`); anything at or under the threshold
+/// passes through untouched. Each chunk keeps the parent's metadata and
+/// encodes its ordinal into the stored `msg_idx`, which the query side
+/// decodes back to the parent message index.
+fn expand_long_message_documents(doc: FsCassDocument) -> Vec<FsCassDocument> {
+    use crate::search::message_chunking as chunking;
+
+    if !chunking::chunkable_parent_idx(doc.msg_idx) {
+        return vec![doc];
+    }
+    let Some(spans) = chunking::chunk_spans(&doc.content) else {
+        return vec![doc];
+    };
+    spans
+        .iter()
+        .map(|span| FsCassDocument {
+            agent: doc.agent.clone(),
+            workspace: doc.workspace.clone(),
+            workspace_original: doc.workspace_original.clone(),
+            source_path: doc.source_path.clone(),
+            msg_idx: chunking::encode_chunk_msg_idx(doc.msg_idx, span.seq),
+            created_at: doc.created_at,
+            title: doc.title.clone(),
+            content: doc.content[span.range.clone()].to_string(),
+            conversation_id: doc.conversation_id,
+            source_id: doc.source_id.clone(),
+            origin_kind: doc.origin_kind.clone(),
+            origin_host: doc.origin_host.clone(),
+        })
+        .collect()
 }
 
 /// Build the per-document context the lexical sink needs from a
@@ -372,7 +410,9 @@ fn cass_document_for_packet_message(
         msg_idx: msg.idx.max(0) as u64,
         created_at: msg.created_at.or(context.started_at_fallback),
         title: context.title.clone(),
-        content: msg.content.clone(),
+        // Same CJK pre-segmentation as `cass_document_for_message`; the
+        // packet and legacy pipelines must emit byte-identical documents.
+        content: crate::search::cjk::segment(&msg.content).into_owned(),
         conversation_id: context.conversation_id,
         source_id: context.source_id.clone(),
         origin_kind: context.origin_kind.clone(),
@@ -471,9 +511,10 @@ fn federated_search_manifest_path(index_path: &Path) -> PathBuf {
 }
 
 fn write_root_schema_hash_file(index_path: &Path) -> Result<()> {
+    let tokenizer = crate::search::cjk::active_segmentation().id();
     fs::write(
         index_path.join("schema_hash.json"),
-        format!("{{\"schema_hash\":\"{CASS_SCHEMA_HASH}\"}}"),
+        format!("{{\"schema_hash\":\"{CASS_SCHEMA_HASH}\",\"cjk_tokenizer\":\"{tokenizer}\"}}"),
     )
     .with_context(|| {
         format!(
@@ -484,6 +525,24 @@ fn write_root_schema_hash_file(index_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Returns true when the index was built with the currently active CJK
+/// segmenter. A missing marker reads as `off` — every index predating the
+/// marker was built without segmentation — so legacy indexes only rebuild
+/// when segmentation is actually switched on. Mismatches force a lexical
+/// rebuild exactly like a schema hash change (the token stream differs).
+pub(crate) fn stored_cjk_tokenizer_matches(index_path: &Path) -> bool {
+    let stored = fs::read_to_string(index_path.join("schema_hash.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| {
+            json.get("cjk_tokenizer")
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "off".to_string());
+    stored == crate::search::cjk::active_segmentation().id()
+}
+
 fn manifest_relative_shard_path(shard_idx: usize) -> String {
     format!("shards/shard-{shard_idx:05}")
 }
@@ -1419,11 +1478,7 @@ impl TantivyIndex {
                 )
             })?;
         ensure_tokenizer(&mut merged);
-        fs::write(
-            output_path.join("schema_hash.json"),
-            format!("{{\"schema_hash\":\"{CASS_SCHEMA_HASH}\"}}"),
-        )
-        .with_context(|| {
+        write_root_schema_hash_file(output_path).with_context(|| {
             format!(
                 "writing cass schema hash metadata for merged Tantivy index {}",
                 output_path.display()
@@ -2050,6 +2105,45 @@ mod tests {
         Err(label)
     }
 
+    #[test]
+    fn long_message_documents_expand_into_chunk_docs() {
+        let make_doc = |content: String| FsCassDocument {
+            agent: "codex".to_string(),
+            workspace: Some("/w".to_string()),
+            workspace_original: None,
+            source_path: "/sessions/a.jsonl".to_string(),
+            msg_idx: 7,
+            created_at: Some(1_700_000_000_000),
+            title: Some("t".to_string()),
+            content,
+            conversation_id: Some(3),
+            source_id: "local".to_string(),
+            origin_kind: "local".to_string(),
+            origin_host: None,
+        };
+
+        let short = expand_long_message_documents(make_doc("short".to_string()));
+        assert_eq!(short.len(), 1);
+        assert_eq!(short[0].msg_idx, 7);
+
+        let line = format!("{}\n", "tool output ".repeat(40));
+        let long_content =
+            line.repeat(crate::search::message_chunking::CHUNK_THRESHOLD_CHARS / line.len() + 8);
+        let chunks = expand_long_message_documents(make_doc(long_content.clone()));
+        assert!(chunks.len() > 1);
+        let mut rebuilt = String::new();
+        for (seq, chunk) in chunks.iter().enumerate() {
+            let (parent, decoded_seq) =
+                crate::search::message_chunking::decode_chunk_msg_idx(chunk.msg_idx);
+            assert_eq!(parent, 7, "every chunk references the parent message");
+            assert_eq!(decoded_seq as usize, seq);
+            assert_eq!(chunk.source_path, "/sessions/a.jsonl");
+            assert_eq!(chunk.conversation_id, Some(3));
+            rebuilt.push_str(&chunk.content);
+        }
+        assert_eq!(rebuilt, long_content, "chunks partition the content");
+    }
+
     #[test]
     fn generate_edge_ngrams_prefixes() {
         let out = frankensearch::lexical::cass_generate_edge_ngrams("hello world");
@@ -45,16 +45,21 @@
 //! values as arguments) so it is unit-tested without mutating process-global env
 //! — important because the test suite runs in parallel and every search test
 //! transitively reads env.
+//!
+//! One-shot commands re-read the file on every invocation, so they always see
+//! the latest version. Long-lived processes (`cass index --watch`, the model
+//! daemon) instead hold a [`ConfigReloader`] and poll it, picking up edits
+//! without a restart — see its docs for the validate/apply/reject contract.
 
 use std::path::PathBuf;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// The `[search]` table of `~/.config/cass/cass.toml`.
 ///
 /// Every field is optional: an absent field falls through to the next lower
 /// precedence source. Unknown keys are ignored (forward-compatible).
-#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SearchDefaults {
     /// Default search timeout in milliseconds. `None` = no timeout (legacy
     /// behavior). A value of `0` is treated as "no timeout" as well, so the
@@ -67,16 +72,105 @@ pub struct SearchDefaults {
     /// a clear error rather than a confusing deserialize failure for the whole
     /// config file.
     pub mode: Option<String>,
+    /// Path to a user ranking script applied to search hits (see
+    /// `crate::ranking_script`). `None` = built-in ranking only.
+    pub ranking_script: Option<String>,
+    /// Lower bound (in characters) for adaptively sized snippets. `None` =
+    /// the built-in minimum. See [`resolve_snippet_bounds`].
+    pub snippet_min_chars: Option<usize>,
+    /// Upper bound (in characters) for adaptively sized snippets. `None` =
+    /// the built-in maximum. See [`resolve_snippet_bounds`].
+    pub snippet_max_chars: Option<usize>,
+    /// Whether to boost hits from workspaces with recent local git commits
+    /// (see `crate::git_activity`). `None` = off: the boost reads repo
+    /// reflogs, so privacy-sensitive setups must opt in.
+    pub git_recency_boost: Option<bool>,
+}
+
+/// The `[tui]` table of `~/.config/cass/cass.toml`: interactive-viewer
+/// defaults that have no CLI-flag equivalent.
+///
+/// Every field is optional and absent fields keep the built-in behavior, so an
+/// older config file (or none at all) changes nothing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TuiDefaults {
+    /// Whether the conversation viewer collapses tool/system messages to a
+    /// one-line summary when a session is opened. `None` = collapse (the
+    /// historical behavior); set `false` to always open fully expanded.
+    pub collapse_tool_output: Option<bool>,
+    /// Whether find-in-conversation also matches inside collapsed sections,
+    /// expanding the messages that hold a match. `None` = match inside.
+    pub find_in_collapsed: Option<bool>,
+}
+
+/// The `[budget]` table of `~/.config/cass/cass.toml`: monthly token-budget
+/// alerts checked by `cass index` / `cass index --watch` (see
+/// `crate::token_budget`).
+///
+/// Every field is optional; with no `monthly_tokens` configured no budget
+/// checks run at all, so an absent table keeps the historical behavior.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct BudgetDefaults {
+    /// Prompt + completion tokens allowed per calendar month (UTC). `None` =
+    /// no budget, no checks.
+    pub monthly_tokens: Option<i64>,
+    /// Percentage of the budget at which the warning fires. `None` = 80.
+    pub warn_at_percent: Option<u8>,
+    /// Command spawned (detached, best-effort) when the threshold is
+    /// crossed; the event arrives in `CASS_BUDGET_*` env vars.
+    pub hook: Option<String>,
+}
+
+/// The `[pii]` table of `~/.config/cass/cass.toml`: dictionaries for the
+/// index-time PII classification pass (see `crate::search::pii`).
+///
+/// Email and phone detection are always on; only the customer-name
+/// dictionary needs operator input, since "which names are sensitive" is
+/// knowledge only the operator has. An absent table classifies emails and
+/// phones but tags no customer names.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct PiiDefaults {
+    /// Customer/person names whose mention tags a conversation with the
+    /// `pii-customer` facet. Matched case-insensitively as whole words.
+    #[serde(default)]
+    pub customer_names: Vec<String>,
+}
+
+/// The `[paths]` table of `~/.config/cass/cass.toml`: persistent equivalents
+/// of the `CASS_DATA_DIR` / `CASS_DB` environment overrides, for sandboxed or
+/// NixOS setups that relocate state without exporting env vars everywhere.
+///
+/// Precedence for each path is env var > this table > XDG/platform default.
+/// The config *directory* itself can only move via `CASS_CONFIG_DIR` or
+/// `XDG_CONFIG_HOME` — a config file cannot relocate the place it is read
+/// from.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct PathsDefaults {
+    /// Data directory holding the database, Tantivy index, and exports.
+    /// Equivalent to `CASS_DATA_DIR`.
+    pub data_dir: Option<String>,
+    /// Database file path. Equivalent to `CASS_DB`; when unset the database
+    /// lives at `<data_dir>/agent_search.db`.
+    pub db: Option<String>,
 }
 
 /// Top-level shape of `~/.config/cass/cass.toml`.
 ///
-/// Only the `[search]` table is consumed today. Other tables are ignored so the
-/// same file can grow additional sections later without breaking older binaries.
+/// The `[search]`, `[tui]`, `[budget]`, `[pii]` and `[paths]` tables are
+/// consumed today. Other tables are ignored so the same file can grow
+/// additional sections later without breaking older binaries.
 #[derive(Debug, Clone, Default, Deserialize)]
 struct CassConfigFile {
     #[serde(default)]
     search: SearchDefaults,
+    #[serde(default)]
+    tui: TuiDefaults,
+    #[serde(default)]
+    budget: BudgetDefaults,
+    #[serde(default)]
+    pii: PiiDefaults,
+    #[serde(default)]
+    paths: PathsDefaults,
 }
 
 /// Errors surfaced while loading the config file. Kept narrow and stringly so
@@ -105,24 +199,46 @@ impl std::error::Error for ConfigLoadError {}
 /// Resolve the path to the global cass config file (`cass.toml`), mirroring the
 /// XDG resolution used for `sources.toml` so both live side-by-side.
 ///
-/// - Primary: `$XDG_CONFIG_HOME/cass/cass.toml`
+/// - Primary: `$CASS_CONFIG_DIR/cass.toml` (the directory *is* cass's config
+///   dir, no extra `cass/` segment)
+/// - Then: `$XDG_CONFIG_HOME/cass/cass.toml`
 /// - Then: platform config dir (e.g. `~/Library/Application Support/cass/` on
 ///   macOS, `~/.config/cass/` on Linux) when it already exists
 /// - Then: `~/.config/cass/cass.toml` when it already exists
 /// - Else: the platform path (for documentation / future creation)
+///
+/// When a named profile is active (`CASS_PROFILE`), the path is scoped to
+/// `cass/profiles/<name>/cass.toml` alongside the profile's `sources.toml`.
 pub fn config_path() -> Option<PathBuf> {
     config_path_from_parts(
+        cass_config_dir_override(),
         dotenvy::var("XDG_CONFIG_HOME").ok().map(PathBuf::from),
         dirs::config_dir(),
         dirs::home_dir(),
     )
+    .map(crate::profiles::scope_config_path)
+}
+
+/// The `CASS_CONFIG_DIR` override, if set to a non-empty path. Shared with
+/// `sources.toml` resolution so both config files move together.
+pub(crate) fn cass_config_dir_override() -> Option<PathBuf> {
+    let dir = dotenvy::var("CASS_CONFIG_DIR").ok()?;
+    let trimmed = dir.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(trimmed))
 }
 
 fn config_path_from_parts(
+    cass_config_dir: Option<PathBuf>,
     xdg_config_home: Option<PathBuf>,
     platform_config_dir: Option<PathBuf>,
     home_dir: Option<PathBuf>,
 ) -> Option<PathBuf> {
+    if let Some(dir) = cass_config_dir {
+        return Some(dir.join("cass.toml"));
+    }
     if let Some(xdg) = xdg_config_home {
         let trimmed = xdg.as_os_str().is_empty();
         if !trimmed {
@@ -179,6 +295,271 @@ pub fn parse_search_defaults(contents: &str) -> Result<SearchDefaults, ConfigLoa
     Ok(file.search)
 }
 
+/// Load `[tui]` defaults from the config file. Absent file = defaults, same
+/// contract as [`load_search_defaults`].
+pub fn load_tui_defaults() -> Result<TuiDefaults, ConfigLoadError> {
+    let Some(path) = config_path() else {
+        return Ok(TuiDefaults::default());
+    };
+    if !path.exists() {
+        return Ok(TuiDefaults::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(ConfigLoadError::Read)?;
+    parse_tui_defaults(&contents)
+}
+
+/// Parse the `[tui]` table out of a TOML config string.
+pub fn parse_tui_defaults(contents: &str) -> Result<TuiDefaults, ConfigLoadError> {
+    let file: CassConfigFile =
+        toml::from_str(contents).map_err(|e| ConfigLoadError::Parse(e.to_string()))?;
+    Ok(file.tui)
+}
+
+/// Load `[budget]` defaults from the config file. Absent file = defaults,
+/// same contract as [`load_search_defaults`]. Re-read on every budget check,
+/// so long-lived watch loops see config edits without a restart.
+pub fn load_budget_defaults() -> Result<BudgetDefaults, ConfigLoadError> {
+    let Some(path) = config_path() else {
+        return Ok(BudgetDefaults::default());
+    };
+    if !path.exists() {
+        return Ok(BudgetDefaults::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(ConfigLoadError::Read)?;
+    parse_budget_defaults(&contents)
+}
+
+/// Parse the `[budget]` table out of a TOML config string.
+pub fn parse_budget_defaults(contents: &str) -> Result<BudgetDefaults, ConfigLoadError> {
+    let file: CassConfigFile =
+        toml::from_str(contents).map_err(|e| ConfigLoadError::Parse(e.to_string()))?;
+    Ok(file.budget)
+}
+
+/// Load `[pii]` defaults from the config file. Absent file = defaults, same
+/// contract as [`load_search_defaults`]. Re-read by each index run, so
+/// dictionary edits apply on the next refresh without a restart.
+pub fn load_pii_defaults() -> Result<PiiDefaults, ConfigLoadError> {
+    let Some(path) = config_path() else {
+        return Ok(PiiDefaults::default());
+    };
+    if !path.exists() {
+        return Ok(PiiDefaults::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(ConfigLoadError::Read)?;
+    parse_pii_defaults(&contents)
+}
+
+/// Parse the `[pii]` table out of a TOML config string.
+pub fn parse_pii_defaults(contents: &str) -> Result<PiiDefaults, ConfigLoadError> {
+    let file: CassConfigFile =
+        toml::from_str(contents).map_err(|e| ConfigLoadError::Parse(e.to_string()))?;
+    Ok(file.pii)
+}
+
+/// Load `[paths]` overrides from the config file. Absent file = defaults,
+/// same contract as [`load_search_defaults`]. Consulted by the default data
+/// dir / db path resolvers only after the corresponding env var, so a broken
+/// file degrades to the XDG defaults rather than failing path resolution.
+pub fn load_paths_defaults() -> Result<PathsDefaults, ConfigLoadError> {
+    let Some(path) = config_path() else {
+        return Ok(PathsDefaults::default());
+    };
+    if !path.exists() {
+        return Ok(PathsDefaults::default());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(ConfigLoadError::Read)?;
+    parse_paths_defaults(&contents)
+}
+
+/// Parse the `[paths]` table out of a TOML config string.
+pub fn parse_paths_defaults(contents: &str) -> Result<PathsDefaults, ConfigLoadError> {
+    let file: CassConfigFile =
+        toml::from_str(contents).map_err(|e| ConfigLoadError::Parse(e.to_string()))?;
+    Ok(file.paths)
+}
+
+/// Outcome of a [`ConfigReloader::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigReload {
+    /// The file has not changed (or is still absent) since the last poll.
+    Unchanged,
+    /// A new version parsed cleanly and is now in force. Carries one
+    /// `table.key: old -> new` string per changed setting, for logging.
+    Applied(Vec<String>),
+    /// The new version could not be read or parsed; the previous settings
+    /// stay in force. Carries the error text for the warning log.
+    Rejected(String),
+}
+
+/// Hot-reload support for long-lived processes (`cass index --watch`, the
+/// model daemon): re-reads `cass.toml` when it changes so edits apply without
+/// a restart.
+///
+/// Polling-based, matching the watch loop's control-file cadence — no extra
+/// filesystem watcher on a config file that changes a few times a year.
+/// [`ConfigReloader::poll`] re-stats the file and only re-reads when the
+/// mtime or size moved. A version that fails to parse is rejected wholesale
+/// and the last good settings stay applied, so a half-saved edit can't
+/// degrade a running daemon; a successful parse swaps both tables at once,
+/// so [`ConfigReloader::search`] / [`ConfigReloader::tui`] never observe a
+/// partially applied file.
+pub struct ConfigReloader {
+    path: Option<PathBuf>,
+    /// `(mtime, size)` of the last version we acted on (applied *or*
+    /// rejected); `None` when the file was absent.
+    last_seen: Option<(std::time::SystemTime, u64)>,
+    search: SearchDefaults,
+    tui: TuiDefaults,
+}
+
+impl ConfigReloader {
+    /// Build a reloader against the global config path, seeded with the
+    /// current file contents (a broken or absent file seeds defaults, same
+    /// forgiving contract as the one-shot loaders).
+    pub fn new() -> Self {
+        Self::at_path(config_path())
+    }
+
+    /// Build against an explicit path (`None` = no config file; used by
+    /// `new` and by tests).
+    pub fn at_path(path: Option<PathBuf>) -> Self {
+        let mut reloader = Self {
+            path,
+            last_seen: None,
+            search: SearchDefaults::default(),
+            tui: TuiDefaults::default(),
+        };
+        // Seed from the current file; errors at startup degrade to defaults
+        // exactly like load_search_defaults().unwrap_or_default() callers.
+        let _ = reloader.poll();
+        reloader
+    }
+
+    /// The `[search]` table currently in force.
+    pub fn search(&self) -> &SearchDefaults {
+        &self.search
+    }
+
+    /// The `[tui]` table currently in force.
+    pub fn tui(&self) -> &TuiDefaults {
+        &self.tui
+    }
+
+    /// Re-stat the config file and apply it if it changed since the last
+    /// poll. Cheap when nothing changed (one `stat`), so callers can invoke
+    /// this on every control-poll tick.
+    pub fn poll(&mut self) -> ConfigReload {
+        let Some(path) = self.path.clone() else {
+            return ConfigReload::Unchanged;
+        };
+        let seen = std::fs::metadata(&path)
+            .ok()
+            .and_then(|m| m.modified().ok().map(|mtime| (mtime, m.len())));
+        if seen == self.last_seen {
+            return ConfigReload::Unchanged;
+        }
+        self.last_seen = seen;
+
+        if seen.is_none() {
+            // File deleted: built-in defaults are the new config.
+            let changes = self.apply(SearchDefaults::default(), TuiDefaults::default());
+            return if changes.is_empty() {
+                ConfigReload::Unchanged
+            } else {
+                ConfigReload::Applied(changes)
+            };
+        }
+
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => return ConfigReload::Rejected(ConfigLoadError::Read(e).to_string()),
+        };
+        let file: CassConfigFile = match toml::from_str(&contents) {
+            Ok(file) => file,
+            Err(e) => {
+                return ConfigReload::Rejected(ConfigLoadError::Parse(e.to_string()).to_string());
+            }
+        };
+        let changes = self.apply(file.search, file.tui);
+        if changes.is_empty() {
+            ConfigReload::Unchanged
+        } else {
+            ConfigReload::Applied(changes)
+        }
+    }
+
+    /// Swap in a new version, returning the per-key diff against the old one.
+    fn apply(&mut self, search: SearchDefaults, tui: TuiDefaults) -> Vec<String> {
+        let mut changes = Vec::new();
+        note_change(
+            &mut changes,
+            "search.timeout_ms",
+            &self.search.timeout_ms,
+            &search.timeout_ms,
+        );
+        note_change(
+            &mut changes,
+            "search.limit",
+            &self.search.limit,
+            &search.limit,
+        );
+        note_change(&mut changes, "search.mode", &self.search.mode, &search.mode);
+        note_change(
+            &mut changes,
+            "search.ranking_script",
+            &self.search.ranking_script,
+            &search.ranking_script,
+        );
+        note_change(
+            &mut changes,
+            "search.snippet_min_chars",
+            &self.search.snippet_min_chars,
+            &search.snippet_min_chars,
+        );
+        note_change(
+            &mut changes,
+            "search.snippet_max_chars",
+            &self.search.snippet_max_chars,
+            &search.snippet_max_chars,
+        );
+        note_change(
+            &mut changes,
+            "search.git_recency_boost",
+            &self.search.git_recency_boost,
+            &search.git_recency_boost,
+        );
+        note_change(
+            &mut changes,
+            "tui.collapse_tool_output",
+            &self.tui.collapse_tool_output,
+            &tui.collapse_tool_output,
+        );
+        note_change(
+            &mut changes,
+            "tui.find_in_collapsed",
+            &self.tui.find_in_collapsed,
+            &tui.find_in_collapsed,
+        );
+        self.search = search;
+        self.tui = tui;
+        changes
+    }
+}
+
+impl Default for ConfigReloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Record `key: old -> new` when a field changed.
+fn note_change<T: std::fmt::Debug + PartialEq>(out: &mut Vec<String>, key: &str, old: &T, new: &T) {
+    if old != new {
+        out.push(format!("{key}: {old:?} -> {new:?}"));
+    }
+}
+
 /// Read the timeout environment variable, accepting `CASS_SEARCH_TIMEOUT_MS`
 /// (preferred, unambiguous units) and the issue's `CASS_SEARCH_TIMEOUT` as an
 /// alias. `_MS` wins when both are set.
@@ -308,6 +689,33 @@ pub fn resolve_mode(
     Ok((None, DefaultSource::BuiltIn))
 }
 
+/// Built-in lower bound for adaptive snippet sizing: enough to show a match
+/// with a few words of context even in a very narrow terminal.
+pub const BUILTIN_SNIPPET_MIN_CHARS: usize = 80;
+
+/// Built-in upper bound for adaptive snippet sizing: matches the preview
+/// content cap so an ultrawide terminal can't pull whole messages into every
+/// result row.
+pub const BUILTIN_SNIPPET_MAX_CHARS: usize = 400;
+
+/// Resolve the `[min, max]` character bounds for adaptively sized snippets
+/// from the optional `[search].snippet_min_chars` / `snippet_max_chars`
+/// config values.
+///
+/// Values are normalized rather than rejected, in the same forgiving spirit
+/// as the timeout handling above: the minimum is floored at 16 characters
+/// (below that a snippet is just an ellipsis) and the maximum is raised to
+/// the minimum when the config inverts them, so a misconfiguration degrades
+/// to a fixed-size snippet instead of an error.
+pub fn resolve_snippet_bounds(
+    config_min: Option<usize>,
+    config_max: Option<usize>,
+) -> (usize, usize) {
+    let min = config_min.unwrap_or(BUILTIN_SNIPPET_MIN_CHARS).max(16);
+    let max = config_max.unwrap_or(BUILTIN_SNIPPET_MAX_CHARS).max(min);
+    (min, max)
+}
+
 fn validate_mode(value: &str) -> Result<String, String> {
     match value.to_ascii_lowercase().as_str() {
         m @ ("lexical" | "semantic" | "hybrid") => Ok(m.to_string()),
@@ -423,6 +831,71 @@ mod tests {
         assert!(resolve_mode(None, None, Some("vector")).is_err());
     }
 
+    #[test]
+    fn parse_tui_table() {
+        let toml = "[tui]\ncollapse_tool_output = false\nfind_in_collapsed = true\n";
+        let d = parse_tui_defaults(toml).unwrap();
+        assert_eq!(d.collapse_tool_output, Some(false));
+        assert_eq!(d.find_in_collapsed, Some(true));
+        // A search-only config leaves the tui table at defaults, and vice
+        // versa — the tables are independent.
+        assert_eq!(
+            parse_tui_defaults("[search]\nlimit = 5\n").unwrap(),
+            TuiDefaults::default()
+        );
+        assert_eq!(
+            parse_search_defaults(toml).unwrap(),
+            SearchDefaults::default()
+        );
+    }
+
+    #[test]
+    fn parse_budget_table() {
+        let toml =
+            "[budget]\nmonthly_tokens = 50000000\nwarn_at_percent = 90\nhook = \"/opt/notify\"\n";
+        let d = parse_budget_defaults(toml).unwrap();
+        assert_eq!(d.monthly_tokens, Some(50_000_000));
+        assert_eq!(d.warn_at_percent, Some(90));
+        assert_eq!(d.hook.as_deref(), Some("/opt/notify"));
+        // Absent table = no budget configured.
+        assert_eq!(
+            parse_budget_defaults("[search]\nlimit = 5\n").unwrap(),
+            BudgetDefaults::default()
+        );
+    }
+
+    #[test]
+    fn parse_pii_table() {
+        let toml = "[pii]\ncustomer_names = [\"Acme Corp\", \"Jane Doe\"]\n";
+        let d = parse_pii_defaults(toml).unwrap();
+        assert_eq!(d.customer_names, vec!["Acme Corp", "Jane Doe"]);
+        // Absent table = empty dictionary, emails/phones still classified.
+        assert_eq!(
+            parse_pii_defaults("[search]\nlimit = 5\n").unwrap(),
+            PiiDefaults::default()
+        );
+    }
+
+    #[test]
+    fn snippet_bounds_builtin_defaults() {
+        assert_eq!(
+            resolve_snippet_bounds(None, None),
+            (BUILTIN_SNIPPET_MIN_CHARS, BUILTIN_SNIPPET_MAX_CHARS)
+        );
+    }
+
+    #[test]
+    fn snippet_bounds_config_values_win() {
+        assert_eq!(resolve_snippet_bounds(Some(120), Some(240)), (120, 240));
+    }
+
+    #[test]
+    fn snippet_bounds_normalize_degenerate_config() {
+        // A tiny minimum is floored; an inverted max is raised to the min.
+        assert_eq!(resolve_snippet_bounds(Some(1), None).0, 16);
+        assert_eq!(resolve_snippet_bounds(Some(200), Some(50)), (200, 200));
+    }
+
     #[test]
     fn parse_full_search_table() {
         let toml = r#"
@@ -430,11 +903,15 @@ mod tests {
             timeout_ms = 300000
             limit = 200
             mode = "hybrid"
+            snippet_min_chars = 100
+            snippet_max_chars = 300
         "#;
         let d = parse_search_defaults(toml).unwrap();
         assert_eq!(d.timeout_ms, Some(300000));
         assert_eq!(d.limit, Some(200));
         assert_eq!(d.mode.as_deref(), Some("hybrid"));
+        assert_eq!(d.snippet_min_chars, Some(100));
+        assert_eq!(d.snippet_max_chars, Some(300));
     }
 
     #[test]
@@ -480,9 +957,88 @@ mod tests {
         assert_eq!(d, SearchDefaults::default());
     }
 
+    #[test]
+    fn reloader_applies_valid_edits_and_reports_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cass.toml");
+        std::fs::write(&path, "[search]\nlimit = 100\n").unwrap();
+
+        let mut reloader = ConfigReloader::at_path(Some(path.clone()));
+        assert_eq!(reloader.search().limit, Some(100));
+        assert_eq!(reloader.poll(), ConfigReload::Unchanged);
+
+        std::fs::write(&path, "[search]\nlimit = 200\nmode = \"lexical\"\n").unwrap();
+        match reloader.poll() {
+            ConfigReload::Applied(changes) => {
+                assert!(
+                    changes
+                        .iter()
+                        .any(|c| c.contains("search.limit") && c.contains("200")),
+                    "{changes:?}"
+                );
+                assert!(
+                    changes.iter().any(|c| c.contains("search.mode")),
+                    "{changes:?}"
+                );
+            }
+            other => panic!("expected Applied, got {other:?}"),
+        }
+        assert_eq!(reloader.search().limit, Some(200));
+        assert_eq!(reloader.search().mode.as_deref(), Some("lexical"));
+    }
+
+    #[test]
+    fn reloader_rejects_broken_toml_and_keeps_old_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cass.toml");
+        std::fs::write(&path, "[search]\ntimeout_ms = 5000\n").unwrap();
+
+        let mut reloader = ConfigReloader::at_path(Some(path.clone()));
+        assert_eq!(reloader.search().timeout_ms, Some(5000));
+
+        std::fs::write(&path, "[search\ntimeout_ms = ").unwrap();
+        assert!(matches!(reloader.poll(), ConfigReload::Rejected(_)));
+        // Old settings survive the bad version, and the bad version is not
+        // re-reported on every subsequent poll.
+        assert_eq!(reloader.search().timeout_ms, Some(5000));
+        assert_eq!(reloader.poll(), ConfigReload::Unchanged);
+    }
+
+    #[test]
+    fn reloader_reverts_to_defaults_when_file_is_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cass.toml");
+        std::fs::write(&path, "[tui]\ncollapse_tool_output = false\n").unwrap();
+
+        let mut reloader = ConfigReloader::at_path(Some(path.clone()));
+        assert_eq!(reloader.tui().collapse_tool_output, Some(false));
+
+        std::fs::remove_file(&path).unwrap();
+        match reloader.poll() {
+            ConfigReload::Applied(changes) => {
+                assert!(
+                    changes
+                        .iter()
+                        .any(|c| c.contains("tui.collapse_tool_output")),
+                    "{changes:?}"
+                );
+            }
+            other => panic!("expected Applied, got {other:?}"),
+        }
+        assert_eq!(reloader.tui(), &TuiDefaults::default());
+    }
+
+    #[test]
+    fn reloader_with_no_config_path_is_inert() {
+        let mut reloader = ConfigReloader::at_path(None);
+        assert_eq!(reloader.poll(), ConfigReload::Unchanged);
+        assert_eq!(reloader.search(), &SearchDefaults::default());
+    }
+
     #[test]
     fn config_path_prefers_xdg_config_home() {
         let p = config_path_from_parts(
+            None,
             Some(PathBuf::from("/xdg")),
             Some(PathBuf::from("/platform")),
             Some(PathBuf::from("/home/u")),
@@ -491,10 +1047,25 @@ mod tests {
         assert_eq!(p, PathBuf::from("/xdg/cass/cass.toml"));
     }
 
+    #[test]
+    fn config_path_cass_config_dir_beats_xdg() {
+        // CASS_CONFIG_DIR points directly at cass's config dir, so no extra
+        // `cass/` segment is appended.
+        let p = config_path_from_parts(
+            Some(PathBuf::from("/sandbox/cfg")),
+            Some(PathBuf::from("/xdg")),
+            Some(PathBuf::from("/platform")),
+            Some(PathBuf::from("/home/u")),
+        )
+        .unwrap();
+        assert_eq!(p, PathBuf::from("/sandbox/cfg/cass.toml"));
+    }
+
     #[test]
     fn config_path_falls_back_to_platform_when_no_files_exist() {
         // Neither platform nor ~/.config file exists -> platform path returned.
         let p = config_path_from_parts(
+            None,
             None,
             Some(PathBuf::from("/platform")),
             Some(PathBuf::from("/home/definitely-missing")),
@@ -502,4 +1073,17 @@ mod tests {
         .unwrap();
         assert_eq!(p, PathBuf::from("/platform/cass/cass.toml"));
     }
+
+    #[test]
+    fn parse_paths_table() {
+        let toml = "[paths]\ndata_dir = \"/srv/cass\"\ndb = \"/srv/cass/custom.db\"\n";
+        let d = parse_paths_defaults(toml).unwrap();
+        assert_eq!(d.data_dir.as_deref(), Some("/srv/cass"));
+        assert_eq!(d.db.as_deref(), Some("/srv/cass/custom.db"));
+        // Absent table = no overrides.
+        assert_eq!(
+            parse_paths_defaults("[search]\nlimit = 5\n").unwrap(),
+            PathsDefaults::default()
+        );
+    }
 }
@@ -0,0 +1,267 @@
+//! Shareable search-configuration profiles (`cass profile export/import`).
+//!
+//! A team converging on one cass setup previously had to trade cass.toml
+//! fragments and tui_state.json snippets by hand. A profile is one named,
+//! versioned JSON file bundling the settings worth standardizing:
+//!
+//! * the `[search]` table — timeout/limit/mode defaults, snippet bounds,
+//!   recency boost, and the user ranking script (inlined by content, so the
+//!   receiving machine doesn't need the exporter's file layout)
+//! * the `[tui]` viewer defaults
+//! * saved searches (the TUI's saved views, kept as raw JSON exactly like
+//!   `cass meta export` does)
+//! * ignore rules (`disabled_agents` from sources.toml)
+//!
+//! Importing merges rather than replaces: the profile's tables are written
+//! into cass.toml (other tables, e.g. `[budget]`, survive untouched), saved
+//! views append into free slots, and disabled agents union. Validation runs
+//! before anything is written — a profile that names an unknown search mode
+//! or carries a ranking script that doesn't compile is rejected whole.
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::search_defaults::{SearchDefaults, TuiDefaults};
+
+/// Current profile file format. Importers accept this version and older;
+/// a newer file is rejected with an upgrade hint rather than half-applied.
+pub const PROFILE_FORMAT_VERSION: u32 = 1;
+
+/// A named, shareable bundle of search configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchProfile {
+    pub format_version: u32,
+    /// Profile name; also names the imported ranking-script file.
+    pub name: String,
+    /// Export time (unix millis).
+    pub exported_at: i64,
+    /// The `[search]` table. `ranking_script` inside it is ignored on
+    /// import — [`Self::ranking_script`] carries the script by content.
+    #[serde(default)]
+    pub search: SearchDefaults,
+    /// The `[tui]` table.
+    #[serde(default)]
+    pub tui: TuiDefaults,
+    /// Source of the user ranking script, inlined so the profile is
+    /// self-contained.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranking_script: Option<String>,
+    /// Saved views from `tui_state.json`, raw JSON as in `MetaSnapshot`.
+    #[serde(default)]
+    pub saved_views: Vec<serde_json::Value>,
+    /// Connectors excluded from indexing (sources.toml `disabled_agents`).
+    #[serde(default)]
+    pub disabled_agents: Vec<String>,
+}
+
+impl SearchProfile {
+    /// Structural validation, run on export (catch a broken local setup
+    /// before it spreads) and before import applies anything.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format_version == 0 {
+            return Err("format_version must be at least 1".to_string());
+        }
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("profile name cannot be empty".to_string());
+        }
+        if name.len() > 64
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!(
+                "profile name {name:?} must be <= 64 chars of [alphanumeric - _ .]"
+            ));
+        }
+        if let Some(mode) = self.search.mode.as_deref() {
+            let canonical = mode.trim().to_ascii_lowercase();
+            if !matches!(canonical.as_str(), "lexical" | "semantic" | "hybrid") {
+                return Err(format!(
+                    "invalid search mode {mode:?}; expected lexical, semantic, or hybrid"
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) =
+            (self.search.snippet_min_chars, self.search.snippet_max_chars)
+            && min > max
+        {
+            return Err(format!(
+                "snippet_min_chars ({min}) exceeds snippet_max_chars ({max})"
+            ));
+        }
+        if let Some(script) = self.ranking_script.as_deref() {
+            crate::ranking_script::RankingProgram::compile(script)
+                .map_err(|e| format!("ranking script does not compile: {e}"))?;
+        }
+        for (idx, view) in self.saved_views.iter().enumerate() {
+            if !view.is_object() {
+                return Err(format!("saved_views[{idx}] is not a JSON object"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read and validate a profile file. Rejects files written by a newer cass
+/// (the format is versioned precisely so this fails loudly, not subtly).
+pub fn read_profile(path: &Path) -> Result<SearchProfile> {
+    let raw =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let profile: SearchProfile =
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+    if profile.format_version > PROFILE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "profile format v{} is newer than this cass (supports up to v{}); upgrade cass first",
+            profile.format_version,
+            PROFILE_FORMAT_VERSION
+        ));
+    }
+    profile
+        .validate()
+        .map_err(|e| anyhow!("invalid profile: {e}"))?;
+    Ok(profile)
+}
+
+/// Merge a profile's `[search]` and `[tui]` tables into an existing
+/// cass.toml, returning the new file contents. Tables the profile doesn't
+/// own (`[budget]`, future sections) pass through untouched; `None` fields
+/// in the profile clear nothing and leave the local value in place.
+/// `ranking_script_path` is the local path the inlined script was written
+/// to, if any.
+pub fn merged_config_toml(
+    existing: &str,
+    profile: &SearchProfile,
+    ranking_script_path: Option<&str>,
+) -> Result<String, String> {
+    let mut root: toml::Value = if existing.trim().is_empty() {
+        toml::Value::Table(toml::map::Map::new())
+    } else {
+        toml::from_str(existing).map_err(|e| format!("existing cass.toml does not parse: {e}"))?
+    };
+    let table = root
+        .as_table_mut()
+        .ok_or_else(|| "cass.toml root is not a table".to_string())?;
+
+    let mut search = profile.search.clone();
+    search.ranking_script = ranking_script_path.map(ToOwned::to_owned);
+    merge_section(table, "search", &search).map_err(|e| format!("[search]: {e}"))?;
+    merge_section(table, "tui", &profile.tui).map_err(|e| format!("[tui]: {e}"))?;
+
+    toml::to_string_pretty(&root).map_err(|e| format!("serializing merged config: {e}"))
+}
+
+/// Overlay one serializable section onto `table[key]`, keeping local keys
+/// the profile leaves unset.
+fn merge_section<T: Serialize>(
+    table: &mut toml::map::Map<String, toml::Value>,
+    key: &str,
+    section: &T,
+) -> Result<(), String> {
+    let incoming = toml::Value::try_from(section).map_err(|e| e.to_string())?;
+    let Some(incoming) = incoming.as_table() else {
+        return Err("section did not serialize to a table".to_string());
+    };
+    let dest = table
+        .entry(key.to_string())
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    let dest = dest
+        .as_table_mut()
+        .ok_or_else(|| format!("existing [{key}] is not a table"))?;
+    for (k, v) in incoming {
+        dest.insert(k.clone(), v.clone());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> SearchProfile {
+        SearchProfile {
+            format_version: PROFILE_FORMAT_VERSION,
+            name: "team-default".to_string(),
+            exported_at: 1_700_000_000_000,
+            search: SearchDefaults {
+                limit: Some(100),
+                mode: Some("hybrid".to_string()),
+                ..SearchDefaults::default()
+            },
+            tui: TuiDefaults {
+                collapse_tool_output: Some(false),
+                ..TuiDefaults::default()
+            },
+            ranking_script: Some("score * 0.8 + max(0, 5 - age_days)".to_string()),
+            saved_views: vec![serde_json::json!({"name": "bugs", "query": "panic"})],
+            disabled_agents: vec!["openclaw".to_string()],
+        }
+    }
+
+    #[test]
+    fn validation_accepts_a_sound_profile_and_rejects_broken_fields() {
+        assert!(profile().validate().is_ok());
+
+        let mut bad = profile();
+        bad.name = "has spaces".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = profile();
+        bad.search.mode = Some("fuzzy".to_string());
+        assert!(bad.validate().is_err());
+
+        let mut bad = profile();
+        bad.search.snippet_min_chars = Some(300);
+        bad.search.snippet_max_chars = Some(100);
+        assert!(bad.validate().is_err());
+
+        let mut bad = profile();
+        bad.ranking_script = Some("score * (".to_string());
+        assert!(bad.validate().is_err());
+
+        let mut bad = profile();
+        bad.saved_views = vec![serde_json::json!("not an object")];
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn newer_format_versions_are_rejected_on_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("p.json");
+        let mut newer = profile();
+        newer.format_version = PROFILE_FORMAT_VERSION + 1;
+        std::fs::write(&path, serde_json::to_vec(&newer).unwrap()).unwrap();
+        let err = read_profile(&path).unwrap_err().to_string();
+        assert!(err.contains("newer than this cass"), "{err}");
+    }
+
+    #[test]
+    fn merge_overlays_profile_tables_and_preserves_others() {
+        let existing =
+            "[search]\ntimeout_ms = 9000\nlimit = 5\n\n[budget]\nmonthly_tokens = 1000\n";
+        let merged =
+            merged_config_toml(existing, &profile(), Some("/cfg/team-default.rank")).unwrap();
+        let search = crate::search_defaults::parse_search_defaults(&merged).unwrap();
+        // Profile limit wins; the locally set timeout the profile left unset
+        // survives; the script path points at the imported copy.
+        assert_eq!(search.limit, Some(100));
+        assert_eq!(search.timeout_ms, Some(9000));
+        assert_eq!(
+            search.ranking_script.as_deref(),
+            Some("/cfg/team-default.rank")
+        );
+        let budget = crate::search_defaults::parse_budget_defaults(&merged).unwrap();
+        assert_eq!(budget.monthly_tokens, Some(1000));
+        let tui = crate::search_defaults::parse_tui_defaults(&merged).unwrap();
+        assert_eq!(tui.collapse_tool_output, Some(false));
+    }
+
+    #[test]
+    fn merge_into_an_empty_config_creates_the_tables() {
+        let merged = merged_config_toml("", &profile(), None).unwrap();
+        let search = crate::search_defaults::parse_search_defaults(&merged).unwrap();
+        assert_eq!(search.mode.as_deref(), Some("hybrid"));
+        assert_eq!(search.ranking_script, None);
+    }
+}
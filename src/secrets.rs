@@ -0,0 +1,313 @@
+//! OS-keychain-backed storage for cass credentials.
+//!
+//! Secrets like the HTTP server token or an embedding/summarizer API key
+//! should not live in plaintext config files. This module stores them in the
+//! native OS keychain (macOS Keychain, Windows Credential Manager, the
+//! Secret Service on Linux) via the `keyring` crate, behind the opt-in
+//! `keychain` cargo feature — headless CI and minimal server builds often
+//! have no secret service running, so default builds stay keychain-free.
+//!
+//! Resolution order at runtime is keychain first, then environment
+//! variables, so `CASS_SERVER_TOKEN=... cass ...` keeps working unchanged
+//! and builds without the feature degrade to env-only lookup. Values are
+//! never logged; display paths go through [`redact_for_display`], which
+//! shows only the length and a short suffix.
+//!
+//! CLI surface: `cass secrets set|get|list|delete` (see `SecretsCommand` in
+//! the crate root).
+
+#[cfg(feature = "keychain")]
+use anyhow::anyhow;
+use anyhow::{Result, bail};
+
+/// Keychain service name under which every cass secret is filed.
+pub const KEYCHAIN_SERVICE: &str = "cass";
+
+/// A secret name cass itself knows how to consume.
+pub struct KnownSecret {
+    /// Canonical name used as the keychain entry user and CLI argument.
+    pub name: &'static str,
+    /// Environment variables consulted (in order) when the keychain has no
+    /// entry or the `keychain` feature is off.
+    pub env_vars: &'static [&'static str],
+    /// One-line description for `cass secrets list`.
+    pub purpose: &'static str,
+}
+
+/// Secrets with first-class consumers in cass. Custom names are also
+/// accepted (validated by [`validate_name`]) and fall back to
+/// `CASS_SECRET_<NAME>` in the environment.
+pub const KNOWN_SECRETS: &[KnownSecret] = &[
+    KnownSecret {
+        name: "server_token",
+        env_vars: &["CASS_SERVER_TOKEN"],
+        purpose: "HTTP server bearer token",
+    },
+    KnownSecret {
+        name: "openai_api_key",
+        env_vars: &["CASS_OPENAI_API_KEY", "OPENAI_API_KEY"],
+        purpose: "OpenAI-compatible API key (embeddings/summaries)",
+    },
+    KnownSecret {
+        name: "embedding_api_key",
+        env_vars: &["CASS_EMBEDDING_API_KEY"],
+        purpose: "remote embedding provider API key",
+    },
+    KnownSecret {
+        name: "summarizer_api_key",
+        env_vars: &["CASS_SUMMARIZER_API_KEY"],
+        purpose: "remote summarizer provider API key",
+    },
+];
+
+/// Look up the well-known descriptor for `name`, if cass consumes it itself.
+#[must_use]
+pub fn known_secret(name: &str) -> Option<&'static KnownSecret> {
+    KNOWN_SECRETS.iter().find(|secret| secret.name == name)
+}
+
+/// Validate a secret name: lowercase alphanumeric plus `_`, 1..=64 chars.
+/// Keeps keychain entry names portable and env-var mapping unambiguous.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 64 {
+        bail!("secret name must be 1..=64 characters, got {}", name.len());
+    }
+    if !name
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+    {
+        bail!("secret name '{name}' must be lowercase alphanumeric with underscores");
+    }
+    Ok(())
+}
+
+/// Where a resolved secret value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    /// Native OS keychain entry.
+    Keychain,
+    /// Environment variable (name recorded for diagnostics, never the value).
+    EnvVar(String),
+}
+
+impl SecretSource {
+    /// Short label for structured output.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            SecretSource::Keychain => "keychain".to_string(),
+            SecretSource::EnvVar(var) => format!("env:{var}"),
+        }
+    }
+}
+
+/// A secret value plus provenance. Callers must not log `value`.
+#[derive(Debug, Clone)]
+pub struct ResolvedSecret {
+    pub value: String,
+    pub source: SecretSource,
+}
+
+/// Environment variables consulted for `name`, canonical first.
+fn env_vars_for(name: &str) -> Vec<String> {
+    match known_secret(name) {
+        Some(secret) => secret
+            .env_vars
+            .iter()
+            .map(|var| (*var).to_string())
+            .collect(),
+        None => vec![format!("CASS_SECRET_{}", name.to_ascii_uppercase())],
+    }
+}
+
+/// Resolve `name` against the provided env lookup (keychain is consulted
+/// first when compiled in). Split out from [`resolve`] so the fallback order
+/// is testable without mutating process environment.
+fn resolve_with_env(
+    name: &str,
+    env_lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Option<ResolvedSecret>> {
+    validate_name(name)?;
+    if let Some(value) = keychain_get(name)? {
+        return Ok(Some(ResolvedSecret {
+            value,
+            source: SecretSource::Keychain,
+        }));
+    }
+    for var in env_vars_for(name) {
+        if let Some(value) = env_lookup(&var)
+            && !value.trim().is_empty()
+        {
+            return Ok(Some(ResolvedSecret {
+                value,
+                source: SecretSource::EnvVar(var),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolve a secret: keychain entry first, then environment variables.
+/// Returns `Ok(None)` when neither holds a value.
+pub fn resolve(name: &str) -> Result<Option<ResolvedSecret>> {
+    resolve_with_env(name, |var| dotenvy::var(var).ok())
+}
+
+/// Whether this binary was built with native keychain support.
+#[must_use]
+pub fn keychain_available() -> bool {
+    cfg!(feature = "keychain")
+}
+
+/// Store `value` for `name` in the OS keychain.
+pub fn set(name: &str, value: &str) -> Result<()> {
+    validate_name(name)?;
+    if value.is_empty() {
+        bail!("refusing to store an empty secret; use `cass secrets delete {name}` to remove one");
+    }
+    keychain_set(name, value)
+}
+
+/// Delete the keychain entry for `name`. Returns `false` when no entry
+/// existed. Env-var fallbacks are unaffected.
+pub fn delete(name: &str) -> Result<bool> {
+    validate_name(name)?;
+    keychain_delete(name)
+}
+
+/// Redact a secret value for human display: length plus a 4-char suffix for
+/// long values, a bare placeholder for short ones. Never shows enough to
+/// reconstruct the secret.
+#[must_use]
+pub fn redact_for_display(value: &str) -> String {
+    const SUFFIX_CHARS: usize = 4;
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 12 {
+        return format!("[REDACTED] ({} chars)", chars.len());
+    }
+    let suffix: String = chars[chars.len() - SUFFIX_CHARS..].iter().collect();
+    format!("…{suffix} ({} chars)", chars.len())
+}
+
+#[cfg(feature = "keychain")]
+fn keychain_entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, name)
+        .map_err(|err| anyhow!("open keychain entry for '{name}': {err}"))
+}
+
+#[cfg(feature = "keychain")]
+fn keychain_get(name: &str) -> Result<Option<String>> {
+    match keychain_entry(name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(anyhow!("read keychain entry for '{name}': {err}")),
+    }
+}
+
+#[cfg(feature = "keychain")]
+fn keychain_set(name: &str, value: &str) -> Result<()> {
+    keychain_entry(name)?
+        .set_password(value)
+        .map_err(|err| anyhow!("write keychain entry for '{name}': {err}"))
+}
+
+#[cfg(feature = "keychain")]
+fn keychain_delete(name: &str) -> Result<bool> {
+    match keychain_entry(name)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(err) => Err(anyhow!("delete keychain entry for '{name}': {err}")),
+    }
+}
+
+#[cfg(not(feature = "keychain"))]
+fn keychain_get(_name: &str) -> Result<Option<String>> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "keychain"))]
+fn keychain_set(name: &str, _value: &str) -> Result<()> {
+    bail!(
+        "this build has no native keychain support; rebuild with `--features keychain` \
+         or export {} instead",
+        env_vars_for(name).join(" / ")
+    )
+}
+
+#[cfg(not(feature = "keychain"))]
+fn keychain_delete(name: &str) -> Result<bool> {
+    bail!(
+        "this build has no native keychain support; rebuild with `--features keychain` \
+         or unset {} instead",
+        env_vars_for(name).join(" / ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_names_validate_and_map_to_their_env_vars() {
+        for secret in KNOWN_SECRETS {
+            validate_name(secret.name).expect("known name validates");
+            assert_eq!(
+                env_vars_for(secret.name),
+                secret
+                    .env_vars
+                    .iter()
+                    .map(|var| (*var).to_string())
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn custom_names_map_to_cass_secret_env_var() {
+        assert_eq!(env_vars_for("my_webhook"), vec!["CASS_SECRET_MY_WEBHOOK"]);
+    }
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        for bad in ["", "Has-Dash", "UPPER", "with space", &"x".repeat(65)] {
+            assert!(validate_name(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn env_fallback_respects_variable_order_and_skips_blank_values() {
+        let resolved = resolve_with_env("openai_api_key", |var| match var {
+            "CASS_OPENAI_API_KEY" => Some("   ".to_string()),
+            "OPENAI_API_KEY" => Some("sk-test-value".to_string()),
+            _ => None,
+        })
+        .expect("resolve")
+        .expect("value present");
+        assert_eq!(resolved.value, "sk-test-value");
+        assert_eq!(
+            resolved.source,
+            SecretSource::EnvVar("OPENAI_API_KEY".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_secret_resolves_to_none() {
+        assert!(
+            resolve_with_env("server_token", |_| None)
+                .expect("resolve")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn redaction_never_echoes_the_value() {
+        let secret = "sk-abcdefghijklmnopqrstuvwxyz";
+        let shown = redact_for_display(secret);
+        assert!(
+            !shown.contains("sk-abcdef"),
+            "prefix must not leak: {shown}"
+        );
+        assert!(shown.contains("(28 chars)"), "length shown: {shown}");
+        assert_eq!(redact_for_display("short"), "[REDACTED] (5 chars)");
+    }
+}
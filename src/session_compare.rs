@@ -0,0 +1,278 @@
+//! Side-by-side comparison of two indexed sessions.
+//!
+//! Powering `cass compare <id-a> <id-b>`: the common workflow is running two
+//! agents (say Codex and Claude Code) against the same bug and wanting a
+//! structured answer to "who did what, and how expensively" afterwards. All
+//! inputs come from the `messages` table, so the comparison works for any
+//! indexed session — including remote-source sessions with no local file.
+//!
+//! Stats are derived with the same conventions the rest of the tree uses:
+//! prompts are `role = 'user'` messages, tool calls are `role = 'tool'`
+//! messages named by their first content line (see the TUI inspector), token
+//! counts are the chars/4 `content_tokens_est` estimate, and duration comes
+//! from [`crate::session_metrics`].
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::session_metrics::{MetricsMessage, compute_conversation_metrics};
+
+/// Cap on distinct tools / files reported per session, keeping the report
+/// readable for tool-heavy sessions.
+const MAX_TOOLS_REPORTED: usize = 10;
+const MAX_FILES_REPORTED: usize = 25;
+
+/// One message's comparison-relevant fields, in conversation order.
+#[derive(Debug, Clone)]
+pub struct CompareMessage {
+    /// Stored role string (`user`, `agent`, `tool`, ...).
+    pub role: String,
+    /// Message content.
+    pub content: String,
+    /// Message timestamp (unix millis), when the connector recorded one.
+    pub created_at: Option<i64>,
+}
+
+/// Per-session stats for one side of the comparison.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionStats {
+    /// Conversation row id.
+    pub conversation_id: i64,
+    /// Agent slug.
+    pub agent: String,
+    /// Conversation title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Source session file.
+    pub source_path: String,
+    /// All messages in the conversation.
+    pub message_count: u64,
+    /// User messages (prompts).
+    pub prompt_count: u64,
+    /// Agent messages.
+    pub agent_message_count: u64,
+    /// Tool-result messages.
+    pub tool_call_count: u64,
+    /// Distinct tools by first content line, most-used first.
+    pub tools: Vec<ToolUsage>,
+    /// Path-like tokens seen in tool output, deduplicated.
+    pub files_touched: Vec<String>,
+    /// Wall-clock span from first to last timestamped message (millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+    /// Rough token estimate across all message content (chars/4).
+    pub content_tokens_est: u64,
+}
+
+/// One tool's usage count within a session.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolUsage {
+    pub name: String,
+    pub calls: u64,
+}
+
+/// Compute per-session stats from messages in index order.
+#[must_use]
+pub fn compute_session_stats(messages: &[CompareMessage]) -> SessionStats {
+    let mut stats = SessionStats {
+        message_count: messages.len() as u64,
+        ..Default::default()
+    };
+
+    let mut tools: BTreeMap<String, u64> = BTreeMap::new();
+    let mut files: Vec<String> = Vec::new();
+    for msg in messages {
+        stats.content_tokens_est += (msg.content.chars().count() / 4) as u64;
+        match msg.role.as_str() {
+            "user" => stats.prompt_count += 1,
+            "agent" => stats.agent_message_count += 1,
+            "tool" => {
+                stats.tool_call_count += 1;
+                // First content line carries the tool name for most harnesses.
+                let name: String = msg
+                    .content
+                    .lines()
+                    .next()
+                    .unwrap_or("(unnamed)")
+                    .chars()
+                    .take(40)
+                    .collect();
+                *tools.entry(name).or_default() += 1;
+                for path in extract_path_tokens(&msg.content) {
+                    if !files.contains(&path) {
+                        files.push(path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut tool_list: Vec<ToolUsage> = tools
+        .into_iter()
+        .map(|(name, calls)| ToolUsage { name, calls })
+        .collect();
+    tool_list.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.name.cmp(&b.name)));
+    tool_list.truncate(MAX_TOOLS_REPORTED);
+    stats.tools = tool_list;
+    files.truncate(MAX_FILES_REPORTED);
+    stats.files_touched = files;
+
+    let metric_messages: Vec<MetricsMessage> = messages
+        .iter()
+        .map(|m| MetricsMessage {
+            role: m.role.clone(),
+            created_at: m.created_at,
+        })
+        .collect();
+    stats.duration_ms = compute_conversation_metrics(&metric_messages).duration_ms;
+
+    stats
+}
+
+/// Pull path-like tokens out of tool output. Deliberately conservative: a
+/// token counts as a path when it contains a `/`, is not a URL, and either
+/// starts from a root-ish prefix or ends in a dotted file name. Trailing
+/// punctuation from prose ("edited `src/lib.rs`.") is stripped first.
+#[must_use]
+pub fn extract_path_tokens(content: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for token in content.split_whitespace() {
+        let token = token.trim_matches(|c: char| {
+            matches!(
+                c,
+                '`' | '\'' | '"' | '(' | ')' | '[' | ']' | ',' | ';' | ':'
+            )
+        });
+        let token = token.strip_suffix('.').unwrap_or(token);
+        if !token.contains('/') || token.contains("://") || token.len() > 200 {
+            continue;
+        }
+        let rooted = token.starts_with('/')
+            || token.starts_with("./")
+            || token.starts_with("~/")
+            || token.starts_with("../");
+        let dotted_file = token
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| name.contains('.') && !name.starts_with('.'));
+        if (rooted || dotted_file) && !out.contains(&token.to_string()) {
+            out.push(token.to_string());
+        }
+    }
+    out
+}
+
+/// Align the two sessions' prompts by turn number for the side-by-side view.
+/// Sessions rarely have the same prompt count; shorter sides pad with `None`.
+#[must_use]
+pub fn align_prompts(
+    a: &[CompareMessage],
+    b: &[CompareMessage],
+    max_turns: usize,
+) -> Vec<(Option<String>, Option<String>)> {
+    let prompts = |messages: &[CompareMessage]| -> Vec<String> {
+        messages
+            .iter()
+            .filter(|m| m.role == "user")
+            .map(|m| first_line_snippet(&m.content, 80))
+            .collect()
+    };
+    let a_prompts = prompts(a);
+    let b_prompts = prompts(b);
+    let turns = a_prompts.len().max(b_prompts.len()).min(max_turns);
+    (0..turns)
+        .map(|i| (a_prompts.get(i).cloned(), b_prompts.get(i).cloned()))
+        .collect()
+}
+
+/// First non-empty content line, trimmed and character-capped.
+#[must_use]
+pub fn first_line_snippet(content: &str, max_chars: usize) -> String {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    let mut snippet: String = line.chars().take(max_chars).collect();
+    if line.chars().count() > max_chars {
+        snippet.push('\u{2026}');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str, ts: Option<i64>) -> CompareMessage {
+        CompareMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+            created_at: ts,
+        }
+    }
+
+    #[test]
+    fn stats_count_roles_tools_and_tokens() {
+        let messages = vec![
+            msg("user", "fix the login bug", Some(1_000)),
+            msg("agent", "Looking at the auth module.", Some(2_000)),
+            msg("tool", "Read\n/repo/src/auth.rs contents...", Some(3_000)),
+            msg("tool", "Read\nmore of /repo/src/auth.rs", Some(4_000)),
+            msg("tool", "Edit\npatched src/auth.rs.", Some(5_000)),
+            msg("user", "now add a test", Some(6_000)),
+        ];
+        let stats = compute_session_stats(&messages);
+        assert_eq!(stats.message_count, 6);
+        assert_eq!(stats.prompt_count, 2);
+        assert_eq!(stats.agent_message_count, 1);
+        assert_eq!(stats.tool_call_count, 3);
+        assert_eq!(stats.duration_ms, Some(5_000));
+        assert!(stats.content_tokens_est > 0);
+
+        assert_eq!(stats.tools[0].name, "Read");
+        assert_eq!(stats.tools[0].calls, 2);
+        assert_eq!(stats.tools[1].name, "Edit");
+        // Same file via absolute and relative spelling: both kept, deduped.
+        assert_eq!(
+            stats.files_touched,
+            vec!["/repo/src/auth.rs".to_string(), "src/auth.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn path_extraction_ignores_urls_and_bare_words() {
+        let paths =
+            extract_path_tokens("see https://example.com/a.rs and `src/lib.rs`, plus ./run.sh");
+        assert_eq!(
+            paths,
+            vec!["src/lib.rs".to_string(), "./run.sh".to_string()]
+        );
+        assert!(extract_path_tokens("no paths here, just words").is_empty());
+    }
+
+    #[test]
+    fn prompt_alignment_pads_the_shorter_session() {
+        let a = vec![
+            msg("user", "fix the bug", None),
+            msg("agent", "done", None),
+            msg("user", "add tests", None),
+        ];
+        let b = vec![msg("user", "fix the bug please", None)];
+        let aligned = align_prompts(&a, &b, 10);
+        assert_eq!(aligned.len(), 2);
+        assert_eq!(aligned[0].0.as_deref(), Some("fix the bug"));
+        assert_eq!(aligned[0].1.as_deref(), Some("fix the bug please"));
+        assert_eq!(aligned[1].0.as_deref(), Some("add tests"));
+        assert_eq!(aligned[1].1, None);
+    }
+
+    #[test]
+    fn first_line_snippet_caps_length() {
+        let snippet = first_line_snippet(&"x".repeat(200), 10);
+        assert_eq!(snippet.chars().count(), 11);
+        assert!(snippet.ends_with('\u{2026}'));
+        assert_eq!(first_line_snippet("\n\n  hello\nworld", 80), "hello");
+    }
+}
@@ -0,0 +1,301 @@
+//! Session-length and response-latency metrics from message timestamps.
+//!
+//! Computed from the indexed per-message timestamps rather than persisted at
+//! write time: the inputs (role, `created_at`) are already in the `messages`
+//! table, so the metrics are pure derived data and stay correct across
+//! re-indexes without a schema migration. Per-conversation we report wall
+//! duration, messages per hour, and — when both sides carry timestamps — the
+//! average latency between a user message and the next assistant reply.
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::ConnectionExt;
+use serde::Serialize;
+
+/// Metrics for a single conversation.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConversationMetrics {
+    /// Messages considered (those carrying a timestamp).
+    pub message_count: u64,
+    /// Wall-clock span from first to last timestamped message (millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+    /// Message rate over the conversation's span. `None` for single-message
+    /// or zero-duration sessions where a rate would be meaningless.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages_per_hour: Option<f64>,
+    /// Mean user → next-assistant-reply latency (millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_response_latency_ms: Option<i64>,
+    /// Number of user/assistant pairs behind the latency average.
+    pub latency_samples: u64,
+}
+
+/// Aggregate metrics across all conversations with usable timestamps.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionMetricsSummary {
+    /// Conversations with at least one timestamped message.
+    pub sessions_with_timestamps: u64,
+    /// Mean session duration (millis), over sessions with a span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_duration_ms: Option<i64>,
+    /// Longest single session (millis).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_duration_ms: Option<i64>,
+    /// Mean messages-per-hour rate, over sessions with a rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_messages_per_hour: Option<f64>,
+    /// Mean assistant response latency (millis), weighted by sample count.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_response_latency_ms: Option<i64>,
+    /// Total user/assistant latency samples across all sessions.
+    pub latency_samples: u64,
+}
+
+/// One message's metric-relevant fields, in conversation order.
+#[derive(Debug, Clone)]
+pub struct MetricsMessage {
+    /// Stored role string (`user`, `agent`, `tool`, ...).
+    pub role: String,
+    /// Message timestamp (unix millis), when the connector recorded one.
+    pub created_at: Option<i64>,
+}
+
+/// Compute metrics for one conversation from its messages in index order.
+#[must_use]
+pub fn compute_conversation_metrics(messages: &[MetricsMessage]) -> ConversationMetrics {
+    let stamped: Vec<&MetricsMessage> =
+        messages.iter().filter(|m| m.created_at.is_some()).collect();
+    let mut metrics = ConversationMetrics {
+        message_count: stamped.len() as u64,
+        ..Default::default()
+    };
+    if stamped.is_empty() {
+        return metrics;
+    }
+
+    let first = stamped.iter().filter_map(|m| m.created_at).min();
+    let last = stamped.iter().filter_map(|m| m.created_at).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        let duration = last - first;
+        metrics.duration_ms = Some(duration);
+        if duration > 0 && stamped.len() > 1 {
+            let hours = duration as f64 / 3_600_000.0;
+            metrics.messages_per_hour = Some(stamped.len() as f64 / hours);
+        }
+    }
+
+    // Latency: time from a user message to the next agent message. Tool and
+    // system messages in between are part of the agent's turn, so they do not
+    // reset the pending user timestamp.
+    let mut pending_user_ts: Option<i64> = None;
+    let mut latency_total: i64 = 0;
+    let mut latency_samples: u64 = 0;
+    for msg in messages {
+        match msg.role.as_str() {
+            "user" => pending_user_ts = msg.created_at.or(pending_user_ts),
+            "agent" => {
+                if let (Some(user_ts), Some(agent_ts)) = (pending_user_ts, msg.created_at)
+                    && agent_ts >= user_ts
+                {
+                    latency_total += agent_ts - user_ts;
+                    latency_samples += 1;
+                }
+                pending_user_ts = None;
+            }
+            _ => {}
+        }
+    }
+    if latency_samples > 0 {
+        metrics.avg_response_latency_ms = Some(latency_total / latency_samples as i64);
+        metrics.latency_samples = latency_samples;
+    }
+
+    metrics
+}
+
+/// Aggregate [`ConversationMetrics`] across sessions.
+#[must_use]
+pub fn summarize(per_conversation: &[ConversationMetrics]) -> SessionMetricsSummary {
+    let mut summary = SessionMetricsSummary::default();
+    let mut duration_total: i64 = 0;
+    let mut duration_count: u64 = 0;
+    let mut rate_total: f64 = 0.0;
+    let mut rate_count: u64 = 0;
+    let mut latency_weighted_total: i64 = 0;
+
+    for metrics in per_conversation {
+        if metrics.message_count == 0 {
+            continue;
+        }
+        summary.sessions_with_timestamps += 1;
+        if let Some(duration) = metrics.duration_ms {
+            duration_total += duration;
+            duration_count += 1;
+            summary.max_duration_ms = Some(summary.max_duration_ms.unwrap_or(0).max(duration));
+        }
+        if let Some(rate) = metrics.messages_per_hour {
+            rate_total += rate;
+            rate_count += 1;
+        }
+        if let Some(latency) = metrics.avg_response_latency_ms {
+            latency_weighted_total += latency * metrics.latency_samples as i64;
+            summary.latency_samples += metrics.latency_samples;
+        }
+    }
+
+    if duration_count > 0 {
+        summary.avg_duration_ms = Some(duration_total / duration_count as i64);
+    }
+    if rate_count > 0 {
+        summary.avg_messages_per_hour = Some(rate_total / rate_count as f64);
+    }
+    if summary.latency_samples > 0 {
+        summary.avg_response_latency_ms =
+            Some(latency_weighted_total / summary.latency_samples as i64);
+    }
+    summary
+}
+
+/// Stream every conversation's messages out of the database and aggregate
+/// session metrics. Messages come back ordered by `(conversation_id, idx)` so
+/// the per-conversation latency pass sees turns in their original order.
+pub fn aggregate_session_metrics(conn: &Connection) -> Result<SessionMetricsSummary> {
+    let rows: Vec<(i64, String, Option<i64>)> = conn
+        .query_map_collect(
+            "SELECT conversation_id, role, created_at
+             FROM messages
+             WHERE conversation_id IS NOT NULL
+             ORDER BY conversation_id, idx",
+            &[],
+            |row| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                ))
+            },
+        )
+        .context("querying message timestamps for session metrics")?;
+
+    let mut per_conversation = Vec::new();
+    let mut current_id: Option<i64> = None;
+    let mut current: Vec<MetricsMessage> = Vec::new();
+    for (conversation_id, role, created_at) in rows {
+        if current_id != Some(conversation_id) {
+            if !current.is_empty() {
+                per_conversation.push(compute_conversation_metrics(&current));
+                current.clear();
+            }
+            current_id = Some(conversation_id);
+        }
+        current.push(MetricsMessage { role, created_at });
+    }
+    if !current.is_empty() {
+        per_conversation.push(compute_conversation_metrics(&current));
+    }
+
+    Ok(summarize(&per_conversation))
+}
+
+/// Render a millisecond duration for human output (`2h 15m`, `45s`).
+#[must_use]
+pub fn format_duration_ms(ms: i64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, ts: Option<i64>) -> MetricsMessage {
+        MetricsMessage {
+            role: role.to_string(),
+            created_at: ts,
+        }
+    }
+
+    #[test]
+    fn duration_and_rate_from_timestamps() {
+        let metrics = compute_conversation_metrics(&[
+            msg("user", Some(0)),
+            msg("agent", Some(600_000)),
+            msg("user", Some(1_200_000)),
+            msg("agent", Some(1_800_000)),
+        ]);
+        assert_eq!(metrics.duration_ms, Some(1_800_000));
+        // 4 messages over half an hour.
+        let rate = metrics.messages_per_hour.expect("rate");
+        assert!((rate - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn latency_pairs_user_with_next_agent_reply() {
+        let metrics = compute_conversation_metrics(&[
+            msg("user", Some(0)),
+            msg("tool", Some(1_000)),
+            msg("agent", Some(5_000)),
+            msg("user", Some(10_000)),
+            msg("agent", Some(12_000)),
+        ]);
+        assert_eq!(metrics.latency_samples, 2);
+        // (5000 + 2000) / 2
+        assert_eq!(metrics.avg_response_latency_ms, Some(3_500));
+    }
+
+    #[test]
+    fn missing_timestamps_degrade_gracefully() {
+        let metrics = compute_conversation_metrics(&[
+            msg("user", None),
+            msg("agent", None),
+            msg("user", Some(100)),
+        ]);
+        assert_eq!(metrics.message_count, 1);
+        assert_eq!(metrics.duration_ms, Some(0));
+        assert!(metrics.messages_per_hour.is_none());
+        assert!(metrics.avg_response_latency_ms.is_none());
+
+        let empty = compute_conversation_metrics(&[]);
+        assert_eq!(empty.message_count, 0);
+        assert!(empty.duration_ms.is_none());
+    }
+
+    #[test]
+    fn summary_weights_latency_by_sample_count() {
+        let a = ConversationMetrics {
+            message_count: 4,
+            duration_ms: Some(1_000_000),
+            messages_per_hour: Some(10.0),
+            avg_response_latency_ms: Some(1_000),
+            latency_samples: 3,
+        };
+        let b = ConversationMetrics {
+            message_count: 2,
+            duration_ms: Some(3_000_000),
+            messages_per_hour: Some(2.0),
+            avg_response_latency_ms: Some(5_000),
+            latency_samples: 1,
+        };
+        let summary = summarize(&[a, b]);
+        assert_eq!(summary.sessions_with_timestamps, 2);
+        assert_eq!(summary.avg_duration_ms, Some(2_000_000));
+        assert_eq!(summary.max_duration_ms, Some(3_000_000));
+        // (1000*3 + 5000*1) / 4
+        assert_eq!(summary.avg_response_latency_ms, Some(2_000));
+        assert_eq!(summary.latency_samples, 4);
+    }
+
+    #[test]
+    fn format_duration_covers_units() {
+        assert_eq!(format_duration_ms(42_000), "42s");
+        assert_eq!(format_duration_ms(150_000), "2m 30s");
+        assert_eq!(format_duration_ms(8_100_000), "2h 15m");
+    }
+}
@@ -0,0 +1,308 @@
+//! Shareable transcript rendering for `cass share`.
+//!
+//! Turns an indexed conversation into a cleaned transcript suitable for
+//! pasting into a PR description or sending to a teammate: secrets are
+//! scrubbed through the indexer's redaction pass, tool output is collapsed
+//! behind `<details>` blocks (GitHub-flavored Markdown renders these as
+//! expandable sections), and code fences keep their language tags so both
+//! GitHub and the HTML output highlight them.
+
+use crate::model::types::MessageRole;
+use crate::ui::data::ConversationView;
+use chrono::{TimeZone, Utc};
+use clap::ValueEnum;
+
+/// Output format for a shared transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShareFormat {
+    /// GitHub-flavored Markdown (code fences, collapsible tool output)
+    Md,
+    /// HTML page; syntax highlighting loads Prism.js from cdnjs, so opening
+    /// it fetches those assets (the transcript itself is embedded and
+    /// renders fine — just unhighlighted — offline)
+    Html,
+}
+
+impl ShareFormat {
+    /// File extension for this format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ShareFormat::Md => "md",
+            ShareFormat::Html => "html",
+        }
+    }
+}
+
+/// Rendering options for a shared transcript.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShareOptions {
+    /// Scrub secrets from message content, titles, and tool output.
+    pub redact: bool,
+}
+
+/// Render a conversation as a cleaned Markdown transcript.
+#[must_use]
+pub fn render_markdown(view: &ConversationView, opts: ShareOptions) -> String {
+    let mut out = String::new();
+
+    let title = view
+        .convo
+        .title
+        .as_deref()
+        .unwrap_or("Untitled conversation");
+    let title = clean(title, opts);
+    out.push_str(&format!("# {title}\n\n"));
+
+    out.push_str(&format!("- **Agent:** {}\n", view.convo.agent_slug));
+    if let Some(ws) = &view.convo.workspace {
+        out.push_str(&format!("- **Workspace:** `{}`\n", ws.display()));
+    }
+    if let Some(started) = view.convo.started_at {
+        out.push_str(&format!("- **Started:** {}\n", format_timestamp(started)));
+    }
+    out.push_str(&format!("- **Messages:** {}\n\n---\n\n", view.messages.len()));
+
+    for msg in &view.messages {
+        let content = clean(&msg.content, opts);
+        match msg.role {
+            MessageRole::Tool => {
+                // Tool output is noise in a shared transcript; collapse it so
+                // the conversation flow stays readable but the detail remains
+                // available on expansion.
+                let line_count = content.lines().count();
+                out.push_str(&format!(
+                    "<details>\n<summary>Tool output ({line_count} line{}) — collapsed</summary>\n\n",
+                    if line_count == 1 { "" } else { "s" }
+                ));
+                out.push_str(&fenced_block(&content));
+                out.push_str("\n</details>\n\n");
+            }
+            _ => {
+                let label = role_label(&msg.role, msg.author.as_deref());
+                let when = msg
+                    .created_at
+                    .map(|ts| format!(" — {}", format_timestamp(ts)))
+                    .unwrap_or_default();
+                out.push_str(&format!("**{label}**{when}\n\n{content}\n\n"));
+            }
+        }
+    }
+
+    out
+}
+
+/// Render a conversation as a single HTML file. The Markdown pipeline does
+/// the cleaning; pulldown-cmark converts it, preserving `language-*` classes
+/// on code fences for Prism.js to highlight.
+///
+/// The transcript and styles are embedded, but the highlighter is *not*:
+/// Prism's CSS/JS are linked from cdnjs, so a browser opening the page
+/// makes requests to that third-party CDN (and falls back to unhighlighted
+/// code offline). Nothing from the transcript is included in those
+/// requests, but recipients who must avoid the external fetch entirely
+/// should share the Markdown format instead.
+#[must_use]
+pub fn render_html(view: &ConversationView, opts: ShareOptions) -> String {
+    let markdown = render_markdown(view, opts);
+    let parser = pulldown_cmark::Parser::new_ext(&markdown, pulldown_cmark::Options::all());
+    let mut body = String::new();
+    pulldown_cmark::html::push_html(&mut body, parser);
+
+    let title = view
+        .convo
+        .title
+        .as_deref()
+        .unwrap_or("Shared conversation");
+    let title = html_escape(&clean(title, opts));
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/prism/1.29.0/themes/prism-tomorrow.min.css">
+<style>
+body {{ max-width: 56rem; margin: 2rem auto; padding: 0 1rem; font-family: -apple-system, "Segoe UI", sans-serif; line-height: 1.6; color: #1a1b26; }}
+pre {{ overflow-x: auto; border-radius: 6px; }}
+details {{ margin: 1rem 0; padding: 0.5rem; background: #f5f5f7; border-radius: 6px; }}
+summary {{ cursor: pointer; color: #565f89; }}
+hr {{ border: none; border-top: 1px solid #d5d6db; }}
+</style>
+</head>
+<body>
+{body}
+<script src="https://cdnjs.cloudflare.com/ajax/libs/prism/1.29.0/prism.min.js"></script>
+<script src="https://cdnjs.cloudflare.com/ajax/libs/prism/1.29.0/plugins/autoloader/prism-autoloader.min.js"></script>
+</body>
+</html>
+"#
+    )
+}
+
+/// Apply redaction when requested, always normalizing trailing whitespace.
+fn clean(text: &str, opts: ShareOptions) -> String {
+    let trimmed = text.trim_end();
+    if opts.redact {
+        crate::indexer::redact_secrets::redact_text(trimmed).into_owned()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Wrap content in a text code fence, widening the fence when the content
+/// itself contains backtick runs.
+fn fenced_block(content: &str) -> String {
+    let longest_backtick_run = content
+        .lines()
+        .flat_map(|line| {
+            line.split(|c| c != '`')
+                .map(str::len)
+                .max()
+                .into_iter()
+        })
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat((longest_backtick_run + 1).max(3));
+    format!("{fence}text\n{content}\n{fence}\n")
+}
+
+fn role_label(role: &MessageRole, author: Option<&str>) -> String {
+    match (role, author) {
+        (MessageRole::Agent, Some(author)) if !author.trim().is_empty() => {
+            format!("Assistant ({author})")
+        }
+        (MessageRole::Agent, _) => "Assistant".to_string(),
+        (MessageRole::User, _) => "User".to_string(),
+        (MessageRole::System, _) => "System".to_string(),
+        (MessageRole::Tool, _) => "Tool".to_string(),
+        (MessageRole::Other(name), _) => name.clone(),
+    }
+}
+
+fn format_timestamp(millis: i64) -> String {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| millis.to_string())
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::{Conversation, Message};
+    use std::path::PathBuf;
+
+    fn message(idx: i64, role: MessageRole, content: &str) -> Message {
+        Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx * 60_000),
+            content: content.to_string(),
+            extra_json: serde_json::json!({}),
+            snippets: vec![],
+        }
+    }
+
+    fn view(messages: Vec<Message>) -> ConversationView {
+        ConversationView {
+            convo: Conversation {
+                id: Some(1),
+                agent_slug: "claude-code".to_string(),
+                workspace: Some(PathBuf::from("/work/project")),
+                external_id: Some("ext-1".to_string()),
+                title: Some("Debugging the race".to_string()),
+                source_path: PathBuf::from("/logs/session.jsonl"),
+                started_at: Some(1_700_000_000_000),
+                ended_at: None,
+                approx_tokens: None,
+                metadata_json: serde_json::json!({}),
+                messages: vec![],
+                source_id: "local".to_string(),
+                origin_host: None,
+            },
+            messages,
+            workspace: None,
+        }
+    }
+
+    #[test]
+    fn markdown_includes_header_and_messages() {
+        let v = view(vec![
+            message(0, MessageRole::User, "why does this fail?"),
+            message(1, MessageRole::Agent, "Because of a race condition."),
+        ]);
+        let md = render_markdown(&v, ShareOptions::default());
+        assert!(md.starts_with("# Debugging the race"));
+        assert!(md.contains("**Agent:** claude-code"));
+        assert!(md.contains("**User**"));
+        assert!(md.contains("why does this fail?"));
+        assert!(md.contains("**Assistant**"));
+    }
+
+    #[test]
+    fn tool_output_is_collapsed_in_details_block() {
+        let v = view(vec![
+            message(0, MessageRole::User, "run the tests"),
+            message(1, MessageRole::Tool, "test a ... ok\ntest b ... ok\nall passed"),
+        ]);
+        let md = render_markdown(&v, ShareOptions::default());
+        assert!(md.contains("<details>"));
+        assert!(md.contains("Tool output (3 lines) — collapsed"));
+        assert!(md.contains("all passed"));
+        assert!(md.contains("</details>"));
+    }
+
+    #[test]
+    fn redaction_scrubs_secrets_from_content() {
+        let v = view(vec![message(
+            0,
+            MessageRole::User,
+            "use key sk-abcdefghijklmnopqrstuvwxyz1234567890ABCDEFGH please",
+        )]);
+        let md = render_markdown(&v, ShareOptions { redact: true });
+        assert!(
+            !md.contains("sk-abcdefghijklmnopqrstuvwxyz1234567890ABCDEFGH"),
+            "secret leaked into shared transcript: {md}"
+        );
+    }
+
+    #[test]
+    fn fence_widens_for_embedded_backticks() {
+        let block = fenced_block("here is ```` four backticks");
+        assert!(block.starts_with("`````text\n"));
+    }
+
+    #[test]
+    fn html_output_carries_language_classes_and_prism() {
+        let v = view(vec![message(
+            0,
+            MessageRole::Agent,
+            "```rust\nfn main() {}\n```",
+        )]);
+        let html = render_html(&v, ShareOptions::default());
+        assert!(html.contains("language-rust"));
+        assert!(html.contains("prism"));
+        assert!(html.contains("<!DOCTYPE html>"));
+    }
+
+    #[test]
+    fn html_title_is_escaped() {
+        let mut v = view(vec![message(0, MessageRole::User, "hi")]);
+        v.convo.title = Some("<script>alert(1)</script>".to_string());
+        let html = render_html(&v, ShareOptions::default());
+        assert!(!html.contains("<title><script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+}
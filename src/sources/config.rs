@@ -505,14 +505,22 @@ impl SourcesConfig {
     /// Get the default configuration file path.
     ///
     /// Uses XDG conventions:
-    /// - Primary: `$XDG_CONFIG_HOME/cass/sources.toml`
+    /// - Primary: `$CASS_CONFIG_DIR/sources.toml` (the directory is cass's
+    ///   config dir itself, no extra `cass/` segment)
+    /// - Then: `$XDG_CONFIG_HOME/cass/sources.toml`
     /// - Fallback: platform-specific config dir (e.g., `~/.config/cass/sources.toml` on Linux)
+    ///
+    /// When a named profile is active (`CASS_PROFILE`), the path is scoped
+    /// to `cass/profiles/<name>/sources.toml` so each profile keeps its own
+    /// connector set and disabled-agent list.
     pub fn config_path() -> Result<PathBuf, ConfigError> {
         config_path_from_parts(
+            crate::search_defaults::cass_config_dir_override(),
             dotenvy::var("XDG_CONFIG_HOME").ok().map(PathBuf::from),
             dirs::config_dir(),
             dirs::home_dir(),
         )
+        .map(crate::profiles::scope_config_path)
     }
 
     /// Validate all sources in the configuration.
@@ -641,10 +649,17 @@ impl SourcesConfig {
 }
 
 fn config_path_from_parts(
+    cass_config_dir: Option<PathBuf>,
     xdg_config_home: Option<PathBuf>,
     platform_config_dir: Option<PathBuf>,
     home_dir: Option<PathBuf>,
 ) -> Result<PathBuf, ConfigError> {
+    // An explicit CASS_CONFIG_DIR wins outright; it names cass's own config
+    // dir, so the file sits directly inside it.
+    if let Some(dir) = cass_config_dir {
+        return Ok(dir.join("sources.toml"));
+    }
+
     // Respect XDG_CONFIG_HOME first (important for testing and Linux users).
     if let Some(xdg_config) = xdg_config_home {
         return Ok(xdg_config.join("cass").join("sources.toml"));
@@ -1563,6 +1578,7 @@ mod tests {
 
         assert_eq!(
             config_path_from_parts(
+                None,
                 Some(xdg_config_home.clone()),
                 Some(platform_config_dir),
                 Some(home_dir)
@@ -1572,6 +1588,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_path_from_parts_cass_config_dir_beats_xdg() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let cass_config_dir = temp.path().join("sandbox-config");
+        let xdg_config_home = temp.path().join("xdg-config");
+
+        assert_eq!(
+            config_path_from_parts(
+                Some(cass_config_dir.clone()),
+                Some(xdg_config_home),
+                None,
+                None
+            )
+            .expect("path from CASS_CONFIG_DIR"),
+            cass_config_dir.join("sources.toml")
+        );
+    }
+
     #[test]
     fn test_config_path_from_parts_prefers_existing_platform_path_before_dot_config() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1585,7 +1619,7 @@ mod tests {
         std::fs::write(&dot_config_path, "").unwrap();
 
         assert_eq!(
-            config_path_from_parts(None, Some(platform_config_dir), Some(home_dir))
+            config_path_from_parts(None, None, Some(platform_config_dir), Some(home_dir))
                 .expect("existing platform path"),
             platform_path
         );
@@ -1601,7 +1635,7 @@ mod tests {
         std::fs::write(&dot_config_path, "").unwrap();
 
         assert_eq!(
-            config_path_from_parts(None, Some(platform_config_dir), Some(home_dir))
+            config_path_from_parts(None, None, Some(platform_config_dir), Some(home_dir))
                 .expect("existing dot-config path"),
             dot_config_path
         );
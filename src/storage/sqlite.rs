@@ -1015,7 +1015,7 @@ fn deserialize_msgpack_to_json(bytes: &[u8]) -> serde_json::Value {
 }
 
 /// Read metadata from a frankensqlite Row, preferring binary (msgpack) over JSON.
-fn franken_read_metadata_compat(
+pub(crate) fn franken_read_metadata_compat(
     row: &FrankenRow,
     json_idx: usize,
     bin_idx: usize,
@@ -1092,7 +1092,13 @@ const MAX_BACKUPS: usize = 3;
 const BACKUP_VACUUM_BUSY_TIMEOUT_PRAGMA: &str = "PRAGMA busy_timeout = 30000;";
 
 /// Files that contain user-authored state and must NEVER be deleted during rebuild.
-const USER_DATA_FILES: &[&str] = &["bookmarks.db", "tui_state.json", "sources.toml", ".env"];
+const USER_DATA_FILES: &[&str] = &[
+    "bookmarks.db",
+    "view_state.db",
+    "tui_state.json",
+    "sources.toml",
+    ".env",
+];
 
 /// Check if a file is user-authored data that must be preserved during rebuild.
 pub fn is_user_data_file(path: &Path) -> bool {
@@ -1102,6 +1108,53 @@ pub fn is_user_data_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Whether `dir` accepts new files, probed by actually creating and
+/// removing one. Permission bits alone are not trusted: read-only mounts,
+/// network filesystems, and container bind mounts all report modes that do
+/// not match what a write will actually do.
+#[must_use]
+pub fn directory_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(
+        ".cass-write-probe-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    ));
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+    {
+        Ok(file) => {
+            drop(file);
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Whether the database at `path` can be opened for writing: the file (if
+/// present) is not read-only, and its directory accepts new files — a
+/// writable open also needs to create WAL/SHM sidecars next to the
+/// database. Used to decide between [`FrankenStorage::open`] and
+/// [`FrankenStorage::open_readonly`] on read paths.
+#[must_use]
+pub fn db_location_writable(path: &Path) -> bool {
+    if let Ok(meta) = fs::metadata(path)
+        && meta.permissions().readonly()
+    {
+        return false;
+    }
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    directory_writable(dir)
+}
+
 /// SQL to register the FTS5 virtual table on a frankensqlite connection.
 ///
 /// FrankenSQLite skips virtual-table entries (rootpage=0) when loading
@@ -1439,6 +1492,138 @@ fn backup_vacuum_error_requires_consistent_retry(err: &frankensqlite::FrankenErr
     retryable_franken_error(err)
 }
 
+/// Result of `cass db slice`: a trimmed standalone copy of the canonical
+/// database holding only recently active conversations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DbSliceReport {
+    /// Conversations active at or after this unix-millis timestamp survive.
+    pub cutoff_ms: i64,
+    pub conversations_total: usize,
+    pub conversations_kept: usize,
+    pub conversations_dropped: usize,
+    pub messages_kept: usize,
+    pub output_bytes: u64,
+}
+
+/// Produce a trimmed, fully functional copy of the database at `out_path`
+/// containing only conversations whose activity timestamp
+/// (`COALESCE(ended_at, started_at)`) falls at or after `cutoff_ms`.
+/// Conversations with no recorded timestamps count as old and are dropped.
+///
+/// The slice is assembled in a staging file next to `out_path`: `VACUUM INTO`
+/// snapshots a consistent copy, the old rows are pruned and the derived
+/// surfaces (FTS, analytics, daily stats) rebuilt, then a second
+/// `VACUUM INTO` compacts the result into `out_path` — so the output file
+/// only ever appears complete and already reclaimed.
+pub fn slice_database(db_path: &Path, out_path: &Path, cutoff_ms: i64) -> Result<DbSliceReport> {
+    if out_path.exists() {
+        bail!("slice output path already exists: {}", out_path.display());
+    }
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let stage_path = database_sidecar_path(out_path, ".slice-stage");
+    remove_database_bundle_files(&stage_path);
+
+    vacuum_into_backup_stage(db_path, &stage_path).map_err(|e| {
+        anyhow!(
+            "VACUUM INTO staging copy failed for {}: {e}",
+            db_path.display()
+        )
+    })?;
+
+    let result = prune_slice_stage(&stage_path, cutoff_ms).and_then(|report| {
+        vacuum_into_backup_stage(&stage_path, out_path)
+            .map_err(|e| anyhow!("VACUUM INTO final slice failed: {e}"))?;
+        Ok(report)
+    });
+    remove_database_bundle_files(&stage_path);
+
+    let mut report = result?;
+    report.output_bytes = fs::metadata(out_path).map(|m| m.len()).unwrap_or(0);
+    Ok(report)
+}
+
+/// Best-effort removal of a database file plus its WAL/SHM sidecars.
+fn remove_database_bundle_files(root: &Path) {
+    let _ = fs::remove_file(root);
+    let _ = fs::remove_file(database_sidecar_path(root, "-wal"));
+    let _ = fs::remove_file(database_sidecar_path(root, "-shm"));
+}
+
+/// Delete conversations older than `cutoff_ms` from the staging copy and
+/// rebuild the derived surfaces so the slice is self-consistent.
+fn prune_slice_stage(stage_path: &Path, cutoff_ms: i64) -> Result<DbSliceReport> {
+    let storage = FrankenStorage::open(stage_path)
+        .with_context(|| format!("opening slice staging copy at {}", stage_path.display()))?;
+
+    let rows: Vec<(i64, Option<i64>)> = storage.conn.query_map_collect(
+        "SELECT id, COALESCE(ended_at, started_at) FROM conversations ORDER BY id",
+        fparams![],
+        |row| Ok((row.get_typed::<i64>(0)?, row.get_typed::<Option<i64>>(1)?)),
+    )?;
+    let conversations_total = rows.len();
+    let stale_ids: Vec<i64> = rows
+        .iter()
+        .filter(|(_, activity)| activity.is_none_or(|ts| ts < cutoff_ms))
+        .map(|(id, _)| *id)
+        .collect();
+
+    if !stale_ids.is_empty() {
+        let id_list = stale_ids
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut tx = storage.conn.transaction()?;
+        // Non-cascading external-lookup tables first (mirrors `cass forget`).
+        tx.execute_compat(
+            &format!(
+                "DELETE FROM conversation_external_lookup WHERE conversation_id IN ({id_list})"
+            ),
+            fparams![],
+        )?;
+        tx.execute_compat(
+            &format!(
+                "DELETE FROM conversation_external_tail_lookup WHERE conversation_id IN ({id_list})"
+            ),
+            fparams![],
+        )?;
+        // The remaining child tables (messages, snippets, tags, ...) cascade.
+        tx.execute_compat(
+            &format!("DELETE FROM conversations WHERE id IN ({id_list})"),
+            fparams![],
+        )?;
+        tx.commit()?;
+
+        storage
+            .rebuild_fts()
+            .context("rebuilding FTS in slice staging copy")?;
+        storage
+            .rebuild_analytics()
+            .context("rebuilding analytics in slice staging copy")?;
+        storage
+            .rebuild_daily_stats()
+            .context("rebuilding daily stats in slice staging copy")?;
+    }
+
+    let messages_kept: i64 =
+        storage
+            .conn
+            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+                row.get_typed(0)
+            })?;
+
+    Ok(DbSliceReport {
+        cutoff_ms,
+        conversations_total,
+        conversations_kept: conversations_total - stale_ids.len(),
+        conversations_dropped: stale_ids.len(),
+        messages_kept: messages_kept.max(0) as usize,
+        output_bytes: 0,
+    })
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct DatabaseBundleMoveResult {
     pub database: bool,
@@ -2985,7 +3170,34 @@ fn has_db_sidecar_suffix(name: &str) -> bool {
 }
 
 /// Public schema version constant for external checks.
-pub const CURRENT_SCHEMA_VERSION: i64 = 20;
+pub const CURRENT_SCHEMA_VERSION: i64 = 30;
+
+/// Durable conversation UUID, derived deterministically from the identity
+/// fields that survive database rebuilds and merges: the agent slug, the
+/// connector's external id (source path when the connector reports none),
+/// and a hash of the first message's content. Not a random UUID — 128 stable
+/// bits laid out in the familiar 8-4-4-4-12 shape, so re-indexing the same
+/// session on any machine yields the same id.
+#[must_use]
+pub fn derive_conversation_uuid(
+    agent_slug: &str,
+    external_id: &str,
+    first_message: &str,
+) -> String {
+    let hi = crate::search::query::stable_content_hash(&format!(
+        "cass-conversation-uuid:{agent_slug}\u{1f}{external_id}"
+    ));
+    let lo = crate::search::query::stable_content_hash(first_message) ^ hi.rotate_left(31);
+    let bytes = format!("{hi:016x}{lo:016x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &bytes[0..8],
+        &bytes[8..12],
+        &bytes[12..16],
+        &bytes[16..20],
+        &bytes[20..32]
+    )
+}
 const MIN_IN_PLACE_MIGRATION_SCHEMA_VERSION: i64 = 13;
 
 /// Result of checking schema compatibility.
@@ -3123,6 +3335,133 @@ fn check_schema_compatibility(
     result
 }
 
+/// How far from [`CURRENT_SCHEMA_VERSION`] a database may drift and still be
+/// opened read-only through the compatibility shim. One version in either
+/// direction: cass migrations are additive within a release window (new
+/// tables, new nullable columns), so the core conversation/message queries a
+/// reader issues keep working against an adjacent-version database.
+pub const READ_SHIM_VERSION_SLACK: i64 = 1;
+
+/// Outcome of negotiating a read-only open against a database that may have
+/// been written by a different cass build (e.g. synced from another machine).
+#[derive(Debug, Clone)]
+pub enum ReadCompatibility {
+    /// Schema version matches this build exactly.
+    Native,
+    /// Adjacent schema version; read-only access works through the
+    /// compatibility shim, but the caller should tell the user.
+    Shimmed { version: i64 },
+    /// Too far from this build to read safely. `guidance` is a full
+    /// user-facing explanation of what to run next; `version` is `None` when
+    /// the marker itself could not be read.
+    Unsupported {
+        version: Option<i64>,
+        guidance: String,
+    },
+}
+
+/// Negotiate read-only compatibility with the database at `path` without
+/// modifying it.
+///
+/// Unlike [`check_schema_compatibility`] (which decides whether a *writer*
+/// must migrate or rebuild), this classifies what a pure reader such as the
+/// TUI can do. Every failure mode — including open errors — folds into
+/// [`ReadCompatibility::Unsupported`] with actionable guidance, so callers
+/// never have to surface a raw database error to the user.
+pub fn check_read_compatibility(path: &Path) -> ReadCompatibility {
+    let version = match read_schema_version_readonly(path) {
+        Ok(version) => version,
+        Err(err) => {
+            return ReadCompatibility::Unsupported {
+                version: None,
+                guidance: format!(
+                    "Could not read the database at {}: {err}. Run `cass doctor` to \
+                     diagnose it, or `cass index --full` to rebuild from the session logs.",
+                    path.display()
+                ),
+            };
+        }
+    };
+
+    let Some(version) = version else {
+        return ReadCompatibility::Unsupported {
+            version: None,
+            guidance: "The database has no schema version marker, so it is either \
+                       empty or predates versioned schemas. Run `cass index --full` \
+                       to (re)build it."
+                .to_string(),
+        };
+    };
+
+    if version == SCHEMA_VERSION {
+        return ReadCompatibility::Native;
+    }
+    if (version - SCHEMA_VERSION).abs() <= READ_SHIM_VERSION_SLACK {
+        return ReadCompatibility::Shimmed { version };
+    }
+    if version > SCHEMA_VERSION {
+        ReadCompatibility::Unsupported {
+            version: Some(version),
+            guidance: format!(
+                "This database uses schema v{version}, written by a newer cass than \
+                 this build (which reads up to v{}). Upgrade with `cass self-update`, \
+                 or point --data-dir at an index built by this version.",
+                SCHEMA_VERSION + READ_SHIM_VERSION_SLACK
+            ),
+        }
+    } else {
+        ReadCompatibility::Unsupported {
+            version: Some(version),
+            guidance: format!(
+                "This database uses schema v{version}, older than this build can read \
+                 (v{} and up). Run `cass index --full` to migrate it in place.",
+                SCHEMA_VERSION - READ_SHIM_VERSION_SLACK
+            ),
+        }
+    }
+}
+
+/// Read `meta.schema_version` through a read-only connection. `Ok(None)`
+/// when the marker (or the meta table) is absent.
+fn read_schema_version_readonly(
+    path: &Path,
+) -> std::result::Result<Option<i64>, frankensqlite::FrankenError> {
+    let mut conn = open_franken_with_flags(
+        &path.to_string_lossy(),
+        FrankenOpenFlags::SQLITE_OPEN_READ_ONLY,
+    )?;
+
+    let result = (|| {
+        let meta_exists: i32 = conn.query_row_map(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='meta'",
+            fparams![],
+            |row| row.get_typed(0),
+        )?;
+        if meta_exists == 0 {
+            return Ok(None);
+        }
+        Ok(conn
+            .query_row_map(
+                "SELECT value FROM meta WHERE key = 'schema_version'",
+                fparams![],
+                |row| Ok(row.get_typed::<String>(0)?.parse().ok()),
+            )
+            .ok()
+            .flatten())
+    })();
+
+    if let Err(close_err) = conn.close_in_place() {
+        tracing::warn!(
+            error = %close_err,
+            db_path = %path.display(),
+            "read_schema_version_readonly: close_in_place failed; falling back to best-effort close"
+        );
+        conn.close_best_effort_in_place();
+    }
+
+    result
+}
+
 const SCHEMA_VERSION: i64 = CURRENT_SCHEMA_VERSION;
 
 #[cfg(test)]
@@ -3614,6 +3953,317 @@ FROM conversations c
 WHERE c.external_id IS NOT NULL;
 ";
 
+const MIGRATION_V21: &str = r"
+-- Normalize legacy second-valued timestamps to the canonical millisecond unit.
+-- Positive values under 1e11 can only be seconds (1e11 ms is March 1973, 1e11 s
+-- is the year 5138); new writes are normalized at the storage boundary, this
+-- fixes rows ingested before that existed. Zero/negative sentinels are left
+-- alone.
+UPDATE conversations SET started_at = started_at * 1000
+    WHERE started_at > 0 AND started_at < 100000000000;
+UPDATE conversations SET ended_at = ended_at * 1000
+    WHERE ended_at > 0 AND ended_at < 100000000000;
+UPDATE conversations SET last_message_created_at = last_message_created_at * 1000
+    WHERE last_message_created_at > 0 AND last_message_created_at < 100000000000;
+UPDATE messages SET created_at = created_at * 1000
+    WHERE created_at > 0 AND created_at < 100000000000;
+UPDATE conversation_tail_state SET ended_at = ended_at * 1000
+    WHERE ended_at > 0 AND ended_at < 100000000000;
+UPDATE conversation_tail_state SET last_message_created_at = last_message_created_at * 1000
+    WHERE last_message_created_at > 0 AND last_message_created_at < 100000000000;
+UPDATE conversation_external_tail_lookup SET ended_at = ended_at * 1000
+    WHERE ended_at > 0 AND ended_at < 100000000000;
+UPDATE conversation_external_tail_lookup SET last_message_created_at = last_message_created_at * 1000
+    WHERE last_message_created_at > 0 AND last_message_created_at < 100000000000;
+";
+
+const MIGRATION_V22: &str = r"
+-- Conversation-level access tracking for 'what was I just looking at?'.
+-- Keyed by source_path rather than conversation id so view history survives
+-- reindexing (row ids churn when a conversation is re-ingested; the session
+-- file path is the stable handle follow-up commands already use).
+CREATE TABLE IF NOT EXISTS conversation_views (
+    source_path TEXT PRIMARY KEY,
+    last_viewed_at INTEGER NOT NULL,
+    view_count INTEGER NOT NULL DEFAULT 1
+);
+CREATE INDEX IF NOT EXISTS idx_conversation_views_last_viewed
+    ON conversation_views(last_viewed_at DESC);
+";
+
+const MIGRATION_V23: &str = r"
+-- Derived registry of content repeated across conversations (system prompts,
+-- environment dumps, MCP tool manifests). One row per repeated block, keyed
+-- by the whitespace-normalized content fingerprint; `minhash` is the block's
+-- MinHash signature (16 little-endian u64s) so ranking can also drop
+-- near-identical variants. Rebuilt wholesale at the end of each index run;
+-- see `refresh_boilerplate_contents`.
+CREATE TABLE IF NOT EXISTS boilerplate_contents (
+    content_hash INTEGER PRIMARY KEY,
+    minhash BLOB NOT NULL,
+    conversations INTEGER NOT NULL,
+    chars INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V24: &str = r"
+-- Per-conversation integrity seal for tamper detection (`cass verify`).
+-- `chain_hash` is a blake3 hash chain over (idx, role, content) in idx order,
+-- sealed at index time; recomputing the chain later and comparing detects
+-- post-hoc edits to indexed content. A seal is only rewritten when the
+-- conversation's message count changes through normal ingest, so an
+-- out-of-band UPDATE of message text leaves a stale seal behind as evidence.
+CREATE TABLE IF NOT EXISTS conversation_integrity (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    chain_hash TEXT NOT NULL,
+    message_count INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V25: &str = r"
+-- Message-level source provenance: the byte/line range of the raw record a
+-- message was parsed from (JSONL line for file-backed connectors). Kept in a
+-- side table rather than widening the hot messages row; rows are derived data
+-- filled in by `refresh_message_provenance` and absent for messages whose
+-- source record could not be located (non-JSONL sources, rewritten files).
+CREATE TABLE IF NOT EXISTS message_provenance (
+    message_id INTEGER PRIMARY KEY REFERENCES messages(id) ON DELETE CASCADE,
+    start_byte INTEGER NOT NULL,
+    end_byte INTEGER NOT NULL,
+    line_no INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V26: &str = r"
+-- Heuristic per-conversation usefulness score (see `search::quality`): did
+-- the session go anywhere, or was it a dead end / retry loop? Kept in a side
+-- table as derived data, filled in by `refresh_conversation_quality` at the
+-- end of non-watch index runs and recomputed only when a conversation's
+-- message count changes. `message_count` records the count the score was
+-- computed against so the refresh pass can stay incremental.
+CREATE TABLE IF NOT EXISTS conversation_quality (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    score REAL NOT NULL,
+    message_count INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V27: &str = r"
+-- Headless/batch run classification (see `model::agent_run`): was this
+-- session an automated invocation (codex exec, claude -p) rather than an
+-- interactive chat? One row per evaluated conversation so the refresh pass
+-- (`refresh_agent_runs`) stays incremental — is_run = 0 rows record 'looked
+-- at it, it's a chat' and are skipped while the message count is stable.
+-- exit_code is the status echoed in the transcript, when derivable.
+CREATE TABLE IF NOT EXISTS agent_runs (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    is_run INTEGER NOT NULL,
+    exec_heavy INTEGER NOT NULL,
+    exit_code INTEGER,
+    message_count INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V28: &str = r"
+-- Per-conversation topic facets (see `search::facets`): languages seen in
+-- code blocks, frameworks named, package managers run. Derived data filled
+-- in by `refresh_conversation_facets` at the end of non-watch index runs and
+-- consumed by inline `facet:` query terms and the `cass stats` summary.
+-- `facet_state` is the incremental-refresh bookkeeping, separate from the
+-- rows because a conversation can legitimately have zero facets.
+CREATE TABLE IF NOT EXISTS facets (
+    conversation_id INTEGER NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (conversation_id, kind, value)
+);
+CREATE INDEX IF NOT EXISTS idx_facets_value ON facets(value);
+CREATE TABLE IF NOT EXISTS facet_state (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    message_count INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V29: &str = r"
+-- Exact per-conversation token usage (see `token_budget`), extracted from
+-- the usage records agents leave in message extra_json. One row per
+-- evaluated conversation so the refresh pass (`refresh_token_usage`) stays
+-- incremental — NULL prompt/completion records 'looked, the agent reported
+-- nothing' and is skipped while the message count is stable. Consumed by
+-- the monthly budget checks at the end of index runs.
+CREATE TABLE IF NOT EXISTS token_usage (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    prompt_tokens INTEGER,
+    completion_tokens INTEGER,
+    message_count INTEGER NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+";
+
+const MIGRATION_V30: &str = r"
+-- Durable per-conversation UUIDs, derived deterministically from agent slug,
+-- external id, and the first message's content hash (see
+-- `derive_conversation_uuid`). Integer conversation ids are rowids and change
+-- across rebuilds and merges; external references (scripts, notes, links)
+-- need an id that survives both. This table is also the legacy-id mapping:
+-- one row per conversation pairing the current rowid with its stable uuid.
+CREATE TABLE IF NOT EXISTS conversation_uuids (
+    conversation_id INTEGER PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    uuid TEXT NOT NULL,
+    computed_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_conversation_uuids_uuid ON conversation_uuids(uuid);
+";
+
+/// One full-text hit from [`FrankenStorage::search_messages_fts`].
+#[derive(Debug, Clone, Serialize)]
+pub struct EphemeralSearchHit {
+    pub agent: String,
+    pub title: Option<String>,
+    pub source_path: String,
+    pub message_idx: i64,
+    pub role: String,
+    pub content: String,
+}
+
+/// Byte/line range of the raw source record one message came from.
+/// `start_byte..end_byte` covers the record without its trailing newline;
+/// `line_no` is 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MessageSourceSpan {
+    pub start_byte: i64,
+    pub end_byte: i64,
+    pub line_no: i64,
+}
+
+/// One conversation whose stored integrity seal no longer matches
+/// recomputation from the current message rows.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityMismatch {
+    pub conversation_id: i64,
+    pub source_path: String,
+    /// The seal recorded at index time.
+    pub expected: String,
+    /// The chain recomputed from the rows as they are now.
+    pub actual: String,
+}
+
+/// Outcome of [`FrankenStorage::verify_conversation_integrity`].
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    /// Sealed conversations that were recomputed and compared.
+    pub checked: usize,
+    /// Seals that matched recomputation.
+    pub verified: usize,
+    /// Conversations with no seal yet (indexed before the seal table existed,
+    /// or added since the last index run).
+    pub unsealed: usize,
+    /// Seals that did not match: indexed content changed after sealing.
+    pub mismatched: Vec<IntegrityMismatch>,
+}
+
+/// Blake3 hash chain over a conversation's messages in `idx` order:
+/// `chain_0 = H(seed)`, `chain_i = H(chain_{i-1} || idx || role || content)`.
+///
+/// The seed domain-separates this chain from other blake3 uses in cass, and
+/// role/content are length-prefixed so adjacent fields can't be reassociated
+/// (`("ab", "c")` and `("a", "bc")` hash differently). Chaining (rather than
+/// hashing a concatenation) makes the seal sensitive to message order and
+/// means a prefix of the conversation never shares a hash with the whole.
+pub fn conversation_chain_hash<'a>(
+    messages: impl IntoIterator<Item = (i64, &'a str, &'a str)>,
+) -> String {
+    let mut chain = blake3::hash(b"cass:conversation-integrity:v1");
+    for (idx, role, content) in messages {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(chain.as_bytes());
+        hasher.update(&idx.to_le_bytes());
+        hasher.update(&(role.len() as u64).to_le_bytes());
+        hasher.update(role.as_bytes());
+        hasher.update(&(content.len() as u64).to_le_bytes());
+        hasher.update(content.as_bytes());
+        chain = hasher.finalize();
+    }
+    chain.to_hex().to_string()
+}
+
+/// Locate each message's source record inside a raw JSONL file.
+///
+/// Matching is textual: a message's content appears inside its record as a
+/// JSON string, so the first ~64 chars of content are JSON-escaped and
+/// searched for line by line. The cursor only moves forward (records appear
+/// in message order in every JSONL connector), and consecutive messages may
+/// resolve to the same line — some formats pack a whole turn into one record.
+/// Messages whose fragment is not found yield no span rather than a guess.
+pub(crate) fn locate_message_spans(
+    raw: &str,
+    messages: &[(i64, String)],
+) -> Vec<(i64, MessageSourceSpan)> {
+    struct RawLine<'a> {
+        start_byte: usize,
+        end_byte: usize,
+        line_no: i64,
+        text: &'a str,
+    }
+    let mut lines = Vec::new();
+    let mut offset = 0usize;
+    for (index, line) in raw.split_inclusive('\n').enumerate() {
+        let record = line.strip_suffix('\n').unwrap_or(line);
+        let record = record.strip_suffix('\r').unwrap_or(record);
+        lines.push(RawLine {
+            start_byte: offset,
+            end_byte: offset + record.len(),
+            line_no: index as i64 + 1,
+            text: record,
+        });
+        offset += line.len();
+    }
+
+    let mut spans = Vec::new();
+    let mut cursor = 0usize;
+    for (message_id, content) in messages {
+        let Some(fragment) = json_escaped_fragment(content) else {
+            continue;
+        };
+        let Some(matched) = lines[cursor..]
+            .iter()
+            .position(|line| line.text.contains(&fragment))
+            .map(|found| cursor + found)
+        else {
+            continue;
+        };
+        let line = &lines[matched];
+        spans.push((
+            *message_id,
+            MessageSourceSpan {
+                start_byte: line.start_byte as i64,
+                end_byte: line.end_byte as i64,
+                line_no: line.line_no,
+            },
+        ));
+        cursor = matched;
+    }
+    spans
+}
+
+/// JSON-escape the first 64 chars of message content for raw-record search.
+/// The escaping of a prefix is a prefix of the escaping of the whole string,
+/// so this fragment appears verbatim in the record's content field. Empty or
+/// whitespace-only content cannot be located and yields `None`.
+fn json_escaped_fragment(content: &str) -> Option<String> {
+    let prefix: String = content.trim_start().chars().take(64).collect();
+    if prefix.is_empty() {
+        return None;
+    }
+    let escaped = serde_json::to_string(&prefix).ok()?;
+    Some(escaped[1..escaped.len() - 1].to_string())
+}
+
 /// Row from the embedding_jobs table.
 #[derive(Debug, Clone)]
 pub struct EmbeddingJobRow {
@@ -4022,6 +4672,21 @@ impl FrankenStorage {
         Ok(storage)
     }
 
+    /// Open a fresh in-memory database with the canonical schema applied.
+    /// Backs ephemeral consumers (`cass scan`) that parse ad-hoc session
+    /// files without touching a persistent database; everything vanishes
+    /// with the connection. The derived `fts_messages` shadow is not built
+    /// here — callers that want full-text matching run
+    /// [`Self::rebuild_fts_via_frankensqlite`] once after inserting.
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = FrankenConnection::open(":memory:")
+            .with_context(|| "opening in-memory frankensqlite db")?;
+        let storage = Self::new(conn, PathBuf::from(":memory:"));
+        storage.run_migrations()?;
+        storage.apply_config()?;
+        Ok(storage)
+    }
+
     /// Open a writer connection that skips migration (assumes DB already migrated).
     ///
     /// Used by the BEGIN CONCURRENT parallel writer pool: each writer needs its
@@ -4255,6 +4920,20 @@ impl FrankenStorage {
         }
     }
 
+    /// Open writable — running migrations and any WAL recovery — when the
+    /// database location permits writes, read-only otherwise. Read paths
+    /// that historically used [`Self::open`] (TUI detail loads, `cass view`
+    /// follow-up hydration) go through this so a database on a read-only
+    /// mount is served as-is instead of failing on the recovery attempt or
+    /// WAL sidecar creation.
+    pub fn open_rw_or_readonly(path: &Path) -> Result<Self> {
+        if db_location_writable(path) {
+            Self::open(path)
+        } else {
+            Self::open_readonly(path)
+        }
+    }
+
     /// Open in read-only mode using frankensqlite compat flags.
     pub fn open_readonly(path: &Path) -> Result<Self> {
         Self::open_readonly_with_doctor_lock_timeout(path, DOCTOR_MUTATION_DB_OPEN_LOCK_TIMEOUT)
@@ -4400,6 +5079,11 @@ impl FrankenStorage {
         self.conn
             .execute("PRAGMA query_only = 1;")
             .with_context(|| "setting query_only")?;
+        // Keep sort/materialization spill in memory: a read-only open may be
+        // serving a database on a mount that rejects temp files too.
+        self.conn
+            .execute("PRAGMA temp_store = MEMORY;")
+            .with_context(|| "setting temp_store")?;
         self.conn
             .execute("PRAGMA busy_timeout = 5000;")
             .with_context(|| "setting busy_timeout")?;
@@ -4649,165 +5333,969 @@ impl FrankenStorage {
         Ok(0)
     }
 
-    /// Keep `meta.schema_version` in sync for backward compatibility with `SqliteStorage`.
-    fn sync_meta_schema_version(&self, version: i64) -> Result<()> {
-        // The meta table is created by V1 migration. If it doesn't exist yet,
-        // there's nothing to sync.
-        if self.conn.query("SELECT key FROM meta LIMIT 1;").is_err() {
-            return Ok(());
-        }
-
-        // Only write if the version needs updating to avoid write lock contention
-        if let Ok(rows) = self
-            .conn
-            .query("SELECT value FROM meta WHERE key = 'schema_version';")
-            && let Some(row) = rows.first()
-            && let Ok(val) = row.get_typed::<String>(0)
-            && val == version.to_string()
-        {
-            return Ok(()); // Already up to date
+    /// Record that a conversation was opened (TUI detail load, follow-up
+    /// fetch). Best-effort by design: view history is a convenience, so a
+    /// failed write (read-only mount, pre-v22 schema) must never fail the
+    /// read path that triggered it.
+    pub fn record_conversation_view(&self, source_path: &str) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        if let Err(error) = self.conn.execute_compat(
+            "INSERT INTO conversation_views (source_path, last_viewed_at, view_count)
+             VALUES (?1, ?2, 1)
+             ON CONFLICT(source_path) DO UPDATE SET
+                 view_count = view_count + 1,
+                 last_viewed_at = excluded.last_viewed_at",
+            fparams![source_path, now_ms],
+        ) {
+            tracing::debug!(source_path, %error, "skipping conversation view tracking");
         }
+    }
 
-        self.conn
-            .execute_compat(
-                "INSERT OR REPLACE INTO meta(key, value) VALUES('schema_version', ?1);",
-                &[ParamValue::from(version.to_string())],
-            )
-            .with_context(|| "syncing meta schema_version")?;
+    /// Rebuild the `boilerplate_contents` registry from the current corpus.
+    ///
+    /// The SQL pass pre-filters to messages long enough to matter that
+    /// already repeat verbatim in at least two conversations; the Rust pass
+    /// (`detect_boilerplate`) clusters near-identical variants by MinHash and
+    /// applies the distinct-conversation threshold. Returns the number of
+    /// boilerplate rows written. Called at the end of non-watch index runs;
+    /// the table is a derived asset, so wholesale replacement is safe.
+    pub fn refresh_boilerplate_contents(&self) -> Result<usize> {
+        use crate::search::boilerplate::{
+            MIN_CONTENT_CHARS, detect_boilerplate, signature_to_blob,
+        };
 
-        Ok(())
-    }
+        let candidates: Vec<(String, i64)> = self.conn.query_map_collect(
+            "SELECT content, COUNT(DISTINCT conversation_id) AS convs
+             FROM messages
+             WHERE LENGTH(content) >= ?1
+             GROUP BY content
+             HAVING convs >= 2",
+            &[ParamValue::from(MIN_CONTENT_CHARS as i64)],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
 
-    /// Resolve the database file path for this connection.
-    pub fn database_path(&self) -> Result<PathBuf> {
-        Ok(self.db_path.clone())
-    }
+        let rows = detect_boilerplate(&candidates);
+        let now_ms = chrono::Utc::now().timestamp_millis();
 
-    pub(crate) fn ephemeral_writer_preflight_verified(&self) -> bool {
-        self.ephemeral_writer_preflight_verified
-            .load(Ordering::Relaxed)
+        let mut tx = self.conn.transaction()?;
+        tx.execute_compat("DELETE FROM boilerplate_contents", &[])?;
+        for row in &rows {
+            let blob = signature_to_blob(&row.signature);
+            tx.execute_compat(
+                "INSERT OR REPLACE INTO boilerplate_contents
+                     (content_hash, minhash, conversations, chars, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                fparams![
+                    row.fingerprint as i64,
+                    blob.as_slice(),
+                    row.conversations,
+                    row.chars,
+                    now_ms
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(rows.len())
     }
 
-    pub(crate) fn mark_ephemeral_writer_preflight_verified(&self) {
-        self.ephemeral_writer_preflight_verified
-            .store(true, Ordering::Relaxed);
-    }
+    /// Seal integrity hashes for conversations that have none yet or whose
+    /// message count changed through normal ingest. Called at the end of
+    /// non-watch index runs, next to the boilerplate refresh.
+    ///
+    /// Deliberately *not* wholesale: a conversation whose content changed
+    /// without its message count moving is exactly the tamper signature
+    /// `cass verify` exists to catch, so its stale seal must survive the next
+    /// index run as evidence. Returns the number of seals written.
+    pub fn refresh_conversation_integrity(&self) -> Result<usize> {
+        let current_counts: Vec<(i64, i64)> = self.conn.query_map_collect(
+            "SELECT c.id, COUNT(m.id)
+             FROM conversations c
+             LEFT JOIN messages m ON m.conversation_id = c.id
+             GROUP BY c.id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let sealed: HashMap<i64, i64> = self
+            .conn
+            .query_map_collect(
+                "SELECT conversation_id, message_count FROM conversation_integrity",
+                &[],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-    pub(crate) fn index_writer_checkpoint_pages(&self) -> Option<i64> {
-        let pages = self.index_writer_checkpoint_pages.load(Ordering::Relaxed);
-        (pages != UNSET_INDEX_WRITER_CHECKPOINT_PAGES).then_some(pages)
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, message_count) in current_counts {
+            if sealed.get(&conv_id) == Some(&message_count) {
+                continue;
+            }
+            let chain_hash = self.compute_conversation_chain_hash(conv_id)?;
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO conversation_integrity
+                     (conversation_id, chain_hash, message_count, computed_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                fparams![conv_id, chain_hash.as_str(), message_count, now_ms],
+            )?;
+            written += 1;
+        }
+        Ok(written)
     }
 
-    pub(crate) fn mark_index_writer_checkpoint_pages(&self, pages: i64) {
-        self.index_writer_checkpoint_pages
-            .store(pages, Ordering::Relaxed);
-    }
+    /// Recompute every sealed conversation's hash chain and compare it to the
+    /// stored seal (`cass verify`). Conversations without a seal (indexed
+    /// before the seal table existed, or added since the last index run) are
+    /// counted as `unsealed` rather than failed.
+    pub fn verify_conversation_integrity(&self) -> Result<IntegrityReport> {
+        let total: i64 =
+            self.conn
+                .query_row_map("SELECT COUNT(*) FROM conversations", fparams![], |row| {
+                    row.get_typed(0)
+                })?;
+        let seals: Vec<(i64, String, String)> = self.conn.query_map_collect(
+            "SELECT ci.conversation_id, ci.chain_hash, c.source_path
+             FROM conversation_integrity ci
+             JOIN conversations c ON c.id = ci.conversation_id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?)),
+        )?;
 
-    pub(crate) fn index_writer_busy_timeout_ms(&self) -> Option<u64> {
-        let timeout_ms = self.index_writer_busy_timeout_ms.load(Ordering::Relaxed);
-        (timeout_ms != UNSET_INDEX_WRITER_BUSY_TIMEOUT_MS).then_some(timeout_ms)
+        let mut report = IntegrityReport {
+            checked: seals.len(),
+            verified: 0,
+            unsealed: (total as usize).saturating_sub(seals.len()),
+            mismatched: Vec::new(),
+        };
+        for (conv_id, expected, source_path) in seals {
+            let actual = self.compute_conversation_chain_hash(conv_id)?;
+            if actual == expected {
+                report.verified += 1;
+            } else {
+                report.mismatched.push(IntegrityMismatch {
+                    conversation_id: conv_id,
+                    source_path,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(report)
     }
 
-    pub(crate) fn mark_index_writer_busy_timeout_ms(&self, timeout_ms: u64) {
-        self.index_writer_busy_timeout_ms
-            .store(timeout_ms, Ordering::Relaxed);
-    }
+    /// Recompute the hash chain for one conversation from its current rows.
+    fn compute_conversation_chain_hash(&self, conversation_id: i64) -> Result<String> {
+        let rows: Vec<(i64, String, String)> = self.conn.query_map_collect(
+            "SELECT idx, role, content FROM messages
+             WHERE conversation_id = ?1 ORDER BY idx",
+            fparams![conversation_id],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?)),
+        )?;
+        Ok(conversation_chain_hash(rows.iter().map(
+            |(idx, role, content)| (*idx, role.as_str(), content.as_str()),
+        )))
+    }
+
+    /// Fill in message-level source provenance (byte/line spans, see
+    /// [`MessageSourceSpan`]) for conversations that have messages without
+    /// it. Called at the end of non-watch index runs, next to the integrity
+    /// seal pass, and incremental by construction: a conversation is only
+    /// re-read when at least one of its messages lacks a span, so appended
+    /// messages get located on the next pass without rewriting the rest.
+    /// Returns the number of spans written.
+    pub fn refresh_message_provenance(&self) -> Result<usize> {
+        let pending: Vec<(i64, String)> = self.conn.query_map_collect(
+            "SELECT DISTINCT c.id, c.source_path
+             FROM conversations c
+             JOIN messages m ON m.conversation_id = c.id
+             LEFT JOIN message_provenance p ON p.message_id = m.id
+             WHERE p.message_id IS NULL AND c.source_path LIKE '%.jsonl'",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
 
-    /// Open database with migration, backing up if schema is incompatible.
-    pub fn open_or_rebuild(path: &Path) -> std::result::Result<Self, MigrationError> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+        let mut written = 0usize;
+        let mut tx = self.conn.transaction()?;
+        for (conversation_id, source_path) in pending {
+            // Unreadable or rewritten sources are skipped, not failed: the
+            // span is a convenience and the file may legitimately be gone.
+            let Ok(raw) = std::fs::read_to_string(&source_path) else {
+                continue;
+            };
+            let messages: Vec<(i64, String)> = self.conn.query_map_collect(
+                "SELECT id, content FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![conversation_id],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?;
+            for (message_id, span) in locate_message_spans(&raw, &messages) {
+                tx.execute_compat(
+                    "INSERT OR REPLACE INTO message_provenance
+                         (message_id, start_byte, end_byte, line_no)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    fparams![message_id, span.start_byte, span.end_byte, span.line_no],
+                )?;
+                written += 1;
+            }
         }
+        tx.commit()?;
+        Ok(written)
+    }
+
+    /// Stored source span for one message, if provenance has been computed
+    /// for it. Pre-v25 databases (no table yet) report `None` rather than
+    /// erroring so read paths degrade gracefully.
+    pub fn message_provenance(&self, message_id: i64) -> Result<Option<MessageSourceSpan>> {
+        let rows: Vec<MessageSourceSpan> = match self.conn.query_map_collect(
+            "SELECT start_byte, end_byte, line_no FROM message_provenance
+             WHERE message_id = ?1",
+            fparams![message_id],
+            |row: &FrankenRow| {
+                Ok(MessageSourceSpan {
+                    start_byte: row.get_typed(0)?,
+                    end_byte: row.get_typed(1)?,
+                    line_no: row.get_typed(2)?,
+                })
+            },
+        ) {
+            Ok(rows) => rows,
+            Err(_) => return Ok(None),
+        };
+        Ok(rows.into_iter().next())
+    }
+
+    /// Recompute the heuristic usefulness score (see [`crate::search::quality`])
+    /// for conversations that have none yet or whose message count changed
+    /// through normal ingest. Called at the end of non-watch index runs, next
+    /// to the integrity seal pass, and incremental the same way: a stable
+    /// message count means the stored score is still current. Returns the
+    /// number of scores written.
+    pub fn refresh_conversation_quality(&self) -> Result<usize> {
+        let current_counts: Vec<(i64, i64)> = self.conn.query_map_collect(
+            "SELECT c.id, COUNT(m.id)
+             FROM conversations c
+             LEFT JOIN messages m ON m.conversation_id = c.id
+             GROUP BY c.id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let scored: HashMap<i64, i64> = self
+            .conn
+            .query_map_collect(
+                "SELECT conversation_id, message_count FROM conversation_quality",
+                &[],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-        if path.exists() {
-            let check_result = check_schema_compatibility(path);
-            match check_result {
-                Ok(SchemaCheck::Compatible) | Ok(SchemaCheck::NeedsMigration) => {
-                    // Continue with normal open
-                }
-                Ok(SchemaCheck::NeedsRebuild(reason)) => {
-                    let backup_path = create_backup(path)?;
-                    cleanup_old_backups(path, MAX_BACKUPS)?;
-                    remove_database_files(path)?;
-                    return Err(MigrationError::RebuildRequired {
-                        reason,
-                        backup_path,
-                    });
-                }
-                Err(err) if schema_check_error_requires_rebuild(&err) => {
-                    let backup_path = create_backup(path)?;
-                    cleanup_old_backups(path, MAX_BACKUPS)?;
-                    remove_database_files(path)?;
-                    return Err(MigrationError::RebuildRequired {
-                        reason: format!("Database appears corrupted: {err}"),
-                        backup_path,
-                    });
-                }
-                Err(err) => return Err(MigrationError::Database(err)),
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, message_count) in current_counts {
+            if scored.get(&conv_id) == Some(&message_count) {
+                continue;
             }
+            let messages: Vec<(String, String)> = self.conn.query_map_collect(
+                "SELECT role, content FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![conv_id],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?;
+            let score = crate::search::quality::score_conversation(&messages);
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO conversation_quality
+                     (conversation_id, score, message_count, computed_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                fparams![conv_id, score, message_count, now_ms],
+            )?;
+            written += 1;
         }
+        Ok(written)
+    }
 
-        let storage = Self::open(path).map_err(|e| MigrationError::Other(e.to_string()))?;
-        Ok(storage)
+    /// Stored usefulness score for one conversation, if computed. Pre-v26
+    /// databases (no table yet) report `None` rather than erroring so read
+    /// paths degrade gracefully.
+    pub fn conversation_quality(&self, conversation_id: i64) -> Result<Option<f64>> {
+        let rows: Vec<f64> = match self.conn.query_map_collect(
+            "SELECT score FROM conversation_quality WHERE conversation_id = ?1",
+            fparams![conversation_id],
+            |row: &FrankenRow| row.get_typed(0),
+        ) {
+            Ok(rows) => rows,
+            Err(_) => return Ok(None),
+        };
+        Ok(rows.into_iter().next())
     }
-}
 
-// -------------------------------------------------------------------------
-// Frankensqlite migration helpers
-// -------------------------------------------------------------------------
+    /// Re-classify headless/batch runs (see [`crate::model::agent_run`]) for
+    /// conversations that have no classification yet or whose message count
+    /// changed through normal ingest. Called at the end of non-watch index
+    /// runs, next to the quality score pass, and incremental the same way.
+    /// Returns the number of classifications written.
+    pub fn refresh_agent_runs(&self) -> Result<usize> {
+        let current_counts: Vec<(i64, i64)> = self.conn.query_map_collect(
+            "SELECT c.id, COUNT(m.id)
+             FROM conversations c
+             LEFT JOIN messages m ON m.conversation_id = c.id
+             GROUP BY c.id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let classified: HashMap<i64, i64> = self
+            .conn
+            .query_map_collect(
+                "SELECT conversation_id, message_count FROM agent_runs",
+                &[],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-/// Build the `MigrationRunner` for the frankensqlite migration path.
-///
-/// Uses a single combined migration (version 13) that creates the complete
-/// final schema in one step. This avoids the V5 `DROP TABLE conversations`
-/// operation which triggers a known frankensqlite limitation: autoindex entries
-/// in sqlite_master are not properly cleaned up during DROP TABLE, causing
-/// "sqlite_master entry not found" errors.
-///
-/// For existing databases transitioned from SqliteStorage, the transition
-/// function backfills `_schema_migrations`; post-V13 additive migrations then
-/// run normally.
-fn build_cass_migrations_before_tail_cache() -> MigrationRunner {
-    MigrationRunner::new()
-        .add(13, "full_schema_v13", MIGRATION_FRESH_SCHEMA)
-        .add(14, "fts_contentless", MIGRATION_V14)
-}
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, message_count) in current_counts {
+            if classified.get(&conv_id) == Some(&message_count) {
+                continue;
+            }
+            let messages: Vec<(String, String)> = self.conn.query_map_collect(
+                "SELECT role, content FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![conv_id],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?;
+            let classification = crate::model::agent_run::classify_run(&messages);
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO agent_runs
+                     (conversation_id, is_run, exec_heavy, exit_code, message_count, computed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                fparams![
+                    conv_id,
+                    i64::from(classification.is_run),
+                    i64::from(classification.exec_heavy),
+                    classification.exit_code,
+                    message_count,
+                    now_ms
+                ],
+            )?;
+            written += 1;
+        }
+        Ok(written)
+    }
 
-fn build_cass_migrations_after_tail_cache() -> MigrationRunner {
-    MigrationRunner::new()
-        .add(16, "drop_redundant_message_conv_idx", MIGRATION_V16)
-        .add(17, "drop_message_created_idx", MIGRATION_V17)
-        .add(18, "conversation_tail_state_hot_table", MIGRATION_V18)
-        .add(19, "conversation_external_lookup", MIGRATION_V19)
-        .add(20, "conversation_external_tail_lookup", MIGRATION_V20)
-}
+    /// Stored run classification for one conversation, if computed. Pre-v27
+    /// databases (no table yet) report `None` rather than erroring so read
+    /// paths degrade gracefully.
+    pub fn agent_run_classification(
+        &self,
+        conversation_id: i64,
+    ) -> Result<Option<crate::model::agent_run::RunClassification>> {
+        let rows: Vec<crate::model::agent_run::RunClassification> =
+            match self.conn.query_map_collect(
+                "SELECT is_run, exec_heavy, exit_code FROM agent_runs
+                 WHERE conversation_id = ?1",
+                fparams![conversation_id],
+                |row: &FrankenRow| {
+                    Ok(crate::model::agent_run::RunClassification {
+                        is_run: row.get_typed::<i64>(0)? != 0,
+                        exec_heavy: row.get_typed::<i64>(1)? != 0,
+                        exit_code: row.get_typed(2)?,
+                    })
+                },
+            ) {
+                Ok(rows) => rows,
+                Err(_) => return Ok(None),
+            };
+        Ok(rows.into_iter().next())
+    }
+
+    /// Re-derive topic facets (see [`crate::search::facets`]) and PII
+    /// classification tags (see [`crate::search::pii`]) for conversations
+    /// that have none computed yet or whose message count changed through
+    /// normal ingest. Called at the end of non-watch index runs, next to the
+    /// run classification pass, and incremental the same way; `facet_state`
+    /// carries the bookkeeping because a conversation can legitimately have
+    /// zero facet rows. `pii_customer_names` is the operator's dictionary
+    /// from the `[pii]` config table; since it is not part of the
+    /// incremental state, dictionary edits take effect as conversations are
+    /// re-derived (or on a full rebuild). Returns the number of
+    /// conversations re-derived.
+    pub fn refresh_conversation_facets(&self, pii_customer_names: &[String]) -> Result<usize> {
+        let current_counts: Vec<(i64, i64)> = self.conn.query_map_collect(
+            "SELECT c.id, COUNT(m.id)
+             FROM conversations c
+             LEFT JOIN messages m ON m.conversation_id = c.id
+             GROUP BY c.id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let derived: HashMap<i64, i64> = self
+            .conn
+            .query_map_collect(
+                "SELECT conversation_id, message_count FROM facet_state",
+                &[],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-fn schema_migration_is_applied(conn: &FrankenConnection, version: i64) -> Result<bool> {
-    let rows = conn
-        .query_with_params(
-            "SELECT 1 FROM _schema_migrations WHERE version = ?1 LIMIT 1;",
-            &[SqliteValue::from(version)],
-        )
-        .with_context(|| format!("checking schema migration version {version}"))?;
-    Ok(!rows.is_empty())
-}
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, message_count) in current_counts {
+            if derived.get(&conv_id) == Some(&message_count) {
+                continue;
+            }
+            let messages: Vec<(String, String)> = self.conn.query_map_collect(
+                "SELECT role, content FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![conv_id],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?;
+            let mut facets = crate::search::facets::extract_facets(&messages);
+            facets.extend(crate::search::pii::extract_pii_facets(
+                &messages,
+                pii_customer_names,
+            ));
+            self.conn.execute_compat(
+                "DELETE FROM facets WHERE conversation_id = ?1",
+                fparams![conv_id],
+            )?;
+            for (kind, value) in &facets {
+                self.conn.execute_compat(
+                    "INSERT OR REPLACE INTO facets (conversation_id, kind, value)
+                     VALUES (?1, ?2, ?3)",
+                    fparams![conv_id, kind.as_str(), value.as_str()],
+                )?;
+            }
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO facet_state
+                     (conversation_id, message_count, computed_at)
+                 VALUES (?1, ?2, ?3)",
+                fparams![conv_id, message_count, now_ms],
+            )?;
+            written += 1;
+        }
+        Ok(written)
+    }
 
-fn apply_conversation_tail_state_cache_migration(conn: &FrankenConnection) -> Result<bool> {
-    conn.execute("BEGIN IMMEDIATE;")
-        .with_context(|| "starting v15 conversation tail-state migration transaction")?;
+    /// Stored `(kind, value)` facets for one conversation, if derived.
+    /// Pre-v28 databases (no table yet) report an empty list rather than
+    /// erroring so read paths degrade gracefully.
+    pub fn conversation_facets(&self, conversation_id: i64) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .conn
+            .query_map_collect(
+                "SELECT kind, value FROM facets
+                 WHERE conversation_id = ?1 ORDER BY kind, value",
+                fparams![conversation_id],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )
+            .unwrap_or_default())
+    }
+
+    /// Re-extract exact token usage (see [`crate::token_budget`]) for
+    /// conversations that have none computed yet or whose message count
+    /// changed through normal ingest. Same incremental contract as the
+    /// facet pass; a NULL-count row records a conversation whose agent
+    /// reported no usage. Where exact counts were found the conversation's
+    /// `approx_tokens` estimate is replaced with their sum, so listings and
+    /// stats show real numbers. Returns the number of conversations
+    /// re-evaluated.
+    pub fn refresh_token_usage(&self) -> Result<usize> {
+        let current_counts: Vec<(i64, i64)> = self.conn.query_map_collect(
+            "SELECT c.id, COUNT(m.id)
+             FROM conversations c
+             LEFT JOIN messages m ON m.conversation_id = c.id
+             GROUP BY c.id",
+            &[],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let derived: HashMap<i64, i64> = self
+            .conn
+            .query_map_collect(
+                "SELECT conversation_id, message_count FROM token_usage",
+                &[],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-    let result = (|| -> Result<bool> {
-        if schema_migration_is_applied(conn, 15)? {
-            conn.execute("COMMIT;")
-                .with_context(|| "committing already-applied v15 migration transaction")?;
-            return Ok(false);
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, message_count) in current_counts {
+            if derived.get(&conv_id) == Some(&message_count) {
+                continue;
+            }
+            let extras: Vec<serde_json::Value> = self.conn.query_map_collect(
+                "SELECT extra_json, extra_bin FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![conv_id],
+                |row: &FrankenRow| Ok(franken_read_message_extra_compat(row, 0, 1)),
+            )?;
+            let usage = crate::token_budget::conversation_usage(extras.iter());
+            let (prompt, completion) = match usage {
+                Some((prompt, completion)) => (Some(prompt), Some(completion)),
+                None => (None, None),
+            };
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO token_usage
+                     (conversation_id, prompt_tokens, completion_tokens,
+                      message_count, computed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                fparams![conv_id, prompt, completion, message_count, now_ms],
+            )?;
+            if let Some((prompt, completion)) = usage {
+                self.conn.execute_compat(
+                    "UPDATE conversations SET approx_tokens = ?1 WHERE id = ?2",
+                    fparams![prompt.saturating_add(completion), conv_id],
+                )?;
+            }
+            written += 1;
         }
+        Ok(written)
+    }
 
-        let started = Instant::now();
-        let conversation_columns = franken_table_column_names(conn, "conversations")
-            .with_context(|| "inspecting conversations columns before v15 migration")?;
-        if !conversation_columns.contains("last_message_idx") {
-            conn.execute("ALTER TABLE conversations ADD COLUMN last_message_idx INTEGER;")
+    /// Exact `(prompt, completion)` token counts for one conversation, if
+    /// its agent reported any. Pre-v29 databases (no table yet) report
+    /// `None` rather than erroring so read paths degrade gracefully.
+    pub fn conversation_token_usage(&self, conversation_id: i64) -> Result<Option<(i64, i64)>> {
+        let rows: Vec<(Option<i64>, Option<i64>)> = match self.conn.query_map_collect(
+            "SELECT prompt_tokens, completion_tokens FROM token_usage
+             WHERE conversation_id = ?1",
+            fparams![conversation_id],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        ) {
+            Ok(rows) => rows,
+            Err(_) => return Ok(None),
+        };
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|(prompt, completion)| Some((prompt?, completion?))))
+    }
+
+    /// Summed exact `(prompt, completion)` tokens across conversations that
+    /// started in `[start_ms, end_ms)` — the monthly budget query. Same
+    /// pre-v29 grace as [`Self::conversation_token_usage`].
+    pub fn token_usage_between(&self, start_ms: i64, end_ms: i64) -> Result<(i64, i64)> {
+        let rows: Vec<(i64, i64)> = self
+            .conn
+            .query_map_collect(
+                "SELECT COALESCE(SUM(t.prompt_tokens), 0),
+                        COALESCE(SUM(t.completion_tokens), 0)
+                 FROM token_usage t
+                 JOIN conversations c ON c.id = t.conversation_id
+                 WHERE c.started_at >= ?1 AND c.started_at < ?2",
+                fparams![start_ms, end_ms],
+                |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )
+            .unwrap_or_default();
+        Ok(rows.into_iter().next().unwrap_or((0, 0)))
+    }
+
+    /// Assign durable UUIDs (see [`derive_conversation_uuid`]) to
+    /// conversations that have none yet. The uuid is a pure function of
+    /// identity fields that survive rebuilds and merges, so this pass only
+    /// ever fills gaps — existing rows are never rewritten. Called at the
+    /// end of non-watch index runs, next to the other derived-data passes.
+    /// Returns the number of uuids written.
+    pub fn refresh_conversation_uuids(&self) -> Result<usize> {
+        let missing: Vec<(i64, String, Option<String>, String)> = self.conn.query_map_collect(
+            "SELECT c.id, COALESCE(a.slug, 'unknown'), c.external_id, c.source_path
+             FROM conversations c
+             LEFT JOIN agents a ON a.id = c.agent_id
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM conversation_uuids u WHERE u.conversation_id = c.id
+             )",
+            &[],
+            |row: &FrankenRow| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                ))
+            },
+        )?;
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut written = 0usize;
+        for (conv_id, agent_slug, external_id, source_path) in missing {
+            let first_message: Option<String> = self
+                .conn
+                .query_map_collect(
+                    "SELECT content FROM messages
+                     WHERE conversation_id = ?1 ORDER BY idx LIMIT 1",
+                    fparams![conv_id],
+                    |row: &FrankenRow| row.get_typed(0),
+                )?
+                .into_iter()
+                .next();
+            let uuid = derive_conversation_uuid(
+                &agent_slug,
+                external_id.as_deref().unwrap_or(&source_path),
+                first_message.as_deref().unwrap_or(""),
+            );
+            self.conn.execute_compat(
+                "INSERT OR REPLACE INTO conversation_uuids
+                     (conversation_id, uuid, computed_at)
+                 VALUES (?1, ?2, ?3)",
+                fparams![conv_id, uuid, now_ms],
+            )?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Durable UUID for one conversation, if assigned. Pre-v30 databases
+    /// (no table yet) report `None` rather than erroring so read paths
+    /// degrade gracefully.
+    pub fn conversation_uuid(&self, conversation_id: i64) -> Result<Option<String>> {
+        let rows: Vec<String> = match self.conn.query_map_collect(
+            "SELECT uuid FROM conversation_uuids WHERE conversation_id = ?1",
+            fparams![conversation_id],
+            |row: &FrankenRow| row.get_typed(0),
+        ) {
+            Ok(rows) => rows,
+            Err(_) => return Ok(None),
+        };
+        Ok(rows.into_iter().next())
+    }
+
+    /// Current integer rowid for a durable conversation UUID — the legacy-id
+    /// mapping direction external references resolve through. Same pre-v30
+    /// grace as [`Self::conversation_uuid`].
+    pub fn conversation_id_for_uuid(&self, uuid: &str) -> Result<Option<i64>> {
+        let rows: Vec<i64> = match self.conn.query_map_collect(
+            "SELECT conversation_id FROM conversation_uuids WHERE uuid = ?1
+             ORDER BY conversation_id LIMIT 1",
+            fparams![uuid],
+            |row: &FrankenRow| row.get_typed(0),
+        ) {
+            Ok(rows) => rows,
+            Err(_) => return Ok(None),
+        };
+        Ok(rows.into_iter().next())
+    }
+
+    /// Conversations whose connectors keep plan/brainstorming artifacts next
+    /// to the chat log (Gemini CLI and Antigravity write them into the same
+    /// per-project hash directory). Feeds the artifact-attachment pass in
+    /// `crate::indexer::plan_artifacts`; rows are
+    /// `(id, source_path, started_at, ended_at)`.
+    pub fn plan_artifact_targets(&self) -> Result<Vec<(i64, String, Option<i64>, Option<i64>)>> {
+        self.conn.query_map_collect(
+            "SELECT c.id, c.source_path, c.started_at, c.ended_at
+             FROM conversations c
+             JOIN agents a ON a.id = c.agent_id
+             WHERE a.slug IN ('gemini', 'antigravity')
+             ORDER BY c.id",
+            &[],
+            |row: &FrankenRow| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                ))
+            },
+        )
+    }
+
+    /// Attach one plan/brainstorming artifact to a conversation as a
+    /// synthetic `system` message (author `plan-artifact`), including the
+    /// FTS row so the plan text is searchable. Attach-once per artifact
+    /// path: re-runs skip already-attached artifacts (edited artifacts are
+    /// re-ingested by a full rebuild, like edited session files). Returns
+    /// whether a message was written.
+    pub fn attach_plan_artifact_message(
+        &self,
+        conversation_id: i64,
+        artifact_path: &str,
+        mtime_ms: i64,
+        content: &str,
+    ) -> Result<bool> {
+        let existing: Vec<String> = self.conn.query_map_collect(
+            "SELECT COALESCE(extra_json, '') FROM messages
+             WHERE conversation_id = ?1 AND author = 'plan-artifact'",
+            fparams![conversation_id],
+            |row: &FrankenRow| row.get_typed(0),
+        )?;
+        let already_attached = existing.iter().any(|extra| {
+            serde_json::from_str::<serde_json::Value>(extra)
+                .ok()
+                .and_then(|v| v["plan_artifact"].as_str().map(|p| p == artifact_path))
+                .unwrap_or(false)
+        });
+        if already_attached {
+            return Ok(false);
+        }
+
+        let next_idx: i64 = self
+            .conn
+            .query_map_collect(
+                "SELECT COALESCE(MAX(idx) + 1, 0) FROM messages WHERE conversation_id = ?1",
+                fparams![conversation_id],
+                |row: &FrankenRow| row.get_typed(0),
+            )?
+            .into_iter()
+            .next()
+            .unwrap_or(0);
+        let extra_json = serde_json::json!({
+            "plan_artifact": artifact_path,
+            "mtime_ms": mtime_ms,
+        })
+        .to_string();
+        self.conn.execute_compat(
+            "INSERT INTO messages (conversation_id, idx, role, author, created_at, content, extra_json)
+             VALUES (?1, ?2, 'system', 'plan-artifact', ?3, ?4, ?5)",
+            fparams![conversation_id, next_idx, mtime_ms, content, extra_json],
+        )?;
+        let message_id: i64 = self
+            .conn
+            .query_map_collect("SELECT last_insert_rowid()", &[], |row: &FrankenRow| {
+                row.get_typed(0)
+            })?
+            .into_iter()
+            .next()
+            .unwrap_or(0);
+
+        let fts_context: Option<(Option<String>, String, Option<String>, String)> = self
+            .conn
+            .query_map_collect(
+                "SELECT c.title, COALESCE(a.slug, 'unknown'), w.path, c.source_path
+                 FROM conversations c
+                 LEFT JOIN agents a ON a.id = c.agent_id
+                 LEFT JOIN workspaces w ON w.id = c.workspace_id
+                 WHERE c.id = ?1",
+                fparams![conversation_id],
+                |row: &FrankenRow| {
+                    Ok((
+                        row.get_typed(0)?,
+                        row.get_typed(1)?,
+                        row.get_typed(2)?,
+                        row.get_typed(3)?,
+                    ))
+                },
+            )?
+            .into_iter()
+            .next();
+        if let Some((title, agent, workspace, source_path)) = fts_context {
+            self.conn.execute_compat(
+                "INSERT INTO fts_messages
+                     (rowid, content, title, agent, workspace, source_path, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                fparams![
+                    message_id,
+                    content,
+                    title.unwrap_or_default(),
+                    agent,
+                    workspace.unwrap_or_default(),
+                    source_path,
+                    mtime_ms
+                ],
+            )?;
+        }
+        Ok(true)
+    }
+
+    /// Final `(role, content)` messages of the most recently started
+    /// conversation at `source_path`, newest first. Used by watch-mode
+    /// failure notifications to inspect how a just-reindexed session ended.
+    pub fn final_messages_for_source(
+        &self,
+        source_path: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, String)>> {
+        self.conn.query_map_collect(
+            "SELECT m.role, m.content
+             FROM messages m
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE c.id = (
+                 SELECT id FROM conversations
+                 WHERE source_path = ?1
+                 ORDER BY started_at DESC, id DESC
+                 LIMIT 1
+             )
+             ORDER BY m.idx DESC
+             LIMIT ?2",
+            fparams![source_path, limit as i64],
+            |row: &FrankenRow| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )
+    }
+
+    /// Keep `meta.schema_version` in sync for backward compatibility with `SqliteStorage`.
+    fn sync_meta_schema_version(&self, version: i64) -> Result<()> {
+        // The meta table is created by V1 migration. If it doesn't exist yet,
+        // there's nothing to sync.
+        if self.conn.query("SELECT key FROM meta LIMIT 1;").is_err() {
+            return Ok(());
+        }
+
+        // Only write if the version needs updating to avoid write lock contention
+        if let Ok(rows) = self
+            .conn
+            .query("SELECT value FROM meta WHERE key = 'schema_version';")
+            && let Some(row) = rows.first()
+            && let Ok(val) = row.get_typed::<String>(0)
+            && val == version.to_string()
+        {
+            return Ok(()); // Already up to date
+        }
+
+        self.conn
+            .execute_compat(
+                "INSERT OR REPLACE INTO meta(key, value) VALUES('schema_version', ?1);",
+                &[ParamValue::from(version.to_string())],
+            )
+            .with_context(|| "syncing meta schema_version")?;
+
+        Ok(())
+    }
+
+    /// Resolve the database file path for this connection.
+    pub fn database_path(&self) -> Result<PathBuf> {
+        Ok(self.db_path.clone())
+    }
+
+    pub(crate) fn ephemeral_writer_preflight_verified(&self) -> bool {
+        self.ephemeral_writer_preflight_verified
+            .load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn mark_ephemeral_writer_preflight_verified(&self) {
+        self.ephemeral_writer_preflight_verified
+            .store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn index_writer_checkpoint_pages(&self) -> Option<i64> {
+        let pages = self.index_writer_checkpoint_pages.load(Ordering::Relaxed);
+        (pages != UNSET_INDEX_WRITER_CHECKPOINT_PAGES).then_some(pages)
+    }
+
+    pub(crate) fn mark_index_writer_checkpoint_pages(&self, pages: i64) {
+        self.index_writer_checkpoint_pages
+            .store(pages, Ordering::Relaxed);
+    }
+
+    pub(crate) fn index_writer_busy_timeout_ms(&self) -> Option<u64> {
+        let timeout_ms = self.index_writer_busy_timeout_ms.load(Ordering::Relaxed);
+        (timeout_ms != UNSET_INDEX_WRITER_BUSY_TIMEOUT_MS).then_some(timeout_ms)
+    }
+
+    pub(crate) fn mark_index_writer_busy_timeout_ms(&self, timeout_ms: u64) {
+        self.index_writer_busy_timeout_ms
+            .store(timeout_ms, Ordering::Relaxed);
+    }
+
+    /// Open database with migration, backing up if schema is incompatible.
+    pub fn open_or_rebuild(path: &Path) -> std::result::Result<Self, MigrationError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if path.exists() {
+            let check_result = check_schema_compatibility(path);
+            match check_result {
+                Ok(SchemaCheck::Compatible) | Ok(SchemaCheck::NeedsMigration) => {
+                    // Continue with normal open
+                }
+                Ok(SchemaCheck::NeedsRebuild(reason)) => {
+                    let backup_path = create_backup(path)?;
+                    cleanup_old_backups(path, MAX_BACKUPS)?;
+                    remove_database_files(path)?;
+                    return Err(MigrationError::RebuildRequired {
+                        reason,
+                        backup_path,
+                    });
+                }
+                Err(err) if schema_check_error_requires_rebuild(&err) => {
+                    let backup_path = create_backup(path)?;
+                    cleanup_old_backups(path, MAX_BACKUPS)?;
+                    remove_database_files(path)?;
+                    return Err(MigrationError::RebuildRequired {
+                        reason: format!("Database appears corrupted: {err}"),
+                        backup_path,
+                    });
+                }
+                Err(err) => return Err(MigrationError::Database(err)),
+            }
+        }
+
+        let storage = Self::open(path).map_err(|e| MigrationError::Other(e.to_string()))?;
+        Ok(storage)
+    }
+}
+
+// -------------------------------------------------------------------------
+// Frankensqlite migration helpers
+// -------------------------------------------------------------------------
+
+/// Build the `MigrationRunner` for the frankensqlite migration path.
+///
+/// Uses a single combined migration (version 13) that creates the complete
+/// final schema in one step. This avoids the V5 `DROP TABLE conversations`
+/// operation which triggers a known frankensqlite limitation: autoindex entries
+/// in sqlite_master are not properly cleaned up during DROP TABLE, causing
+/// "sqlite_master entry not found" errors.
+///
+/// For existing databases transitioned from SqliteStorage, the transition
+/// function backfills `_schema_migrations`; post-V13 additive migrations then
+/// run normally.
+fn build_cass_migrations_before_tail_cache() -> MigrationRunner {
+    MigrationRunner::new()
+        .add(13, "full_schema_v13", MIGRATION_FRESH_SCHEMA)
+        .add(14, "fts_contentless", MIGRATION_V14)
+}
+
+fn build_cass_migrations_after_tail_cache() -> MigrationRunner {
+    MigrationRunner::new()
+        .add(16, "drop_redundant_message_conv_idx", MIGRATION_V16)
+        .add(17, "drop_message_created_idx", MIGRATION_V17)
+        .add(18, "conversation_tail_state_hot_table", MIGRATION_V18)
+        .add(19, "conversation_external_lookup", MIGRATION_V19)
+        .add(20, "conversation_external_tail_lookup", MIGRATION_V20)
+        .add(21, "timestamp_unit_normalization", MIGRATION_V21)
+        .add(22, "conversation_view_tracking", MIGRATION_V22)
+        .add(23, "boilerplate_content_registry", MIGRATION_V23)
+        .add(24, "conversation_integrity_seal", MIGRATION_V24)
+        .add(25, "message_source_provenance", MIGRATION_V25)
+        .add(26, "conversation_quality", MIGRATION_V26)
+        .add(27, "agent_runs", MIGRATION_V27)
+        .add(28, "conversation_facets", MIGRATION_V28)
+        .add(29, "token_usage", MIGRATION_V29)
+        .add(30, "conversation_uuids", MIGRATION_V30)
+}
+
+fn schema_migration_is_applied(conn: &FrankenConnection, version: i64) -> Result<bool> {
+    let rows = conn
+        .query_with_params(
+            "SELECT 1 FROM _schema_migrations WHERE version = ?1 LIMIT 1;",
+            &[SqliteValue::from(version)],
+        )
+        .with_context(|| format!("checking schema migration version {version}"))?;
+    Ok(!rows.is_empty())
+}
+
+fn apply_conversation_tail_state_cache_migration(conn: &FrankenConnection) -> Result<bool> {
+    conn.execute("BEGIN IMMEDIATE;")
+        .with_context(|| "starting v15 conversation tail-state migration transaction")?;
+
+    let result = (|| -> Result<bool> {
+        if schema_migration_is_applied(conn, 15)? {
+            conn.execute("COMMIT;")
+                .with_context(|| "committing already-applied v15 migration transaction")?;
+            return Ok(false);
+        }
+
+        let started = Instant::now();
+        let conversation_columns = franken_table_column_names(conn, "conversations")
+            .with_context(|| "inspecting conversations columns before v15 migration")?;
+        if !conversation_columns.contains("last_message_idx") {
+            conn.execute("ALTER TABLE conversations ADD COLUMN last_message_idx INTEGER;")
                 .with_context(|| "adding v15 conversations.last_message_idx column")?;
         }
         if !conversation_columns.contains("last_message_created_at") {
@@ -5628,7 +7116,7 @@ fn current_schema_repair_batches_for_missing_tables(
 }
 
 /// Migration name lookup for backfilling `_schema_migrations` during transition.
-const MIGRATION_NAMES: [(i64, &str); 20] = [
+const MIGRATION_NAMES: [(i64, &str); 23] = [
     (1, "core_tables"),
     (2, "fts_messages"),
     (3, "fts_messages_rebuild"),
@@ -5649,6 +7137,9 @@ const MIGRATION_NAMES: [(i64, &str); 20] = [
     (18, "conversation_tail_state_hot_table"),
     (19, "conversation_external_lookup"),
     (20, "conversation_external_tail_lookup"),
+    (21, "timestamp_unit_normalization"),
+    (22, "conversation_view_tracking"),
+    (23, "boilerplate_content_registry"),
 ];
 
 /// Transitions an existing database from `meta` table schema versioning to the
@@ -7200,6 +8691,72 @@ impl FrankenStorage {
         Ok(id)
     }
 
+    /// Relink a workspace whose directory moved or was renamed, pointing its
+    /// history at `new_path`.
+    ///
+    /// If `new_path` is not yet an indexed workspace this is a pure rename of
+    /// the existing row (the workspace id — and therefore every aggregate
+    /// keyed on it — is untouched). If both paths are indexed, the old
+    /// workspace's conversations move to the new workspace id and the old row
+    /// is deleted, all in one transaction. After a merge the analytics
+    /// rollups and FTS workspace text still reference the old id/path, so
+    /// callers should follow up with [`Self::rebuild_analytics`] and
+    /// [`Self::rebuild_fts`].
+    pub fn relink_workspace(
+        &self,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<WorkspaceRelinkOutcome> {
+        let mut tx = self.conn.transaction()?;
+
+        let old_id: Option<i64> = tx
+            .query_row_map(
+                "SELECT id FROM workspaces WHERE path = ?1 LIMIT 1",
+                fparams![old_path],
+                |row| row.get_typed(0),
+            )
+            .optional()?;
+        let Some(old_id) = old_id else {
+            return Err(anyhow!("no indexed workspace with path {old_path}"));
+        };
+        let new_id: Option<i64> = tx
+            .query_row_map(
+                "SELECT id FROM workspaces WHERE path = ?1 LIMIT 1",
+                fparams![new_path],
+                |row| row.get_typed(0),
+            )
+            .optional()?;
+
+        let outcome = match new_id {
+            None => {
+                tx.execute_compat(
+                    "UPDATE workspaces SET path = ?1 WHERE id = ?2",
+                    fparams![new_path, old_id],
+                )?;
+                WorkspaceRelinkOutcome {
+                    merged: false,
+                    conversations_moved: 0,
+                }
+            }
+            Some(new_id) => {
+                let moved = tx.execute_compat(
+                    "UPDATE conversations SET workspace_id = ?1 WHERE workspace_id = ?2",
+                    fparams![new_id, old_id],
+                )?;
+                tx.execute_compat("DELETE FROM workspaces WHERE id = ?1", fparams![old_id])?;
+                WorkspaceRelinkOutcome {
+                    merged: true,
+                    conversations_moved: moved,
+                }
+            }
+        };
+
+        tx.commit()?;
+        // Cached workspace ids may now point at a deleted or re-pathed row.
+        self.ensured_workspaces.lock().clear();
+        Ok(outcome)
+    }
+
     /// Get current time as milliseconds since epoch.
     pub fn now_millis() -> i64 {
         SystemTime::now()
@@ -7613,6 +9170,7 @@ impl FrankenStorage {
                 messages_matched: 0,
                 conversations_deleted: 0,
                 sample_source_paths: Vec::new(),
+                trash_operation_id: None,
             });
         }
 
@@ -7636,9 +9194,15 @@ impl FrankenStorage {
                 messages_matched: messages_matched.max(0) as usize,
                 conversations_deleted: 0,
                 sample_source_paths: sample_paths,
+                trash_operation_id: None,
             });
         }
 
+        // Snapshot into the recycle bin first so `cass undo` / `cass trash
+        // restore` can bring the rows back within the retention window.
+        let trash_operation_id =
+            self.trash_snapshot_conversations("forget", trimmed, &matched_ids)?;
+
         let mut tx = self.conn.transaction()?;
         // Non-cascading external-lookup tables first (mirrors agent purge).
         tx.execute_compat(
@@ -7667,9 +9231,371 @@ impl FrankenStorage {
             messages_matched: messages_matched.max(0) as usize,
             conversations_deleted: matched_ids.len(),
             sample_source_paths: sample_paths,
+            trash_operation_id,
+        })
+    }
+
+    /// Create the recycle-bin shadow tables if they do not exist yet. Lazy so
+    /// `cass trash` works against databases created before the feature and no
+    /// schema migration is needed.
+    fn ensure_trash_schema(&self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS trash_operations (
+                     operation_id TEXT PRIMARY KEY,
+                     kind TEXT NOT NULL,
+                     detail TEXT,
+                     created_at INTEGER NOT NULL,
+                     conversation_count INTEGER NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS trash_conversations (
+                     id INTEGER PRIMARY KEY,
+                     operation_id TEXT NOT NULL
+                         REFERENCES trash_operations(operation_id) ON DELETE CASCADE,
+                     original_conversation_id INTEGER,
+                     agent_slug TEXT NOT NULL,
+                     source_path TEXT,
+                     title TEXT,
+                     payload TEXT NOT NULL
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_trash_conversations_op
+                     ON trash_conversations(operation_id);",
+            )
+            .context("creating trash shadow tables")?;
+        Ok(())
+    }
+
+    /// Load one conversation tree (row + agent + workspace + messages +
+    /// snippets) into the portable model types, for trash snapshots.
+    fn load_conversation_tree_for_trash(
+        &self,
+        conversation_id: i64,
+    ) -> Result<(Agent, Conversation)> {
+        let row = self
+            .conn
+            .query_row_map(
+                "SELECT c.external_id, c.title, c.source_path, c.started_at, c.ended_at,
+                        c.approx_tokens, c.metadata_json, c.source_id, c.origin_host,
+                        COALESCE(a.slug, 'unknown'), COALESCE(a.name, a.slug, 'unknown'),
+                        a.version, COALESCE(a.kind, 'cli'), w.path
+                 FROM conversations c
+                 LEFT JOIN agents a ON a.id = c.agent_id
+                 LEFT JOIN workspaces w ON w.id = c.workspace_id
+                 WHERE c.id = ?1",
+                fparams![conversation_id],
+                |row| {
+                    Ok((
+                        row.get_typed::<Option<String>>(0)?,
+                        row.get_typed::<Option<String>>(1)?,
+                        row.get_typed::<String>(2)?,
+                        row.get_typed::<Option<i64>>(3)?,
+                        row.get_typed::<Option<i64>>(4)?,
+                        row.get_typed::<Option<i64>>(5)?,
+                        row.get_typed::<Option<String>>(6)?,
+                        row.get_typed::<Option<String>>(7)?,
+                        row.get_typed::<Option<String>>(8)?,
+                        row.get_typed::<String>(9)?,
+                        row.get_typed::<String>(10)?,
+                        row.get_typed::<Option<String>>(11)?,
+                        row.get_typed::<String>(12)?,
+                        row.get_typed::<Option<String>>(13)?,
+                    ))
+                },
+            )
+            .with_context(|| format!("loading conversation {conversation_id} for trash"))?;
+        let (
+            external_id,
+            title,
+            source_path,
+            started_at,
+            ended_at,
+            approx_tokens,
+            metadata_json,
+            raw_source_id,
+            raw_origin_host,
+            agent_slug,
+            agent_name,
+            agent_version,
+            agent_kind,
+            workspace_path,
+        ) = row;
+
+        let mut messages = self.fetch_messages(conversation_id)?;
+        // Re-attach snippets: fetch_messages leaves them empty.
+        let snippet_rows: Vec<(i64, Snippet)> = self.conn.query_map_collect(
+            "SELECT m.idx, s.file_path, s.start_line, s.end_line, s.language, s.snippet_text
+             FROM snippets s
+             JOIN messages m ON s.message_id = m.id
+             WHERE m.conversation_id = ?1
+             ORDER BY m.idx",
+            fparams![conversation_id],
+            |row| {
+                let file_path: Option<String> = row.get_typed(1)?;
+                Ok((
+                    row.get_typed::<i64>(0)?,
+                    Snippet {
+                        id: None,
+                        file_path: file_path.map(PathBuf::from),
+                        start_line: row.get_typed(2)?,
+                        end_line: row.get_typed(3)?,
+                        language: row.get_typed(4)?,
+                        snippet_text: row.get_typed(5)?,
+                    },
+                ))
+            },
+        )?;
+        for (idx, snippet) in snippet_rows {
+            if let Some(message) = messages.iter_mut().find(|message| message.idx == idx) {
+                message.snippets.push(snippet);
+            }
+        }
+        for message in &mut messages {
+            message.id = None;
+        }
+
+        let (source_id, _, origin_host) = normalized_storage_source_parts(
+            raw_source_id.as_deref(),
+            None,
+            raw_origin_host.as_deref(),
+        );
+        let agent = Agent {
+            id: None,
+            slug: agent_slug.clone(),
+            name: agent_name,
+            version: agent_version,
+            kind: match agent_kind.as_str() {
+                "vscode" | "vs_code" => AgentKind::VsCode,
+                "hybrid" => AgentKind::Hybrid,
+                _ => AgentKind::Cli,
+            },
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug,
+            workspace: workspace_path.map(PathBuf::from),
+            external_id,
+            title,
+            source_path: PathBuf::from(source_path),
+            started_at,
+            ended_at,
+            approx_tokens,
+            metadata_json: metadata_json
+                .as_deref()
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .unwrap_or(serde_json::Value::Null),
+            messages,
+            source_id,
+            origin_host,
+        };
+        Ok((agent, conversation))
+    }
+
+    /// Snapshot `conversation_ids` into the recycle bin under a fresh
+    /// operation id, before the caller deletes them. Returns `None` when
+    /// there is nothing to snapshot. Expired operations are purged
+    /// opportunistically on the way in.
+    pub fn trash_snapshot_conversations(
+        &self,
+        kind: &str,
+        detail: &str,
+        conversation_ids: &[i64],
+    ) -> Result<Option<String>> {
+        if conversation_ids.is_empty() {
+            return Ok(None);
+        }
+        self.ensure_trash_schema()?;
+        let now = Self::now_millis();
+        if let Err(err) = self.trash_purge_expired(now) {
+            tracing::warn!(error = %err, "trash: failed to purge expired operations");
+        }
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let operation_id = format!("trash-{now}-{nanos:08x}");
+
+        let mut tx = self.conn.transaction()?;
+        tx.execute_compat(
+            "INSERT INTO trash_operations(operation_id, kind, detail, created_at, conversation_count)
+             VALUES(?1, ?2, ?3, ?4, ?5)",
+            fparams![
+                operation_id.as_str(),
+                kind,
+                detail,
+                now,
+                conversation_ids.len() as i64
+            ],
+        )?;
+        tx.commit()?;
+        for &conversation_id in conversation_ids {
+            let (agent, conversation) = self.load_conversation_tree_for_trash(conversation_id)?;
+            let payload = serde_json::to_string(&serde_json::json!({
+                "schema_version": 1,
+                "agent": agent,
+                "conversation": conversation,
+            }))?;
+            self.conn.execute_compat(
+                "INSERT INTO trash_conversations(
+                     operation_id, original_conversation_id, agent_slug,
+                     source_path, title, payload)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6)",
+                fparams![
+                    operation_id.as_str(),
+                    conversation_id,
+                    conversation.agent_slug.as_str(),
+                    conversation.source_path.to_string_lossy().as_ref(),
+                    conversation.title.as_deref(),
+                    payload.as_str()
+                ],
+            )?;
+        }
+        Ok(Some(operation_id))
+    }
+
+    /// List recycle-bin operations, newest first.
+    pub fn trash_list_operations(&self) -> Result<Vec<TrashOperation>> {
+        self.ensure_trash_schema()?;
+        let retention_ms = trash_retention_ms();
+        self.conn
+            .query_map_collect(
+                "SELECT operation_id, kind, detail, created_at, conversation_count
+                 FROM trash_operations ORDER BY created_at DESC",
+                fparams![],
+                |row| {
+                    let created_at: i64 = row.get_typed(3)?;
+                    Ok(TrashOperation {
+                        operation_id: row.get_typed(0)?,
+                        kind: row.get_typed(1)?,
+                        detail: row.get_typed(2)?,
+                        created_at,
+                        conversation_count: row.get_typed::<i64>(4)?.max(0) as usize,
+                        expires_at: created_at.saturating_add(retention_ms),
+                    })
+                },
+            )
+            .context("listing trash operations")
+    }
+
+    /// Restore every conversation captured by `operation_id` through the
+    /// canonical insert path, then drop the operation from the bin.
+    /// Conversations whose payload no longer deserializes are skipped and
+    /// counted rather than failing the whole restore.
+    pub fn trash_restore_operation(&self, operation_id: &str) -> Result<TrashRestoreResult> {
+        self.ensure_trash_schema()?;
+        let payloads: Vec<String> = self.conn.query_map_collect(
+            "SELECT payload FROM trash_conversations WHERE operation_id = ?1 ORDER BY id",
+            fparams![operation_id],
+            |row| row.get_typed(0),
+        )?;
+        if payloads.is_empty() {
+            return Err(anyhow!("no trash operation with id {operation_id}"));
+        }
+
+        let mut restored = 0usize;
+        let mut skipped = 0usize;
+        for payload in payloads {
+            let parsed: serde_json::Value = match serde_json::from_str(&payload) {
+                Ok(value) => value,
+                Err(err) => {
+                    tracing::warn!(error = %err, operation_id, "trash: unreadable payload, skipping");
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let agent: Agent = match serde_json::from_value(parsed["agent"].clone()) {
+                Ok(agent) => agent,
+                Err(err) => {
+                    tracing::warn!(error = %err, operation_id, "trash: unreadable agent, skipping");
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let conversation: Conversation =
+                match serde_json::from_value(parsed["conversation"].clone()) {
+                    Ok(conversation) => conversation,
+                    Err(err) => {
+                        tracing::warn!(
+                            error = %err,
+                            operation_id,
+                            "trash: unreadable conversation, skipping"
+                        );
+                        skipped += 1;
+                        continue;
+                    }
+                };
+            let agent_id = self.ensure_agent(&agent)?;
+            let workspace_id = match &conversation.workspace {
+                Some(path) => Some(self.ensure_workspace(path, None)?),
+                None => None,
+            };
+            self.insert_conversation_tree(agent_id, workspace_id, &conversation)?;
+            restored += 1;
+        }
+
+        self.conn.execute_compat(
+            "DELETE FROM trash_operations WHERE operation_id = ?1",
+            fparams![operation_id],
+        )?;
+        self.conn.execute_compat(
+            "DELETE FROM trash_conversations WHERE operation_id = ?1",
+            fparams![operation_id],
+        )?;
+        Ok(TrashRestoreResult {
+            operation_id: operation_id.to_string(),
+            conversations_restored: restored,
+            conversations_skipped: skipped,
         })
     }
 
+    /// Delete recycle-bin operations older than `older_than_ms` (all of them
+    /// when `None`). Returns how many operations were dropped.
+    pub fn trash_empty(&self, older_than_ms: Option<i64>) -> Result<usize> {
+        self.ensure_trash_schema()?;
+        let cutoff = match older_than_ms {
+            Some(age) => Self::now_millis().saturating_sub(age),
+            None => i64::MAX,
+        };
+        let ids: Vec<String> = self.conn.query_map_collect(
+            "SELECT operation_id FROM trash_operations WHERE created_at < ?1",
+            fparams![cutoff],
+            |row| row.get_typed(0),
+        )?;
+        for operation_id in &ids {
+            self.conn.execute_compat(
+                "DELETE FROM trash_conversations WHERE operation_id = ?1",
+                fparams![operation_id.as_str()],
+            )?;
+            self.conn.execute_compat(
+                "DELETE FROM trash_operations WHERE operation_id = ?1",
+                fparams![operation_id.as_str()],
+            )?;
+        }
+        Ok(ids.len())
+    }
+
+    /// Drop operations past the retention window (`CASS_TRASH_RETENTION_DAYS`,
+    /// default 30).
+    fn trash_purge_expired(&self, now_ms: i64) -> Result<usize> {
+        let cutoff = now_ms.saturating_sub(trash_retention_ms());
+        let ids: Vec<String> = self.conn.query_map_collect(
+            "SELECT operation_id FROM trash_operations WHERE created_at < ?1",
+            fparams![cutoff],
+            |row| row.get_typed(0),
+        )?;
+        for operation_id in &ids {
+            self.conn.execute_compat(
+                "DELETE FROM trash_conversations WHERE operation_id = ?1",
+                fparams![operation_id.as_str()],
+            )?;
+            self.conn.execute_compat(
+                "DELETE FROM trash_operations WHERE operation_id = ?1",
+                fparams![operation_id.as_str()],
+            )?;
+        }
+        Ok(ids.len())
+    }
+
     /// `coding_agent_session_search-uhhxy` (gh #302 ask #2): collapse
     /// PRE-EXISTING duplicate conversation rows created before the
     /// external_id `projects/` canonicalization fix. The watcher and
@@ -7814,6 +9740,38 @@ impl FrankenStorage {
             .with_context(|| "listing workspaces")
     }
 
+    /// List indexed workspace paths ordered by most recent conversation
+    /// activity (latest `ended_at`/`started_at` first; workspaces with no
+    /// conversations sort last, alphabetically). This feeds the `workspace:`
+    /// completion surfaces — the TUI filter prompt and the
+    /// `complete-workspaces` CLI helper — where "what did I touch lately" is
+    /// the ranking that matters, not lexical order.
+    pub fn list_workspace_paths_by_recency(&self) -> Result<Vec<String>> {
+        // Correlated subquery instead of a JOIN + GROUP BY, and the ordering
+        // in Rust instead of ORDER BY over the computed column — both to stay
+        // off frankensqlite's materialization fallback (see the note in
+        // `list_conversations`). The workspaces table is tiny (~30 rows), so
+        // the per-row subquery and the client-side sort are both cheap.
+        let mut rows: Vec<(String, Option<i64>)> = self
+            .conn
+            .query_map_collect(
+                r"SELECT w.path,
+                         (SELECT MAX(COALESCE(c.ended_at, c.started_at))
+                          FROM conversations c
+                          WHERE c.workspace_id = w.id)
+                  FROM workspaces w",
+                fparams![],
+                |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )
+            .with_context(|| "listing workspaces by recency")?;
+        // `None < Some(_)`, so descending timestamp order naturally pushes
+        // conversation-less workspaces to the end.
+        rows.sort_by(|(a_path, a_ts), (b_path, b_ts)| {
+            b_ts.cmp(a_ts).then_with(|| a_path.cmp(b_path))
+        });
+        Ok(rows.into_iter().map(|(path, _)| path).collect())
+    }
+
     /// List conversations with pagination.
     pub fn list_conversations(&self, limit: i64, offset: i64) -> Result<Vec<Conversation>> {
         // Avoid the multi-table JOIN with LIMIT/OFFSET that triggers
@@ -10852,6 +12810,41 @@ impl FrankenStorage {
         self.rebuild_fts_via_frankensqlite().map(|_| ())
     }
 
+    /// Run an FTS5 MATCH query against this database's `fts_messages`
+    /// shadow, best-ranked hits first. Built for `cass scan`'s ephemeral
+    /// in-memory index, where the shadow was just populated by
+    /// [`Self::rebuild_fts_via_frankensqlite`]; `match_expr` must already be
+    /// valid FTS5 MATCH syntax, not raw user input.
+    pub fn search_messages_fts(
+        &self,
+        match_expr: &str,
+        limit: usize,
+    ) -> Result<Vec<EphemeralSearchHit>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        Ok(self.conn.query_map_collect(
+            "SELECT COALESCE(a.slug, 'unknown'), c.title, c.source_path,
+                    m.idx, m.role, m.content
+             FROM fts_messages
+             JOIN messages m ON m.id = fts_messages.rowid
+             JOIN conversations c ON c.id = m.conversation_id
+             LEFT JOIN agents a ON a.id = c.agent_id
+             WHERE fts_messages MATCH ?1
+             ORDER BY rank
+             LIMIT ?2",
+            fparams![match_expr, limit],
+            |row: &FrankenRow| {
+                Ok(EphemeralSearchHit {
+                    agent: row.get_typed(0)?,
+                    title: row.get_typed(1)?,
+                    source_path: row.get_typed(2)?,
+                    message_idx: row.get_typed(3)?,
+                    role: row.get_typed(4)?,
+                    content: row.get_typed(5)?,
+                })
+            },
+        )?)
+    }
+
     fn stream_fts_rows_via_frankensqlite(&self, missing_only: bool) -> Result<usize> {
         let batch_size = fts_rebuild_batch_size().max(1);
         let batch_limit = i64::try_from(batch_size).unwrap_or(i64::MAX);
@@ -12037,14 +14030,88 @@ fn is_bootstrap_local_source(source: &Source) -> bool {
         && source.updated_at.is_none()
 }
 
+/// Whether a raw timestamp would change under unit normalization (i.e. it is
+/// not already in plausible milliseconds).
+fn timestamp_needs_unit_normalization(raw: Option<i64>) -> bool {
+    raw.is_some_and(|value| crate::model::types::normalize_unix_millis(value) != value)
+}
+
+/// True when any timestamp on the conversation (or its messages) is in the
+/// wrong unit. Split out so the common all-ms case stays on the borrow path.
+fn conversation_has_non_millis_timestamps(conv: &Conversation) -> bool {
+    timestamp_needs_unit_normalization(conv.started_at)
+        || timestamp_needs_unit_normalization(conv.ended_at)
+        || conv
+            .messages
+            .iter()
+            .any(|msg| timestamp_needs_unit_normalization(msg.created_at))
+}
+
 fn normalized_conversation_for_storage<'a>(conv: &'a Conversation) -> Cow<'a, Conversation> {
     let normalized_source = normalized_source_for_conversation(conv);
-    if normalized_source.id == conv.source_id && normalized_source.host_label == conv.origin_host {
+    let needs_timestamp_fix = conversation_has_non_millis_timestamps(conv);
+    // Untitled (or boilerplate-titled) conversations get a heuristic title so
+    // search results never surface `<environment_context>` as the headline.
+    let derived_title = crate::title_heuristics::derived_title_for_storage(conv);
+    // Connectors that lack workspace metadata leave it NULL even though the
+    // session content betrays it; infer a consensus workspace and mark it as
+    // inferred in metadata_json so filters can tell it apart.
+    let inferred_workspace = if conv.workspace.is_none() {
+        crate::workspace_inference::infer_workspace(
+            conv.messages.iter().map(|m| m.content.as_str()),
+        )
+    } else {
+        None
+    };
+    if normalized_source.id == conv.source_id
+        && normalized_source.host_label == conv.origin_host
+        && !needs_timestamp_fix
+        && derived_title.is_none()
+        && inferred_workspace.is_none()
+    {
         Cow::Borrowed(conv)
     } else {
         let mut normalized = conv.clone();
         normalized.source_id = normalized_source.id;
         normalized.origin_host = normalized_source.host_label;
+        if derived_title.is_some() {
+            normalized.title = derived_title;
+        }
+        if let Some(inference) = inferred_workspace {
+            normalized.workspace = Some(PathBuf::from(&inference.path));
+            let record = serde_json::json!({
+                "confidence": inference.confidence,
+                "signals": inference.signals,
+            });
+            match &mut normalized.metadata_json {
+                serde_json::Value::Object(map) => {
+                    map.insert(crate::workspace_inference::METADATA_KEY.to_string(), record);
+                }
+                value @ serde_json::Value::Null => {
+                    *value =
+                        serde_json::json!({ crate::workspace_inference::METADATA_KEY: record });
+                }
+                // Non-object metadata (legacy rows): keep the inferred
+                // workspace but leave the blob alone.
+                _ => {}
+            }
+        }
+        if needs_timestamp_fix {
+            // Everything past this point assumes unix milliseconds; connectors
+            // occasionally hand over seconds (or microseconds) and the
+            // ms-vs-seconds mismatch then leaks into timeline filters.
+            normalized.started_at = normalized
+                .started_at
+                .map(crate::model::types::normalize_unix_millis);
+            normalized.ended_at = normalized
+                .ended_at
+                .map(crate::model::types::normalize_unix_millis);
+            for msg in &mut normalized.messages {
+                msg.created_at = msg
+                    .created_at
+                    .map(crate::model::types::normalize_unix_millis);
+            }
+        }
         Cow::Owned(normalized)
     }
 }
@@ -13131,26 +15198,68 @@ fn franken_batch_insert_new_messages_with_profile_batch_size(
     Ok(inserted_ids)
 }
 
+/// Snippet rows per multi-row INSERT. Snippet-heavy messages are rare, so
+/// one chunk covers nearly every message; the cap only bounds the cached
+/// SQL table.
+const SNIPPET_INSERT_BATCH_SIZE: usize = 50;
+
+/// Cached multi-row snippet INSERT statements, one per row count — same
+/// prepared-SQL cache scheme as [`message_insert_batch_sql`], so repeated
+/// batches reuse identical statement text instead of rebuilding it.
+fn snippet_insert_batch_sql(row_count: usize) -> &'static str {
+    static SNIPPET_INSERT_BATCH_SQL: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
+
+    let cached_sql = SNIPPET_INSERT_BATCH_SQL.get_or_init(|| {
+        let mut sql_by_row_count = Vec::with_capacity(SNIPPET_INSERT_BATCH_SIZE + 1);
+        sql_by_row_count.push(String::new());
+        for row_count in 1..=SNIPPET_INSERT_BATCH_SIZE {
+            let placeholders = (0..row_count)
+                .map(|idx| {
+                    let base = idx * 6;
+                    format!(
+                        "(?{},?{},?{},?{},?{},?{})",
+                        base + 1,
+                        base + 2,
+                        base + 3,
+                        base + 4,
+                        base + 5,
+                        base + 6
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            sql_by_row_count.push(format!(
+                "INSERT INTO snippets(message_id, file_path, start_line, end_line, language, snippet_text) VALUES {placeholders}"
+            ));
+        }
+        sql_by_row_count
+    });
+
+    cached_sql
+        .get(row_count)
+        .map(String::as_str)
+        .expect("snippet insert batch size must be covered by the cached SQL table")
+}
+
 /// Insert snippets within a frankensqlite transaction.
 fn franken_insert_snippets(
     tx: &FrankenTransaction<'_>,
     message_id: i64,
     snippets: &[Snippet],
 ) -> Result<()> {
-    for snip in snippets {
-        let file_path_str = snip.file_path.as_ref().map(path_to_string);
-        tx.execute_compat(
-            "INSERT INTO snippets(message_id, file_path, start_line, end_line, language, snippet_text)
-             VALUES(?1,?2,?3,?4,?5,?6)",
-            fparams![
-                message_id,
-                file_path_str.as_deref(),
-                snip.start_line,
-                snip.end_line,
-                snip.language.as_deref(),
-                snip.snippet_text.as_deref()
-            ],
-        )?;
+    for chunk in snippets.chunks(SNIPPET_INSERT_BATCH_SIZE) {
+        let sql = snippet_insert_batch_sql(chunk.len());
+        let mut param_values: Vec<SqliteValue> = Vec::with_capacity(chunk.len() * 6);
+        for snip in chunk {
+            let file_path_str = snip.file_path.as_ref().map(path_to_string);
+            param_values.push(SqliteValue::from(message_id));
+            param_values.push(SqliteValue::from(file_path_str.as_deref()));
+            param_values.push(SqliteValue::from(snip.start_line));
+            param_values.push(SqliteValue::from(snip.end_line));
+            param_values.push(SqliteValue::from(snip.language.as_deref()));
+            param_values.push(SqliteValue::from(snip.snippet_text.as_deref()));
+        }
+        tx.execute_with_params(sql, &param_values)?;
     }
     Ok(())
 }
@@ -15946,6 +18055,15 @@ pub struct DailyCount {
     pub chars: i64,
 }
 
+/// Result of relinking a workspace path via [`FrankenStorage::relink_workspace`].
+#[derive(Debug, Clone, Copy)]
+pub struct WorkspaceRelinkOutcome {
+    /// True when the new path already had a workspace row and the two merged.
+    pub merged: bool,
+    /// Conversations re-pointed at the surviving workspace (0 for a pure rename).
+    pub conversations_moved: usize,
+}
+
 /// Result of an analytics rebuild operation.
 #[derive(Debug, Clone)]
 pub struct AnalyticsRebuildResult {
@@ -15983,6 +18101,45 @@ pub struct ForgetConversationsResult {
     pub conversations_deleted: usize,
     /// Bounded (<= 20) sample of matched source paths, for operator review.
     pub sample_source_paths: Vec<String>,
+    /// Recycle-bin operation holding the deleted rows (`cass undo <id>`),
+    /// when the deletion was applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_operation_id: Option<String>,
+}
+
+/// Retention window for recycle-bin operations, from
+/// `CASS_TRASH_RETENTION_DAYS` (default 30 days).
+pub fn trash_retention_ms() -> i64 {
+    const DEFAULT_DAYS: i64 = 30;
+    let days = dotenvy::var("CASS_TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_DAYS);
+    days.saturating_mul(86_400_000)
+}
+
+/// One recycle-bin entry: a destructive operation whose deleted
+/// conversations are still restorable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashOperation {
+    pub operation_id: String,
+    /// Which destructive command produced it (e.g. "forget").
+    pub kind: String,
+    /// Operation argument for operator review (e.g. the forget glob).
+    pub detail: Option<String>,
+    pub created_at: i64,
+    pub conversation_count: usize,
+    /// When the retention sweep becomes eligible to drop this operation.
+    pub expires_at: i64,
+}
+
+/// Outcome of restoring one recycle-bin operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashRestoreResult {
+    pub operation_id: String,
+    pub conversations_restored: usize,
+    pub conversations_skipped: usize,
 }
 
 /// A single PRE-EXISTING duplicate conversation pair detected by
@@ -16747,6 +18904,11 @@ mod tests {
         assert!(is_user_data_file(Path::new("bookmarks.db")));
     }
 
+    #[test]
+    fn is_user_data_file_detects_view_state() {
+        assert!(is_user_data_file(Path::new("/data/view_state.db")));
+    }
+
     #[test]
     fn is_user_data_file_detects_tui_state() {
         assert!(is_user_data_file(Path::new("/data/tui_state.json")));
@@ -16902,6 +19064,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_normalizes_second_and_microsecond_timestamps_to_millis() {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+        use std::path::PathBuf;
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("agent_search.db");
+        let storage = SqliteStorage::open(&db_path).unwrap();
+
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: None,
+            external_id: Some("conv-units".into()),
+            title: None,
+            source_path: PathBuf::from("/tmp/conv-units.jsonl"),
+            // Seconds: a connector that never multiplied by 1000.
+            started_at: Some(1_700_000_000),
+            ended_at: Some(1_700_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: None,
+                    // Microseconds: some exports record epoch micros.
+                    created_at: Some(1_700_000_000_000_000),
+                    content: "first".into(),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                },
+                Message {
+                    id: None,
+                    idx: 1,
+                    role: MessageRole::Agent,
+                    author: None,
+                    // Already milliseconds: must pass through untouched.
+                    created_at: Some(1_700_000_050_000),
+                    content: "second".into(),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                },
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .unwrap();
+
+        let (started_at, ended_at): (i64, i64) = storage
+            .conn
+            .query_row_map(
+                "SELECT started_at, ended_at FROM conversations WHERE external_id = ?1",
+                fparams!["conv-units"],
+                |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )
+            .unwrap();
+        assert_eq!(started_at, 1_700_000_000_000);
+        assert_eq!(ended_at, 1_700_000_100_000);
+
+        let created: Vec<i64> = storage
+            .conn
+            .query_map_collect("SELECT created_at FROM messages ORDER BY idx", &[], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(created, vec![1_700_000_000_000, 1_700_000_050_000]);
+    }
+
     /// `coding_agent_session_search-uhhxy` (gh #302 ask #2): the dedup
     /// cleanup must detect a `projects/<rel>` twin of a bare `<rel>`
     /// conversation sharing one source_path, report it on dry-run without
@@ -17850,6 +20092,89 @@ mod tests {
         assert!(storage.schema_version().is_ok());
     }
 
+    fn force_schema_version(db_path: &Path, version: i64) {
+        let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
+        conn.execute(&format!(
+            "UPDATE meta SET value = '{version}' WHERE key = 'schema_version'"
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn read_compatibility_is_native_for_current_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("current.db");
+        SqliteStorage::open(&db_path).unwrap().close().unwrap();
+
+        assert!(matches!(
+            check_read_compatibility(&db_path),
+            ReadCompatibility::Native
+        ));
+    }
+
+    #[test]
+    fn read_compatibility_shims_adjacent_versions() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("adjacent.db");
+        SqliteStorage::open(&db_path).unwrap().close().unwrap();
+
+        for delta in [1, -1] {
+            let version = CURRENT_SCHEMA_VERSION + delta;
+            force_schema_version(&db_path, version);
+            match check_read_compatibility(&db_path) {
+                ReadCompatibility::Shimmed { version: v } => assert_eq!(v, version),
+                other => panic!("expected shim for v{version}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn read_compatibility_guides_upgrade_for_far_newer_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("newer.db");
+        SqliteStorage::open(&db_path).unwrap().close().unwrap();
+        force_schema_version(&db_path, CURRENT_SCHEMA_VERSION + 5);
+
+        match check_read_compatibility(&db_path) {
+            ReadCompatibility::Unsupported { version, guidance } => {
+                assert_eq!(version, Some(CURRENT_SCHEMA_VERSION + 5));
+                assert!(guidance.contains("newer cass"), "guidance: {guidance}");
+                assert!(guidance.contains("self-update"), "guidance: {guidance}");
+            }
+            other => panic!("expected unsupported, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn read_compatibility_guides_migration_for_far_older_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("older.db");
+        SqliteStorage::open(&db_path).unwrap().close().unwrap();
+        force_schema_version(&db_path, CURRENT_SCHEMA_VERSION - 5);
+
+        match check_read_compatibility(&db_path) {
+            ReadCompatibility::Unsupported { version, guidance } => {
+                assert_eq!(version, Some(CURRENT_SCHEMA_VERSION - 5));
+                assert!(guidance.contains("index --full"), "guidance: {guidance}");
+            }
+            other => panic!("expected unsupported, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn read_compatibility_folds_open_errors_into_guidance() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("missing.db");
+
+        match check_read_compatibility(&db_path) {
+            ReadCompatibility::Unsupported { version, guidance } => {
+                assert_eq!(version, None);
+                assert!(guidance.contains("cass doctor"), "guidance: {guidance}");
+            }
+            other => panic!("expected unsupported, got {other:?}"),
+        }
+    }
+
     #[test]
     fn reopen_existing_current_schema_is_idempotent() {
         let dir = TempDir::new().unwrap();
@@ -24489,39 +26814,197 @@ mod tests {
         let db_path = dir.path().join("test.db");
         let storage = SqliteStorage::open(&db_path).unwrap();
 
-        let path = Path::new("/home/user/myproject");
-        let id1 = storage.ensure_workspace(path, Some("Before")).unwrap();
-        let id2 = storage.ensure_workspace(path, Some("After")).unwrap();
+        let path = Path::new("/home/user/myproject");
+        let id1 = storage.ensure_workspace(path, Some("Before")).unwrap();
+        let id2 = storage.ensure_workspace(path, Some("After")).unwrap();
+
+        let display_name: Option<String> = storage
+            .conn
+            .query_row_map(
+                "SELECT display_name FROM workspaces WHERE path = ?1",
+                fparams![path.to_string_lossy().as_ref()],
+                |row| row.get_typed(0),
+            )
+            .unwrap();
+
+        assert_eq!(id1, id2);
+        assert_eq!(display_name.as_deref(), Some("After"));
+    }
+
+    #[test]
+    fn list_workspaces_returns_inserted() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+        let storage = SqliteStorage::open(&db_path).unwrap();
+
+        storage
+            .ensure_workspace(Path::new("/test/workspace"), Some("Test WS"))
+            .unwrap();
+
+        let workspaces = storage.list_workspaces().unwrap();
+        assert!(
+            workspaces
+                .iter()
+                .any(|w| w.path.to_str() == Some("/test/workspace"))
+        );
+    }
+
+    #[test]
+    fn list_workspace_paths_by_recency_orders_latest_activity_first() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+        let storage = SqliteStorage::open(&db_path).unwrap();
+
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+        let stale_id = storage
+            .ensure_workspace(Path::new("/home/u/dev/stale"), None)
+            .unwrap();
+        let fresh_id = storage
+            .ensure_workspace(Path::new("/home/u/dev/fresh"), None)
+            .unwrap();
+        storage
+            .ensure_workspace(Path::new("/home/u/dev/empty"), None)
+            .unwrap();
+
+        // `fresh` has the most recent activity (via `ended_at`), `stale` only
+        // an old `started_at`, and `empty` no conversations at all.
+        storage
+            .conn
+            .execute_compat(
+                "INSERT INTO conversations (
+                    agent_id, workspace_id, source_id, source_path, started_at, ended_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                fparams![
+                    agent_id,
+                    stale_id,
+                    LOCAL_SOURCE_ID,
+                    "/logs/stale.jsonl",
+                    1_000_i64,
+                    Option::<i64>::None
+                ],
+            )
+            .unwrap();
+        storage
+            .conn
+            .execute_compat(
+                "INSERT INTO conversations (
+                    agent_id, workspace_id, source_id, source_path, started_at, ended_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                fparams![
+                    agent_id,
+                    fresh_id,
+                    LOCAL_SOURCE_ID,
+                    "/logs/fresh.jsonl",
+                    2_000_i64,
+                    Some(9_000_i64)
+                ],
+            )
+            .unwrap();
+
+        let paths = storage.list_workspace_paths_by_recency().unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                "/home/u/dev/fresh".to_string(),
+                "/home/u/dev/stale".to_string(),
+                "/home/u/dev/empty".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn relink_workspace_renames_row_when_new_path_is_unindexed() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+        let storage = SqliteStorage::open(&db_path).unwrap();
+
+        let id = storage
+            .ensure_workspace(Path::new("/home/u/dev/foo"), None)
+            .unwrap();
+
+        let outcome = storage
+            .relink_workspace("/home/u/dev/foo", "/home/u/dev/foo-api")
+            .unwrap();
+        assert!(!outcome.merged);
+        assert_eq!(outcome.conversations_moved, 0);
+
+        // Same row, new path: aggregates keyed on the id stay valid.
+        let relinked_id: i64 = storage
+            .conn
+            .query_row_map(
+                "SELECT id FROM workspaces WHERE path = ?1",
+                fparams!["/home/u/dev/foo-api"],
+                |row| row.get_typed(0),
+            )
+            .unwrap();
+        assert_eq!(relinked_id, id);
+    }
+
+    #[test]
+    fn relink_workspace_merges_conversations_into_existing_row() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+        let storage = SqliteStorage::open(&db_path).unwrap();
+
+        let old_id = storage
+            .ensure_workspace(Path::new("/home/u/dev/foo"), None)
+            .unwrap();
+        let new_id = storage
+            .ensure_workspace(Path::new("/home/u/dev/foo-api"), None)
+            .unwrap();
+        storage
+            .raw()
+            .execute(&format!(
+                "INSERT INTO conversations (id, workspace_id, source_path, source_id)
+                 VALUES (1, {old_id}, '/tmp/a.jsonl', 'local')"
+            ))
+            .unwrap();
+
+        let outcome = storage
+            .relink_workspace("/home/u/dev/foo", "/home/u/dev/foo-api")
+            .unwrap();
+        assert!(outcome.merged);
+        assert_eq!(outcome.conversations_moved, 1);
 
-        let display_name: Option<String> = storage
+        let conv_workspace: i64 = storage
             .conn
             .query_row_map(
-                "SELECT display_name FROM workspaces WHERE path = ?1",
-                fparams![path.to_string_lossy().as_ref()],
+                "SELECT workspace_id FROM conversations WHERE id = 1",
+                fparams![],
                 |row| row.get_typed(0),
             )
             .unwrap();
+        assert_eq!(conv_workspace, new_id);
 
-        assert_eq!(id1, id2);
-        assert_eq!(display_name.as_deref(), Some("After"));
+        let old_rows: i64 = storage
+            .conn
+            .query_row_map(
+                "SELECT COUNT(*) FROM workspaces WHERE path = '/home/u/dev/foo'",
+                fparams![],
+                |row| row.get_typed(0),
+            )
+            .unwrap();
+        assert_eq!(old_rows, 0);
     }
 
     #[test]
-    fn list_workspaces_returns_inserted() {
+    fn relink_workspace_rejects_unknown_old_path() {
         let dir = TempDir::new().unwrap();
         let db_path = dir.path().join("test.db");
         let storage = SqliteStorage::open(&db_path).unwrap();
 
-        storage
-            .ensure_workspace(Path::new("/test/workspace"), Some("Test WS"))
-            .unwrap();
-
-        let workspaces = storage.list_workspaces().unwrap();
-        assert!(
-            workspaces
-                .iter()
-                .any(|w| w.path.to_str() == Some("/test/workspace"))
-        );
+        let err = storage
+            .relink_workspace("/nowhere/old", "/nowhere/new")
+            .unwrap_err();
+        assert!(err.to_string().contains("/nowhere/old"));
     }
 
     // =========================================================================
@@ -26209,11 +28692,7 @@ mod tests {
     /// ALTER TABLE RENAME which triggers sqlite_autoindex lookup issues on
     /// file-based pagers).
     fn franken_storage_in_memory() -> FrankenStorage {
-        let conn = FrankenConnection::open(":memory:").unwrap();
-        let storage = FrankenStorage::new(conn, PathBuf::from(":memory:"));
-        storage.run_migrations().unwrap();
-        storage.apply_config().unwrap();
-        storage
+        FrankenStorage::open_in_memory().unwrap()
     }
 
     #[test]
@@ -26265,1654 +28744,2678 @@ mod tests {
             "missing daily_stats table"
         );
 
-        // V9 embedding_jobs table.
-        assert!(
-            table_names.contains(&"embedding_jobs".to_string()),
-            "missing embedding_jobs table"
-        );
+        // V9 embedding_jobs table.
+        assert!(
+            table_names.contains(&"embedding_jobs".to_string()),
+            "missing embedding_jobs table"
+        );
+
+        // V11 message_metrics, usage_hourly, usage_daily tables.
+        for analytics_table in ["message_metrics", "usage_hourly", "usage_daily"] {
+            assert!(
+                table_names.contains(&analytics_table.to_string()),
+                "missing table: {analytics_table}"
+            );
+        }
+        assert!(
+            table_names.contains(&"conversation_tail_state".to_string()),
+            "missing conversation_tail_state table"
+        );
+        assert!(
+            table_names.contains(&"conversation_external_lookup".to_string()),
+            "missing conversation_external_lookup table"
+        );
+        assert!(
+            table_names.contains(&"conversation_external_tail_lookup".to_string()),
+            "missing conversation_external_tail_lookup table"
+        );
+
+        // Fresh frankensqlite databases should record the combined V13 base
+        // schema plus every additive post-V13 migration.
+        let rows = storage
+            .raw()
+            .query("SELECT COUNT(*) FROM _schema_migrations;")
+            .unwrap();
+        let count: i64 = rows.first().unwrap().get_typed(0).unwrap();
+        assert_eq!(
+            count,
+            (13..=CURRENT_SCHEMA_VERSION).count() as i64,
+            "_schema_migrations should record the V13 base schema and post-V13 migrations"
+        );
+
+        // The latest applied migration should be the current schema version.
+        let rows = storage
+            .raw()
+            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+            .unwrap();
+        let versions: Vec<i64> = rows
+            .iter()
+            .map(|row| row.get_typed(0))
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            versions,
+            (13..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
+            "_schema_migrations should contain v13 through current"
+        );
+    }
+
+    #[test]
+    fn franken_migrations_idempotent() {
+        let storage = franken_storage_in_memory();
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+
+        // Re-running migrations on the same connection is a no-op.
+        storage.run_migrations().unwrap();
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn migration_v20_backfills_conversation_external_tail_lookup() {
+        let storage = franken_storage_in_memory();
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+        let workspace_id = storage
+            .ensure_workspace(&PathBuf::from("/ws/profiled-storage-remote"), None)
+            .unwrap();
+        let mut conv = make_profiled_storage_remote_conversation(1919, 2);
+        conv.source_id = "profiled-storage-remote-source-東京".into();
+        conv.external_id = Some("profiled-storage-remote-☃-1919".into());
+        let outcome = storage
+            .insert_conversation_tree(agent_id, Some(workspace_id), &conv)
+            .unwrap();
+        let external_id = conv.external_id.as_deref().unwrap();
+        let lookup_key = conversation_external_lookup_key(&conv.source_id, agent_id, external_id);
+
+        storage
+            .raw()
+            .execute("DELETE FROM conversation_external_tail_lookup")
+            .unwrap();
+        storage
+            .raw()
+            .execute("DELETE FROM _schema_migrations WHERE version = 20")
+            .unwrap();
+        storage
+            .raw()
+            .execute_compat(
+                "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
+                fparams!["19"],
+            )
+            .unwrap();
+
+        storage.run_migrations().unwrap();
+
+        let backfilled: (i64, Option<i64>, Option<i64>, Option<i64>) = storage
+            .raw()
+            .query_row_map(
+                "SELECT conversation_id, ended_at, last_message_idx, last_message_created_at
+                 FROM conversation_external_tail_lookup
+                 WHERE lookup_key = ?1",
+                fparams![lookup_key.as_str()],
+                |row| {
+                    Ok((
+                        row.get_typed(0)?,
+                        row.get_typed(1)?,
+                        row.get_typed(2)?,
+                        row.get_typed(3)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            backfilled,
+            (
+                outcome.conversation_id,
+                conv.ended_at,
+                Some(1),
+                conv.messages[1].created_at
+            )
+        );
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn migration_v15_creates_lazy_tail_state_cache() {
+        let conn = FrankenConnection::open(":memory:").unwrap();
+        conn.execute_batch(
+            "CREATE TABLE conversations (
+                 id INTEGER PRIMARY KEY,
+                 ended_at INTEGER
+             );
+             CREATE TABLE messages (
+                 id INTEGER PRIMARY KEY,
+                 conversation_id INTEGER NOT NULL,
+                 idx INTEGER NOT NULL,
+                 created_at INTEGER
+             );
+             INSERT INTO conversations(id, ended_at) VALUES
+                 (1, 1710000000300),
+                 (2, NULL);
+             INSERT INTO messages(id, conversation_id, idx, created_at) VALUES
+                 (10, 1, 0, 1710000000100),
+                 (11, 1, 1, 1710000000200),
+                 (12, 2, 0, 1710000000400);",
+        )
+        .unwrap();
+
+        conn.execute(
+            "CREATE TABLE _schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+             );",
+        )
+        .unwrap();
+
+        assert!(
+            apply_conversation_tail_state_cache_migration(&conn).unwrap(),
+            "v15 migration should apply once"
+        );
+        assert!(
+            !apply_conversation_tail_state_cache_migration(&conn).unwrap(),
+            "v15 migration should be idempotent once recorded"
+        );
+
+        let columns = conn.query("PRAGMA table_info(conversations);").unwrap();
+        let column_names: HashSet<String> = columns
+            .iter()
+            .map(|row| row.get_typed(1))
+            .collect::<std::result::Result<_, frankensqlite::FrankenError>>()
+            .unwrap();
+        assert!(column_names.contains("last_message_idx"));
+        assert!(column_names.contains("last_message_created_at"));
+
+        let tail_rows: i64 = conn
+            .query("SELECT COUNT(*) FROM conversation_tail_state;")
+            .unwrap()
+            .first()
+            .unwrap()
+            .get_typed(0)
+            .unwrap();
+        assert_eq!(
+            tail_rows, 0,
+            "v15 should create the cache without an open-time message scan"
+        );
+
+        let applied: i64 = conn
+            .query("SELECT COUNT(*) FROM _schema_migrations WHERE version = 15;")
+            .unwrap()
+            .first()
+            .unwrap()
+            .get_typed(0)
+            .unwrap();
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn schema_repair_adds_missing_conversations_token_columns() {
+        let conn = FrankenConnection::open(":memory:").unwrap();
+        conn.execute_batch(
+            "CREATE TABLE conversations (
+                 id INTEGER PRIMARY KEY,
+                 agent_id INTEGER NOT NULL,
+                 source_path TEXT NOT NULL
+             );",
+        )
+        .unwrap();
+        let storage = FrankenStorage::new(conn, std::path::PathBuf::from(":memory:"));
+
+        storage.repair_missing_conversation_token_columns().unwrap();
+        storage.repair_missing_conversation_token_columns().unwrap();
 
-        // V11 message_metrics, usage_hourly, usage_daily tables.
-        for analytics_table in ["message_metrics", "usage_hourly", "usage_daily"] {
+        let columns = franken_table_column_names(&storage.conn, "conversations").unwrap();
+        for &(column_name, _) in REQUIRED_CONVERSATION_TOKEN_COLUMNS {
             assert!(
-                table_names.contains(&analytics_table.to_string()),
-                "missing table: {analytics_table}"
+                columns.contains(column_name),
+                "schema repair should add conversations.{column_name}"
             );
         }
-        assert!(
-            table_names.contains(&"conversation_tail_state".to_string()),
-            "missing conversation_tail_state table"
-        );
-        assert!(
-            table_names.contains(&"conversation_external_lookup".to_string()),
-            "missing conversation_external_lookup table"
-        );
-        assert!(
-            table_names.contains(&"conversation_external_tail_lookup".to_string()),
-            "missing conversation_external_tail_lookup table"
-        );
+    }
 
-        // Fresh frankensqlite databases should record the combined V13 base
-        // schema plus every additive post-V13 migration.
+    #[test]
+    fn franken_meta_schema_version_in_sync() {
+        let storage = franken_storage_in_memory();
+
+        // meta.schema_version should be kept in sync.
         let rows = storage
             .raw()
-            .query("SELECT COUNT(*) FROM _schema_migrations;")
+            .query("SELECT value FROM meta WHERE key = 'schema_version';")
             .unwrap();
-        let count: i64 = rows.first().unwrap().get_typed(0).unwrap();
+        let meta_version: String = rows.first().unwrap().get_typed(0).unwrap();
         assert_eq!(
-            count,
-            (13..=CURRENT_SCHEMA_VERSION).count() as i64,
-            "_schema_migrations should record the V13 base schema and post-V13 migrations"
+            meta_version,
+            CURRENT_SCHEMA_VERSION.to_string(),
+            "meta.schema_version should match CURRENT_SCHEMA_VERSION"
         );
+    }
 
-        // The latest applied migration should be the current schema version.
-        let rows = storage
-            .raw()
-            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+    #[test]
+    fn franken_transition_from_meta_version() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_transition.db");
+
+        // Simulate an existing database created by SqliteStorage at version 10.
+        // We create just enough schema to test the transition.
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        conn.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);")
             .unwrap();
-        let versions: Vec<i64> = rows
-            .iter()
-            .map(|row| row.get_typed(0))
-            .collect::<std::result::Result<_, _>>()
+        conn.execute("INSERT INTO meta(key, value) VALUES('schema_version', '10');")
+            .unwrap();
+        // Create a dummy conversations table so transition doesn't think it's corrupted.
+        conn.execute("CREATE TABLE conversations (id INTEGER PRIMARY KEY);")
+            .unwrap();
+        drop(conn);
+
+        // Now run the transition function.
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        transition_from_meta_version(&conn).unwrap();
+
+        // The frankensqlite path uses a combined V13 base migration, so a
+        // legacy V10 marker is bridged to V13 and later idempotent repair fills
+        // in any missing V11-V13 objects.
+        let rows = conn
+            .query("SELECT version FROM _schema_migrations ORDER BY version;")
             .unwrap();
+        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
         assert_eq!(
             versions,
-            (13..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
-            "_schema_migrations should contain v13 through current"
+            (1..=13).collect::<Vec<i64>>(),
+            "transition should bridge legacy V10 databases through the combined V13 base marker"
         );
     }
 
     #[test]
-    fn franken_migrations_idempotent() {
-        let storage = franken_storage_in_memory();
-        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
-
-        // Re-running migrations on the same connection is a no-op.
-        storage.run_migrations().unwrap();
-        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
-    }
+    fn franken_transition_from_current_meta_backfills_current_schema_marker() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_current_transition.db");
 
-    #[test]
-    fn migration_v20_backfills_conversation_external_tail_lookup() {
-        let storage = franken_storage_in_memory();
-        let agent_id = storage
-            .ensure_agent(&Agent {
-                id: None,
-                slug: "codex".into(),
-                name: "Codex".into(),
-                version: None,
-                kind: AgentKind::Cli,
-            })
-            .unwrap();
-        let workspace_id = storage
-            .ensure_workspace(&PathBuf::from("/ws/profiled-storage-remote"), None)
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        conn.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);")
             .unwrap();
-        let mut conv = make_profiled_storage_remote_conversation(1919, 2);
-        conv.source_id = "profiled-storage-remote-source-東京".into();
-        conv.external_id = Some("profiled-storage-remote-☃-1919".into());
-        let outcome = storage
-            .insert_conversation_tree(agent_id, Some(workspace_id), &conv)
+        conn.execute_compat(
+            "INSERT INTO meta(key, value) VALUES('schema_version', ?1);",
+            &[ParamValue::from(CURRENT_SCHEMA_VERSION.to_string())],
+        )
+        .unwrap();
+        conn.execute("CREATE TABLE conversations (id INTEGER PRIMARY KEY);")
             .unwrap();
-        let external_id = conv.external_id.as_deref().unwrap();
-        let lookup_key = conversation_external_lookup_key(&conv.source_id, agent_id, external_id);
+        drop(conn);
 
-        storage
-            .raw()
-            .execute("DELETE FROM conversation_external_tail_lookup")
-            .unwrap();
-        storage
-            .raw()
-            .execute("DELETE FROM _schema_migrations WHERE version = 20")
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        transition_from_meta_version(&conn).unwrap();
+
+        let rows = conn
+            .query("SELECT version FROM _schema_migrations ORDER BY version;")
             .unwrap();
-        storage
-            .raw()
-            .execute_compat(
-                "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
-                fparams!["19"],
-            )
+        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
+        assert_eq!(
+            versions,
+            (1..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
+            "current meta schema marker should backfill every known migration"
+        );
+    }
+
+    #[test]
+    fn franken_transition_skips_when_already_done() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_transition_skip.db");
+
+        // Create a DB that already has _schema_migrations.
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        conn.execute(
+            "CREATE TABLE _schema_migrations (version INTEGER PRIMARY KEY, name TEXT NOT NULL, applied_at TEXT NOT NULL DEFAULT 'now');",
+        ).unwrap();
+        conn.execute("INSERT INTO _schema_migrations (version, name) VALUES (1, 'test');")
             .unwrap();
 
-        storage.run_migrations().unwrap();
+        // Transition should be a no-op.
+        transition_from_meta_version(&conn).unwrap();
 
-        let backfilled: (i64, Option<i64>, Option<i64>, Option<i64>) = storage
-            .raw()
-            .query_row_map(
-                "SELECT conversation_id, ended_at, last_message_idx, last_message_created_at
-                 FROM conversation_external_tail_lookup
-                 WHERE lookup_key = ?1",
-                fparams![lookup_key.as_str()],
-                |row| {
-                    Ok((
-                        row.get_typed(0)?,
-                        row.get_typed(1)?,
-                        row.get_typed(2)?,
-                        row.get_typed(3)?,
-                    ))
-                },
-            )
+        // Should still have exactly 1 entry.
+        let rows = conn
+            .query("SELECT COUNT(*) FROM _schema_migrations;")
             .unwrap();
+        let count: i64 = rows.first().unwrap().get_typed(0).unwrap();
         assert_eq!(
-            backfilled,
-            (
-                outcome.conversation_id,
-                conv.ended_at,
-                Some(1),
-                conv.messages[1].created_at
-            )
+            count, 1,
+            "transition should not re-run on already-transitioned DB"
         );
-        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
     }
 
     #[test]
-    fn migration_v15_creates_lazy_tail_state_cache() {
-        let conn = FrankenConnection::open(":memory:").unwrap();
+    fn franken_transition_fresh_db_is_noop() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_fresh_noop.db");
+
+        // Empty database — no meta table, no tables at all.
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        transition_from_meta_version(&conn).unwrap();
+
+        // _schema_migrations should NOT have been created.
+        let res = conn.query("SELECT * FROM \"_schema_migrations\";");
+        assert!(
+            res.is_err(),
+            "transition should not create _schema_migrations on fresh DB"
+        );
+    }
+
+    #[test]
+    fn franken_transition_with_fts_virtual_table_succeeds() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_transition_with_fts.db");
+
+        let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             INSERT INTO meta(key, value) VALUES('schema_version', '13');
+             CREATE TABLE conversations (id INTEGER PRIMARY KEY);
+             CREATE VIRTUAL TABLE fts_messages USING fts5(
+                 content,
+                 title,
+                 agent,
+                 workspace,
+                 source_path,
+                 created_at,
+                 content='',
+                 tokenize='porter unicode61'
+             );",
+        )
+        .unwrap();
+        drop(conn);
+
+        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
+        transition_from_meta_version(&conn).unwrap();
+
+        let rows = conn
+            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+            .unwrap();
+        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
+        assert_eq!(versions, (1..=13).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn franken_storage_open_legacy_v13_with_fts_virtual_table_succeeds() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_open_legacy_v13_with_fts.db");
+
+        let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
         conn.execute_batch(
-            "CREATE TABLE conversations (
+            "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             INSERT INTO meta(key, value) VALUES('schema_version', '13');
+             CREATE TABLE agents (
+                 id INTEGER PRIMARY KEY,
+                 slug TEXT NOT NULL
+             );
+             CREATE TABLE workspaces (
+                 id INTEGER PRIMARY KEY,
+                 path TEXT NOT NULL
+             );
+             CREATE TABLE sources (
+                 id TEXT PRIMARY KEY,
+                 kind TEXT NOT NULL,
+                 host_label TEXT,
+                 machine_id TEXT,
+                 platform TEXT,
+                 config_json TEXT,
+                 created_at INTEGER NOT NULL,
+                 updated_at INTEGER NOT NULL
+             );
+             CREATE TABLE conversations (
                  id INTEGER PRIMARY KEY,
+                 agent_id INTEGER NOT NULL,
+                 workspace_id INTEGER,
+                 source_id TEXT NOT NULL DEFAULT 'local',
+                 external_id TEXT,
+                 title TEXT,
+                 source_path TEXT NOT NULL,
+                 started_at INTEGER,
                  ended_at INTEGER
              );
              CREATE TABLE messages (
                  id INTEGER PRIMARY KEY,
                  conversation_id INTEGER NOT NULL,
                  idx INTEGER NOT NULL,
-                 created_at INTEGER
+                 role TEXT NOT NULL,
+                 author TEXT,
+                 created_at INTEGER,
+                 content TEXT NOT NULL,
+                 extra_json TEXT,
+                 extra_bin BLOB
              );
-             INSERT INTO conversations(id, ended_at) VALUES
-                 (1, 1710000000300),
-                 (2, NULL);
-             INSERT INTO messages(id, conversation_id, idx, created_at) VALUES
-                 (10, 1, 0, 1710000000100),
-                 (11, 1, 1, 1710000000200),
-                 (12, 2, 0, 1710000000400);",
-        )
-        .unwrap();
-
-        conn.execute(
-            "CREATE TABLE _schema_migrations (
-                version INTEGER PRIMARY KEY,
-                name TEXT NOT NULL,
-                applied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+             INSERT INTO agents(id, slug) VALUES (1, 'codex');
+             INSERT INTO workspaces(id, path) VALUES (1, '/data/projects/coding_agent_session_search');
+             INSERT INTO sources(id, kind, host_label, created_at, updated_at)
+             VALUES ('local', 'local', NULL, 1710000000000, 1710000000000);
+             INSERT INTO conversations(
+                 id,
+                 agent_id,
+                 workspace_id,
+                 source_id,
+                 external_id,
+                 title,
+                 source_path,
+                 started_at
+             )
+             VALUES (
+                 1,
+                 1,
+                 1,
+                 'local',
+                 'legacy-session',
+                 'legacy session',
+                 '/tmp/legacy.jsonl',
+                 1710000000000
+             );
+             INSERT INTO messages(id, conversation_id, idx, role, author, created_at, content)
+             VALUES (1, 1, 0, 'user', 'tester', 1710000000000, 'legacy content');
+             CREATE VIRTUAL TABLE fts_messages USING fts5(
+                 content,
+                 title,
+                 agent,
+                 workspace,
+                 source_path,
+                 created_at,
+                 message_id,
+                 content='',
+                 tokenize='porter unicode61'
              );",
         )
         .unwrap();
+        drop(conn);
 
-        assert!(
-            apply_conversation_tail_state_cache_migration(&conn).unwrap(),
-            "v15 migration should apply once"
-        );
-        assert!(
-            !apply_conversation_tail_state_cache_migration(&conn).unwrap(),
-            "v15 migration should be idempotent once recorded"
-        );
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
 
-        let columns = conn.query("PRAGMA table_info(conversations);").unwrap();
-        let column_names: HashSet<String> = columns
-            .iter()
-            .map(|row| row.get_typed(1))
-            .collect::<std::result::Result<_, frankensqlite::FrankenError>>()
+        let rows = storage
+            .raw()
+            .query("SELECT version FROM _schema_migrations ORDER BY version;")
             .unwrap();
-        assert!(column_names.contains("last_message_idx"));
-        assert!(column_names.contains("last_message_created_at"));
+        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
+        assert_eq!(versions, (1..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>());
+    }
 
-        let tail_rows: i64 = conn
-            .query("SELECT COUNT(*) FROM conversation_tail_state;")
-            .unwrap()
-            .first()
-            .unwrap()
-            .get_typed(0)
-            .unwrap();
-        assert_eq!(
-            tail_rows, 0,
-            "v15 should create the cache without an open-time message scan"
-        );
+    #[test]
+    #[ignore = "blocked on frankensqlite contentless-FTS fix (y8n3i / fsqlite bd-sf8dx): open-validation demands a _content shadow for contentless fts_messages; un-ignore via cljkz once fsqlite publishes the fix"]
+    fn franken_storage_open_repairs_duplicate_fts_messages_schema_rows() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test_open_repairs_duplicate_fts_schema.db");
 
-        let applied: i64 = conn
-            .query("SELECT COUNT(*) FROM _schema_migrations WHERE version = 15;")
-            .unwrap()
-            .first()
-            .unwrap()
-            .get_typed(0)
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        let agent = Agent {
+            id: None,
+            slug: "codex".into(),
+            name: "Codex".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("dup-fts-schema".into()),
+            title: Some("Duplicate FTS schema".into()),
+            source_path: PathBuf::from("/tmp/dup-fts-schema.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: Some(42),
+            metadata_json: serde_json::Value::Null,
+            messages: vec![Message {
+                id: None,
+                idx: 0,
+                role: MessageRole::User,
+                author: Some("user".into()),
+                created_at: Some(1_700_000_000_050),
+                content: "message that should remain queryable".into(),
+                extra_json: serde_json::Value::Null,
+                snippets: Vec::new(),
+            }],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
-        assert_eq!(applied, 1);
-    }
+        drop(storage);
+        materialize_fresh_fts_schema_via_rusqlite(&db_path).unwrap();
 
-    #[test]
-    fn schema_repair_adds_missing_conversations_token_columns() {
-        let conn = FrankenConnection::open(":memory:").unwrap();
-        conn.execute_batch(
-            "CREATE TABLE conversations (
-                 id INTEGER PRIMARY KEY,
-                 agent_id INTEGER NOT NULL,
-                 source_path TEXT NOT NULL
-             );",
+        let duplicate_legacy_fts_sql = "CREATE VIRTUAL TABLE fts_messages USING fts5(content, title, agent, workspace, source_path, created_at UNINDEXED, message_id UNINDEXED, tokenize='porter')";
+        let conn = rusqlite_test_fixture_conn(&db_path);
+        conn.execute_batch("PRAGMA writable_schema = ON;").unwrap();
+        conn.execute(
+            "INSERT INTO sqlite_master(type, name, tbl_name, rootpage, sql)
+             VALUES('table', 'fts_messages', 'fts_messages', 0, ?1)",
+            [duplicate_legacy_fts_sql],
         )
         .unwrap();
-        let storage = FrankenStorage::new(conn, std::path::PathBuf::from(":memory:"));
-
-        storage.repair_missing_conversation_token_columns().unwrap();
-        storage.repair_missing_conversation_token_columns().unwrap();
-
-        let columns = franken_table_column_names(&storage.conn, "conversations").unwrap();
-        for &(column_name, _) in REQUIRED_CONVERSATION_TOKEN_COLUMNS {
-            assert!(
-                columns.contains(column_name),
-                "schema repair should add conversations.{column_name}"
-            );
-        }
-    }
+        conn.execute(
+            "DELETE FROM meta WHERE key = ?1",
+            [FTS_FRANKEN_REBUILD_META_KEY],
+        )
+        .unwrap();
+        // Simulate a pre-fix upgraded database that has never gone through the
+        // authoritative frankensqlite FTS rebuild generation yet.
+        conn.execute_batch("PRAGMA writable_schema = OFF;").unwrap();
 
-    #[test]
-    fn franken_meta_schema_version_in_sync() {
-        let storage = franken_storage_in_memory();
+        let duplicate_rows: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'fts_messages'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(duplicate_rows, 2);
+        drop(conn);
 
-        // meta.schema_version should be kept in sync.
-        let rows = storage
+        let reopened = FrankenStorage::open(&db_path).unwrap();
+        assert_eq!(reopened.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        let generation_rows: Vec<String> = reopened
             .raw()
-            .query("SELECT value FROM meta WHERE key = 'schema_version';")
+            .query_map_collect(
+                "SELECT value FROM meta WHERE key = ?1",
+                fparams![FTS_FRANKEN_REBUILD_META_KEY],
+                |row| row.get_typed(0),
+            )
             .unwrap();
-        let meta_version: String = rows.first().unwrap().get_typed(0).unwrap();
         assert_eq!(
-            meta_version,
-            CURRENT_SCHEMA_VERSION.to_string(),
-            "meta.schema_version should match CURRENT_SCHEMA_VERSION"
+            generation_rows.len(),
+            0,
+            "canonical open should not eagerly rewrite FTS repair metadata"
         );
+        reopened.ensure_search_fallback_fts_consistency().unwrap();
+        let repaired = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
+        assert_eq!(franken_fts_schema_rows(&repaired).unwrap(), 1);
+
+        let total_messages: i64 = reopened
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        let total_fts_rows: i64 = reopened
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM fts_messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(total_fts_rows, total_messages);
     }
 
     #[test]
-    fn franken_transition_from_meta_version() {
+    fn fts_messages_integrity_reports_missing_shadow_tables() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_transition.db");
-
-        // Simulate an existing database created by SqliteStorage at version 10.
-        // We create just enough schema to test the transition.
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        conn.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);")
-            .unwrap();
-        conn.execute("INSERT INTO meta(key, value) VALUES('schema_version', '10');")
-            .unwrap();
-        // Create a dummy conversations table so transition doesn't think it's corrupted.
-        conn.execute("CREATE TABLE conversations (id INTEGER PRIMARY KEY);")
-            .unwrap();
-        drop(conn);
+        let healthy_db_path = dir.path().join("healthy_fts.db");
 
-        // Now run the transition function.
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        transition_from_meta_version(&conn).unwrap();
+        {
+            let storage = FrankenStorage::open(&healthy_db_path).unwrap();
+            storage.ensure_search_fallback_fts_consistency().unwrap();
+            storage
+                .validate_fts_messages_integrity()
+                .expect("freshly materialized fts_messages should pass integrity validation");
+        }
 
-        // The frankensqlite path uses a combined V13 base migration, so a
-        // legacy V10 marker is bridged to V13 and later idempotent repair fills
-        // in any missing V11-V13 objects.
-        let rows = conn
-            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+        let corrupt_db_path = dir.path().join("test_corrupt_fts_missing_shadows.db");
+        {
+            let conn = rusqlite_test_fixture_conn(&corrupt_db_path);
+            conn.execute("CREATE TABLE schema_anchor(id INTEGER PRIMARY KEY)", [])
+                .unwrap();
+            let orphaned_fts_sql = "CREATE VIRTUAL TABLE fts_messages USING fts5(content, title, agent, workspace, source_path, created_at UNINDEXED, message_id UNINDEXED, tokenize='porter')";
+            conn.execute_batch("PRAGMA writable_schema = ON;").unwrap();
+            conn.execute(
+                "INSERT INTO sqlite_master(type, name, tbl_name, rootpage, sql)
+                 VALUES('table', 'fts_messages', 'fts_messages', 0, ?1)",
+                [orphaned_fts_sql],
+            )
             .unwrap();
-        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
-        assert_eq!(
-            versions,
-            (1..=13).collect::<Vec<i64>>(),
-            "transition should bridge legacy V10 databases through the combined V13 base marker"
+            conn.execute_batch("PRAGMA writable_schema = OFF;").unwrap();
+        }
+
+        let open_err = FrankenConnection::open(corrupt_db_path.to_string_lossy().to_string())
+            .expect_err("orphaned fts_messages schema should fail during connection open");
+        let integrity = fts_messages_integrity_error_from_message(open_err.to_string())
+            .expect("open-time FTS corruption should map to the typed FTS integrity kind");
+        assert_eq!(integrity.missing_shadow_tables(), &["fts_messages_content"]);
+        let rendered = integrity.to_string();
+        assert!(
+            rendered.contains("fts_messages")
+                && rendered.contains("required FTS5 shadow tables")
+                && rendered.contains("fts_messages_content"),
+            "error should be an operator-facing FTS corruption diagnosis: {rendered}"
         );
     }
 
     #[test]
-    fn franken_transition_from_current_meta_backfills_current_schema_marker() {
+    fn franken_storage_open_fresh_db_keeps_single_franken_fts_schema_row() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_current_transition.db");
+        let db_path = dir.path().join("fresh-franken-storage-open.db");
 
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        conn.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);")
-            .unwrap();
-        conn.execute_compat(
-            "INSERT INTO meta(key, value) VALUES('schema_version', ?1);",
-            &[ParamValue::from(CURRENT_SCHEMA_VERSION.to_string())],
-        )
-        .unwrap();
-        conn.execute("CREATE TABLE conversations (id INTEGER PRIMARY KEY);")
-            .unwrap();
-        drop(conn);
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
 
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        transition_from_meta_version(&conn).unwrap();
+        // The FTS5 virtual table is no longer created eagerly by the
+        // migration runner (V14 drops the old internal-content table and the
+        // current contentless table is recreated lazily — see MIGRATION_V14).
+        // Invoke the repair path to match normal cass startup, then assert
+        // there is exactly one fts_messages entry in sqlite_schema (no
+        // duplicates).
+        storage
+            .ensure_search_fallback_fts_consistency()
+            .expect("ensure FTS consistency after fresh open");
+        drop(storage);
 
-        let rows = conn
-            .query("SELECT version FROM _schema_migrations ORDER BY version;")
-            .unwrap();
-        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
+        let c_reader = FrankenConnection::open(db_path.to_string_lossy().into_owned())
+            .expect("open DB via frankensqlite for sqlite_master inspection");
         assert_eq!(
-            versions,
-            (1..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
-            "current meta schema marker should backfill every known migration"
+            franken_fts_schema_rows(&c_reader).unwrap(),
+            1,
+            "exactly one fts_messages schema row should exist after ensure_search_fallback_fts_consistency"
+        );
+        drop(c_reader);
+
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        assert!(
+            storage
+                .raw()
+                .query("SELECT COUNT(*) FROM fts_messages")
+                .is_ok(),
+            "fts_messages must be queryable through frankensqlite after open"
         );
     }
 
     #[test]
-    fn franken_transition_skips_when_already_done() {
+    fn franken_storage_open_repairs_missing_analytics_tables_when_version_markers_lie() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_transition_skip.db");
+        let db_path = dir.path().join("test_repair_missing_analytics.db");
 
-        // Create a DB that already has _schema_migrations.
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        conn.execute(
-            "CREATE TABLE _schema_migrations (version INTEGER PRIMARY KEY, name TEXT NOT NULL, applied_at TEXT NOT NULL DEFAULT 'now');",
-        ).unwrap();
-        conn.execute("INSERT INTO _schema_migrations (version, name) VALUES (1, 'test');")
+        {
+            let storage = FrankenStorage::open(&db_path).unwrap();
+            assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        }
+
+        {
+            let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
+            for table in &[
+                "usage_models_daily",
+                "usage_daily",
+                "usage_hourly",
+                "message_metrics",
+                "token_daily_stats",
+                "token_usage",
+                "model_pricing",
+                "embedding_jobs",
+                "daily_stats",
+            ] {
+                conn.execute(&format!("DROP TABLE IF EXISTS {table}"))
+                    .unwrap();
+            }
+            conn.execute_compat(
+                "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
+                &[ParamValue::from(CURRENT_SCHEMA_VERSION.to_string())],
+            )
             .unwrap();
+        }
 
-        // Transition should be a no-op.
-        transition_from_meta_version(&conn).unwrap();
+        let repaired = FrankenStorage::open(&db_path).unwrap();
+        assert_eq!(repaired.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
 
-        // Should still have exactly 1 entry.
-        let rows = conn
-            .query("SELECT COUNT(*) FROM _schema_migrations;")
+        let analytics_count: i64 = repaired
+            .raw()
+            .query_row_map(
+                "SELECT COUNT(*) FROM sqlite_master
+                 WHERE type='table'
+                   AND name IN (
+                     'daily_stats',
+                     'embedding_jobs',
+                     'token_usage',
+                     'token_daily_stats',
+                     'model_pricing',
+                     'message_metrics',
+                     'usage_hourly',
+                     'usage_daily',
+                     'usage_models_daily'
+                   )",
+                &[],
+                |row| row.get_typed(0),
+            )
             .unwrap();
-        let count: i64 = rows.first().unwrap().get_typed(0).unwrap();
         assert_eq!(
-            count, 1,
-            "transition should not re-run on already-transitioned DB"
+            analytics_count, 9,
+            "open() should recreate the missing analytics tables even when schema_version already says current"
         );
     }
 
     #[test]
-    fn franken_transition_fresh_db_is_noop() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_fresh_noop.db");
+    fn current_schema_repair_batches_cover_every_required_probe() {
+        let missing_tables: Vec<&'static str> = REQUIRED_CURRENT_SCHEMA_TABLE_PROBES
+            .iter()
+            .map(|(table_name, _)| *table_name)
+            .collect();
 
-        // Empty database — no meta table, no tables at all.
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        transition_from_meta_version(&conn).unwrap();
+        let batches = current_schema_repair_batches_for_missing_tables(&missing_tables).unwrap();
+        let covered_tables: HashSet<&'static str> = batches
+            .iter()
+            .flat_map(|batch| batch.tables.iter().copied())
+            .collect();
 
-        // _schema_migrations should NOT have been created.
-        let res = conn.query("SELECT * FROM \"_schema_migrations\";");
-        assert!(
-            res.is_err(),
-            "transition should not create _schema_migrations on fresh DB"
-        );
+        for table_name in missing_tables {
+            assert!(
+                covered_tables.contains(table_name),
+                "missing repair coverage for {table_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn current_schema_repair_batches_do_not_replay_core_schema_bootstrap() {
+        for batch in CURRENT_SCHEMA_REPAIR_BATCHES {
+            assert!(
+                !batch.sql.contains("CREATE TABLE IF NOT EXISTS meta"),
+                "repair batch {} should not recreate meta",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("CREATE TABLE IF NOT EXISTS agents"),
+                "repair batch {} should not recreate agents",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("CREATE TABLE IF NOT EXISTS workspaces"),
+                "repair batch {} should not recreate workspaces",
+                batch.name
+            );
+            assert!(
+                !batch
+                    .sql
+                    .contains("CREATE TABLE IF NOT EXISTS conversations"),
+                "repair batch {} should not recreate conversations",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("CREATE TABLE IF NOT EXISTS messages"),
+                "repair batch {} should not recreate messages",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("CREATE TABLE IF NOT EXISTS snippets"),
+                "repair batch {} should not recreate snippets",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("CREATE VIRTUAL TABLE fts_messages"),
+                "repair batch {} should not recreate FTS tables",
+                batch.name
+            );
+            assert!(
+                !batch.sql.contains("DROP TABLE"),
+                "repair batch {} should never drop tables",
+                batch.name
+            );
+        }
     }
 
     #[test]
-    fn franken_transition_with_fts_virtual_table_succeeds() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_transition_with_fts.db");
-
-        let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
-        conn.execute_batch(
-            "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
-             INSERT INTO meta(key, value) VALUES('schema_version', '13');
-             CREATE TABLE conversations (id INTEGER PRIMARY KEY);
-             CREATE VIRTUAL TABLE fts_messages USING fts5(
-                 content,
-                 title,
-                 agent,
-                 workspace,
-                 source_path,
-                 created_at,
-                 content='',
-                 tokenize='porter unicode61'
-             );",
-        )
-        .unwrap();
-        drop(conn);
-
-        let conn = FrankenConnection::open(db_path.to_string_lossy().to_string()).unwrap();
-        transition_from_meta_version(&conn).unwrap();
-
-        let rows = conn
-            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+    fn build_cass_migrations_applies_combined_v13() {
+        let conn = FrankenConnection::open(":memory:").unwrap();
+        let base_result = build_cass_migrations_before_tail_cache()
+            .run(&conn)
             .unwrap();
-        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
-        assert_eq!(versions, (1..=13).collect::<Vec<i64>>());
-    }
-
-    #[test]
-    fn franken_storage_open_legacy_v13_with_fts_virtual_table_succeeds() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_open_legacy_v13_with_fts.db");
-
-        let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
-        conn.execute_batch(
-            "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
-             INSERT INTO meta(key, value) VALUES('schema_version', '13');
-             CREATE TABLE agents (
-                 id INTEGER PRIMARY KEY,
-                 slug TEXT NOT NULL
-             );
-             CREATE TABLE workspaces (
-                 id INTEGER PRIMARY KEY,
-                 path TEXT NOT NULL
-             );
-             CREATE TABLE sources (
-                 id TEXT PRIMARY KEY,
-                 kind TEXT NOT NULL,
-                 host_label TEXT,
-                 machine_id TEXT,
-                 platform TEXT,
-                 config_json TEXT,
-                 created_at INTEGER NOT NULL,
-                 updated_at INTEGER NOT NULL
-             );
-             CREATE TABLE conversations (
-                 id INTEGER PRIMARY KEY,
-                 agent_id INTEGER NOT NULL,
-                 workspace_id INTEGER,
-                 source_id TEXT NOT NULL DEFAULT 'local',
-                 external_id TEXT,
-                 title TEXT,
-                 source_path TEXT NOT NULL,
-                 started_at INTEGER,
-                 ended_at INTEGER
-             );
-             CREATE TABLE messages (
-                 id INTEGER PRIMARY KEY,
-                 conversation_id INTEGER NOT NULL,
-                 idx INTEGER NOT NULL,
-                 role TEXT NOT NULL,
-                 author TEXT,
-                 created_at INTEGER,
-                 content TEXT NOT NULL,
-                 extra_json TEXT,
-                 extra_bin BLOB
-             );
-             INSERT INTO agents(id, slug) VALUES (1, 'codex');
-             INSERT INTO workspaces(id, path) VALUES (1, '/data/projects/coding_agent_session_search');
-             INSERT INTO sources(id, kind, host_label, created_at, updated_at)
-             VALUES ('local', 'local', NULL, 1710000000000, 1710000000000);
-             INSERT INTO conversations(
-                 id,
-                 agent_id,
-                 workspace_id,
-                 source_id,
-                 external_id,
-                 title,
-                 source_path,
-                 started_at
-             )
-             VALUES (
-                 1,
-                 1,
-                 1,
-                 'local',
-                 'legacy-session',
-                 'legacy session',
-                 '/tmp/legacy.jsonl',
-                 1710000000000
-             );
-             INSERT INTO messages(id, conversation_id, idx, role, author, created_at, content)
-             VALUES (1, 1, 0, 'user', 'tester', 1710000000000, 'legacy content');
-             CREATE VIRTUAL TABLE fts_messages USING fts5(
-                 content,
-                 title,
-                 agent,
-                 workspace,
-                 source_path,
-                 created_at,
-                 message_id,
-                 content='',
-                 tokenize='porter unicode61'
-             );",
-        )
-        .unwrap();
-        drop(conn);
-
-        let storage = FrankenStorage::open(&db_path).unwrap();
-        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        assert!(apply_conversation_tail_state_cache_migration(&conn).unwrap());
+        let post_result = build_cass_migrations_after_tail_cache().run(&conn).unwrap();
 
-        let rows = storage
-            .raw()
-            .query("SELECT version FROM _schema_migrations ORDER BY version;")
+        assert!(base_result.was_fresh);
+        let mut applied = base_result.applied;
+        applied.push(15);
+        applied.extend(post_result.applied);
+        assert_eq!(
+            applied,
+            (13..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
+            "should apply combined V13 plus additive post-V13 migrations"
+        );
+        let current: i64 = conn
+            .query("SELECT MAX(version) FROM _schema_migrations;")
+            .unwrap()
+            .first()
+            .unwrap()
+            .get_typed(0)
             .unwrap();
-        let versions: Vec<i64> = rows.iter().filter_map(|r| r.get_typed(0).ok()).collect();
-        assert_eq!(versions, (1..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>());
+        assert_eq!(current, CURRENT_SCHEMA_VERSION);
     }
 
     #[test]
-    #[ignore = "blocked on frankensqlite contentless-FTS fix (y8n3i / fsqlite bd-sf8dx): open-validation demands a _content shadow for contentless fts_messages; un-ignore via cljkz once fsqlite publishes the fix"]
-    fn franken_storage_open_repairs_duplicate_fts_messages_schema_rows() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_open_repairs_duplicate_fts_schema.db");
+    fn franken_insert_conversations_batched_populates_analytics_rollups() {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+        use frankensqlite::compat::{ConnectionExt, RowExt};
+        use std::path::PathBuf;
 
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("franken-index.db");
         let storage = FrankenStorage::open(&db_path).unwrap();
+
         let agent = Agent {
             id: None,
-            slug: "codex".into(),
-            name: "Codex".into(),
-            version: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: Some("1.0".into()),
             kind: AgentKind::Cli,
         };
         let agent_id = storage.ensure_agent(&agent).unwrap();
-        let conversation = Conversation {
+
+        let ts_ms = 1_770_551_400_000_i64;
+        let usage_json = serde_json::json!({
+            "message": {
+                "model": "claude-opus-4-6",
+                "usage": {
+                    "input_tokens": 100,
+                    "output_tokens": 50,
+                    "cache_read_input_tokens": 25,
+                    "cache_creation_input_tokens": 10,
+                    "service_tier": "standard"
+                }
+            }
+        });
+
+        let conv = Conversation {
             id: None,
-            agent_slug: "codex".into(),
+            agent_slug: "claude_code".into(),
             workspace: Some(PathBuf::from("/tmp/workspace")),
-            external_id: Some("dup-fts-schema".into()),
-            title: Some("Duplicate FTS schema".into()),
-            source_path: PathBuf::from("/tmp/dup-fts-schema.jsonl"),
-            started_at: Some(1_700_000_000_000),
-            ended_at: Some(1_700_000_000_100),
-            approx_tokens: Some(42),
+            external_id: Some("franken-batch-upsert".into()),
+            title: Some("Franken batch upsert".into()),
+            source_path: PathBuf::from("/tmp/franken.jsonl"),
+            started_at: Some(ts_ms),
+            ended_at: Some(ts_ms + 60_000),
+            approx_tokens: None,
             metadata_json: serde_json::Value::Null,
-            messages: vec![Message {
-                id: None,
-                idx: 0,
-                role: MessageRole::User,
-                author: Some("user".into()),
-                created_at: Some(1_700_000_000_050),
-                content: "message that should remain queryable".into(),
-                extra_json: serde_json::Value::Null,
-                snippets: Vec::new(),
-            }],
-            source_id: LOCAL_SOURCE_ID.into(),
-            origin_host: None,
-        };
-        storage
-            .insert_conversation_tree(agent_id, None, &conversation)
-            .unwrap();
-        drop(storage);
-        materialize_fresh_fts_schema_via_rusqlite(&db_path).unwrap();
-
-        let duplicate_legacy_fts_sql = "CREATE VIRTUAL TABLE fts_messages USING fts5(content, title, agent, workspace, source_path, created_at UNINDEXED, message_id UNINDEXED, tokenize='porter')";
-        let conn = rusqlite_test_fixture_conn(&db_path);
-        conn.execute_batch("PRAGMA writable_schema = ON;").unwrap();
-        conn.execute(
-            "INSERT INTO sqlite_master(type, name, tbl_name, rootpage, sql)
-             VALUES('table', 'fts_messages', 'fts_messages', 0, ?1)",
-            [duplicate_legacy_fts_sql],
-        )
-        .unwrap();
-        conn.execute(
-            "DELETE FROM meta WHERE key = ?1",
-            [FTS_FRANKEN_REBUILD_META_KEY],
-        )
-        .unwrap();
-        // Simulate a pre-fix upgraded database that has never gone through the
-        // authoritative frankensqlite FTS rebuild generation yet.
-        conn.execute_batch("PRAGMA writable_schema = OFF;").unwrap();
+            messages: vec![
+                Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: None,
+                    created_at: Some(ts_ms),
+                    content: "Please make a plan.".into(),
+                    extra_json: serde_json::Value::Null,
+                    snippets: vec![],
+                },
+                Message {
+                    id: None,
+                    idx: 1,
+                    role: MessageRole::Agent,
+                    author: None,
+                    created_at: Some(ts_ms + 30_000),
+                    content: "## Plan\n\n1. Reproduce\n2. Patch\n3. Verify".into(),
+                    extra_json: usage_json,
+                    snippets: vec![],
+                },
+            ],
+            source_id: "local".into(),
+            origin_host: None,
+        };
 
-        let duplicate_rows: i64 = conn
-            .query_row(
-                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'fts_messages'",
-                [],
-                |row| row.get(0),
-            )
+        let outcomes = storage
+            .insert_conversations_batched(&[(agent_id, None, &conv)])
             .unwrap();
-        assert_eq!(duplicate_rows, 2);
-        drop(conn);
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].inserted_indices, vec![0, 1]);
 
-        let reopened = FrankenStorage::open(&db_path).unwrap();
-        assert_eq!(reopened.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
-        let generation_rows: Vec<String> = reopened
-            .raw()
-            .query_map_collect(
-                "SELECT value FROM meta WHERE key = ?1",
-                fparams![FTS_FRANKEN_REBUILD_META_KEY],
+        let conn = storage.raw();
+        let daily_stats_rows: i64 = conn
+            .query_row_map("SELECT COUNT(*) FROM daily_stats", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        let token_daily_rows: i64 = conn
+            .query_row_map(
+                "SELECT COUNT(*) FROM token_daily_stats",
+                fparams![],
                 |row| row.get_typed(0),
             )
             .unwrap();
-        assert_eq!(
-            generation_rows.len(),
-            0,
-            "canonical open should not eagerly rewrite FTS repair metadata"
-        );
-        reopened.ensure_search_fallback_fts_consistency().unwrap();
-        let repaired = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
-        assert_eq!(franken_fts_schema_rows(&repaired).unwrap(), 1);
-
-        let total_messages: i64 = reopened
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+        let usage_daily_rows: i64 = conn
+            .query_row_map("SELECT COUNT(*) FROM usage_daily", fparams![], |row| {
                 row.get_typed(0)
             })
             .unwrap();
-        let total_fts_rows: i64 = reopened
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM fts_messages", fparams![], |row| {
-                row.get_typed(0)
-            })
+        let model_daily_rows: i64 = conn
+            .query_row_map(
+                "SELECT COUNT(*) FROM usage_models_daily",
+                fparams![],
+                |row| row.get_typed(0),
+            )
             .unwrap();
-        assert_eq!(total_fts_rows, total_messages);
+
+        assert!(daily_stats_rows > 0, "daily_stats should be populated");
+        assert!(
+            token_daily_rows > 0,
+            "token_daily_stats should be populated"
+        );
+        assert!(usage_daily_rows > 0, "usage_daily should be populated");
+        assert!(
+            model_daily_rows > 0,
+            "usage_models_daily should be populated"
+        );
     }
 
+    // =========================================================================
+    // FrankenConnectionManager tests (bead 3rlf8)
+    // =========================================================================
+
     #[test]
-    fn fts_messages_integrity_reports_missing_shadow_tables() {
+    fn connection_manager_creates_readers() {
         let dir = TempDir::new().unwrap();
-        let healthy_db_path = dir.path().join("healthy_fts.db");
-
-        {
-            let storage = FrankenStorage::open(&healthy_db_path).unwrap();
-            storage.ensure_search_fallback_fts_consistency().unwrap();
-            storage
-                .validate_fts_messages_integrity()
-                .expect("freshly materialized fts_messages should pass integrity validation");
-        }
+        let db_path = dir.path().join("cm.db");
 
-        let corrupt_db_path = dir.path().join("test_corrupt_fts_missing_shadows.db");
-        {
-            let conn = rusqlite_test_fixture_conn(&corrupt_db_path);
-            conn.execute("CREATE TABLE schema_anchor(id INTEGER PRIMARY KEY)", [])
-                .unwrap();
-            let orphaned_fts_sql = "CREATE VIRTUAL TABLE fts_messages USING fts5(content, title, agent, workspace, source_path, created_at UNINDEXED, message_id UNINDEXED, tokenize='porter')";
-            conn.execute_batch("PRAGMA writable_schema = ON;").unwrap();
-            conn.execute(
-                "INSERT INTO sqlite_master(type, name, tbl_name, rootpage, sql)
-                 VALUES('table', 'fts_messages', 'fts_messages', 0, ?1)",
-                [orphaned_fts_sql],
-            )
-            .unwrap();
-            conn.execute_batch("PRAGMA writable_schema = OFF;").unwrap();
-        }
+        // Create the DB first
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
 
-        let open_err = FrankenConnection::open(corrupt_db_path.to_string_lossy().to_string())
-            .expect_err("orphaned fts_messages schema should fail during connection open");
-        let integrity = fts_messages_integrity_error_from_message(open_err.to_string())
-            .expect("open-time FTS corruption should map to the typed FTS integrity kind");
-        assert_eq!(integrity.missing_shadow_tables(), &["fts_messages_content"]);
-        let rendered = integrity.to_string();
-        assert!(
-            rendered.contains("fts_messages")
-                && rendered.contains("required FTS5 shadow tables")
-                && rendered.contains("fts_messages_content"),
-            "error should be an operator-facing FTS corruption diagnosis: {rendered}"
-        );
+        let config = ConnectionManagerConfig {
+            reader_count: 3,
+            max_writers: 2,
+        };
+        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+        assert_eq!(mgr.reader_count(), 3);
+        assert_eq!(mgr.max_writers(), 2);
     }
 
     #[test]
-    fn franken_storage_open_fresh_db_keeps_single_franken_fts_schema_row() {
+    fn connection_manager_clamps_zero_writer_limit_to_prevent_deadlock() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("fresh-franken-storage-open.db");
-
-        let storage = FrankenStorage::open(&db_path).unwrap();
-        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        let db_path = dir.path().join("cm.db");
 
-        // The FTS5 virtual table is no longer created eagerly by the
-        // migration runner (V14 drops the old internal-content table and the
-        // current contentless table is recreated lazily — see MIGRATION_V14).
-        // Invoke the repair path to match normal cass startup, then assert
-        // there is exactly one fts_messages entry in sqlite_schema (no
-        // duplicates).
-        storage
-            .ensure_search_fallback_fts_consistency()
-            .expect("ensure FTS consistency after fresh open");
-        drop(storage);
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
 
-        let c_reader = FrankenConnection::open(db_path.to_string_lossy().into_owned())
-            .expect("open DB via frankensqlite for sqlite_master inspection");
-        assert_eq!(
-            franken_fts_schema_rows(&c_reader).unwrap(),
-            1,
-            "exactly one fts_messages schema row should exist after ensure_search_fallback_fts_consistency"
+        let mgr = std::sync::Arc::new(
+            FrankenConnectionManager::new(
+                &db_path,
+                ConnectionManagerConfig {
+                    reader_count: 0,
+                    max_writers: 0,
+                },
+            )
+            .unwrap(),
         );
-        drop(c_reader);
+        assert_eq!(mgr.reader_count(), 1);
+        assert_eq!(mgr.max_writers(), 1);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mgr_for_thread = std::sync::Arc::clone(&mgr);
+        std::thread::spawn(move || {
+            let result = mgr_for_thread.writer().map(|mut guard| {
+                guard.mark_committed();
+            });
+            tx.send(result.is_ok()).expect("writer result send");
+        });
 
-        let storage = FrankenStorage::open(&db_path).unwrap();
         assert!(
-            storage
-                .raw()
-                .query("SELECT COUNT(*) FROM fts_messages")
-                .is_ok(),
-            "fts_messages must be queryable through frankensqlite after open"
+            rx.recv_timeout(Duration::from_secs(10)).unwrap(),
+            "writer acquisition should not block forever when configured with zero writer slots"
         );
     }
 
     #[test]
-    fn franken_storage_open_repairs_missing_analytics_tables_when_version_markers_lie() {
+    fn connection_manager_reader_round_robin() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("test_repair_missing_analytics.db");
+        let db_path = dir.path().join("cm.db");
 
-        {
-            let storage = FrankenStorage::open(&db_path).unwrap();
-            assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
-        }
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
 
-        {
-            let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned()).unwrap();
-            for table in &[
-                "usage_models_daily",
-                "usage_daily",
-                "usage_hourly",
-                "message_metrics",
-                "token_daily_stats",
-                "token_usage",
-                "model_pricing",
-                "embedding_jobs",
-                "daily_stats",
-            ] {
-                conn.execute(&format!("DROP TABLE IF EXISTS {table}"))
-                    .unwrap();
-            }
-            conn.execute_compat(
-                "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
-                &[ParamValue::from(CURRENT_SCHEMA_VERSION.to_string())],
-            )
-            .unwrap();
-        }
+        let config = ConnectionManagerConfig {
+            reader_count: 2,
+            max_writers: 1,
+        };
+        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
 
-        let repaired = FrankenStorage::open(&db_path).unwrap();
-        assert_eq!(repaired.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        // Reader index should advance (round-robin)
+        let idx_before = mgr.reader_idx.load(std::sync::atomic::Ordering::Relaxed);
+        let _r1 = mgr.reader();
+        let idx_after = mgr.reader_idx.load(std::sync::atomic::Ordering::Relaxed);
+        assert_eq!(idx_after, idx_before + 1, "reader index should advance");
+    }
 
-        let analytics_count: i64 = repaired
-            .raw()
-            .query_row_map(
-                "SELECT COUNT(*) FROM sqlite_master
-                 WHERE type='table'
-                   AND name IN (
-                     'daily_stats',
-                     'embedding_jobs',
-                     'token_usage',
-                     'token_daily_stats',
-                     'model_pricing',
-                     'message_metrics',
-                     'usage_hourly',
-                     'usage_daily',
-                     'usage_models_daily'
-                   )",
-                &[],
-                |row| row.get_typed(0),
-            )
-            .unwrap();
-        assert_eq!(
-            analytics_count, 9,
-            "open() should recreate the missing analytics tables even when schema_version already says current"
-        );
+    #[test]
+    fn connection_manager_writer_reads_and_writes() {
+        use frankensqlite::compat::RowExt;
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("cm.db");
+
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
+
+        let mgr = FrankenConnectionManager::new(&db_path, Default::default()).unwrap();
+
+        // Acquire writer and insert data
+        {
+            let mut guard = mgr.writer().unwrap();
+            guard
+                .storage()
+                .raw()
+                .execute("CREATE TABLE IF NOT EXISTS cm_test (id INTEGER PRIMARY KEY, val TEXT)")
+                .unwrap();
+            guard
+                .storage()
+                .raw()
+                .execute("INSERT INTO cm_test (val) VALUES ('hello')")
+                .unwrap();
+            guard.mark_committed();
+        }
+
+        // Verify via reader (returns MutexGuard<SendFrankenConnection>)
+        let reader_guard = mgr.reader();
+        let rows = reader_guard.query("SELECT val FROM cm_test").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_typed::<String>(0).unwrap(), "hello");
     }
 
     #[test]
-    fn current_schema_repair_batches_cover_every_required_probe() {
-        let missing_tables: Vec<&'static str> = REQUIRED_CURRENT_SCHEMA_TABLE_PROBES
-            .iter()
-            .map(|(table_name, _)| *table_name)
-            .collect();
+    fn connection_manager_writer_guard_drops_releases_slot() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("cm.db");
 
-        let batches = current_schema_repair_batches_for_missing_tables(&missing_tables).unwrap();
-        let covered_tables: HashSet<&'static str> = batches
-            .iter()
-            .flat_map(|batch| batch.tables.iter().copied())
-            .collect();
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
 
-        for table_name in missing_tables {
-            assert!(
-                covered_tables.contains(table_name),
-                "missing repair coverage for {table_name}"
-            );
+        let config = ConnectionManagerConfig {
+            reader_count: 1,
+            max_writers: 1,
+        };
+        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+
+        // Acquire and release writer
+        {
+            let mut guard = mgr.writer().unwrap();
+            guard.mark_committed();
         }
+
+        // Should be able to acquire again (slot released)
+        let mut guard2 = mgr.writer().unwrap();
+        guard2.mark_committed();
     }
 
     #[test]
-    fn current_schema_repair_batches_do_not_replay_core_schema_bootstrap() {
-        for batch in CURRENT_SCHEMA_REPAIR_BATCHES {
-            assert!(
-                !batch.sql.contains("CREATE TABLE IF NOT EXISTS meta"),
-                "repair batch {} should not recreate meta",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("CREATE TABLE IF NOT EXISTS agents"),
-                "repair batch {} should not recreate agents",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("CREATE TABLE IF NOT EXISTS workspaces"),
-                "repair batch {} should not recreate workspaces",
-                batch.name
-            );
-            assert!(
-                !batch
-                    .sql
-                    .contains("CREATE TABLE IF NOT EXISTS conversations"),
-                "repair batch {} should not recreate conversations",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("CREATE TABLE IF NOT EXISTS messages"),
-                "repair batch {} should not recreate messages",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("CREATE TABLE IF NOT EXISTS snippets"),
-                "repair batch {} should not recreate snippets",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("CREATE VIRTUAL TABLE fts_messages"),
-                "repair batch {} should not recreate FTS tables",
-                batch.name
-            );
-            assert!(
-                !batch.sql.contains("DROP TABLE"),
-                "repair batch {} should never drop tables",
-                batch.name
-            );
+    fn connection_manager_concurrent_writer_works() {
+        use frankensqlite::compat::RowExt;
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("cm.db");
+
+        let fs = FrankenStorage::open(&db_path).unwrap();
+        drop(fs);
+
+        let config = ConnectionManagerConfig {
+            reader_count: 1,
+            max_writers: 2,
+        };
+        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+
+        {
+            let mut guard = mgr.concurrent_writer().unwrap();
+            guard
+                .storage()
+                .raw()
+                .execute("CREATE TABLE IF NOT EXISTS cm_conc (id INTEGER PRIMARY KEY, val TEXT)")
+                .unwrap();
+            guard
+                .storage()
+                .raw()
+                .execute("INSERT INTO cm_conc (val) VALUES ('concurrent')")
+                .unwrap();
+            guard.mark_committed();
         }
+
+        let reader_guard = mgr.reader();
+        let rows = reader_guard.query("SELECT val FROM cm_conc").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_typed::<String>(0).unwrap(), "concurrent");
     }
 
     #[test]
-    fn build_cass_migrations_applies_combined_v13() {
-        let conn = FrankenConnection::open(":memory:").unwrap();
-        let base_result = build_cass_migrations_before_tail_cache()
-            .run(&conn)
-            .unwrap();
-        assert!(apply_conversation_tail_state_cache_migration(&conn).unwrap());
-        let post_result = build_cass_migrations_after_tail_cache().run(&conn).unwrap();
-
-        assert!(base_result.was_fresh);
-        let mut applied = base_result.applied;
-        applied.push(15);
-        applied.extend(post_result.applied);
-        assert_eq!(
-            applied,
-            (13..=CURRENT_SCHEMA_VERSION).collect::<Vec<i64>>(),
-            "should apply combined V13 plus additive post-V13 migrations"
-        );
-        let current: i64 = conn
-            .query("SELECT MAX(version) FROM _schema_migrations;")
-            .unwrap()
-            .first()
-            .unwrap()
-            .get_typed(0)
-            .unwrap();
-        assert_eq!(current, CURRENT_SCHEMA_VERSION);
+    fn connection_manager_default_config() {
+        let config = ConnectionManagerConfig::default();
+        assert_eq!(config.reader_count, 4);
+        assert!(config.max_writers > 0);
     }
 
     #[test]
-    fn franken_insert_conversations_batched_populates_analytics_rollups() {
+    fn purge_agent_archive_data_removes_only_target_agent_and_rebuilds_derived_tables() {
         use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
-        use frankensqlite::compat::{ConnectionExt, RowExt};
         use std::path::PathBuf;
 
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("franken-index.db");
-        let storage = FrankenStorage::open(&db_path).unwrap();
-
-        let agent = Agent {
-            id: None,
-            slug: "claude_code".into(),
-            name: "Claude Code".into(),
-            version: Some("1.0".into()),
-            kind: AgentKind::Cli,
-        };
-        let agent_id = storage.ensure_agent(&agent).unwrap();
-
-        let ts_ms = 1_770_551_400_000_i64;
-        let usage_json = serde_json::json!({
-            "message": {
-                "model": "claude-opus-4-6",
-                "usage": {
-                    "input_tokens": 100,
-                    "output_tokens": 50,
-                    "cache_read_input_tokens": 25,
-                    "cache_creation_input_tokens": 10,
-                    "service_tier": "standard"
-                }
-            }
-        });
-
-        let conv = Conversation {
-            id: None,
-            agent_slug: "claude_code".into(),
-            workspace: Some(PathBuf::from("/tmp/workspace")),
-            external_id: Some("franken-batch-upsert".into()),
-            title: Some("Franken batch upsert".into()),
-            source_path: PathBuf::from("/tmp/franken.jsonl"),
-            started_at: Some(ts_ms),
-            ended_at: Some(ts_ms + 60_000),
-            approx_tokens: None,
-            metadata_json: serde_json::Value::Null,
-            messages: vec![
-                Message {
-                    id: None,
-                    idx: 0,
-                    role: MessageRole::User,
-                    author: None,
-                    created_at: Some(ts_ms),
-                    content: "Please make a plan.".into(),
-                    extra_json: serde_json::Value::Null,
-                    snippets: vec![],
-                },
-                Message {
-                    id: None,
-                    idx: 1,
-                    role: MessageRole::Agent,
-                    author: None,
-                    created_at: Some(ts_ms + 30_000),
-                    content: "## Plan\n\n1. Reproduce\n2. Patch\n3. Verify".into(),
-                    extra_json: usage_json,
-                    snippets: vec![],
-                },
-            ],
-            source_id: "local".into(),
-            origin_host: None,
-        };
+        fn seed_conversation(storage: &FrankenStorage, agent_slug: &str, marker: &str) {
+            let agent = Agent {
+                id: None,
+                slug: agent_slug.into(),
+                name: agent_slug.into(),
+                version: None,
+                kind: AgentKind::Cli,
+            };
+            let agent_id = storage.ensure_agent(&agent).unwrap();
+            let conversation = Conversation {
+                id: None,
+                agent_slug: agent_slug.into(),
+                workspace: Some(PathBuf::from("/tmp/workspace")),
+                external_id: Some(format!("{agent_slug}-{marker}")),
+                title: Some(format!("{agent_slug} {marker}")),
+                source_path: PathBuf::from(format!("/tmp/{agent_slug}-{marker}.jsonl")),
+                started_at: Some(1_700_000_000_000),
+                ended_at: Some(1_700_000_000_100),
+                approx_tokens: None,
+                metadata_json: serde_json::Value::Null,
+                messages: vec![
+                    Message {
+                        id: None,
+                        idx: 0,
+                        role: MessageRole::User,
+                        author: Some("user".into()),
+                        created_at: Some(1_700_000_000_010),
+                        content: format!("{agent_slug} {marker} user"),
+                        extra_json: serde_json::Value::Null,
+                        snippets: Vec::new(),
+                    },
+                    Message {
+                        id: None,
+                        idx: 1,
+                        role: MessageRole::Agent,
+                        author: Some("assistant".into()),
+                        created_at: Some(1_700_000_000_020),
+                        content: format!("{agent_slug} {marker} assistant"),
+                        extra_json: serde_json::Value::Null,
+                        snippets: Vec::new(),
+                    },
+                ],
+                source_id: LOCAL_SOURCE_ID.into(),
+                origin_host: None,
+            };
+            storage
+                .insert_conversation_tree(agent_id, None, &conversation)
+                .unwrap();
+        }
 
-        let outcomes = storage
-            .insert_conversations_batched(&[(agent_id, None, &conv)])
-            .unwrap();
-        assert_eq!(outcomes.len(), 1);
-        assert_eq!(outcomes[0].inserted_indices, vec![0, 1]);
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("agent_search.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
 
-        let conn = storage.raw();
-        let daily_stats_rows: i64 = conn
-            .query_row_map("SELECT COUNT(*) FROM daily_stats", fparams![], |row| {
+        seed_conversation(&storage, "openclaw", "purge-target");
+        seed_conversation(&storage, "codex", "keep-target");
+
+        let purge = storage.purge_agent_archive_data("openclaw").unwrap();
+        assert_eq!(purge.conversations_deleted, 1);
+        assert_eq!(purge.messages_deleted, 2);
+
+        storage.rebuild_fts().unwrap();
+        storage.rebuild_analytics().unwrap();
+        storage.rebuild_daily_stats().unwrap();
+        storage.rebuild_token_daily_stats().unwrap();
+
+        let agents = storage.list_agents().unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].slug, "codex");
+        assert_eq!(storage.total_conversation_count().unwrap(), 1);
+        assert_eq!(storage.total_message_count().unwrap(), 2);
+
+        let fts_rows: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM fts_messages", fparams![], |row| {
                 row.get_typed(0)
             })
             .unwrap();
-        let token_daily_rows: i64 = conn
+        assert_eq!(fts_rows, 2);
+
+        let total_daily_sessions: i64 = storage
+            .raw()
             .query_row_map(
-                "SELECT COUNT(*) FROM token_daily_stats",
+                "SELECT COALESCE(SUM(session_count), 0)
+                 FROM daily_stats
+                 WHERE agent_slug = 'all' AND source_id = 'all'",
                 fparams![],
                 |row| row.get_typed(0),
             )
             .unwrap();
-        let usage_daily_rows: i64 = conn
-            .query_row_map("SELECT COUNT(*) FROM usage_daily", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        let model_daily_rows: i64 = conn
+        assert_eq!(total_daily_sessions, 1);
+
+        let openclaw_token_rows: i64 = storage
+            .raw()
             .query_row_map(
-                "SELECT COUNT(*) FROM usage_models_daily",
+                "SELECT COUNT(*) FROM token_daily_stats WHERE agent_slug = 'openclaw'",
                 fparams![],
                 |row| row.get_typed(0),
             )
             .unwrap();
+        assert_eq!(openclaw_token_rows, 0);
+    }
 
-        assert!(daily_stats_rows > 0, "daily_stats should be populated");
-        assert!(
-            token_daily_rows > 0,
-            "token_daily_stats should be populated"
+    /// #292 ask #2: `cass forget --source-glob` prunes the matching subset of
+    /// conversations from the canonical DB (dry-run reports, --apply deletes),
+    /// leaving non-matching conversations and their messages intact.
+    #[test]
+    fn forget_conversations_by_source_glob_dry_run_then_apply() {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+        use std::path::PathBuf;
+
+        fn seed(storage: &FrankenStorage, agent_slug: &str, source_path: &str, marker: &str) {
+            let agent = Agent {
+                id: None,
+                slug: agent_slug.into(),
+                name: agent_slug.into(),
+                version: None,
+                kind: AgentKind::Cli,
+            };
+            let agent_id = storage.ensure_agent(&agent).unwrap();
+            let conversation = Conversation {
+                id: None,
+                agent_slug: agent_slug.into(),
+                workspace: Some(PathBuf::from("/tmp/workspace")),
+                external_id: Some(format!("{agent_slug}-{marker}")),
+                title: Some(format!("{agent_slug} {marker}")),
+                source_path: PathBuf::from(source_path),
+                started_at: Some(1_700_000_000_000),
+                ended_at: Some(1_700_000_000_100),
+                approx_tokens: None,
+                metadata_json: serde_json::Value::Null,
+                messages: vec![Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: Some("user".into()),
+                    created_at: Some(1_700_000_000_010),
+                    content: format!("{marker} content"),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                }],
+                source_id: LOCAL_SOURCE_ID.into(),
+                origin_host: None,
+            };
+            storage
+                .insert_conversation_tree(agent_id, None, &conversation)
+                .unwrap();
+        }
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("forget.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+
+        // Two subagent transcripts (match the glob) + one top-level session.
+        seed(
+            &storage,
+            "claude",
+            "/home/u/.claude/projects/p/sess-1/subagents/agent-aaa.jsonl",
+            "sub-a",
         );
-        assert!(usage_daily_rows > 0, "usage_daily should be populated");
-        assert!(
-            model_daily_rows > 0,
-            "usage_models_daily should be populated"
+        seed(
+            &storage,
+            "claude",
+            "/home/u/.claude/projects/p/sess-2/subagents/agent-bbb.jsonl",
+            "sub-b",
         );
-    }
+        seed(
+            &storage,
+            "claude",
+            "/home/u/.claude/projects/p/sess-3/session.jsonl",
+            "top",
+        );
+        assert_eq!(storage.total_conversation_count().unwrap(), 3);
 
-    // =========================================================================
-    // FrankenConnectionManager tests (bead 3rlf8)
-    // =========================================================================
+        let glob = "**/subagents/*.jsonl";
 
-    #[test]
-    fn connection_manager_creates_readers() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
+        // Dry-run: reports matches, deletes nothing.
+        let dry = storage
+            .forget_conversations_by_source_glob(glob, true)
+            .unwrap();
+        assert!(dry.dry_run);
+        assert_eq!(dry.conversations_matched, 2);
+        assert_eq!(dry.messages_matched, 2);
+        assert_eq!(dry.conversations_deleted, 0);
+        assert_eq!(storage.total_conversation_count().unwrap(), 3);
 
-        // Create the DB first
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
+        // Apply: deletes the two subagent conversations, keeps the top-level one.
+        let applied = storage
+            .forget_conversations_by_source_glob(glob, false)
+            .unwrap();
+        assert!(!applied.dry_run);
+        assert_eq!(applied.conversations_matched, 2);
+        assert_eq!(applied.conversations_deleted, 2);
+        assert_eq!(storage.total_conversation_count().unwrap(), 1);
+        // The surviving top-level session's message is intact.
+        assert_eq!(storage.total_message_count().unwrap(), 1);
 
-        let config = ConnectionManagerConfig {
-            reader_count: 3,
-            max_writers: 2,
-        };
-        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
-        assert_eq!(mgr.reader_count(), 3);
-        assert_eq!(mgr.max_writers(), 2);
+        // An empty pattern is rejected; a non-matching glob is a clean no-op.
+        assert!(
+            storage
+                .forget_conversations_by_source_glob("   ", true)
+                .is_err()
+        );
+        let none = storage
+            .forget_conversations_by_source_glob("**/nope/*.jsonl", false)
+            .unwrap();
+        assert_eq!(none.conversations_matched, 0);
+        assert_eq!(none.conversations_deleted, 0);
+        assert_eq!(storage.total_conversation_count().unwrap(), 1);
+
+        // The applied deletion left a restorable recycle-bin operation.
+        let operation_id = applied.trash_operation_id.expect("trash operation id");
+        let ops = storage.trash_list_operations().unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].operation_id, operation_id);
+        assert_eq!(ops[0].kind, "forget");
+        assert_eq!(ops[0].conversation_count, 2);
+
+        // Restore brings both conversations (and their messages) back and
+        // drops the operation from the bin.
+        let restored = storage.trash_restore_operation(&operation_id).unwrap();
+        assert_eq!(restored.conversations_restored, 2);
+        assert_eq!(restored.conversations_skipped, 0);
+        assert_eq!(storage.total_conversation_count().unwrap(), 3);
+        assert_eq!(storage.total_message_count().unwrap(), 3);
+        assert!(storage.trash_list_operations().unwrap().is_empty());
+        assert!(storage.trash_restore_operation(&operation_id).is_err());
+
+        // A second forget round lands in the bin; `trash_empty(None)` drops it.
+        let applied_again = storage
+            .forget_conversations_by_source_glob(glob, false)
+            .unwrap();
+        assert!(applied_again.trash_operation_id.is_some());
+        assert_eq!(storage.trash_empty(None).unwrap(), 1);
+        assert!(storage.trash_list_operations().unwrap().is_empty());
     }
 
     #[test]
-    fn connection_manager_clamps_zero_writer_limit_to_prevent_deadlock() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
+    fn slice_database_keeps_only_recent_conversations() {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+        use std::path::PathBuf;
 
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
+        fn seed(storage: &FrankenStorage, marker: &str, ended_at: i64) {
+            let agent = Agent {
+                id: None,
+                slug: "claude".into(),
+                name: "claude".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            };
+            let agent_id = storage.ensure_agent(&agent).unwrap();
+            let conversation = Conversation {
+                id: None,
+                agent_slug: "claude".into(),
+                workspace: Some(PathBuf::from("/tmp/workspace")),
+                external_id: Some(format!("slice-{marker}")),
+                title: Some(marker.to_string()),
+                source_path: PathBuf::from(format!("/tmp/slice-{marker}.jsonl")),
+                started_at: Some(ended_at - 100),
+                ended_at: Some(ended_at),
+                approx_tokens: None,
+                metadata_json: serde_json::Value::Null,
+                messages: vec![Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: Some("user".into()),
+                    created_at: Some(ended_at - 50),
+                    content: format!("{marker} content"),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                }],
+                source_id: LOCAL_SOURCE_ID.into(),
+                origin_host: None,
+            };
+            storage
+                .insert_conversation_tree(agent_id, None, &conversation)
+                .unwrap();
+        }
 
-        let mgr = std::sync::Arc::new(
-            FrankenConnectionManager::new(
-                &db_path,
-                ConnectionManagerConfig {
-                    reader_count: 0,
-                    max_writers: 0,
-                },
-            )
-            .unwrap(),
-        );
-        assert_eq!(mgr.reader_count(), 1);
-        assert_eq!(mgr.max_writers(), 1);
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("full.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        seed(&storage, "old", 1_000);
+        seed(&storage, "recent", 2_000_000);
+
+        let out_path = dir.path().join("small.db");
+        let report = slice_database(&db_path, &out_path, 1_000_000).unwrap();
+        assert_eq!(report.cutoff_ms, 1_000_000);
+        assert_eq!(report.conversations_total, 2);
+        assert_eq!(report.conversations_kept, 1);
+        assert_eq!(report.conversations_dropped, 1);
+        assert_eq!(report.messages_kept, 1);
+        assert!(report.output_bytes > 0);
+
+        // The source database is untouched.
+        assert_eq!(storage.total_conversation_count().unwrap(), 2);
+
+        // The slice stands alone: only the recent conversation survives and
+        // the rebuilt FTS covers exactly its messages.
+        let sliced = FrankenStorage::open(&out_path).unwrap();
+        assert_eq!(sliced.total_conversation_count().unwrap(), 1);
+        assert_eq!(sliced.total_message_count().unwrap(), 1);
+        let fts_rows: i64 = sliced
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM fts_messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(fts_rows, 1);
 
-        let (tx, rx) = std::sync::mpsc::channel();
-        let mgr_for_thread = std::sync::Arc::clone(&mgr);
-        std::thread::spawn(move || {
-            let result = mgr_for_thread.writer().map(|mut guard| {
-                guard.mark_committed();
-            });
-            tx.send(result.is_ok()).expect("writer result send");
-        });
+        // No staging litter, and an existing output path is refused.
+        assert!(!dir.path().join("small.db.slice-stage").exists());
+        assert!(slice_database(&db_path, &out_path, 1_000_000).is_err());
+    }
 
-        assert!(
-            rx.recv_timeout(Duration::from_secs(10)).unwrap(),
-            "writer acquisition should not block forever when configured with zero writer slots"
+    #[test]
+    fn locate_message_spans_matches_forward_and_skips_unlocatable() {
+        let raw = concat!(
+            "{\"type\":\"meta\",\"version\":1}\n",
+            "{\"role\":\"user\",\"content\":\"fix the \\\"auth\\\" bug\"}\n",
+            "{\"role\":\"agent\",\"content\":\"done, see src/auth.rs\"}\n",
+        );
+        let messages = vec![
+            (10, "fix the \"auth\" bug".to_string()),
+            (11, "done, see src/auth.rs".to_string()),
+            (12, "never appeared in the file".to_string()),
+        ];
+        let spans = locate_message_spans(raw, &messages);
+        assert_eq!(spans.len(), 2);
+
+        let (id, span) = spans[0];
+        assert_eq!(id, 10);
+        assert_eq!(span.line_no, 2);
+        // The span covers exactly the raw record, escapes intact.
+        let record = &raw[span.start_byte as usize..span.end_byte as usize];
+        assert_eq!(
+            record,
+            "{\"role\":\"user\",\"content\":\"fix the \\\"auth\\\" bug\"}"
         );
+        assert_eq!(spans[1].1.line_no, 3);
     }
 
     #[test]
-    fn connection_manager_reader_round_robin() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
+    fn refresh_message_provenance_fills_spans_for_jsonl_sources() {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
 
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
+        let dir = TempDir::new().unwrap();
+        let source_path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &source_path,
+            "{\"role\":\"user\",\"content\":\"hello provenance\"}\n\
+             {\"role\":\"agent\",\"content\":\"hello back\"}\n",
+        )
+        .unwrap();
 
-        let config = ConnectionManagerConfig {
-            reader_count: 2,
-            max_writers: 1,
+        let db_path = dir.path().join("provenance.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        let agent = Agent {
+            id: None,
+            slug: "claude".into(),
+            name: "claude".into(),
+            version: None,
+            kind: AgentKind::Cli,
         };
-        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let make_message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_000),
+            content: content.to_string(),
+            extra_json: serde_json::Value::Null,
+            snippets: Vec::new(),
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude".into(),
+            workspace: None,
+            external_id: Some("provenance-1".into()),
+            title: None,
+            source_path: source_path.clone(),
+            started_at: Some(1_000),
+            ended_at: Some(2_000),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                make_message(0, MessageRole::User, "hello provenance"),
+                make_message(1, MessageRole::Agent, "hello back"),
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .unwrap();
 
-        // Reader index should advance (round-robin)
-        let idx_before = mgr.reader_idx.load(std::sync::atomic::Ordering::Relaxed);
-        let _r1 = mgr.reader();
-        let idx_after = mgr.reader_idx.load(std::sync::atomic::Ordering::Relaxed);
-        assert_eq!(idx_after, idx_before + 1, "reader index should advance");
+        assert_eq!(storage.refresh_message_provenance().unwrap(), 2);
+        // Incremental by construction: nothing pending on the second pass.
+        assert_eq!(storage.refresh_message_provenance().unwrap(), 0);
+
+        let message_ids: Vec<i64> = storage
+            .raw()
+            .query_map_collect(
+                "SELECT id FROM messages ORDER BY idx",
+                &[],
+                |row: &FrankenRow| row.get_typed(0),
+            )
+            .unwrap();
+        let span = storage
+            .message_provenance(message_ids[0])
+            .unwrap()
+            .expect("first message should have a span");
+        assert_eq!(span.line_no, 1);
+        let raw = std::fs::read_to_string(&source_path).unwrap();
+        assert_eq!(
+            &raw[span.start_byte as usize..span.end_byte as usize],
+            "{\"role\":\"user\",\"content\":\"hello provenance\"}"
+        );
+        assert_eq!(
+            storage
+                .message_provenance(message_ids[1])
+                .unwrap()
+                .expect("second message should have a span")
+                .line_no,
+            2
+        );
     }
 
+    /// Regression for cass#202: a `Connection` dropped mid-transaction can
+    /// leave child rows persisted without a matching parent. The next indexer
+    /// pass then trips `FOREIGN KEY constraint failed` on every write, the
+    /// session never gets marked indexed, and the pending backlog grows
+    /// without bound. `cleanup_orphan_fk_rows` is the indexer-startup
+    /// self-heal that breaks the cycle.
     #[test]
-    fn connection_manager_writer_reads_and_writes() {
-        use frankensqlite::compat::RowExt;
-
+    fn cleanup_orphan_fk_rows_removes_orphans_and_is_noop_on_clean_db() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
+        let db_path = dir.path().join("orphan_fk_self_heal.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
 
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
+        // Plant orphan rows directly: rows whose FK parent does not exist.
+        // FK enforcement is temporarily off so the planted rows can land.
+        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
 
-        let mgr = FrankenConnectionManager::new(&db_path, Default::default()).unwrap();
+        // Seed a real conversation so a subset of children DO have valid
+        // parents — we want the cleanup to be precise, not a table-flush.
+        storage
+            .raw()
+            .execute_compat(
+                "INSERT INTO agents(id, slug, name, kind, created_at, updated_at) \
+                 VALUES(1, 'test-agent', 'Test Agent', 'cli', 0, 0)",
+                fparams![],
+            )
+            .unwrap();
+        storage
+            .raw()
+            .execute_compat(
+                "INSERT INTO conversations(id, agent_id, source_id, source_path, started_at) \
+                 VALUES(1, 1, 'local', '/tmp/real.jsonl', 0)",
+                fparams![],
+            )
+            .unwrap();
+        storage
+            .raw()
+            .execute_compat(
+                "INSERT INTO messages(id, conversation_id, idx, role, content) \
+                 VALUES(1, 1, 0, 'user', 'real message')",
+                fparams![],
+            )
+            .unwrap();
 
-        // Acquire writer and insert data
-        {
-            let mut guard = mgr.writer().unwrap();
-            guard
-                .storage()
+        // Plant orphan messages referencing conversation_id=99999 (does not exist)
+        // and conversation_id=0 (the specific shape reported in #202). Distinct
+        // (conversation_id, idx) pairs are required by the UNIQUE constraint.
+        for (mid, cid, idx) in [(101_i64, 99_999_i64, 0_i64), (102, 0, 0), (103, 0, 1)] {
+            storage
                 .raw()
-                .execute("CREATE TABLE IF NOT EXISTS cm_test (id INTEGER PRIMARY KEY, val TEXT)")
+                .execute_compat(
+                    "INSERT INTO messages(id, conversation_id, idx, role, content) \
+                     VALUES(?1, ?2, ?3, 'user', 'orphan message')",
+                    fparams![mid, cid, idx],
+                )
                 .unwrap();
-            guard
-                .storage()
+        }
+
+        // Rows below are not directly orphaned because their immediate
+        // `messages` parent exists, but that parent is itself orphaned. The
+        // cleanup deletes them explicitly before deleting orphan messages so the
+        // FK cascade engine does not have to run one delete program per orphan.
+        for message_id in [1_i64, 101_i64, 102_i64] {
+            storage
                 .raw()
-                .execute("INSERT INTO cm_test (val) VALUES ('hello')")
+                .execute_compat(
+                    "INSERT INTO message_metrics(
+                         message_id, created_at_ms, hour_id, day_id, agent_slug,
+                         role, content_chars, content_tokens_est
+                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 13, 2)",
+                    fparams![message_id],
+                )
+                .unwrap();
+            storage
+                .raw()
+                .execute_compat(
+                    "INSERT INTO token_usage(
+                         message_id, conversation_id, agent_id, timestamp_ms, day_id,
+                         role, content_chars
+                     ) VALUES(?1, 1, 1, 0, 0, 'user', 13)",
+                    fparams![message_id],
+                )
                 .unwrap();
-            guard.mark_committed();
         }
 
-        // Verify via reader (returns MutexGuard<SendFrankenConnection>)
-        let reader_guard = mgr.reader();
-        let rows = reader_guard.query("SELECT val FROM cm_test").unwrap();
-        assert_eq!(rows.len(), 1);
-        assert_eq!(rows[0].get_typed::<String>(0).unwrap(), "hello");
+        // Plant a directly-orphan snippet — message_id=99999 does not exist
+        // anywhere, so this exercises the snippets DELETE path rather than
+        // riding on the cascade from the orphan-message DELETE.
+        storage
+            .raw()
+            .execute_compat(
+                "INSERT INTO snippets(message_id, file_path, start_line, end_line, language, snippet_text) \
+                 VALUES(99999, '/tmp/orphan-snippet.rs', 1, 2, 'rust', 'fn main() {}')",
+                fparams![],
+            )
+            .unwrap();
+
+        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
+
+        // Sanity: the planted orphans are visible.
+        let messages_before: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(messages_before, 4); // 1 real + 3 orphans
+        let snippets_before: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM snippets", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(snippets_before, 1);
+        let metrics_before: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(metrics_before, 3);
+        let token_usage_before: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM token_usage", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(token_usage_before, 3);
+
+        // Run the self-heal.
+        let report = storage.cleanup_orphan_fk_rows().unwrap();
+
+        // 3 orphan messages + 1 directly-orphan snippet = 4 primary orphans
+        // reported. Dependent message_metrics/token_usage rows for orphan
+        // messages are pruned too, but they are not double-counted because the
+        // orphan message is the root row that made them invalid.
+        let messages_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(messages_after, 1, "real message must be preserved");
+        let snippets_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM snippets", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(snippets_after, 0);
+        let metrics_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(metrics_after, 1, "real message metric must be preserved");
+        let token_usage_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM token_usage", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(token_usage_after, 1, "real token row must be preserved");
+
+        assert_eq!(report.total, 4, "report total: {:?}", report);
+        let messages_count = report
+            .per_table
+            .iter()
+            .find(|(t, _)| *t == "messages")
+            .map(|(_, c)| *c);
+        assert_eq!(messages_count, Some(3));
+        let snippets_count = report
+            .per_table
+            .iter()
+            .find(|(t, _)| *t == "snippets")
+            .map(|(_, c)| *c);
+        assert_eq!(snippets_count, Some(1));
+
+        // Second invocation on a now-clean DB must be a no-op.
+        let second = storage.cleanup_orphan_fk_rows().unwrap();
+        assert_eq!(second.total, 0);
+        assert!(second.per_table.is_empty());
     }
 
     #[test]
-    fn connection_manager_writer_guard_drops_releases_slot() {
+    fn cleanup_orphan_fk_rows_handles_more_than_one_delete_chunk() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
-
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
-
-        let config = ConnectionManagerConfig {
-            reader_count: 1,
-            max_writers: 1,
-        };
-        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+        let db_path = dir.path().join("orphan_fk_chunked_self_heal.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        let orphan_count = ORPHAN_FK_ID_CHUNK_SIZE + 3;
 
-        // Acquire and release writer
+        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
         {
-            let mut guard = mgr.writer().unwrap();
-            guard.mark_committed();
+            let mut tx = storage.raw().transaction().unwrap();
+            for idx in 0..orphan_count {
+                let message_id = 10_000_i64 + i64::try_from(idx).unwrap();
+                let conversation_id = 20_000_i64 + i64::try_from(idx).unwrap();
+                tx.execute_compat(
+                    "INSERT INTO messages(id, conversation_id, idx, role, content) \
+                     VALUES(?1, ?2, 0, 'user', 'orphan message')",
+                    fparams![message_id, conversation_id],
+                )
+                .unwrap();
+                tx.execute_compat(
+                    "INSERT INTO message_metrics(
+                         message_id, created_at_ms, hour_id, day_id, agent_slug,
+                         role, content_chars, content_tokens_est
+                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 14, 2)",
+                    fparams![message_id],
+                )
+                .unwrap();
+            }
+            tx.commit().unwrap();
         }
+        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
 
-        // Should be able to acquire again (slot released)
-        let mut guard2 = mgr.writer().unwrap();
-        guard2.mark_committed();
+        let report = storage.cleanup_orphan_fk_rows().unwrap();
+
+        assert_eq!(report.total, i64::try_from(orphan_count).unwrap());
+        let messages_count = report
+            .per_table
+            .iter()
+            .find(|(table, _)| *table == "messages")
+            .map(|(_, count)| *count);
+        assert_eq!(messages_count, Some(i64::try_from(orphan_count).unwrap()));
+        let messages_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(messages_after, 0);
+        let metrics_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(metrics_after, 0);
     }
 
     #[test]
-    fn connection_manager_concurrent_writer_works() {
-        use frankensqlite::compat::RowExt;
-
+    fn cleanup_orphan_fk_rows_pages_direct_child_orphans() {
         let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("cm.db");
-
-        let fs = FrankenStorage::open(&db_path).unwrap();
-        drop(fs);
-
-        let config = ConnectionManagerConfig {
-            reader_count: 1,
-            max_writers: 2,
-        };
-        let mgr = FrankenConnectionManager::new(&db_path, config).unwrap();
+        let db_path = dir.path().join("direct_orphan_fk_paged_self_heal.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+        let orphan_count = (ORPHAN_FK_ID_CHUNK_SIZE * 2) + 5;
 
+        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
         {
-            let mut guard = mgr.concurrent_writer().unwrap();
-            guard
-                .storage()
-                .raw()
-                .execute("CREATE TABLE IF NOT EXISTS cm_conc (id INTEGER PRIMARY KEY, val TEXT)")
-                .unwrap();
-            guard
-                .storage()
-                .raw()
-                .execute("INSERT INTO cm_conc (val) VALUES ('concurrent')")
+            let mut tx = storage.raw().transaction().unwrap();
+            for idx in 0..orphan_count {
+                let message_id = 50_000_i64 + i64::try_from(idx).unwrap();
+                tx.execute_compat(
+                    "INSERT INTO message_metrics(
+                         message_id, created_at_ms, hour_id, day_id, agent_slug,
+                         role, content_chars, content_tokens_est
+                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 21, 3)",
+                    fparams![message_id],
+                )
                 .unwrap();
-            guard.mark_committed();
+            }
+            tx.commit().unwrap();
         }
+        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
 
-        let reader_guard = mgr.reader();
-        let rows = reader_guard.query("SELECT val FROM cm_conc").unwrap();
-        assert_eq!(rows.len(), 1);
-        assert_eq!(rows[0].get_typed::<String>(0).unwrap(), "concurrent");
+        let report = storage.cleanup_orphan_fk_rows().unwrap();
+
+        assert_eq!(report.total, i64::try_from(orphan_count).unwrap());
+        let metrics_count = report
+            .per_table
+            .iter()
+            .filter(|(table, _)| *table == "message_metrics")
+            .map(|(_, count)| *count)
+            .sum::<i64>();
+        assert_eq!(metrics_count, i64::try_from(orphan_count).unwrap());
+        assert_eq!(
+            report
+                .per_table
+                .iter()
+                .filter(|(table, _)| *table == "message_metrics")
+                .count(),
+            1,
+            "paged cleanup should aggregate report entries by table: {report:?}"
+        );
+        let metrics_after: i64 = storage
+            .raw()
+            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
+                row.get_typed(0)
+            })
+            .unwrap();
+        assert_eq!(metrics_after, 0);
     }
 
     #[test]
-    fn connection_manager_default_config() {
-        let config = ConnectionManagerConfig::default();
-        assert_eq!(config.reader_count, 4);
-        assert!(config.max_writers > 0);
+    fn conversation_chain_hash_is_order_and_content_sensitive() {
+        let base = conversation_chain_hash([(0, "user", "hello"), (1, "agent", "world")]);
+        // Deterministic.
+        assert_eq!(
+            base,
+            conversation_chain_hash([(0, "user", "hello"), (1, "agent", "world")])
+        );
+        // Content, order, and role all perturb the chain.
+        assert_ne!(
+            base,
+            conversation_chain_hash([(0, "user", "hello"), (1, "agent", "world!")])
+        );
+        assert_ne!(
+            base,
+            conversation_chain_hash([(1, "agent", "world"), (0, "user", "hello")])
+        );
+        assert_ne!(
+            base,
+            conversation_chain_hash([(0, "tool", "hello"), (1, "agent", "world")])
+        );
+        // Field boundaries are length-prefixed, not concatenated.
+        assert_ne!(
+            conversation_chain_hash([(0, "ab", "c")]),
+            conversation_chain_hash([(0, "a", "bc")])
+        );
+        // A prefix never shares a hash with the whole conversation.
+        assert_ne!(base, conversation_chain_hash([(0, "user", "hello")]));
     }
 
     #[test]
-    fn purge_agent_archive_data_removes_only_target_agent_and_rebuilds_derived_tables() {
-        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
-        use std::path::PathBuf;
+    fn integrity_seal_roundtrip_detects_tampering() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
 
-        fn seed_conversation(storage: &FrankenStorage, agent_slug: &str, marker: &str) {
-            let agent = Agent {
-                id: None,
-                slug: agent_slug.into(),
-                name: agent_slug.into(),
-                version: None,
-                kind: AgentKind::Cli,
-            };
-            let agent_id = storage.ensure_agent(&agent).unwrap();
-            let conversation = Conversation {
-                id: None,
-                agent_slug: agent_slug.into(),
-                workspace: Some(PathBuf::from("/tmp/workspace")),
-                external_id: Some(format!("{agent_slug}-{marker}")),
-                title: Some(format!("{agent_slug} {marker}")),
-                source_path: PathBuf::from(format!("/tmp/{agent_slug}-{marker}.jsonl")),
-                started_at: Some(1_700_000_000_000),
-                ended_at: Some(1_700_000_000_100),
-                approx_tokens: None,
-                metadata_json: serde_json::Value::Null,
-                messages: vec![
-                    Message {
-                        id: None,
-                        idx: 0,
-                        role: MessageRole::User,
-                        author: Some("user".into()),
-                        created_at: Some(1_700_000_000_010),
-                        content: format!("{agent_slug} {marker} user"),
-                        extra_json: serde_json::Value::Null,
-                        snippets: Vec::new(),
-                    },
-                    Message {
-                        id: None,
-                        idx: 1,
-                        role: MessageRole::Agent,
-                        author: Some("assistant".into()),
-                        created_at: Some(1_700_000_000_020),
-                        content: format!("{agent_slug} {marker} assistant"),
-                        extra_json: serde_json::Value::Null,
-                        snippets: Vec::new(),
-                    },
-                ],
-                source_id: LOCAL_SOURCE_ID.into(),
-                origin_host: None,
-            };
-            storage
-                .insert_conversation_tree(agent_id, None, &conversation)
-                .unwrap();
-        }
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-seal-1".into()),
+            title: Some("Integrity seal".into()),
+            source_path: PathBuf::from("/tmp/conv-seal-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                Message {
+                    id: None,
+                    idx: 0,
+                    role: MessageRole::User,
+                    author: Some("user".into()),
+                    created_at: Some(1_700_000_000_010),
+                    content: "original content".into(),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                },
+                Message {
+                    id: None,
+                    idx: 1,
+                    role: MessageRole::Agent,
+                    author: Some("assistant".into()),
+                    created_at: Some(1_700_000_000_020),
+                    content: "reply".into(),
+                    extra_json: serde_json::Value::Null,
+                    snippets: Vec::new(),
+                },
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .unwrap();
 
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("agent_search.db");
-        let storage = FrankenStorage::open(&db_path).unwrap();
+        // First refresh seals the new conversation; a second is a no-op.
+        assert_eq!(storage.refresh_conversation_integrity().unwrap(), 1);
+        assert_eq!(storage.refresh_conversation_integrity().unwrap(), 0);
 
-        seed_conversation(&storage, "openclaw", "purge-target");
-        seed_conversation(&storage, "codex", "keep-target");
+        let report = storage.verify_conversation_integrity().unwrap();
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.verified, 1);
+        assert_eq!(report.unsealed, 0);
+        assert!(report.mismatched.is_empty());
 
-        let purge = storage.purge_agent_archive_data("openclaw").unwrap();
-        assert_eq!(purge.conversations_deleted, 1);
-        assert_eq!(purge.messages_deleted, 2);
+        // Out-of-band edit: same message count, different content. The seal
+        // must survive the next refresh and fail verification.
+        storage
+            .raw()
+            .execute("UPDATE messages SET content = 'edited after the fact' WHERE idx = 0")
+            .unwrap();
+        assert_eq!(storage.refresh_conversation_integrity().unwrap(), 0);
 
-        storage.rebuild_fts().unwrap();
-        storage.rebuild_analytics().unwrap();
-        storage.rebuild_daily_stats().unwrap();
-        storage.rebuild_token_daily_stats().unwrap();
+        let report = storage.verify_conversation_integrity().unwrap();
+        assert_eq!(report.verified, 0);
+        assert_eq!(report.mismatched.len(), 1);
+        assert_eq!(
+            report.mismatched[0].source_path,
+            "/tmp/conv-seal-1.jsonl".to_string()
+        );
+        assert_ne!(report.mismatched[0].expected, report.mismatched[0].actual);
+    }
 
-        let agents = storage.list_agents().unwrap();
-        assert_eq!(agents.len(), 1);
-        assert_eq!(agents[0].slug, "codex");
-        assert_eq!(storage.total_conversation_count().unwrap(), 1);
-        assert_eq!(storage.total_message_count().unwrap(), 2);
+    #[test]
+    fn quality_refresh_scores_new_conversations_and_stays_incremental() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
 
-        let fts_rows: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM fts_messages", fparams![], |row| {
-                row.get_typed(0)
-            })
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: content.into(),
+            extra_json: serde_json::Value::Null,
+            snippets: Vec::new(),
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-quality-1".into()),
+            title: Some("Quality score".into()),
+            source_path: PathBuf::from("/tmp/conv-quality-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                message(0, MessageRole::User, "please fix the bug"),
+                message(
+                    1,
+                    MessageRole::Agent,
+                    "diff --git a/src/lib.rs b/src/lib.rs",
+                ),
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
-        assert_eq!(fts_rows, 2);
 
-        let total_daily_sessions: i64 = storage
-            .raw()
-            .query_row_map(
-                "SELECT COALESCE(SUM(session_count), 0)
-                 FROM daily_stats
-                 WHERE agent_slug = 'all' AND source_id = 'all'",
-                fparams![],
-                |row| row.get_typed(0),
-            )
+        // First refresh scores the new conversation; a second is a no-op.
+        assert_eq!(storage.refresh_conversation_quality().unwrap(), 1);
+        assert_eq!(storage.refresh_conversation_quality().unwrap(), 0);
+
+        let score = storage
+            .conversation_quality(outcome.conversation_id)
+            .unwrap()
+            .expect("conversation should be scored after refresh");
+        assert!((0.0..=1.0).contains(&score));
+
+        // Appending messages changes the count, so the next refresh
+        // recomputes the score.
+        let mut grown = conversation.clone();
+        grown
+            .messages
+            .push(message(2, MessageRole::User, "now add a regression test"));
+        grown.messages.push(message(
+            3,
+            MessageRole::Agent,
+            "added a test covering the fix",
+        ));
+        storage
+            .insert_conversation_tree(agent_id, None, &grown)
             .unwrap();
-        assert_eq!(total_daily_sessions, 1);
+        assert_eq!(storage.refresh_conversation_quality().unwrap(), 1);
+        let rescored = storage
+            .conversation_quality(outcome.conversation_id)
+            .unwrap()
+            .expect("grown conversation should be rescored");
+        assert!(
+            rescored > score,
+            "longer balanced session should score higher"
+        );
+    }
 
-        let openclaw_token_rows: i64 = storage
-            .raw()
-            .query_row_map(
-                "SELECT COUNT(*) FROM token_daily_stats WHERE agent_slug = 'openclaw'",
-                fparams![],
-                |row| row.get_typed(0),
-            )
+    #[test]
+    fn facet_refresh_derives_facets_and_stays_incremental() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
+        let storage = FrankenStorage::open(&db_path).unwrap();
+
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: content.into(),
+            extra_json: serde_json::Value::Null,
+            snippets: Vec::new(),
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-facet-1".into()),
+            title: Some("Webpack fight".into()),
+            source_path: PathBuf::from("/tmp/conv-facet-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                message(0, MessageRole::User, "why does webpack rebuild everything"),
+                message(1, MessageRole::Agent, "```js\nmodule.exports = {};\n```"),
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
-        assert_eq!(openclaw_token_rows, 0);
+
+        // First refresh derives facets; a second is a no-op.
+        assert_eq!(storage.refresh_conversation_facets(&[]).unwrap(), 1);
+        assert_eq!(storage.refresh_conversation_facets(&[]).unwrap(), 0);
+        assert_eq!(
+            storage
+                .conversation_facets(outcome.conversation_id)
+                .unwrap(),
+            vec![
+                ("framework".to_string(), "webpack".to_string()),
+                ("language".to_string(), "javascript".to_string()),
+            ]
+        );
+
+        // Appending messages changes the count, so the next refresh
+        // re-derives — and stale facets from the old extraction are gone.
+        let mut grown = conversation.clone();
+        grown
+            .messages
+            .push(message(2, MessageRole::Agent, "$ npm install webpack-cli"));
+        storage
+            .insert_conversation_tree(agent_id, None, &grown)
+            .unwrap();
+        assert_eq!(storage.refresh_conversation_facets(&[]).unwrap(), 1);
+        assert_eq!(
+            storage
+                .conversation_facets(outcome.conversation_id)
+                .unwrap(),
+            vec![
+                ("framework".to_string(), "webpack".to_string()),
+                ("language".to_string(), "javascript".to_string()),
+                ("package-manager".to_string(), "npm".to_string()),
+            ]
+        );
     }
 
-    /// #292 ask #2: `cass forget --source-glob` prunes the matching subset of
-    /// conversations from the canonical DB (dry-run reports, --apply deletes),
-    /// leaving non-matching conversations and their messages intact.
     #[test]
-    fn forget_conversations_by_source_glob_dry_run_then_apply() {
-        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
-        use std::path::PathBuf;
+    fn facet_refresh_tags_pii_categories() {
+        let temp = TempDir::new().unwrap();
+        let storage = FrankenStorage::open(&temp.path().join("cass.db")).unwrap();
 
-        fn seed(storage: &FrankenStorage, agent_slug: &str, source_path: &str, marker: &str) {
-            let agent = Agent {
-                id: None,
-                slug: agent_slug.into(),
-                name: agent_slug.into(),
-                version: None,
-                kind: AgentKind::Cli,
-            };
-            let agent_id = storage.ensure_agent(&agent).unwrap();
-            let conversation = Conversation {
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-pii-1".into()),
+            title: Some("Support ticket triage".into()),
+            source_path: PathBuf::from("/tmp/conv-pii-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![Message {
                 id: None,
-                agent_slug: agent_slug.into(),
-                workspace: Some(PathBuf::from("/tmp/workspace")),
-                external_id: Some(format!("{agent_slug}-{marker}")),
-                title: Some(format!("{agent_slug} {marker}")),
-                source_path: PathBuf::from(source_path),
-                started_at: Some(1_700_000_000_000),
-                ended_at: Some(1_700_000_000_100),
-                approx_tokens: None,
-                metadata_json: serde_json::Value::Null,
-                messages: vec![Message {
-                    id: None,
-                    idx: 0,
-                    role: MessageRole::User,
-                    author: Some("user".into()),
-                    created_at: Some(1_700_000_000_010),
-                    content: format!("{marker} content"),
-                    extra_json: serde_json::Value::Null,
-                    snippets: Vec::new(),
-                }],
-                source_id: LOCAL_SOURCE_ID.into(),
-                origin_host: None,
-            };
+                idx: 0,
+                role: MessageRole::User,
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: "Acme Corp wrote from billing@example.com about the outage".into(),
+                extra_json: serde_json::Value::Null,
+                snippets: Vec::new(),
+            }],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .unwrap();
+
+        let names = vec!["Acme Corp".to_string()];
+        assert_eq!(storage.refresh_conversation_facets(&names).unwrap(), 1);
+        assert_eq!(
             storage
-                .insert_conversation_tree(agent_id, None, &conversation)
-                .unwrap();
-        }
+                .conversation_facets(outcome.conversation_id)
+                .unwrap(),
+            vec![
+                ("pii".to_string(), "pii-customer".to_string()),
+                ("pii".to_string(), "pii-email".to_string()),
+            ]
+        );
+    }
 
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("forget.db");
+    #[test]
+    fn token_usage_refresh_persists_exact_counts_and_stays_incremental() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
         let storage = FrankenStorage::open(&db_path).unwrap();
 
-        // Two subagent transcripts (match the glob) + one top-level session.
-        seed(
-            &storage,
-            "claude",
-            "/home/u/.claude/projects/p/sess-1/subagents/agent-aaa.jsonl",
-            "sub-a",
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let message = |idx: i64, role: MessageRole, extra_json: serde_json::Value| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: format!("message {idx}"),
+            extra_json,
+            snippets: Vec::new(),
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-usage-1".into()),
+            title: Some("Usage counted".into()),
+            source_path: PathBuf::from("/tmp/conv-usage-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                message(0, MessageRole::User, serde_json::Value::Null),
+                message(
+                    1,
+                    MessageRole::Agent,
+                    serde_json::json!({
+                        "message": {"usage": {"input_tokens": 1200, "output_tokens": 300}}
+                    }),
+                ),
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
+            .unwrap();
+
+        // First refresh extracts the counts; a second is a no-op.
+        assert_eq!(storage.refresh_token_usage().unwrap(), 1);
+        assert_eq!(storage.refresh_token_usage().unwrap(), 0);
+        assert_eq!(
+            storage
+                .conversation_token_usage(outcome.conversation_id)
+                .unwrap(),
+            Some((1200, 300))
         );
-        seed(
-            &storage,
-            "claude",
-            "/home/u/.claude/projects/p/sess-2/subagents/agent-bbb.jsonl",
-            "sub-b",
+
+        // Exact counts replace the chars/4 estimate on the conversation row.
+        let approx: Vec<i64> = storage
+            .conn
+            .query_map_collect(
+                "SELECT approx_tokens FROM conversations WHERE id = ?1",
+                fparams![outcome.conversation_id],
+                |row: &FrankenRow| row.get_typed(0),
+            )
+            .unwrap();
+        assert_eq!(approx, vec![1500]);
+
+        // The monthly rollup sees the conversation through its started_at.
+        assert_eq!(
+            storage
+                .token_usage_between(1_699_999_999_999, 1_700_000_000_001)
+                .unwrap(),
+            (1200, 300)
         );
-        seed(
-            &storage,
-            "claude",
-            "/home/u/.claude/projects/p/sess-3/session.jsonl",
-            "top",
+        assert_eq!(storage.token_usage_between(0, 10).unwrap(), (0, 0));
+
+        // Appending a usage-bearing message re-evaluates the conversation.
+        let mut grown = conversation.clone();
+        grown.messages.push(message(
+            2,
+            MessageRole::Agent,
+            serde_json::json!({"usage": {"input_tokens": 800, "output_tokens": 200}}),
+        ));
+        storage
+            .insert_conversation_tree(agent_id, None, &grown)
+            .unwrap();
+        assert_eq!(storage.refresh_token_usage().unwrap(), 1);
+        assert_eq!(
+            storage
+                .conversation_token_usage(outcome.conversation_id)
+                .unwrap(),
+            Some((2000, 500))
         );
-        assert_eq!(storage.total_conversation_count().unwrap(), 3);
+    }
 
-        let glob = "**/subagents/*.jsonl";
+    #[test]
+    fn conversation_uuid_refresh_assigns_stable_ids_once() {
+        let temp = TempDir::new().unwrap();
+        let storage = FrankenStorage::open(&temp.path().join("cass.db")).unwrap();
 
-        // Dry-run: reports matches, deletes nothing.
-        let dry = storage
-            .forget_conversations_by_source_glob(glob, true)
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("conv-uuid-1".into()),
+            title: Some("Durable id".into()),
+            source_path: PathBuf::from("/tmp/conv-uuid-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![Message {
+                id: None,
+                idx: 0,
+                role: MessageRole::User,
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: "please fix the bug".into(),
+                extra_json: serde_json::Value::Null,
+                snippets: Vec::new(),
+            }],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
-        assert!(dry.dry_run);
-        assert_eq!(dry.conversations_matched, 2);
-        assert_eq!(dry.messages_matched, 2);
-        assert_eq!(dry.conversations_deleted, 0);
-        assert_eq!(storage.total_conversation_count().unwrap(), 3);
 
-        // Apply: deletes the two subagent conversations, keeps the top-level one.
-        let applied = storage
-            .forget_conversations_by_source_glob(glob, false)
-            .unwrap();
-        assert!(!applied.dry_run);
-        assert_eq!(applied.conversations_matched, 2);
-        assert_eq!(applied.conversations_deleted, 2);
-        assert_eq!(storage.total_conversation_count().unwrap(), 1);
-        // The surviving top-level session's message is intact.
-        assert_eq!(storage.total_message_count().unwrap(), 1);
+        // First refresh assigns the uuid; a second is a no-op.
+        assert_eq!(storage.refresh_conversation_uuids().unwrap(), 1);
+        assert_eq!(storage.refresh_conversation_uuids().unwrap(), 0);
 
-        // An empty pattern is rejected; a non-matching glob is a clean no-op.
-        assert!(
-            storage
-                .forget_conversations_by_source_glob("   ", true)
-                .is_err()
+        let uuid = storage
+            .conversation_uuid(outcome.conversation_id)
+            .unwrap()
+            .expect("uuid assigned after refresh");
+        assert_eq!(
+            uuid,
+            derive_conversation_uuid("claude_code", "conv-uuid-1", "please fix the bug"),
+            "uuid must be the pure derivation, independent of the rowid"
         );
-        let none = storage
-            .forget_conversations_by_source_glob("**/nope/*.jsonl", false)
+        assert_eq!(uuid.len(), 36, "uuid must be 8-4-4-4-12 shaped: {uuid}");
+        assert_eq!(
+            storage.conversation_id_for_uuid(&uuid).unwrap(),
+            Some(outcome.conversation_id)
+        );
+
+        // A rebuilt database (fresh rowids) re-derives the identical uuid.
+        let rebuilt = FrankenStorage::open(&temp.path().join("rebuilt.db")).unwrap();
+        let rebuilt_agent_id = rebuilt.ensure_agent(&agent).unwrap();
+        let rebuilt_outcome = rebuilt
+            .insert_conversation_tree(rebuilt_agent_id, None, &conversation)
             .unwrap();
-        assert_eq!(none.conversations_matched, 0);
-        assert_eq!(none.conversations_deleted, 0);
-        assert_eq!(storage.total_conversation_count().unwrap(), 1);
+        assert_eq!(rebuilt.refresh_conversation_uuids().unwrap(), 1);
+        assert_eq!(
+            rebuilt
+                .conversation_uuid(rebuilt_outcome.conversation_id)
+                .unwrap(),
+            Some(uuid)
+        );
     }
 
-    /// Regression for cass#202: a `Connection` dropped mid-transaction can
-    /// leave child rows persisted without a matching parent. The next indexer
-    /// pass then trips `FOREIGN KEY constraint failed` on every write, the
-    /// session never gets marked indexed, and the pending backlog grows
-    /// without bound. `cleanup_orphan_fk_rows` is the indexer-startup
-    /// self-heal that breaks the cycle.
     #[test]
-    fn cleanup_orphan_fk_rows_removes_orphans_and_is_noop_on_clean_db() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("orphan_fk_self_heal.db");
-        let storage = FrankenStorage::open(&db_path).unwrap();
+    fn plan_artifact_message_attaches_once_with_fts_row() {
+        let temp = TempDir::new().unwrap();
+        let storage = FrankenStorage::open(&temp.path().join("cass.db")).unwrap();
 
-        // Plant orphan rows directly: rows whose FK parent does not exist.
-        // FK enforcement is temporarily off so the planted rows can land.
-        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
-
-        // Seed a real conversation so a subset of children DO have valid
-        // parents — we want the cleanup to be precise, not a table-flush.
-        storage
-            .raw()
-            .execute_compat(
-                "INSERT INTO agents(id, slug, name, kind, created_at, updated_at) \
-                 VALUES(1, 'test-agent', 'Test Agent', 'cli', 0, 0)",
-                fparams![],
-            )
-            .unwrap();
-        storage
-            .raw()
-            .execute_compat(
-                "INSERT INTO conversations(id, agent_id, source_id, source_path, started_at) \
-                 VALUES(1, 1, 'local', '/tmp/real.jsonl', 0)",
-                fparams![],
-            )
-            .unwrap();
-        storage
-            .raw()
-            .execute_compat(
-                "INSERT INTO messages(id, conversation_id, idx, role, content) \
-                 VALUES(1, 1, 0, 'user', 'real message')",
-                fparams![],
-            )
+        let agent = Agent {
+            id: None,
+            slug: "gemini".into(),
+            name: "Gemini".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "gemini".into(),
+            workspace: Some(PathBuf::from("/tmp/workspace")),
+            external_id: Some("gem-1".into()),
+            title: Some("Gemini session".into()),
+            source_path: PathBuf::from("/tmp/hashdir/logs.json"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![Message {
+                id: None,
+                idx: 0,
+                role: MessageRole::User,
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: "build the widget".into(),
+                extra_json: serde_json::Value::Null,
+                snippets: Vec::new(),
+            }],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        let outcome = storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
 
-        // Plant orphan messages referencing conversation_id=99999 (does not exist)
-        // and conversation_id=0 (the specific shape reported in #202). Distinct
-        // (conversation_id, idx) pairs are required by the UNIQUE constraint.
-        for (mid, cid, idx) in [(101_i64, 99_999_i64, 0_i64), (102, 0, 0), (103, 0, 1)] {
-            storage
-                .raw()
-                .execute_compat(
-                    "INSERT INTO messages(id, conversation_id, idx, role, content) \
-                     VALUES(?1, ?2, ?3, 'user', 'orphan message')",
-                    fparams![mid, cid, idx],
-                )
-                .unwrap();
-        }
+        assert_eq!(
+            storage.plan_artifact_targets().unwrap().len(),
+            1,
+            "gemini conversations must be artifact targets"
+        );
 
-        // Rows below are not directly orphaned because their immediate
-        // `messages` parent exists, but that parent is itself orphaned. The
-        // cleanup deletes them explicitly before deleting orphan messages so the
-        // FK cascade engine does not have to run one delete program per orphan.
-        for message_id in [1_i64, 101_i64, 102_i64] {
+        // First attach writes the message; a repeat for the same path is a
+        // no-op regardless of content.
+        assert!(
             storage
-                .raw()
-                .execute_compat(
-                    "INSERT INTO message_metrics(
-                         message_id, created_at_ms, hour_id, day_id, agent_slug,
-                         role, content_chars, content_tokens_est
-                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 13, 2)",
-                    fparams![message_id],
+                .attach_plan_artifact_message(
+                    outcome.conversation_id,
+                    "/tmp/hashdir/plan.md",
+                    1_700_000_000_050,
+                    "[plan artifact] plan.md\n\nwidget design decisions",
                 )
-                .unwrap();
-            storage
-                .raw()
-                .execute_compat(
-                    "INSERT INTO token_usage(
-                         message_id, conversation_id, agent_id, timestamp_ms, day_id,
-                         role, content_chars
-                     ) VALUES(?1, 1, 1, 0, 0, 'user', 13)",
-                    fparams![message_id],
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .attach_plan_artifact_message(
+                    outcome.conversation_id,
+                    "/tmp/hashdir/plan.md",
+                    1_700_000_999_999,
+                    "changed",
                 )
-                .unwrap();
-        }
-
-        // Plant a directly-orphan snippet — message_id=99999 does not exist
-        // anywhere, so this exercises the snippets DELETE path rather than
-        // riding on the cascade from the orphan-message DELETE.
-        storage
-            .raw()
-            .execute_compat(
-                "INSERT INTO snippets(message_id, file_path, start_line, end_line, language, snippet_text) \
-                 VALUES(99999, '/tmp/orphan-snippet.rs', 1, 2, 'rust', 'fn main() {}')",
-                fparams![],
-            )
-            .unwrap();
-
-        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
+                .unwrap()
+        );
 
-        // Sanity: the planted orphans are visible.
-        let messages_before: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(messages_before, 4); // 1 real + 3 orphans
-        let snippets_before: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM snippets", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(snippets_before, 1);
-        let metrics_before: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
-                row.get_typed(0)
-            })
+        let rows: Vec<(i64, String, String, String)> = storage
+            .conn
+            .query_map_collect(
+                "SELECT idx, role, COALESCE(author, ''), content FROM messages
+                 WHERE conversation_id = ?1 ORDER BY idx",
+                fparams![outcome.conversation_id],
+                |row: &FrankenRow| {
+                    Ok((
+                        row.get_typed(0)?,
+                        row.get_typed(1)?,
+                        row.get_typed(2)?,
+                        row.get_typed(3)?,
+                    ))
+                },
+            )
             .unwrap();
-        assert_eq!(metrics_before, 3);
-        let token_usage_before: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM token_usage", fparams![], |row| {
-                row.get_typed(0)
-            })
+        assert_eq!(rows.len(), 2, "exactly one artifact message: {rows:?}");
+        let (idx, role, author, content) = &rows[1];
+        assert_eq!(*idx, 1, "artifact appends after the last turn");
+        assert_eq!(role, "system");
+        assert_eq!(author, "plan-artifact");
+        assert!(content.contains("widget design decisions"));
+
+        // The plan text must be searchable.
+        let hits: Vec<i64> = storage
+            .conn
+            .query_map_collect(
+                "SELECT rowid FROM fts_messages WHERE fts_messages MATCH 'widget design'",
+                &[],
+                |row: &FrankenRow| row.get_typed(0),
+            )
             .unwrap();
-        assert_eq!(token_usage_before, 3);
+        assert!(!hits.is_empty(), "artifact message missing from FTS");
+    }
 
-        // Run the self-heal.
-        let report = storage.cleanup_orphan_fk_rows().unwrap();
+    #[test]
+    fn in_memory_storage_supports_ephemeral_fts_search() {
+        let storage = FrankenStorage::open_in_memory().unwrap();
 
-        // 3 orphan messages + 1 directly-orphan snippet = 4 primary orphans
-        // reported. Dependent message_metrics/token_usage rows for orphan
-        // messages are pruned too, but they are not double-counted because the
-        // orphan message is the root row that made them invalid.
-        let messages_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(messages_after, 1, "real message must be preserved");
-        let snippets_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM snippets", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(snippets_after, 0);
-        let metrics_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(metrics_after, 1, "real message metric must be preserved");
-        let token_usage_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM token_usage", fparams![], |row| {
-                row.get_typed(0)
-            })
+        let agent = Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "claude_code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: content.into(),
+            extra_json: serde_json::Value::Null,
+            snippets: Vec::new(),
+        };
+        let conversation = Conversation {
+            id: None,
+            agent_slug: "claude_code".into(),
+            workspace: None,
+            external_id: Some("conv-ephemeral-1".into()),
+            title: Some("Parser crash".into()),
+            source_path: PathBuf::from("/tmp/conv-ephemeral-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_000_000_100),
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages: vec![
+                message(0, MessageRole::User, "the tokenizer panics on emoji"),
+                message(1, MessageRole::Agent, "clamping the byte offset fixes it"),
+            ],
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conversation)
             .unwrap();
-        assert_eq!(token_usage_after, 1, "real token row must be preserved");
 
-        assert_eq!(report.total, 4, "report total: {:?}", report);
-        let messages_count = report
-            .per_table
-            .iter()
-            .find(|(t, _)| *t == "messages")
-            .map(|(_, c)| *c);
-        assert_eq!(messages_count, Some(3));
-        let snippets_count = report
-            .per_table
-            .iter()
-            .find(|(t, _)| *t == "snippets")
-            .map(|(_, c)| *c);
-        assert_eq!(snippets_count, Some(1));
+        // Fresh schemas drop `fts_messages` (migration v14); the ephemeral
+        // path builds it on demand before querying.
+        storage.rebuild_fts_via_frankensqlite().unwrap();
+        let hits = storage.search_messages_fts("tokenizer", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].agent, "claude_code");
+        assert_eq!(hits[0].title.as_deref(), Some("Parser crash"));
+        assert_eq!(hits[0].message_idx, 0);
+        assert!(hits[0].content.contains("tokenizer"));
 
-        // Second invocation on a now-clean DB must be a no-op.
-        let second = storage.cleanup_orphan_fk_rows().unwrap();
-        assert_eq!(second.total, 0);
-        assert!(second.per_table.is_empty());
+        assert!(
+            storage
+                .search_messages_fts("nonexistent_term", 10)
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
-    fn cleanup_orphan_fk_rows_handles_more_than_one_delete_chunk() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("orphan_fk_chunked_self_heal.db");
-        let storage = FrankenStorage::open(&db_path).unwrap();
-        let orphan_count = ORPHAN_FK_ID_CHUNK_SIZE + 3;
-
-        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
-        {
-            let mut tx = storage.raw().transaction().unwrap();
-            for idx in 0..orphan_count {
-                let message_id = 10_000_i64 + i64::try_from(idx).unwrap();
-                let conversation_id = 20_000_i64 + i64::try_from(idx).unwrap();
-                tx.execute_compat(
-                    "INSERT INTO messages(id, conversation_id, idx, role, content) \
-                     VALUES(?1, ?2, 0, 'user', 'orphan message')",
-                    fparams![message_id, conversation_id],
-                )
-                .unwrap();
-                tx.execute_compat(
-                    "INSERT INTO message_metrics(
-                         message_id, created_at_ms, hour_id, day_id, agent_slug,
-                         role, content_chars, content_tokens_est
-                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 14, 2)",
-                    fparams![message_id],
-                )
-                .unwrap();
-            }
-            tx.commit().unwrap();
-        }
-        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
+    fn directory_writable_probes_with_a_real_write() {
+        let temp = TempDir::new().unwrap();
+        assert!(directory_writable(temp.path()));
+        assert!(!directory_writable(&temp.path().join("does-not-exist")));
+        // No probe file left behind.
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 0);
+    }
 
-        let report = storage.cleanup_orphan_fk_rows().unwrap();
+    #[cfg(unix)]
+    #[test]
+    fn open_rw_or_readonly_serves_a_readonly_location() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
+        FrankenStorage::open(&db_path).unwrap().close().unwrap();
+
+        let restore_mode = std::fs::metadata(temp.path()).unwrap().permissions().mode();
+        let mut readonly = std::fs::metadata(temp.path()).unwrap().permissions();
+        readonly.set_mode(0o555);
+        std::fs::set_permissions(temp.path(), readonly).unwrap();
+
+        // Under root the kernel ignores the mode bits and the probe rightly
+        // reports writable; either way the open must succeed and serve reads
+        // without attempting recovery against a location it cannot write.
+        let location_writable = db_location_writable(&db_path);
+        let storage = FrankenStorage::open_rw_or_readonly(&db_path).unwrap();
+        let version = storage.schema_version().unwrap();
+        assert!(version >= 1, "schema unreadable through fallback open");
+        drop(storage);
 
-        assert_eq!(report.total, i64::try_from(orphan_count).unwrap());
-        let messages_count = report
-            .per_table
-            .iter()
-            .find(|(table, _)| *table == "messages")
-            .map(|(_, count)| *count);
-        assert_eq!(messages_count, Some(i64::try_from(orphan_count).unwrap()));
-        let messages_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM messages", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(messages_after, 0);
-        let metrics_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
-                row.get_typed(0)
-            })
-            .unwrap();
-        assert_eq!(metrics_after, 0);
+        let mut restore = std::fs::metadata(temp.path()).unwrap().permissions();
+        restore.set_mode(restore_mode);
+        std::fs::set_permissions(temp.path(), restore).unwrap();
+        assert!(
+            location_writable || db_location_writable(&db_path),
+            "restored directory should probe writable again"
+        );
     }
 
     #[test]
-    fn cleanup_orphan_fk_rows_pages_direct_child_orphans() {
-        let dir = TempDir::new().unwrap();
-        let db_path = dir.path().join("direct_orphan_fk_paged_self_heal.db");
+    fn agent_run_refresh_classifies_headless_runs() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("cass.db");
         let storage = FrankenStorage::open(&db_path).unwrap();
-        let orphan_count = (ORPHAN_FK_ID_CHUNK_SIZE * 2) + 5;
-
-        storage.raw().execute("PRAGMA foreign_keys = OFF").unwrap();
-        {
-            let mut tx = storage.raw().transaction().unwrap();
-            for idx in 0..orphan_count {
-                let message_id = 50_000_i64 + i64::try_from(idx).unwrap();
-                tx.execute_compat(
-                    "INSERT INTO message_metrics(
-                         message_id, created_at_ms, hour_id, day_id, agent_slug,
-                         role, content_chars, content_tokens_est
-                     ) VALUES(?1, 0, 0, 0, 'test-agent', 'user', 21, 3)",
-                    fparams![message_id],
-                )
-                .unwrap();
-            }
-            tx.commit().unwrap();
-        }
-        storage.raw().execute("PRAGMA foreign_keys = ON").unwrap();
 
-        let report = storage.cleanup_orphan_fk_rows().unwrap();
+        let agent = Agent {
+            id: None,
+            slug: "codex".into(),
+            name: "Codex".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        };
+        let agent_id = storage.ensure_agent(&agent).unwrap();
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: content.into(),
+            extra_json: serde_json::Value::Null,
+            snippets: Vec::new(),
+        };
+        let conversation = |external_id: &str, messages: Vec<Message>| Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: Some(external_id.into()),
+            title: None,
+            source_path: PathBuf::from(format!("/tmp/{external_id}.jsonl")),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: serde_json::Value::Null,
+            messages,
+            source_id: LOCAL_SOURCE_ID.into(),
+            origin_host: None,
+        };
 
-        assert_eq!(report.total, i64::try_from(orphan_count).unwrap());
-        let metrics_count = report
-            .per_table
-            .iter()
-            .filter(|(table, _)| *table == "message_metrics")
-            .map(|(_, count)| *count)
-            .sum::<i64>();
-        assert_eq!(metrics_count, i64::try_from(orphan_count).unwrap());
-        assert_eq!(
-            report
-                .per_table
-                .iter()
-                .filter(|(table, _)| *table == "message_metrics")
-                .count(),
-            1,
-            "paged cleanup should aggregate report entries by table: {report:?}"
-        );
-        let metrics_after: i64 = storage
-            .raw()
-            .query_row_map("SELECT COUNT(*) FROM message_metrics", fparams![], |row| {
-                row.get_typed(0)
-            })
+        let headless = storage
+            .insert_conversation_tree(
+                agent_id,
+                None,
+                &conversation(
+                    "run-1",
+                    vec![
+                        message(0, MessageRole::User, "run the test suite"),
+                        message(1, MessageRole::Tool, "cargo test ..."),
+                        message(2, MessageRole::Tool, "process exited with code 101"),
+                    ],
+                ),
+            )
             .unwrap();
-        assert_eq!(metrics_after, 0);
+        let interactive = storage
+            .insert_conversation_tree(
+                agent_id,
+                None,
+                &conversation(
+                    "chat-1",
+                    vec![
+                        message(0, MessageRole::User, "what does this mean?"),
+                        message(1, MessageRole::Agent, "a borrow checker complaint"),
+                        message(2, MessageRole::User, "how do I fix it?"),
+                        message(3, MessageRole::Agent, "clone before the loop"),
+                    ],
+                ),
+            )
+            .unwrap();
+
+        // First refresh classifies both conversations; a second is a no-op.
+        assert_eq!(storage.refresh_agent_runs().unwrap(), 2);
+        assert_eq!(storage.refresh_agent_runs().unwrap(), 0);
+
+        let run = storage
+            .agent_run_classification(headless.conversation_id)
+            .unwrap()
+            .expect("headless conversation should be classified");
+        assert!(run.is_run);
+        assert!(run.exec_heavy);
+        assert_eq!(run.exit_code, Some(101));
+
+        let chat = storage
+            .agent_run_classification(interactive.conversation_id)
+            .unwrap()
+            .expect("interactive conversation should be classified");
+        assert!(!chat.is_run);
     }
 }
@@ -0,0 +1,331 @@
+//! Streaming NDJSON import for programmatic ingestion.
+//!
+//! `cass import ndjson -` reads one JSON object per line from stdin (or a
+//! file) and persists each as a conversation through the same
+//! `persist_conversation` dedupe/upsert path the connectors use. This is the
+//! escape hatch for custom scripts and CI agents that want sessions in the
+//! index without writing a full connector: emit objects shaped like
+//! `NormalizedConversation` and pipe them in.
+//!
+//! The wire shape is deliberately forgiving — only `agent_slug`, `messages`,
+//! and a stable identity (`external_id` or `source_path`) are required;
+//! unknown fields are ignored so producers can carry extra data without
+//! breaking on cass upgrades. Re-importing the same objects is idempotent:
+//! identity flows through the normal upsert keys, so a CI job can replay its
+//! whole history on every run.
+
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::connectors::{NormalizedConversation, NormalizedMessage};
+
+/// How many per-line parse errors are kept verbatim in the report. Past this
+/// the count still climbs but the messages are dropped — a malformed feed
+/// should not balloon robot output.
+pub const MAX_REPORTED_ERRORS: usize = 20;
+
+/// One conversation object on the NDJSON stream.
+#[derive(Debug, Deserialize)]
+struct StreamConversation {
+    agent_slug: String,
+    #[serde(default)]
+    external_id: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    workspace: Option<PathBuf>,
+    #[serde(default)]
+    source_path: Option<PathBuf>,
+    #[serde(default)]
+    started_at: Option<i64>,
+    #[serde(default)]
+    ended_at: Option<i64>,
+    #[serde(default)]
+    metadata: Option<Value>,
+    #[serde(default)]
+    messages: Vec<StreamMessage>,
+}
+
+/// One message within a streamed conversation.
+#[derive(Debug, Deserialize)]
+struct StreamMessage {
+    role: String,
+    content: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    created_at: Option<i64>,
+    #[serde(default)]
+    extra: Option<Value>,
+}
+
+/// A line that could not be ingested, with its 1-indexed position.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StreamLineError {
+    /// 1-indexed line number on the input stream.
+    pub line: usize,
+    /// Why the line was skipped.
+    pub error: String,
+}
+
+/// Outcome of one streaming import, suitable for robot output.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StreamImportReport {
+    /// Non-empty lines seen on the stream.
+    pub lines: usize,
+    /// Conversations persisted (new or updated rows).
+    pub imported: usize,
+    /// Messages persisted across all imported conversations.
+    pub messages: usize,
+    /// Lines skipped (bad JSON, missing identity, or no usable messages).
+    pub skipped: usize,
+    /// The first [`MAX_REPORTED_ERRORS`] skip reasons, in stream order.
+    pub errors: Vec<StreamLineError>,
+}
+
+/// Parse one NDJSON line into a [`NormalizedConversation`].
+///
+/// Enforces the identity requirement (an `external_id` or an explicit
+/// `source_path` — without one, re-imports could not dedupe) and drops
+/// messages with empty content, renumbering `idx` sequentially over what
+/// remains.
+pub fn parse_stream_line(line: &str) -> std::result::Result<NormalizedConversation, String> {
+    let parsed: StreamConversation =
+        serde_json::from_str(line).map_err(|e| format!("invalid JSON: {e}"))?;
+    if parsed.agent_slug.trim().is_empty() {
+        return Err("agent_slug must be non-empty".to_string());
+    }
+    let source_path = match (&parsed.source_path, &parsed.external_id) {
+        (Some(path), _) => path.clone(),
+        // Synthetic but deterministic: the same object re-imported later maps
+        // to the same row.
+        (None, Some(id)) => PathBuf::from(format!("stream-import/{}/{id}", parsed.agent_slug)),
+        (None, None) => {
+            return Err(
+                "no stable identity: provide external_id or source_path so re-imports dedupe"
+                    .to_string(),
+            );
+        }
+    };
+
+    let messages: Vec<NormalizedMessage> = parsed
+        .messages
+        .into_iter()
+        .filter(|m| !m.content.trim().is_empty())
+        .enumerate()
+        .map(|(idx, m)| NormalizedMessage {
+            idx: idx as i64,
+            role: m.role,
+            author: m.author,
+            created_at: m.created_at,
+            content: m.content,
+            extra: m.extra.unwrap_or_else(|| serde_json::json!({})),
+            snippets: vec![],
+            invocations: Vec::new(),
+        })
+        .collect();
+    if messages.is_empty() {
+        return Err("no messages with non-empty content".to_string());
+    }
+
+    let started_at = parsed
+        .started_at
+        .or_else(|| messages.iter().find_map(|m| m.created_at));
+    let ended_at = parsed
+        .ended_at
+        .or_else(|| messages.iter().rev().find_map(|m| m.created_at));
+
+    Ok(NormalizedConversation {
+        agent_slug: parsed.agent_slug,
+        external_id: parsed.external_id,
+        title: parsed.title,
+        workspace: parsed.workspace,
+        source_path,
+        started_at,
+        ended_at,
+        metadata: stream_metadata(parsed.metadata),
+        messages,
+    })
+}
+
+/// Provenance metadata for streamed conversations, merged over anything the
+/// producer supplied. The `cass.origin.kind = "imported"` marker matches the
+/// archive importers so streamed sessions are distinguishable from locally
+/// scanned ones.
+fn stream_metadata(provided: Option<Value>) -> Value {
+    let mut metadata = match provided {
+        Some(Value::Object(map)) => Value::Object(map),
+        _ => serde_json::json!({}),
+    };
+    if let Some(obj) = metadata.as_object_mut() {
+        obj.insert(
+            "cass".to_string(),
+            serde_json::json!({
+                "origin": { "kind": "imported", "source_id": "local" }
+            }),
+        );
+        obj.entry("import".to_string())
+            .or_insert_with(|| serde_json::json!({ "format": "ndjson-stream" }));
+    }
+    metadata
+}
+
+/// Persist a stream of NDJSON conversation objects into the canonical
+/// archive and lexical index under `data_dir`, returning a report for CLI
+/// output. Blank lines are ignored; bad lines are skipped and reported
+/// rather than aborting the whole stream.
+pub fn import_reader(reader: impl BufRead, data_dir: &Path) -> Result<StreamImportReport> {
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("creating data dir {}", data_dir.display()))?;
+    let db_path = data_dir.join("agent_search.db");
+    let storage = crate::storage::sqlite::FrankenStorage::open(&db_path)
+        .with_context(|| format!("opening archive db at {}", db_path.display()))?;
+    let index_path = crate::search::tantivy::index_dir(data_dir)?;
+    let mut t_index = crate::search::tantivy::TantivyIndex::open_or_create(&index_path)
+        .context("opening lexical index")?;
+
+    let mut report = StreamImportReport {
+        lines: 0,
+        imported: 0,
+        messages: 0,
+        skipped: 0,
+        errors: Vec::new(),
+    };
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("reading input line {}", line_no + 1))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        report.lines += 1;
+        let conv = match parse_stream_line(&line) {
+            Ok(conv) => conv,
+            Err(error) => {
+                report.skipped += 1;
+                if report.errors.len() < MAX_REPORTED_ERRORS {
+                    report.errors.push(StreamLineError {
+                        line: line_no + 1,
+                        error,
+                    });
+                }
+                continue;
+            }
+        };
+        crate::indexer::persist::persist_conversation(&storage, &mut t_index, &conv).with_context(
+            || {
+                format!(
+                    "persisting streamed conversation {}",
+                    conv.external_id.as_deref().unwrap_or("<unknown>")
+                )
+            },
+        )?;
+        report.imported += 1;
+        report.messages += conv.messages.len();
+    }
+    t_index.commit().context("committing lexical index")?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_object_parses_with_synthetic_source_path() {
+        let conv = parse_stream_line(
+            r#"{"agent_slug":"ci-bot","external_id":"run-42",
+                "messages":[{"role":"user","content":"deploy failed"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(conv.agent_slug, "ci-bot");
+        assert_eq!(
+            conv.source_path,
+            PathBuf::from("stream-import/ci-bot/run-42")
+        );
+        assert_eq!(conv.messages.len(), 1);
+        assert_eq!(conv.metadata["cass"]["origin"]["kind"], "imported");
+    }
+
+    #[test]
+    fn identity_is_required() {
+        let err = parse_stream_line(
+            r#"{"agent_slug":"ci-bot","messages":[{"role":"user","content":"x"}]}"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("external_id or source_path"), "{err}");
+    }
+
+    #[test]
+    fn empty_messages_are_dropped_and_idx_renumbered() {
+        let conv = parse_stream_line(
+            r#"{"agent_slug":"a","external_id":"e",
+                "messages":[{"role":"user","content":"  "},
+                            {"role":"agent","content":"kept","created_at":5}]}"#,
+        )
+        .unwrap();
+        assert_eq!(conv.messages.len(), 1);
+        assert_eq!(conv.messages[0].idx, 0);
+        assert_eq!(conv.started_at, Some(5));
+        assert!(
+            parse_stream_line(r#"{"agent_slug":"a","external_id":"e","messages":[]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn producer_metadata_survives_under_provenance_marker() {
+        let conv = parse_stream_line(
+            r#"{"agent_slug":"a","external_id":"e","metadata":{"pipeline":"nightly"},
+                "messages":[{"role":"user","content":"x"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(conv.metadata["pipeline"], "nightly");
+        assert_eq!(conv.metadata["cass"]["origin"]["kind"], "imported");
+        assert_eq!(conv.metadata["import"]["format"], "ndjson-stream");
+    }
+
+    #[test]
+    fn reader_skips_bad_lines_and_reports_them() {
+        let input = "\n{not json}\n{\"agent_slug\":\"a\",\"messages\":[]}\n";
+        let dir = tempfile::tempdir().unwrap();
+        let report = import_reader(std::io::Cursor::new(input), dir.path()).unwrap();
+        assert_eq!(report.lines, 2);
+        assert_eq!(report.imported, 0);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[0].line, 2);
+    }
+
+    #[test]
+    fn reimporting_the_same_stream_is_idempotent() {
+        let line = r#"{"agent_slug":"ci-bot","external_id":"run-1","title":"CI run",
+            "messages":[{"role":"user","content":"build the thing","created_at":1000},
+                        {"role":"agent","content":"done","created_at":2000}]}"#
+            .replace('\n', " ");
+        let dir = tempfile::tempdir().unwrap();
+        let first = import_reader(std::io::Cursor::new(line.clone()), dir.path()).unwrap();
+        assert_eq!(first.imported, 1);
+        assert_eq!(first.messages, 2);
+        let second = import_reader(std::io::Cursor::new(line), dir.path()).unwrap();
+        assert_eq!(second.imported, 1);
+
+        let storage =
+            crate::storage::sqlite::FrankenStorage::open(&dir.path().join("agent_search.db"))
+                .unwrap();
+        use frankensqlite::compat::ConnectionExt;
+        let convs: i64 = storage
+            .raw()
+            .query_row_map(
+                "SELECT COUNT(*) FROM conversations",
+                &[],
+                |r: &frankensqlite::Row| {
+                    use frankensqlite::compat::RowExt;
+                    r.get_typed(0)
+                },
+            )
+            .unwrap();
+        assert_eq!(convs, 1);
+    }
+}
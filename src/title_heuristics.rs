@@ -0,0 +1,204 @@
+//! Heuristic title derivation for untitled sessions.
+//!
+//! Connectors frequently hand over conversations without a title, and the
+//! naive "first user line" fallback surfaces `<environment_context>` dumps,
+//! pasted stack traces, and system-prompt boilerplate in search results. This
+//! module picks the first line that looks like something a human actually
+//! asked: it skips tag/markup lines, tracebacks, log noise, and bare paths,
+//! preferring a real question or imperative request.
+//!
+//! Applied in two places: at the storage boundary for newly ingested
+//! conversations (see `normalized_conversation_for_storage`), and in bulk via
+//! `cass retitle` for rows indexed before the heuristic existed.
+
+use crate::model::types::{Conversation, MessageRole};
+
+/// Display cap matching the repo's other title truncation points.
+pub const MAX_TITLE_CHARS: usize = 80;
+
+/// Lines at least this share non-alphabetic (symbols, digits, punctuation)
+/// read as code or log output rather than prose.
+const MAX_NON_ALPHA_RATIO: f32 = 0.5;
+
+/// True when a line is boilerplate rather than a usable title: markup tags,
+/// stack-trace frames, log output, bare paths, fences, or system-prompt
+/// preamble.
+#[must_use]
+pub fn looks_like_boilerplate(line: &str) -> bool {
+    let line = line.trim();
+    if line.is_empty() {
+        return true;
+    }
+    // Markup / injected context: <environment_context>, <system-reminder>,
+    // XML-ish or templated wrappers.
+    if line.starts_with('<') || line.starts_with("```") || line.starts_with("---") {
+        return true;
+    }
+    // Stack traces and log lines.
+    let lowered = line.to_lowercase();
+    if lowered.starts_with("traceback (")
+        || lowered.starts_with("at ")
+        || lowered.starts_with("error:")
+        || lowered.starts_with("error[")
+        || lowered.starts_with("warning:")
+        || lowered.starts_with("panicked at")
+        || lowered.starts_with("exception")
+        || lowered.contains("stack trace")
+    {
+        return true;
+    }
+    // System-prompt style preamble pasted into the user turn.
+    if lowered.starts_with("you are ") || lowered.starts_with("# context") {
+        return true;
+    }
+    // JSON/structured blobs.
+    if line.starts_with('{') || line.starts_with('[') {
+        return true;
+    }
+    // A bare path or command invocation carries no intent.
+    if !line.contains(' ') && (line.contains('/') || line.contains('\\')) {
+        return true;
+    }
+    // Mostly symbols/digits: diff hunks, hashes, log timestamps.
+    let total = line.chars().count();
+    let non_alpha = line
+        .chars()
+        .filter(|c| !c.is_alphabetic() && !c.is_whitespace())
+        .count();
+    if total >= 8 && (non_alpha as f32 / total as f32) > MAX_NON_ALPHA_RATIO {
+        return true;
+    }
+    false
+}
+
+/// Whether an existing title should be replaced: missing, blank, or itself
+/// boilerplate (the old fallback copied whatever the first user line was).
+#[must_use]
+pub fn title_needs_regeneration(title: Option<&str>) -> bool {
+    match title {
+        None => true,
+        Some(title) => looks_like_boilerplate(title),
+    }
+}
+
+/// Derive a title from message content: the first non-boilerplate line of the
+/// earliest user messages, preferring an actual question when one appears in
+/// the first few candidates.
+#[must_use]
+pub fn derive_title<'a, I>(user_contents: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut first_statement: Option<String> = None;
+    let mut candidates_seen = 0usize;
+    for content in user_contents {
+        let mut in_fence = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence || looks_like_boilerplate(line) {
+                continue;
+            }
+            candidates_seen += 1;
+            if line.ends_with('?') {
+                // A question states the task better than preceding setup prose.
+                return Some(truncate_title(line));
+            }
+            if first_statement.is_none() {
+                first_statement = Some(truncate_title(line));
+            }
+            // Don't dig arbitrarily deep looking for a question; the opening
+            // request is almost always within the first few real lines.
+            if candidates_seen >= 5 {
+                return first_statement;
+            }
+        }
+        if first_statement.is_some() {
+            break;
+        }
+    }
+    first_statement
+}
+
+/// Storage-boundary helper: the title to write for `conv`, or `None` when the
+/// existing title should be kept (or nothing better could be derived).
+#[must_use]
+pub fn derived_title_for_storage(conv: &Conversation) -> Option<String> {
+    if !title_needs_regeneration(conv.title.as_deref()) {
+        return None;
+    }
+    derive_title(
+        conv.messages
+            .iter()
+            .filter(|m| matches!(m.role, MessageRole::User))
+            .map(|m| m.content.as_str()),
+    )
+}
+
+fn truncate_title(line: &str) -> String {
+    let mut title: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    if line.chars().count() > MAX_TITLE_CHARS {
+        title.push('\u{2026}');
+    }
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boilerplate_lines_are_rejected() {
+        assert!(looks_like_boilerplate("<environment_context>"));
+        assert!(looks_like_boilerplate("```rust"));
+        assert!(looks_like_boilerplate("Traceback (most recent call last):"));
+        assert!(looks_like_boilerplate(
+            "at Object.<anonymous> (/app/index.js:1:1)"
+        ));
+        assert!(looks_like_boilerplate("{\"role\": \"user\"}"));
+        assert!(looks_like_boilerplate("/home/user/project/src/main.rs"));
+        assert!(looks_like_boilerplate(
+            "You are a helpful coding assistant."
+        ));
+        assert!(looks_like_boilerplate(""));
+        assert!(!looks_like_boilerplate("fix the login redirect bug"));
+    }
+
+    #[test]
+    fn derive_title_skips_context_dump_and_finds_the_question() {
+        let content = "<environment_context>\nos: linux\n</environment_context>\n\
+                       Here is my setup.\nwhy does the session index twice?";
+        assert_eq!(
+            derive_title([content]).as_deref(),
+            Some("why does the session index twice?")
+        );
+    }
+
+    #[test]
+    fn derive_title_falls_back_to_first_statement() {
+        let content = "```\npanic log here\n```\nplease add retry logic to the uploader";
+        assert_eq!(
+            derive_title([content]).as_deref(),
+            Some("please add retry logic to the uploader")
+        );
+        assert_eq!(derive_title(["<tag>\n{\"k\":1}"]), None);
+    }
+
+    #[test]
+    fn long_titles_are_truncated_with_ellipsis() {
+        let long = "a".repeat(200);
+        let title = derive_title([long.as_str()]).expect("title");
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS + 1);
+        assert!(title.ends_with('\u{2026}'));
+    }
+
+    #[test]
+    fn regeneration_targets_missing_and_boilerplate_titles() {
+        assert!(title_needs_regeneration(None));
+        assert!(title_needs_regeneration(Some("<environment_context>")));
+        assert!(!title_needs_regeneration(Some("fix flaky watcher test")));
+    }
+}
@@ -0,0 +1,427 @@
+//! Exact token-usage ingestion and monthly budget alerts.
+//!
+//! Session files from the usage-reporting agents carry real token counts:
+//! Claude Code records an Anthropic `usage` object (`input_tokens` /
+//! `output_tokens`) on each assistant message, Codex emits cumulative
+//! `token_count` events with a `total_token_usage` snapshot, and
+//! OpenAI-shaped logs use `prompt_tokens` / `completion_tokens`. Those
+//! records survive ingest verbatim inside each message's `extra_json`, so
+//! per-conversation prompt/completion totals are *derived* data — this
+//! module extracts them and `FrankenStorage::refresh_token_usage` persists
+//! them into the `token_usage` side table at the end of index runs, the
+//! same incremental pattern as quality scores and facets. Where exact
+//! counts exist they also replace the chars/4 `approx_tokens` estimate on
+//! the conversation row.
+//!
+//! On top of the persisted totals sits an optional monthly budget, set in
+//! the `[budget]` table of `~/.config/cass/cass.toml`:
+//!
+//! ```toml
+//! [budget]
+//! monthly_tokens  = 50000000
+//! warn_at_percent = 80           # default
+//! hook            = "/path/to/notify.sh"
+//! ```
+//!
+//! (`CASS_BUDGET_MONTHLY_TOKENS` / `CASS_BUDGET_HOOK` override the file,
+//! matching the env-over-config precedence used for search defaults.)
+//! `cass index` and `cass index --watch` check the current calendar month's
+//! usage after each refresh pass and, when the warn threshold is *crossed*
+//! by that run's newly ingested usage, emit a warning and spawn the hook
+//! command once. Comparing before/after keeps the alert edge-triggered: a
+//! watch loop that ingests nothing new does not re-fire every cycle.
+//!
+//! The hook receives the event through `CASS_BUDGET_*` environment
+//! variables (event name, month, used/budget token counts, percent) and is
+//! spawned detached and best-effort — a missing or failing hook never fails
+//! an index run.
+
+use std::path::PathBuf;
+
+use chrono::{Datelike, TimeZone, Utc};
+use serde_json::Value;
+
+use crate::search_defaults::BudgetDefaults;
+use crate::storage::sqlite::FrankenStorage;
+
+/// Token counts found in one message's `extra_json`, plus whether the
+/// record is a running total (Codex `total_token_usage`) rather than a
+/// per-request increment (Claude/OpenAI `usage`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageTokenUsage {
+    pub prompt: i64,
+    pub completion: i64,
+    pub cumulative: bool,
+}
+
+/// Extract a token-usage record from one message's `extra_json`, if the
+/// connector preserved one. Recognized shapes, in the order they are
+/// probed:
+///
+/// - cumulative: `info.total_token_usage`, `payload.info.total_token_usage`,
+///   or a top-level `total_token_usage` (Codex `token_count` events)
+/// - per-request: `usage` or `message.usage` (Claude Code, OpenAI-shaped
+///   logs)
+///
+/// Counts come from `input_tokens`/`output_tokens` or their
+/// `prompt_tokens`/`completion_tokens` aliases; a record where both sides
+/// are missing or zero yields `None`.
+#[must_use]
+pub fn usage_from_extra(extra: &Value) -> Option<MessageTokenUsage> {
+    for path in [
+        &["info", "total_token_usage"][..],
+        &["payload", "info", "total_token_usage"][..],
+        &["total_token_usage"][..],
+    ] {
+        if let Some((prompt, completion)) = counts_at(extra, path) {
+            return Some(MessageTokenUsage {
+                prompt,
+                completion,
+                cumulative: true,
+            });
+        }
+    }
+    for path in [&["usage"][..], &["message", "usage"][..]] {
+        if let Some((prompt, completion)) = counts_at(extra, path) {
+            return Some(MessageTokenUsage {
+                prompt,
+                completion,
+                cumulative: false,
+            });
+        }
+    }
+    None
+}
+
+/// Walk `path` into `extra` and read the prompt/completion pair from the
+/// object found there. `None` when the path is absent, not an object, or
+/// holds no positive count.
+fn counts_at(extra: &Value, path: &[&str]) -> Option<(i64, i64)> {
+    let mut node = extra;
+    for key in path {
+        node = node.get(key)?;
+    }
+    if !node.is_object() {
+        return None;
+    }
+    let read = |keys: [&str; 2]| {
+        keys.iter()
+            .find_map(|key| node.get(key).and_then(Value::as_i64))
+            .unwrap_or(0)
+            .max(0)
+    };
+    let prompt = read(["input_tokens", "prompt_tokens"]);
+    let completion = read(["output_tokens", "completion_tokens"]);
+    (prompt > 0 || completion > 0).then_some((prompt, completion))
+}
+
+/// Fold the usage records of one conversation's messages into exact
+/// `(prompt, completion)` totals. Per-request records are summed;
+/// cumulative snapshots supersede each other, so the largest snapshot wins.
+/// When a conversation somehow carries both shapes the componentwise max of
+/// the two totals is used — a cumulative snapshot already includes the
+/// requests it covers, so adding them would double-count. `None` when no
+/// message carried a usage record (callers fall back to estimates).
+#[must_use]
+pub fn conversation_usage<'a, I>(extras: I) -> Option<(i64, i64)>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut summed: Option<(i64, i64)> = None;
+    let mut cumulative: Option<(i64, i64)> = None;
+    for extra in extras {
+        let Some(usage) = usage_from_extra(extra) else {
+            continue;
+        };
+        let slot = if usage.cumulative {
+            &mut cumulative
+        } else {
+            &mut summed
+        };
+        let (prompt, completion) = slot.unwrap_or((0, 0));
+        *slot = Some(if usage.cumulative {
+            (prompt.max(usage.prompt), completion.max(usage.completion))
+        } else {
+            (
+                prompt.saturating_add(usage.prompt),
+                completion.saturating_add(usage.completion),
+            )
+        });
+    }
+    match (summed, cumulative) {
+        (Some((sp, sc)), Some((cp, cc))) => Some((sp.max(cp), sc.max(cc))),
+        (one, None) | (None, one) => one,
+    }
+}
+
+/// A fully resolved monthly token budget. Only built when a budget is
+/// actually configured — no configuration means no checks and no warnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetConfig {
+    /// Prompt + completion tokens allowed per calendar month (UTC).
+    pub monthly_tokens: i64,
+    /// Percentage of the budget at which the warning fires (1–100).
+    pub warn_at_percent: u8,
+    /// Command spawned when the threshold is crossed, if any.
+    pub hook: Option<PathBuf>,
+}
+
+/// Resolve the budget from the environment and `[budget]` in `cass.toml`,
+/// env winning per key. `None` when no monthly limit is configured
+/// anywhere. A broken config file resolves to `None` too — budget alerts
+/// are advisory and must never fail an index run.
+#[must_use]
+pub fn resolve_budget_config() -> Option<BudgetConfig> {
+    let defaults = crate::search_defaults::load_budget_defaults().unwrap_or_default();
+    budget_config_from_parts(
+        &defaults,
+        dotenvy::var("CASS_BUDGET_MONTHLY_TOKENS").ok(),
+        dotenvy::var("CASS_BUDGET_HOOK").ok(),
+    )
+}
+
+/// Pure resolution against already-read env values, mirroring the
+/// testability contract of `search_defaults::resolve_*`.
+#[must_use]
+pub fn budget_config_from_parts(
+    defaults: &BudgetDefaults,
+    env_monthly_tokens: Option<String>,
+    env_hook: Option<String>,
+) -> Option<BudgetConfig> {
+    let monthly_tokens = env_monthly_tokens
+        .and_then(|raw| raw.trim().parse::<i64>().ok())
+        .or(defaults.monthly_tokens)
+        .filter(|tokens| *tokens > 0)?;
+    let warn_at_percent = defaults.warn_at_percent.unwrap_or(80).clamp(1, 100);
+    let hook = env_hook
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())
+        .or_else(|| defaults.hook.clone())
+        .map(PathBuf::from);
+    Some(BudgetConfig {
+        monthly_tokens,
+        warn_at_percent,
+        hook,
+    })
+}
+
+/// UTC calendar-month bounds around `now_ms`: `[start, end)` in unix
+/// millis plus the `YYYY-MM` label used in warnings and hook payloads.
+#[must_use]
+pub fn month_bounds_ms(now_ms: i64) -> (i64, i64, String) {
+    let now = Utc
+        .timestamp_millis_opt(now_ms)
+        .single()
+        .unwrap_or_else(Utc::now);
+    let start = Utc
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .map(|dt| dt.timestamp_millis())
+        .unwrap_or(now_ms);
+    let (next_year, next_month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    let end = Utc
+        .with_ymd_and_hms(next_year, next_month, 1, 0, 0, 0)
+        .single()
+        .map(|dt| dt.timestamp_millis())
+        .unwrap_or(now_ms);
+    (start, end, format!("{:04}-{:02}", now.year(), now.month()))
+}
+
+/// Edge trigger: the threshold was crossed by this run iff usage was below
+/// it before and is at or above it after.
+#[must_use]
+pub fn threshold_crossed(used_before: i64, used_after: i64, threshold: i64) -> bool {
+    used_before < threshold && used_after >= threshold
+}
+
+/// Current-month usage (prompt + completion) as persisted in the
+/// `token_usage` table, for before/after comparison around a refresh pass.
+/// Errors degrade to 0 — on a pre-v29 database there is simply no usage to
+/// alert on yet.
+#[must_use]
+pub fn month_usage_total(storage: &FrankenStorage, now_ms: i64) -> i64 {
+    let (start, end, _) = month_bounds_ms(now_ms);
+    storage
+        .token_usage_between(start, end)
+        .map(|(prompt, completion)| prompt.saturating_add(completion))
+        .unwrap_or(0)
+}
+
+/// Re-check the month's usage after a refresh pass and, when this run
+/// pushed it across the warn threshold, log a warning and fire the hook.
+/// Returns whether the alert fired (for tests and watch-loop logging).
+pub fn warn_if_crossed(
+    config: &BudgetConfig,
+    storage: &FrankenStorage,
+    used_before: i64,
+    now_ms: i64,
+) -> bool {
+    let (_, _, month) = month_bounds_ms(now_ms);
+    let used_after = month_usage_total(storage, now_ms);
+    let threshold = (config.monthly_tokens / 100).saturating_mul(i64::from(config.warn_at_percent));
+    if !threshold_crossed(used_before, used_after, threshold) {
+        return false;
+    }
+    let percent = (used_after.saturating_mul(100)) / config.monthly_tokens.max(1);
+    tracing::warn!(
+        month = %month,
+        used_tokens = used_after,
+        monthly_tokens = config.monthly_tokens,
+        percent,
+        "token budget threshold crossed"
+    );
+    if let Some(hook) = &config.hook {
+        fire_hook(hook, &month, used_after, config.monthly_tokens, percent);
+    }
+    true
+}
+
+/// Spawn the configured hook command, detached. The event travels in
+/// `CASS_BUDGET_*` env vars so any shell script can consume it without
+/// argument parsing. Failures are logged and swallowed.
+fn fire_hook(hook: &std::path::Path, month: &str, used: i64, budget: i64, percent: i64) {
+    let spawned = std::process::Command::new(hook)
+        .env("CASS_BUDGET_EVENT", "threshold_crossed")
+        .env("CASS_BUDGET_MONTH", month)
+        .env("CASS_BUDGET_USED_TOKENS", used.to_string())
+        .env("CASS_BUDGET_MONTHLY_TOKENS", budget.to_string())
+        .env("CASS_BUDGET_PERCENT", percent.to_string())
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(_) => tracing::debug!(hook = %hook.display(), "spawned budget hook"),
+        Err(err) => {
+            tracing::warn!(hook = %hook.display(), error = %err, "budget hook failed to spawn");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn extracts_claude_and_openai_shaped_usage() {
+        let claude = json!({"message": {"usage": {"input_tokens": 1200, "output_tokens": 340}}});
+        assert_eq!(
+            usage_from_extra(&claude),
+            Some(MessageTokenUsage {
+                prompt: 1200,
+                completion: 340,
+                cumulative: false,
+            })
+        );
+
+        let openai = json!({"usage": {"prompt_tokens": 55, "completion_tokens": 9}});
+        assert_eq!(
+            usage_from_extra(&openai),
+            Some(MessageTokenUsage {
+                prompt: 55,
+                completion: 9,
+                cumulative: false,
+            })
+        );
+
+        assert_eq!(usage_from_extra(&json!({"usage": {}})), None);
+        assert_eq!(usage_from_extra(&json!({"note": "no usage here"})), None);
+    }
+
+    #[test]
+    fn codex_total_token_usage_is_cumulative() {
+        let codex = json!({
+            "payload": {
+                "type": "token_count",
+                "info": {"total_token_usage": {"input_tokens": 9000, "output_tokens": 750}}
+            }
+        });
+        assert_eq!(
+            usage_from_extra(&codex),
+            Some(MessageTokenUsage {
+                prompt: 9000,
+                completion: 750,
+                cumulative: true,
+            })
+        );
+    }
+
+    #[test]
+    fn conversation_usage_sums_requests_and_takes_max_snapshot() {
+        let per_request = [
+            json!({"usage": {"input_tokens": 100, "output_tokens": 10}}),
+            json!({"role": "user"}),
+            json!({"usage": {"input_tokens": 200, "output_tokens": 20}}),
+        ];
+        assert_eq!(conversation_usage(per_request.iter()), Some((300, 30)));
+
+        let snapshots = [
+            json!({"info": {"total_token_usage": {"input_tokens": 500, "output_tokens": 40}}}),
+            json!({"info": {"total_token_usage": {"input_tokens": 900, "output_tokens": 70}}}),
+        ];
+        assert_eq!(conversation_usage(snapshots.iter()), Some((900, 70)));
+
+        assert_eq!(conversation_usage([json!({})].iter()), None);
+    }
+
+    #[test]
+    fn budget_resolution_prefers_env_and_requires_a_limit() {
+        let defaults = BudgetDefaults {
+            monthly_tokens: Some(1_000_000),
+            warn_at_percent: Some(90),
+            hook: Some("/opt/notify.sh".into()),
+        };
+        let resolved = budget_config_from_parts(&defaults, None, None).unwrap();
+        assert_eq!(resolved.monthly_tokens, 1_000_000);
+        assert_eq!(resolved.warn_at_percent, 90);
+        assert_eq!(
+            resolved.hook.as_deref(),
+            Some(std::path::Path::new("/opt/notify.sh"))
+        );
+
+        let overridden =
+            budget_config_from_parts(&defaults, Some("2000000".into()), Some("/tmp/h".into()))
+                .unwrap();
+        assert_eq!(overridden.monthly_tokens, 2_000_000);
+        assert_eq!(
+            overridden.hook.as_deref(),
+            Some(std::path::Path::new("/tmp/h"))
+        );
+
+        assert_eq!(
+            budget_config_from_parts(&BudgetDefaults::default(), None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn month_bounds_cover_the_calendar_month() {
+        // 2026-09-15T12:00:00Z
+        let (start, end, label) = month_bounds_ms(1_789_473_600_000);
+        assert_eq!(label, "2026-09");
+        assert!(start < 1_789_473_600_000 && 1_789_473_600_000 < end);
+        // 30 days of September.
+        assert_eq!(end - start, 30 * 24 * 3600 * 1000);
+
+        // December rolls into January of the next year.
+        let (dec_start, dec_end, dec_label) = month_bounds_ms(1_797_292_800_000);
+        assert_eq!(dec_label, "2026-12");
+        assert_eq!(dec_end - dec_start, 31 * 24 * 3600 * 1000);
+    }
+
+    #[test]
+    fn threshold_alert_is_edge_triggered() {
+        assert!(threshold_crossed(799, 800, 800));
+        assert!(threshold_crossed(0, 1_000, 800));
+        assert!(
+            !threshold_crossed(800, 900, 800),
+            "already over: no re-fire"
+        );
+        assert!(!threshold_crossed(100, 700, 800), "still under");
+    }
+}
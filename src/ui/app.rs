@@ -120,11 +120,12 @@ use crate::ui::components::palette::{
     ScreenshotTarget, TimeFilterPreset, action_by_id, action_id, default_actions, execute_selected,
 };
 use crate::ui::components::pills::Pill;
+use crate::ui::components::result_columns::{ResultColumn, ResultColumnLayout};
 use crate::ui::components::toast::ToastManager;
 use crate::ui::data::{
-    BudgetHealthContract, CockpitState, ConversationView, DiffStrategyContract, InputMode,
-    ResizeRegimeContract, conversation_view_matches_hit, format_time_short,
-    load_conversation_for_hit, search_hit_has_identity_hint,
+    AgentIndexBadge, BudgetHealthContract, CockpitState, ConversationView, DiffStrategyContract,
+    InputMode, ResizeRegimeContract, conversation_view_matches_hit, format_age_compact,
+    format_time_short, load_conversation_for_hit, search_hit_has_identity_hint,
     search_hit_has_secondary_identity_hint,
 };
 use crate::ui::shortcuts;
@@ -174,6 +175,7 @@ pub mod focus_ids {
     pub const SAVED_VIEWS_MODAL: FocusId = 15;
     pub const SOURCE_FILTER_MENU: FocusId = 16;
     pub const DETAIL_MODAL: FocusId = 17;
+    pub const COLUMN_PICKER: FocusId = 18;
     // Focus groups
     pub const GROUP_MAIN: u32 = 99;
     pub const GROUP_PALETTE: u32 = 100;
@@ -184,6 +186,7 @@ pub mod focus_ids {
     pub const GROUP_SAVED_VIEWS: u32 = 105;
     pub const GROUP_SOURCE_FILTER: u32 = 106;
     pub const GROUP_DETAIL_MODAL: u32 = 107;
+    pub const GROUP_COLUMN_PICKER: u32 = 108;
 }
 
 // =========================================================================
@@ -253,8 +256,15 @@ const PANEL_RATIO_MAX: f64 = 0.75;
 const FOOTER_HINT_ROOT_ID: HelpId = HelpId(1_000_000);
 const RESULTS_REVEAL_MIN_HITS: usize = 6;
 const RESULTS_REVEAL_MAX_HITS: usize = 400;
+/// Hits kept per conversation when repeat collapsing is on (Alt+X toggles).
+const TUI_MAX_HITS_PER_CONVERSATION: usize = 3;
 const SURFACE_TRANSITION_DURATION: Duration = Duration::from_millis(160);
 const ANALYTICS_VIEW_TRANSITION_DURATION: Duration = Duration::from_millis(120);
+/// How often follow mode (Alt+F) stats the tailed source file for growth.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(1500);
+/// Header badge age past which an agent renders in the warning style: a
+/// connector with no new sessions for a week has probably stopped working.
+const AGENT_BADGE_STALE_MS: i64 = 7 * 86_400_000;
 
 #[derive(Clone, Debug)]
 struct FooterHintCandidate {
@@ -2749,6 +2759,29 @@ pub struct DetailFindState {
     pub current: usize,
 }
 
+/// In-progress note entry within the detail pane (`a` to annotate).
+#[derive(Clone, Debug, Default)]
+pub struct DetailNoteState {
+    /// Note text being typed
+    pub draft: String,
+    /// 0-based message position the note attaches to; `None` annotates the
+    /// conversation as a whole
+    pub message_idx: Option<usize>,
+}
+
+/// Rendered-markdown cache for the detail Messages tab. Syntect highlighting
+/// of fenced code blocks is the expensive part of a render frame, so
+/// scrolling must not re-highlight every message; entries are keyed by
+/// message index and the whole cache is dropped when the render fingerprint
+/// (conversation, width, theme) changes.
+#[derive(Default)]
+pub struct DetailMdCache {
+    /// Fingerprint of the inputs the cached lines were rendered under.
+    key: u64,
+    /// Owned rendered lines (gutter included) per message index.
+    lines: HashMap<usize, Vec<ftui::text::Line<'static>>>,
+}
+
 /// How results are grouped into panes (G to cycle).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum ResultsGrouping {
@@ -2779,6 +2812,75 @@ impl ResultsGrouping {
     }
 }
 
+/// Coarse recency bucket used to group date-sorted results under fold
+/// headers (Alt+Z folds/unfolds the bucket under the cursor).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateBucket {
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    Older,
+}
+
+impl DateBucket {
+    /// Bucket for a raw timestamp (seconds or milliseconds), using the same
+    /// UTC day arithmetic as the Alt+[/Alt+] timeline jumps.
+    fn for_timestamp(ts: Option<i64>, now_secs: i64) -> Self {
+        let Some(ts) = ts else {
+            return Self::Older;
+        };
+        let days_ago = (now_secs / 86400) - (ts_to_secs(ts) / 86400);
+        match days_ago {
+            // Clock skew can put a hit slightly in the future; file it
+            // under Today rather than inventing a "Tomorrow" bucket.
+            ..=0 => Self::Today,
+            1 => Self::Yesterday,
+            2..=6 => Self::ThisWeek,
+            7..=13 => Self::LastWeek,
+            14..=30 => Self::ThisMonth,
+            _ => Self::Older,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Today => "Today",
+            Self::Yesterday => "Yesterday",
+            Self::ThisWeek => "This week",
+            Self::LastWeek => "Last week",
+            Self::ThisMonth => "This month",
+            Self::Older => "Older",
+        }
+    }
+}
+
+/// Drop every hit of a folded date bucket except the first (which carries the
+/// fold header), returning the kept hits plus `(bucket, hidden)` counts.
+fn fold_collapsed_date_buckets(
+    hits: Vec<SearchHit>,
+    collapsed: &HashSet<DateBucket>,
+    now_secs: i64,
+) -> (Vec<SearchHit>, Vec<(DateBucket, usize)>) {
+    let mut kept = Vec::with_capacity(hits.len());
+    let mut hidden: Vec<(DateBucket, usize)> = Vec::new();
+    let mut seen: HashSet<DateBucket> = HashSet::new();
+    for hit in hits {
+        let bucket = DateBucket::for_timestamp(hit.created_at, now_secs);
+        if collapsed.contains(&bucket) && !seen.insert(bucket) {
+            match hidden.iter_mut().find(|(b, _)| *b == bucket) {
+                Some((_, count)) => *count += 1,
+                None => hidden.push((bucket, 1)),
+            }
+        } else {
+            seen.insert(bucket);
+            kept.push(hit);
+        }
+    }
+    (kept, hidden)
+}
+
 /// Snapshot of undoable state for undo/redo (Ctrl+Z / Ctrl+Y).
 #[derive(Clone, Debug)]
 pub struct UndoEntry {
@@ -2935,6 +3037,14 @@ pub struct ResultItem {
     pub query_highlight_style: ftui::Style,
     /// Whether this row is currently under the mouse cursor.
     pub hovered: bool,
+    /// Enabled metadata columns (set, order, widths) for line two.
+    pub columns: ResultColumnLayout,
+    /// Hits collapsed away from this hit's conversation (rendered as a
+    /// `+N more matches` note on the conversation's last visible hit).
+    pub suppressed_count: usize,
+    /// Date-bucket fold header rendered above this row when it starts a new
+    /// bucket in a date-sorted list (consumes the row's last content line).
+    pub date_header: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -3200,6 +3310,17 @@ fn format_compact_metric(n: i64) -> String {
 }
 
 fn autocomplete_csv_suffix(input: &str, candidates: &BTreeSet<String>) -> Option<String> {
+    autocomplete_csv_suffix_ordered(input, candidates)
+}
+
+/// Like [`autocomplete_csv_suffix`], but honors the iteration order of
+/// `candidates`: the first candidate whose prefix matches wins. Used by the
+/// workspace filter prompt, where the candidate list comes from the index
+/// ranked by recency and "most recently active first" beats alphabetical.
+fn autocomplete_csv_suffix_ordered<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a String>,
+) -> Option<String> {
     let (prefix, suffix) = if let Some(idx) = input.rfind(',') {
         (&input[..=idx], &input[idx + 1..])
     } else {
@@ -3216,7 +3337,7 @@ fn autocomplete_csv_suffix(input: &str, candidates: &BTreeSet<String>) -> Option
 
     let token_lower = token.to_ascii_lowercase();
     let candidate = candidates
-        .iter()
+        .into_iter()
         .find(|value| value.to_ascii_lowercase().starts_with(&token_lower))?;
 
     if candidate.eq_ignore_ascii_case(token) {
@@ -3364,6 +3485,27 @@ fn search_hit_snippet_fallback_text(hit: &SearchHit) -> &str {
     search_hit_snippet_fallback_text_opt(hit).unwrap_or("<no snippet>")
 }
 
+/// First fenced code block in `text`, without the fence lines. An unclosed
+/// fence yields nothing: the "block" is still being streamed or the fences
+/// are unbalanced, and copying half a block is worse than copying none.
+fn first_fenced_code_block(text: &str) -> Option<String> {
+    let mut in_block = false;
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            if in_block {
+                return Some(lines.join("\n"));
+            }
+            in_block = true;
+            continue;
+        }
+        if in_block {
+            lines.push(line);
+        }
+    }
+    None
+}
+
 fn clamp_cursor_boundary(text: &str, cursor: usize) -> usize {
     let mut idx = cursor.min(text.len());
     while idx > 0 && !text.is_char_boundary(idx) {
@@ -3819,19 +3961,12 @@ impl RenderItem for ResultItem {
             | MatchType::Substring => self.text_subtle_style.bold(),
         };
         let match_chip = format!("mt {}", self.match_type_label());
-        let mut meta_spans = vec![
-            ftui::text::Span::styled("      ", bg_style),
-            ftui::text::Span::styled(score_bar, self.score_style),
-            ftui::text::Span::styled(" ", bg_style),
-            ftui::text::Span::styled(
-                source_badge,
-                if source_is_remote {
-                    self.source_remote_style
-                } else {
-                    self.source_local_style
-                },
-            ),
-        ];
+        let source_style = if source_is_remote {
+            self.source_remote_style
+        } else {
+            self.source_local_style
+        };
+        let mut meta_spans = vec![ftui::text::Span::styled("      ", bg_style)];
         let compact_preview = if self.row_height <= 2 {
             let used = 6 + score_bar_chars + 1 + source_badge_chars + 3;
             self.compact_snippet_preview(content_width.saturating_sub(used))
@@ -3839,11 +3974,13 @@ impl RenderItem for ResultItem {
             None
         };
         if let Some(snippet_preview) = compact_preview {
+            // Compact rows keep the fixed layout: every cell counts, so the
+            // snippet preview wins over configurable columns.
+            meta_spans.push(ftui::text::Span::styled(score_bar, self.score_style));
             meta_spans.push(ftui::text::Span::styled(" ", bg_style));
-            meta_spans.push(ftui::text::Span::styled(
-                match_chip.clone(),
-                match_chip_style,
-            ));
+            meta_spans.push(ftui::text::Span::styled(source_badge, source_style));
+            meta_spans.push(ftui::text::Span::styled(" ", bg_style));
+            meta_spans.push(ftui::text::Span::styled(match_chip, match_chip_style));
             let analytics_spans = self.mini_analytics_spans();
             if !analytics_spans.is_empty() {
                 meta_spans.push(ftui::text::Span::styled(" · ", self.text_muted_style));
@@ -3855,38 +3992,74 @@ impl RenderItem for ResultItem {
                 self.text_muted_style,
             ));
         } else {
-            // Structured metadata with per-field styling for visual hierarchy
+            // Column-driven structured metadata with per-field styling.
+            // Score/source badges join with a plain space (they read as one
+            // cluster); the remaining fields are `│`-separated.
             let hit = &self.hit;
-            let ws_label = self.display_workspace_label(32);
-            meta_spans.push(ftui::text::Span::styled(" ", bg_style));
-            meta_spans.push(ftui::text::Span::styled(match_chip, match_chip_style));
+            let mut prev: Option<ResultColumn> = None;
+            for spec in self.columns.specs() {
+                let column_spans: Vec<ftui::text::Span<'static>> = match spec.column {
+                    ResultColumn::Score => vec![ftui::text::Span::styled(
+                        score_bar.clone(),
+                        self.score_style,
+                    )],
+                    ResultColumn::Source => {
+                        vec![ftui::text::Span::styled(source_badge.clone(), source_style)]
+                    }
+                    ResultColumn::Match => vec![ftui::text::Span::styled(
+                        match_chip.clone(),
+                        match_chip_style,
+                    )],
+                    ResultColumn::Agent => vec![ftui::text::Span::styled(
+                        format!("@{}", elide_text(self.display_agent_slug(), 18)),
+                        self.agent_accent_style,
+                    )],
+                    ResultColumn::Workspace => {
+                        let width = spec.effective_width().unwrap_or(32);
+                        vec![ftui::text::Span::styled(
+                            format!("ws {}", self.display_workspace_label(usize::from(width))),
+                            self.text_primary_style,
+                        )]
+                    }
+                    ResultColumn::Date => hit
+                        .created_at
+                        .and_then(smart_timestamp)
+                        .map(|dt| {
+                            vec![ftui::text::Span::styled(
+                                dt.format("%Y-%m-%d %H:%M").to_string(),
+                                self.text_muted_style,
+                            )]
+                        })
+                        .unwrap_or_default(),
+                    ResultColumn::Messages => self.mini_analytics_spans(),
+                };
+                if column_spans.is_empty() {
+                    continue;
+                }
+                match prev {
+                    None => {}
+                    Some(ResultColumn::Score | ResultColumn::Source) => {
+                        meta_spans.push(ftui::text::Span::styled(" ", bg_style));
+                    }
+                    Some(_) => {
+                        meta_spans.push(ftui::text::Span::styled(
+                            " \u{2502} ",
+                            self.text_subtle_style,
+                        ));
+                    }
+                }
+                meta_spans.extend(column_spans);
+                prev = Some(spec.column);
+            }
+        }
+        if self.suppressed_count > 0 {
             meta_spans.push(ftui::text::Span::styled(
-                " \u{2502} ",
+                format!(
+                    " · +{} more matches in this conversation",
+                    self.suppressed_count
+                ),
                 self.text_subtle_style,
             ));
-            meta_spans.push(ftui::text::Span::styled(
-                format!("ws {ws_label}"),
-                self.text_primary_style,
-            ));
-            if let Some(ts) = hit
-                .created_at
-                .and_then(smart_timestamp)
-                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
-            {
-                meta_spans.push(ftui::text::Span::styled(
-                    " \u{2502} ",
-                    self.text_subtle_style,
-                ));
-                meta_spans.push(ftui::text::Span::styled(ts, self.text_muted_style));
-            }
-            let analytics_spans = self.mini_analytics_spans();
-            if !analytics_spans.is_empty() {
-                meta_spans.push(ftui::text::Span::styled(
-                    " \u{2502} ",
-                    self.text_subtle_style,
-                ));
-                meta_spans.extend(analytics_spans);
-            }
         }
         let meta_line = ftui::text::Line::from_spans(meta_spans);
 
@@ -3965,6 +4138,21 @@ impl RenderItem for ResultItem {
             }
         }
 
+        if let Some(header) = &self.date_header {
+            let label = format!(" {header} ");
+            let fill = usize::from(self.max_width)
+                .saturating_sub(display_width(&label) + 4)
+                .max(2);
+            lines.insert(
+                0,
+                ftui::text::Line::from_spans(vec![
+                    ftui::text::Span::styled("\u{2500}\u{2500}".to_string(), self.text_subtle_style),
+                    ftui::text::Span::styled(label, self.text_muted_style.bold()),
+                    ftui::text::Span::styled("\u{2500}".repeat(fill), self.text_subtle_style),
+                ]),
+            );
+        }
+
         let reveal_line_budget = if reveal_progress < 0.34 {
             1usize
         } else if reveal_progress < 0.67 {
@@ -4579,6 +4767,139 @@ fn build_footer_hud_line(
     ftui::text::Line::from_spans(spans)
 }
 
+/// Greedy word-wrap for plain prose (no styling, no unicode-width subtleties)
+/// used by full-pane notices like the database compatibility screen.
+fn wrap_plain_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current = word.to_string();
+        } else if display_width(&current) + 1 + display_width(word) <= width {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current = word.to_string();
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// One-line summary for a collapsed tool/system message: the tool label when
+/// the content starts with a `[Tool: …]` / `[Tool Output]` marker (otherwise a
+/// first-line excerpt), plus the hidden byte count and the exit status when
+/// one is visible near either end of the output.
+fn collapsed_tool_summary(content: &str) -> String {
+    let content = content.trim();
+    let first_line = content.lines().next().unwrap_or("");
+
+    // A leading bracketed marker names the section; everything else gets an
+    // excerpt of the first line.
+    let (label, excerpt_source) = if let Some(rest) = first_line.strip_prefix('[')
+        && let Some(end) = rest.find(']')
+    {
+        (
+            Some(rest[..end].to_string()),
+            content.lines().nth(1).unwrap_or(""),
+        )
+    } else {
+        (None, first_line)
+    };
+
+    let excerpt: String = excerpt_source.trim().chars().take(60).collect();
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(label) = label {
+        parts.push(label);
+    }
+    if !excerpt.is_empty() {
+        parts.push(format!("{excerpt}\u{2026}"));
+    }
+    parts.push(format_collapsed_size(content.len()));
+    if let Some(code) = detect_exit_status(content) {
+        parts.push(format!("exit {code}"));
+    }
+    parts.join(" \u{b7} ")
+}
+
+/// Human-readable byte count for collapsed summaries (`387 B`, `12.4 KB`).
+fn format_collapsed_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Scan the first and last few lines of tool output for an `exit code N` /
+/// `exit status N` marker. Bounded to the edges so huge outputs stay cheap to
+/// summarize on every render.
+fn detect_exit_status(content: &str) -> Option<i64> {
+    let head = content.lines().take(3);
+    let tail = content.lines().rev().take(4);
+    for line in head.chain(tail) {
+        let lower = line.to_ascii_lowercase();
+        for marker in ["exit code", "exit status", "exited with"] {
+            if let Some(pos) = lower.find(marker) {
+                let rest = lower[pos + marker.len()..]
+                    .trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+                let digits: String = rest
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit() || *c == '-')
+                    .collect();
+                if let Ok(code) = digits.parse::<i64>() {
+                    return Some(code);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Build the note-entry bar line shown while annotating (`a` in the detail view).
+fn build_detail_note_bar_line(
+    note: &DetailNoteState,
+    width: u16,
+    query_style: ftui::Style,
+    hint_style: ftui::Style,
+) -> ftui::text::Line<'static> {
+    let max_width = width as usize;
+    if max_width == 0 {
+        return ftui::text::Line::raw(String::new());
+    }
+    let label = match note.message_idx {
+        Some(idx) => format!("\u{270e} msg {} ", idx + 1),
+        None => "\u{270e} conversation ".to_string(),
+    };
+    let hint = " \u{b7} Enter save \u{b7} Esc discard";
+    let mut budget = max_width
+        .saturating_sub(display_width(&label))
+        .saturating_sub(1);
+    let show_hint = budget > display_width(hint) + 8;
+    if show_hint {
+        budget -= display_width(hint);
+    }
+    // Keep the caret end of a long draft visible.
+    let chars: Vec<char> = note.draft.chars().collect();
+    let visible: String = chars[chars.len().saturating_sub(budget)..].iter().collect();
+
+    let mut spans = vec![
+        ftui::text::Span::styled(label, hint_style),
+        ftui::text::Span::styled(visible, query_style),
+        ftui::text::Span::styled("\u{2502}".to_string(), query_style),
+    ];
+    if show_hint {
+        spans.push(ftui::text::Span::styled(hint.to_string(), hint_style));
+    }
+    ftui::text::Line::from_spans(spans)
+}
+
 /// Build the detail-pane find bar line with styled query + match-state segments.
 fn build_detail_find_bar_line(
     find: &DetailFindState,
@@ -5122,6 +5443,17 @@ pub struct CassApp {
     pub filters: SearchFilters,
     /// Last search results (flat list, before pane grouping).
     pub results: Vec<SearchHit>,
+    /// Collapse repeated hits from one conversation down to the top few
+    /// (toggle with Alt+X).
+    pub collapse_conversation_repeats: bool,
+    /// Hits suppressed per `(source_id, source_path)` by repeat collapsing.
+    pub suppressed_hits: HashMap<(String, String), usize>,
+    /// Date buckets folded in date-sorted results (Alt+Z on the bucket under
+    /// the cursor). Session-scoped: survives re-searches, not restarts.
+    pub collapsed_date_buckets: HashSet<DateBucket>,
+    /// Hits hidden per `(pane key, bucket)` by date-bucket folding, shown on
+    /// the fold header.
+    pub date_bucket_hidden: HashMap<(String, DateBucket), usize>,
     /// Results grouped into per-agent panes.
     pub panes: Vec<AgentPane>,
     /// Currently active pane index.
@@ -5214,6 +5546,10 @@ pub struct CassApp {
     pub detail_find: Option<DetailFindState>,
     /// Cache for find-in-detail match line numbers (written during rendering).
     pub detail_find_matches_cache: RefCell<Vec<u32>>,
+    /// In-progress note entry within the detail pane (`a` to annotate).
+    pub detail_note: Option<DetailNoteState>,
+    /// Notes for the open conversation, reloaded on detail open and save.
+    pub detail_notes: Vec<crate::notes::Note>,
     /// Message line numbers (1-indexed) for search hits in the active session.
     /// Used to highlight context and drive hit-to-hit navigation in detail modal.
     pub detail_session_hit_lines: Vec<usize>,
@@ -5225,12 +5561,23 @@ pub struct CassApp {
     pub detail_session_hit_scroll_pending: Cell<bool>,
     /// Pending scroll target computed during render, applied on the next `Tick`.
     pub detail_pending_scroll_to: Cell<Option<u32>>,
+    /// Live-follow mode for the open session (Alt+F): the source file is
+    /// polled and re-tailed through the indexer so new messages append as
+    /// the agent writes them.
+    pub follow_mode: bool,
+    /// Last observed (mtime millis, byte length) of the followed source file.
+    pub follow_stat: Option<(i64, u64)>,
+    /// A follow-mode single-file index pass is running in the background.
+    pub follow_index_in_flight: bool,
     /// Whether the detail drill-in modal is open.
     pub show_detail_modal: bool,
     /// Scroll position within the detail modal.
     pub modal_scroll: u32,
     /// Cached conversation for the currently selected result.
     pub cached_detail: Option<(String, ConversationView)>,
+    /// Per-message rendered-markdown cache for the Messages tab (RefCell for
+    /// view-time mutation).
+    pub detail_md_cache: RefCell<DetailMdCache>,
     /// Whether word-wrap is enabled in the detail pane.
     pub detail_wrap: bool,
     /// Whether the detail preview pane is hidden (maximise results area).
@@ -5239,6 +5586,12 @@ pub struct CassApp {
     /// When a message index is in this set its content is hidden behind a
     /// one-line summary bar; pressing Enter/Space toggles it.
     pub collapsed_tools: HashSet<usize>,
+    /// Whether tool/system messages start collapsed when a session is opened
+    /// (`[tui].collapse_tool_output` in cass.toml; defaults to true).
+    pub collapse_tools_on_open: bool,
+    /// Whether find-in-conversation expands collapsed messages whose hidden
+    /// content matches (`[tui].find_in_collapsed`; defaults to true).
+    pub detail_find_in_collapsed: bool,
 
     /// Whether the aggregate stats bar is visible at the bottom of results pane.
     pub show_stats_bar: bool,
@@ -5292,6 +5645,12 @@ pub struct CassApp {
     pub saved_view_rename_mode: bool,
     /// Rename buffer used while editing saved view labels.
     pub saved_view_rename_buffer: String,
+    /// Enabled results-list metadata columns (set, order, widths).
+    pub result_columns: ResultColumnLayout,
+    /// Whether the results column picker overlay is visible.
+    pub show_column_picker: bool,
+    /// Current cursor position inside the column picker.
+    pub column_picker_cursor: usize,
     /// Whether the consent dialog (model download) is visible.
     pub show_consent_dialog: bool,
     /// Semantic search availability state.
@@ -5334,6 +5693,9 @@ pub struct CassApp {
     pub terminal_focused: bool,
     /// Last known terminal size `(width, height)` for macro metadata and fallbacks.
     pub last_terminal_size: Cell<(u16, u16)>,
+    /// `[min, max]` character bounds for adaptively sized snippets, from
+    /// `[search].snippet_{min,max}_chars` in cass.toml (built-ins otherwise).
+    pub snippet_bounds: (usize, usize),
 
     // -- Animation & timing -----------------------------------------------
     /// Spring-based animation state (focus flash, reveal, modal, panel).
@@ -5421,7 +5783,17 @@ pub struct CassApp {
     pub db_path: PathBuf,
     /// Database reader (initialized on first use).
     pub db_reader: Option<Arc<FrankenStorage>>,
-    /// Known workspace list (populated on first filter prompt).
+    /// Guidance shown instead of results when the database schema is too far
+    /// from this build to read (see `storage::sqlite::check_read_compatibility`).
+    pub db_compat_notice: Option<String>,
+    /// Whether the data dir rejected a write probe at startup (read-only
+    /// mount). Write-backed conveniences — state saves, view-state
+    /// bookmarks, analytics auto-rebuild — are skipped cleanly instead of
+    /// surfacing an error per attempt.
+    pub data_dir_read_only: bool,
+    /// Known workspace list, most recently active first (loaded from the
+    /// index on first workspace filter prompt; feeds `workspace:`
+    /// autocomplete).
     pub known_workspaces: Option<Vec<String>>,
     /// Search service for async query dispatch.
     pub search_service: Option<Arc<dyn SearchService>>,
@@ -5479,6 +5851,13 @@ pub struct CassApp {
     pub(crate) index_progress_snapshot: IndexProgressSnapshot,
     /// Phase accumulator for indeterminate (ping-pong) progress bars.
     pub indeterminate_progress_phase: f64,
+    /// Per-agent indexed conversation counts and last-activity times for the
+    /// header badge strip; refreshed after every index run.
+    pub agent_index_badges: Vec<AgentIndexBadge>,
+    /// True once the agent badge load has completed at least once, so an
+    /// empty badge list means "the index is empty" rather than "still
+    /// loading".
+    pub agent_badges_loaded: bool,
 }
 
 impl Default for CassApp {
@@ -5499,6 +5878,10 @@ impl Default for CassApp {
             query: String::new(),
             filters: SearchFilters::default(),
             results: Vec::new(),
+            collapse_conversation_repeats: true,
+            suppressed_hits: HashMap::new(),
+            collapsed_date_buckets: HashSet::new(),
+            date_bucket_hidden: HashMap::new(),
             panes: Vec::new(),
             active_pane: 0,
             pane_scroll_offset: 0,
@@ -5540,17 +5923,25 @@ impl Default for CassApp {
             detail_tab: DetailTab::default(),
             detail_find: None,
             detail_find_matches_cache: RefCell::new(Vec::new()),
+            detail_note: None,
+            detail_notes: Vec::new(),
             detail_session_hit_lines: Vec::new(),
             detail_session_hit_offsets_cache: RefCell::new(Vec::new()),
             detail_session_hit_current: 0,
             detail_session_hit_scroll_pending: Cell::new(false),
             detail_pending_scroll_to: Cell::new(None),
+            follow_mode: false,
+            follow_stat: None,
+            follow_index_in_flight: false,
             show_detail_modal: false,
             modal_scroll: 0,
             cached_detail: None,
+            detail_md_cache: RefCell::new(DetailMdCache::default()),
             detail_wrap: true,
             detail_pane_hidden: false,
             collapsed_tools: HashSet::new(),
+            collapse_tools_on_open: true,
+            detail_find_in_collapsed: true,
             show_stats_bar: true,
             theme_dark: true,
             theme_preset: UiThemePreset::TokyoNight,
@@ -5575,6 +5966,9 @@ impl Default for CassApp {
             saved_view_drag: None,
             saved_view_rename_mode: false,
             saved_view_rename_buffer: String::new(),
+            result_columns: ResultColumnLayout::default(),
+            show_column_picker: false,
+            column_picker_cursor: 0,
             show_consent_dialog: false,
             semantic_availability: SemanticAvailability::NotInstalled,
             source_filter_menu_open: false,
@@ -5613,6 +6007,7 @@ impl Default for CassApp {
             undo_history: UndoHistory::default(),
             terminal_focused: true,
             last_terminal_size: Cell::new((80, 24)),
+            snippet_bounds: crate::search_defaults::resolve_snippet_bounds(None, None),
             anim: AnimationState::from_env(),
             view_transition: None,
             view_transition_snapshot: RefCell::new(None),
@@ -5651,6 +6046,8 @@ impl Default for CassApp {
             data_dir: crate::default_data_dir(),
             db_path: crate::default_db_path(),
             db_reader: None,
+            db_compat_notice: None,
+            data_dir_read_only: false,
             known_workspaces: None,
             search_service: None,
             progressive_search_service: None,
@@ -5674,6 +6071,8 @@ impl Default for CassApp {
             indexing_progress: None,
             index_progress_snapshot: IndexProgressSnapshot::default(),
             indeterminate_progress_phase: 0.0,
+            agent_index_badges: Vec::new(),
+            agent_badges_loaded: false,
         };
         // Load persisted theme config (if any) and apply overrides to initial options.
         app.refresh_theme_config_from_data_dir();
@@ -5791,6 +6190,11 @@ impl CassApp {
                 .with_tab_index(-1)
                 .with_group(GROUP_DETAIL_MODAL),
         );
+        g.insert(
+            FocusNode::new(COLUMN_PICKER, Rect::new(20, 4, 40, 14))
+                .with_tab_index(-1)
+                .with_group(GROUP_COLUMN_PICKER),
+        );
 
         // -- Focus groups (one per modal, used with push_trap/pop_trap) ---
         self.focus_manager
@@ -5811,6 +6215,8 @@ impl CassApp {
             .create_group(GROUP_SOURCE_FILTER, vec![SOURCE_FILTER_MENU]);
         self.focus_manager
             .create_group(GROUP_DETAIL_MODAL, vec![DETAIL_MODAL]);
+        self.focus_manager
+            .create_group(GROUP_COLUMN_PICKER, vec![COLUMN_PICKER]);
 
         // Start with ResultsList focused (matches legacy default FocusRegion::Results)
         self.focus_manager.focus(RESULTS_LIST);
@@ -5944,6 +6350,205 @@ impl CassApp {
         };
     }
 
+    /// Open the per-conversation view-state store (`data_dir/view_state.db`)
+    /// for writing. Best-effort like the other user-data files: a failure
+    /// (read-only data dir) degrades to no persistence rather than an error.
+    fn view_state_store(&self) -> Option<crate::view_state::ViewStateStore> {
+        if self.data_dir_read_only {
+            return None;
+        }
+        crate::view_state::ViewStateStore::open(&self.data_dir.join("view_state.db"))
+            .map_err(|error| tracing::debug!(%error, "view state store unavailable"))
+            .ok()
+    }
+
+    /// Read-only variant: `None` when the store was never created, so pure
+    /// reads (position restore, bookmark jump) leave no file behind.
+    fn existing_view_state_store(&self) -> Option<crate::view_state::ViewStateStore> {
+        crate::view_state::ViewStateStore::open_existing(&self.data_dir.join("view_state.db"))
+            .ok()
+            .flatten()
+    }
+
+    /// Toggle the persistent bookmark marker for the open conversation at the
+    /// current scroll position (key `m` in the detail view).
+    fn toggle_detail_bookmark(&mut self) {
+        let Some(hit) = self.selected_hit().cloned() else {
+            return;
+        };
+        let Some(store) = self.view_state_store() else {
+            self.status = "Bookmark unavailable (view_state.db not writable).".to_string();
+            return;
+        };
+        let has_bookmark = store
+            .get(&hit.source_path)
+            .ok()
+            .flatten()
+            .is_some_and(|state| state.bookmark_line.is_some());
+        if has_bookmark {
+            let _ = store.clear_bookmark(&hit.source_path);
+            self.status = "Bookmark cleared.".to_string();
+        } else {
+            let line = self.detail_scroll;
+            // Resolve which message the bookmark falls in from the rendered
+            // header offsets (populated during the Messages render) so
+            // non-TUI consumers can address it by message index.
+            let message_idx = self
+                .detail_message_offsets
+                .borrow()
+                .iter()
+                .rposition(|(offset, _)| *offset <= line);
+            match store.set_bookmark(&hit.source_path, line, message_idx) {
+                Ok(()) => {
+                    self.status = "Bookmark set ('m' clears, ' jumps back).".to_string();
+                }
+                Err(error) => self.status = format!("Bookmark failed: {error}"),
+            }
+        }
+    }
+
+    /// Jump the detail view to the persisted bookmark, if one is set.
+    fn jump_to_detail_bookmark(&mut self) {
+        let Some(hit) = self.selected_hit().cloned() else {
+            return;
+        };
+        let bookmark = self
+            .existing_view_state_store()
+            .and_then(|store| store.get(&hit.source_path).ok().flatten())
+            .and_then(|state| state.bookmark_line);
+        match bookmark {
+            Some(line) => self.set_detail_scroll_clamped(line),
+            None => {
+                self.status = "No bookmark set in this conversation ('m' sets one).".to_string()
+            }
+        }
+    }
+
+    /// Toggle collapse of the tool/system message at the current scroll
+    /// position (key `o` in the detail view). A no-op on other roles: prose
+    /// messages have nothing folded behind them.
+    fn toggle_tool_collapse_at_cursor(&mut self) -> ftui::Cmd<CassMsg> {
+        let line = self.detail_scroll;
+        let target = self
+            .detail_message_offsets
+            .borrow()
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, (offset, _))| *offset <= line)
+            .map(|(idx, (_, role))| {
+                (
+                    idx,
+                    matches!(
+                        role,
+                        crate::model::types::MessageRole::Tool
+                            | crate::model::types::MessageRole::System
+                    ),
+                )
+            });
+        match target {
+            Some((idx, true)) => self.update(CassMsg::ToolCollapseToggled(idx)),
+            _ => ftui::Cmd::none(),
+        }
+    }
+
+    /// Message index at the current detail scroll position, resolved from the
+    /// rendered header offsets (same rule the bookmark and note markers use).
+    fn detail_message_index_at_cursor(&self) -> Option<usize> {
+        let line = self.detail_scroll;
+        self.detail_message_offsets
+            .borrow()
+            .iter()
+            .rposition(|(offset, _)| *offset <= line)
+    }
+
+    /// Body of the message at the current detail scroll position, from the
+    /// loaded detail conversation.
+    fn detail_message_body_at_cursor(&self) -> Option<String> {
+        let hit = self.selected_hit()?;
+        let view = self.cached_detail_for_hit(hit)?;
+        let idx = self.detail_message_index_at_cursor()?;
+        view.messages
+            .get(idx)
+            .map(|message| message.content.clone())
+    }
+
+    /// Reload inline notes for the open conversation (`data_dir/notes.db`).
+    /// Read-only: a missing store just means no notes.
+    fn reload_detail_notes(&mut self) {
+        self.detail_notes.clear();
+        let Some(source_path) = self.selected_hit().map(|hit| hit.source_path.clone()) else {
+            return;
+        };
+        if let Ok(Some(store)) =
+            crate::notes::NoteStore::open_existing(&self.data_dir.join("notes.db"))
+            && let Ok(notes) = store.notes_for_source(&source_path)
+        {
+            self.detail_notes = notes;
+        }
+    }
+
+    /// Begin note entry for the message at the current scroll position
+    /// (key `a` in the detail view).
+    fn start_detail_note(&mut self) {
+        if self.selected_hit().is_none() {
+            return;
+        }
+        // Resolve which message the note attaches to from the rendered
+        // header offsets, same as the bookmark marker.
+        let line = self.detail_scroll;
+        let message_idx = self
+            .detail_message_offsets
+            .borrow()
+            .iter()
+            .rposition(|(offset, _)| *offset <= line);
+        self.detail_note = Some(DetailNoteState {
+            draft: String::new(),
+            message_idx,
+        });
+        self.status = "Type the note, Enter saves, Esc discards.".to_string();
+    }
+
+    /// Persist the in-progress note and refresh the inline display.
+    fn save_detail_note(&mut self) {
+        let Some(state) = self.detail_note.take() else {
+            return;
+        };
+        let draft = state.draft.trim().to_string();
+        if draft.is_empty() {
+            self.status = "Empty note discarded.".to_string();
+            return;
+        }
+        let Some(hit) = self.selected_hit().cloned() else {
+            return;
+        };
+        match crate::notes::NoteStore::open(&self.data_dir.join("notes.db")) {
+            Ok(store) => {
+                let message_idx = state.message_idx.and_then(|idx| i64::try_from(idx).ok());
+                match store.add(&hit.source_path, message_idx, &draft) {
+                    Ok(_) => {
+                        self.status = match state.message_idx {
+                            Some(idx) => format!("Note saved on message {}.", idx + 1),
+                            None => "Note saved.".to_string(),
+                        };
+                        self.reload_detail_notes();
+                    }
+                    Err(error) => self.status = format!("Note failed: {error}"),
+                }
+            }
+            Err(error) => self.status = format!("Note unavailable: {error}"),
+        }
+    }
+
+    /// Persist the current detail scroll position for the open conversation.
+    fn save_detail_view_position(&self) {
+        if let Some(hit) = self.selected_hit()
+            && let Some(store) = self.view_state_store()
+        {
+            let _ = store.save_scroll(&hit.source_path, self.detail_scroll);
+        }
+    }
+
     fn apply_persisted_state(&mut self, state: &PersistedState, mark_first_run_dirty: bool) {
         self.search_mode = state.search_mode;
         self.match_mode = state.match_mode;
@@ -5988,6 +6593,7 @@ impl CassApp {
         self.sort_saved_views();
         self.clamp_saved_views_selection();
         self.fancy_borders = state.fancy_borders;
+        self.result_columns = state.result_columns.clone();
         self.help_pinned = state.help_pinned;
         // Re-open help if the user pinned it, or on first run so key
         // hints are immediately discoverable.
@@ -6071,6 +6677,7 @@ impl CassApp {
             fancy_borders: self.fancy_borders,
             help_pinned: self.help_pinned,
             has_seen_help: self.has_seen_help || self.help_pinned || self.show_help,
+            result_columns: self.result_columns.clone(),
         }
     }
 
@@ -6306,6 +6913,26 @@ impl CassApp {
             && self.search_error_message.is_some()
     }
 
+    /// Whether the empty-state panel should explain that the index itself is
+    /// empty — the agent badge load has completed and reported no indexed
+    /// agents — rather than that a query happened to match nothing.
+    fn empty_index_state_visible(&self) -> bool {
+        self.agent_badges_loaded
+            && self.agent_index_badges.is_empty()
+            && !self.index_refresh_in_flight
+    }
+
+    /// Short `label:value` summary of the active filter pills, shown in the
+    /// zero-results empty state so "no matches" can be traced to a filter
+    /// instead of the query text.
+    fn active_filter_summary(&self) -> Vec<String> {
+        self.filter_pills()
+            .into_iter()
+            .filter(|pill| pill.active)
+            .map(|pill| format!("{}:{}", pill.label, pill.value))
+            .collect()
+    }
+
     fn visible_query_suggestion_count(&self) -> usize {
         if self.settled_zero_results_visible() {
             self.suggestions.len().min(3)
@@ -6469,6 +7096,7 @@ impl CassApp {
             && !self.show_detail_modal
             && !self.show_bulk_modal
             && !self.show_saved_views_modal
+            && !self.show_column_picker
             && !self.show_export_modal
             && !self.show_consent_dialog
             && !self.source_filter_menu_open
@@ -6479,6 +7107,7 @@ impl CassApp {
         if self.show_export_modal
             || self.show_bulk_modal
             || self.show_saved_views_modal
+            || self.show_column_picker
             || self.show_consent_dialog
             || self.source_filter_menu_open
             || self.command_palette.is_visible()
@@ -6579,6 +7208,7 @@ impl CassApp {
             PaletteResult::SaveViewSlot(slot) => ftui::Cmd::msg(CassMsg::ViewSaved(slot)),
             PaletteResult::LoadViewSlot(slot) => ftui::Cmd::msg(CassMsg::ViewLoaded(slot)),
             PaletteResult::OpenBulkActions => ftui::Cmd::msg(CassMsg::BulkActionsOpened),
+            PaletteResult::OpenColumnPicker => ftui::Cmd::msg(CassMsg::ColumnPickerOpened),
             PaletteResult::ReloadIndex => ftui::Cmd::msg(CassMsg::IndexRefreshRequested),
             PaletteResult::OpenAnalyticsView(target) => {
                 let view = match target {
@@ -6949,6 +7579,93 @@ impl CassApp {
         ftui::text::Line::from_spans(spans)
     }
 
+    /// Background reload of the per-agent header badges. Best-effort: a
+    /// missing or unreadable database simply clears the strip.
+    fn agent_badges_reload_cmd(&self) -> ftui::Cmd<CassMsg> {
+        let db_path = self.db_path.clone();
+        ftui::Cmd::task(move || {
+            let badges = crate::storage::sqlite::FrankenStorage::open_readonly(&db_path)
+                .ok()
+                .and_then(|storage| crate::ui::data::load_agent_index_badges(&storage).ok())
+                .unwrap_or_default();
+            CassMsg::AgentBadgesLoaded(badges)
+        })
+    }
+
+    /// Compact per-agent badge strip: `slug count ·age` per indexed agent,
+    /// busiest first, truncated to the available width. An agent whose
+    /// newest indexed session is older than [`AGENT_BADGE_STALE_MS`] renders
+    /// in the warning style — the at-a-glance cue that a connector has
+    /// stopped finding new sessions.
+    fn build_agent_badge_line(
+        &self,
+        width: u16,
+        styles: &StyleContext,
+        apply_style: bool,
+    ) -> ftui::text::Line<'_> {
+        let plain = ftui::Style::default();
+        let label_style = if apply_style {
+            styles.style(style_system::STYLE_TEXT_SUBTLE)
+        } else {
+            plain
+        };
+        let agent_style = if apply_style {
+            styles.style(style_system::STYLE_KBD_DESC)
+        } else {
+            plain
+        };
+        let count_style = if apply_style {
+            styles.style(style_system::STYLE_TEXT_PRIMARY)
+        } else {
+            plain
+        };
+        let age_style = if apply_style {
+            styles.style(style_system::STYLE_TEXT_MUTED)
+        } else {
+            plain
+        };
+        let stale_style = if apply_style {
+            styles.style(style_system::STYLE_STATUS_WARNING)
+        } else {
+            plain
+        };
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let max_chars = width as usize;
+        let used = std::cell::Cell::new(0usize);
+        let mut spans: Vec<ftui::text::Span<'static>> = Vec::new();
+        let mut try_push = |text: String, style: ftui::Style| -> bool {
+            let cols = display_width(&text);
+            if used.get() + cols > max_chars {
+                return false;
+            }
+            used.set(used.get() + cols);
+            spans.push(ftui::text::Span::styled(text, style));
+            true
+        };
+
+        let _ = try_push(" indexed ".to_string(), label_style);
+        for (idx, badge) in self.agent_index_badges.iter().enumerate() {
+            let joiner = if idx == 0 { "" } else { "  " };
+            let stale = badge
+                .last_indexed_ms
+                .is_some_and(|ts| now_ms.saturating_sub(ts) > AGENT_BADGE_STALE_MS);
+            let age = match badge.last_indexed_ms {
+                Some(ts) => format!(" \u{00b7}{}", format_age_compact(now_ms, ts)),
+                None => String::new(),
+            };
+            // Stop rather than render a half-badge; busiest agents come
+            // first, so truncation drops the least informative entries.
+            if !try_push(format!("{joiner}{} ", badge.agent), agent_style)
+                || !try_push(badge.conversations.to_string(), count_style)
+                || !try_push(age, if stale { stale_style } else { age_style })
+            {
+                break;
+            }
+        }
+        ftui::text::Line::from_spans(spans)
+    }
+
     fn sort_saved_views(&mut self) {
         self.saved_views.sort_by_key(|v| v.slot);
     }
@@ -7006,6 +7723,75 @@ impl CassApp {
         true
     }
 
+    // -- Results column picker --------------------------------------------
+
+    fn move_column_picker_cursor(&mut self, delta: i32) {
+        let len = self.result_columns.picker_entries().len() as i64;
+        if len == 0 {
+            self.column_picker_cursor = 0;
+            return;
+        }
+        let next = self.column_picker_cursor as i64 + i64::from(delta);
+        self.column_picker_cursor = next.rem_euclid(len) as usize;
+    }
+
+    fn column_picker_cursor_column(&self) -> Option<ResultColumn> {
+        self.result_columns
+            .picker_entries()
+            .get(self.column_picker_cursor)
+            .map(|(column, _)| *column)
+    }
+
+    fn column_picker_toggle(&mut self) {
+        let Some(column) = self.column_picker_cursor_column() else {
+            return;
+        };
+        if self.result_columns.toggle(column) {
+            self.dirty_since = Some(Instant::now());
+            // Keep the cursor on the column it was on: toggling moves it to
+            // the end of either the enabled or the disabled section.
+            self.column_picker_cursor = self
+                .result_columns
+                .picker_entries()
+                .iter()
+                .position(|(c, _)| *c == column)
+                .unwrap_or(0);
+            self.status = format!("Results columns: {}", self.result_columns.spec_string());
+        } else {
+            self.status = "At least one results column must stay enabled".to_string();
+        }
+    }
+
+    fn column_picker_reorder(&mut self, delta: i32) {
+        let Some(column) = self.column_picker_cursor_column() else {
+            return;
+        };
+        if self.result_columns.move_column(column, delta) {
+            self.dirty_since = Some(Instant::now());
+            if let Some(idx) = self
+                .result_columns
+                .picker_entries()
+                .iter()
+                .position(|(c, _)| *c == column)
+            {
+                self.column_picker_cursor = idx;
+            }
+            self.status = format!("Results columns: {}", self.result_columns.spec_string());
+        }
+    }
+
+    fn column_picker_adjust_width(&mut self, delta: i32) {
+        let Some(column) = self.column_picker_cursor_column() else {
+            return;
+        };
+        if self.result_columns.adjust_width(column, delta) {
+            self.dirty_since = Some(Instant::now());
+            self.status = format!("Results columns: {}", self.result_columns.spec_string());
+        } else {
+            self.status = format!("{} has no configurable width", column.label());
+        }
+    }
+
     fn panel_ratio_from_mouse_x(&self, x: u16) -> Option<f64> {
         let area = self.last_content_area.borrow().as_ref().copied()?;
         if area.width < 4 {
@@ -7129,6 +7915,16 @@ impl CassApp {
             SearchPass::Interactive => self.interactive_search_limit(),
             SearchPass::Upgrade | SearchPass::Pagination => self.search_page_size.max(1),
         };
+        // Size snippet windows to the current terminal: the results pane gets
+        // roughly half the width, and display-side wrapping handles the rest.
+        let (width, _) = self.last_terminal_size.get();
+        let (snippet_min, snippet_max) = self.snippet_bounds;
+        let snippet_chars = crate::search::query::adaptive_snippet_chars(
+            (width > 0).then_some(width as usize),
+            self.query.split_whitespace().count(),
+            snippet_min,
+            snippet_max,
+        );
         SearchParams {
             query: self.query.clone(),
             filters: self.filters.clone(),
@@ -7139,6 +7935,7 @@ impl CassApp {
             context_window: self.context_window,
             limit,
             offset,
+            snippet_chars: Some(snippet_chars),
         }
     }
 
@@ -7280,6 +8077,51 @@ impl CassApp {
         })
     }
 
+    /// Schedule the next follow-mode poll of the tailed source file.
+    fn follow_poll_cmd() -> ftui::Cmd<CassMsg> {
+        ftui::Cmd::task(|| {
+            std::thread::sleep(FOLLOW_POLL_INTERVAL);
+            CassMsg::FollowPollTicked
+        })
+    }
+
+    /// (mtime millis, byte length) of a local source file, if it exists.
+    fn source_file_stat(path: &str) -> Option<(i64, u64)> {
+        let meta = std::fs::metadata(path).ok()?;
+        let mtime = meta
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_millis() as i64;
+        Some((mtime, meta.len()))
+    }
+
+    /// The (pane, hit) whose local source file was modified most recently —
+    /// the session an agent is writing right now. Remote hits are skipped
+    /// because their paths are not stat-able on this machine.
+    fn most_recently_modified_hit(&self) -> Option<(usize, usize)> {
+        let mut stats: HashMap<&str, Option<(i64, u64)>> = HashMap::new();
+        let mut best: Option<(i64, usize, usize)> = None;
+        for (pane_idx, pane) in self.panes.iter().enumerate() {
+            for (hit_idx, hit) in pane.hits.iter().enumerate() {
+                if hit.origin_kind != "local" {
+                    continue;
+                }
+                let stat = *stats
+                    .entry(hit.source_path.as_str())
+                    .or_insert_with(|| Self::source_file_stat(&hit.source_path));
+                let Some((mtime, _)) = stat else {
+                    continue;
+                };
+                if best.is_none_or(|(best_mtime, _, _)| mtime > best_mtime) {
+                    best = Some((mtime, pane_idx, hit_idx));
+                }
+            }
+        }
+        best.map(|(_, pane_idx, hit_idx)| (pane_idx, hit_idx))
+    }
+
     fn split_content_area(
         &self,
         area: Rect,
@@ -7368,9 +8210,23 @@ impl CassApp {
             .map(|pane| pane.agent.clone());
         let prev_active_hit = self.selected_hit().map(SelectedHitKey::from_hit);
 
+        // Collapse repeated hits from one chatty conversation down to the top
+        // few so a single session cannot fill a pane. Hits arrive in score
+        // order, so the kept hits are the strongest; the remainder surfaces as
+        // a `+N more matches` note on the conversation's last visible hit.
+        let mut working_results = self.results.clone();
+        self.suppressed_hits = if self.collapse_conversation_repeats {
+            crate::search::result_grouping::cap_hits_per_conversation(
+                &mut working_results,
+                TUI_MAX_HITS_PER_CONVERSATION,
+            )
+        } else {
+            HashMap::new()
+        };
+
         let mut pane_map: std::collections::BTreeMap<String, Vec<SearchHit>> =
             std::collections::BTreeMap::new();
-        for hit in &self.results {
+        for hit in &working_results {
             if let Some(filter) = self.pane_filter.as_deref().filter(|s| !s.trim().is_empty())
                 && !pane_filter_matches_hit(hit, filter)
             {
@@ -7415,6 +8271,13 @@ impl CassApp {
             0 // single group: no cap
         };
 
+        // Fold away hits in collapsed date buckets (date sorts only); the
+        // bucket's first hit stays visible to carry the fold header.
+        let fold_dates = self.date_grouping_active() && !self.collapsed_date_buckets.is_empty();
+        let collapsed_buckets = self.collapsed_date_buckets.clone();
+        let now_secs = chrono::Utc::now().timestamp();
+        let mut bucket_hidden: HashMap<(String, DateBucket), usize> = HashMap::new();
+
         self.panes = pane_map
             .into_iter()
             .map(|(key, hits)| {
@@ -7424,6 +8287,16 @@ impl CassApp {
                 } else {
                     hits
                 };
+                let capped = if fold_dates {
+                    let (kept, hidden) =
+                        fold_collapsed_date_buckets(capped, &collapsed_buckets, now_secs);
+                    for (bucket, count) in hidden {
+                        bucket_hidden.insert((key.clone(), bucket), count);
+                    }
+                    kept
+                } else {
+                    capped
+                };
                 AgentPane {
                     agent: key,
                     hits: capped,
@@ -7432,6 +8305,7 @@ impl CassApp {
                 }
             })
             .collect();
+        self.date_bucket_hidden = bucket_hidden;
 
         if let Some(active_key) = prev_active_key
             && let Some(idx) = self.panes.iter().position(|pane| pane.agent == active_key)
@@ -7468,6 +8342,64 @@ impl CassApp {
         self.adjust_pane_scroll_offset();
     }
 
+    /// Suppressed-repeat count to show on `hits[idx]`: the number of hits
+    /// collapsed away from its conversation, attributed to the conversation's
+    /// last visible hit in the pane so the note renders exactly once.
+    fn suppressed_count_for(&self, hits: &[SearchHit], idx: usize) -> usize {
+        let hit = &hits[idx];
+        let later_repeat = hits[idx + 1..]
+            .iter()
+            .any(|later| later.source_id == hit.source_id && later.source_path == hit.source_path);
+        if later_repeat {
+            return 0;
+        }
+        self.suppressed_hits
+            .get(&(hit.source_id.clone(), hit.source_path.clone()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Date-bucket fold headers only make sense when results arrive in date
+    /// order; score-mixed rankings interleave days and would repeat headers.
+    fn date_grouping_active(&self) -> bool {
+        matches!(
+            self.ranking_mode,
+            RankingMode::DateNewest | RankingMode::DateOldest
+        )
+    }
+
+    /// Fold-header label to render above `hits[idx]`: present when date
+    /// grouping is active and the hit starts a new date bucket in the pane
+    /// keyed by `pane_key`. Folded buckets report how many hits they hide.
+    fn date_header_for(
+        &self,
+        pane_key: &str,
+        hits: &[SearchHit],
+        idx: usize,
+        now_secs: i64,
+    ) -> Option<String> {
+        if !self.date_grouping_active() {
+            return None;
+        }
+        let bucket = DateBucket::for_timestamp(hits.get(idx)?.created_at, now_secs);
+        if idx > 0 && DateBucket::for_timestamp(hits[idx - 1].created_at, now_secs) == bucket {
+            return None;
+        }
+        if self.collapsed_date_buckets.contains(&bucket) {
+            let hidden = self
+                .date_bucket_hidden
+                .get(&(pane_key.to_string(), bucket))
+                .copied()
+                .unwrap_or(0);
+            Some(format!(
+                "\u{25b8} {} \u{00b7} {hidden} folded",
+                bucket.label()
+            ))
+        } else {
+            Some(format!("\u{25be} {}", bucket.label()))
+        }
+    }
+
     fn visible_pane_capacity(&self) -> usize {
         // Use the results-pane inner rect (not full content area) so the
         // pane-strip scroll offset matches how many panes actually fit on
@@ -7594,10 +8526,42 @@ impl CassApp {
     }
 
     fn autocomplete_input_buffer(&self) -> Option<String> {
+        // Workspace completions prefer the index's recency ranking: a prefix
+        // shared by several workspaces should complete to the one touched
+        // most recently, not the alphabetically smallest. Fall back to the
+        // merged (sorted) candidate set when the ranked list has no match.
+        if matches!(self.input_mode, InputMode::Workspace)
+            && let Some(known) = &self.known_workspaces
+            && let Some(completed) = autocomplete_csv_suffix_ordered(&self.input_buffer, known)
+        {
+            return Some(completed);
+        }
         let candidates = self.input_autocomplete_candidates();
         autocomplete_csv_suffix(&self.input_buffer, &candidates)
     }
 
+    /// Populate `known_workspaces` from the index the first time the
+    /// workspace filter prompt opens. The list arrives ordered by most
+    /// recent conversation activity (see
+    /// `FrankenStorage::list_workspace_paths_by_recency`), so autocomplete
+    /// offers "what I touched lately" first. Best-effort: without a readable
+    /// database the prompt still works, it just completes from visible
+    /// results only.
+    fn refresh_known_workspaces(&mut self) {
+        if self.known_workspaces.is_some() {
+            return;
+        }
+        let Some(reader) = &self.db_reader else {
+            return;
+        };
+        match reader.list_workspace_paths_by_recency() {
+            Ok(paths) => self.known_workspaces = Some(paths),
+            Err(e) => {
+                tracing::debug!(error = %e, "failed to load workspace completion list");
+            }
+        }
+    }
+
     fn refresh_available_source_ids(&mut self) {
         let mut ids = BTreeSet::new();
         for hit in &self.results {
@@ -8682,6 +9646,7 @@ impl CassApp {
             let pending_search = self.pending_query_search_visible();
             let failed_search = self.failed_empty_results_visible();
             let settled_zero_results = self.settled_zero_results_visible();
+            let empty_index = self.empty_index_state_visible();
 
             let mut lines: Vec<ftui::text::Line<'static>> = Vec::new();
             let mut rendered_suggestions: Vec<(usize, String)> = Vec::new();
@@ -8724,6 +9689,44 @@ impl CassApp {
                         ftui::text::Span::styled(error.to_string(), subtle_s),
                     ]));
                 }
+                if inner.height >= 8 {
+                    lines.push(ftui::text::Line::from(""));
+                    lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled("  Run ", subtle_s),
+                        ftui::text::Span::styled(" cass doctor ", pill_s),
+                        ftui::text::Span::styled(" to diagnose database issues", subtle_s),
+                    ]));
+                }
+            } else if empty_index {
+                // The index itself is empty — no query tweak will help, so
+                // point straight at indexing instead of the zero-hit hints.
+                lines.push(ftui::text::Line::from_spans(vec![
+                    ftui::text::Span::styled("No sessions indexed yet", info_s.bold()),
+                ]));
+                if inner.height >= 6 {
+                    lines.push(ftui::text::Line::from(""));
+                    lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled(
+                            "cass has not indexed any agent history on this machine.",
+                            subtle_s,
+                        ),
+                    ]));
+                }
+                if inner.height >= 8 {
+                    lines.push(ftui::text::Line::from(""));
+                    lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled("  Ctrl+Shift+R ", pill_s),
+                        ftui::text::Span::styled(
+                            " build the index now (progress shows here)",
+                            subtle_s,
+                        ),
+                    ]));
+                    lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled("  or run ", subtle_s),
+                        ftui::text::Span::styled(" cass index ", pill_s),
+                        ftui::text::Span::styled(" from a shell", subtle_s),
+                    ]));
+                }
             } else if settled_zero_results {
                 // Zero results for a real query.
                 lines.push(ftui::text::Line::from_spans(vec![
@@ -8760,6 +9763,19 @@ impl CassApp {
                     }
                 }
                 if inner.height >= 8 {
+                    let active_filters = self.active_filter_summary();
+                    if !active_filters.is_empty() {
+                        lines.push(ftui::text::Line::from(""));
+                        lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("  Filtered by ", subtle_s),
+                            ftui::text::Span::styled(active_filters.join("  "), info_s),
+                        ]));
+                        lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("  \u{2022} Press ", subtle_s),
+                            ftui::text::Span::styled(shortcuts::CLEAR_FILTERS, pill_s),
+                            ftui::text::Span::styled(" to clear all filters", subtle_s),
+                        ]));
+                    }
                     lines.push(ftui::text::Line::from(""));
                     lines.push(ftui::text::Line::from_spans(vec![
                         ftui::text::Span::styled("  \u{2022} Check active filters ", subtle_s),
@@ -8956,6 +9972,14 @@ impl CassApp {
                             ftui::text::Span::styled(error.to_string(), subtle_s),
                         ]));
                     }
+                    if inner.height >= 8 {
+                        zero_lines.push(ftui::text::Line::from(""));
+                        zero_lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("  Run ", subtle_s),
+                            ftui::text::Span::styled(" cass doctor ", pill_s),
+                            ftui::text::Span::styled(" to diagnose database issues", subtle_s),
+                        ]));
+                    }
                 } else {
                     zero_lines.push(ftui::text::Line::from_spans(vec![
                         ftui::text::Span::styled(
@@ -8996,6 +10020,19 @@ impl CassApp {
                         }
                     }
                     // Always show static hints below.
+                    let active_filters = self.active_filter_summary();
+                    if !active_filters.is_empty() {
+                        zero_lines.push(ftui::text::Line::from(""));
+                        zero_lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("  Filtered by ", subtle_s),
+                            ftui::text::Span::styled(active_filters.join("  "), accent_s),
+                        ]));
+                        zero_lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("  \u{2022} Press ", subtle_s),
+                            ftui::text::Span::styled(shortcuts::CLEAR_FILTERS, pill_s),
+                            ftui::text::Span::styled(" to clear all filters", subtle_s),
+                        ]));
+                    }
                     zero_lines.push(ftui::text::Line::from(""));
                     zero_lines.push(ftui::text::Line::from_spans(vec![
                         ftui::text::Span::styled("  \u{2022} Check active filters ", subtle_s),
@@ -9041,6 +10078,7 @@ impl CassApp {
                 return;
             }
 
+            let now_secs = chrono::Utc::now().timestamp();
             let items: Vec<ResultItem> = pane
                 .hits
                 .iter()
@@ -9088,6 +10126,9 @@ impl CassApp {
                                 pane_idx: self.active_pane,
                                 item_idx: i,
                             }),
+                        columns: self.result_columns.clone(),
+                        suppressed_count: self.suppressed_count_for(&pane.hits, i),
+                        date_header: self.date_header_for(&pane.agent, &pane.hits, i, now_secs),
                     }
                 })
                 .collect();
@@ -9208,6 +10249,7 @@ impl CassApp {
                 },
             );
             let selected_row_style = row_selected_style.bg(accent_color).fg(sel_fg).bold();
+            let now_secs = chrono::Utc::now().timestamp();
             let items: Vec<ResultItem> = pane
                 .hits
                 .iter()
@@ -9259,6 +10301,9 @@ impl CassApp {
                                 pane_idx,
                                 item_idx: i,
                             }),
+                        columns: self.result_columns.clone(),
+                        suppressed_count: self.suppressed_count_for(&pane.hits, i),
+                        date_header: self.date_header_for(&pane.agent, &pane.hits, i, now_secs),
                     }
                 })
                 .collect();
@@ -9715,12 +10760,40 @@ impl CassApp {
         if let Some(cv) = cached_detail {
             let md_width = inner_width.saturating_sub(4);
             let mut md_renderer = None;
+            // Fingerprint of everything that changes rendered output: a
+            // mismatch drops the whole per-message markdown cache.
+            let md_cache_key = {
+                use std::hash::{Hash, Hasher};
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                hit.source_path.hash(&mut hasher);
+                hit.source_id.hash(&mut hasher);
+                md_width.hash(&mut hasher);
+                cv.messages.len().hash(&mut hasher);
+                styles.options.dark_mode.hash(&mut hasher);
+                format!("{:?}", styles.options.preset).hash(&mut hasher);
+                format!("{:?}", styles.options.color_profile).hash(&mut hasher);
+                hasher.finish()
+            };
 
             let msg_count = cv.messages.len();
             let plain_text_style = styles.style(style_system::STYLE_TEXT_PRIMARY);
             let subtle_style = styles.style(style_system::STYLE_TEXT_SUBTLE);
             let mut msg_offsets: Vec<(u32, crate::model::types::MessageRole)> =
                 Vec::with_capacity(msg_count);
+            // Conversation-level notes render once, ahead of the messages.
+            let note_style = styles.style(style_system::STYLE_STATUS_WARNING);
+            let mut had_conversation_note = false;
+            for note in &self.detail_notes {
+                if note.message_idx.is_none() {
+                    lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled(format!("\u{270e} {}", note.text), note_style),
+                    ]));
+                    had_conversation_note = true;
+                }
+            }
+            if had_conversation_note {
+                lines.push(ftui::text::Line::from(""));
+            }
             for (msg_idx, msg) in cv.messages.iter().enumerate() {
                 // Record line offset for message-level navigation
                 msg_offsets.push((lines.len() as u32, msg.role.clone()));
@@ -9815,24 +10888,25 @@ impl CassApp {
                 }
                 lines.push(ftui::text::Line::from_spans(header_spans));
 
+                // Inline annotations for this message, right under its header.
+                for note in &self.detail_notes {
+                    if note.message_idx == Some(msg_idx as i64) {
+                        lines.push(ftui::text::Line::from_spans(vec![
+                            ftui::text::Span::styled("\u{258c} ", gutter_s),
+                            ftui::text::Span::styled(format!("\u{270e} {}", note.text), note_style),
+                        ]));
+                    }
+                }
+
                 if is_collapsed {
-                    // Collapsed: show truncated first-line summary
-                    let content = msg.content.trim();
-                    let first_line = content
-                        .lines()
-                        .next()
-                        .unwrap_or("")
-                        .chars()
-                        .take(80)
-                        .collect::<String>();
-                    let ellipsis = if first_line.len() < content.len() {
-                        "\u{2026}"
-                    } else {
-                        ""
-                    };
+                    // Collapsed: show a one-line summary (label, size, exit
+                    // status when visible); 'o' on the message expands it.
                     lines.push(ftui::text::Line::from_spans(vec![
                         ftui::text::Span::styled("\u{258c} ", gutter_s),
-                        ftui::text::Span::styled(format!("  {first_line}{ellipsis}"), subtle_style),
+                        ftui::text::Span::styled(
+                            format!("  {}", collapsed_tool_summary(&msg.content)),
+                            subtle_style,
+                        ),
                     ]));
                 } else {
                     // Expanded: preserve the original message bytes for the renderer.
@@ -9847,18 +10921,32 @@ impl CassApp {
                                 spans = vec![ftui::text::Span::styled("\u{258c} ", gutter_s)];
                             }
                         } else {
-                            let renderer = md_renderer.get_or_insert_with(|| {
-                                MarkdownRenderer::new(styles.markdown_theme())
-                                    .with_syntax_theme(styles.syntax_highlight_theme())
-                                    .rule_width(md_width)
-                                    .table_max_width(md_width)
-                            });
-                            let rendered = renderer.render(content);
-                            for line in rendered.into_iter() {
-                                let mut spans =
-                                    vec![ftui::text::Span::styled("\u{258c} ", gutter_s)];
-                                spans.extend(line.spans().iter().cloned());
-                                lines.push(ftui::text::Line::from_spans(spans));
+                            let mut cache = self.detail_md_cache.borrow_mut();
+                            if cache.key != md_cache_key {
+                                cache.key = md_cache_key;
+                                cache.lines.clear();
+                            }
+                            if let Some(cached) = cache.lines.get(&msg_idx) {
+                                lines.extend(cached.iter().cloned());
+                            } else {
+                                let renderer = md_renderer.get_or_insert_with(|| {
+                                    MarkdownRenderer::new(styles.markdown_theme())
+                                        .with_syntax_theme(styles.syntax_highlight_theme())
+                                        .rule_width(md_width)
+                                        .table_max_width(md_width)
+                                });
+                                let rendered = renderer.render(content);
+                                let mut owned = Vec::new();
+                                for line in rendered.into_iter() {
+                                    let mut spans =
+                                        vec![ftui::text::Span::styled("\u{258c} ", gutter_s)];
+                                    spans.extend(line.spans().iter().cloned());
+                                    owned.push(line_into_static(ftui::text::Line::from_spans(
+                                        spans,
+                                    )));
+                                }
+                                lines.extend(owned.iter().cloned());
+                                cache.lines.insert(msg_idx, owned);
                             }
                         }
                     }
@@ -11258,7 +12346,8 @@ impl CassApp {
 
         // Reserve space for the find bar whenever a find state exists.
         // This keeps query + match context visible even when the user is not actively editing.
-        let find_bar_visible = self.detail_find.is_some();
+        // Note entry ('a') borrows the same strip while a draft is open.
+        let find_bar_visible = self.detail_find.is_some() || self.detail_note.is_some();
         let (content_area, find_area) = if find_bar_visible {
             let find_h = if inner.height >= 4 { 2u16 } else { 1u16 };
             if inner.height <= find_h + 1 {
@@ -11416,7 +12505,21 @@ impl CassApp {
             let subtle_s = styles.style(style_system::STYLE_TEXT_SUBTLE);
             let pill_s = styles.style(style_system::STYLE_PILL_ACTIVE);
             let mut hint_lines: Vec<ftui::text::Line<'static>> = Vec::new();
-            if self.panes.is_empty() {
+            if let Some(notice) = &self.db_compat_notice {
+                // Schema too far from this build: show the guidance screen
+                // instead of the normal empty-state art.
+                let warn_s = styles.style(style_system::STYLE_STATUS_WARNING);
+                hint_lines.push(ftui::text::Line::from_spans(vec![
+                    ftui::text::Span::styled("\u{26a0} Database compatibility", warn_s.bold()),
+                ]));
+                hint_lines.push(ftui::text::Line::from(""));
+                let wrap_width = (content_area.width as usize).saturating_sub(8).max(20);
+                for wrapped in wrap_plain_text(notice, wrap_width) {
+                    hint_lines.push(ftui::text::Line::from_spans(vec![
+                        ftui::text::Span::styled(wrapped, subtle_s),
+                    ]));
+                }
+            } else if self.panes.is_empty() {
                 // No results at all — guide user to search.
                 if content_area.height >= 16 && content_area.width >= 45 {
                     hint_lines.push(ftui::text::Line::from_spans(vec![
@@ -11514,8 +12617,53 @@ impl CassApp {
                 .render(centered_area, frame);
         }
 
-        // Render find bar only while actively editing find text.
-        if let (Some(find), Some(find_rect)) = (&self.detail_find, find_area) {
+        // Note entry takes priority over the find bar in the shared strip.
+        if let (Some(note), Some(note_rect)) = (&self.detail_note, find_area) {
+            let container_style = styles.style(style_system::STYLE_DETAIL_FIND_CONTAINER);
+            let query_style = styles.style(style_system::STYLE_DETAIL_FIND_QUERY);
+            let hint_style = styles.style(style_system::STYLE_DETAIL_FIND_MATCH_INACTIVE);
+
+            if note_rect.height > 1 {
+                let note_block = Block::new()
+                    .borders(Borders::TOP)
+                    .border_type(border_type)
+                    .border_style(if detail_focused {
+                        title_focused_style
+                    } else {
+                        title_unfocused_style
+                    })
+                    .title(" Note ")
+                    .title_alignment(Alignment::Left)
+                    .style(container_style);
+                let note_inner = note_block.inner(note_rect);
+                note_block.render(note_rect, frame);
+
+                if !note_inner.is_empty() {
+                    let text_area = if note_inner.width > 2 {
+                        Rect::new(
+                            note_inner.x + 1,
+                            note_inner.y,
+                            note_inner.width - 2,
+                            note_inner.height,
+                        )
+                    } else {
+                        note_inner
+                    };
+                    let line =
+                        build_detail_note_bar_line(note, text_area.width, query_style, hint_style);
+                    Paragraph::new(ftui::text::Text::from_lines(vec![line_into_static(line)]))
+                        .style(container_style)
+                        .render(text_area, frame);
+                }
+            } else {
+                Block::new().style(container_style).render(note_rect, frame);
+                let line =
+                    build_detail_note_bar_line(note, note_rect.width, query_style, hint_style);
+                Paragraph::new(ftui::text::Text::from_lines(vec![line_into_static(line)]))
+                    .style(container_style)
+                    .render(note_rect, frame);
+            }
+        } else if let (Some(find), Some(find_rect)) = (&self.detail_find, find_area) {
             let container_style = styles.style(style_system::STYLE_DETAIL_FIND_CONTAINER);
             let query_style = styles.style(style_system::STYLE_DETAIL_FIND_QUERY);
             let match_active_style = styles.style(style_system::STYLE_DETAIL_FIND_MATCH_ACTIVE);
@@ -12575,6 +13723,8 @@ impl CassApp {
                 ),
                 ("{ / }", "Jump messages"),
                 ("[ / ]", "Jump user messages"),
+                ("o (in detail)", "Toggle fold of tool output under cursor"),
+                ("e / c (in detail)", "Expand / collapse all tool output"),
                 ("Ctrl+Enter", "Queue item; Ctrl+O open all queued"),
             ],
         );
@@ -12602,6 +13752,8 @@ impl CassApp {
                     "{} detail-find within messages; n/N cycle session hits",
                     shortcuts::PANE_FILTER
                 ),
+                "a (in detail) annotate current message; notes render inline, search with note:"
+                    .into(),
                 format!(
                     "{} HTML export modal | {} quick Markdown export",
                     shortcuts::EXPORT_HTML,
@@ -13049,6 +14201,94 @@ impl CassApp {
         }
     }
 
+    /// Render the results column picker overlay centered on screen.
+    fn render_column_picker_overlay(
+        &self,
+        frame: &mut super::ftui_adapter::Frame,
+        area: Rect,
+        styles: &StyleContext,
+    ) {
+        let entries = self.result_columns.picker_entries();
+        let modal_w = 52u16.min(area.width.saturating_sub(2));
+        let modal_h = (entries.len() as u16 + 4).min(area.height.saturating_sub(2));
+        if modal_w == 0 || modal_h == 0 {
+            return;
+        }
+
+        let modal_x = area.x + (area.width.saturating_sub(modal_w)) / 2;
+        let modal_y = area.y + (area.height.saturating_sub(modal_h)) / 2;
+        let modal_area = Rect::new(modal_x, modal_y, modal_w, modal_h);
+
+        let bg_style = styles.style(style_system::STYLE_PANE_BASE);
+        let border_style = styles.style(style_system::STYLE_PANE_FOCUSED);
+        let text_style = styles.style(style_system::STYLE_TEXT_PRIMARY);
+        let muted_style = styles.style(style_system::STYLE_TEXT_MUTED);
+        let selected_style = styles.style(style_system::STYLE_RESULT_ROW_SELECTED);
+
+        let bg_color = bg_style.bg.unwrap_or(ftui::PackedRgba::rgb(0, 0, 0));
+        frame.draw_rect_filled(modal_area, ftui::Cell::from_char(' ').with_bg(bg_color));
+        let outer = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(" Results Columns ")
+            .title_alignment(Alignment::Left)
+            .style(border_style);
+        let inner = outer.inner(modal_area);
+        outer.render(modal_area, frame);
+        if inner.is_empty() {
+            return;
+        }
+
+        let footer_h = 2u16.min(inner.height.saturating_sub(1));
+        let list_h = inner.height.saturating_sub(footer_h).max(1);
+        let list_area = Rect::new(inner.x, inner.y, inner.width, list_h);
+        let footer_area = Rect::new(inner.x, inner.y + list_h, inner.width, footer_h);
+
+        let cursor = self
+            .column_picker_cursor
+            .min(entries.len().saturating_sub(1));
+        let visible = list_area.height as usize;
+        let start = cursor.saturating_sub(visible.saturating_sub(1));
+        for (row, (column, enabled)) in entries.iter().enumerate().skip(start).take(visible) {
+            let y = list_area.y + (row - start) as u16;
+            let row_area = Rect::new(list_area.x, y, list_area.width, 1);
+            let marker = if row == cursor { "> " } else { "  " };
+            let check = if *enabled { "[x]" } else { "[ ]" };
+            let width_hint = self
+                .result_columns
+                .specs()
+                .iter()
+                .find(|s| s.column == *column)
+                .and_then(|s| s.effective_width())
+                .map(|w| format!("  w:{w}"))
+                .unwrap_or_default();
+            let line = format!("{marker}{check} {}{width_hint}", column.label());
+            let style = if row == cursor {
+                selected_style
+            } else if *enabled {
+                text_style
+            } else {
+                muted_style
+            };
+            Paragraph::new(line).style(style).render(row_area, frame);
+        }
+
+        Paragraph::new("Enter/Space=toggle · </>=reorder · +/-=width · R=reset · Esc=close")
+            .style(muted_style)
+            .render(
+                Rect::new(footer_area.x, footer_area.y, footer_area.width, 1),
+                frame,
+            );
+        if footer_area.height >= 2 {
+            Paragraph::new(format!("spec: {}", self.result_columns.spec_string()))
+                .style(muted_style)
+                .render(
+                    Rect::new(footer_area.x, footer_area.y + 1, footer_area.width, 1),
+                    frame,
+                );
+        }
+    }
+
     /// Render the export modal overlay centered on screen.
     fn render_export_overlay(
         &self,
@@ -14148,8 +15388,20 @@ pub enum CassMsg {
 
     /// Cycle the results grouping mode (Agent → Conversation → Workspace → Flat).
     GroupingCycled,
+    /// Toggle per-conversation repeat collapsing in the results list (Alt+X).
+    RepeatCollapseToggled,
     /// Jump to the next/previous day boundary in results.
     TimelineJumped { forward: bool },
+    /// Fold/unfold the date bucket under the cursor (Alt+Z, date sorts only).
+    DateFoldToggled,
+    /// Toggle live follow of the open session (Alt+F). With no modal open,
+    /// opens the most recently modified local session and follows it.
+    FollowModeToggled,
+    /// Periodic poll while follow mode is active: stat the followed source
+    /// file and re-tail it through the indexer when it changed.
+    FollowPollTicked,
+    /// A follow-mode single-file index pass finished; reload the open view.
+    FollowIndexCompleted,
 
     // -- Detail view ------------------------------------------------------
     /// Open the detail modal for the currently selected result.
@@ -14208,6 +15460,13 @@ pub enum CassMsg {
     CopyContent,
     /// Copy the current search query to clipboard.
     CopyQuery,
+    /// Copy the body of the message at the detail cursor to clipboard.
+    CopyMessageBody,
+    /// Copy the first fenced code block of the message at the detail cursor.
+    CopyCodeBlock,
+    /// Copy the conversation permalink (`source_path:message-number`, the
+    /// form `cass note add` and `cass view` accept).
+    CopyPermalink,
     /// Open the current result in $EDITOR.
     OpenInEditor,
     /// Open content in nano.
@@ -14384,6 +15643,12 @@ pub enum CassMsg {
     /// Load a saved view from a slot (1-9).
     ViewLoaded(u8),
 
+    // -- Results column picker --------------------------------------------
+    /// Open the results column picker overlay.
+    ColumnPickerOpened,
+    /// Close the results column picker overlay.
+    ColumnPickerClosed,
+
     // -- Index ------------------------------------------------------------
     /// User requested index refresh.
     IndexRefreshRequested,
@@ -14397,6 +15662,8 @@ pub enum CassMsg {
     IndexRefreshCompleted,
     /// Index refresh failed.
     IndexRefreshFailed(String),
+    /// Per-agent header badges reloaded from the database.
+    AgentBadgesLoaded(Vec<AgentIndexBadge>),
 
     // -- State persistence ------------------------------------------------
     /// Load persisted state from disk.
@@ -14631,6 +15898,7 @@ pub struct PersistedState {
     pub fancy_borders: bool,
     pub help_pinned: bool,
     pub has_seen_help: bool,
+    pub result_columns: ResultColumnLayout,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
@@ -14699,6 +15967,9 @@ struct PersistedStateFile {
     help_pinned: Option<bool>,
     #[serde(default)]
     has_seen_help: Option<bool>,
+    /// Results column layout spec (e.g. `score,match,workspace:24,date`).
+    #[serde(default)]
+    result_columns: Option<String>,
 }
 
 fn parse_search_mode(value: &str) -> Option<SearchMode> {
@@ -14863,6 +16134,7 @@ fn persisted_state_defaults() -> PersistedState {
         fancy_borders: true,
         help_pinned: false,
         has_seen_help: false,
+        result_columns: ResultColumnLayout::default(),
     }
 }
 
@@ -14917,6 +16189,7 @@ fn persisted_state_file_from_state(state: &PersistedState) -> PersistedStateFile
         fancy_borders: Some(state.fancy_borders),
         help_pinned: Some(state.help_pinned),
         has_seen_help: Some(state.has_seen_help),
+        result_columns: Some(state.result_columns.spec_string()),
     }
 }
 
@@ -15019,6 +16292,11 @@ fn persisted_state_from_file(file: PersistedStateFile) -> PersistedState {
         fancy_borders: file.fancy_borders.unwrap_or(defaults.fancy_borders),
         help_pinned: file.help_pinned.unwrap_or(defaults.help_pinned),
         has_seen_help: file.has_seen_help.unwrap_or(defaults.has_seen_help),
+        result_columns: file
+            .result_columns
+            .as_deref()
+            .and_then(|spec| ResultColumnLayout::parse(spec).ok())
+            .unwrap_or(defaults.result_columns),
     }
 }
 
@@ -15249,6 +16527,10 @@ pub struct SearchParams {
     pub context_window: ContextWindow,
     pub limit: usize,
     pub offset: usize,
+    /// Snippet character window sized for the current terminal
+    /// (see `crate::search::query::adaptive_snippet_chars`); `None` keeps the
+    /// backend default.
+    pub snippet_chars: Option<usize>,
 }
 
 struct CassSearchSubscription {
@@ -15448,7 +16730,8 @@ impl TantivySearchService {
                         filters: params.filters.clone(),
                         limit: params.limit,
                         sparse_threshold: 0,
-                        field_mask: crate::search::query::FieldMask::new(false, true, true, true),
+                        field_mask: crate::search::query::FieldMask::new(false, true, true, true)
+                            .with_snippet_chars(params.snippet_chars),
                         mode: params.mode,
                     },
                     |event| {
@@ -15522,7 +16805,8 @@ impl SearchService for TantivySearchService {
             FieldMask::new(false, true, true, true)
         } else {
             FieldMask::new(true, true, true, true)
-        };
+        }
+        .with_snippet_chars(params.snippet_chars);
         let two_tier_enabled = Self::progressive_enabled();
         let semantic_tier_mode = if two_tier_enabled {
             match params.pass {
@@ -15720,7 +17004,7 @@ impl From<super::ftui_adapter::Event> for CassMsg {
                     // -- Context window -------------------------------------------
                     KeyCode::F(7) => CassMsg::ContextWindowCycled,
                     KeyCode::Char('c') if alt => CassMsg::ContextWindowCycled,
-                    KeyCode::Char('C') if alt => CassMsg::ContextWindowCycled,
+                    KeyCode::Char('C') if alt && !shift => CassMsg::ContextWindowCycled,
 
                     // -- Editor ---------------------------------------------------
                     KeyCode::F(8) => CassMsg::OpenInEditor,
@@ -15760,12 +17044,19 @@ impl From<super::ftui_adapter::Event> for CassMsg {
                     // Keep printable characters available for query editing.
                     KeyCode::Char('/') if alt => CassMsg::PaneFilterOpened,
                     KeyCode::Char('y') if alt => CassMsg::CopySnippet,
+                    KeyCode::Char('Y') if alt => CassMsg::CopyMessageBody,
+                    KeyCode::Char('C') if alt && shift => CassMsg::CopyCodeBlock,
+                    KeyCode::Char('L') if alt => CassMsg::CopyPermalink,
                     KeyCode::Char('o') if alt => CassMsg::OpenInEditor,
                     KeyCode::Char('v') if alt => CassMsg::ViewRaw,
                     KeyCode::Char('J') if alt => CassMsg::ToggleJsonView,
                     KeyCode::Char('r') if alt => CassMsg::ResultsRefreshed,
                     KeyCode::Char('b') if alt => CassMsg::BulkActionsOpened,
                     KeyCode::Char('g') if alt => CassMsg::GroupingCycled,
+                    KeyCode::Char('x') if alt => CassMsg::RepeatCollapseToggled,
+                    KeyCode::Char('z') if alt => CassMsg::DateFoldToggled,
+                    KeyCode::Char('f') if alt => CassMsg::FollowModeToggled,
+                    KeyCode::Char('F') if alt => CassMsg::FollowModeToggled,
                     KeyCode::Char('[') if alt => CassMsg::TimelineJumped { forward: false },
                     KeyCode::Char(']') if alt => CassMsg::TimelineJumped { forward: true },
 
@@ -15973,7 +17264,7 @@ impl super::ftui_adapter::Model for CassApp {
     type Message = CassMsg;
 
     fn init(&mut self) -> ftui::Cmd<CassMsg> {
-        if self.startup_state_bootstrapped {
+        let startup = if self.startup_state_bootstrapped {
             // Startup already applied persisted state synchronously, so begin
             // initial browse/search immediately instead of showing a transient
             // default frame and waiting for an async state-load task.
@@ -15985,7 +17276,9 @@ impl super::ftui_adapter::Model for CassApp {
         } else {
             // Request state load on startup.
             ftui::Cmd::msg(CassMsg::StateLoadRequested)
-        }
+        };
+        // Populate the header badge strip without waiting for an index run.
+        ftui::Cmd::batch(vec![startup, self.agent_badges_reload_cmd()])
     }
 
     fn subscriptions(&self) -> Vec<Box<dyn Subscription<Self::Message>>> {
@@ -16322,6 +17615,59 @@ impl super::ftui_adapter::Model for CassApp {
             }
         }
 
+        // Column picker intercept. While open, consume navigation and edit
+        // keys so query/search state is not mutated underneath.
+        if self.show_column_picker {
+            match &msg {
+                CassMsg::QuitRequested => return ftui::Cmd::msg(CassMsg::ColumnPickerClosed),
+                CassMsg::SelectionMoved { delta } => {
+                    self.move_column_picker_cursor(*delta);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text.eq_ignore_ascii_case("j") => {
+                    self.move_column_picker_cursor(1);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text.eq_ignore_ascii_case("k") => {
+                    self.move_column_picker_cursor(-1);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::DetailOpened | CassMsg::QuerySubmitted => {
+                    self.column_picker_toggle();
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text == " " => {
+                    self.column_picker_toggle();
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text == "<" => {
+                    self.column_picker_reorder(-1);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text == ">" => {
+                    self.column_picker_reorder(1);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text == "+" || text == "=" => {
+                    self.column_picker_adjust_width(2);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text == "-" => {
+                    self.column_picker_adjust_width(-2);
+                    return ftui::Cmd::none();
+                }
+                CassMsg::QueryChanged(text) if text.eq_ignore_ascii_case("r") => {
+                    self.result_columns = ResultColumnLayout::default();
+                    self.column_picker_cursor = 0;
+                    self.dirty_since = Some(Instant::now());
+                    self.status = "Results columns reset to defaults".to_string();
+                    return ftui::Cmd::none();
+                }
+                CassMsg::ColumnPickerOpened | CassMsg::ColumnPickerClosed => {}
+                _ => return ftui::Cmd::none(),
+            }
+        }
+
         // Source filter menu: while open, consume navigation keys and apply
         // selection without affecting results/query.
         if self.source_filter_menu_open {
@@ -16357,6 +17703,40 @@ impl super::ftui_adapter::Model for CassApp {
         // When the full-screen detail modal is open, remap navigation and
         // provide find-in-detail text search (Ctrl+F or /).
         if self.show_detail_modal {
+            // Sub-intercept: when note entry is active, route text input there.
+            if self.detail_note.is_some() {
+                match &msg {
+                    CassMsg::QueryChanged(text) => {
+                        if let Some(note) = self.detail_note.as_mut() {
+                            if text.is_empty() {
+                                note.draft.pop();
+                            } else {
+                                note.draft.push_str(text);
+                            }
+                        }
+                        return ftui::Cmd::none();
+                    }
+                    // Enter saves the note.
+                    CassMsg::QuerySubmitted | CassMsg::DetailOpened => {
+                        self.save_detail_note();
+                        return ftui::Cmd::none();
+                    }
+                    // Esc discards the draft, keeping the modal open.
+                    CassMsg::QuitRequested => {
+                        self.detail_note = None;
+                        self.status = "Note discarded.".to_string();
+                        return ftui::Cmd::none();
+                    }
+                    CassMsg::DetailClosed
+                    | CassMsg::DetailScrolled { .. }
+                    | CassMsg::Tick
+                    | CassMsg::FollowPollTicked
+                    | CassMsg::FollowIndexCompleted
+                    | CassMsg::MouseEvent { .. }
+                    | CassMsg::ForceQuit => {}
+                    _ => return ftui::Cmd::none(),
+                }
+            }
             // Sub-intercept: when find bar is active, route text input there.
             if self.detail_find.is_some() && self.input_mode == InputMode::DetailFind {
                 match &msg {
@@ -16373,7 +17753,19 @@ impl super::ftui_adapter::Model for CassApp {
                         return ftui::Cmd::none();
                     }
                     CassMsg::QuerySubmitted | CassMsg::DetailOpened => {
-                        // Enter → navigate to next match
+                        // Enter commits the find: leave the input bar so n/N
+                        // jump between matches while highlights stay applied.
+                        // An empty query just closes the bar.
+                        self.input_mode = InputMode::Query;
+                        if self
+                            .detail_find
+                            .as_ref()
+                            .is_none_or(|find| find.query.trim().is_empty())
+                        {
+                            self.detail_find = None;
+                            self.detail_find_matches_cache.borrow_mut().clear();
+                            return ftui::Cmd::none();
+                        }
                         return self.update(CassMsg::DetailFindNavigated { forward: true });
                     }
                     CassMsg::QuitRequested => {
@@ -16396,10 +17788,16 @@ impl super::ftui_adapter::Model for CassApp {
                     | CassMsg::CopySnippet
                     | CassMsg::CopyPath
                     | CassMsg::CopyContent
+                    | CassMsg::CopyMessageBody
+                    | CassMsg::CopyCodeBlock
+                    | CassMsg::CopyPermalink
                     | CassMsg::OpenInEditor
                     | CassMsg::OpenInNano
                     | CassMsg::ViewRaw
                     | CassMsg::Tick
+                    | CassMsg::FollowModeToggled
+                    | CassMsg::FollowPollTicked
+                    | CassMsg::FollowIndexCompleted
                     | CassMsg::MouseEvent { .. }
                     | CassMsg::ForceQuit => {}
                     _ => return ftui::Cmd::none(),
@@ -16441,11 +17839,26 @@ impl super::ftui_adapter::Model for CassApp {
                     CassMsg::QueryChanged(text) if text == "k" => {
                         return self.update(CassMsg::DetailScrolled { delta: -3 });
                     }
-                    // n/N navigate contextual search hits for this session.
+                    // n/N jump between committed in-conversation find matches;
+                    // without one they navigate contextual search hits.
                     CassMsg::QueryChanged(text) if text == "n" => {
+                        if self
+                            .detail_find
+                            .as_ref()
+                            .is_some_and(|find| !find.query.trim().is_empty())
+                        {
+                            return self.update(CassMsg::DetailFindNavigated { forward: true });
+                        }
                         return self.update(CassMsg::DetailSessionHitNavigated { forward: true });
                     }
                     CassMsg::QueryChanged(text) if text == "N" => {
+                        if self
+                            .detail_find
+                            .as_ref()
+                            .is_some_and(|find| !find.query.trim().is_empty())
+                        {
+                            return self.update(CassMsg::DetailFindNavigated { forward: false });
+                        }
                         return self.update(CassMsg::DetailSessionHitNavigated { forward: false });
                     }
                     // w toggles wrap
@@ -16473,6 +17886,25 @@ impl super::ftui_adapter::Model for CassApp {
                     CassMsg::QueryChanged(text) if text == "c" => {
                         return self.update(CassMsg::ToolCollapseAll);
                     }
+                    // o toggles the fold of the tool/system message under the cursor
+                    CassMsg::QueryChanged(text) if text == "o" => {
+                        return self.toggle_tool_collapse_at_cursor();
+                    }
+                    // m toggles a persistent bookmark at the current position
+                    CassMsg::QueryChanged(text) if text == "m" => {
+                        self.toggle_detail_bookmark();
+                        return ftui::Cmd::none();
+                    }
+                    // ' jumps to the bookmark, if one is set
+                    CassMsg::QueryChanged(text) if text == "'" => {
+                        self.jump_to_detail_bookmark();
+                        return ftui::Cmd::none();
+                    }
+                    // a annotates the message at the current position
+                    CassMsg::QueryChanged(text) if text == "a" => {
+                        self.start_detail_note();
+                        return ftui::Cmd::none();
+                    }
                     // { / } jump between messages
                     CassMsg::QueryChanged(text) if text == "{" => {
                         return self.update(CassMsg::DetailMessageJumped {
@@ -16559,6 +17991,9 @@ impl super::ftui_adapter::Model for CassApp {
                     | CassMsg::CopySnippet
                     | CassMsg::CopyPath
                     | CassMsg::CopyContent
+                    | CassMsg::CopyMessageBody
+                    | CassMsg::CopyCodeBlock
+                    | CassMsg::CopyPermalink
                     | CassMsg::OpenInEditor
                     | CassMsg::OpenInNano
                     | CassMsg::ViewRaw
@@ -16568,6 +18003,9 @@ impl super::ftui_adapter::Model for CassApp {
                     | CassMsg::DetailMessageJumped { .. }
                     | CassMsg::PageScrolled { .. }
                     | CassMsg::Tick
+                    | CassMsg::FollowModeToggled
+                    | CassMsg::FollowPollTicked
+                    | CassMsg::FollowIndexCompleted
                     | CassMsg::MouseEvent { .. }
                     | CassMsg::ForceQuit => {}
                     // Typing a non-navigation letter while the detail modal
@@ -17702,6 +19140,16 @@ impl super::ftui_adapter::Model for CassApp {
                 self.status = format!("Grouping: {}", self.grouping_mode.label());
                 ftui::Cmd::none()
             }
+            CassMsg::RepeatCollapseToggled => {
+                self.collapse_conversation_repeats = !self.collapse_conversation_repeats;
+                self.regroup_panes();
+                self.status = if self.collapse_conversation_repeats {
+                    "Conversation repeats: collapsed".to_string()
+                } else {
+                    "Conversation repeats: expanded".to_string()
+                };
+                ftui::Cmd::none()
+            }
             CassMsg::TimelineJumped { forward } => {
                 if let Some(target) = self.timeline_jump_index(forward) {
                     if let Some(pane) = self.panes.get_mut(self.active_pane) {
@@ -17721,6 +19169,144 @@ impl super::ftui_adapter::Model for CassApp {
                 }
                 ftui::Cmd::none()
             }
+            CassMsg::DateFoldToggled => {
+                if !self.date_grouping_active() {
+                    self.status =
+                        "Date folds need a date sort (cycle ranking with Alt+R)".to_string();
+                } else if let Some(created_at) = self.selected_hit().map(|hit| hit.created_at) {
+                    let now_secs = chrono::Utc::now().timestamp();
+                    let bucket = DateBucket::for_timestamp(created_at, now_secs);
+                    let folded = if self.collapsed_date_buckets.remove(&bucket) {
+                        false
+                    } else {
+                        self.collapsed_date_buckets.insert(bucket);
+                        true
+                    };
+                    self.regroup_panes();
+                    self.status = format!(
+                        "{}: {}",
+                        bucket.label(),
+                        if folded { "folded" } else { "expanded" }
+                    );
+                } else {
+                    self.status = "No result selected".to_string();
+                }
+                ftui::Cmd::none()
+            }
+            CassMsg::FollowModeToggled => {
+                if self.follow_mode {
+                    self.follow_mode = false;
+                    self.follow_stat = None;
+                    self.status = "Follow mode off".to_string();
+                    return ftui::Cmd::none();
+                }
+                if self.show_detail_modal {
+                    // Follow the session already open in the viewer.
+                    let Some(path) = self.selected_hit().map(|hit| hit.source_path.clone())
+                    else {
+                        self.status = "No session to follow".to_string();
+                        return ftui::Cmd::none();
+                    };
+                    self.follow_mode = true;
+                    self.follow_stat = Self::source_file_stat(&path);
+                    self.detail_session_hit_scroll_pending.set(false);
+                    self.detail_pending_scroll_to.set(Some(u32::MAX));
+                    self.status = "Following session (Alt+F to stop)".to_string();
+                    return Self::follow_poll_cmd();
+                }
+                // No viewer open: jump to the session being written right now.
+                let Some((pane_idx, hit_idx)) = self.most_recently_modified_hit() else {
+                    self.status = "No local session files to follow".to_string();
+                    return ftui::Cmd::none();
+                };
+                self.active_pane = pane_idx;
+                if let Some(pane) = self.panes.get_mut(pane_idx) {
+                    pane.selected = hit_idx;
+                }
+                self.follow_mode = true;
+                self.follow_stat = self
+                    .selected_hit()
+                    .and_then(|hit| Self::source_file_stat(&hit.source_path));
+                let open = self.update(CassMsg::DetailOpened);
+                // Land on the tail, overriding any saved reading position:
+                // follow mode is about what the agent writes next.
+                self.detail_session_hit_scroll_pending.set(false);
+                self.detail_pending_scroll_to.set(Some(u32::MAX));
+                self.status = "Following most recent session (Alt+F to stop)".to_string();
+                ftui::Cmd::batch(vec![open, Self::follow_poll_cmd()])
+            }
+            CassMsg::FollowPollTicked => {
+                if !self.follow_mode {
+                    return ftui::Cmd::none();
+                }
+                if !self.show_detail_modal {
+                    // Viewer was closed since the last poll: stop following.
+                    self.follow_mode = false;
+                    self.follow_stat = None;
+                    return ftui::Cmd::none();
+                }
+                let Some(hit) = self.selected_hit().cloned() else {
+                    return Self::follow_poll_cmd();
+                };
+                let stat = Self::source_file_stat(&hit.source_path);
+                let changed = stat.is_some() && stat != self.follow_stat;
+                if changed {
+                    self.follow_stat = stat;
+                }
+                if !changed || self.follow_index_in_flight || self.index_refresh_in_flight {
+                    return Self::follow_poll_cmd();
+                }
+                // The file grew: tail just this source through the indexer's
+                // single-shot watch path, then reload the open view.
+                self.follow_index_in_flight = true;
+                let db_path = self.db_path.clone();
+                let data_dir = self.data_dir.clone();
+                let source_path = PathBuf::from(&hit.source_path);
+                #[cfg(test)]
+                let index_cmd = {
+                    let _ = (db_path, data_dir, source_path);
+                    ftui::Cmd::task(|| CassMsg::FollowIndexCompleted)
+                };
+                #[cfg(not(test))]
+                let index_cmd = ftui::Cmd::task(move || {
+                    let opts = crate::indexer::IndexOptions {
+                        full: false,
+                        force_rebuild: false,
+                        watch: false,
+                        watch_once_paths: Some(vec![source_path]),
+                        db_path,
+                        data_dir,
+                        semantic: false,
+                        build_hnsw: false,
+                        embedder: "fastembed".to_string(),
+                        progress: None,
+                        watch_interval_secs: 30,
+                        pause_on_battery: false,
+                    };
+                    if let Err(error) = crate::indexer::run_index(opts, None) {
+                        tracing::warn!(%error, "follow mode: single-file index pass failed");
+                    }
+                    CassMsg::FollowIndexCompleted
+                });
+                ftui::Cmd::batch(vec![index_cmd, Self::follow_poll_cmd()])
+            }
+            CassMsg::FollowIndexCompleted => {
+                self.follow_index_in_flight = false;
+                if !self.follow_mode || !self.show_detail_modal {
+                    return ftui::Cmd::none();
+                }
+                let Some(hit) = self.selected_hit().cloned() else {
+                    return ftui::Cmd::none();
+                };
+                // Drop the cached view so the reload picks up freshly indexed
+                // messages; the shared conversation cache revalidates itself
+                // against the database head.
+                self.cached_detail = None;
+                ftui::Cmd::batch(vec![
+                    ftui::Cmd::msg(CassMsg::DetailLoadRequested { hit }),
+                    self.agent_badges_reload_cmd(),
+                ])
+            }
 
             // -- Detail view --------------------------------------------------
             CassMsg::DetailOpened => {
@@ -17780,10 +19366,24 @@ impl super::ftui_adapter::Model for CassApp {
                 self.detail_scroll = 0;
                 self.modal_scroll = 0;
                 self.sync_detail_session_hit_state(&selected_hit);
-                // Auto-scroll to the selected search hit once the Messages renderer
-                // computes exact line offsets for session hit anchors.
-                self.detail_session_hit_scroll_pending.set(true);
-                self.detail_pending_scroll_to.set(None);
+                // Restore the persisted reading position (view_state.db),
+                // which wins over the search-hit auto-scroll: the reader asked
+                // to come back to where they left off, and hit navigation
+                // (n/N, { }) still reaches the matches. With no saved
+                // position, auto-scroll to the selected search hit once the
+                // Messages renderer computes exact line offsets.
+                let saved_scroll = self
+                    .existing_view_state_store()
+                    .and_then(|store| store.get(&selected_hit.source_path).ok().flatten())
+                    .map(|state| state.scroll_offset)
+                    .filter(|&offset| offset > 0);
+                if let Some(offset) = saved_scroll {
+                    self.detail_session_hit_scroll_pending.set(false);
+                    self.detail_pending_scroll_to.set(Some(offset));
+                } else {
+                    self.detail_session_hit_scroll_pending.set(true);
+                    self.detail_pending_scroll_to.set(None);
+                }
                 // Seed modal-highlight terms from the active search query so
                 // matches are visible immediately in full conversation context.
                 let mut highlight_terms = extract_query_terms(&self.query);
@@ -17805,11 +19405,16 @@ impl super::ftui_adapter::Model for CassApp {
                     })
                 };
                 self.detail_find_matches_cache.borrow_mut().clear();
+                self.detail_note = None;
+                self.reload_detail_notes();
                 self.input_mode = InputMode::Query;
                 // Auto-collapse tool/system messages on open for a compact
-                // initial view; user can expand with Enter or 'e'.
+                // initial view; user can expand with 'o' or 'e', and
+                // `[tui].collapse_tool_output = false` opens expanded.
                 self.collapsed_tools.clear();
-                if let Some(cv) = self.cached_detail_for_hit(&selected_hit) {
+                if self.collapse_tools_on_open
+                    && let Some(cv) = self.cached_detail_for_hit(&selected_hit)
+                {
                     let collapse_indices: Vec<usize> = cv
                         .messages
                         .iter()
@@ -17848,7 +19453,9 @@ impl super::ftui_adapter::Model for CassApp {
                             self.cached_detail = Some((loaded_path.clone(), view));
                             // Auto-collapse tool/system messages on fresh load
                             self.collapsed_tools.clear();
-                            if let Some((_, ref cv)) = self.cached_detail {
+                            if self.collapse_tools_on_open
+                                && let Some((_, ref cv)) = self.cached_detail
+                            {
                                 for (idx, msg) in cv.messages.iter().enumerate() {
                                     if matches!(
                                         msg.role,
@@ -17886,14 +19493,23 @@ impl super::ftui_adapter::Model for CassApp {
                     self.detail_session_hit_scroll_pending.set(true);
                     self.detail_pending_scroll_to.set(None);
                 }
+                if self.follow_mode && self.show_detail_modal {
+                    // Follow mode pins the view to the tail on every reload.
+                    self.detail_session_hit_scroll_pending.set(false);
+                    self.detail_pending_scroll_to.set(Some(u32::MAX));
+                }
                 self.clear_loading_context(LoadingContext::DetailModal);
                 ftui::Cmd::none()
             }
             CassMsg::DetailClosed => {
+                self.save_detail_view_position();
                 self.show_detail_modal = false;
+                self.follow_mode = false;
+                self.follow_stat = None;
                 self.input_mode = InputMode::Query;
                 self.detail_find = None;
                 self.detail_find_matches_cache.borrow_mut().clear();
+                self.detail_note = None;
                 self.detail_session_hit_lines.clear();
                 self.detail_session_hit_offsets_cache.borrow_mut().clear();
                 self.detail_session_hit_current = 0;
@@ -18051,13 +19667,41 @@ impl super::ftui_adapter::Model for CassApp {
             }
             CassMsg::DetailFindQueryChanged(q) => {
                 if let Some(ref mut find) = self.detail_find {
-                    find.query = q;
+                    find.query = q.clone();
                     find.current = 0;
                     // Matches are computed during rendering by apply_find_highlight,
                     // which writes back to find.matches. Clear stale matches here
                     // so the renderer recomputes from scratch.
                     find.matches.clear();
                 }
+                // Find also matches inside collapsed sections: expand any
+                // collapsed message whose hidden content contains the query so
+                // its matches become visible and navigable. Two characters
+                // minimum, so the first keystroke doesn't unfold everything.
+                let needle = q.trim().to_lowercase();
+                if self.detail_find_in_collapsed
+                    && needle.chars().count() >= 2
+                    && !self.collapsed_tools.is_empty()
+                {
+                    let expand_indices: Vec<usize> = self
+                        .selected_hit()
+                        .and_then(|hit| self.cached_detail_for_render(hit))
+                        .map(|cv| {
+                            self.collapsed_tools
+                                .iter()
+                                .copied()
+                                .filter(|idx| {
+                                    cv.messages.get(*idx).is_some_and(|msg| {
+                                        msg.content.to_lowercase().contains(&needle)
+                                    })
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    for idx in expand_indices {
+                        self.collapsed_tools.remove(&idx);
+                    }
+                }
                 ftui::Cmd::none()
             }
             CassMsg::DetailFindNavigated { forward } => {
@@ -18393,6 +20037,81 @@ impl super::ftui_adapter::Model for CassApp {
                 }
                 ftui::Cmd::none()
             }
+            CassMsg::CopyMessageBody => {
+                use crate::ui::components::toast::{Toast, ToastType};
+                if let Some(body) = self.detail_message_body_at_cursor() {
+                    match copy_to_clipboard(&body) {
+                        Ok(()) => {
+                            self.status = "Copied message to clipboard".to_string();
+                            self.toast_manager
+                                .push(Toast::new("Copied message".to_string(), ToastType::Success));
+                        }
+                        Err(e) => {
+                            self.status = format!("Clipboard: {e}");
+                            self.toast_manager
+                                .push(Toast::new(format!("Copy failed: {e}"), ToastType::Error));
+                        }
+                    }
+                } else {
+                    self.status = "Open a conversation to copy a message.".to_string();
+                }
+                ftui::Cmd::none()
+            }
+            CassMsg::CopyCodeBlock => {
+                use crate::ui::components::toast::{Toast, ToastType};
+                let block = self
+                    .detail_message_body_at_cursor()
+                    .as_deref()
+                    .and_then(first_fenced_code_block);
+                if let Some(block) = block {
+                    match copy_to_clipboard(&block) {
+                        Ok(()) => {
+                            self.status = "Copied code block to clipboard".to_string();
+                            self.toast_manager.push(Toast::new(
+                                "Copied code block".to_string(),
+                                ToastType::Success,
+                            ));
+                        }
+                        Err(e) => {
+                            self.status = format!("Clipboard: {e}");
+                            self.toast_manager
+                                .push(Toast::new(format!("Copy failed: {e}"), ToastType::Error));
+                        }
+                    }
+                } else {
+                    self.status = "No fenced code block in the message at the cursor.".to_string();
+                }
+                ftui::Cmd::none()
+            }
+            CassMsg::CopyPermalink => {
+                use crate::ui::components::toast::{Toast, ToastType};
+                if let Some(hit) = self.selected_hit() {
+                    // `path:N` is the 1-based message address `cass note add`
+                    // and the viewer display share; a bare path addresses the
+                    // whole conversation.
+                    let permalink = match self.detail_message_index_at_cursor() {
+                        Some(idx) => format!("{}:{}", hit.source_path, idx + 1),
+                        None => hit.source_path.clone(),
+                    };
+                    match copy_to_clipboard(&permalink) {
+                        Ok(()) => {
+                            self.status = format!("Copied permalink: {permalink}");
+                            self.toast_manager.push(Toast::new(
+                                "Copied permalink".to_string(),
+                                ToastType::Success,
+                            ));
+                        }
+                        Err(e) => {
+                            self.status = format!("Clipboard: {e}");
+                            self.toast_manager
+                                .push(Toast::new(format!("Copy failed: {e}"), ToastType::Error));
+                        }
+                    }
+                } else {
+                    self.status = "No active result to copy.".to_string();
+                }
+                ftui::Cmd::none()
+            }
             CassMsg::OpenInEditor => {
                 if let Some(hit) = self.selected_hit().cloned() {
                     let editor_cmd = editor_command_for_actions();
@@ -18511,6 +20230,9 @@ impl super::ftui_adapter::Model for CassApp {
             CassMsg::InputModeEntered(mode) => {
                 self.input_mode = mode;
                 self.input_buffer.clear();
+                if matches!(mode, InputMode::Workspace) {
+                    self.refresh_known_workspaces();
+                }
                 self.focus_manager.focus(focus_ids::SEARCH_BAR);
                 ftui::Cmd::none()
             }
@@ -19494,6 +21216,25 @@ impl super::ftui_adapter::Model for CassApp {
                 ftui::Cmd::none()
             }
 
+            // -- Results column picker ----------------------------------------
+            CassMsg::ColumnPickerOpened => {
+                self.show_column_picker = true;
+                self.column_picker_cursor = 0;
+                self.status =
+                    "Results columns: \u{2191}\u{2193} navigate \u{00b7} Enter/Space toggle \u{00b7} </> reorder \u{00b7} +/- width \u{00b7} R reset \u{00b7} Esc close"
+                        .to_string();
+                self.focus_manager.push_trap(focus_ids::GROUP_COLUMN_PICKER);
+                self.focus_manager.focus(focus_ids::COLUMN_PICKER);
+                ftui::Cmd::none()
+            }
+            CassMsg::ColumnPickerClosed => {
+                self.show_column_picker = false;
+                self.focus_manager.pop_trap();
+                self.dirty_since = Some(Instant::now());
+                self.status = format!("Results columns: {}", self.result_columns.spec_string());
+                ftui::Cmd::none()
+            }
+
             // -- Index --------------------------------------------------------
             CassMsg::IndexRefreshRequested => {
                 if self.index_refresh_in_flight {
@@ -19530,6 +21271,7 @@ impl super::ftui_adapter::Model for CassApp {
                             embedder: "fastembed".to_string(),
                             progress: Some(progress),
                             watch_interval_secs: 30,
+                            pause_on_battery: false,
                         };
                         match crate::indexer::run_index(opts, None) {
                             Ok(()) => CassMsg::IndexRefreshCompleted,
@@ -19560,12 +21302,13 @@ impl super::ftui_adapter::Model for CassApp {
                     ));
 
                 // Trigger reload of the current surface.
-                if self.surface == AppSurface::Analytics {
+                let reload = if self.surface == AppSurface::Analytics {
                     self.analytics_cache = None;
                     self.schedule_analytics_reload()
                 } else {
                     ftui::Cmd::msg(CassMsg::SearchRequested)
-                }
+                };
+                ftui::Cmd::batch(vec![reload, self.agent_badges_reload_cmd()])
             }
             CassMsg::IndexRefreshFailed(err) => {
                 self.index_refresh_in_flight = false;
@@ -19579,6 +21322,11 @@ impl super::ftui_adapter::Model for CassApp {
                     )));
                 ftui::Cmd::none()
             }
+            CassMsg::AgentBadgesLoaded(badges) => {
+                self.agent_index_badges = badges;
+                self.agent_badges_loaded = true;
+                ftui::Cmd::none()
+            }
 
             // -- State persistence --------------------------------------------
             CassMsg::StateLoadRequested => {
@@ -19610,6 +21358,12 @@ impl super::ftui_adapter::Model for CassApp {
                 ftui::Cmd::none()
             }
             CassMsg::StateSaveRequested => {
+                if self.data_dir_read_only {
+                    // Read-only mount: skipping is the feature — attempting
+                    // the save would surface a failure status on every
+                    // debounce tick.
+                    return ftui::Cmd::none();
+                }
                 let Some(save_token) = self.begin_state_save() else {
                     return ftui::Cmd::none();
                 };
@@ -19792,6 +21546,7 @@ impl super::ftui_adapter::Model for CassApp {
                 let any_modal = self.show_export_modal
                     || self.show_bulk_modal
                     || self.show_saved_views_modal
+                    || self.show_column_picker
                     || self.show_detail_modal
                     || self.show_help
                     || self.show_inspector
@@ -20036,6 +21791,7 @@ impl super::ftui_adapter::Model for CassApp {
                                 }
                                 "ws" => {
                                     self.input_mode = InputMode::Workspace;
+                                    self.refresh_known_workspaces();
                                     self.input_buffer = if self.filters.workspaces.len() == 1 {
                                         self.filters
                                             .workspaces
@@ -20349,9 +22105,10 @@ impl super::ftui_adapter::Model for CassApp {
                 let db_path = self.db_path.clone();
                 let filters = self.analytics_filters.clone();
                 let group_by = self.explorer_group_by;
+                let data_dir_read_only = self.data_dir_read_only;
                 #[cfg(test)]
                 {
-                    let _ = (db_path, filters, group_by);
+                    let _ = (db_path, filters, group_by, data_dir_read_only);
                     ftui::Cmd::task(|| CassMsg::AnalyticsChartDataLoaded(Box::default()))
                 }
                 #[cfg(not(test))]
@@ -20402,7 +22159,11 @@ impl super::ftui_adapter::Model for CassApp {
                                     false
                                 };
 
-                                if should_auto_rebuild {
+                                if should_auto_rebuild && data_dir_read_only {
+                                    tracing::info!(
+                                        "analytics auto-rebuild skipped (read-only data dir)"
+                                    );
+                                } else if should_auto_rebuild {
                                     tracing::info!("analytics auto-rebuild triggered");
                                     match crate::storage::sqlite::FrankenStorage::open(&db_path) {
                                         Ok(db_rw) => match db_rw.rebuild_analytics() {
@@ -21222,11 +22983,15 @@ impl super::ftui_adapter::Model for CassApp {
         );
         let border_type = match deco.border_tier {
             style_system::BorderTier::Rounded => BorderType::Rounded,
-            style_system::BorderTier::Square | style_system::BorderTier::None => BorderType::Square,
+            style_system::BorderTier::Square
+            | style_system::BorderTier::Ascii
+            | style_system::BorderTier::None => BorderType::Square,
         };
         let effective_density = self.density_mode.effective(area.width);
         let row_h = effective_density.row_height();
-        let adaptive_borders = if deco.border_tier == style_system::BorderTier::None {
+        // Ascii tier drops borders entirely rather than painting box-drawing
+        // glyphs that legacy consoles render as mojibake.
+        let adaptive_borders = if deco.border_tier >= style_system::BorderTier::Ascii {
             Borders::NONE
         } else {
             Borders::ALL
@@ -21382,6 +23147,34 @@ impl super::ftui_adapter::Model for CassApp {
             self.last_tab_rects.borrow_mut().clear();
         }
 
+        // Per-agent index badge strip: conversation counts + last-activity
+        // age per connector, refreshed after each index run. Needs one more
+        // row than the shell strip, so tiny terminals skip it.
+        if layout_area.height >= 10
+            && layout_area.width >= 52
+            && !self.agent_index_badges.is_empty()
+        {
+            let badge_area = Rect::new(layout_area.x, layout_area.y, layout_area.width, 1);
+            let badge_bg_style = if apply_style {
+                styles.style(style_system::STYLE_TAB_INACTIVE)
+            } else {
+                plain
+            };
+            Block::new().style(badge_bg_style).render(badge_area, frame);
+            let badge_line = self.build_agent_badge_line(badge_area.width, &styles, apply_style);
+            Paragraph::new(ftui::text::Text::from_lines(vec![line_into_static(
+                badge_line,
+            )]))
+            .style(badge_bg_style)
+            .render(badge_area, frame);
+            layout_area = Rect::new(
+                layout_area.x,
+                layout_area.y + 1,
+                layout_area.width,
+                layout_area.height - 1,
+            );
+        }
+
         // ── Surface routing ──────────────────────────────────────────────
         match self.surface {
             AppSurface::Search => {
@@ -22417,6 +24210,7 @@ impl super::ftui_adapter::Model for CassApp {
         let modal_visible = self.show_export_modal
             || self.show_bulk_modal
             || self.show_saved_views_modal
+            || self.show_column_picker
             || self.show_detail_modal
             || self.show_help
             || self.show_inspector
@@ -22534,6 +24328,10 @@ impl super::ftui_adapter::Model for CassApp {
             self.last_saved_view_row_areas.borrow_mut().clear();
         }
 
+        if self.show_column_picker {
+            self.render_column_picker_overlay(frame, area, &styles);
+        }
+
         if self.source_filter_menu_open {
             self.render_source_filter_menu_overlay(frame, area, &styles);
         }
@@ -22774,8 +24572,8 @@ fn load_indexed_export_view(
         return Ok(None);
     }
 
-    let storage =
-        FrankenStorage::open(db_path).map_err(|err| format!("Failed to open database: {err}"))?;
+    let storage = FrankenStorage::open_rw_or_readonly(db_path)
+        .map_err(|err| format!("Failed to open database: {err}"))?;
     load_conversation_for_hit(&storage, hit).map_err(|err| format!("Failed to load session: {err}"))
 }
 
@@ -23263,17 +25061,53 @@ pub fn run_tui_ftui(
     let data_dir = data_dir_override.unwrap_or_else(crate::default_data_dir);
     model.data_dir = data_dir.clone();
     model.db_path = data_dir.join("agent_search.db");
+    model.data_dir_read_only = !crate::data_dir_is_writable(&data_dir);
+    if model.data_dir_read_only {
+        model.status = "Data dir is read-only: browsing and search work; state saves, view-state bookmarks, and analytics rebuilds are off.".to_string();
+    }
+    // Snippet sizing bounds from cass.toml; a broken config keeps the
+    // built-ins (the search CLI already reports config errors loudly).
+    if let Ok(defaults) = crate::search_defaults::load_search_defaults() {
+        model.snippet_bounds = crate::search_defaults::resolve_snippet_bounds(
+            defaults.snippet_min_chars,
+            defaults.snippet_max_chars,
+        );
+    }
+    if let Ok(tui_defaults) = crate::search_defaults::load_tui_defaults() {
+        model.collapse_tools_on_open = tui_defaults.collapse_tool_output.unwrap_or(true);
+        model.detail_find_in_collapsed = tui_defaults.find_in_collapsed.unwrap_or(true);
+    }
     model.refresh_doctor_hud_summary_from_cached_state();
     if model.db_path.exists() {
-        match crate::storage::sqlite::FrankenStorage::open_readonly(&model.db_path) {
-            Ok(storage) => {
-                #[allow(clippy::arc_with_non_send_sync)]
-                {
-                    model.db_reader = Some(Arc::new(storage));
-                }
+        // Negotiate schema compatibility before opening: a DB synced from a
+        // machine running a different cass must never surface a raw database
+        // error. Adjacent versions open read-only through the shim; anything
+        // farther gets a guidance screen instead of a reader.
+        match crate::storage::sqlite::check_read_compatibility(&model.db_path) {
+            crate::storage::sqlite::ReadCompatibility::Unsupported { guidance, .. } => {
+                model.db_compat_notice = Some(guidance);
+                model.status =
+                    "Database schema incompatible \u{2014} see the preview pane for guidance."
+                        .to_string();
             }
-            Err(e) => {
-                eprintln!("warn: failed to open db_reader: {e}");
+            compat => {
+                if let crate::storage::sqlite::ReadCompatibility::Shimmed { version } = compat {
+                    model.status = format!(
+                        "Database schema v{version} (this build uses v{}); reading via compatibility shim.",
+                        crate::storage::sqlite::CURRENT_SCHEMA_VERSION
+                    );
+                }
+                match crate::storage::sqlite::FrankenStorage::open_readonly(&model.db_path) {
+                    Ok(storage) => {
+                        #[allow(clippy::arc_with_non_send_sync)]
+                        {
+                            model.db_reader = Some(Arc::new(storage));
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("warn: failed to open db_reader: {e}");
+                    }
+                }
             }
         }
     }
@@ -24775,6 +26609,7 @@ mod tests {
             fancy_borders: true,
             help_pinned: false,
             has_seen_help: false,
+            result_columns: ResultColumnLayout::default(),
         };
     }
 
@@ -24825,6 +26660,7 @@ mod tests {
             fancy_borders: false,
             help_pinned: true,
             has_seen_help: true,
+            result_columns: ResultColumnLayout::parse("score,workspace:24,date").unwrap(),
         };
 
         save_persisted_state_to_path(&state_path, &state).expect("save state");
@@ -24858,6 +26694,10 @@ mod tests {
             loaded.analytics_source_filter,
             SourceFilter::Remote
         ));
+        assert_eq!(
+            loaded.result_columns.spec_string(),
+            "score,workspace:24,date"
+        );
     }
 
     #[test]
@@ -26172,6 +28012,7 @@ mod tests {
             PaletteResult::SaveViewSlot(1),
             PaletteResult::LoadViewSlot(1),
             PaletteResult::OpenBulkActions,
+            PaletteResult::OpenColumnPicker,
             PaletteResult::ReloadIndex,
             PaletteResult::OpenAnalyticsView(AnalyticsTarget::Dashboard),
             PaletteResult::OpenAnalyticsView(AnalyticsTarget::Explorer),
@@ -26310,6 +28151,68 @@ mod tests {
         assert!(!app.show_saved_views_modal);
     }
 
+    #[test]
+    fn column_picker_open_toggle_and_close() {
+        let mut app = CassApp::default();
+        let _ = app.update(CassMsg::ColumnPickerOpened);
+        assert!(app.show_column_picker);
+        assert_eq!(app.column_picker_cursor, 0);
+
+        // Cursor starts on the first enabled column (Score); toggling
+        // disables it and moves it to the disabled section.
+        let _ = app.update(CassMsg::QuerySubmitted);
+        assert!(!app.result_columns.contains(ResultColumn::Score));
+        assert!(app.dirty_since.is_some());
+
+        // Toggle it back on from the disabled section: the cursor followed
+        // the column, so another toggle re-enables it (appended last).
+        let _ = app.update(CassMsg::QuerySubmitted);
+        assert!(app.result_columns.contains(ResultColumn::Score));
+        assert_eq!(
+            app.result_columns.spec_string(),
+            "source,match,workspace,date,messages,score"
+        );
+
+        let _ = app.update(CassMsg::ColumnPickerClosed);
+        assert!(!app.show_column_picker);
+
+        // Layout round-trips through persisted state.
+        let state = app.capture_persisted_state();
+        assert_eq!(
+            state.result_columns.spec_string(),
+            app.result_columns.spec_string()
+        );
+    }
+
+    #[test]
+    fn column_picker_reorder_and_width_edits() {
+        let mut app = CassApp::default();
+        app.result_columns = ResultColumnLayout::parse("score,workspace,date").unwrap();
+        let _ = app.update(CassMsg::ColumnPickerOpened);
+
+        // Move cursor to the workspace column and push it down.
+        let _ = app.update(CassMsg::SelectionMoved { delta: 1 });
+        let _ = app.update(CassMsg::QueryChanged(">".to_string()));
+        assert_eq!(app.result_columns.spec_string(), "score,date,workspace");
+        // Cursor follows the moved column.
+        assert_eq!(app.column_picker_cursor, 2);
+
+        // Narrow the workspace tail.
+        let _ = app.update(CassMsg::QueryChanged("-".to_string()));
+        assert_eq!(app.result_columns.spec_string(), "score,date,workspace:30");
+
+        // Reset restores the defaults.
+        let _ = app.update(CassMsg::QueryChanged("r".to_string()));
+        assert_eq!(app.result_columns, ResultColumnLayout::default());
+
+        // Esc closes via QuitRequested without quitting the app.
+        let cmd = app.update(CassMsg::QuitRequested);
+        assert!(matches!(
+            extract_msg(cmd),
+            Some(CassMsg::ColumnPickerClosed)
+        ));
+    }
+
     #[test]
     fn saved_views_selection_move_handles_extreme_delta() {
         let mut app = CassApp::default();
@@ -26575,6 +28478,44 @@ mod tests {
         assert_eq!(app.input_buffer, "foo, /work/project-alpha");
     }
 
+    #[test]
+    fn workspace_autocomplete_prefers_recency_order_over_alphabetical() {
+        let mut app = CassApp::default();
+        let _ = app.update(CassMsg::InputModeEntered(InputMode::Workspace));
+        // `known_workspaces` arrives from the index most-recently-active
+        // first; a shared prefix must complete to the recent one even though
+        // it sorts after the other alphabetically.
+        app.known_workspaces = Some(vec![
+            "/home/u/dev/zeta".to_string(),
+            "/home/u/dev/alpha".to_string(),
+        ]);
+        app.input_buffer = "/home/u/dev/".to_string();
+
+        let _ = app.update(CassMsg::InputAutoCompleted);
+        assert_eq!(app.input_buffer, "/home/u/dev/zeta");
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn entering_workspace_filter_mode_loads_known_workspaces_from_index() {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = tmp.path().join("workspace_completions.db");
+        let storage = FrankenStorage::open(&db_path).expect("open frankensqlite");
+        storage
+            .ensure_workspace(std::path::Path::new("/home/u/dev/foo"), None)
+            .expect("insert workspace");
+
+        let mut app = CassApp::default();
+        app.db_reader = Some(Arc::new(storage));
+        assert!(app.known_workspaces.is_none());
+
+        let _ = app.update(CassMsg::InputModeEntered(InputMode::Workspace));
+        assert_eq!(
+            app.known_workspaces,
+            Some(vec!["/home/u/dev/foo".to_string()])
+        );
+    }
+
     #[test]
     fn focus_toggled_in_input_mode_autocompletes_without_changing_focus() {
         let mut app = CassApp::default();
@@ -27821,6 +29762,7 @@ mod tests {
             context_window: ContextWindow::Medium,
             limit: 16,
             offset: 0,
+            snippet_chars: None,
         };
         assert!(TantivySearchService::request_is_progressive_eligible(
             &params, true
@@ -28007,6 +29949,7 @@ mod tests {
                 context_window: app.context_window,
                 limit: app.interactive_search_limit(),
                 offset: 0,
+                snippet_chars: None,
             },
             progressive: true,
         });
@@ -28052,6 +29995,7 @@ mod tests {
                 context_window: ContextWindow::Medium,
                 limit: 16,
                 offset: 0,
+                snippet_chars: None,
             },
             progressive: true,
         });
@@ -28137,6 +30081,9 @@ mod tests {
                 query_terms: vec![],
                 query_highlight_style: ftui::Style::new(),
                 hovered: false,
+                columns: ResultColumnLayout::default(),
+                suppressed_count: 0,
+                date_header: None,
             };
             assert_eq!(item.height(), density_h, "density {mode:?}");
         }
@@ -28316,6 +30263,9 @@ mod tests {
             query_terms: vec![],
             query_highlight_style: ftui::Style::new(),
             hovered: false,
+            columns: ResultColumnLayout::default(),
+            suppressed_count: 0,
+            date_header: None,
         };
         let not_queued = ResultItem {
             index: 1,
@@ -28341,6 +30291,9 @@ mod tests {
             query_terms: vec![],
             query_highlight_style: ftui::Style::new(),
             hovered: false,
+            columns: ResultColumnLayout::default(),
+            suppressed_count: 0,
+            date_header: None,
         };
         assert!(queued_item.queued);
         assert!(!not_queued.queued);
@@ -28444,6 +30397,9 @@ mod tests {
             query_terms: vec![],
             query_highlight_style: ftui::Style::new(),
             hovered: false,
+            columns: ResultColumnLayout::default(),
+            suppressed_count: 0,
+            date_header: None,
         };
         assert_eq!(local_item.source_badge(), "[local]");
 
@@ -28475,6 +30431,9 @@ mod tests {
             query_terms: vec![],
             query_highlight_style: ftui::Style::new(),
             hovered: false,
+            columns: ResultColumnLayout::default(),
+            suppressed_count: 0,
+            date_header: None,
         };
         assert_eq!(remote_item.source_badge(), "[laptop]");
     }
@@ -29353,6 +31312,7 @@ mod tests {
             no_icons: false,
             no_gradient: false,
             a11y: false,
+            ascii: false,
         })
     }
 
@@ -30376,6 +32336,40 @@ mod tests {
         assert_eq!(app.detail_find.as_ref().unwrap().query, "h");
     }
 
+    #[test]
+    fn detail_modal_enter_commits_find_and_n_jumps_between_matches() {
+        let mut app = CassApp::default();
+        app.show_detail_modal = true;
+        let _ = app.update(CassMsg::DetailFindToggled);
+        let _ = app.update(CassMsg::QueryChanged("panic".to_string()));
+        *app.detail_find_matches_cache.borrow_mut() = vec![4, 12, 30];
+
+        // Enter leaves the input bar but keeps the find (and its highlights).
+        let _ = app.update(CassMsg::QuerySubmitted);
+        assert_eq!(app.input_mode, InputMode::Query);
+        let find = app.detail_find.as_ref().expect("find survives commit");
+        assert_eq!(find.query, "panic");
+        assert_eq!(find.current, 1, "Enter advances to the next match");
+
+        // n/N now jump between find matches instead of session hits.
+        let _ = app.update(CassMsg::QueryChanged("n".to_string()));
+        assert_eq!(app.detail_find.as_ref().unwrap().current, 2);
+        let _ = app.update(CassMsg::QueryChanged("N".to_string()));
+        assert_eq!(app.detail_find.as_ref().unwrap().current, 1);
+    }
+
+    #[test]
+    fn detail_modal_enter_on_empty_find_query_closes_the_bar() {
+        let mut app = CassApp::default();
+        app.show_detail_modal = true;
+        let _ = app.update(CassMsg::DetailFindToggled);
+        assert!(app.detail_find.is_some());
+        let _ = app.update(CassMsg::QuerySubmitted);
+        assert!(app.detail_find.is_none());
+        assert_eq!(app.input_mode, InputMode::Query);
+        assert!(app.show_detail_modal, "modal itself stays open");
+    }
+
     #[test]
     fn detail_modal_intercept_esc_closes_modal_in_one_press() {
         let mut app = CassApp::default();
@@ -31706,6 +33700,21 @@ not jsonl",
         }
     }
 
+    #[test]
+    fn wrap_plain_text_wraps_greedily_and_never_exceeds_width() {
+        let text = "This database uses schema v99, written by a newer cass than this build.";
+        let lines = wrap_plain_text(text, 24);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(display_width(line) <= 24, "line too wide: {line:?}");
+        }
+        assert_eq!(lines.join(" "), text);
+
+        // Degenerate widths still make progress (one word per line).
+        assert_eq!(wrap_plain_text("alpha beta", 1), vec!["alpha", "beta"]);
+        assert!(wrap_plain_text("", 10).is_empty());
+    }
+
     /// Find bar rendering in detail pane produces per-span styled output across presets.
     #[test]
     fn detail_find_bar_render_uses_styled_spans_across_presets() {
@@ -31970,6 +33979,9 @@ not jsonl",
             query_terms: vec![],
             query_highlight_style: ftui::Style::new(),
             hovered: false,
+            columns: ResultColumnLayout::default(),
+            suppressed_count: 0,
+            date_header: None,
         }
     }
 
@@ -33493,6 +35505,108 @@ not jsonl",
         assert_eq!(copied, "Fallback title");
     }
 
+    #[test]
+    fn copy_message_body_uses_the_message_at_the_detail_cursor() {
+        let mut app = app_with_hits(1);
+        app.show_detail_modal = true;
+        app.detail_tab = DetailTab::Messages;
+
+        let hit = app.panes[0].hits[0].clone();
+        let mut cv = make_test_conversation_view();
+        cv.convo.source_path = std::path::PathBuf::from(&hit.source_path);
+        cv.convo.source_id = hit.source_id.clone();
+        cv.messages = (0..2)
+            .map(|idx| Message {
+                id: Some(idx + 1),
+                idx,
+                role: MessageRole::Agent,
+                author: Some("cass".to_string()),
+                created_at: Some(1_700_000_000),
+                content: format!("message body {idx}"),
+                extra_json: serde_json::json!({}),
+                snippets: Vec::new(),
+            })
+            .collect();
+        app.cached_detail = Some((hit.source_path.clone(), cv));
+        *app.detail_message_offsets.borrow_mut() = vec![
+            (0, crate::model::types::MessageRole::Agent),
+            (10, crate::model::types::MessageRole::Agent),
+        ];
+        app.detail_scroll = 12;
+
+        let _ = app.update(CassMsg::CopyMessageBody);
+        assert_eq!(take_test_clipboard().as_deref(), Some("message body 1"));
+        assert!(app.status.contains("Copied message"));
+    }
+
+    #[test]
+    fn copy_code_block_extracts_the_fenced_block_under_the_cursor() {
+        let mut app = app_with_hits(1);
+        app.show_detail_modal = true;
+        app.detail_tab = DetailTab::Messages;
+
+        let hit = app.panes[0].hits[0].clone();
+        let mut cv = make_test_conversation_view();
+        cv.convo.source_path = std::path::PathBuf::from(&hit.source_path);
+        cv.convo.source_id = hit.source_id.clone();
+        cv.messages = vec![Message {
+            id: Some(1),
+            idx: 0,
+            role: MessageRole::Agent,
+            author: Some("cass".to_string()),
+            created_at: Some(1_700_000_000),
+            content: "Run this:\n```sh\ncargo test\n```\ndone".to_string(),
+            extra_json: serde_json::json!({}),
+            snippets: Vec::new(),
+        }];
+        app.cached_detail = Some((hit.source_path.clone(), cv));
+        *app.detail_message_offsets.borrow_mut() =
+            vec![(0, crate::model::types::MessageRole::Agent)];
+
+        let _ = app.update(CassMsg::CopyCodeBlock);
+        assert_eq!(take_test_clipboard().as_deref(), Some("cargo test"));
+
+        // A message without a fence reports instead of copying.
+        app.cached_detail.as_mut().unwrap().1.messages[0].content = "no code here".to_string();
+        let _ = app.update(CassMsg::CopyCodeBlock);
+        assert!(take_test_clipboard().is_none());
+        assert!(app.status.contains("No fenced code block"));
+    }
+
+    #[test]
+    fn copy_permalink_appends_the_one_based_message_number() {
+        let mut app = app_with_hits(1);
+        app.show_detail_modal = true;
+        *app.detail_message_offsets.borrow_mut() = vec![
+            (0, crate::model::types::MessageRole::User),
+            (8, crate::model::types::MessageRole::Agent),
+        ];
+        app.detail_scroll = 9;
+
+        let source_path = app.panes[0].hits[0].source_path.clone();
+        let _ = app.update(CassMsg::CopyPermalink);
+        assert_eq!(
+            take_test_clipboard(),
+            Some(format!("{source_path}:2")),
+            "permalink should use the 1-based message number cass note add accepts"
+        );
+
+        // Without rendered offsets the permalink is the bare path.
+        app.detail_message_offsets.borrow_mut().clear();
+        let _ = app.update(CassMsg::CopyPermalink);
+        assert_eq!(take_test_clipboard(), Some(source_path));
+    }
+
+    #[test]
+    fn first_fenced_code_block_ignores_unclosed_fences() {
+        assert_eq!(
+            first_fenced_code_block("a\n```rust\nlet x = 1;\n```\nb").as_deref(),
+            Some("let x = 1;")
+        );
+        assert_eq!(first_fenced_code_block("```\nstill streaming"), None);
+        assert_eq!(first_fenced_code_block("plain prose"), None);
+    }
+
     #[test]
     #[serial]
     fn copy_path_rewrites_configured_remote_source_paths() {
@@ -38265,6 +40379,104 @@ not jsonl",
         assert!(!app.collapsed_tools.contains(&1));
     }
 
+    #[test]
+    fn collapsed_summary_shows_label_size_and_exit_status() {
+        let summary =
+            collapsed_tool_summary("[Tool Output]\ncargo test output here\nfailed\nexit code: 101");
+        assert!(summary.starts_with("Tool Output"), "{summary}");
+        assert!(summary.contains("cargo test output here"), "{summary}");
+        assert!(summary.contains(" B"), "size missing: {summary}");
+        assert!(summary.contains("exit 101"), "{summary}");
+
+        // No bracketed marker: first line becomes the excerpt, no exit noise.
+        let plain = collapsed_tool_summary("plain system note without markers");
+        assert!(plain.contains("plain system note"), "{plain}");
+        assert!(!plain.contains("exit"), "{plain}");
+
+        // Size formatting crosses the KB threshold.
+        let big = collapsed_tool_summary(&"x".repeat(4096));
+        assert!(big.contains("4.0 KB"), "{big}");
+    }
+
+    #[test]
+    fn o_key_toggles_fold_only_on_tool_messages() {
+        let mut app = app_with_hits(3);
+        *app.detail_message_offsets.borrow_mut() = vec![
+            (0, crate::model::types::MessageRole::User),
+            (5, crate::model::types::MessageRole::Tool),
+            (12, crate::model::types::MessageRole::Agent),
+        ];
+        // Cursor inside the tool message toggles its fold (message index 1).
+        app.detail_scroll = 7;
+        let _ = app.toggle_tool_collapse_at_cursor();
+        assert!(app.collapsed_tools.contains(&1));
+        let _ = app.toggle_tool_collapse_at_cursor();
+        assert!(!app.collapsed_tools.contains(&1));
+        // Cursor on a prose message is a no-op.
+        app.detail_scroll = 13;
+        let _ = app.toggle_tool_collapse_at_cursor();
+        assert!(app.collapsed_tools.is_empty());
+    }
+
+    #[test]
+    fn detail_open_respects_collapse_config_opt_out() {
+        let mut app = app_with_cached_conversation();
+        app.collapse_tools_on_open = false;
+        let _ = app.update(CassMsg::DetailOpened);
+        assert!(
+            app.collapsed_tools.is_empty(),
+            "collapse_tool_output = false must open fully expanded"
+        );
+    }
+
+    #[test]
+    fn detail_find_expands_collapsed_messages_that_match() {
+        use crate::model::types::{Message, MessageRole};
+        let mut app = app_with_hits(3);
+
+        fn msg(role: MessageRole, content: &str) -> Message {
+            Message {
+                id: None,
+                idx: 0,
+                role,
+                author: None,
+                created_at: None,
+                content: content.to_string(),
+                extra_json: serde_json::json!({}),
+                snippets: Vec::new(),
+            }
+        }
+
+        let mut cv = make_test_conversation_view();
+        cv.convo.source_path = std::path::PathBuf::from("/path/0");
+        cv.convo.source_id = "local".to_string();
+        cv.messages = vec![
+            msg(MessageRole::User, "hello"),
+            msg(MessageRole::Tool, "grep found the needle in tool output"),
+            msg(MessageRole::System, "unrelated system note"),
+        ];
+        app.cached_detail = Some(("/path/0".to_string(), cv));
+        app.collapsed_tools.insert(1);
+        app.collapsed_tools.insert(2);
+        app.detail_find = Some(DetailFindState::default());
+
+        let _ = app.update(CassMsg::DetailFindQueryChanged("needle".to_string()));
+        assert!(
+            !app.collapsed_tools.contains(&1),
+            "matching collapsed message should expand"
+        );
+        assert!(
+            app.collapsed_tools.contains(&2),
+            "non-matching message stays collapsed"
+        );
+
+        // With the config off, collapsed sections are left alone.
+        app.collapsed_tools.insert(1);
+        app.detail_find_in_collapsed = false;
+        let _ = app.update(CassMsg::DetailFindQueryChanged("needle".to_string()));
+        assert!(app.collapsed_tools.contains(&1));
+    }
+
     // -- End collapsible tool call tests --------------------------------------
 
     // -- Detail header tests --------------------------------------------------
@@ -40137,6 +42349,254 @@ not jsonl",
         assert!(app.status.contains("next day"));
     }
 
+    #[test]
+    fn date_bucket_for_timestamp_classifies_by_day_distance() {
+        let now = 86400 * 19000 + 3600;
+        let day = |n: i64| Some(now - n * 86400);
+        assert_eq!(DateBucket::for_timestamp(day(0), now), DateBucket::Today);
+        assert_eq!(DateBucket::for_timestamp(day(1), now), DateBucket::Yesterday);
+        assert_eq!(DateBucket::for_timestamp(day(3), now), DateBucket::ThisWeek);
+        assert_eq!(DateBucket::for_timestamp(day(8), now), DateBucket::LastWeek);
+        assert_eq!(DateBucket::for_timestamp(day(20), now), DateBucket::ThisMonth);
+        assert_eq!(DateBucket::for_timestamp(day(90), now), DateBucket::Older);
+        assert_eq!(DateBucket::for_timestamp(None, now), DateBucket::Older);
+        // Slightly-future timestamps (clock skew) land in Today.
+        assert_eq!(
+            DateBucket::for_timestamp(Some(now + 60), now),
+            DateBucket::Today
+        );
+    }
+
+    #[test]
+    fn date_headers_mark_bucket_starts_in_date_sorts_only() {
+        let mut app = CassApp::default();
+        let now = chrono::Utc::now().timestamp();
+        let mut hits: Vec<SearchHit> = Vec::new();
+        for i in 0..2 {
+            let mut h = make_hit(i, &format!("/today/{i}"));
+            h.created_at = Some(now - i as i64 * 60);
+            hits.push(h);
+        }
+        let mut old = make_hit(2, "/old/2");
+        old.created_at = Some(now - 90 * 86400);
+        hits.push(old);
+
+        // Score-mixed ranking: no headers, regardless of hit dates.
+        app.ranking_mode = RankingMode::Balanced;
+        assert_eq!(app.date_header_for("All", &hits, 0, now), None);
+
+        app.ranking_mode = RankingMode::DateNewest;
+        let first = app.date_header_for("All", &hits, 0, now);
+        assert!(first.is_some_and(|h| h.contains("Today")));
+        assert_eq!(
+            app.date_header_for("All", &hits, 1, now),
+            None,
+            "second hit of the same bucket should not repeat the header"
+        );
+        let boundary = app.date_header_for("All", &hits, 2, now);
+        assert!(boundary.is_some_and(|h| h.contains("Older")));
+    }
+
+    #[test]
+    fn date_fold_toggle_hides_all_but_first_hit_of_bucket() {
+        let mut app = CassApp::default();
+        app.ranking_mode = RankingMode::DateNewest;
+        app.grouping_mode = ResultsGrouping::Flat;
+        let now = chrono::Utc::now().timestamp();
+        let mut hits: Vec<SearchHit> = Vec::new();
+        for i in 0..3 {
+            let mut h = make_hit(i, &format!("/today/{i}"));
+            h.created_at = Some(now - i as i64 * 60);
+            hits.push(h);
+        }
+        for i in 3..5 {
+            let mut h = make_hit(i, &format!("/old/{i}"));
+            h.created_at = Some(now - 90 * 86400 - i as i64 * 60);
+            hits.push(h);
+        }
+        app.results = hits;
+        app.regroup_panes();
+        assert_eq!(app.panes[0].hits.len(), 5);
+
+        // Fold Today (the bucket under the cursor): its first hit stays as
+        // the fold header carrier, the other two hide.
+        let _ = app.update(CassMsg::DateFoldToggled);
+        assert!(app.status.contains("folded"));
+        assert_eq!(app.panes[0].hits.len(), 3);
+        assert_eq!(
+            app.date_bucket_hidden
+                .get(&("All".to_string(), DateBucket::Today)),
+            Some(&2)
+        );
+        let header = app.date_header_for("All", &app.panes[0].hits, 0, now);
+        assert!(header.is_some_and(|h| h.contains("Today") && h.contains("2 folded")));
+
+        // Toggling again expands the bucket.
+        let _ = app.update(CassMsg::DateFoldToggled);
+        assert!(app.status.contains("expanded"));
+        assert_eq!(app.panes[0].hits.len(), 5);
+    }
+
+    #[test]
+    fn date_fold_toggle_requires_a_date_sort() {
+        let mut app = CassApp::default();
+        app.ranking_mode = RankingMode::Balanced;
+        let _ = app.update(CassMsg::DateFoldToggled);
+        assert!(app.status.contains("date sort"));
+        assert!(app.collapsed_date_buckets.is_empty());
+    }
+
+    #[test]
+    fn follow_toggle_with_no_followable_session_reports_status() {
+        let mut app = CassApp::default();
+        let _ = app.update(CassMsg::FollowModeToggled);
+        assert!(!app.follow_mode);
+        assert!(app.status.contains("follow"));
+    }
+
+    #[test]
+    fn follow_toggle_opens_most_recently_modified_local_session() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let stale = dir.path().join("stale.jsonl");
+        let live = dir.path().join("live.jsonl");
+        std::fs::write(&stale, "old session").unwrap();
+        std::fs::write(&live, "active session").unwrap();
+        let backdated = std::time::SystemTime::now() - Duration::from_secs(3600);
+        std::fs::File::options()
+            .write(true)
+            .open(&stale)
+            .unwrap()
+            .set_modified(backdated)
+            .unwrap();
+
+        let mut app = CassApp::default();
+        app.results = vec![
+            make_hit(0, stale.to_str().unwrap()),
+            make_hit(1, live.to_str().unwrap()),
+        ];
+        app.regroup_panes();
+
+        let _ = app.update(CassMsg::FollowModeToggled);
+        assert!(app.follow_mode);
+        assert!(app.show_detail_modal);
+        assert_eq!(
+            app.selected_hit().map(|hit| hit.source_path.clone()),
+            Some(live.to_str().unwrap().to_string()),
+            "follow should land on the most recently modified source file"
+        );
+        assert!(app.follow_stat.is_some());
+
+        // Alt+F again turns follow off without closing the viewer.
+        let _ = app.update(CassMsg::FollowModeToggled);
+        assert!(!app.follow_mode);
+        assert!(app.show_detail_modal);
+    }
+
+    #[test]
+    fn follow_poll_stops_after_detail_modal_closes() {
+        let mut app = CassApp::default();
+        app.follow_mode = true;
+        app.follow_stat = Some((1, 1));
+        let _ = app.update(CassMsg::FollowPollTicked);
+        assert!(!app.follow_mode);
+        assert!(app.follow_stat.is_none());
+    }
+
+    #[test]
+    fn follow_index_completed_clears_in_flight_flag() {
+        let mut app = CassApp::default();
+        app.follow_index_in_flight = true;
+        let _ = app.update(CassMsg::FollowIndexCompleted);
+        assert!(!app.follow_index_in_flight);
+    }
+
+    #[test]
+    fn agent_badges_loaded_updates_header_strip_state() {
+        let mut app = CassApp::default();
+        let _ = app.update(CassMsg::AgentBadgesLoaded(vec![AgentIndexBadge {
+            agent: "codex".to_string(),
+            conversations: 12,
+            last_indexed_ms: Some(1_700_000_000_000),
+        }]));
+        assert_eq!(app.agent_index_badges.len(), 1);
+        assert_eq!(app.agent_index_badges[0].agent, "codex");
+    }
+
+    #[test]
+    fn empty_index_state_waits_for_badge_load_before_showing() {
+        let mut app = CassApp::default();
+        assert!(
+            !app.empty_index_state_visible(),
+            "an unloaded badge list must not read as an empty index"
+        );
+
+        let _ = app.update(CassMsg::AgentBadgesLoaded(Vec::new()));
+        assert!(app.empty_index_state_visible());
+
+        app.index_refresh_in_flight = true;
+        assert!(
+            !app.empty_index_state_visible(),
+            "progress should replace the empty-index hint while indexing runs"
+        );
+        app.index_refresh_in_flight = false;
+
+        let _ = app.update(CassMsg::AgentBadgesLoaded(vec![AgentIndexBadge {
+            agent: "codex".to_string(),
+            conversations: 3,
+            last_indexed_ms: None,
+        }]));
+        assert!(!app.empty_index_state_visible());
+    }
+
+    #[test]
+    fn active_filter_summary_lists_only_active_pills() {
+        let mut app = CassApp::default();
+        assert!(app.active_filter_summary().is_empty());
+
+        app.filters.agents.insert("codex".to_string());
+        app.filters.created_from = Some(1_700_000_000_000);
+        let summary = app.active_filter_summary();
+        assert_eq!(summary.len(), 2, "{summary:?}");
+        assert_eq!(summary[0], "agent:codex");
+        assert!(summary[1].starts_with("time:"), "{summary:?}");
+    }
+
+    #[test]
+    fn agent_badge_line_shows_counts_and_ages() {
+        let mut app = CassApp::default();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        app.agent_index_badges = vec![
+            AgentIndexBadge {
+                agent: "claude-code".to_string(),
+                conversations: 1204,
+                last_indexed_ms: Some(now_ms - 3_600_000),
+            },
+            AgentIndexBadge {
+                agent: "codex".to_string(),
+                conversations: 87,
+                last_indexed_ms: Some(now_ms - 30 * 86_400_000),
+            },
+        ];
+        let styles = app.resolved_style_context();
+        let line = app.build_agent_badge_line(120, &styles, false);
+        let plain: String = line.spans().iter().map(|s| s.content.as_ref()).collect();
+        assert!(plain.contains("claude-code 1204"), "got: {plain}");
+        assert!(plain.contains("codex 87"), "got: {plain}");
+        assert!(plain.contains("\u{b7}1h"), "fresh age should render: {plain}");
+        assert!(plain.contains("\u{b7}30d"), "stale age should render: {plain}");
+    }
+
+    #[test]
+    fn index_refresh_completed_schedules_badge_reload() {
+        let mut app = CassApp::default();
+        let cmd = app.update(CassMsg::IndexRefreshCompleted);
+        let debug = format!("{cmd:?}");
+        assert!(
+            debug.contains("Task"),
+            "completion should schedule the badge reload task, got: {debug}"
+        );
+    }
+
     #[test]
     fn results_title_shows_grouping_mode() {
         let mut app = app_with_hits(3);
@@ -41488,6 +43948,35 @@ See also: [RFC-2847](https://internal/rfc/2847) for the full design doc.
         );
     }
 
+    /// Scrolling re-renders reuse the per-message markdown cache; a width
+    /// change drops it.
+    #[test]
+    fn build_messages_lines_caches_rendered_markdown_per_message() {
+        let app = app_with_markdown_detail(3);
+        let hit = make_test_hit();
+        let styles = StyleContext::from_options(StyleOptions::default());
+
+        let first = lines_into_static(app.build_messages_lines(&hit, 120, &styles));
+        let cached_messages = app.detail_md_cache.borrow().lines.len();
+        assert!(cached_messages > 0, "render should populate the md cache");
+        let key = app.detail_md_cache.borrow().key;
+
+        let second = lines_into_static(app.build_messages_lines(&hit, 120, &styles));
+        assert_eq!(first.len(), second.len(), "cached render must match");
+        assert_eq!(
+            app.detail_md_cache.borrow().key,
+            key,
+            "same fingerprint should keep the cache"
+        );
+
+        let _ = app.build_messages_lines(&hit, 80, &styles);
+        assert_ne!(
+            app.detail_md_cache.borrow().key,
+            key,
+            "a width change must re-key the cache"
+        );
+    }
+
     /// Budget: build_messages_lines with plain text (no markdown) < 10ms.
     #[test]
     fn perf_profile_build_messages_lines_plain_text() {
@@ -9,6 +9,7 @@ pub mod export_modal;
 pub mod help_strip;
 pub mod palette;
 pub mod pills;
+pub mod result_columns;
 pub mod theme;
 pub mod toast;
 /// Retained legacy shell module; active widget behavior lives elsewhere.
@@ -23,7 +23,7 @@
 //! |-------------|------------------------------------------------------------|
 //! | Chrome      | ToggleTheme, ToggleDensity, ToggleHelpStrip, OpenUpdate    |
 //! | Filter      | FilterAgent, FilterWorkspace, FilterToday/Week/CustomDate  |
-//! | View        | OpenSavedViews, SaveViewSlot, LoadViewSlot, BulkActions, ReloadIndex |
+//! | View        | OpenSavedViews, SaveViewSlot, LoadViewSlot, BulkActions, ConfigureColumns, ReloadIndex |
 //! | Analytics   | AnalyticsDashboard..AnalyticsCoverage                      |
 //! | Export      | ScreenshotHtml, ScreenshotSvg, ScreenshotText             |
 //! | Recording   | MacroRecordingToggle                                       |
@@ -163,6 +163,7 @@ pub enum PaletteAction {
     SaveViewSlot(u8),
     LoadViewSlot(u8),
     OpenBulkActions,
+    ConfigureColumns,
     ReloadIndex,
     // -- Analytics surface ------------------------------------------------
     AnalyticsDashboard,
@@ -199,6 +200,7 @@ impl PaletteAction {
             | Self::SaveViewSlot(_)
             | Self::LoadViewSlot(_)
             | Self::OpenBulkActions
+            | Self::ConfigureColumns
             | Self::ReloadIndex => PaletteGroup::View,
             Self::AnalyticsDashboard
             | Self::AnalyticsExplorer
@@ -238,6 +240,7 @@ impl PaletteAction {
             Self::SaveViewSlot(_) => "ViewSaved(slot)",
             Self::LoadViewSlot(_) => "ViewLoaded(slot)",
             Self::OpenBulkActions => "BulkActionsOpened",
+            Self::ConfigureColumns => "ColumnPickerOpened",
             Self::ReloadIndex => "IndexRefreshRequested",
             // Analytics (all batch: AnalyticsEntered + AnalyticsViewChanged)
             Self::AnalyticsDashboard => "batch[AnalyticsEntered, AnalyticsViewChanged(Dashboard)]",
@@ -288,6 +291,8 @@ pub enum PaletteResult {
     LoadViewSlot(u8),
     /// Open the bulk-actions menu.
     OpenBulkActions,
+    /// Open the results column picker.
+    OpenColumnPicker,
     /// Reload/refresh the index.
     ReloadIndex,
     /// Navigate to an analytics sub-view (by name).
@@ -365,6 +370,7 @@ impl PaletteAction {
             Self::SaveViewSlot(slot) => PaletteResult::SaveViewSlot(*slot),
             Self::LoadViewSlot(slot) => PaletteResult::LoadViewSlot(*slot),
             Self::OpenBulkActions => PaletteResult::OpenBulkActions,
+            Self::ConfigureColumns => PaletteResult::OpenColumnPicker,
             Self::ReloadIndex => PaletteResult::ReloadIndex,
             // Analytics
             Self::AnalyticsDashboard => {
@@ -543,6 +549,11 @@ pub fn default_actions() -> Vec<PaletteItem> {
             "Bulk actions",
             shortcuts::BULK_MENU,
         ),
+        item(
+            PaletteAction::ConfigureColumns,
+            "Configure columns",
+            "Results list columns",
+        ),
         item(
             PaletteAction::ReloadIndex,
             "Reload index/view",
@@ -1054,6 +1065,7 @@ mod tests {
             PaletteAction::SaveViewSlot(1),
             PaletteAction::LoadViewSlot(1),
             PaletteAction::OpenBulkActions,
+            PaletteAction::ConfigureColumns,
             PaletteAction::ReloadIndex,
             PaletteAction::AnalyticsDashboard,
             PaletteAction::AnalyticsExplorer,
@@ -1089,6 +1101,7 @@ mod tests {
             PaletteAction::SaveViewSlot(1),
             PaletteAction::LoadViewSlot(1),
             PaletteAction::OpenBulkActions,
+            PaletteAction::ConfigureColumns,
             PaletteAction::ReloadIndex,
             PaletteAction::AnalyticsDashboard,
             PaletteAction::AnalyticsExplorer,
@@ -1196,6 +1209,7 @@ mod tests {
             PaletteAction::FilterCustomDate,
             PaletteAction::OpenSavedViews,
             PaletteAction::OpenBulkActions,
+            PaletteAction::ConfigureColumns,
             PaletteAction::ReloadIndex,
             PaletteAction::AnalyticsDashboard,
             PaletteAction::AnalyticsExplorer,
@@ -1396,6 +1410,7 @@ mod tests {
             PaletteAction::SaveViewSlot(1),
             PaletteAction::LoadViewSlot(1),
             PaletteAction::OpenBulkActions,
+            PaletteAction::ConfigureColumns,
             PaletteAction::ReloadIndex,
             PaletteAction::AnalyticsDashboard,
             PaletteAction::AnalyticsExplorer,
@@ -0,0 +1,334 @@
+//! Configurable column layout for the results list metadata row.
+//!
+//! Each result row's second line is a sequence of metadata columns (score
+//! bar, source badge, match chip, workspace tail, date, matched-message
+//! count). The fixed layout wastes width in narrow panes, so the set,
+//! order, and workspace width are user-configurable: the layout is edited
+//! at runtime through the column picker overlay (command palette →
+//! "Configure columns") and persists in `tui_state.json` as a compact spec
+//! string like `score,source,match,workspace:32,date,messages`.
+//!
+//! State and pure layout operations live here; rendering is done in
+//! [`crate::ui::app`] (`ResultItem::render` consumes the layout, the picker
+//! overlay is drawn by `CassApp::render_column_picker_overlay`).
+
+/// A metadata column the results list can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultColumn {
+    /// Relevance score bar.
+    Score,
+    /// Source badge (local vs remote host).
+    Source,
+    /// Match-type chip (exact/prefix/wildcard/...).
+    Match,
+    /// Agent slug. Off by default — line one already shows it.
+    Agent,
+    /// Workspace tail, elided to the column width.
+    Workspace,
+    /// Session timestamp (`YYYY-MM-DD HH:MM`).
+    Date,
+    /// Matched-message count for the session (mini analytics).
+    Messages,
+}
+
+impl ResultColumn {
+    /// Canonical ordering, used for the picker's disabled section.
+    pub const ALL: [ResultColumn; 7] = [
+        Self::Score,
+        Self::Source,
+        Self::Match,
+        Self::Agent,
+        Self::Workspace,
+        Self::Date,
+        Self::Messages,
+    ];
+
+    /// Spec-string token for this column.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Score => "score",
+            Self::Source => "source",
+            Self::Match => "match",
+            Self::Agent => "agent",
+            Self::Workspace => "workspace",
+            Self::Date => "date",
+            Self::Messages => "messages",
+        }
+    }
+
+    /// Human-readable label for the picker overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Score => "Score bar",
+            Self::Source => "Source badge",
+            Self::Match => "Match type",
+            Self::Agent => "Agent",
+            Self::Workspace => "Workspace tail",
+            Self::Date => "Date",
+            Self::Messages => "Message count",
+        }
+    }
+
+    /// Parse a spec token, accepting common aliases.
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "score" => Some(Self::Score),
+            "source" | "src" => Some(Self::Source),
+            "match" | "match_type" | "mt" => Some(Self::Match),
+            "agent" => Some(Self::Agent),
+            "workspace" | "ws" => Some(Self::Workspace),
+            "date" | "time" | "when" => Some(Self::Date),
+            "messages" | "msgs" | "count" => Some(Self::Messages),
+            _ => None,
+        }
+    }
+
+    /// Whether a width annotation is meaningful for this column.
+    /// Only the workspace tail is elided to a configurable width; the
+    /// other columns render at their natural size.
+    pub fn supports_width(self) -> bool {
+        matches!(self, Self::Workspace)
+    }
+
+    /// Default width for width-bearing columns.
+    pub fn default_width(self) -> Option<u16> {
+        self.supports_width().then_some(32)
+    }
+}
+
+/// Width bounds for width-bearing columns (workspace tail).
+pub const MIN_COLUMN_WIDTH: u16 = 8;
+pub const MAX_COLUMN_WIDTH: u16 = 64;
+
+/// One column in the layout, with an optional configured width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSpec {
+    pub column: ResultColumn,
+    /// Configured width; `None` means the column's natural/default size.
+    pub width: Option<u16>,
+}
+
+impl ColumnSpec {
+    fn new(column: ResultColumn) -> Self {
+        Self {
+            column,
+            width: None,
+        }
+    }
+
+    /// Effective width for width-bearing columns.
+    pub fn effective_width(&self) -> Option<u16> {
+        self.width.or_else(|| self.column.default_width())
+    }
+}
+
+/// Ordered set of enabled columns for the results metadata row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultColumnLayout {
+    specs: Vec<ColumnSpec>,
+}
+
+impl Default for ResultColumnLayout {
+    /// Matches the historical fixed layout.
+    fn default() -> Self {
+        Self {
+            specs: vec![
+                ColumnSpec::new(ResultColumn::Score),
+                ColumnSpec::new(ResultColumn::Source),
+                ColumnSpec::new(ResultColumn::Match),
+                ColumnSpec::new(ResultColumn::Workspace),
+                ColumnSpec::new(ResultColumn::Date),
+                ColumnSpec::new(ResultColumn::Messages),
+            ],
+        }
+    }
+}
+
+impl ResultColumnLayout {
+    /// Enabled columns in display order.
+    pub fn specs(&self) -> &[ColumnSpec] {
+        &self.specs
+    }
+
+    pub fn contains(&self, column: ResultColumn) -> bool {
+        self.specs.iter().any(|s| s.column == column)
+    }
+
+    /// Parse a comma-separated spec string (`name` or `name:width` tokens).
+    /// Unknown tokens are errors so typos in hand-edited state files are
+    /// surfaced instead of silently dropping columns; duplicates keep the
+    /// first occurrence.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut specs: Vec<ColumnSpec> = Vec::new();
+        for token in spec.split(',') {
+            let token = token.trim();
+            if token.is_empty() {
+                continue;
+            }
+            let (name, width) = match token.split_once(':') {
+                Some((name, width)) => {
+                    let width: u16 = width
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("invalid column width in '{token}'"))?;
+                    (name, Some(width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH)))
+                }
+                None => (token, None),
+            };
+            let column = ResultColumn::parse(name)
+                .ok_or_else(|| format!("unknown results column '{name}'"))?;
+            if specs.iter().any(|s| s.column == column) {
+                continue;
+            }
+            specs.push(ColumnSpec {
+                column,
+                width: width.filter(|_| column.supports_width()),
+            });
+        }
+        if specs.is_empty() {
+            return Err("column spec selects no columns".to_string());
+        }
+        Ok(Self { specs })
+    }
+
+    /// Serialize back to the spec-string form accepted by [`Self::parse`].
+    pub fn spec_string(&self) -> String {
+        self.specs
+            .iter()
+            .map(|s| match s.width {
+                Some(w) => format!("{}:{w}", s.column.key()),
+                None => s.column.key().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Enable a disabled column (appended at the end) or disable an enabled
+    /// one. The last remaining column cannot be disabled; returns whether
+    /// anything changed.
+    pub fn toggle(&mut self, column: ResultColumn) -> bool {
+        if let Some(idx) = self.specs.iter().position(|s| s.column == column) {
+            if self.specs.len() == 1 {
+                return false;
+            }
+            self.specs.remove(idx);
+        } else {
+            self.specs.push(ColumnSpec::new(column));
+        }
+        true
+    }
+
+    /// Move an enabled column up (`delta < 0`) or down (`delta > 0`).
+    pub fn move_column(&mut self, column: ResultColumn, delta: i32) -> bool {
+        let Some(idx) = self.specs.iter().position(|s| s.column == column) else {
+            return false;
+        };
+        let target = idx.saturating_add_signed(delta as isize);
+        let target = target.min(self.specs.len().saturating_sub(1));
+        if target == idx {
+            return false;
+        }
+        let spec = self.specs.remove(idx);
+        self.specs.insert(target, spec);
+        true
+    }
+
+    /// Adjust the width of a width-bearing enabled column, clamped to
+    /// [`MIN_COLUMN_WIDTH`]..=[`MAX_COLUMN_WIDTH`]. No-op for natural-size
+    /// columns.
+    pub fn adjust_width(&mut self, column: ResultColumn, delta: i32) -> bool {
+        let Some(spec) = self.specs.iter_mut().find(|s| s.column == column) else {
+            return false;
+        };
+        if !spec.column.supports_width() {
+            return false;
+        }
+        let current = spec.effective_width().unwrap_or(MIN_COLUMN_WIDTH);
+        let next = current
+            .saturating_add_signed(delta as i16)
+            .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        if next == current && spec.width.is_some() {
+            return false;
+        }
+        spec.width = Some(next);
+        true
+    }
+
+    /// Picker rows: enabled columns in display order, then disabled columns
+    /// in canonical order.
+    pub fn picker_entries(&self) -> Vec<(ResultColumn, bool)> {
+        let mut entries: Vec<(ResultColumn, bool)> =
+            self.specs.iter().map(|s| (s.column, true)).collect();
+        for column in ResultColumn::ALL {
+            if !self.contains(column) {
+                entries.push((column, false));
+            }
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_layout_round_trips_through_spec_string() {
+        let layout = ResultColumnLayout::default();
+        assert_eq!(
+            layout.spec_string(),
+            "score,source,match,workspace,date,messages"
+        );
+        let parsed = ResultColumnLayout::parse(&layout.spec_string()).unwrap();
+        assert_eq!(parsed, layout);
+    }
+
+    #[test]
+    fn parse_accepts_widths_aliases_and_rejects_unknown_columns() {
+        let layout = ResultColumnLayout::parse("score, ws:24, date").unwrap();
+        assert_eq!(layout.specs().len(), 3);
+        assert_eq!(layout.specs()[1].column, ResultColumn::Workspace);
+        assert_eq!(layout.specs()[1].effective_width(), Some(24));
+
+        // Width clamps; duplicates keep the first occurrence.
+        let layout = ResultColumnLayout::parse("workspace:200,score,score").unwrap();
+        assert_eq!(layout.specs()[0].effective_width(), Some(MAX_COLUMN_WIDTH));
+        assert_eq!(layout.specs().len(), 2);
+
+        assert!(ResultColumnLayout::parse("score,bogus").is_err());
+        assert!(ResultColumnLayout::parse("").is_err());
+    }
+
+    #[test]
+    fn toggle_keeps_at_least_one_column() {
+        let mut layout = ResultColumnLayout::parse("score").unwrap();
+        assert!(!layout.toggle(ResultColumn::Score));
+        assert!(layout.toggle(ResultColumn::Date));
+        assert!(layout.toggle(ResultColumn::Score));
+        assert_eq!(layout.spec_string(), "date");
+    }
+
+    #[test]
+    fn move_and_width_operations() {
+        let mut layout = ResultColumnLayout::parse("score,workspace,date").unwrap();
+        assert!(layout.move_column(ResultColumn::Date, -1));
+        assert_eq!(layout.spec_string(), "score,date,workspace");
+        // Already at the bottom: no change.
+        assert!(!layout.move_column(ResultColumn::Workspace, 1));
+
+        assert!(layout.adjust_width(ResultColumn::Workspace, -10));
+        assert_eq!(layout.spec_string(), "score,date,workspace:22");
+        // Natural-size columns ignore width adjustments.
+        assert!(!layout.adjust_width(ResultColumn::Score, 4));
+    }
+
+    #[test]
+    fn picker_entries_list_enabled_then_disabled() {
+        let layout = ResultColumnLayout::parse("date,score").unwrap();
+        let entries = layout.picker_entries();
+        assert_eq!(entries[0], (ResultColumn::Date, true));
+        assert_eq!(entries[1], (ResultColumn::Score, true));
+        assert!(entries[2..].iter().all(|(_, enabled)| !enabled));
+        assert_eq!(entries.len(), ResultColumn::ALL.len());
+    }
+}
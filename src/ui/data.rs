@@ -507,6 +507,9 @@ pub fn load_conversation(
 
     // Cache the result if found
     if let Some(v) = view {
+        // Track the open for `cass recent` (once per cache lifetime, so
+        // scrolling through cached previews does not inflate counts).
+        storage.record_conversation_view(source_path);
         if let Some(scope) = cache_scope.as_deref() {
             CONVERSATION_CACHE.insert_scoped(scope, None, source_path, v.clone());
         }
@@ -548,6 +551,7 @@ pub fn load_conversation_for_source(
     let view = load_conversation_uncached(storage, Some(source_id), source_path)?;
 
     if let Some(v) = view {
+        storage.record_conversation_view(source_path);
         if let Some(scope) = cache_scope.as_deref() {
             CONVERSATION_CACHE.insert_scoped(scope, Some(source_id), source_path, v.clone());
         }
@@ -849,6 +853,62 @@ pub fn format_time_short(ms: i64) -> String {
         .unwrap_or_else(|| "?".to_string())
 }
 
+/// Compact relative age for badge strips: "3m", "2h", "5d". Sub-minute ages
+/// render as "now"; a future timestamp (clock skew) clamps to "now" too.
+#[must_use]
+pub fn format_age_compact(now_ms: i64, ts_ms: i64) -> String {
+    let age_secs = now_ms.saturating_sub(ts_ms) / 1000;
+    if age_secs < 60 {
+        "now".to_string()
+    } else if age_secs < 3600 {
+        format!("{}m", age_secs / 60)
+    } else if age_secs < 86_400 {
+        format!("{}h", age_secs / 3600)
+    } else {
+        format!("{}d", age_secs / 86_400)
+    }
+}
+
+// -------------------------------------------------------------------------
+// Agent Index Badges
+// -------------------------------------------------------------------------
+
+/// One agent's index activity for the header badge strip: how many
+/// conversations are indexed and when the newest one was last seen. A
+/// connector whose badge age keeps growing while the agent is in daily use
+/// has silently stopped finding new sessions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentIndexBadge {
+    /// Agent slug as recorded in the index (e.g. "claude-code", "codex").
+    pub agent: String,
+    /// Indexed conversation count for this agent.
+    pub conversations: i64,
+    /// Newest indexed activity (`ended_at` falling back to `started_at`),
+    /// in epoch milliseconds; `None` when nothing is indexed yet.
+    pub last_indexed_ms: Option<i64>,
+}
+
+/// Load per-agent badge rows, busiest agents first so truncation on narrow
+/// terminals drops the least informative entries.
+pub fn load_agent_index_badges(storage: &FrankenStorage) -> Result<Vec<AgentIndexBadge>> {
+    let rows = storage.raw().query_map_collect(
+        "SELECT a.slug, COUNT(c.id), MAX(COALESCE(c.ended_at, c.started_at))
+         FROM agents a
+         LEFT JOIN conversations c ON c.agent_id = a.id
+         GROUP BY a.slug
+         ORDER BY COUNT(c.id) DESC, a.slug",
+        frankensqlite::params![],
+        |row: &Row| {
+            Ok(AgentIndexBadge {
+                agent: row.get_typed(0)?,
+                conversations: row.get_typed(1)?,
+                last_indexed_ms: row.get_typed(2)?,
+            })
+        },
+    )?;
+    Ok(rows)
+}
+
 // =========================================================================
 // Explainability Cockpit — Information Architecture (1mfw3.3.1)
 // =========================================================================
@@ -10,6 +10,7 @@ pub mod data;
 pub mod ftui_adapter;
 pub mod shortcuts;
 pub mod style_system;
+pub mod term_caps;
 pub mod theme;
 pub mod time_parser;
 pub mod trace;
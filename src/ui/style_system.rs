@@ -344,6 +344,9 @@ pub struct StyleOptions {
     pub no_icons: bool,
     pub no_gradient: bool,
     pub a11y: bool,
+    /// Render with ASCII-safe borders and no decorative Unicode glyphs
+    /// (`CASS_ASCII`, `--ascii`, or a detected legacy Windows console).
+    pub ascii: bool,
 }
 
 impl Default for StyleOptions {
@@ -356,6 +359,7 @@ impl Default for StyleOptions {
             no_icons: false,
             no_gradient: false,
             a11y: false,
+            ascii: false,
         }
     }
 }
@@ -372,6 +376,7 @@ struct EnvValues<'a> {
     cass_a11y: Option<&'a str>,
     cass_theme: Option<&'a str>,
     cass_color_profile: Option<&'a str>,
+    cass_ascii: Option<&'a str>,
 }
 
 impl StyleOptions {
@@ -386,6 +391,7 @@ impl StyleOptions {
         let cass_a11y = dotenvy::var("CASS_A11Y").ok();
         let cass_theme = dotenvy::var("CASS_THEME").ok();
         let cass_color_profile = dotenvy::var("CASS_COLOR_PROFILE").ok();
+        let cass_ascii = dotenvy::var("CASS_ASCII").ok();
 
         let mut options = Self::from_env_values(EnvValues {
             no_color: no_color.as_deref(),
@@ -398,6 +404,7 @@ impl StyleOptions {
             cass_a11y: cass_a11y.as_deref(),
             cass_theme: cass_theme.as_deref(),
             cass_color_profile: cass_color_profile.as_deref(),
+            cass_ascii: cass_ascii.as_deref(),
         });
 
         // Prefer runtime terminal capability detection for interactive TUI.
@@ -414,6 +421,21 @@ impl StyleOptions {
             };
         }
 
+        // Windows console quirks: legacy conhost renders box-drawing as
+        // mojibake and ignores 256-color/truecolor sequences, so fall back
+        // to ASCII-safe rendering and a 16-color theme there. An explicit
+        // CASS_COLOR_PROFILE still wins, matching the precedence table.
+        let quirks = super::term_caps::detect();
+        if quirks.ascii_only {
+            options.ascii = true;
+        }
+        if quirks.cap_colors_ansi16 && !options.no_color && cass_color_profile.is_none() {
+            options.color_profile = match options.color_profile {
+                ColorProfile::TrueColor | ColorProfile::Ansi256 => ColorProfile::Ansi16,
+                other => other,
+            };
+        }
+
         options
     }
 
@@ -461,6 +483,7 @@ impl StyleOptions {
         let a11y = env_truthy(values.cass_a11y);
         let no_icons = env_truthy(values.cass_no_icons);
         let no_gradient = env_truthy(values.cass_no_gradient) || no_color_enabled || a11y;
+        let ascii = env_truthy(values.cass_ascii);
 
         let dark_mode = match preset {
             UiThemePreset::Daylight | UiThemePreset::SolarizedLight => false,
@@ -476,6 +499,7 @@ impl StyleOptions {
             no_icons,
             no_gradient,
             a11y,
+            ascii,
         }
     }
 
@@ -495,6 +519,8 @@ pub enum BorderTier {
     Rounded,
     /// Plain box-drawing (`┌─┐`).
     Square,
+    /// ASCII-safe: no box-drawing glyphs (legacy conhost, `--ascii`).
+    Ascii,
     /// No borders at all.
     None,
 }
@@ -515,6 +541,11 @@ pub enum BorderTier {
 /// | SimpleBorders     | any          | _             | Square        | true         | true          |
 /// | NoStyling         | any          | _             | Square        | true         | false         |
 /// | EssentialOnly+    | any          | _             | None          | false        | false         |
+///
+/// When `StyleOptions::ascii` is set (CASS_ASCII / `--ascii` / legacy
+/// Windows console), any Rounded/Square outcome collapses to Ascii and
+/// icons are suppressed — box-drawing and nerd-font glyphs are exactly
+/// what those consoles cannot display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DecorativePolicy {
     /// Which border rendering tier to use.
@@ -557,8 +588,13 @@ impl DecorativePolicy {
         } else {
             BorderTier::Rounded
         };
+        let border_tier = if options.ascii && border_tier != BorderTier::None {
+            BorderTier::Ascii
+        } else {
+            border_tier
+        };
 
-        let show_icons = degradation.render_decorative() && !options.no_icons;
+        let show_icons = degradation.render_decorative() && !options.no_icons && !options.ascii;
         let use_styling = degradation.apply_styling() && !options.no_color;
         let use_gradients = options.gradients_enabled() && degradation.apply_styling();
 
@@ -595,6 +631,8 @@ pub struct CapabilityMatrixInputs<'a> {
     pub cass_no_gradient: bool,
     /// Whether `CASS_A11Y` is set/truthy.
     pub cass_a11y: bool,
+    /// Whether `CASS_ASCII` is set/truthy.
+    pub cass_ascii: bool,
     /// Optional explicit theme preset override.
     pub cass_theme: Option<&'a str>,
     /// Optional explicit color profile override.
@@ -665,6 +703,7 @@ fn border_tier_name(tier: BorderTier) -> &'static str {
     match tier {
         BorderTier::Rounded => "rounded",
         BorderTier::Square => "square",
+        BorderTier::Ascii => "ascii",
         BorderTier::None => "none",
     }
 }
@@ -715,6 +754,7 @@ pub fn style_policy_diagnostic(
         cass_a11y: env_flag(inputs.cass_a11y),
         cass_theme: inputs.cass_theme,
         cass_color_profile: inputs.cass_color_profile,
+        cass_ascii: env_flag(inputs.cass_ascii),
     };
 
     let mut options = StyleOptions::from_env_values(env_values);
@@ -1044,16 +1084,50 @@ impl StyleContext {
         }
     }
 
-    /// Return a syntax highlight theme matching the current UI theme brightness.
+    /// Return a syntax highlight theme for fenced code blocks.
+    ///
+    /// Defaults to tracking the UI theme brightness (which itself tracks the
+    /// terminal background); `CASS_SYNTAX_THEME=dark|light` pins one
+    /// explicitly for terminals whose background the theme preset guesses
+    /// wrong. Unknown values fall back to auto.
     pub fn syntax_highlight_theme(&self) -> HighlightTheme {
-        if self.options.dark_mode {
-            HighlightTheme::dark()
-        } else {
-            HighlightTheme::light()
+        let pref = dotenvy::var("CASS_SYNTAX_THEME")
+            .ok()
+            .as_deref()
+            .and_then(parse_syntax_theme_pref)
+            .unwrap_or(SyntaxThemePref::Auto);
+        match pref {
+            SyntaxThemePref::Dark => HighlightTheme::dark(),
+            SyntaxThemePref::Light => HighlightTheme::light(),
+            SyntaxThemePref::Auto => {
+                if self.options.dark_mode {
+                    HighlightTheme::dark()
+                } else {
+                    HighlightTheme::light()
+                }
+            }
         }
     }
 }
 
+/// Explicit syntax-highlight brightness override (`CASS_SYNTAX_THEME`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxThemePref {
+    /// Follow the UI theme brightness (default).
+    Auto,
+    Dark,
+    Light,
+}
+
+fn parse_syntax_theme_pref(value: &str) -> Option<SyntaxThemePref> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "auto" => Some(SyntaxThemePref::Auto),
+        "dark" => Some(SyntaxThemePref::Dark),
+        "light" => Some(SyntaxThemePref::Light),
+        _ => None,
+    }
+}
+
 fn parse_color_profile(value: &str) -> Option<ColorProfile> {
     match value.trim().to_ascii_lowercase().as_str() {
         "mono" | "none" => Some(ColorProfile::Mono),
@@ -2356,6 +2430,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: false,
+            ascii: false,
         });
         // Should not panic when resolving any token.
         let _ = ctx.style(STYLE_TEXT_PRIMARY);
@@ -2387,6 +2462,7 @@ mod tests {
                     no_icons: false,
                     no_gradient: profile == ColorProfile::Mono,
                     a11y: false,
+                    ascii: false,
                 });
                 // Smoke test: resolve every token without panicking.
                 for &(_, token) in ALL_STYLE_TOKENS {
@@ -2406,6 +2482,7 @@ mod tests {
             no_icons: false,
             no_gradient: false,
             a11y: false,
+            ascii: false,
         });
 
         assert_eq!(context.resolved.background, Color::rgb(26, 27, 38));
@@ -2424,6 +2501,7 @@ mod tests {
             no_icons: false,
             no_gradient: false,
             a11y: false,
+            ascii: false,
         });
 
         for key in [
@@ -2520,6 +2598,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: false,
+            ascii: false,
         });
 
         for (label, token) in [
@@ -2546,6 +2625,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: false,
+            ascii: false,
         });
 
         assert!(matches!(context.resolved.primary, Color::Mono(_)));
@@ -2563,6 +2643,7 @@ mod tests {
             no_icons: true,
             no_gradient: true,
             a11y: true,
+            ascii: false,
         });
 
         assert_eq!(markers.user, "[user]");
@@ -2586,6 +2667,7 @@ mod tests {
                 no_icons: false,
                 no_gradient: false,
                 a11y: false,
+                ascii: false,
             });
 
             let root = context.style(STYLE_APP_ROOT);
@@ -2610,6 +2692,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: true,
+            ascii: false,
         });
 
         let selected = context.style(STYLE_RESULT_ROW_SELECTED);
@@ -2697,6 +2780,7 @@ mod tests {
                 no_icons: false,
                 no_gradient: false,
                 a11y: false,
+                ascii: false,
             },
             &config,
         );
@@ -2831,6 +2915,7 @@ mod tests {
                     no_icons: false,
                     no_gradient: false,
                     a11y: false,
+                    ascii: false,
                 });
 
                 for &token in CRITICAL_FG_TOKENS {
@@ -2866,6 +2951,7 @@ mod tests {
                     no_icons: false,
                     no_gradient: false,
                     a11y: false,
+                    ascii: false,
                 });
 
                 for &token in CRITICAL_BG_TOKENS {
@@ -2897,6 +2983,7 @@ mod tests {
                 no_icons: false,
                 no_gradient: true,
                 a11y: true,
+                ascii: false,
             });
 
             let user = ctx.style(STYLE_ROLE_USER);
@@ -3029,6 +3116,32 @@ mod tests {
         assert!(!policy.render_content, "Skeleton should not render content");
     }
 
+    #[test]
+    fn deco_ascii_mode_collapses_borders_and_icons() {
+        use crate::ui::app::LayoutBreakpoint as LB;
+        use ftui::render::budget::DegradationLevel as DL;
+
+        let options = StyleOptions {
+            ascii: true,
+            ..StyleOptions::default()
+        };
+        let policy = DecorativePolicy::resolve(options, DL::Full, LB::Wide, true);
+        assert_eq!(
+            policy.border_tier,
+            BorderTier::Ascii,
+            "ascii mode overrides fancy_borders"
+        );
+        assert!(!policy.show_icons, "decorative glyphs are mojibake bait");
+        assert!(policy.use_styling, "ascii mode keeps (16-)color styling");
+
+        let policy = DecorativePolicy::resolve(options, DL::EssentialOnly, LB::Wide, true);
+        assert_eq!(
+            policy.border_tier,
+            BorderTier::None,
+            "degradation past borders still wins over ascii"
+        );
+    }
+
     #[test]
     fn deco_no_color_drops_styling() {
         use crate::ui::app::LayoutBreakpoint as LB;
@@ -3318,6 +3431,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn capability_matrix_windows_console_falls_back_to_ascii_and_ansi16() {
+        use crate::ui::app::LayoutBreakpoint as LB;
+        use crate::ui::term_caps::{self, ConsoleClass, TermEnvSnapshot};
+        use ftui::core::terminal_capabilities::TerminalProfile;
+        use ftui::render::budget::DegradationLevel as DL;
+
+        // Simulated bare conhost: Windows, no WT_SESSION / TERM / ConEmu
+        // markers. The quirks layer demands ASCII + 16 colors.
+        let quirks = term_caps::resolve(TermEnvSnapshot {
+            os_windows: true,
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(quirks.console, ConsoleClass::LegacyConsole);
+
+        let caps = TerminalCapabilities::from_profile(TerminalProfile::WindowsConsole);
+        let diag = style_policy_diagnostic(
+            caps,
+            CapabilityMatrixInputs {
+                cass_ascii: quirks.ascii_only,
+                ..CapabilityMatrixInputs::default()
+            },
+            DL::Full,
+            LB::Wide,
+            true,
+        );
+        assert_eq!(
+            diag.policy_border_tier, "ascii",
+            "conhost must not be handed box-drawing borders"
+        );
+        assert!(!diag.policy_show_icons, "conhost fonts lack icon glyphs");
+        assert!(
+            !caps.true_color && !caps.colors_256,
+            "WindowsConsole profile should cap at 16 colors"
+        );
+        assert_eq!(diag.resolved_color_profile, "ansi16");
+
+        // Windows Terminal on the same OS keeps the full pipeline.
+        let wt = term_caps::resolve(TermEnvSnapshot {
+            os_windows: true,
+            wt_session: Some("guid"),
+            ..TermEnvSnapshot::default()
+        });
+        assert!(!wt.ascii_only && !wt.cap_colors_ansi16);
+        let diag = style_policy_diagnostic(
+            TerminalCapabilities::from_profile(TerminalProfile::Kitty),
+            CapabilityMatrixInputs {
+                cass_ascii: wt.ascii_only,
+                ..CapabilityMatrixInputs::default()
+            },
+            DL::Full,
+            LB::Wide,
+            true,
+        );
+        assert_eq!(diag.policy_border_tier, "rounded");
+        assert!(diag.policy_show_icons);
+    }
+
     #[test]
     fn capability_matrix_no_color_precedence_matches_policy_contract() {
         use crate::ui::app::LayoutBreakpoint as LB;
@@ -3573,6 +3744,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: false,
+            ascii: false,
         });
         let no_color_style = no_color_ctx.agent_accent_style("codex");
         assert!(
@@ -3588,6 +3760,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: true,
+            ascii: false,
         });
         let a11y_style = a11y_ctx.agent_accent_style("codex");
         assert!(
@@ -3630,6 +3803,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: false,
+            ascii: false,
         });
         assert_eq!(
             no_color_ctx.result_row_style_for_agent(base, "codex"),
@@ -3645,6 +3819,7 @@ mod tests {
             no_icons: false,
             no_gradient: true,
             a11y: true,
+            ascii: false,
         });
         assert_eq!(
             a11y_ctx.result_row_style_for_agent(base, "codex"),
@@ -3804,6 +3979,7 @@ mod tests {
             no_icons: false,
             no_gradient: false,
             a11y: false,
+            ascii: false,
         })
     }
 
@@ -4154,6 +4330,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn syntax_theme_pref_parses_known_values_only() {
+        assert_eq!(
+            parse_syntax_theme_pref(" Dark "),
+            Some(SyntaxThemePref::Dark)
+        );
+        assert_eq!(
+            parse_syntax_theme_pref("light"),
+            Some(SyntaxThemePref::Light)
+        );
+        assert_eq!(parse_syntax_theme_pref("auto"), Some(SyntaxThemePref::Auto));
+        assert_eq!(parse_syntax_theme_pref("solarized"), None);
+        assert_eq!(parse_syntax_theme_pref(""), None);
+    }
+
     #[test]
     fn markdown_theme_differs_across_presets() {
         let mut themes = std::collections::HashSet::new();
@@ -0,0 +1,228 @@
+//! Windows console capability detection for TUI rendering.
+//!
+//! Legacy conhost (the console that hosts `cmd.exe` and older PowerShell
+//! windows) ships raster fonts that render box-drawing glyphs as mojibake
+//! and only honors 16 ANSI colors, while Windows Terminal, ConEmu, and
+//! mintty handle the full Unicode/truecolor pipeline fine. This module
+//! classifies the hosting console from environment markers so the style
+//! system can fall back to ASCII-safe rendering and a 16-color theme
+//! without penalizing modern terminals.
+//!
+//! Detection is split into a pure [`resolve`] over an [`TermEnvSnapshot`]
+//! (deterministic, testable with simulated Windows profiles — the same
+//! env-snapshot idiom as `StyleOptions::from_env_values`) and a thin
+//! [`detect`] that samples the real process environment.
+
+/// Hosting console classification, from environment markers alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleClass {
+    /// Windows Terminal (sets `WT_SESSION` for every pane).
+    WindowsTerminal,
+    /// ConEmu / Cmder with ANSI processing enabled (`ConEmuANSI=ON`).
+    ConEmu,
+    /// A terminal that exports `TERM` / `TERM_PROGRAM` (mintty, VS Code,
+    /// ssh from a Unix box) and therefore negotiates capabilities itself.
+    TermAware,
+    /// Bare conhost: none of the modern markers present on Windows.
+    LegacyConsole,
+    /// Not Windows; no console quirks apply.
+    NonWindows,
+}
+
+/// Environment snapshot consumed by [`resolve`].
+///
+/// Mirrors `EnvValues` in the style system: tests construct these directly
+/// to simulate conhost / Windows Terminal / ConEmu without touching the
+/// process environment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TermEnvSnapshot<'a> {
+    /// Whether the binary is running on Windows (`cfg!(windows)` at runtime).
+    pub os_windows: bool,
+    /// `WT_SESSION` — set by Windows Terminal for every pane.
+    pub wt_session: Option<&'a str>,
+    /// `TERM_PROGRAM` — set by VS Code's integrated terminal, mintty, etc.
+    pub term_program: Option<&'a str>,
+    /// `TERM` — legacy conhost never sets this; Unix-style terminals do.
+    pub term: Option<&'a str>,
+    /// `ConEmuANSI` — `ON` when ConEmu's ANSI emulation is active.
+    pub conemu_ansi: Option<&'a str>,
+    /// `CASS_ASCII` — explicit user opt-in to ASCII-safe rendering.
+    pub cass_ascii: Option<&'a str>,
+}
+
+/// Resolved console quirks consumed by `StyleOptions::from_env`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleQuirks {
+    /// Which console class the markers point at.
+    pub console: ConsoleClass,
+    /// Render without box-drawing / decorative Unicode glyphs.
+    pub ascii_only: bool,
+    /// Cap the color profile at 16 ANSI colors (conhost palettes ignore
+    /// 256-color and truecolor sequences or quantize them badly).
+    pub cap_colors_ansi16: bool,
+}
+
+/// Classify the hosting console from an environment snapshot.
+///
+/// `CASS_ASCII` forces ASCII-safe rendering on every platform (useful for
+/// screen readers, plain log capture, and terminals with broken fonts) but
+/// never downgrades colors on its own; the 16-color cap is reserved for
+/// the legacy console, whose palette genuinely cannot do better.
+#[must_use]
+pub fn resolve(env: TermEnvSnapshot<'_>) -> ConsoleQuirks {
+    let forced_ascii = env_truthy(env.cass_ascii);
+
+    let console = if !env.os_windows {
+        ConsoleClass::NonWindows
+    } else if env.wt_session.is_some_and(|v| !v.trim().is_empty()) {
+        ConsoleClass::WindowsTerminal
+    } else if env
+        .conemu_ansi
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("on"))
+    {
+        ConsoleClass::ConEmu
+    } else if env.term_program.is_some_and(|v| !v.trim().is_empty())
+        || env.term.is_some_and(|v| !v.trim().is_empty())
+    {
+        ConsoleClass::TermAware
+    } else {
+        ConsoleClass::LegacyConsole
+    };
+
+    let legacy = console == ConsoleClass::LegacyConsole;
+    ConsoleQuirks {
+        console,
+        ascii_only: forced_ascii || legacy,
+        cap_colors_ansi16: legacy,
+    }
+}
+
+/// Sample the real process environment and classify the hosting console.
+#[must_use]
+pub fn detect() -> ConsoleQuirks {
+    let wt_session = dotenvy::var("WT_SESSION").ok();
+    let term_program = dotenvy::var("TERM_PROGRAM").ok();
+    let term = dotenvy::var("TERM").ok();
+    let conemu_ansi = dotenvy::var("ConEmuANSI").ok();
+    let cass_ascii = dotenvy::var("CASS_ASCII").ok();
+    resolve(TermEnvSnapshot {
+        os_windows: cfg!(windows),
+        wt_session: wt_session.as_deref(),
+        term_program: term_program.as_deref(),
+        term: term.as_deref(),
+        conemu_ansi: conemu_ansi.as_deref(),
+        cass_ascii: cass_ascii.as_deref(),
+    })
+}
+
+fn env_truthy(value: Option<&str>) -> bool {
+    match value {
+        Some(raw) => {
+            let normalized = raw.trim().to_ascii_lowercase();
+            if normalized.is_empty() {
+                return false;
+            }
+            !(normalized == "0"
+                || normalized == "false"
+                || normalized == "off"
+                || normalized == "no")
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_conhost_gets_ascii_and_a_16_color_cap() {
+        let quirks = resolve(TermEnvSnapshot {
+            os_windows: true,
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(quirks.console, ConsoleClass::LegacyConsole);
+        assert!(quirks.ascii_only);
+        assert!(quirks.cap_colors_ansi16);
+    }
+
+    #[test]
+    fn windows_terminal_keeps_full_rendering() {
+        let quirks = resolve(TermEnvSnapshot {
+            os_windows: true,
+            wt_session: Some("8f3b2c1a-guid"),
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(quirks.console, ConsoleClass::WindowsTerminal);
+        assert!(!quirks.ascii_only);
+        assert!(!quirks.cap_colors_ansi16);
+    }
+
+    #[test]
+    fn conemu_with_ansi_on_is_modern_but_off_falls_back() {
+        let on = resolve(TermEnvSnapshot {
+            os_windows: true,
+            conemu_ansi: Some("ON"),
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(on.console, ConsoleClass::ConEmu);
+        assert!(!on.ascii_only);
+
+        let off = resolve(TermEnvSnapshot {
+            os_windows: true,
+            conemu_ansi: Some("OFF"),
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(off.console, ConsoleClass::LegacyConsole);
+        assert!(off.ascii_only);
+    }
+
+    #[test]
+    fn term_aware_shells_on_windows_are_trusted() {
+        for snapshot in [
+            TermEnvSnapshot {
+                os_windows: true,
+                term: Some("xterm-256color"),
+                ..TermEnvSnapshot::default()
+            },
+            TermEnvSnapshot {
+                os_windows: true,
+                term_program: Some("vscode"),
+                ..TermEnvSnapshot::default()
+            },
+        ] {
+            let quirks = resolve(snapshot);
+            assert_eq!(quirks.console, ConsoleClass::TermAware);
+            assert!(!quirks.ascii_only);
+            assert!(!quirks.cap_colors_ansi16);
+        }
+    }
+
+    #[test]
+    fn cass_ascii_forces_ascii_without_touching_colors() {
+        let quirks = resolve(TermEnvSnapshot {
+            os_windows: false,
+            term: Some("xterm-256color"),
+            cass_ascii: Some("1"),
+            ..TermEnvSnapshot::default()
+        });
+        assert_eq!(quirks.console, ConsoleClass::NonWindows);
+        assert!(quirks.ascii_only);
+        assert!(!quirks.cap_colors_ansi16);
+
+        let disabled = resolve(TermEnvSnapshot {
+            os_windows: false,
+            cass_ascii: Some("0"),
+            ..TermEnvSnapshot::default()
+        });
+        assert!(!disabled.ascii_only);
+    }
+
+    #[test]
+    fn non_windows_has_no_quirks() {
+        let quirks = resolve(TermEnvSnapshot::default());
+        assert_eq!(quirks.console, ConsoleClass::NonWindows);
+        assert!(!quirks.ascii_only);
+        assert!(!quirks.cap_colors_ansi16);
+    }
+}
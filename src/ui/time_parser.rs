@@ -1,14 +1,34 @@
-use chrono::{Duration, Local, LocalResult, NaiveDate, TimeZone, Utc};
+use chrono::{Datelike, Duration, Local, LocalResult, NaiveDate, TimeZone, Utc, Weekday};
+
+/// One-line summary of the accepted input forms, shared by the CLI error
+/// hints so every time-accepting flag describes the same grammar.
+pub const SUPPORTED_FORMS: &str = "ISO dates (2024-11-03, 2024-11-03T14:30:00Z), relative \
+     offsets (90d, -24h, 2 weeks ago), keywords (now, today, yesterday), weekdays \
+     (last monday), and month-day dates (nov 3, nov 3 2024)";
 
 /// Parses human-readable time input into a UTC timestamp (milliseconds).
 ///
 /// Supported formats:
 /// - Relative: "-7d", "-24h", "-30m", "-1w"
 /// - Keywords: "now", "today", "yesterday"
+/// - Weekdays: "monday", "last monday" (most recent occurrence before today)
+/// - Month-day: "nov 3", "november 3 2024", "3 nov" (year-less forms resolve
+///   to the most recent occurrence not in the future)
 /// - ISO dates: "2024-11-25", "2024-11-25T14:30:00Z"
 /// - Date formats: "YYYY-MM-DD", "YYYY/MM/DD", "MM/DD/YYYY", "MM-DD-YYYY"
 /// - Unix timestamp: seconds (if < 10^11) or milliseconds
+///
+/// Date-only forms are anchored at local midnight; see [`parse_time_input_tz`]
+/// for the UTC-anchored variant behind the `--utc` flags.
 pub fn parse_time_input(input: &str) -> Option<i64> {
+    parse_time_input_tz(input, false)
+}
+
+/// Like [`parse_time_input`], but when `utc` is set, date-only and
+/// midnight-based forms ("today", "last monday", "2024-11-03") are anchored
+/// at UTC midnight instead of local midnight. Inputs carrying an explicit
+/// zone (RFC3339) and pure offsets ("7d") are unaffected.
+pub fn parse_time_input_tz(input: &str, utc: bool) -> Option<i64> {
     let input = input.trim().to_lowercase();
     if input.is_empty() {
         return None;
@@ -71,35 +91,60 @@ pub fn parse_time_input(input: &str) -> Option<i64> {
     match input.as_str() {
         "now" => return Some(now_ms),
         "today" => {
-            let today = Local::now().date_naive();
-            return local_midnight_to_utc(today);
+            return midnight_ms(today_naive(utc), utc);
         }
         "yesterday" => {
-            let yesterday = Local::now()
-                .date_naive()
-                .checked_sub_signed(Duration::try_days(1)?)?;
-            return local_midnight_to_utc(yesterday);
+            let yesterday = today_naive(utc).checked_sub_signed(Duration::try_days(1)?)?;
+            return midnight_ms(yesterday, utc);
         }
         _ => {}
     }
 
+    // Natural-language phrases: weekdays and month-day dates.
+    {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+        // "last monday" or a bare weekday name: the most recent such day
+        // strictly before today. These flags look backward, so "monday" on
+        // a Monday means a week ago, not this morning.
+        let weekday_part = match parts.as_slice() {
+            [day] => Some(*day),
+            ["last", day] => Some(*day),
+            _ => None,
+        };
+        if let Some(day) = weekday_part
+            && let Some(weekday) = weekday_from_name(day)
+        {
+            let today = today_naive(utc);
+            let mut days_back =
+                (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+            if days_back == 0 {
+                days_back = 7;
+            }
+            let date = today.checked_sub_signed(Duration::try_days(i64::from(days_back))?)?;
+            return midnight_ms(date, utc);
+        }
+        if let Some(ts) = parse_month_day(&parts, utc) {
+            return Some(ts);
+        }
+    }
+
     // ISO date formats (RFC3339)
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&input) {
         return Some(dt.timestamp_millis());
     }
 
-    // YYYY-MM-DD or YYYY/MM/DD (Local midnight)
+    // YYYY-MM-DD or YYYY/MM/DD (midnight in the chosen zone)
     if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d")
         .or_else(|_| NaiveDate::parse_from_str(&input, "%Y/%m/%d"))
     {
-        return local_midnight_to_utc(date);
+        return midnight_ms(date, utc);
     }
 
     // US Formats: MM/DD/YYYY or MM-DD-YYYY
     if let Ok(date) = NaiveDate::parse_from_str(&input, "%m/%d/%Y")
         .or_else(|_| NaiveDate::parse_from_str(&input, "%m-%d-%Y"))
     {
-        return local_midnight_to_utc(date);
+        return midnight_ms(date, utc);
     }
     // Numeric fallback (ms or seconds)
     if let Ok(n) = input.parse::<i64>() {
@@ -113,8 +158,19 @@ pub fn parse_time_input(input: &str) -> Option<i64> {
     None
 }
 
-fn local_midnight_to_utc(date: NaiveDate) -> Option<i64> {
+fn today_naive(utc: bool) -> NaiveDate {
+    if utc {
+        Utc::now().date_naive()
+    } else {
+        Local::now().date_naive()
+    }
+}
+
+fn midnight_ms(date: NaiveDate, utc: bool) -> Option<i64> {
     let dt = date.and_hms_opt(0, 0, 0)?;
+    if utc {
+        return Some(Utc.from_utc_datetime(&dt).timestamp_millis());
+    }
     let local = match Local.from_local_datetime(&dt) {
         LocalResult::Single(value) => value,
         LocalResult::Ambiguous(earliest, _) => earliest,
@@ -126,6 +182,70 @@ fn local_midnight_to_utc(date: NaiveDate) -> Option<i64> {
     Some(local.with_timezone(&Utc).timestamp_millis())
 }
 
+/// Month-day phrases: "nov 3", "november 3 2024", "3 nov", "nov 3, 2024".
+/// Without a year, resolves to the most recent occurrence not in the future.
+fn parse_month_day(parts: &[&str], utc: bool) -> Option<i64> {
+    let cleaned: Vec<&str> = parts.iter().map(|p| p.trim_end_matches(',')).collect();
+    let (month, day, year) = match cleaned.as_slice() {
+        [a, b] => match month_from_name(a) {
+            Some(month) => (month, b.parse::<u32>().ok()?, None),
+            None => (month_from_name(b)?, a.parse::<u32>().ok()?, None),
+        },
+        [a, b, c] => {
+            let year = Some(c.parse::<i32>().ok()?);
+            match month_from_name(a) {
+                Some(month) => (month, b.parse::<u32>().ok()?, year),
+                None => (month_from_name(b)?, a.parse::<u32>().ok()?, year),
+            }
+        }
+        _ => return None,
+    };
+    let date = match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day)?,
+        None => {
+            let today = today_naive(utc);
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+            if this_year > today {
+                NaiveDate::from_ymd_opt(today.year() - 1, month, day)?
+            } else {
+                this_year
+            }
+        }
+    };
+    midnight_ms(date, utc)
+}
+
+fn weekday_from_name(name: &str) -> Option<Weekday> {
+    match name {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    match name {
+        "jan" | "january" => Some(1),
+        "feb" | "february" => Some(2),
+        "mar" | "march" => Some(3),
+        "apr" | "april" => Some(4),
+        "may" => Some(5),
+        "jun" | "june" => Some(6),
+        "jul" | "july" => Some(7),
+        "aug" | "august" => Some(8),
+        "sep" | "sept" | "september" => Some(9),
+        "oct" | "october" => Some(10),
+        "nov" | "november" => Some(11),
+        "dec" | "december" => Some(12),
+        _ => None,
+    }
+}
+
 fn relative_duration(unit: &str, val: i64) -> Option<Duration> {
     match unit {
         "d" | "day" | "days" => Duration::try_days(val),
@@ -226,6 +346,58 @@ mod tests {
         assert!(parse_time_input("01-01-2023").is_some());
     }
 
+    #[test]
+    fn test_weekday_phrases() {
+        let last_monday = parse_time_input("last monday").unwrap();
+        assert_eq!(parse_time_input("monday"), Some(last_monday));
+        assert_eq!(parse_time_input("mon"), Some(last_monday));
+
+        let now = Utc::now().timestamp_millis();
+        let age = now - last_monday;
+        assert!(age > 0, "a weekday must resolve strictly into the past");
+        assert!(
+            age <= 8 * 86_400_000,
+            "a weekday must be within the last 8 days, got {age} ms"
+        );
+        assert!(parse_time_input("last fortnight").is_none());
+    }
+
+    #[test]
+    fn test_month_day_phrases() {
+        let now = Utc::now().timestamp_millis();
+        let ts = parse_time_input("nov 3").unwrap();
+        assert!(
+            ts <= now,
+            "a year-less month-day must not land in the future"
+        );
+        assert!(now - ts <= 367 * 86_400_000, "must be within the last year");
+        assert_eq!(parse_time_input("november 3"), Some(ts));
+        assert_eq!(parse_time_input("3 nov"), Some(ts));
+
+        // Explicit year pins the date; UTC anchoring makes it exact.
+        let explicit = parse_time_input_tz("nov 3 2024", true).unwrap();
+        assert_eq!(explicit, 1_730_592_000_000);
+        assert_eq!(parse_time_input_tz("nov 3, 2024", true), Some(explicit));
+        assert_eq!(parse_time_input_tz("3 november 2024", true), Some(explicit));
+    }
+
+    #[test]
+    fn test_utc_flag_anchors_midnight() {
+        assert_eq!(
+            parse_time_input_tz("2024-11-03", true),
+            Some(1_730_592_000_000)
+        );
+        assert_eq!(parse_time_input_tz("today", true), {
+            let dt = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
+            Some(Utc.from_utc_datetime(&dt).timestamp_millis())
+        });
+        // Explicit zones are unaffected by the flag.
+        assert_eq!(
+            parse_time_input_tz("2024-11-03T12:00:00Z", true),
+            parse_time_input("2024-11-03T12:00:00Z")
+        );
+    }
+
     #[test]
     fn test_numeric() {
         let _sec = 1700000000;
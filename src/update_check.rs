@@ -180,6 +180,30 @@ impl UpdateInfo {
 struct GitHubRelease {
     tag_name: String,
     html_url: String,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// Release channel for self-update. Stable follows `/releases/latest`
+/// (GitHub never marks prereleases as latest); nightly scans the release
+/// list for the newest prerelease build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum UpdateChannel {
+    /// Latest stable GitHub release
+    #[default]
+    Stable,
+    /// Newest prerelease build
+    Nightly,
+}
+
+impl UpdateChannel {
+    /// Stable label for logs and structured output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Nightly => "nightly",
+        }
+    }
 }
 
 /// Check for updates asynchronously
@@ -231,6 +255,43 @@ pub async fn force_check(current_version: &str) -> Option<UpdateInfo> {
     check_for_updates_async_impl(current_version, true).await
 }
 
+/// Force a check on a specific release channel (for `cass self-update`).
+///
+/// Stable matches [`force_check`]. Nightly scans the release list for the
+/// newest prerelease; prerelease versions of the current base sort below it
+/// under semver, so a nightly is reported as an update whenever its version
+/// differs from the running one.
+pub async fn force_check_channel(
+    current_version: &str,
+    channel: UpdateChannel,
+) -> Option<UpdateInfo> {
+    if updates_disabled() {
+        return None;
+    }
+    if channel == UpdateChannel::Stable {
+        return check_for_updates_async_impl(current_version, true).await;
+    }
+
+    let mut state = UpdateState::load_async().await;
+    let release =
+        match asupersync::runtime::spawn_blocking(fetch_latest_nightly_release_blocking).await {
+            Ok(r) => r,
+            Err(e) => {
+                debug!("update check: nightly fetch failed (offline?): {e}");
+                return None;
+            }
+        };
+
+    let mut info = build_update_info(current_version, release, &state)?;
+    info.is_newer = info.latest_version != current_version;
+
+    state.mark_checked();
+    if let Err(e) = state.save_async().await {
+        warn!("update check: failed to save state: {e}");
+    }
+    Some(info)
+}
+
 /// Skip the specified version
 pub fn skip_version(version: &str) -> Result<()> {
     let mut state = UpdateState::load();
@@ -532,6 +593,62 @@ pub fn run_self_update(version: &str) -> ! {
     }
 }
 
+/// Run the self-update installer as a child process and wait for it.
+///
+/// Same checksum-verified scripts as [`run_self_update`], but spawns instead
+/// of exec'ing so the caller can run post-install steps (e.g. prompting to
+/// apply database migrations with the freshly installed binary).
+pub fn run_self_update_blocking(version: &str) -> std::io::Result<std::process::ExitStatus> {
+    if !is_valid_update_tag(version) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid version string: {version}"),
+        ));
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        let install_url = release_asset_url(version, UNIX_INSTALL_ASSET);
+        let checksums_url = release_asset_url(version, CHECKSUMS_ASSET);
+        let checksums_alt_url = release_asset_url(version, CHECKSUMS_ASSET_ALT);
+        let install_checksum_url = release_asset_url(version, UNIX_INSTALL_CHECKSUM_ASSET);
+        std::process::Command::new("bash")
+            .args([
+                "-c",
+                unix_self_update_script(),
+                "cass-updater",
+                &install_url,
+                &checksums_url,
+                version,
+                &checksums_alt_url,
+                &install_checksum_url,
+            ])
+            .status()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let install_url = release_asset_url(version, WINDOWS_INSTALL_ASSET);
+        let checksums_url = release_asset_url(version, CHECKSUMS_ASSET);
+        let checksums_alt_url = release_asset_url(version, CHECKSUMS_ASSET_ALT);
+        let install_checksum_url = release_asset_url(version, WINDOWS_INSTALL_CHECKSUM_ASSET);
+        std::process::Command::new("powershell")
+            .args([
+                "-ExecutionPolicy",
+                "Bypass",
+                "-NoProfile",
+                "-Command",
+                windows_self_update_script(),
+                &install_url,
+                &checksums_url,
+                version,
+                &checksums_alt_url,
+                &install_checksum_url,
+            ])
+            .status()
+    }
+}
+
 /// Get the base URL for release API. Overridable for testing via the
 /// `CASS_UPDATE_API_BASE_URL` env var, but the override is validated
 /// against an allow-list of schemes + hosts so a malicious `.env` or
@@ -837,7 +954,9 @@ fn build_update_info(
     release: GitHubRelease,
     state: &UpdateState,
 ) -> Option<UpdateInfo> {
-    let GitHubRelease { tag_name, html_url } = release;
+    let GitHubRelease {
+        tag_name, html_url, ..
+    } = release;
     let (latest_version, latest) = match parse_update_tag(&tag_name) {
         Some((version, parsed)) => (version.to_string(), parsed),
         None => {
@@ -898,6 +1017,51 @@ fn fetch_latest_release_blocking() -> Result<GitHubRelease> {
         .context("parsing release JSON")
 }
 
+/// Fetch the newest prerelease build from the release list. Falls back to the
+/// newest valid-tagged release when no prerelease is published (a nightly
+/// channel that has caught up with stable is still a valid answer).
+fn fetch_latest_nightly_release_blocking() -> Result<GitHubRelease> {
+    let url = format!("{}/releases?per_page=20", release_api_base_url());
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("cass/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .build()
+        .context("building update-check HTTP client")?;
+
+    let response = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, "application/vnd.github.v3+json")
+        .send()
+        .with_context(|| format!("fetching release list from {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("GitHub API returned {}", status.as_u16());
+    }
+
+    let releases = response
+        .json::<Vec<GitHubRelease>>()
+        .context("parsing release list JSON")?;
+    pick_nightly_release(releases)
+        .ok_or_else(|| anyhow::anyhow!("no release with a valid version tag found"))
+}
+
+/// Select the nightly-channel release from a newest-first release list:
+/// the first valid-tagged prerelease, else the first valid-tagged release.
+fn pick_nightly_release(releases: Vec<GitHubRelease>) -> Option<GitHubRelease> {
+    releases
+        .iter()
+        .position(|r| r.prerelease && is_valid_update_tag(&r.tag_name))
+        .or_else(|| {
+            releases
+                .iter()
+                .position(|r| is_valid_update_tag(&r.tag_name))
+        })
+        .map(|idx| {
+            let mut releases = releases;
+            releases.swap_remove(idx)
+        })
+}
+
 /// Start a background thread to check for updates.
 /// Returns a receiver that will contain the result when ready.
 pub fn spawn_update_check(
@@ -1088,6 +1252,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: format!("https://token@github.com/{GITHUB_REPO}/releases/tag/v9.9.9"),
+            prerelease: false,
         };
         if build_update_info("1.0.0", release, &state).is_some() {
             return Err("release metadata accepted embedded credentials");
@@ -1113,6 +1278,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: "https://attacker.example/releases/tag/v9.9.9".to_string(),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1122,6 +1288,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: "file:///tmp/release-notes.html".to_string(),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1131,6 +1298,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: "https://github.com/other/project/releases/tag/v9.9.9".to_string(),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1151,6 +1319,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.8"),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1160,6 +1329,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.9?download=1"),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1169,6 +1339,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.9#assets"),
+            prerelease: false,
         };
         assert!(
             build_update_info("1.0.0", release, &state).is_none(),
@@ -1182,6 +1353,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9+build.5".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.9%2Bbuild.5"),
+            prerelease: false,
         };
         let info = build_update_info("1.0.0", release, &state)
             .expect("valid GitHub release notes URL should be accepted");
@@ -1197,6 +1369,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9+build.5".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.9%2bbuild.5"),
+            prerelease: false,
         };
         let info = build_update_info("1.0.0", release, &state)
             .expect("percent-encoded plus in a path segment is case-insensitive");
@@ -1212,6 +1385,7 @@ mod tests {
         let release = GitHubRelease {
             tag_name: "v9.9.9+build.5".to_string(),
             html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/v9.9.9%2BBUILD.5"),
+            prerelease: false,
         };
 
         assert!(
@@ -1282,6 +1456,7 @@ mod tests {
             let release = GitHubRelease {
                 tag_name: tag.to_string(),
                 html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/{tag}"),
+                prerelease: false,
             };
             assert!(
                 build_update_info("1.0.0", release, &state).is_none(),
@@ -1290,6 +1465,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn nightly_picker_prefers_first_valid_prerelease() {
+        let mk = |tag: &str, prerelease: bool| GitHubRelease {
+            tag_name: tag.to_string(),
+            html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/{tag}"),
+            prerelease,
+        };
+        // Newest-first, as the GitHub API returns them. The bad tag must be
+        // skipped even though it is marked prerelease.
+        let picked = pick_nightly_release(vec![
+            mk("nightly-latest", true),
+            mk("v1.3.0-nightly.20250901", true),
+            mk("v1.2.0", false),
+        ])
+        .expect("a valid release exists");
+        assert_eq!(picked.tag_name, "v1.3.0-nightly.20250901");
+    }
+
+    #[test]
+    fn nightly_picker_falls_back_to_stable_when_no_prerelease() {
+        let mk = |tag: &str| GitHubRelease {
+            tag_name: tag.to_string(),
+            html_url: format!("https://github.com/{GITHUB_REPO}/releases/tag/{tag}"),
+            prerelease: false,
+        };
+        let picked =
+            pick_nightly_release(vec![mk("v1.2.0"), mk("v1.1.0")]).expect("stable fallback");
+        assert_eq!(picked.tag_name, "v1.2.0");
+
+        assert!(pick_nightly_release(vec![]).is_none());
+    }
+
     /// `coding_agent_session_search-87sqx` / `coding_agent_session_search-6bvx8`: the allow-list on
     /// `CASS_UPDATE_API_BASE_URL` must reject non-https overrides
     /// against non-loopback hosts and non-GitHub HTTPS hosts (malicious .env / shell pollution)
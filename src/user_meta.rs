@@ -0,0 +1,517 @@
+//! Portable export/import of user curation metadata.
+//!
+//! Powers `cass meta export` / `cass meta import`. Everything the user curates
+//! by hand — conversation tags in the canonical DB, bookmarks (notes + tags)
+//! in `bookmarks.db`, and saved views in `tui_state.json` — lives only on the
+//! local machine and is lost on a `rm -rf` of the data dir or a move to new
+//! hardware. The snapshot produced here round-trips that curation through a
+//! single JSON file.
+//!
+//! Conversation-level entries are keyed by stable identity rather than row id:
+//! agent slug + harness external id + a content hash over the opening message.
+//! Row ids are assigned per-index and do not survive a re-index; the identity
+//! triple does. On import, agent + external id is tried first and the content
+//! hash is the fallback for sessions whose harness never recorded an id.
+//!
+//! Imports are merges: existing tags, bookmarks, and saved views are never
+//! removed, and duplicates are skipped.
+
+use anyhow::{Context, Result, anyhow};
+use frankensqlite::Connection;
+use frankensqlite::compat::{ConnectionExt, OptionalExtension, ParamValue, RowExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::bookmarks::Bookmark;
+
+/// Snapshot format version; bump on incompatible layout changes.
+pub const META_SNAPSHOT_VERSION: u32 = 1;
+
+/// Stable identity for one conversation, independent of row ids.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationKey {
+    /// Agent slug (e.g. `claude-code`, `codex`).
+    pub agent: String,
+    /// Harness session/thread id, when the connector recorded one.
+    pub external_id: Option<String>,
+    /// Content hash over the opening message (see [`conversation_content_hash`]).
+    pub content_hash: String,
+}
+
+/// Tags attached to one conversation, keyed by stable identity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaggedConversation {
+    pub key: ConversationKey,
+    pub tags: Vec<String>,
+}
+
+/// The full curation snapshot written by `cass meta export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaSnapshot {
+    pub schema_version: u32,
+    /// Export time (unix millis).
+    pub exported_at: i64,
+    /// Device that produced the snapshot (see [`local_device_id`]). Stamped
+    /// onto imported annotations that predate per-row device tracking, so
+    /// last-writer-wins merges stay deterministic. Empty in pre-device
+    /// snapshots.
+    #[serde(default)]
+    pub device_id: String,
+    /// Conversation tags from the canonical DB.
+    pub tags: Vec<TaggedConversation>,
+    /// Bookmarks (notes + tags) from `bookmarks.db`.
+    pub bookmarks: Vec<Bookmark>,
+    /// Saved views from `tui_state.json`, kept as raw JSON so the snapshot
+    /// does not chase the TUI's persisted-state schema.
+    pub saved_views: Vec<serde_json::Value>,
+}
+
+/// Outcome of `cass meta import`, for both human and structured output.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetaImportReport {
+    /// Tag links inserted (conversation, tag) pairs.
+    pub tags_applied: usize,
+    /// Snapshot entries that matched an indexed conversation.
+    pub conversations_matched: usize,
+    /// Snapshot entries with no matching conversation (kept in the file; a
+    /// later re-import after indexing can still pick them up).
+    pub conversations_unmatched: usize,
+    /// Bookmarks inserted (duplicates skipped).
+    pub bookmarks_imported: usize,
+    /// Existing bookmarks that gained tags or a newer note from the merge.
+    pub bookmarks_merged: usize,
+    /// Note conflicts resolved by last-writer-wins instead of dropping a
+    /// side (see `bookmarks::merge_annotations`).
+    pub bookmark_conflicts: Vec<crate::bookmarks::BookmarkConflict>,
+    /// Saved views appended to `tui_state.json`.
+    pub saved_views_added: usize,
+}
+
+/// Stable identifier for this machine, generated once and persisted at
+/// `<data_dir>/device_id`. Stamped onto exported annotations so a
+/// two-machine merge can order concurrent writes deterministically (see
+/// `bookmarks::merge_annotations`): timestamps order writes, the device id
+/// breaks same-millisecond ties the same way on both sides.
+#[must_use]
+pub fn local_device_id(data_dir: &Path) -> String {
+    let path = data_dir.join("device_id");
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        let trimmed = existing.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    let mut bytes = [0u8; 8];
+    rand::RngCore::fill_bytes(&mut rand::rng(), &mut bytes);
+    let id: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    // Best-effort persistence: an unwritable data dir just means a fresh id
+    // per export, which only weakens tie-breaking, never corrupts a merge.
+    let _ = std::fs::create_dir_all(data_dir);
+    let _ = std::fs::write(&path, format!("{id}\n"));
+    id
+}
+
+/// Hash the opening message plus the message count into a short stable id.
+/// The opening message survives re-indexing verbatim, and the count guards
+/// against two sessions that start with the same boilerplate prompt.
+#[must_use]
+pub fn conversation_content_hash(
+    first_role: &str,
+    first_content: &str,
+    message_count: i64,
+) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(first_role.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(first_content.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(message_count.to_string().as_bytes());
+    // 16 hex chars: plenty of margin for a per-user corpus, keeps files small.
+    hasher.finalize().to_hex().to_string()[..16].to_string()
+}
+
+fn hash_for_conversation(conn: &Connection, conversation_id: i64) -> Result<String> {
+    let first: Option<(String, String)> = conn
+        .query_row_map(
+            "SELECT role, content FROM messages WHERE conversation_id = ? ORDER BY idx LIMIT 1",
+            &[ParamValue::from(conversation_id)],
+            |r: &frankensqlite::Row| Ok((r.get_typed(0)?, r.get_typed(1)?)),
+        )
+        .optional()
+        .context("reading opening message")?;
+    let count: i64 = conn
+        .query_row_map(
+            "SELECT COUNT(*) FROM messages WHERE conversation_id = ?",
+            &[ParamValue::from(conversation_id)],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .context("counting messages")?;
+    let (role, content) = first.unwrap_or_default();
+    Ok(conversation_content_hash(&role, &content, count))
+}
+
+/// Collect every tagged conversation from the canonical DB, keyed by stable
+/// identity.
+pub fn collect_tagged_conversations(conn: &Connection) -> Result<Vec<TaggedConversation>> {
+    let rows: Vec<(i64, String, Option<String>, String)> = conn
+        .query_map_collect(
+            "SELECT c.id, COALESCE(a.slug, 'unknown'), c.external_id, t.name
+             FROM conversation_tags ct
+             JOIN conversations c ON c.id = ct.conversation_id
+             JOIN tags t ON t.id = ct.tag_id
+             LEFT JOIN agents a ON a.id = c.agent_id
+             ORDER BY c.id, t.name",
+            &[],
+            |r: &frankensqlite::Row| {
+                Ok((
+                    r.get_typed(0)?,
+                    r.get_typed(1)?,
+                    r.get_typed(2)?,
+                    r.get_typed(3)?,
+                ))
+            },
+        )
+        .context("reading conversation tags")?;
+
+    let mut grouped: BTreeMap<i64, (String, Option<String>, Vec<String>)> = BTreeMap::new();
+    for (conv_id, agent, external_id, tag) in rows {
+        grouped
+            .entry(conv_id)
+            .or_insert_with(|| (agent, external_id, Vec::new()))
+            .2
+            .push(tag);
+    }
+
+    let mut out = Vec::with_capacity(grouped.len());
+    for (conv_id, (agent, external_id, tags)) in grouped {
+        out.push(TaggedConversation {
+            key: ConversationKey {
+                agent,
+                external_id,
+                content_hash: hash_for_conversation(conn, conv_id)?,
+            },
+            tags,
+        });
+    }
+    Ok(out)
+}
+
+/// Resolve a snapshot key against the index: agent + external id first, then
+/// the content hash over the agent's conversations.
+fn resolve_key(conn: &Connection, key: &ConversationKey) -> Result<Option<i64>> {
+    if let Some(external_id) = &key.external_id {
+        let hit: Option<i64> = conn
+            .query_row_map(
+                "SELECT c.id FROM conversations c
+                 LEFT JOIN agents a ON a.id = c.agent_id
+                 WHERE COALESCE(a.slug, 'unknown') = ? AND c.external_id = ?
+                 ORDER BY c.id DESC LIMIT 1",
+                &[
+                    ParamValue::from(key.agent.clone()),
+                    ParamValue::from(external_id.clone()),
+                ],
+                |r: &frankensqlite::Row| r.get_typed(0),
+            )
+            .optional()
+            .context("matching by external id")?;
+        if hit.is_some() {
+            return Ok(hit);
+        }
+    }
+
+    let candidates: Vec<i64> = conn
+        .query_map_collect(
+            "SELECT c.id FROM conversations c
+             LEFT JOIN agents a ON a.id = c.agent_id
+             WHERE COALESCE(a.slug, 'unknown') = ?",
+            &[ParamValue::from(key.agent.clone())],
+            |r: &frankensqlite::Row| r.get_typed(0),
+        )
+        .context("listing agent conversations")?;
+    for conv_id in candidates {
+        if hash_for_conversation(conn, conv_id)? == key.content_hash {
+            return Ok(Some(conv_id));
+        }
+    }
+    Ok(None)
+}
+
+/// Merge snapshot tags into the canonical DB. Returns the applied/matched/
+/// unmatched counts for [`MetaImportReport`].
+pub fn apply_tagged_conversations(
+    conn: &Connection,
+    entries: &[TaggedConversation],
+) -> Result<(usize, usize, usize)> {
+    let mut applied = 0usize;
+    let mut matched = 0usize;
+    let mut unmatched = 0usize;
+
+    for entry in entries {
+        let Some(conv_id) = resolve_key(conn, &entry.key)? else {
+            unmatched += 1;
+            continue;
+        };
+        matched += 1;
+        for tag in &entry.tags {
+            let tag = tag.trim();
+            if tag.is_empty() {
+                continue;
+            }
+            conn.execute_compat(
+                "INSERT OR IGNORE INTO tags (name) VALUES (?1)",
+                frankensqlite::params![tag],
+            )
+            .context("creating tag")?;
+            let tag_id: i64 = conn
+                .query_row_map(
+                    "SELECT id FROM tags WHERE name = ?",
+                    &[ParamValue::from(tag.to_string())],
+                    |r: &frankensqlite::Row| r.get_typed(0),
+                )
+                .context("looking up tag id")?;
+            let inserted = conn
+                .execute_compat(
+                    "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag_id) VALUES (?1, ?2)",
+                    frankensqlite::params![conv_id, tag_id],
+                )
+                .context("linking tag")?;
+            applied += inserted;
+        }
+    }
+    Ok((applied, matched, unmatched))
+}
+
+/// Read the saved-views array out of `tui_state.json` (empty if the file or
+/// field is missing).
+pub fn read_saved_views(state_path: &Path) -> Result<Vec<serde_json::Value>> {
+    if !state_path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(state_path)
+        .with_context(|| format!("reading {}", state_path.display()))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", state_path.display()))?;
+    Ok(value
+        .get("saved_views")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Append imported saved views into `tui_state.json`, skipping slots that are
+/// already taken. Returns how many views were added.
+pub fn merge_saved_views(state_path: &Path, incoming: &[serde_json::Value]) -> Result<usize> {
+    if incoming.is_empty() {
+        return Ok(0);
+    }
+    let mut state: serde_json::Value = if state_path.exists() {
+        let raw = std::fs::read_to_string(state_path)
+            .with_context(|| format!("reading {}", state_path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", state_path.display()))?
+    } else {
+        serde_json::json!({})
+    };
+    let obj = state
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("{} is not a JSON object", state_path.display()))?;
+    let views = obj
+        .entry("saved_views")
+        .or_insert_with(|| serde_json::json!([]));
+    let views = views
+        .as_array_mut()
+        .ok_or_else(|| anyhow!("saved_views in {} is not an array", state_path.display()))?;
+
+    let slot_of = |view: &serde_json::Value| view.get("slot").and_then(serde_json::Value::as_u64);
+    let taken: Vec<Option<u64>> = views.iter().map(slot_of).collect();
+    let mut added = 0usize;
+    for view in incoming {
+        let slot = slot_of(view);
+        if slot.is_some() && taken.contains(&slot) {
+            continue;
+        }
+        views.push(view.clone());
+        added += 1;
+    }
+    if added > 0 {
+        if let Some(parent) = state_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        std::fs::write(state_path, serde_json::to_vec_pretty(&state)?)
+            .with_context(|| format!("writing {}", state_path.display()))?;
+    }
+    Ok(added)
+}
+
+/// Parse and version-check a snapshot file.
+pub fn read_snapshot(path: &Path) -> Result<MetaSnapshot> {
+    let raw =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let snapshot: MetaSnapshot =
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+    if snapshot.schema_version > META_SNAPSHOT_VERSION {
+        return Err(anyhow!(
+            "snapshot schema v{} is newer than this cass (supports up to v{}); upgrade cass first",
+            snapshot.schema_version,
+            META_SNAPSHOT_VERSION
+        ));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open(":memory:").unwrap();
+        conn.execute_batch(
+            "CREATE TABLE agents (id INTEGER PRIMARY KEY, slug TEXT NOT NULL UNIQUE);
+             CREATE TABLE conversations (
+                 id INTEGER PRIMARY KEY,
+                 agent_id INTEGER,
+                 external_id TEXT,
+                 source_path TEXT NOT NULL
+             );
+             CREATE TABLE messages (
+                 id INTEGER PRIMARY KEY,
+                 conversation_id INTEGER NOT NULL,
+                 idx INTEGER NOT NULL,
+                 role TEXT NOT NULL,
+                 content TEXT NOT NULL
+             );
+             CREATE TABLE tags (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE);
+             CREATE TABLE conversation_tags (
+                 conversation_id INTEGER NOT NULL,
+                 tag_id INTEGER NOT NULL,
+                 PRIMARY KEY (conversation_id, tag_id)
+             );",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn seed_conversation(conn: &Connection, id: i64, external_id: Option<&str>, opening: &str) {
+        conn.execute_batch("INSERT OR IGNORE INTO agents (id, slug) VALUES (1, 'claude-code');")
+            .unwrap();
+        conn.execute_compat(
+            "INSERT INTO conversations (id, agent_id, external_id, source_path) VALUES (?1, 1, ?2, ?3)",
+            frankensqlite::params![id, external_id, format!("/s/{id}.jsonl").as_str()],
+        )
+        .unwrap();
+        conn.execute_compat(
+            "INSERT INTO messages (conversation_id, idx, role, content) VALUES (?1, 0, 'user', ?2)",
+            frankensqlite::params![id, opening],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_distinguishes_sessions() {
+        let a = conversation_content_hash("user", "fix the bug", 4);
+        assert_eq!(a, conversation_content_hash("user", "fix the bug", 4));
+        assert_eq!(a.len(), 16);
+        assert_ne!(a, conversation_content_hash("user", "fix the bug", 5));
+        assert_ne!(a, conversation_content_hash("user", "fix another bug", 4));
+    }
+
+    #[test]
+    fn tags_round_trip_by_external_id_after_reindex() {
+        let source = test_db();
+        seed_conversation(&source, 10, Some("sess-1"), "fix login");
+        source
+            .execute_batch(
+                "INSERT INTO tags (id, name) VALUES (1, 'important');
+                 INSERT INTO conversation_tags (conversation_id, tag_id) VALUES (10, 1);",
+            )
+            .unwrap();
+        let entries = collect_tagged_conversations(&source).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key.agent, "claude-code");
+        assert_eq!(entries[0].tags, vec!["important".to_string()]);
+
+        // "Re-indexed" DB: same session under a different row id, no tags.
+        let fresh = test_db();
+        seed_conversation(&fresh, 77, Some("sess-1"), "fix login");
+        let (applied, matched, unmatched) = apply_tagged_conversations(&fresh, &entries).unwrap();
+        assert_eq!((applied, matched, unmatched), (1, 1, 0));
+        // Importing the same snapshot again is a no-op.
+        let (applied, matched, _) = apply_tagged_conversations(&fresh, &entries).unwrap();
+        assert_eq!((applied, matched), (0, 1));
+    }
+
+    #[test]
+    fn content_hash_matches_sessions_without_external_id() {
+        let source = test_db();
+        seed_conversation(&source, 1, None, "investigate flaky test");
+        source
+            .execute_batch(
+                "INSERT INTO tags (id, name) VALUES (1, 'flaky');
+                 INSERT INTO conversation_tags (conversation_id, tag_id) VALUES (1, 1);",
+            )
+            .unwrap();
+        let entries = collect_tagged_conversations(&source).unwrap();
+
+        let fresh = test_db();
+        seed_conversation(&fresh, 5, None, "something unrelated");
+        seed_conversation(&fresh, 6, None, "investigate flaky test");
+        let (applied, matched, unmatched) = apply_tagged_conversations(&fresh, &entries).unwrap();
+        assert_eq!((applied, matched, unmatched), (1, 1, 0));
+
+        let tagged: i64 = fresh
+            .query_row_map(
+                "SELECT conversation_id FROM conversation_tags",
+                &[],
+                |r: &frankensqlite::Row| r.get_typed(0),
+            )
+            .unwrap();
+        assert_eq!(tagged, 6);
+    }
+
+    #[test]
+    fn saved_views_merge_skips_taken_slots() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("tui_state.json");
+        std::fs::write(
+            &state_path,
+            serde_json::to_vec(&serde_json::json!({
+                "saved_views": [{"slot": 1, "label": "mine"}]
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let incoming = vec![
+            serde_json::json!({"slot": 1, "label": "theirs"}),
+            serde_json::json!({"slot": 2, "label": "imported"}),
+        ];
+        assert_eq!(merge_saved_views(&state_path, &incoming).unwrap(), 1);
+
+        let views = read_saved_views(&state_path).unwrap();
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[0]["label"], "mine");
+        assert_eq!(views[1]["slot"], 2);
+    }
+
+    #[test]
+    fn snapshot_rejects_newer_schema_versions() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("meta.json");
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&serde_json::json!({
+                "schema_version": META_SNAPSHOT_VERSION + 1,
+                "exported_at": 0,
+                "tags": [],
+                "bookmarks": [],
+                "saved_views": []
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let err = read_snapshot(&path).unwrap_err();
+        assert!(err.to_string().contains("newer than this cass"));
+    }
+}
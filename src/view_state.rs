@@ -0,0 +1,236 @@
+//! Per-conversation reading positions for the TUI viewer.
+//!
+//! Reopening a 2,000-message conversation should land where the reader left
+//! off. This module persists the viewer scroll position and an explicit
+//! bookmark marker (set with `m` in the detail view) per conversation,
+//! keyed by source path so positions survive reindexing. Stored in a
+//! separate `view_state.db` next to `bookmarks.db` — user-authored state
+//! that rebuild flows must never touch (see `is_user_data_file`).
+
+use anyhow::{Context, Result};
+use frankensqlite::Connection;
+use frankensqlite::compat::{ConnectionExt, OptionalExtension, RowExt};
+use frankensqlite::params;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Persisted viewer state for one conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewState {
+    /// Last scroll position in the detail pane, in rendered lines.
+    pub scroll_offset: u32,
+    /// Bookmarked rendered line, if the reader set a marker with `m`.
+    pub bookmark_line: Option<u32>,
+    /// Message index (0-based) the bookmark falls in, for non-TUI consumers
+    /// (`cass view --at-bookmark`) that address messages rather than
+    /// rendered lines.
+    pub bookmark_message_idx: Option<usize>,
+}
+
+/// Storage backend for viewer positions using `SQLite`.
+pub struct ViewStateStore {
+    conn: Connection,
+}
+
+impl ViewStateStore {
+    /// Open or create a view-state store at the given path.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating view state directory {}", parent.display()))?;
+        }
+
+        let conn = Connection::open(path.to_string_lossy().as_ref())
+            .with_context(|| format!("opening view state db at {}", path.display()))?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Open the store at the default location (`data_dir/view_state.db`).
+    pub fn open_default() -> Result<Self> {
+        Self::open(&default_view_state_path())
+    }
+
+    /// Open only if the database already exists. Read paths (position
+    /// restore, `--at-bookmark`) use this so merely viewing a conversation
+    /// never creates the file; it appears on the first persisted write.
+    pub fn open_existing(path: &Path) -> Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        Self::open(path).map(Some)
+    }
+
+    /// Load the persisted state for a conversation, if any.
+    pub fn get(&self, source_path: &str) -> Result<Option<ViewState>> {
+        self.conn
+            .query_row_map(
+                "SELECT scroll_offset, bookmark_line, bookmark_message_idx
+                 FROM view_state WHERE source_path = ?1",
+                params![source_path],
+                |row: &frankensqlite::Row| {
+                    Ok(ViewState {
+                        scroll_offset: u32::try_from(row.get_typed::<i64>(0)?.max(0))
+                            .unwrap_or(u32::MAX),
+                        bookmark_line: row
+                            .get_typed::<Option<i64>>(1)?
+                            .and_then(|v| u32::try_from(v).ok()),
+                        bookmark_message_idx: row
+                            .get_typed::<Option<i64>>(2)?
+                            .and_then(|v| usize::try_from(v).ok()),
+                    })
+                },
+            )
+            .optional()
+            .context("loading view state")
+    }
+
+    /// Persist the scroll position, preserving any existing bookmark.
+    pub fn save_scroll(&self, source_path: &str, scroll_offset: u32) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO view_state (source_path, scroll_offset, updated_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(source_path) DO UPDATE SET
+                 scroll_offset = excluded.scroll_offset,
+                 updated_at = excluded.updated_at",
+            params![source_path, i64::from(scroll_offset), current_timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Set the bookmark marker. `message_idx` is the message the line falls
+    /// in, when the caller can resolve it.
+    pub fn set_bookmark(
+        &self,
+        source_path: &str,
+        line: u32,
+        message_idx: Option<usize>,
+    ) -> Result<()> {
+        let message_idx = message_idx.and_then(|idx| i64::try_from(idx).ok());
+        self.conn.execute_compat(
+            "INSERT INTO view_state
+                 (source_path, scroll_offset, bookmark_line, bookmark_message_idx, updated_at)
+             VALUES (?1, ?2, ?2, ?3, ?4)
+             ON CONFLICT(source_path) DO UPDATE SET
+                 bookmark_line = excluded.bookmark_line,
+                 bookmark_message_idx = excluded.bookmark_message_idx,
+                 updated_at = excluded.updated_at",
+            params![
+                source_path,
+                i64::from(line),
+                message_idx,
+                current_timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Clear the bookmark marker, keeping the scroll position.
+    pub fn clear_bookmark(&self, source_path: &str) -> Result<bool> {
+        let changed = self.conn.execute_compat(
+            "UPDATE view_state
+             SET bookmark_line = NULL, bookmark_message_idx = NULL, updated_at = ?2
+             WHERE source_path = ?1 AND bookmark_line IS NOT NULL",
+            params![source_path, current_timestamp()],
+        )?;
+        Ok(changed > 0)
+    }
+}
+
+/// Default path for the view-state database.
+#[must_use]
+pub fn default_view_state_path() -> PathBuf {
+    crate::default_data_dir().join("view_state.db")
+}
+
+/// SQL schema for the view-state database.
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS view_state (
+    source_path TEXT PRIMARY KEY,
+    scroll_offset INTEGER NOT NULL DEFAULT 0,
+    bookmark_line INTEGER,
+    bookmark_message_idx INTEGER,
+    updated_at INTEGER NOT NULL
+);
+";
+
+fn current_timestamp() -> i64 {
+    i64::try_from(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+    )
+    .unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_store() -> (ViewStateStore, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let store = ViewStateStore::open(&dir.path().join("view_state.db")).unwrap();
+        (store, dir)
+    }
+
+    #[test]
+    fn scroll_round_trips_and_updates() {
+        let (store, _dir) = test_store();
+        assert_eq!(store.get("/tmp/a.jsonl").unwrap(), None);
+
+        store.save_scroll("/tmp/a.jsonl", 120).unwrap();
+        store.save_scroll("/tmp/a.jsonl", 340).unwrap();
+        let state = store.get("/tmp/a.jsonl").unwrap().unwrap();
+        assert_eq!(state.scroll_offset, 340);
+        assert_eq!(state.bookmark_line, None);
+    }
+
+    #[test]
+    fn bookmark_survives_scroll_updates() {
+        let (store, _dir) = test_store();
+        store.set_bookmark("/tmp/a.jsonl", 88, Some(14)).unwrap();
+        store.save_scroll("/tmp/a.jsonl", 500).unwrap();
+
+        let state = store.get("/tmp/a.jsonl").unwrap().unwrap();
+        assert_eq!(state.scroll_offset, 500);
+        assert_eq!(state.bookmark_line, Some(88));
+        assert_eq!(state.bookmark_message_idx, Some(14));
+    }
+
+    #[test]
+    fn clearing_a_bookmark_keeps_the_scroll_position() {
+        let (store, _dir) = test_store();
+        store.set_bookmark("/tmp/a.jsonl", 88, None).unwrap();
+        assert!(store.clear_bookmark("/tmp/a.jsonl").unwrap());
+        assert!(!store.clear_bookmark("/tmp/a.jsonl").unwrap());
+
+        let state = store.get("/tmp/a.jsonl").unwrap().unwrap();
+        assert_eq!(state.bookmark_line, None);
+        assert_eq!(state.scroll_offset, 88);
+    }
+
+    #[test]
+    fn conversations_are_independent() {
+        let (store, _dir) = test_store();
+        store.save_scroll("/tmp/a.jsonl", 10).unwrap();
+        store.save_scroll("/tmp/b.jsonl", 20).unwrap();
+        assert_eq!(
+            store.get("/tmp/a.jsonl").unwrap().unwrap().scroll_offset,
+            10
+        );
+        assert_eq!(
+            store.get("/tmp/b.jsonl").unwrap().unwrap().scroll_offset,
+            20
+        );
+    }
+}
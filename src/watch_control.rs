@@ -0,0 +1,258 @@
+//! Runtime controls for watch mode: pause/resume, adaptive backoff, and a
+//! status heartbeat for `cass watch status`.
+//!
+//! The watch loop polls a small JSON control file instead of installing a
+//! SIGUSR1 handler — AGENTS.md forbids `unsafe` signal handlers and the
+//! `signal_hook`/`ctrlc` dependencies (see the `cass doctor --watch` SIGINT
+//! note), and a file survives daemon restarts where signal state would not.
+//! `cass watch pause|resume` writes the control file; the loop picks the
+//! change up within one poll interval. The loop also writes a heartbeat
+//! status file each cycle so `cass watch status` can answer without talking
+//! to the running process.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Control-file name under the data dir.
+const CONTROL_FILE: &str = "watch_control.json";
+/// Status-heartbeat file name under the data dir.
+const STATUS_FILE: &str = "watch_status.json";
+
+/// Operator-requested watch state, written by `cass watch pause|resume`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchControl {
+    /// Whether the watch loop should hold off on scan cycles.
+    pub paused: bool,
+    /// Optional operator-supplied reason, echoed in `watch status`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the request was written (unix millis).
+    #[serde(default)]
+    pub requested_at_ms: i64,
+}
+
+/// Heartbeat written by the watch loop each cycle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchStatus {
+    /// PID of the watch process.
+    pub pid: u32,
+    /// `running`, `paused`, or `paused-on-battery`.
+    pub state: String,
+    /// When the watch loop started (unix millis).
+    pub started_at_ms: i64,
+    /// When the last scan cycle fired (unix millis), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_cycle_at_ms: Option<i64>,
+    /// Scan cycles completed since start.
+    pub cycles_completed: u64,
+    /// Effective interval after adaptive backoff (seconds).
+    pub current_interval_secs: u64,
+    /// Whether `--pause-on-battery` is active for this run.
+    pub pause_on_battery: bool,
+    /// Heartbeat timestamp (unix millis).
+    pub updated_at_ms: i64,
+}
+
+/// Path of the control file under `data_dir`.
+#[must_use]
+pub fn control_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CONTROL_FILE)
+}
+
+/// Path of the status-heartbeat file under `data_dir`.
+#[must_use]
+pub fn status_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(STATUS_FILE)
+}
+
+/// Read the current control request. A missing or unparseable file means
+/// "not paused" — the control file is advisory, never load-bearing.
+#[must_use]
+pub fn load_control(data_dir: &Path) -> WatchControl {
+    std::fs::read_to_string(control_path(data_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Write a pause/resume request for the watch loop.
+pub fn write_control(data_dir: &Path, paused: bool, reason: Option<&str>) -> Result<()> {
+    let control = WatchControl {
+        paused,
+        reason: reason.map(str::to_string),
+        requested_at_ms: chrono::Utc::now().timestamp_millis(),
+    };
+    let path = control_path(data_dir);
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("creating data dir {}", data_dir.display()))?;
+    std::fs::write(&path, serde_json::to_string_pretty(&control)?)
+        .with_context(|| format!("writing watch control file {}", path.display()))?;
+    Ok(())
+}
+
+/// Read the last heartbeat, if a watch loop ever wrote one.
+#[must_use]
+pub fn load_status(data_dir: &Path) -> Option<WatchStatus> {
+    std::fs::read_to_string(status_path(data_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+}
+
+/// Write the heartbeat. Best-effort: a full disk must not kill the watcher.
+pub fn write_status(data_dir: &Path, status: &WatchStatus) {
+    let path = status_path(data_dir);
+    if let Ok(json) = serde_json::to_string_pretty(status)
+        && let Err(e) = std::fs::write(&path, json)
+    {
+        tracing::debug!("failed to write watch status heartbeat: {e}");
+    }
+}
+
+/// Best-effort battery detection. `Some(true)` only when the platform
+/// positively reports discharging; unknown platforms return `None` so
+/// `--pause-on-battery` never pauses a desktop that can't report power state.
+#[must_use]
+pub fn on_battery() -> Option<bool> {
+    #[cfg(target_os = "linux")]
+    {
+        let supplies = std::fs::read_dir("/sys/class/power_supply").ok()?;
+        let mut saw_battery = false;
+        let mut ac_online = false;
+        for entry in supplies.flatten() {
+            let path = entry.path();
+            let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            match kind.trim() {
+                "Mains" => {
+                    if std::fs::read_to_string(path.join("online"))
+                        .map(|s| s.trim() == "1")
+                        .unwrap_or(false)
+                    {
+                        ac_online = true;
+                    }
+                }
+                "Battery" => saw_battery = true,
+                _ => {}
+            }
+        }
+        if saw_battery {
+            Some(!ac_online)
+        } else {
+            None
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Adaptive scan-interval backoff: each idle cycle doubles the effective
+/// interval up to a ceiling, and any observed change snaps it back to the
+/// configured base. Keeps a quiet overnight machine from waking every 30s
+/// while staying responsive the moment files change.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBackoff {
+    base_secs: u64,
+    max_secs: u64,
+    current_secs: u64,
+}
+
+impl AdaptiveBackoff {
+    /// Ceiling multiplier over the base interval.
+    const MAX_MULTIPLIER: u64 = 8;
+
+    /// Start at the configured base interval.
+    #[must_use]
+    pub fn new(base_secs: u64) -> Self {
+        let base_secs = base_secs.max(1);
+        Self {
+            base_secs,
+            max_secs: base_secs.saturating_mul(Self::MAX_MULTIPLIER),
+            current_secs: base_secs,
+        }
+    }
+
+    /// Effective interval right now.
+    #[must_use]
+    pub fn current_secs(&self) -> u64 {
+        self.current_secs
+    }
+
+    /// An idle cycle elapsed with no changes: widen the interval.
+    pub fn note_idle(&mut self) {
+        self.current_secs = self.current_secs.saturating_mul(2).min(self.max_secs);
+    }
+
+    /// Changes were observed: snap back to the base interval.
+    pub fn note_activity(&mut self) {
+        self.current_secs = self.base_secs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn control_round_trips_and_defaults_to_running() {
+        let dir = tempdir().unwrap();
+        assert!(!load_control(dir.path()).paused);
+
+        write_control(dir.path(), true, Some("on battery")).unwrap();
+        let control = load_control(dir.path());
+        assert!(control.paused);
+        assert_eq!(control.reason.as_deref(), Some("on battery"));
+
+        write_control(dir.path(), false, None).unwrap();
+        assert!(!load_control(dir.path()).paused);
+    }
+
+    #[test]
+    fn corrupt_control_file_means_not_paused() {
+        let dir = tempdir().unwrap();
+        std::fs::write(control_path(dir.path()), "{not json").unwrap();
+        assert!(!load_control(dir.path()).paused);
+    }
+
+    #[test]
+    fn status_heartbeat_round_trips() {
+        let dir = tempdir().unwrap();
+        assert!(load_status(dir.path()).is_none());
+
+        let status = WatchStatus {
+            pid: 1234,
+            state: "running".to_string(),
+            started_at_ms: 1_700_000_000_000,
+            last_cycle_at_ms: Some(1_700_000_060_000),
+            cycles_completed: 3,
+            current_interval_secs: 30,
+            pause_on_battery: true,
+            updated_at_ms: 1_700_000_061_000,
+        };
+        write_status(dir.path(), &status);
+        let loaded = load_status(dir.path()).expect("status written");
+        assert_eq!(loaded.pid, 1234);
+        assert_eq!(loaded.cycles_completed, 3);
+        assert_eq!(loaded.state, "running");
+    }
+
+    #[test]
+    fn backoff_doubles_to_ceiling_and_resets() {
+        let mut backoff = AdaptiveBackoff::new(30);
+        assert_eq!(backoff.current_secs(), 30);
+        for _ in 0..10 {
+            backoff.note_idle();
+        }
+        assert_eq!(backoff.current_secs(), 240);
+        backoff.note_activity();
+        assert_eq!(backoff.current_secs(), 30);
+    }
+
+    #[test]
+    fn backoff_clamps_zero_base() {
+        let backoff = AdaptiveBackoff::new(0);
+        assert_eq!(backoff.current_secs(), 1);
+    }
+}
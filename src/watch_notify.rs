@@ -0,0 +1,211 @@
+//! Desktop notifications for watch-mode session failures.
+//!
+//! Watch mode usually indexes sessions nobody is looking at — overnight
+//! agent runs, batch jobs on a second machine. When one of those sessions
+//! ends in what looks like a failure (a panic, a non-zero exec exit, or the
+//! agent declaring it cannot finish), this module fires a desktop
+//! notification naming the session and the `cass view` command that opens
+//! it, so the failure is seen in minutes instead of at the next manual
+//! check-in.
+//!
+//! Detection is a tail heuristic: only the last few messages of a
+//! just-reindexed session are inspected, and refusal phrasing only counts
+//! in the final agent message (an agent *quoting* an earlier failure while
+//! fixing it must not page anyone). Delivery goes through the platform
+//! notifier (`notify-send` on Linux, `osascript` on macOS) spawned
+//! best-effort like the other configured hooks — no notification daemon,
+//! no failure. Disable entirely with `CASS_WATCH_NOTIFY=0`.
+
+use std::path::PathBuf;
+
+/// How many tail messages of a session the failure heuristics inspect.
+pub const FAILURE_TAIL_MESSAGES: usize = 4;
+
+/// Whether watch-mode failure notifications are enabled
+/// (`CASS_WATCH_NOTIFY`, default on).
+#[must_use]
+pub fn notifications_enabled() -> bool {
+    notifications_enabled_from_parts(dotenvy::var("CASS_WATCH_NOTIFY").ok())
+}
+
+/// Pure resolution against an already-read env value.
+#[must_use]
+pub fn notifications_enabled_from_parts(env: Option<String>) -> bool {
+    match env.as_deref().map(str::trim) {
+        Some("0") | Some("false") | Some("FALSE") => false,
+        _ => true,
+    }
+}
+
+/// Inspect a session tail (`(role, content)` pairs, newest first) and
+/// return a short human-readable reason when it looks like the session
+/// ended in failure, `None` otherwise.
+#[must_use]
+pub fn detect_failure(tail_newest_first: &[(String, String)]) -> Option<String> {
+    // Refusal phrasing only counts in the final agent message; anywhere
+    // earlier it is likely being quoted or already recovered from.
+    if let Some((_, content)) = tail_newest_first.iter().find(|(role, _)| role == "agent") {
+        let lowered = content.to_lowercase();
+        if [
+            "i'm unable to",
+            "i am unable to",
+            "i cannot complete",
+            "i was unable to complete",
+        ]
+        .iter()
+        .any(|phrase| lowered.contains(phrase))
+        {
+            return Some("agent reported it was unable to finish".to_string());
+        }
+    }
+    for (_, content) in tail_newest_first {
+        if content.contains("panicked at") {
+            return Some("panic in session output".to_string());
+        }
+        if let Some(code) = nonzero_exit_code(content) {
+            return Some(format!("command exited with code {code}"));
+        }
+    }
+    None
+}
+
+/// Non-zero exit code from tool-output phrasings like `exit code: 1`,
+/// `exit status: 101`, or `exited with code 2`.
+fn nonzero_exit_code(content: &str) -> Option<i64> {
+    let lowered = content.to_lowercase();
+    for marker in ["exit code", "exit status", "exited with code"] {
+        let Some(at) = lowered.find(marker) else {
+            continue;
+        };
+        let rest = lowered[at + marker.len()..].trim_start_matches([':', ' ', '=']);
+        let digits: String = rest
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '-')
+            .collect();
+        if let Ok(code) = digits.parse::<i64>()
+            && code != 0
+        {
+            return Some(code);
+        }
+    }
+    None
+}
+
+/// Check the just-reindexed `source_paths` for failure tails and fire one
+/// notification per failed session. Entirely best-effort: storage or
+/// notifier errors are logged at debug level and never disturb the watch
+/// loop.
+pub fn notify_failed_sessions(
+    storage: &crate::storage::sqlite::FrankenStorage,
+    source_paths: &[PathBuf],
+) {
+    for path in source_paths.iter().take(20) {
+        let source_path = path.to_string_lossy();
+        let tail = match storage.final_messages_for_source(&source_path, FAILURE_TAIL_MESSAGES) {
+            Ok(tail) => tail,
+            Err(err) => {
+                tracing::debug!(source_path = %source_path, error = %err, "failure-tail lookup failed");
+                continue;
+            }
+        };
+        if let Some(reason) = detect_failure(&tail) {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| source_path.to_string());
+            notify(
+                &format!("cass: agent session failed — {name}"),
+                &format!("{reason}\nOpen with: cass view {source_path}"),
+            );
+        }
+    }
+}
+
+/// Fire a desktop notification through the platform notifier, spawned
+/// detached like the budget and ranking hooks. Missing notifier binaries
+/// are logged and ignored.
+pub fn notify(summary: &str, body: &str) {
+    let result = if cfg!(target_os = "macos") {
+        std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification \"{}\" with title \"{}\"",
+                applescript_escape(body),
+                applescript_escape(summary),
+            ))
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+    } else if cfg!(target_os = "linux") {
+        std::process::Command::new("notify-send")
+            .arg("--app-name=cass")
+            .arg(summary)
+            .arg(body)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+    } else {
+        tracing::debug!("no desktop notifier wired for this platform");
+        return;
+    };
+    if let Err(err) = result {
+        tracing::debug!(error = %err, "desktop notification failed to spawn");
+    }
+}
+
+/// Escape a string for embedding in a double-quoted AppleScript literal.
+fn applescript_escape(raw: &str) -> String {
+    raw.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> (String, String) {
+        (role.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn env_flag_defaults_on_and_zero_disables() {
+        assert!(notifications_enabled_from_parts(None));
+        assert!(notifications_enabled_from_parts(Some("1".into())));
+        assert!(!notifications_enabled_from_parts(Some("0".into())));
+        assert!(!notifications_enabled_from_parts(Some(" false ".into())));
+    }
+
+    #[test]
+    fn detects_panic_exit_code_and_refusal() {
+        assert_eq!(
+            detect_failure(&[msg("tool", "thread 'main' panicked at src/lib.rs:10")]),
+            Some("panic in session output".to_string())
+        );
+        assert_eq!(
+            detect_failure(&[msg("tool", "Process exited with code 101")]),
+            Some("command exited with code 101".to_string())
+        );
+        assert_eq!(
+            detect_failure(&[msg(
+                "agent",
+                "I'm unable to complete this without credentials"
+            )]),
+            Some("agent reported it was unable to finish".to_string())
+        );
+        assert_eq!(detect_failure(&[msg("agent", "All tests pass.")]), None);
+        // Exit code 0 is success, not failure.
+        assert_eq!(detect_failure(&[msg("tool", "exit code: 0")]), None);
+    }
+
+    #[test]
+    fn refusal_in_an_earlier_agent_message_does_not_fire() {
+        // Newest first: the final agent message recovered; the refusal is
+        // an older message being superseded.
+        let tail = vec![
+            msg("agent", "Fixed it by switching to the fallback API."),
+            msg("agent", "I'm unable to reach the primary API."),
+        ];
+        assert_eq!(detect_failure(&tail), None);
+    }
+}
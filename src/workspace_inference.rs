@@ -0,0 +1,273 @@
+//! Workspace inference for conversations whose connector left it NULL.
+//!
+//! Several harnesses only record the workspace in optional metadata, so a
+//! missing field does not mean there was no workspace — the session content
+//! almost always betrays it. This module scans message text for cwd-like
+//! declarations, `cd` commands, and git remote URLs, tallies votes per
+//! directory, and returns the consensus candidate with a confidence score.
+//!
+//! Inferred workspaces are written at the storage boundary (see
+//! `normalized_conversation_for_storage`) and marked in the conversation's
+//! `metadata_json` under [`METADATA_KEY`] so downstream consumers can tell an
+//! inferred workspace from a connector-reported one and include or exclude
+//! them accordingly.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// `metadata_json` key carrying the inference record for marked rows.
+pub const METADATA_KEY: &str = "cass_workspace_inference";
+
+/// Minimum confidence required before an inferred workspace is persisted.
+pub const MIN_CONFIDENCE: f32 = 0.5;
+
+/// Minimum accumulated vote weight; a single weak signal is not enough.
+const MIN_SCORE: f32 = 2.0;
+
+/// Messages scanned per conversation. Workspace evidence concentrates at the
+/// start of a session (environment dumps, the first few commands).
+pub const MAX_MESSAGES_SCANNED: usize = 50;
+
+/// Vote weights per signal kind. An explicit cwd declaration is near-certain;
+/// a `cd` target is strong; a repo-name match from a git remote URL only
+/// corroborates paths seen elsewhere.
+const WEIGHT_CWD: f32 = 3.0;
+const WEIGHT_CD: f32 = 2.0;
+const WEIGHT_REMOTE_MATCH: f32 = 1.0;
+
+/// A consensus workspace inferred from session content.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceInference {
+    /// The inferred workspace directory.
+    pub path: String,
+    /// Share of total vote weight the winning candidate received, in `0..=1`.
+    pub confidence: f32,
+    /// Number of individual signals that voted for the winner.
+    pub signals: usize,
+}
+
+/// Infer the workspace for a conversation from its message contents.
+///
+/// Returns `None` when no candidate clears both the vote-weight floor and
+/// [`MIN_CONFIDENCE`]; callers should then leave the workspace NULL rather
+/// than guess.
+#[must_use]
+pub fn infer_workspace<'a, I>(contents: I) -> Option<WorkspaceInference>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut votes: BTreeMap<String, (f32, usize)> = BTreeMap::new();
+    let mut repo_names: Vec<String> = Vec::new();
+    let mut total_weight = 0f32;
+
+    for content in contents.into_iter().take(MAX_MESSAGES_SCANNED) {
+        for line in content.lines() {
+            let line = line.trim();
+            for (path, weight) in path_signals(line) {
+                total_weight += weight;
+                let entry = votes.entry(path).or_insert((0.0, 0));
+                entry.0 += weight;
+                entry.1 += 1;
+            }
+            if let Some(name) = git_remote_repo_name(line)
+                && !repo_names.contains(&name)
+            {
+                repo_names.push(name);
+            }
+        }
+    }
+
+    // Remote URLs corroborate a path whose final component matches the repo
+    // name; on their own they point at no local directory.
+    for name in &repo_names {
+        let matching: Vec<String> = votes
+            .keys()
+            .filter(|path| path.rsplit('/').next() == Some(name.as_str()))
+            .cloned()
+            .collect();
+        for path in matching {
+            total_weight += WEIGHT_REMOTE_MATCH;
+            let entry = votes.entry(path).or_insert((0.0, 0));
+            entry.0 += WEIGHT_REMOTE_MATCH;
+            entry.1 += 1;
+        }
+    }
+
+    let (path, (score, signals)) = votes
+        .into_iter()
+        .max_by(|a, b| a.1.0.total_cmp(&b.1.0).then_with(|| b.0.cmp(&a.0)))?;
+    if score < MIN_SCORE || total_weight <= 0.0 {
+        return None;
+    }
+    let confidence = score / total_weight;
+    if confidence < MIN_CONFIDENCE {
+        return None;
+    }
+    Some(WorkspaceInference {
+        path,
+        confidence,
+        signals,
+    })
+}
+
+/// Extract `(candidate_path, weight)` votes from one line.
+fn path_signals(line: &str) -> Vec<(String, f32)> {
+    let mut out = Vec::new();
+    let lowered = line.to_lowercase();
+    // Byte offsets found in `lowered` are only valid in `line` when the line
+    // is ASCII; fall back to extracting from `lowered` otherwise.
+    let source: &str = if line.is_ascii() { line } else { &lowered };
+
+    // cwd-style declarations: `cwd: /path`, `<cwd>/path</cwd>`,
+    // `Current working directory: /path`, `workdir: /path`.
+    for marker in ["cwd:", "cwd>", "current working directory:", "workdir:"] {
+        if let Some(idx) = lowered.find(marker) {
+            let rest = &source[idx + marker.len()..];
+            let rest = rest.split('<').next().unwrap_or(rest);
+            if let Some(path) = normalize_candidate(rest.trim()) {
+                out.push((path, WEIGHT_CWD));
+            }
+        }
+    }
+
+    // `cd /abs/path` at the start of a command (optionally after a `$ `
+    // prompt). Relative `cd` targets are ambiguous and skipped.
+    let command = line.strip_prefix("$ ").unwrap_or(line);
+    if let Some(target) = command.strip_prefix("cd ") {
+        let target = target.split("&&").next().unwrap_or(target).trim();
+        if let Some(path) = normalize_candidate(target) {
+            out.push((path, WEIGHT_CD));
+        }
+    }
+
+    out
+}
+
+/// Repo name from a git remote URL on this line (`git@host:owner/repo.git`,
+/// `https://host/owner/repo`), if any.
+fn git_remote_repo_name(line: &str) -> Option<String> {
+    for token in line.split_whitespace() {
+        let looks_like_remote = token.starts_with("git@")
+            || ((token.starts_with("https://") || token.starts_with("ssh://"))
+                && token.matches('/').count() >= 4);
+        if !looks_like_remote {
+            continue;
+        }
+        let tail = token.rsplit(['/', ':']).next()?;
+        let name = tail.strip_suffix(".git").unwrap_or(tail);
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+        {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+/// Normalize a candidate to an absolute directory path, rejecting tokens that
+/// cannot name a project workspace (system prefixes, the bare home dir, file
+/// names, URLs).
+fn normalize_candidate(raw: &str) -> Option<String> {
+    let raw = raw
+        .trim_matches(|c: char| matches!(c, '`' | '\'' | '"' | ',' | ';' | ')' | '('))
+        .trim_end_matches('/');
+    if raw.is_empty() || raw.contains("://") || raw.contains(char::is_whitespace) {
+        return None;
+    }
+    if !(raw.starts_with('/') || raw.starts_with("~/")) {
+        return None;
+    }
+    // System prefixes and scratch dirs never host a user workspace.
+    const REJECTED_PREFIXES: &[&str] = &[
+        "/tmp",
+        "/usr",
+        "/etc",
+        "/var",
+        "/bin",
+        "/sbin",
+        "/proc",
+        "/sys",
+        "/dev",
+        "/opt/homebrew",
+    ];
+    if REJECTED_PREFIXES
+        .iter()
+        .any(|p| raw == *p || raw.strip_prefix(p).is_some_and(|r| r.starts_with('/')))
+    {
+        return None;
+    }
+    // The bare home dir is not a workspace.
+    if raw == "~" || raw == "~/" {
+        return None;
+    }
+    // Reject obvious file names; workspaces are directories.
+    if raw
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains('.') && !name.starts_with('.'))
+    {
+        return None;
+    }
+    if raw.len() > 300 {
+        return None;
+    }
+    Some(raw.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cwd_declaration_wins_on_its_own() {
+        let inferred = infer_workspace(["<cwd>/home/user/projects/cass</cwd>"]).expect("inferred");
+        assert_eq!(inferred.path, "/home/user/projects/cass");
+        assert!(inferred.confidence >= MIN_CONFIDENCE);
+        assert_eq!(inferred.signals, 1);
+    }
+
+    #[test]
+    fn consensus_prefers_the_repeated_directory() {
+        let inferred = infer_workspace([
+            "cwd: /home/user/work/api",
+            "$ cd /home/user/work/api",
+            "cd /home/user/scratch",
+        ])
+        .expect("inferred");
+        assert_eq!(inferred.path, "/home/user/work/api");
+        assert_eq!(inferred.signals, 2);
+        assert!(inferred.confidence > 0.6);
+    }
+
+    #[test]
+    fn git_remote_corroborates_matching_path() {
+        let inferred = infer_workspace([
+            "$ cd /home/user/code/frankensearch",
+            "origin  git@github.com:owner/frankensearch.git (fetch)",
+        ])
+        .expect("inferred");
+        assert_eq!(inferred.path, "/home/user/code/frankensearch");
+        assert_eq!(inferred.signals, 2);
+    }
+
+    #[test]
+    fn weak_or_system_signals_do_not_infer() {
+        assert!(infer_workspace(["origin git@github.com:owner/repo.git"]).is_none());
+        assert!(infer_workspace(["cwd: /tmp/build-scratch"]).is_none());
+        assert!(infer_workspace(["cd relative/dir"]).is_none());
+        assert!(infer_workspace(["nothing to see here"]).is_none());
+    }
+
+    #[test]
+    fn candidate_normalization_rejects_files_and_urls() {
+        assert_eq!(
+            normalize_candidate("/home/u/proj/"),
+            Some("/home/u/proj".to_string())
+        );
+        assert!(normalize_candidate("/home/u/proj/main.rs").is_none());
+        assert!(normalize_candidate("https://example.com/a/b").is_none());
+        assert!(normalize_candidate("~").is_none());
+    }
+}
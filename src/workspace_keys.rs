@@ -0,0 +1,339 @@
+//! Workspace-scoped key material and access tokens for shared (team) indexes.
+//!
+//! A single cass index served to a team mixes sessions from unrelated
+//! projects, and "everyone who can reach the server can read everything" is
+//! not an acceptable model there: client A's sessions must stay unreadable to
+//! project B's people. This module provides the two primitives that scoping
+//! needs, both derived from one operator-held master key:
+//!
+//! * **Per-workspace subkeys** — [`derive_workspace_key`] turns the master
+//!   key into an independent AES-256 key per workspace path (HKDF-SHA256,
+//!   workspace path as the domain-separation info). Compromising one
+//!   workspace's key reveals nothing about any other, and
+//!   [`encrypt_for_workspace`] / [`decrypt_for_workspace`] seal payloads so a
+//!   reader without the right subkey gets an authentication failure, not
+//!   garbled plaintext.
+//! * **Scoped tokens** — [`mint_scoped_token`] issues a compact
+//!   `payload.signature` token listing the workspace prefixes a client may
+//!   read plus an expiry; [`verify_scoped_token`] authenticates it (HMAC over
+//!   a key derived from the same master, verified in constant time) and
+//!   rejects tampering or expiry. A front-end serving the index over HTTP
+//!   hands these out as bearer tokens; inside this crate
+//!   [`crate::api::Client::restrict_to_token`] enforces the scope at the
+//!   storage read layer, so every read path (search, session listing,
+//!   conversation fetch) filters to the token's workspaces.
+//!
+//! The master key never appears in tokens or ciphertexts; it lives wherever
+//! the operator keeps secrets (`cass secrets` stores it in the OS keychain).
+//! Key derivation is deterministic, so rotating the master key atomically
+//! rotates every subkey and invalidates every outstanding token.
+
+use serde::{Deserialize, Serialize};
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Length of a derived per-workspace key (AES-256).
+pub const WORKSPACE_KEY_LEN: usize = 32;
+
+/// Minimum accepted master-key length. 16 bytes is already generous for an
+/// operator-generated secret; shorter inputs are almost certainly a
+/// configuration mistake (empty string, truncated paste) and are rejected.
+pub const MIN_MASTER_KEY_LEN: usize = 16;
+
+const KEY_DERIVATION_SALT: &[u8] = b"cass:workspace-key:v1";
+const TOKEN_KEY_INFO: &[u8] = b"cass:scoped-token-key:v1";
+const NONCE_LEN: usize = 12;
+
+/// Derive the encryption subkey for one workspace from the master key.
+///
+/// Deterministic: the same `(master, workspace_path)` always yields the same
+/// key, so no per-workspace key escrow is needed — the master key is the only
+/// stored secret. Different workspace paths yield unrelated keys.
+pub fn derive_workspace_key(
+    master: &[u8],
+    workspace_path: &str,
+) -> Result<[u8; WORKSPACE_KEY_LEN], String> {
+    validate_master(master)?;
+    let okm = crate::encryption::hkdf_extract_expand(
+        master,
+        KEY_DERIVATION_SALT,
+        workspace_path.as_bytes(),
+        WORKSPACE_KEY_LEN,
+    )?;
+    let mut key = [0u8; WORKSPACE_KEY_LEN];
+    key.copy_from_slice(&okm);
+    Ok(key)
+}
+
+/// Encrypt a payload under a workspace's subkey. Output is
+/// `nonce || ciphertext || tag`; `aad` binds context (e.g. the conversation's
+/// source path) so a ciphertext can't be replayed under another record.
+pub fn encrypt_for_workspace(
+    master: &[u8],
+    workspace_path: &str,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, String> {
+    let key = derive_workspace_key(master, workspace_path)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "nonce generation failed".to_string())?;
+    let (ciphertext, tag) = crate::encryption::aes_gcm_encrypt(&key, &nonce, plaintext, aad)?;
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len() + tag.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    out.extend_from_slice(&tag);
+    Ok(out)
+}
+
+/// Decrypt a payload produced by [`encrypt_for_workspace`]. Fails (rather
+/// than returning garbage) when the master key, workspace path, or `aad` do
+/// not match the ones used at encryption time.
+pub fn decrypt_for_workspace(
+    master: &[u8],
+    workspace_path: &str,
+    sealed: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, String> {
+    const TAG_LEN: usize = 16;
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err("sealed payload too short".to_string());
+    }
+    let key = derive_workspace_key(master, workspace_path)?;
+    let (nonce, rest) = sealed.split_at(NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+    crate::encryption::aes_gcm_decrypt(&key, nonce, ciphertext, tag, aad)
+}
+
+/// The scope a token grants: which workspaces, until when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenScope {
+    /// Workspace path prefixes the holder may read. Matching is on whole
+    /// path components: `/home/u/proj` covers itself and
+    /// `/home/u/proj/sub`, never `/home/u/project2`.
+    pub workspaces: Vec<String>,
+    /// Expiry (unix millis). Tokens are always bounded; there is no
+    /// "forever" scope.
+    pub expires_at_ms: i64,
+}
+
+impl TokenScope {
+    /// Whether this scope covers `workspace_path`.
+    #[must_use]
+    pub fn allows(&self, workspace_path: &str) -> bool {
+        self.workspaces.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            workspace_path == prefix
+                || workspace_path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Why a token was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// Structurally not a token (missing separator, bad base64/JSON).
+    Malformed(String),
+    /// The signature does not verify under this master key.
+    BadSignature,
+    /// Verified, but past its expiry.
+    Expired { expires_at_ms: i64 },
+    /// The master key itself is unusable.
+    BadMasterKey(String),
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Malformed(e) => write!(f, "malformed scoped token: {e}"),
+            TokenError::BadSignature => write!(f, "scoped token signature does not verify"),
+            TokenError::Expired { expires_at_ms } => {
+                write!(f, "scoped token expired at {expires_at_ms} (unix ms)")
+            }
+            TokenError::BadMasterKey(e) => write!(f, "unusable master key: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// Mint a scoped bearer token: `base64url(payload) "." base64url(hmac)`.
+///
+/// The HMAC key is derived from the master key (never the master key
+/// directly), so token verification can be delegated to a component that
+/// must not be able to derive workspace encryption keys.
+pub fn mint_scoped_token(master: &[u8], scope: &TokenScope) -> Result<String, String> {
+    validate_master(master)?;
+    let payload = serde_json::to_vec(scope).map_err(|e| format!("encoding token scope: {e}"))?;
+    let key = token_hmac_key(master)?;
+    let sig = hmac::sign(&key, &payload);
+    Ok(format!(
+        "{}.{}",
+        BASE64_URL_SAFE_NO_PAD.encode(&payload),
+        BASE64_URL_SAFE_NO_PAD.encode(sig.as_ref())
+    ))
+}
+
+/// Verify a token against the master key and the current time, returning the
+/// scope it grants. Signature verification is constant-time (ring), and runs
+/// before the expiry check so a forged token learns nothing from timing.
+pub fn verify_scoped_token(
+    master: &[u8],
+    token: &str,
+    now_ms: i64,
+) -> Result<TokenScope, TokenError> {
+    validate_master(master).map_err(TokenError::BadMasterKey)?;
+    let (payload_b64, sig_b64) = token
+        .split_once('.')
+        .ok_or_else(|| TokenError::Malformed("missing '.' separator".to_string()))?;
+    let payload = BASE64_URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .map_err(|e| TokenError::Malformed(format!("payload: {e}")))?;
+    let sig = BASE64_URL_SAFE_NO_PAD
+        .decode(sig_b64)
+        .map_err(|e| TokenError::Malformed(format!("signature: {e}")))?;
+
+    let key = token_hmac_key(master).map_err(TokenError::BadMasterKey)?;
+    hmac::verify(&key, &payload, &sig).map_err(|_| TokenError::BadSignature)?;
+
+    let scope: TokenScope = serde_json::from_slice(&payload)
+        .map_err(|e| TokenError::Malformed(format!("scope: {e}")))?;
+    if now_ms >= scope.expires_at_ms {
+        return Err(TokenError::Expired {
+            expires_at_ms: scope.expires_at_ms,
+        });
+    }
+    Ok(scope)
+}
+
+fn token_hmac_key(master: &[u8]) -> Result<hmac::Key, String> {
+    let key_bytes = crate::encryption::hkdf_extract_expand(
+        master,
+        KEY_DERIVATION_SALT,
+        TOKEN_KEY_INFO,
+        WORKSPACE_KEY_LEN,
+    )?;
+    Ok(hmac::Key::new(hmac::HMAC_SHA256, &key_bytes))
+}
+
+fn validate_master(master: &[u8]) -> Result<(), String> {
+    if master.len() < MIN_MASTER_KEY_LEN {
+        return Err(format!(
+            "master key must be at least {MIN_MASTER_KEY_LEN} bytes, got {}",
+            master.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER: &[u8] = b"unit-test-master-key-material";
+
+    #[test]
+    fn workspace_keys_are_deterministic_and_independent() {
+        let a1 = derive_workspace_key(MASTER, "/home/u/client-a").unwrap();
+        let a2 = derive_workspace_key(MASTER, "/home/u/client-a").unwrap();
+        let b = derive_workspace_key(MASTER, "/home/u/project-b").unwrap();
+        assert_eq!(a1, a2);
+        assert_ne!(a1, b);
+        // A different master rotates every subkey.
+        let rotated = derive_workspace_key(b"another-master-key-material", "/home/u/client-a");
+        assert_ne!(a1, rotated.unwrap());
+    }
+
+    #[test]
+    fn short_master_keys_are_rejected() {
+        assert!(derive_workspace_key(b"short", "/w").is_err());
+        assert!(matches!(
+            verify_scoped_token(b"short", "x.y", 0),
+            Err(TokenError::BadMasterKey(_))
+        ));
+    }
+
+    #[test]
+    fn seal_roundtrip_requires_matching_workspace_and_aad() {
+        let sealed =
+            encrypt_for_workspace(MASTER, "/w/a", b"secret transcript", b"conv-1").unwrap();
+        assert_eq!(
+            decrypt_for_workspace(MASTER, "/w/a", &sealed, b"conv-1").unwrap(),
+            b"secret transcript"
+        );
+        // Wrong workspace, wrong aad, or truncation all fail closed.
+        assert!(decrypt_for_workspace(MASTER, "/w/b", &sealed, b"conv-1").is_err());
+        assert!(decrypt_for_workspace(MASTER, "/w/a", &sealed, b"conv-2").is_err());
+        assert!(decrypt_for_workspace(MASTER, "/w/a", &sealed[..10], b"conv-1").is_err());
+    }
+
+    #[test]
+    fn token_roundtrip_and_tamper_rejection() {
+        let scope = TokenScope {
+            workspaces: vec!["/home/u/client-a".to_string()],
+            expires_at_ms: 2_000_000_000_000,
+        };
+        let token = mint_scoped_token(MASTER, &scope).unwrap();
+        let verified = verify_scoped_token(MASTER, &token, 1_700_000_000_000).unwrap();
+        assert_eq!(verified, scope);
+
+        // Flipping the payload (widening the scope) invalidates the signature.
+        let widened = TokenScope {
+            workspaces: vec!["/".to_string()],
+            expires_at_ms: scope.expires_at_ms,
+        };
+        let payload = BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(&widened).unwrap());
+        let forged = format!("{payload}.{}", token.split_once('.').unwrap().1);
+        assert_eq!(
+            verify_scoped_token(MASTER, &forged, 1_700_000_000_000),
+            Err(TokenError::BadSignature)
+        );
+        // A token minted under another master does not verify here.
+        let other = mint_scoped_token(b"another-master-key-material", &scope).unwrap();
+        assert_eq!(
+            verify_scoped_token(MASTER, &other, 1_700_000_000_000),
+            Err(TokenError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn expired_tokens_are_rejected_after_signature_check() {
+        let scope = TokenScope {
+            workspaces: vec!["/w".to_string()],
+            expires_at_ms: 1_000,
+        };
+        let token = mint_scoped_token(MASTER, &scope).unwrap();
+        assert!(matches!(
+            verify_scoped_token(MASTER, &token, 2_000),
+            Err(TokenError::Expired {
+                expires_at_ms: 1_000
+            })
+        ));
+        assert!(verify_scoped_token(MASTER, &token, 999).is_ok());
+    }
+
+    #[test]
+    fn scope_matches_whole_path_components_only() {
+        let scope = TokenScope {
+            workspaces: vec!["/home/u/proj".to_string()],
+            expires_at_ms: i64::MAX,
+        };
+        assert!(scope.allows("/home/u/proj"));
+        assert!(scope.allows("/home/u/proj/sub"));
+        assert!(!scope.allows("/home/u/project2"));
+        assert!(!scope.allows("/home/u"));
+        assert!(
+            !TokenScope {
+                workspaces: vec![],
+                expires_at_ms: i64::MAX,
+            }
+            .allows("/anything")
+        );
+    }
+}
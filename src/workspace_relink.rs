@@ -0,0 +1,226 @@
+//! Workspace move/rename detection for relinking split histories.
+//!
+//! Renaming a project directory (`~/dev/foo` → `~/dev/foo-api`) splits its
+//! history into two workspace rows: old sessions stay under the dead path
+//! while new ones accumulate under the live one. This module proposes merge
+//! candidates for `cass workspace detect` by pairing workspace rows whose
+//! directory no longer exists with live directories that look like the same
+//! project — same directory name under a different parent, a shared name
+//! prefix under the same parent, or a git remote that still carries the old
+//! name. Applying a proposal is `cass workspace relink <old> <new>`
+//! (`FrankenStorage::relink_workspace`).
+
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Minimum directory-name length for the shared-prefix rule; short names
+/// like `ui` prefix far too many siblings to be evidence of a rename.
+const MIN_PREFIX_NAME_LEN: usize = 3;
+
+/// One indexed workspace annotated with what the filesystem says about it.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceDirRecord {
+    /// Workspace path as recorded in the index.
+    pub path: String,
+    /// Whether the directory currently exists on disk.
+    pub exists: bool,
+    /// Repository name from the directory's git remote, when readable.
+    pub git_remote_repo: Option<String>,
+}
+
+impl WorkspaceDirRecord {
+    /// Build a record for one indexed workspace path by probing the disk.
+    #[must_use]
+    pub fn collect(path: &str) -> Self {
+        let trimmed = path.trim_end_matches('/');
+        let dir = Path::new(trimmed);
+        let exists = dir.is_dir();
+        let git_remote_repo = if exists { git_remote_repo(dir) } else { None };
+        Self {
+            path: trimmed.to_string(),
+            exists,
+            git_remote_repo,
+        }
+    }
+}
+
+/// A proposed `cass workspace relink <old> <new>` merge.
+#[derive(Debug, Clone, Serialize)]
+pub struct RelinkProposal {
+    /// Workspace path whose directory no longer exists.
+    pub old_path: String,
+    /// Live workspace path the history should merge into.
+    pub new_path: String,
+    /// Human-readable evidence for the pairing.
+    pub reason: String,
+}
+
+/// Pair dead workspace rows with live directories that look like the same
+/// project. Purely heuristic and read-only: the caller decides whether to
+/// run the proposed relinks.
+#[must_use]
+pub fn propose_relinks(records: &[WorkspaceDirRecord]) -> Vec<RelinkProposal> {
+    let mut proposals = Vec::new();
+    for old in records.iter().filter(|r| !r.exists) {
+        let old_name = dir_name(&old.path);
+        if old_name.is_empty() {
+            continue;
+        }
+        for new in records.iter().filter(|r| r.exists) {
+            if new.path == old.path {
+                continue;
+            }
+            let new_name = dir_name(&new.path);
+            let reason = if old_name == new_name {
+                Some(format!("directory `{old_name}` moved to a new parent"))
+            } else if same_parent(&old.path, &new.path) && shares_rename_prefix(old_name, new_name)
+            {
+                Some(format!("renamed in place (`{old_name}` → `{new_name}`)"))
+            } else if new.git_remote_repo.as_deref() == Some(old_name) {
+                Some(format!("git remote still names the repo `{old_name}`"))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                proposals.push(RelinkProposal {
+                    old_path: old.path.clone(),
+                    new_path: new.path.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+    proposals.sort_by(|a, b| {
+        a.old_path
+            .cmp(&b.old_path)
+            .then_with(|| a.new_path.cmp(&b.new_path))
+    });
+    proposals
+}
+
+/// Repository name from a directory's git remote, read from `.git/config`.
+#[must_use]
+pub fn git_remote_repo(dir: &Path) -> Option<String> {
+    let config = std::fs::read_to_string(dir.join(".git").join("config")).ok()?;
+    repo_name_from_git_config(&config)
+}
+
+/// Parse the first remote `url = …` line out of a `.git/config` body and
+/// return the trailing repository name (without `.git`).
+fn repo_name_from_git_config(config: &str) -> Option<String> {
+    for line in config.lines() {
+        let line = line.trim();
+        let Some(url) = line
+            .strip_prefix("url = ")
+            .or_else(|| line.strip_prefix("url="))
+        else {
+            continue;
+        };
+        let tail = url.trim().trim_end_matches('/').rsplit(['/', ':']).next()?;
+        let name = tail.strip_suffix(".git").unwrap_or(tail);
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+fn dir_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or("")
+}
+
+fn same_parent(a: &str, b: &str) -> bool {
+    parent_of(a) == parent_of(b)
+}
+
+fn parent_of(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+/// Whether one name extends the other (`foo` → `foo-api`), the common shape
+/// of an in-place rename. Requires a meaningful shared stem.
+fn shares_rename_prefix(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short.len() >= MIN_PREFIX_NAME_LEN && short != long && long.starts_with(short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, exists: bool, remote: Option<&str>) -> WorkspaceDirRecord {
+        WorkspaceDirRecord {
+            path: path.to_string(),
+            exists,
+            git_remote_repo: remote.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn in_place_rename_with_shared_prefix_is_proposed() {
+        let records = vec![
+            record("/home/u/dev/foo", false, None),
+            record("/home/u/dev/foo-api", true, None),
+        ];
+        let proposals = propose_relinks(&records);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].old_path, "/home/u/dev/foo");
+        assert_eq!(proposals[0].new_path, "/home/u/dev/foo-api");
+        assert!(proposals[0].reason.contains("renamed in place"));
+    }
+
+    #[test]
+    fn moved_directory_with_same_name_is_proposed() {
+        let records = vec![
+            record("/home/u/code/api", false, None),
+            record("/home/u/work/api", true, None),
+        ];
+        let proposals = propose_relinks(&records);
+        assert_eq!(proposals.len(), 1);
+        assert!(proposals[0].reason.contains("moved to a new parent"));
+    }
+
+    #[test]
+    fn git_remote_carrying_the_old_name_is_proposed() {
+        let records = vec![
+            record("/home/u/dev/frankensearch", false, None),
+            record("/home/u/dev/fs2", true, Some("frankensearch")),
+        ];
+        let proposals = propose_relinks(&records);
+        assert_eq!(proposals.len(), 1);
+        assert!(proposals[0].reason.contains("git remote"));
+    }
+
+    #[test]
+    fn live_pairs_and_unrelated_names_are_not_proposed() {
+        let records = vec![
+            // Both directories exist: nothing to relink.
+            record("/home/u/dev/foo", true, None),
+            record("/home/u/dev/foo-api", true, None),
+            // Dead, but nothing live resembles it.
+            record("/home/u/dev/zebra", false, None),
+            // Short names never match via the prefix rule.
+            record("/home/u/dev/ui", false, None),
+            record("/home/u/dev/ui-kit-rewrite-2", true, None),
+        ];
+        assert!(propose_relinks(&records).is_empty());
+    }
+
+    #[test]
+    fn git_config_repo_name_parses_common_remote_shapes() {
+        assert_eq!(
+            repo_name_from_git_config(
+                "[remote \"origin\"]\n\turl = git@github.com:owner/foo.git\n"
+            ),
+            Some("foo".to_string())
+        );
+        assert_eq!(
+            repo_name_from_git_config(
+                "[remote \"origin\"]\n\turl = https://github.com/owner/bar\n"
+            ),
+            Some("bar".to_string())
+        );
+        assert_eq!(repo_name_from_git_config("[core]\n\tbare = false\n"), None);
+    }
+}
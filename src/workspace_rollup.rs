@@ -0,0 +1,181 @@
+//! Hierarchical workspace aggregation for monorepos.
+//!
+//! In a monorepo, sessions attach to nested workspace paths —
+//! `repo/services/api` and `repo/web` are distinct workspaces even though
+//! they are one project. Flat per-workspace counts then fragment the repo
+//! across many small rows, and an exact-path filter at the repo root
+//! silently misses every sub-project session. This module infers the
+//! hierarchy from path prefixes (component-wise, so `repo2` is never a
+//! child of `repo`) plus git top-level detection, and rolls counts up to
+//! the root while keeping the per-sub-project breakdown.
+//!
+//! Consumers: `cass stats` prints the rollup next to the flat top-workspace
+//! list, and workspace filters expand a root path to its nested
+//! sub-projects (see `resolve_analytics_workspace_ids`).
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One root in the inferred hierarchy: a repo top-level (or standalone
+/// workspace) with its nested sub-project workspaces rolled up beneath it.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceRollup {
+    /// The root workspace path.
+    pub path: String,
+    /// Sessions attached exactly at the root path.
+    pub direct: i64,
+    /// Sessions across the root and every nested sub-project.
+    pub total: i64,
+    /// Nested sub-projects, largest first. Empty for standalone workspaces.
+    pub children: Vec<WorkspaceChild>,
+}
+
+/// One nested sub-project inside a [`WorkspaceRollup`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceChild {
+    /// Full workspace path of the sub-project.
+    pub path: String,
+    /// Sessions attached at this sub-project.
+    pub count: i64,
+}
+
+/// Whether `child` sits strictly inside `ancestor`, matching on whole path
+/// components: `/repo` contains `/repo/web` but never `/repo2`.
+#[must_use]
+pub fn is_nested_under(child: &str, ancestor: &str) -> bool {
+    let ancestor = ancestor.trim_end_matches('/');
+    child
+        .strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Git top-level detection: a workspace path that carries a `.git` entry is
+/// a repo root and anchors the rollup even when no session attached to it
+/// directly.
+#[must_use]
+pub fn is_git_toplevel(path: &str) -> bool {
+    std::path::Path::new(path).join(".git").exists()
+}
+
+/// Roll flat `(workspace_path, count)` rows up into a hierarchy.
+///
+/// Each workspace is grouped under its root: the shortest ancestor (itself
+/// included) that either `is_repo_root` accepts or that appears in `counts`
+/// with sessions of its own. Workspaces with no such ancestor stand alone.
+/// Roots are ordered by rolled-up total, largest first. `is_repo_root` is a
+/// parameter (rather than hard-wired to [`is_git_toplevel`]) so stats over
+/// a database indexed on another machine — where the paths don't exist
+/// locally — still group by the prefixes it can see, and so tests don't
+/// need a filesystem.
+#[must_use]
+pub fn rollup_counts(
+    counts: &[(String, i64)],
+    is_repo_root: &dyn Fn(&str) -> bool,
+) -> Vec<WorkspaceRollup> {
+    let known: Vec<&str> = counts.iter().map(|(path, _)| path.as_str()).collect();
+    let mut roots: BTreeMap<String, (i64, Vec<WorkspaceChild>)> = BTreeMap::new();
+
+    for (path, count) in counts {
+        let root = rollup_root(path, &known, is_repo_root);
+        let entry = roots.entry(root.clone()).or_insert((0, Vec::new()));
+        if *path == root {
+            entry.0 += count;
+        } else {
+            entry.1.push(WorkspaceChild {
+                path: path.clone(),
+                count: *count,
+            });
+        }
+    }
+
+    let mut out: Vec<WorkspaceRollup> = roots
+        .into_iter()
+        .map(|(path, (direct, mut children))| {
+            children.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+            let total = direct + children.iter().map(|c| c.count).sum::<i64>();
+            WorkspaceRollup {
+                path,
+                direct,
+                total,
+                children,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.path.cmp(&b.path)));
+    out
+}
+
+/// The root a workspace rolls up to: its shortest qualifying ancestor
+/// (component-wise, itself included), or itself when none qualifies.
+fn rollup_root(path: &str, known: &[&str], is_repo_root: &dyn Fn(&str) -> bool) -> String {
+    for (idx, _) in path.match_indices('/') {
+        let prefix = &path[..idx];
+        if prefix.is_empty() {
+            continue;
+        }
+        if is_repo_root(prefix) || known.contains(&prefix) {
+            return prefix.to_string();
+        }
+    }
+    path.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(rows: &[(&str, i64)]) -> Vec<(String, i64)> {
+        rows.iter().map(|(p, c)| (p.to_string(), *c)).collect()
+    }
+
+    #[test]
+    fn nesting_respects_component_boundaries() {
+        assert!(is_nested_under("/repo/services/api", "/repo"));
+        assert!(is_nested_under("/repo/web", "/repo/"));
+        assert!(!is_nested_under("/repo2", "/repo"));
+        assert!(!is_nested_under("/repo", "/repo"));
+    }
+
+    #[test]
+    fn sub_projects_roll_up_under_an_indexed_root() {
+        let rollup = rollup_counts(
+            &counts(&[
+                ("/home/u/repo", 2),
+                ("/home/u/repo/services/api", 5),
+                ("/home/u/repo/web", 3),
+                ("/home/u/other", 1),
+            ]),
+            &|_| false,
+        );
+        assert_eq!(rollup.len(), 2);
+        assert_eq!(rollup[0].path, "/home/u/repo");
+        assert_eq!(rollup[0].direct, 2);
+        assert_eq!(rollup[0].total, 10);
+        assert_eq!(rollup[0].children.len(), 2);
+        assert_eq!(rollup[0].children[0].path, "/home/u/repo/services/api");
+        assert_eq!(rollup[1].path, "/home/u/other");
+        assert!(rollup[1].children.is_empty());
+    }
+
+    #[test]
+    fn git_toplevel_anchors_a_root_no_session_attached_to() {
+        // No session at /home/u/mono itself, but git says it is the repo
+        // root: both sub-projects still group under it.
+        let rollup = rollup_counts(
+            &counts(&[("/home/u/mono/services/api", 4), ("/home/u/mono/web", 2)]),
+            &|path| path == "/home/u/mono",
+        );
+        assert_eq!(rollup.len(), 1);
+        assert_eq!(rollup[0].path, "/home/u/mono");
+        assert_eq!(rollup[0].direct, 0);
+        assert_eq!(rollup[0].total, 6);
+        assert_eq!(rollup[0].children.len(), 2);
+    }
+
+    #[test]
+    fn sibling_prefixes_do_not_merge() {
+        let rollup = rollup_counts(&counts(&[("/w/repo", 1), ("/w/repo2", 1)]), &|_| false);
+        assert_eq!(rollup.len(), 2);
+        assert!(rollup.iter().all(|r| r.children.is_empty()));
+    }
+}
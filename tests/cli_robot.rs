@@ -7197,6 +7197,32 @@ fn introspect_robot_format_includes_toon() {
     );
 }
 
+/// Test that --robot-format msgpack emits a decodable MessagePack payload
+/// with the same object shape as the compact JSON format.
+#[test]
+fn robot_format_msgpack_emits_decodable_payload() {
+    let mut cmd = base_cmd();
+    cmd.args([
+        "search",
+        "hello",
+        "--robot-format",
+        "msgpack",
+        "--limit",
+        "1",
+        "--data-dir",
+        "tests/fixtures/search_demo_data",
+    ]);
+    let output = cmd.assert().success().get_output().clone();
+    let payload: Value =
+        rmp_serde::from_slice(&output.stdout).expect("stdout should decode as MessagePack");
+    assert!(payload.is_object(), "msgpack payload should be an object");
+    assert_eq!(payload["query"], "hello");
+    assert!(
+        payload.get("hits").is_some_and(Value::is_array),
+        "msgpack payload should carry the hits array like compact JSON"
+    );
+}
+
 /// Test that CASS_OUTPUT_FORMAT takes precedence over TOON_DEFAULT_FORMAT
 #[test]
 fn cass_output_format_takes_precedence() {
@@ -0,0 +1,124 @@
+//! End-to-end coverage for `cass tag add`: the dry-run/apply split of a
+//! bulk canonical-DB mutation driven by search hits.
+//!
+//! Runs against an isolated copy of the checked-in search-demo fixture so
+//! the apply half can write tag rows without touching shared state.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use frankensqlite::Connection as FrankenConnection;
+use frankensqlite::compat::{ConnectionExt, RowExt};
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+fn safe_fixture_destination(dst_root: &Path, rel: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let mut dst = dst_root.to_path_buf();
+    for component in rel.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(part) => dst.push(part),
+            _ => {
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "fixture path escaped source root",
+                )));
+            }
+        }
+    }
+    Ok(dst)
+}
+
+/// Copy the search-demo fixture (canonical DB + lexical index) into a fresh
+/// temp data-dir, mirroring `tests/cli_robot::isolated_search_demo_data`.
+fn isolated_search_demo_data() -> Result<TempDir, Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("search_demo_data");
+    for entry in WalkDir::new(&src) {
+        let entry = entry?;
+        let rel = entry.path().strip_prefix(&src)?;
+        let dst = safe_fixture_destination(tmp.path(), rel)?;
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dst)?;
+        } else {
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(entry.path(), &dst)?;
+        }
+    }
+    Ok(tmp)
+}
+
+fn tag_add_json(data_dir: &Path, apply: bool) -> Result<Value, Box<dyn Error>> {
+    let mut cmd = cargo_bin_cmd!("cass");
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .arg("tag")
+        .arg("add")
+        .arg("demo-aider")
+        .arg("--query")
+        .arg("aider")
+        .arg("--data-dir")
+        .arg(data_dir)
+        .arg("--json");
+    if apply {
+        cmd.arg("--apply");
+    }
+    let output = cmd.assert().success().get_output().clone();
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+fn count(conn: &FrankenConnection, sql: &str) -> Result<i64, Box<dyn Error>> {
+    Ok(conn.query_row_map(sql, &[], |r: &frankensqlite::Row| r.get_typed(0))?)
+}
+
+#[test]
+fn tag_add_dry_run_previews_and_apply_links() -> Result<(), Box<dyn Error>> {
+    let fixture = isolated_search_demo_data()?;
+    let data_dir = fixture.path();
+    let db_path = data_dir.join("agent_search.db");
+
+    // Dry run (the default): matches are reported but nothing is written.
+    let preview = tag_add_json(data_dir, false)?;
+    assert_eq!(preview["applied"], Value::Bool(false));
+    assert_eq!(preview["links_added"], 0);
+    let matched = preview["matched_conversations"]
+        .as_u64()
+        .expect("matched_conversations is a number");
+    assert!(matched > 0, "fixture query should match conversations");
+
+    let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned())?;
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM tags")?, 0);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM conversation_tags")?, 0);
+    drop(conn);
+
+    // Apply: every previewed conversation gets linked exactly once.
+    let applied = tag_add_json(data_dir, true)?;
+    assert_eq!(applied["applied"], Value::Bool(true));
+    assert_eq!(applied["matched_conversations"].as_u64(), Some(matched));
+    assert_eq!(applied["links_added"].as_u64(), Some(matched));
+
+    let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned())?;
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM tags")?, 1);
+    assert_eq!(
+        count(&conn, "SELECT COUNT(*) FROM conversation_tags")?,
+        i64::try_from(matched)?
+    );
+    drop(conn);
+
+    // Re-applying is idempotent: no duplicate links.
+    let reapplied = tag_add_json(data_dir, true)?;
+    assert_eq!(reapplied["links_added"].as_u64(), Some(0));
+
+    let conn = FrankenConnection::open(db_path.to_string_lossy().into_owned())?;
+    assert_eq!(
+        count(&conn, "SELECT COUNT(*) FROM conversation_tags")?,
+        i64::try_from(matched)?
+    );
+    Ok(())
+}
@@ -292,6 +292,7 @@ fn test_corrupted_index_triggers_rebuild() {
         embedder: "fastembed".to_string(),
         progress: None,
         watch_interval_secs: 30,
+        pause_on_battery: false,
     };
     let result = indexer::run_index(opts, None);
     // Index creation may fail if connectors aren't configured, which is fine
@@ -322,6 +323,7 @@ fn test_corrupted_index_triggers_rebuild() {
             embedder: "fastembed".to_string(),
             progress: None,
             watch_interval_secs: 30,
+            pause_on_battery: false,
         };
         // force_rebuild should handle corrupted index gracefully
         let _ = indexer::run_index(rebuild_opts, None);
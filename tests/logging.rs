@@ -215,6 +215,7 @@ fn run_index_does_not_drop_storage_without_explicit_close() {
         embedder: "fastembed".to_string(),
         progress: None,
         watch_interval_secs: 30,
+        pause_on_battery: false,
     };
 
     let result = run_index(opts, None);